@@ -0,0 +1,297 @@
+//! Lining up recordings of the same event.
+//!
+//! The offset between two recordings is found with the generalized
+//! cross-correlation with phase transform (GCC-PHAT) from Charles H.
+//! Knapp and G. Clifford Carter, "The generalized correlation method for
+//! estimation of time delay" (1976). Both recordings are mixed down to
+//! one channel, and their cross-power spectrum is divided by its own
+//! magnitude before it is turned back into a cross-correlation. This
+//! whitening gives every frequency the same weight, so the
+//! cross-correlation has a sharp peak at the offset even when the
+//! recordings were made with different microphones, in different rooms,
+//! or at different levels.
+
+use std::convert::TryFrom;
+
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::Error;
+use crate::backend::Signal;
+use crate::backend::Waveform;
+
+/// Cross-power spectrum bins quieter than this, relative to the loudest
+/// bin, are not boosted by the phase transform.
+const PHAT_FLOOR: f32 = 1e-6;
+
+/// How far from the highest peak the second-highest peak has to be, in seconds.
+const PEAK_EXCLUSION_SECONDS: f64 = 0.001;
+
+/// The offset between two recordings of the same sound.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Offset {
+    /// How many frames later a sound appears in the second recording
+    /// than in the first. Frame `t` of the first recording matches
+    /// frame `t + lag_frames` of the second, so a negative lag means
+    /// that the second recording started later.
+    pub lag_frames: i64,
+    /// How much higher the cross-correlation peak at
+    /// [`lag_frames`](#structfield.lag_frames) is than the next highest
+    /// peak, from 0 to 1. It is 1 minus the ratio of the two peaks, so
+    /// values near 0 mean that another offset fits almost as well.
+    pub confidence: f32,
+}
+
+/// Finds the offset between two recordings of the same sound.
+///
+/// Only offsets of up to `max_lag_frames` in either direction are
+/// considered. Waveforms with more than one channel are mixed down to
+/// one channel first. If either waveform has no frames, the lag and the
+/// confidence are both 0.
+///
+/// The work grows with the length of the longer waveform plus
+/// `max_lag_frames`, since both waveforms are transformed with a single
+/// FFT of at least that length.
+///
+/// # Errors
+/// Returns [`Error::CannotAlignWithDifferentFrameRates`] if the
+/// waveforms do not have the same frame rate.
+///
+/// # Examples
+/// ```
+/// use babycat::align::find_offset;
+/// use babycat::Waveform;
+///
+/// // A click, and the same click 100 frames later.
+/// let mut first = vec![0.0_f32; 1000];
+/// first[200] = 1.0;
+/// let mut second = vec![0.0_f32; 1000];
+/// second[300] = 0.5;
+/// let first = Waveform::from_interleaved_samples(8000, 1, &first);
+/// let second = Waveform::from_interleaved_samples(8000, 1, &second);
+///
+/// let offset = find_offset(&first, &second, 500).unwrap();
+/// assert_eq!(offset.lag_frames, 100);
+/// assert!(offset.confidence > 0.9);
+/// ```
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn find_offset(
+    first: &Waveform,
+    second: &Waveform,
+    max_lag_frames: usize,
+) -> Result<Offset, Error> {
+    if first.frame_rate_hz() != second.frame_rate_hz() {
+        return Err(Error::CannotAlignWithDifferentFrameRates(
+            first.frame_rate_hz(),
+            second.frame_rate_hz(),
+        ));
+    }
+    let frame_rate_hz = first.frame_rate_hz();
+    let first = first.to_mono();
+    let second = second.to_mono();
+    let (first, second) = (
+        first.to_interleaved_samples(),
+        second.to_interleaved_samples(),
+    );
+    if first.is_empty() || second.is_empty() {
+        return Ok(Offset {
+            lag_frames: 0,
+            confidence: 0.0,
+        });
+    }
+    // Positive lags can reach the end of the second waveform and
+    // negative lags the end of the first one.
+    let max_positive_lag = std::cmp::min(max_lag_frames, second.len() - 1);
+    let max_negative_lag = std::cmp::min(max_lag_frames, first.len() - 1);
+
+    // The FFT is long enough that none of the lags we search wrap around.
+    let fft_size = (std::cmp::max(first.len(), second.len())
+        + std::cmp::max(max_positive_lag, max_negative_lag))
+    .next_power_of_two();
+    let mut planner = FftPlanner::new();
+    let forward = planner.plan_fft_forward(fft_size);
+    let inverse = planner.plan_fft_inverse(fft_size);
+    let spectrum = |samples: &[f32]| -> Vec<Complex<f32>> {
+        let mut buffer: Vec<Complex<f32>> = samples
+            .iter()
+            .map(|sample| Complex::new(*sample, 0.0))
+            .collect();
+        buffer.resize(fft_size, Complex::new(0.0, 0.0));
+        forward.process(&mut buffer);
+        buffer
+    };
+    let first_spectrum = spectrum(first);
+    let mut cross = spectrum(second);
+    for (value, first_value) in cross.iter_mut().zip(&first_spectrum) {
+        *value *= first_value.conj();
+    }
+    let floor = cross
+        .iter()
+        .map(|value| value.norm())
+        .fold(0.0_f32, f32::max)
+        * PHAT_FLOOR;
+    if floor <= 0.0 {
+        return Ok(Offset {
+            lag_frames: 0,
+            confidence: 0.0,
+        });
+    }
+    for value in &mut cross {
+        *value /= value.norm().max(floor);
+    }
+    inverse.process(&mut cross);
+
+    let correlation_at = |lag: i64| -> f32 {
+        let idx = if lag >= 0 {
+            lag as usize
+        } else {
+            fft_size - lag.unsigned_abs() as usize
+        };
+        cross[idx].re
+    };
+    let lags = -(max_negative_lag as i64)..=max_positive_lag as i64;
+    let best_lag = lags
+        .clone()
+        .max_by(|a, b| correlation_at(*a).total_cmp(&correlation_at(*b)))
+        .unwrap_or(0);
+    let best = correlation_at(best_lag);
+    let exclusion = std::cmp::max(
+        (PEAK_EXCLUSION_SECONDS * f64::from(frame_rate_hz)).round() as i64,
+        1,
+    );
+    let second_best = lags
+        .filter(|lag| (lag - best_lag).abs() > exclusion)
+        .map(correlation_at)
+        .fold(0.0_f32, f32::max);
+    let confidence = if best > 0.0 {
+        (1.0 - second_best / best).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    Ok(Offset {
+        lag_frames: best_lag,
+        confidence,
+    })
+}
+
+/// Lines up recordings of the same event and trims them to the stretch
+/// of time that all of them cover.
+///
+/// The offset of every waveform from the first one is found with
+/// [`find_offset()`], searching up to `max_lag_frames` in either
+/// direction. Every returned waveform starts at the same moment and has
+/// the same number of frames, so their channels can be stacked with
+/// [`align_and_stack()`]. Each waveform keeps its own channels. If the
+/// waveforms do not overlap at all, every returned waveform is empty.
+///
+/// # Errors
+/// Returns [`Error::CannotAlignWithDifferentFrameRates`] if the
+/// waveforms do not all have the same frame rate.
+///
+/// # Examples
+/// ```
+/// use babycat::align::align;
+/// use babycat::{Signal, Waveform};
+///
+/// // Two overlapping pieces of the same noise.
+/// let noise: Vec<f32> = (0..4000_u32)
+///     .map(|i| ((i.wrapping_mul(2_654_435_761) >> 8) % 1000) as f32 / 1000.0 - 0.5)
+///     .collect();
+/// let early = Waveform::from_interleaved_samples(8000, 1, &noise[0..3000]);
+/// let late = Waveform::from_interleaved_samples(8000, 1, &noise[1000..4000]);
+///
+/// let aligned = align(&[early, late], 2000).unwrap();
+/// assert_eq!(aligned[0].num_frames(), 2000);
+/// assert_eq!(aligned[0].to_interleaved_samples(), &noise[1000..3000]);
+/// assert_eq!(aligned[1].to_interleaved_samples(), &noise[1000..3000]);
+/// ```
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss
+)]
+pub fn align(waveforms: &[Waveform], max_lag_frames: usize) -> Result<Vec<Waveform>, Error> {
+    let Some(reference) = waveforms.first() else {
+        return Ok(Vec::new());
+    };
+    let mut lags = Vec::with_capacity(waveforms.len());
+    for waveform in waveforms {
+        lags.push(find_offset(reference, waveform, max_lag_frames)?.lag_frames);
+    }
+    // Frame `t` of the first waveform is frame `t + lag` of every other
+    // one, so the common stretch starts at the first `t` that every
+    // waveform has and ends at the first `t` that one of them lacks.
+    let start = lags.iter().map(|lag| -lag).fold(0, i64::max);
+    let end = waveforms
+        .iter()
+        .zip(&lags)
+        .map(|(waveform, lag)| waveform.num_frames() as i64 - lag)
+        .min()
+        .unwrap_or(0);
+    let num_frames = std::cmp::max(end - start, 0) as usize;
+    Ok(waveforms
+        .iter()
+        .zip(&lags)
+        .map(|(waveform, lag)| {
+            let num_channels = waveform.num_channels() as usize;
+            let first_frame = if num_frames == 0 {
+                0
+            } else {
+                (start + lag) as usize
+            };
+            Waveform::new(
+                waveform.frame_rate_hz(),
+                waveform.num_channels(),
+                waveform.to_interleaved_samples()
+                    [first_frame * num_channels..(first_frame + num_frames) * num_channels]
+                    .to_vec(),
+            )
+        })
+        .collect())
+}
+
+/// Lines up recordings of the same event with [`align()`] and stacks
+/// their channels into one waveform.
+///
+/// The channels of the first waveform come first, followed by the
+/// channels of the second waveform, and so on.
+///
+/// # Errors
+/// Returns [`Error::CannotAlignWithDifferentFrameRates`] if the
+/// waveforms do not all have the same frame rate,
+/// [`Error::CannotAlignWithoutWaveforms`] if there are no waveforms, and
+/// [`Error::CannotStackTooManyChannels`] if they have more than 65535
+/// channels in total.
+pub fn align_and_stack(waveforms: &[Waveform], max_lag_frames: usize) -> Result<Waveform, Error> {
+    if waveforms.is_empty() {
+        return Err(Error::CannotAlignWithoutWaveforms);
+    }
+    let total_num_channels: usize = waveforms
+        .iter()
+        .map(|waveform| waveform.num_channels() as usize)
+        .sum();
+    let num_channels = u16::try_from(total_num_channels)
+        .map_err(|_| Error::CannotStackTooManyChannels(total_num_channels))?;
+    let aligned = align(waveforms, max_lag_frames)?;
+    let num_frames = aligned.first().map_or(0, Waveform::num_frames);
+    let mut samples = Vec::with_capacity(num_frames * num_channels as usize);
+    for frame_idx in 0..num_frames {
+        for waveform in &aligned {
+            let width = waveform.num_channels() as usize;
+            samples.extend_from_slice(
+                &waveform.to_interleaved_samples()[frame_idx * width..(frame_idx + 1) * width],
+            );
+        }
+    }
+    Ok(Waveform::new(
+        aligned[0].frame_rate_hz(),
+        num_channels,
+        samples,
+    ))
+}
@@ -7,6 +7,9 @@
 //! WebAssembly frontend.
 use rayon::prelude::*;
 
+use crate::backend::decoder;
+use crate::backend::fingerprint::{Fingerprint, FingerprintNamedResult};
+use crate::backend::spectral::{FeaturesNamedResult, MelArgs, MfccArgs, StftArgs};
 use crate::backend::BatchArgs;
 use crate::backend::Waveform;
 use crate::backend::WaveformArgs;
@@ -92,3 +95,238 @@ pub fn waveforms_from_files(
     });
     waveforms
 }
+
+/// Decodes a list of audio files in parallel and computes a mel spectrogram of each.
+///
+/// # Arguments
+/// - `filenames`: A filename of an encoded audio file on the local filesystem.
+/// - `waveform_args`: Instructions on how to demux/decode each audio file.
+/// - `stft_args`: Instructions on how to compute each short-time Fourier transform.
+/// - `mel_args`: Instructions on how to build the mel filterbank.
+/// - `batch_args`: Instructions on how to divide the work across multiple threads.
+///
+/// # Feature flags
+/// This function is only available if both of the `enable-filesystem`
+/// and `enable-multithreading` features are enabled.
+///
+/// # Examples
+/// ```
+/// use babycat::batch::mel_spectrograms_from_files;
+/// use babycat::spectral::{MelArgs, StftArgs};
+///
+/// let filenames = &["audio-for-tests/log-sweep/pcm-16.flac", "does-not-exist"];
+/// let mel_args = MelArgs::default().set_num_mels(64);
+/// let batch = mel_spectrograms_from_files(
+///     filenames,
+///     Default::default(),
+///     StftArgs::default(),
+///     mel_args,
+///     Default::default(),
+/// );
+///
+/// let mel = batch[0].result.as_ref().unwrap();
+/// assert_eq!(mel.num_channels(), 2);
+/// assert_eq!(mel.num_features(), 64);
+/// assert_eq!(mel.num_time_steps(), 862);
+/// assert!(batch[1].result.is_err());
+/// ```
+#[allow(dead_code)] // Silence dead code warning because we do not use this function in the C frontend.
+#[allow(clippy::missing_panics_doc)]
+pub fn mel_spectrograms_from_files(
+    filenames: &[&str],
+    waveform_args: WaveformArgs,
+    stft_args: StftArgs,
+    mel_args: MelArgs,
+    batch_args: BatchArgs,
+) -> Vec<FeaturesNamedResult> {
+    let thread_pool: rayon::ThreadPool = rayon::ThreadPoolBuilder::new()
+        .num_threads(batch_args.num_workers)
+        .build()
+        .unwrap();
+
+    thread_pool.install(|| {
+        filenames
+            .par_iter()
+            .map(|filename| FeaturesNamedResult {
+                name: (*filename).to_string(),
+                result: Waveform::from_file(filename, waveform_args)
+                    .and_then(|waveform| waveform.mel_spectrogram(stft_args, mel_args)),
+            })
+            .collect()
+    })
+}
+
+/// Decodes a list of audio files in parallel and computes the
+/// mel-frequency cepstral coefficients of each.
+///
+/// # Arguments
+/// - `filenames`: A filename of an encoded audio file on the local filesystem.
+/// - `waveform_args`: Instructions on how to demux/decode each audio file.
+/// - `stft_args`: Instructions on how to compute each short-time Fourier transform.
+/// - `mel_args`: Instructions on how to build the mel filterbank.
+/// - `mfcc_args`: Instructions on how to compute the coefficients.
+/// - `batch_args`: Instructions on how to divide the work across multiple threads.
+///
+/// # Feature flags
+/// This function is only available if both of the `enable-filesystem`
+/// and `enable-multithreading` features are enabled.
+#[allow(dead_code)] // Silence dead code warning because we do not use this function in the C frontend.
+#[allow(clippy::missing_panics_doc)]
+pub fn mfccs_from_files(
+    filenames: &[&str],
+    waveform_args: WaveformArgs,
+    stft_args: StftArgs,
+    mel_args: MelArgs,
+    mfcc_args: MfccArgs,
+    batch_args: BatchArgs,
+) -> Vec<FeaturesNamedResult> {
+    let thread_pool: rayon::ThreadPool = rayon::ThreadPoolBuilder::new()
+        .num_threads(batch_args.num_workers)
+        .build()
+        .unwrap();
+
+    thread_pool.install(|| {
+        filenames
+            .par_iter()
+            .map(|filename| FeaturesNamedResult {
+                name: (*filename).to_string(),
+                result: Waveform::from_file(filename, waveform_args)
+                    .and_then(|waveform| waveform.mfcc(stft_args, mel_args, mfcc_args)),
+            })
+            .collect()
+    })
+}
+
+/// Decodes a list of audio files in parallel and fingerprints each.
+///
+/// Every file is fingerprinted with [`Fingerprint::from_source()`] while
+/// it is being decoded, so no file is ever held in memory as a whole.
+///
+/// # Arguments
+/// - `filenames`: A filename of an encoded audio file on the local filesystem.
+/// - `batch_args`: Instructions on how to divide the work across multiple threads.
+///
+/// # Feature flags
+/// This function is only available if both of the `enable-filesystem`
+/// and `enable-multithreading` features are enabled.
+#[allow(dead_code)] // Silence dead code warning because we do not use this function in the C frontend.
+#[allow(clippy::missing_panics_doc)]
+pub fn fingerprints_from_files(
+    filenames: &[&str],
+    batch_args: BatchArgs,
+) -> Vec<FingerprintNamedResult> {
+    let thread_pool: rayon::ThreadPool = rayon::ThreadPoolBuilder::new()
+        .num_threads(batch_args.num_workers)
+        .build()
+        .unwrap();
+
+    thread_pool.install(|| {
+        filenames
+            .par_iter()
+            .map(|filename| FingerprintNamedResult {
+                name: (*filename).to_string(),
+                result: decoder::from_file(filename).map(Fingerprint::from_source),
+            })
+            .collect()
+    })
+}
+
+/// Returns the root of a tree in a union-find forest, shortening the
+/// path to it along the way.
+#[allow(dead_code)] // Silence dead code warning because we do not use this function in the C frontend.
+fn find_root(parents: &mut [usize], mut idx: usize) -> usize {
+    while parents[idx] != idx {
+        parents[idx] = parents[parents[idx]];
+        idx = parents[idx];
+    }
+    idx
+}
+
+/// Groups fingerprinted audio files that are copies of the same recording.
+///
+/// Every pair of fingerprints is compared in parallel with
+/// [`Fingerprint::similarity()`]. Two files whose similarity is at least
+/// `threshold` are in the same group, and so are all of the files that
+/// are linked to each other through a chain of such pairs.
+///
+/// # Arguments
+/// - `fingerprints`: The fingerprints from [`fingerprints_from_files()`].
+///   Files that could not be fingerprinted are left out of every group.
+/// - `threshold`: The similarity at or above which two files are copies, such as
+///   [`DEFAULT_DUPLICATE_THRESHOLD`](crate::constants::DEFAULT_DUPLICATE_THRESHOLD).
+/// - `batch_args`: Instructions on how to divide the work across multiple threads.
+///
+/// Returns the names of the files in every group of two or more copies.
+/// The names in each group, and the groups themselves, follow the order
+/// of `fingerprints`.
+///
+/// # Feature flags
+/// This function is only available if both of the `enable-filesystem`
+/// and `enable-multithreading` features are enabled.
+///
+/// # Examples
+/// ```
+/// use babycat::batch::{cluster_duplicates, fingerprints_from_files};
+/// use babycat::constants::DEFAULT_DUPLICATE_THRESHOLD;
+///
+/// let filenames = &[
+///     "audio-for-tests/log-sweep/pcm-16.flac",
+///     "audio-for-tests/mono-dtmf-tones/track.flac",
+///     "audio-for-tests/log-sweep/192kbps-cbr.mp3",
+///     "audio-for-tests/mono-dtmf-tones/track.mp3",
+///     "does-not-exist",
+/// ];
+/// let fingerprints = fingerprints_from_files(filenames, Default::default());
+/// assert!(fingerprints[4].result.is_err());
+///
+/// let groups = cluster_duplicates(&fingerprints, DEFAULT_DUPLICATE_THRESHOLD, Default::default());
+/// assert_eq!(
+///     groups,
+///     vec![
+///         vec!["audio-for-tests/log-sweep/pcm-16.flac", "audio-for-tests/log-sweep/192kbps-cbr.mp3"],
+///         vec!["audio-for-tests/mono-dtmf-tones/track.flac", "audio-for-tests/mono-dtmf-tones/track.mp3"],
+///     ],
+/// );
+/// ```
+#[allow(dead_code)] // Silence dead code warning because we do not use this function in the C frontend.
+#[allow(clippy::missing_panics_doc)]
+pub fn cluster_duplicates(
+    fingerprints: &[FingerprintNamedResult],
+    threshold: f32,
+    batch_args: BatchArgs,
+) -> Vec<Vec<String>> {
+    let thread_pool: rayon::ThreadPool = rayon::ThreadPoolBuilder::new()
+        .num_threads(batch_args.num_workers)
+        .build()
+        .unwrap();
+
+    let pairs: Vec<(usize, usize)> = thread_pool.install(|| {
+        (0..fingerprints.len())
+            .into_par_iter()
+            .flat_map_iter(|i| {
+                (i + 1..fingerprints.len()).filter_map(move |j| {
+                    match (&fingerprints[i].result, &fingerprints[j].result) {
+                        (Ok(a), Ok(b)) if a.similarity(b) >= threshold => Some((i, j)),
+                        _ => None,
+                    }
+                })
+            })
+            .collect()
+    });
+
+    // Join the pairs into groups with a union-find forest, where every
+    // group is named after its first file.
+    let mut parents: Vec<usize> = (0..fingerprints.len()).collect();
+    for (i, j) in pairs {
+        let (root_i, root_j) = (find_root(&mut parents, i), find_root(&mut parents, j));
+        let (first, second) = (root_i.min(root_j), root_i.max(root_j));
+        parents[second] = first;
+    }
+    let mut groups: Vec<Vec<String>> = vec![Vec::new(); fingerprints.len()];
+    for (idx, named_result) in fingerprints.iter().enumerate() {
+        let group_idx = find_root(&mut parents, idx);
+        groups[group_idx].push(named_result.name.clone());
+    }
+    groups.retain(|group| group.len() > 1);
+    groups
+}
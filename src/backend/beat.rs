@@ -0,0 +1,506 @@
+//! Tempo estimation and beat tracking.
+//!
+//! Both start from the [onset strength envelope][crate::onset::onset_strength]
+//! of the audio.
+//!
+//! - The **tempo** is the beat period that best matches the
+//!   autocorrelation of the envelope, weighted by a log-normal prior
+//!   around [`BeatArgs.start_bpm`](BeatArgs#structfield.start_bpm) so that
+//!   the estimate does not jump to half or double the tempo.
+//! - The **beats** are found with the dynamic programming beat tracker
+//!   from Daniel P. W. Ellis, "Beat Tracking by Dynamic Programming"
+//!   (2007). It picks the onsets that are strong while staying close to
+//!   one beat period apart.
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::constants::{
+    DEFAULT_BEAT_TIGHTNESS, DEFAULT_FFT_SIZE, DEFAULT_HOP_LENGTH, DEFAULT_MAX_BPM, DEFAULT_MIN_BPM,
+    DEFAULT_START_BPM,
+};
+use crate::backend::onset::onset_strength;
+use crate::backend::spectral::StftArgs;
+use crate::backend::units::frames_to_milliseconds;
+use crate::backend::Error;
+use crate::backend::Signal;
+use crate::backend::Waveform;
+
+/// The width of the log-normal tempo prior, in octaves.
+const TEMPO_PRIOR_OCTAVES: f64 = 1.0;
+
+fn default_fft_size() -> usize {
+    DEFAULT_FFT_SIZE
+}
+
+fn default_hop_length() -> usize {
+    DEFAULT_HOP_LENGTH
+}
+
+fn default_start_bpm() -> f32 {
+    DEFAULT_START_BPM
+}
+
+fn default_min_bpm() -> f32 {
+    DEFAULT_MIN_BPM
+}
+
+fn default_max_bpm() -> f32 {
+    DEFAULT_MAX_BPM
+}
+
+fn default_tightness() -> f32 {
+    DEFAULT_BEAT_TIGHTNESS
+}
+
+/// Settings for tempo estimation and beat tracking.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BeatArgs {
+    /// The number of frames in each FFT of the onset strength envelope.
+    #[serde(default = "default_fft_size")]
+    pub fft_size: usize,
+    /// The number of frames between consecutive values of the onset
+    /// strength envelope. Beats are placed on multiples of this.
+    #[serde(default = "default_hop_length")]
+    pub hop_length: usize,
+    /// The tempo that is most likely before looking at the audio,
+    /// in beats per minute.
+    #[serde(default = "default_start_bpm")]
+    pub start_bpm: f32,
+    /// The lowest tempo to consider, in beats per minute.
+    #[serde(default = "default_min_bpm")]
+    pub min_bpm: f32,
+    /// The highest tempo to consider, in beats per minute.
+    #[serde(default = "default_max_bpm")]
+    pub max_bpm: f32,
+    /// How strongly the beat tracker prefers evenly-spaced beats over
+    /// strong onsets. Larger values give steadier beats.
+    #[serde(default = "default_tightness")]
+    pub tightness: f32,
+}
+
+impl Default for BeatArgs {
+    fn default() -> Self {
+        BeatArgs {
+            fft_size: DEFAULT_FFT_SIZE,
+            hop_length: DEFAULT_HOP_LENGTH,
+            start_bpm: DEFAULT_START_BPM,
+            min_bpm: DEFAULT_MIN_BPM,
+            max_bpm: DEFAULT_MAX_BPM,
+            tightness: DEFAULT_BEAT_TIGHTNESS,
+        }
+    }
+}
+
+impl BeatArgs {
+    /// Set the [`fft_size`](#structfield.fft_size) field.
+    #[must_use]
+    pub fn set_fft_size(&mut self, fft_size: usize) -> Self {
+        self.fft_size = fft_size;
+        *self
+    }
+
+    /// Set the [`hop_length`](#structfield.hop_length) field.
+    #[must_use]
+    pub fn set_hop_length(&mut self, hop_length: usize) -> Self {
+        self.hop_length = hop_length;
+        *self
+    }
+
+    /// Set the [`start_bpm`](#structfield.start_bpm) field.
+    #[must_use]
+    pub fn set_start_bpm(&mut self, start_bpm: f32) -> Self {
+        self.start_bpm = start_bpm;
+        *self
+    }
+
+    /// Set the [`min_bpm`](#structfield.min_bpm) field.
+    #[must_use]
+    pub fn set_min_bpm(&mut self, min_bpm: f32) -> Self {
+        self.min_bpm = min_bpm;
+        *self
+    }
+
+    /// Set the [`max_bpm`](#structfield.max_bpm) field.
+    #[must_use]
+    pub fn set_max_bpm(&mut self, max_bpm: f32) -> Self {
+        self.max_bpm = max_bpm;
+        *self
+    }
+
+    /// Set the [`tightness`](#structfield.tightness) field.
+    #[must_use]
+    pub fn set_tightness(&mut self, tightness: f32) -> Self {
+        self.tightness = tightness;
+        *self
+    }
+
+    /// Returns the settings for the onset strength envelope.
+    fn stft_args(&self) -> StftArgs {
+        StftArgs::default()
+            .set_fft_size(self.fft_size)
+            .set_hop_length(self.hop_length)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.hop_length == 0 {
+            return Err(Error::WrongHopLength(self.hop_length));
+        }
+        if self.min_bpm > 0.0
+            && self.min_bpm < self.max_bpm
+            && (self.min_bpm..=self.max_bpm).contains(&self.start_bpm)
+        {
+            Ok(())
+        } else {
+            Err(Error::WrongTempo(
+                self.start_bpm,
+                self.min_bpm,
+                self.max_bpm,
+            ))
+        }
+    }
+}
+
+/// An estimated tempo.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Tempo {
+    /// The tempo in beats per minute, or 0 if the audio has no onsets.
+    pub bpm: f32,
+    /// How periodic the onsets are at this tempo, from 0 to 1. This is
+    /// the normalized autocorrelation of the onset strength envelope at
+    /// the beat period.
+    pub confidence: f32,
+}
+
+/// One beat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Beat {
+    pub frame_idx: usize,
+    pub milliseconds: usize,
+}
+
+/// The tempo and beats of a waveform.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BeatTrack {
+    /// The frame rate of the analyzed audio.
+    pub frame_rate_hz: u32,
+    /// The estimated tempo in beats per minute, or 0 if the audio has no onsets.
+    pub bpm: f32,
+    /// How periodic the onsets are at [`bpm`](#structfield.bpm), from 0 to 1.
+    pub confidence: f32,
+    /// The beats, in order.
+    pub beats: Vec<Beat>,
+}
+
+/// Estimates the tempo of a waveform.
+///
+/// # Errors
+/// Returns [`Error::WrongTempo`] if the tempo range in `args` is empty
+/// or does not contain [`start_bpm`](BeatArgs#structfield.start_bpm),
+/// and the same errors as [`onset_strength()`].
+///
+/// # Examples
+/// ```
+/// use babycat::beat::{tempo, BeatArgs};
+/// use babycat::Waveform;
+///
+/// // A click every half second is 120 beats per minute.
+/// let samples: Vec<f32> = (0..22050 * 10)
+///     .map(|i| if i % 11025 < 64 { 0.5 } else { 0.0 })
+///     .collect();
+/// let waveform = Waveform::from_interleaved_samples(22050, 1, &samples);
+///
+/// let estimate = tempo(&waveform, BeatArgs::default()).unwrap();
+/// assert!((estimate.bpm - 120.0).abs() < 1.0);
+/// assert!(estimate.confidence > 0.5);
+/// ```
+pub fn tempo(waveform: &Waveform, args: BeatArgs) -> Result<Tempo, Error> {
+    args.validate()?;
+    let envelope = onset_strength(waveform, args.stft_args())?;
+    tempo_from_onset_strength(&envelope, waveform.frame_rate_hz(), args)
+}
+
+/// Estimates the tempo from an onset strength envelope computed with
+/// the same [`hop_length`](BeatArgs#structfield.hop_length) as `args`.
+///
+/// # Errors
+/// Returns [`Error::WrongHopLength`] if the hop length is zero, and
+/// [`Error::WrongTempo`] if the tempo range in `args` is invalid.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn tempo_from_onset_strength(
+    envelope: &[f32],
+    frame_rate_hz: u32,
+    args: BeatArgs,
+) -> Result<Tempo, Error> {
+    args.validate()?;
+    let num_time_steps = envelope.len();
+    if num_time_steps < 2 {
+        return Ok(Tempo::default());
+    }
+    let mean = envelope.iter().map(|v| f64::from(*v)).sum::<f64>() / num_time_steps as f64;
+    let centered: Vec<f64> = envelope.iter().map(|v| f64::from(*v) - mean).collect();
+    let energy: f64 = centered.iter().map(|v| v * v).sum();
+    if energy <= 0.0 {
+        return Ok(Tempo::default());
+    }
+    // The autocorrelation at each lag is normalized by the energy and
+    // by the number of overlapping values, so longer lags are not penalized.
+    let autocorrelation = |lag: usize| {
+        let sum: f64 = centered
+            .iter()
+            .zip(&centered[lag..])
+            .map(|(a, b)| a * b)
+            .sum();
+        sum / energy * num_time_steps as f64 / (num_time_steps - lag) as f64
+    };
+
+    let steps_per_minute = 60.0 * f64::from(frame_rate_hz) / args.hop_length as f64;
+    let min_lag = std::cmp::max(
+        (steps_per_minute / f64::from(args.max_bpm)).floor() as usize,
+        1,
+    );
+    let max_lag = std::cmp::min(
+        (steps_per_minute / f64::from(args.min_bpm)).ceil() as usize,
+        num_time_steps - 1,
+    );
+    let log_start_bpm = f64::from(args.start_bpm).log2();
+    let prior = |lag: f64| {
+        let octaves = ((steps_per_minute / lag).log2() - log_start_bpm) / TEMPO_PRIOR_OCTAVES;
+        (-0.5 * octaves * octaves).exp()
+    };
+    let best = (min_lag..=max_lag)
+        .map(|lag| (lag, autocorrelation(lag)))
+        .map(|(lag, value)| (lag, value, value * prior(lag as f64)))
+        .filter(|(_, value, _)| *value > 0.0)
+        .max_by(|a, b| a.2.total_cmp(&b.2));
+    let Some((lag, value, _)) = best else {
+        return Ok(Tempo::default());
+    };
+
+    // Fit a parabola through the peak and its neighbors to find the
+    // beat period between time steps.
+    let mut period = lag as f64;
+    if lag + 1 < num_time_steps {
+        let before = autocorrelation(lag - 1);
+        let after = autocorrelation(lag + 1);
+        let curvature = before - 2.0 * value + after;
+        if curvature < 0.0 {
+            period += (0.5 * (before - after) / curvature).clamp(-0.5, 0.5);
+        }
+    }
+    Ok(Tempo {
+        bpm: (steps_per_minute / period) as f32,
+        confidence: value.min(1.0) as f32,
+    })
+}
+
+/// Estimates the tempo of a waveform and finds its beats.
+///
+/// The channels are averaged together before the analysis. Beats fall
+/// on multiples of [`hop_length`](BeatArgs#structfield.hop_length), and
+/// beats at the start and end of the audio with weak onsets are left out.
+/// Audio with no onsets, such as silence, has no beats.
+///
+/// # Errors
+/// Returns the same errors as [`tempo()`].
+///
+/// # Examples
+/// ```
+/// use babycat::beat::{beat_track, BeatArgs};
+/// use babycat::Waveform;
+///
+/// // A click every half second.
+/// let samples: Vec<f32> = (0..22050 * 10)
+///     .map(|i| if i % 11025 < 64 { 0.5 } else { 0.0 })
+///     .collect();
+/// let waveform = Waveform::from_interleaved_samples(22050, 1, &samples);
+///
+/// let track = beat_track(&waveform, BeatArgs::default()).unwrap();
+/// assert!((track.bpm - 120.0).abs() < 1.0);
+/// for pair in track.beats.windows(2) {
+///     let interval = pair[1].milliseconds - pair[0].milliseconds;
+///     assert!((480..=520).contains(&interval));
+/// }
+/// ```
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn beat_track(waveform: &Waveform, args: BeatArgs) -> Result<BeatTrack, Error> {
+    args.validate()?;
+    let frame_rate_hz = waveform.frame_rate_hz();
+    let num_frames = waveform.num_frames();
+    let envelope = onset_strength(waveform, args.stft_args())?;
+    let Tempo { bpm, confidence } = tempo_from_onset_strength(&envelope, frame_rate_hz, args)?;
+    let mut beats = Vec::new();
+    if bpm > 0.0 {
+        let period = 60.0 * f64::from(frame_rate_hz) / (args.hop_length as f64 * f64::from(bpm));
+        let period = std::cmp::max(period.round() as usize, 1);
+        for time_step_idx in track_beats(&envelope, period, f64::from(args.tightness)) {
+            let frame_idx = std::cmp::min(
+                time_step_idx * args.hop_length,
+                num_frames.saturating_sub(1),
+            );
+            beats.push(Beat {
+                frame_idx,
+                milliseconds: frames_to_milliseconds(frame_idx, frame_rate_hz),
+            });
+        }
+    }
+    Ok(BeatTrack {
+        frame_rate_hz,
+        bpm,
+        confidence,
+        beats,
+    })
+}
+
+/// Returns the time steps of the beats in an onset strength envelope,
+/// given the beat period in time steps.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn track_beats(envelope: &[f32], period: usize, tightness: f64) -> Vec<usize> {
+    let num_time_steps = envelope.len();
+    if num_time_steps < 2 {
+        return Vec::new();
+    }
+    let mean = envelope.iter().map(|v| f64::from(*v)).sum::<f64>() / num_time_steps as f64;
+    let variance = envelope
+        .iter()
+        .map(|v| (f64::from(*v) - mean).powi(2))
+        .sum::<f64>()
+        / (num_time_steps - 1) as f64;
+    if variance <= 0.0 {
+        return Vec::new();
+    }
+    let std_dev = variance.sqrt();
+
+    // Smooth the normalized envelope with a Gaussian a fraction of a beat wide.
+    let kernel: Vec<f64> = (0..=2 * period)
+        .map(|k| {
+            let x = (k as f64 - period as f64) * 32.0 / period as f64;
+            (-0.5 * x * x).exp()
+        })
+        .collect();
+    let local_score: Vec<f64> = (0..num_time_steps)
+        .map(|t| {
+            kernel
+                .iter()
+                .enumerate()
+                .filter_map(|(k, weight)| {
+                    (t + period)
+                        .checked_sub(k)
+                        .and_then(|idx| envelope.get(idx))
+                        .map(|v| weight * f64::from(*v) / std_dev)
+                })
+                .sum()
+        })
+        .collect();
+
+    // Each beat looks back between half a period and two periods for the
+    // previous beat, penalizing intervals that stray from one period.
+    let min_back = std::cmp::max((period as f64 / 2.0).round() as usize, 1);
+    let max_back = 2 * period;
+    let penalties: Vec<(usize, f64)> = (min_back..=max_back)
+        .map(|back| {
+            let log_ratio = (back as f64 / period as f64).ln();
+            (back, -tightness * log_ratio * log_ratio)
+        })
+        .collect();
+    let score_threshold = 0.01
+        * local_score
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+    let mut cumulative_score = vec![0.0_f64; num_time_steps];
+    let mut backlinks: Vec<Option<usize>> = vec![None; num_time_steps];
+    let mut first_beat = true;
+    for t in 0..num_time_steps {
+        // Beats before the start of the audio have a score of zero.
+        let (best_back, best_score) = penalties
+            .iter()
+            .map(|&(back, penalty)| {
+                let previous = t.checked_sub(back).map_or(0.0, |idx| cumulative_score[idx]);
+                (back, penalty + previous)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((0, 0.0));
+        cumulative_score[t] = local_score[t] + best_score;
+        if first_beat && local_score[t] < score_threshold {
+            continue;
+        }
+        first_beat = false;
+        backlinks[t] = t.checked_sub(best_back);
+    }
+
+    // The last beat is the last local maximum of the cumulative score
+    // that is at least half of the median local maximum.
+    let is_local_max = |t: usize| {
+        let previous = if t == 0 {
+            cumulative_score[0]
+        } else {
+            cumulative_score[t - 1]
+        };
+        let next = cumulative_score
+            .get(t + 1)
+            .copied()
+            .unwrap_or(cumulative_score[t]);
+        cumulative_score[t] > previous && cumulative_score[t] >= next
+    };
+    let mut maxima: Vec<f64> = (0..num_time_steps)
+        .filter(|&t| is_local_max(t))
+        .map(|t| cumulative_score[t])
+        .collect();
+    if maxima.is_empty() {
+        return Vec::new();
+    }
+    maxima.sort_by(f64::total_cmp);
+    let middle = maxima.len() / 2;
+    let median = if maxima.len().is_multiple_of(2) {
+        maxima[middle - 1].midpoint(maxima[middle])
+    } else {
+        maxima[middle]
+    };
+    let Some(last_beat) = (0..num_time_steps)
+        .rev()
+        .find(|&t| is_local_max(t) && cumulative_score[t] >= 0.5 * median)
+    else {
+        return Vec::new();
+    };
+
+    let mut beats = vec![last_beat];
+    while let Some(previous) = backlinks[beats[beats.len() - 1]] {
+        beats.push(previous);
+    }
+    beats.reverse();
+    trim_weak_beats(&local_score, &beats)
+}
+
+/// Drops beats at the start and end whose smoothed onset strength is
+/// less than half of the root mean square over every beat.
+#[allow(clippy::cast_precision_loss)]
+fn trim_weak_beats(local_score: &[f64], beats: &[usize]) -> Vec<usize> {
+    let scores: Vec<f64> = beats.iter().map(|&t| local_score[t]).collect();
+    let smoothed: Vec<f64> = (0..scores.len())
+        .map(|i| {
+            let before = if i == 0 { 0.0 } else { scores[i - 1] };
+            let after = scores.get(i + 1).copied().unwrap_or(0.0);
+            0.5 * before + scores[i] + 0.5 * after
+        })
+        .collect();
+    let mean_square = smoothed.iter().map(|v| v * v).sum::<f64>() / smoothed.len() as f64;
+    let threshold = 0.5 * mean_square.sqrt();
+    let first = smoothed.iter().position(|v| *v > threshold);
+    let last = smoothed.iter().rposition(|v| *v > threshold);
+    match (first, last) {
+        (Some(first), Some(last)) => beats[first..=last].to_vec(),
+        _ => Vec::new(),
+    }
+}
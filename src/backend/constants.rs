@@ -20,6 +20,89 @@ pub const DEFAULT_REPEAT_PAD_ENDING: bool = false;
 pub const DEFAULT_RESAMPLE_MODE: u32 = 0;
 /// Sets the default audio decoding backend.
 pub const DEFAULT_DECODING_BACKEND: u32 = 0;
+/// By default, we do not normalize the decoded audio.
+pub const DEFAULT_NORMALIZE_MODE: u32 = 0;
+/// The default normalization target, in dBFS for peak normalization or LUFS for loudness normalization.
+pub const DEFAULT_NORMALIZE_TARGET: f32 = 0.0;
+/// The default true-peak ceiling when normalizing loudness, in dBTP.
+pub const DEFAULT_NORMALIZE_TRUE_PEAK_CEILING_DBTP: f32 = 0.0;
+/// By default, we do not trim silence from the start and end of the audio.
+pub const DEFAULT_TRIM_SILENCE: bool = false;
+/// The default level, in dBFS, at or below which audio counts as silence.
+pub const DEFAULT_TRIM_SILENCE_THRESHOLD_DBFS: f32 = -60.0;
+/// The default FFT size for short-time Fourier transforms.
+pub const DEFAULT_FFT_SIZE: usize = 2048;
+/// The default number of frames between the starts of consecutive
+/// short-time Fourier transform windows.
+pub const DEFAULT_HOP_LENGTH: usize = 512;
+/// By default, the short-time Fourier transform window is as long as the FFT.
+pub const DEFAULT_WINDOW_LENGTH: usize = 0;
+/// Sets the default window type, which is a Hann window.
+pub const DEFAULT_WINDOW_TYPE: u32 = 0;
+/// By default, we pad the audio so that every short-time Fourier transform
+/// window is centered on its time step.
+pub const DEFAULT_CENTER: bool = true;
+/// Sets the default padding mode, which reflects the audio around its edges.
+pub const DEFAULT_PAD_MODE: u32 = 0;
+/// The default number of mel bands in a mel spectrogram.
+pub const DEFAULT_NUM_MELS: usize = 128;
+/// Sets the default mel scale, which is the Slaney mel scale.
+pub const DEFAULT_MEL_SCALE: u32 = 0;
+/// By default, each mel filter is divided by its width.
+pub const DEFAULT_MEL_NORMALIZE: bool = true;
+/// By default, mel spectrograms are computed from power spectrograms.
+pub const DEFAULT_MEL_POWER: f32 = 2.0;
+/// The default number of mel-frequency cepstral coefficients.
+pub const DEFAULT_NUM_MFCC: usize = 20;
+/// By default, mel-frequency cepstral coefficients are not liftered.
+pub const DEFAULT_MFCC_LIFTER: f32 = 0.0;
+/// By default, the log-power mel spectrogram is clipped to
+/// 80 decibels below its loudest value before computing MFCCs.
+pub const DEFAULT_MFCC_TOP_DB: Option<f32> = Some(80.0);
+/// The default number of time steps that deltas are estimated over.
+pub const DEFAULT_DELTA_WIDTH: usize = 9;
+/// The default center frequency of the lowest constant-Q transform bin,
+/// which is C1.
+pub const DEFAULT_CQT_MIN_FREQUENCY_HZ: f32 = 32.703_197;
+/// The default number of constant-Q transform bins, which is 7 octaves.
+pub const DEFAULT_CQT_NUM_BINS: usize = 84;
+/// The default number of constant-Q transform bins in each octave.
+pub const DEFAULT_BINS_PER_OCTAVE: usize = 12;
+/// The number of pitch classes in a chromagram.
+pub const NUM_CHROMA: usize = 12;
+/// The default tempo that tempo estimation favors, in beats per minute.
+pub const DEFAULT_START_BPM: f32 = 120.0;
+/// The default lowest tempo that tempo estimation considers, in beats per minute.
+pub const DEFAULT_MIN_BPM: f32 = 30.0;
+/// The default highest tempo that tempo estimation considers, in beats per minute.
+pub const DEFAULT_MAX_BPM: f32 = 300.0;
+/// The default for how strictly beat tracking keeps beats evenly spaced.
+pub const DEFAULT_BEAT_TIGHTNESS: f32 = 100.0;
+/// The default onset detection function, which is [`ONSET_FUNCTION_SPECTRAL_FLUX`].
+pub const DEFAULT_ONSET_FUNCTION: u32 = 0;
+/// The default pitch tracking method, which is [`PITCH_METHOD_PYIN`].
+pub const DEFAULT_PITCH_METHOD: u32 = 0;
+/// The default number of frames in each pitch tracking frame.
+pub const DEFAULT_PITCH_FRAME_LENGTH: usize = 2048;
+/// The default lowest pitch to track, which is C2.
+pub const DEFAULT_PITCH_MIN_FREQUENCY_HZ: f32 = 65.406_39;
+/// The default highest pitch to track, which is C7.
+pub const DEFAULT_PITCH_MAX_FREQUENCY_HZ: f32 = 2_093.004_5;
+/// The default similarity at or above which two audio fingerprints are
+/// treated as copies of the same recording.
+pub const DEFAULT_DUPLICATE_THRESHOLD: f32 = 0.7;
+/// The default width of rendered images, in pixels.
+pub const DEFAULT_RENDER_WIDTH: u32 = 1024;
+/// The default height of rendered images, in pixels.
+pub const DEFAULT_RENDER_HEIGHT: u32 = 256;
+/// The largest width or height of a rendered image, in pixels.
+pub const MAX_RENDER_SIZE: u32 = 8192;
+/// Sets the default color map for rendered images, which is [`COLOR_MAP_VIRIDIS`].
+pub const DEFAULT_COLOR_MAP: u32 = 0;
+/// By default, rendered spectrograms start at 100 decibels below full scale.
+pub const DEFAULT_RENDER_MIN_DB: f32 = -100.0;
+/// By default, rendered spectrograms end at full scale.
+pub const DEFAULT_RENDER_MAX_DB: f32 = 0.0;
 
 /// Use this value to resample audio with libsamplerate.
 ///
@@ -31,6 +114,59 @@ pub const RESAMPLE_MODE_BABYCAT_LANCZOS: u32 = 2;
 /// Use this value to resample audio with Babycat's sinc resampler.
 pub const RESAMPLE_MODE_BABYCAT_SINC: u32 = 3;
 
+/// Use this value to scale audio so that its sample peak hits a target level in dBFS.
+pub const NORMALIZE_MODE_PEAK: u32 = 1;
+/// Use this value to scale audio so that its ITU-R BS.1770 integrated loudness
+/// hits a target level in LUFS, without letting its true peak exceed a ceiling.
+pub const NORMALIZE_MODE_LOUDNESS: u32 = 2;
+
+/// Use this value for a Hann window.
+pub const WINDOW_TYPE_HANN: u32 = 1;
+/// Use this value for a Hamming window.
+pub const WINDOW_TYPE_HAMMING: u32 = 2;
+/// Use this value for a Blackman window.
+pub const WINDOW_TYPE_BLACKMAN: u32 = 3;
+/// Use this value for a rectangular window, which does not taper the audio at all.
+pub const WINDOW_TYPE_RECTANGULAR: u32 = 4;
+
+/// Use this value to pad the edges of the audio with silence.
+pub const PAD_MODE_ZEROS: u32 = 1;
+/// Use this value to pad the edges of the audio with a mirror image
+/// of the audio, not repeating the first or last frame.
+pub const PAD_MODE_REFLECT: u32 = 2;
+
+/// Use this value for the mel scale from Slaney's Auditory Toolbox,
+/// which is linear below 1000 hz and logarithmic above it.
+pub const MEL_SCALE_SLANEY: u32 = 1;
+/// Use this value for the mel scale from the Hidden Markov Model Toolkit (HTK).
+pub const MEL_SCALE_HTK: u32 = 2;
+
+/// Use this value to detect onsets from how much louder a mel spectrogram
+/// gets in decibels. This works well for most music.
+pub const ONSET_FUNCTION_SPECTRAL_FLUX: u32 = 1;
+/// Use this value to detect onsets from the high-frequency content of
+/// the spectrum, which favors percussive onsets.
+pub const ONSET_FUNCTION_HIGH_FREQUENCY_CONTENT: u32 = 2;
+/// Use this value to detect onsets from how far the spectrum strays from
+/// a prediction of its magnitude and phase. This also hears soft onsets,
+/// such as a new note that is no louder than the one before it.
+pub const ONSET_FUNCTION_COMPLEX_DOMAIN: u32 = 3;
+
+/// Use this value to track pitch with pYIN, which smooths the pitch and
+/// voicing of every frame with a hidden Markov model.
+pub const PITCH_METHOD_PYIN: u32 = 1;
+/// Use this value to track pitch with YIN, which looks at every frame on its own.
+pub const PITCH_METHOD_YIN: u32 = 2;
+
+/// Use this value to draw with the viridis color map, which runs from
+/// dark purple through blue and green to yellow.
+pub const COLOR_MAP_VIRIDIS: u32 = 1;
+/// Use this value to draw with the magma color map, which runs from
+/// black through purple and orange to pale yellow.
+pub const COLOR_MAP_MAGMA: u32 = 2;
+/// Use this value to draw from black to white.
+pub const COLOR_MAP_GRAYSCALE: u32 = 3;
+
 /// Sets the decoding backend as [`SymphoniaDecoder`](crate::decoder::SymphoniaDecoder).
 pub const DECODING_BACKEND_SYMPHONIA: u32 = 1;
 
@@ -0,0 +1,389 @@
+//! Dual-tone multi-frequency (DTMF) decoding.
+//!
+//! Every key on a telephone keypad is sent as the sum of two tones: one
+//! from the low group of "row" frequencies and one from the high group of
+//! "column" frequencies. To find them, the audio is mixed down to mono and
+//! cut into short blocks, and the Goertzel algorithm measures the level
+//! of all eight frequencies in every block. A block holds a digit when:
+//!
+//! - The loudest tone of each group is loud enough.
+//! - Each of those tones is clearly louder than the others in its group.
+//! - Together, the two tones hold most of the energy of the block, which
+//!   keeps speech and music from being mistaken for digits.
+//! - The difference between the levels of the two tones, called the
+//!   twist, is within the limits of ITU-T Q.24.
+//!
+//! Blocks are then grouped into digits with the timing rules of ITU-T
+//! Q.24. Short interruptions within a tone are bridged, and tones that
+//! are too short are ignored. Blocks do not line up with the start and
+//! end of tones, so durations are only measured to within one block,
+//! which is about 13 milliseconds.
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::loudness::{dbfs_to_ratio, ratio_to_dbfs};
+use crate::backend::source::BorrowedWaveformSource;
+use crate::backend::units::{frames_to_milliseconds, milliseconds_to_frames};
+use crate::backend::Error;
+use crate::backend::Source;
+use crate::backend::Waveform;
+
+/// The low group of DTMF frequencies, one for each row of the keypad.
+pub const DTMF_ROW_FREQUENCIES_HZ: [f32; 4] = [697.0, 770.0, 852.0, 941.0];
+
+/// The high group of DTMF frequencies, one for each column of the keypad.
+pub const DTMF_COLUMN_FREQUENCIES_HZ: [f32; 4] = [1209.0, 1336.0, 1477.0, 1633.0];
+
+/// The digit sent by every pair of a row frequency and a column frequency.
+pub const DTMF_KEYPAD: [[char; 4]; 4] = [
+    ['1', '2', '3', 'A'],
+    ['4', '5', '6', 'B'],
+    ['7', '8', '9', 'C'],
+    ['*', '0', '#', 'D'],
+];
+
+/// Audio with a lower frame rate cannot hold the highest DTMF frequency.
+pub const DTMF_MIN_FRAME_RATE_HZ: u32 = 4000;
+
+/// The number of frames in each analysis block at 8000 hz. Blocks last
+/// just as long at other frame rates. This is short enough to time
+/// tones accurately, but long enough to tell neighboring rows apart.
+const BLOCK_LENGTH_AT_8000_HZ: usize = 102;
+/// The loudest tone in each group has to be at least this many decibels
+/// louder than every other tone in the group.
+const MIN_RELATIVE_PEAK_DB: f32 = 6.0;
+/// The two tones have to hold at least this fraction of the energy of a block.
+const MIN_TONE_ENERGY_FRACTION: f32 = 0.5;
+
+/// Settings for DTMF decoding.
+///
+/// The defaults follow the receiver requirements of ITU-T Q.24 that are
+/// common to North America and most of Europe.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DtmfArgs {
+    /// Each of the two tones has to be at least this loud, in dBFS,
+    /// where a full-scale sine wave is 0 dBFS.
+    pub min_level_dbfs: f32,
+    /// How many decibels quieter the high-group tone can be than the
+    /// low-group tone. Telephone lines lose more of the higher
+    /// frequencies, so this is usually larger than
+    /// [`max_reverse_twist_db`](#structfield.max_reverse_twist_db).
+    pub max_normal_twist_db: f32,
+    /// How many decibels louder the high-group tone can be than the
+    /// low-group tone.
+    pub max_reverse_twist_db: f32,
+    /// Tones shorter than this are ignored.
+    pub min_tone_milliseconds: usize,
+    /// Gaps shorter than this within a tone are bridged, so pressing the
+    /// same key twice needs at least this long a pause between presses.
+    pub min_pause_milliseconds: usize,
+}
+
+impl Default for DtmfArgs {
+    fn default() -> Self {
+        DtmfArgs {
+            min_level_dbfs: -40.0,
+            max_normal_twist_db: 8.0,
+            max_reverse_twist_db: 4.0,
+            min_tone_milliseconds: 40,
+            min_pause_milliseconds: 40,
+        }
+    }
+}
+
+impl DtmfArgs {
+    /// Set the [`min_level_dbfs`](#structfield.min_level_dbfs) field.
+    #[must_use]
+    pub fn set_min_level_dbfs(&mut self, min_level_dbfs: f32) -> Self {
+        self.min_level_dbfs = min_level_dbfs;
+        *self
+    }
+
+    /// Set the [`max_normal_twist_db`](#structfield.max_normal_twist_db) field.
+    #[must_use]
+    pub fn set_max_normal_twist_db(&mut self, max_normal_twist_db: f32) -> Self {
+        self.max_normal_twist_db = max_normal_twist_db;
+        *self
+    }
+
+    /// Set the [`max_reverse_twist_db`](#structfield.max_reverse_twist_db) field.
+    #[must_use]
+    pub fn set_max_reverse_twist_db(&mut self, max_reverse_twist_db: f32) -> Self {
+        self.max_reverse_twist_db = max_reverse_twist_db;
+        *self
+    }
+
+    /// Set the [`min_tone_milliseconds`](#structfield.min_tone_milliseconds) field.
+    #[must_use]
+    pub fn set_min_tone_milliseconds(&mut self, min_tone_milliseconds: usize) -> Self {
+        self.min_tone_milliseconds = min_tone_milliseconds;
+        *self
+    }
+
+    /// Set the [`min_pause_milliseconds`](#structfield.min_pause_milliseconds) field.
+    #[must_use]
+    pub fn set_min_pause_milliseconds(&mut self, min_pause_milliseconds: usize) -> Self {
+        self.min_pause_milliseconds = min_pause_milliseconds;
+        *self
+    }
+}
+
+/// A keypad digit and when it was sent.
+///
+/// The end is exclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DtmfDigit {
+    /// One of `0` to `9`, `*`, `#`, or `A` to `D`.
+    pub digit: char,
+    pub start_frame_idx: usize,
+    pub end_frame_idx: usize,
+    pub start_milliseconds: usize,
+    pub end_milliseconds: usize,
+}
+
+/// Decides which digit, if any, every block of mono audio holds.
+struct BlockDetector {
+    args: DtmfArgs,
+    block_len: usize,
+    /// The Goertzel coefficient of every row frequency, then every column frequency.
+    coefficients: [f32; 8],
+    /// The last two Goertzel filter outputs for every frequency.
+    states: [[f32; 2]; 8],
+    energy: f32,
+    num_block_frames: usize,
+    blocks: Vec<Option<char>>,
+}
+
+impl BlockDetector {
+    #[allow(clippy::cast_precision_loss)]
+    fn new(frame_rate_hz: u32, block_len: usize, args: DtmfArgs) -> Self {
+        let mut coefficients = [0.0_f32; 8];
+        for (coefficient, frequency_hz) in coefficients.iter_mut().zip(
+            DTMF_ROW_FREQUENCIES_HZ
+                .iter()
+                .chain(&DTMF_COLUMN_FREQUENCIES_HZ),
+        ) {
+            *coefficient =
+                2.0 * (2.0 * std::f32::consts::PI * frequency_hz / frame_rate_hz as f32).cos();
+        }
+        Self {
+            args,
+            block_len,
+            coefficients,
+            states: [[0.0; 2]; 8],
+            energy: 0.0,
+            num_block_frames: 0,
+            blocks: Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn push_frame(&mut self, sample: f32) {
+        for (state, coefficient) in self.states.iter_mut().zip(&self.coefficients) {
+            let output = sample + coefficient * state[0] - state[1];
+            *state = [output, state[0]];
+        }
+        self.energy += sample * sample;
+        self.num_block_frames += 1;
+        if self.num_block_frames == self.block_len {
+            self.end_block();
+        }
+    }
+
+    fn end_block(&mut self) {
+        let digit = self.classify_block();
+        self.blocks.push(digit);
+        self.states = [[0.0; 2]; 8];
+        self.energy = 0.0;
+        self.num_block_frames = 0;
+    }
+
+    /// Returns the digit in the block that just ended, if any.
+    #[allow(clippy::cast_precision_loss)]
+    fn classify_block(&self) -> Option<char> {
+        let block_len = self.num_block_frames as f32;
+        // The amplitude of a sine wave at each frequency.
+        let mut amplitudes = [0.0_f32; 8];
+        for ((amplitude, state), coefficient) in amplitudes
+            .iter_mut()
+            .zip(&self.states)
+            .zip(&self.coefficients)
+        {
+            let power =
+                state[0] * state[0] + state[1] * state[1] - coefficient * state[0] * state[1];
+            *amplitude = 2.0 * power.max(0.0).sqrt() / block_len;
+        }
+        let (row_amplitudes, column_amplitudes) = amplitudes.split_at(4);
+        let (row_idx, row_amplitude) = loudest_tone(row_amplitudes, MIN_RELATIVE_PEAK_DB)?;
+        let (column_idx, column_amplitude) = loudest_tone(column_amplitudes, MIN_RELATIVE_PEAK_DB)?;
+
+        let min_amplitude = dbfs_to_ratio(self.args.min_level_dbfs);
+        if row_amplitude < min_amplitude || column_amplitude < min_amplitude {
+            return None;
+        }
+        let twist_db = ratio_to_dbfs(column_amplitude / row_amplitude);
+        if twist_db > self.args.max_reverse_twist_db || -twist_db > self.args.max_normal_twist_db {
+            return None;
+        }
+        // A sine wave with an amplitude of `a` has an energy of `a^2 / 2` per frame.
+        let tone_energy = (row_amplitude.powi(2) + column_amplitude.powi(2)) * block_len / 2.0;
+        if tone_energy < MIN_TONE_ENERGY_FRACTION * self.energy {
+            return None;
+        }
+        Some(DTMF_KEYPAD[row_idx][column_idx])
+    }
+
+    /// Groups the blocks into digits.
+    fn finish(self, frame_rate_hz: u32) -> Vec<DtmfDigit> {
+        let block_len = self.block_len;
+        // A tone or pause that covers `n` whole blocks can also cover parts
+        // of the blocks on either side of it, so its duration is rounded up
+        // by one block before it is compared against the limits.
+        let min_tone_blocks = std::cmp::max(
+            milliseconds_to_frames(self.args.min_tone_milliseconds, frame_rate_hz)
+                .div_ceil(block_len)
+                .saturating_sub(1),
+            1,
+        );
+        let max_gap_blocks =
+            milliseconds_to_frames(self.args.min_pause_milliseconds, frame_rate_hz)
+                .div_ceil(block_len)
+                .saturating_sub(2);
+
+        let mut runs: Vec<(char, usize, usize)> = Vec::new();
+        for (block_idx, block) in self.blocks.iter().enumerate() {
+            let Some(digit) = *block else {
+                continue;
+            };
+            match runs.last_mut() {
+                Some((last_digit, _, end))
+                    if *last_digit == digit && block_idx - *end <= max_gap_blocks =>
+                {
+                    *end = block_idx + 1;
+                }
+                _ => runs.push((digit, block_idx, block_idx + 1)),
+            }
+        }
+        runs.into_iter()
+            .filter(|(_, start, end)| end - start >= min_tone_blocks)
+            .map(|(digit, start, end)| {
+                let start_frame_idx = start * block_len;
+                let end_frame_idx = end * block_len;
+                DtmfDigit {
+                    digit,
+                    start_frame_idx,
+                    end_frame_idx,
+                    start_milliseconds: frames_to_milliseconds(start_frame_idx, frame_rate_hz),
+                    end_milliseconds: frames_to_milliseconds(end_frame_idx, frame_rate_hz),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns the index and amplitude of the loudest tone, if it is at
+/// least `min_relative_peak_db` louder than every other tone.
+fn loudest_tone(amplitudes: &[f32], min_relative_peak_db: f32) -> Option<(usize, f32)> {
+    let (loudest_idx, loudest) = amplitudes
+        .iter()
+        .copied()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+    let is_clear = amplitudes
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| *idx != loudest_idx)
+        .all(|(_, amplitude)| ratio_to_dbfs(loudest / amplitude) >= min_relative_peak_db);
+    if is_clear {
+        Some((loudest_idx, loudest))
+    } else {
+        None
+    }
+}
+
+/// Decodes mono audio, one sample at a time.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn digits_of_mono<I: Iterator<Item = f32>>(
+    samples: I,
+    frame_rate_hz: u32,
+    args: DtmfArgs,
+) -> Vec<DtmfDigit> {
+    let block_len =
+        ((frame_rate_hz as f32 * BLOCK_LENGTH_AT_8000_HZ as f32) / 8000.0).round() as usize;
+    let mut detector = BlockDetector::new(frame_rate_hz, block_len, args);
+    for sample in samples {
+        detector.push_frame(sample);
+    }
+    detector.finish(frame_rate_hz)
+}
+
+/// Decodes the DTMF digits in any [`Source`], at any frame rate of at
+/// least [`DTMF_MIN_FRAME_RATE_HZ`].
+///
+/// The source is read to the end, but only a few values are kept in
+/// memory at a time. Its channels are mixed down to mono before decoding.
+///
+/// # Errors
+/// Returns [`Error::WrongNumChannels`] if the source has no channels, and
+/// [`Error::FrameRateTooLow`] if its frame rate is below
+/// [`DTMF_MIN_FRAME_RATE_HZ`].
+///
+/// # Examples
+/// ```
+/// use babycat::dtmf::{dtmf_digits, DtmfArgs};
+/// use babycat::WaveformSource;
+///
+/// // The "5" key is 770 hz and 1336 hz. Send it for 100 milliseconds,
+/// // pause for 100 milliseconds, and send it again.
+/// let samples: Vec<f32> = (0..2400)
+///     .map(|i| {
+///         if (800..1600).contains(&i) {
+///             return 0.0;
+///         }
+///         let t = i as f32 / 8000.0;
+///         0.25 * (2.0 * std::f32::consts::PI * 770.0 * t).sin()
+///             + 0.25 * (2.0 * std::f32::consts::PI * 1336.0 * t).sin()
+///     })
+///     .collect();
+/// let source = WaveformSource::from_interleaved_samples(8000, 1, &samples);
+///
+/// let digits = dtmf_digits(source, DtmfArgs::default()).unwrap();
+/// let keyed: String = digits.iter().map(|digit| digit.digit).collect();
+/// assert_eq!(keyed, "55");
+/// assert_eq!(digits[0].start_milliseconds, 0);
+/// // Timestamps are measured to within one block of 102 frames.
+/// assert_eq!(digits[1].end_milliseconds, 293);
+/// ```
+pub fn dtmf_digits<S: Source>(source: S, args: DtmfArgs) -> Result<Vec<DtmfDigit>, Error> {
+    if source.num_channels() == 0 {
+        return Err(Error::WrongNumChannels(1, 0));
+    }
+    let frame_rate_hz = source.frame_rate_hz();
+    if frame_rate_hz < DTMF_MIN_FRAME_RATE_HZ {
+        return Err(Error::FrameRateTooLow(
+            frame_rate_hz,
+            DTMF_MIN_FRAME_RATE_HZ,
+        ));
+    }
+    let digits = if source.num_channels() == 1 {
+        digits_of_mono(source, frame_rate_hz, args)
+    } else {
+        digits_of_mono(source.convert_to_mono(), frame_rate_hz, args)
+    };
+    Ok(digits)
+}
+
+/// Decodes the DTMF digits in a [`Waveform`] that is already in memory.
+///
+/// This gives the same digits as [`dtmf_digits()`].
+///
+/// # Errors
+/// Returns [`Error::FrameRateTooLow`] if the frame rate of the waveform is
+/// below [`DTMF_MIN_FRAME_RATE_HZ`].
+pub fn waveform_dtmf_digits(waveform: &Waveform, args: DtmfArgs) -> Result<Vec<DtmfDigit>, Error> {
+    dtmf_digits(BorrowedWaveformSource::new(waveform), args)
+}
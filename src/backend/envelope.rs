@@ -0,0 +1,186 @@
+//! Breakpoint envelopes for time-varying gain.
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::errors::Error;
+use crate::backend::units::milliseconds_to_frames;
+
+/// Where an [`EnvelopePoint`] sits in time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnvelopePosition {
+    /// A frame index, counted from the beginning of the audio.
+    Frame(usize),
+    /// A millisecond timestamp, counted from the beginning of the audio.
+    Milliseconds(usize),
+}
+
+impl EnvelopePosition {
+    /// Returns this position as a frame index at the given frame rate.
+    #[inline]
+    pub fn to_frame(self, frame_rate_hz: u32) -> usize {
+        match self {
+            EnvelopePosition::Frame(frame_idx) => frame_idx,
+            EnvelopePosition::Milliseconds(ms) => milliseconds_to_frames(ms, frame_rate_hz),
+        }
+    }
+}
+
+/// One breakpoint in a [`GainEnvelope`].
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EnvelopePoint {
+    #[serde(flatten)]
+    pub position: EnvelopePosition,
+    /// The gain at this point, in decibels relative to full scale.
+    pub dbfs: f32,
+}
+
+impl EnvelopePoint {
+    /// Creates a breakpoint at a frame index.
+    #[inline]
+    pub fn at_frame(frame_idx: usize, dbfs: f32) -> Self {
+        Self {
+            position: EnvelopePosition::Frame(frame_idx),
+            dbfs,
+        }
+    }
+
+    /// Creates a breakpoint at a millisecond timestamp.
+    #[inline]
+    pub fn at_milliseconds(milliseconds: usize, dbfs: f32) -> Self {
+        Self {
+            position: EnvelopePosition::Milliseconds(milliseconds),
+            dbfs,
+        }
+    }
+}
+
+/// How the gain moves between two breakpoints.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnvelopeInterpolation {
+    /// The amplitude changes in a straight line between breakpoints.
+    #[default]
+    Linear,
+    /// The level in decibels changes in a straight line between breakpoints,
+    /// so the amplitude changes exponentially.
+    Exponential,
+}
+
+/// A list of `(position, dbfs)` breakpoints describing gain over time.
+///
+/// Before the first breakpoint, the gain holds at the first breakpoint's level.
+/// After the last breakpoint, the gain holds at the last breakpoint's level.
+/// An envelope without any breakpoints leaves the audio unchanged.
+///
+/// # Examples
+/// ```
+/// use babycat::envelope::{EnvelopeInterpolation, GainEnvelope};
+///
+/// let envelope = GainEnvelope::from_json(r#"{
+///     "interpolation": "exponential",
+///     "points": [
+///         {"milliseconds": 0, "dbfs": 0.0},
+///         {"frame": 44100, "dbfs": -12.0}
+///     ]
+/// }"#).unwrap();
+///
+/// assert_eq!(envelope.interpolation, EnvelopeInterpolation::Exponential);
+/// assert_eq!(envelope.points.len(), 2);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GainEnvelope {
+    #[serde(default)]
+    pub points: Vec<EnvelopePoint>,
+    #[serde(default)]
+    pub interpolation: EnvelopeInterpolation,
+}
+
+impl GainEnvelope {
+    /// Creates an envelope from a list of breakpoints. The breakpoints do not need to be sorted.
+    pub fn new(points: Vec<EnvelopePoint>, interpolation: EnvelopeInterpolation) -> Self {
+        Self {
+            points,
+            interpolation,
+        }
+    }
+
+    /// Deserializes an envelope from a JSON string.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json)
+            .map_err(|err| Error::CannotParseGainEnvelope(leak_str!(err.to_string())))
+    }
+
+    /// Serializes this envelope to a JSON string.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Resolves every breakpoint to a frame index at the given frame rate.
+    pub(crate) fn resolve(&self, frame_rate_hz: u32) -> ResolvedGainEnvelope {
+        let mut points: Vec<(usize, f32)> = self
+            .points
+            .iter()
+            .map(|p| (p.position.to_frame(frame_rate_hz), p.dbfs))
+            .collect();
+        points.sort_by_key(|(frame_idx, _)| *frame_idx);
+        ResolvedGainEnvelope {
+            points,
+            interpolation: self.interpolation,
+            segment_idx: 0,
+        }
+    }
+}
+
+/// A [`GainEnvelope`] whose breakpoints have been sorted and converted to frame indexes.
+#[derive(Clone, Debug)]
+pub(crate) struct ResolvedGainEnvelope {
+    points: Vec<(usize, f32)>,
+    interpolation: EnvelopeInterpolation,
+    // A cursor into `points` that only moves forward, which makes
+    // evaluating consecutive frames cheap.
+    segment_idx: usize,
+}
+
+impl ResolvedGainEnvelope {
+    /// Returns the linear gain factor at `frame_idx`.
+    ///
+    /// Calls are expected to have non-decreasing values of `frame_idx`.
+    #[inline]
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn gain_at(&mut self, frame_idx: usize) -> f32 {
+        let (first_frame, first_dbfs) = match self.points.first() {
+            None => return 1.0,
+            Some(first) => *first,
+        };
+        if frame_idx <= first_frame {
+            return dbfs_to_ratio(first_dbfs);
+        }
+        while self.segment_idx + 1 < self.points.len()
+            && self.points[self.segment_idx + 1].0 <= frame_idx
+        {
+            self.segment_idx += 1;
+        }
+        let (start_frame, start_dbfs) = self.points[self.segment_idx];
+        let (end_frame, end_dbfs) = match self.points.get(self.segment_idx + 1) {
+            None => return dbfs_to_ratio(start_dbfs),
+            Some(end) => *end,
+        };
+        let t = (frame_idx - start_frame) as f32 / (end_frame - start_frame) as f32;
+        match self.interpolation {
+            EnvelopeInterpolation::Linear => {
+                let start = dbfs_to_ratio(start_dbfs);
+                let end = dbfs_to_ratio(end_dbfs);
+                start + (end - start) * t
+            }
+            EnvelopeInterpolation::Exponential => {
+                dbfs_to_ratio(start_dbfs + (end_dbfs - start_dbfs) * t)
+            }
+        }
+    }
+}
+
+#[inline]
+fn dbfs_to_ratio(dbfs: f32) -> f32 {
+    (10.0_f32).powf(dbfs / 20.0_f32)
+}
@@ -40,6 +40,58 @@ pub enum Error {
     /// [`WaveformArgs.repeat_pad_ending`][crate::WaveformArgs#structfield.repeat_pad_ending]
     /// as `true`.
     CannotSetZeroPadEndingAndRepeatPadEnding,
+    /// Raised when a [`GainEnvelope`][crate::envelope::GainEnvelope] could not be deserialized.
+    CannotParseGainEnvelope(&'static str),
+    /// Raised when a filter frequency is not between 0 hz and the Nyquist frequency.
+    WrongFilterFrequency(f32, u32),
+    /// Raised when a filter order is not supported by the requested filter design.
+    ///
+    /// Butterworth filters need an order of at least 1. Linkwitz-Riley
+    /// filters need an even order of at least 2.
+    WrongFilterOrder(usize),
+    /// Raised when a filter Q factor is not a positive, finite number.
+    WrongFilterQ(f32),
+    /// Raised when an FFT size is zero or shorter than the window length.
+    WrongFftSize(usize, usize),
+    /// Raised when a short-time Fourier transform hop length is zero.
+    WrongHopLength(usize),
+    /// Raised when a short-time Fourier transform does not have as many
+    /// values as its number of channels, bins and time steps call for.
+    WrongStftShape(usize, usize),
+    /// Raised when asking for zero features, or for more features
+    /// than there are values to compute them from.
+    WrongNumFeatures(usize, usize),
+    /// Raised when a delta width is even, smaller than 3, or
+    /// longer than the number of time steps.
+    WrongDeltaWidth(usize, usize),
+    /// Raised when a delta order is zero or not smaller than the delta width.
+    WrongDeltaOrder(usize, usize),
+    /// Raised when a constant-Q transform has zero bins per octave.
+    WrongBinsPerOctave(usize),
+    /// Raised when a tempo range is empty or not positive, or when the
+    /// starting tempo is outside of it. The values are the starting,
+    /// lowest and highest tempos in beats per minute.
+    WrongTempo(f32, f32, f32),
+    /// Raised when a pitch tracking frame is too short to hold two
+    /// periods of the lowest pitch. The values are the frame length and
+    /// the shortest frame length that would work.
+    WrongFrameLength(usize, usize),
+    /// Raised when asking for peak summary buckets that are zero frames long.
+    WrongFramesPerBucket(usize),
+    /// Raised when asking for a peak summary with zero buckets.
+    WrongNumBuckets(usize),
+    /// Raised when a peak summary could not be read from its binary format.
+    CannotParsePeaks(&'static str),
+    /// Raised when asking for an image that is zero pixels wide or tall, or
+    /// wider or taller than [`MAX_RENDER_SIZE`][crate::constants::MAX_RENDER_SIZE].
+    WrongImageSize(u32, u32),
+    /// Raised when the lowest level of a rendered decibel range is not
+    /// below the highest level.
+    WrongDecibelRange(f32, f32),
+    /// Raised when audio has too low a frame rate to hold the frequencies
+    /// being measured. The values are the frame rate and the lowest frame
+    /// rate that would work.
+    FrameRateTooLow(u32, u32),
     //
     // Decoding errors
     /// Raised when we could not decode any of the audio streams.
@@ -82,6 +134,26 @@ pub enum Error {
     // Raised when we try to append one audio source to another, but they don't have
     // the same frame rate.
     CannotAppendSourcesWithDifferentFrameRates(u32, u32),
+    // Raised when we try to mix audio sources together, but they don't have
+    // the same number of channels.
+    CannotMixSourcesWithDifferentNumChannels(u16, u16),
+    // Raised when we try to mix audio sources together, but they don't have
+    // the same frame rate.
+    CannotMixSourcesWithDifferentFrameRates(u32, u32),
+    // Raised when we try to pan a source that does not have exactly two channels.
+    CannotPanWithoutTwoChannels(u16),
+    // Raised when we try to convolve a source with an impulse response
+    // that has a different frame rate.
+    CannotConvolveWithDifferentFrameRates(u32, u32),
+    // Raised when we try to convolve a source with an impulse response whose
+    // number of channels is not 1, the same as the source, or 4 for a stereo source.
+    CannotConvolveWithImpulseResponseChannels(u16, u16),
+    // Raised when we try to line up waveforms that do not have the same frame rate.
+    CannotAlignWithDifferentFrameRates(u32, u32),
+    // Raised when we try to line up and stack an empty list of waveforms.
+    CannotAlignWithoutWaveforms,
+    // Raised when stacking waveforms would give more than 65535 channels.
+    CannotStackTooManyChannels(usize),
 }
 
 impl std::error::Error for Error {}
@@ -106,6 +178,49 @@ impl Error {
             Error::CannotSetZeroPadEndingAndRepeatPadEnding => {
                 "CannotSetZeroPadEndingAndRepeatPadEnding".to_string()
             }
+            Error::CannotParseGainEnvelope(msg) => format!("CannotParseGainEnvelope({})", msg),
+            Error::WrongFilterFrequency(freq, fr) => {
+                format!("WrongFilterFrequency({},{})", freq, fr)
+            }
+            Error::WrongFilterOrder(order) => format!("WrongFilterOrder({})", order),
+            Error::WrongFilterQ(q) => format!("WrongFilterQ({})", q),
+            Error::WrongFftSize(fft_size, window_length) => {
+                format!("WrongFftSize({},{})", fft_size, window_length)
+            }
+            Error::WrongHopLength(hop_length) => format!("WrongHopLength({})", hop_length),
+            Error::WrongStftShape(expected, actual) => {
+                format!("WrongStftShape({},{})", expected, actual)
+            }
+            Error::WrongNumFeatures(requested, available) => {
+                format!("WrongNumFeatures({},{})", requested, available)
+            }
+            Error::WrongDeltaWidth(width, num_time_steps) => {
+                format!("WrongDeltaWidth({},{})", width, num_time_steps)
+            }
+            Error::WrongDeltaOrder(order, width) => {
+                format!("WrongDeltaOrder({},{})", order, width)
+            }
+            Error::WrongBinsPerOctave(bins_per_octave) => {
+                format!("WrongBinsPerOctave({})", bins_per_octave)
+            }
+            Error::WrongTempo(start_bpm, min_bpm, max_bpm) => {
+                format!("WrongTempo({},{},{})", start_bpm, min_bpm, max_bpm)
+            }
+            Error::WrongFrameLength(frame_length, min_frame_length) => {
+                format!("WrongFrameLength({},{})", frame_length, min_frame_length)
+            }
+            Error::WrongFramesPerBucket(frames_per_bucket) => {
+                format!("WrongFramesPerBucket({})", frames_per_bucket)
+            }
+            Error::WrongNumBuckets(num_buckets) => format!("WrongNumBuckets({})", num_buckets),
+            Error::CannotParsePeaks(msg) => format!("CannotParsePeaks({})", msg),
+            Error::WrongImageSize(width, height) => format!("WrongImageSize({},{})", width, height),
+            Error::WrongDecibelRange(min_db, max_db) => {
+                format!("WrongDecibelRange({},{})", min_db, max_db)
+            }
+            Error::FrameRateTooLow(frame_rate_hz, min_frame_rate_hz) => {
+                format!("FrameRateTooLow({},{})", frame_rate_hz, min_frame_rate_hz)
+            }
 
             Error::NoSuitableAudioStreams(num_streams) => {
                 format!("NoSuitableAudioStreams({})", num_streams)
@@ -148,6 +263,31 @@ impl Error {
                 "CannotAppendSourcesWithDifferentNumChannels({}, {})",
                 f1, f2
             ),
+
+            Error::CannotMixSourcesWithDifferentNumChannels(c1, c2) => {
+                format!("CannotMixSourcesWithDifferentNumChannels({}, {})", c1, c2)
+            }
+
+            Error::CannotMixSourcesWithDifferentFrameRates(f1, f2) => {
+                format!("CannotMixSourcesWithDifferentFrameRates({}, {})", f1, f2)
+            }
+
+            Error::CannotPanWithoutTwoChannels(c) => {
+                format!("CannotPanWithoutTwoChannels({})", c)
+            }
+            Error::CannotConvolveWithDifferentFrameRates(f1, f2) => {
+                format!("CannotConvolveWithDifferentFrameRates({}, {})", f1, f2)
+            }
+            Error::CannotConvolveWithImpulseResponseChannels(c1, c2) => {
+                format!("CannotConvolveWithImpulseResponseChannels({}, {})", c1, c2)
+            }
+            Error::CannotAlignWithDifferentFrameRates(f1, f2) => {
+                format!("CannotAlignWithDifferentFrameRates({}, {})", f1, f2)
+            }
+            Error::CannotAlignWithoutWaveforms => "CannotAlignWithoutWaveforms".to_string(),
+            Error::CannotStackTooManyChannels(c) => {
+                format!("CannotStackTooManyChannels({})", c)
+            }
         }
     }
 }
@@ -169,6 +309,38 @@ impl fmt::Display for Error {
 
             Error::CannotSetZeroPadEndingAndRepeatPadEnding => write!(f, "You cannot set both `zero_pad_ending` and `repeat_pad_ending` as `true`. Pick one or the other."),
 
+            Error::CannotParseGainEnvelope(msg) => write!(f, "Could not parse the gain envelope: {}", msg),
+
+            Error::WrongFilterFrequency(freq, fr) => write!(f, "Cannot design a filter at {} hz for audio with a frame rate of {} hz. The frequency must be above 0 hz and below the Nyquist frequency of {} hz.", freq, fr, fr / 2),
+
+            Error::WrongFilterOrder(order) => write!(f, "Cannot design a filter of order {}. Butterworth filters need an order of at least 1, and Linkwitz-Riley filters need an even order of at least 2.", order),
+
+            Error::WrongFilterQ(q) => write!(f, "Cannot design a filter with a Q factor of {}. The Q factor must be a positive, finite number.", q),
+
+            Error::WrongFftSize(fft_size, window_length) => write!(f, "Cannot use an FFT size of {} with a window length of {}. The FFT size must be above 0 and at least as long as the window.", fft_size, window_length),
+
+            Error::WrongHopLength(hop_length) => write!(f, "Cannot use a hop length of {}. The hop length must be above 0.", hop_length),
+
+            Error::WrongStftShape(expected, actual) => write!(f, "Expected a short-time Fourier transform with {} values, but it has {} values.", expected, actual),
+
+            Error::WrongNumFeatures(requested, available) => write!(f, "Cannot compute {} features from {} values. Ask for at least 1 feature and at most {}.", requested, available, available),
+
+            Error::WrongDeltaWidth(width, num_time_steps) => write!(f, "Cannot compute deltas {} time steps wide over {} time steps. The width must be odd, at least 3, and at most the number of time steps.", width, num_time_steps),
+
+            Error::WrongDeltaOrder(order, width) => write!(f, "Cannot compute deltas of order {} with a width of {}. The order must be at least 1 and smaller than the width.", order, width),
+
+            Error::WrongBinsPerOctave(bins_per_octave) => write!(f, "Cannot compute a constant-Q transform with {} bins per octave. There must be at least 1 bin per octave.", bins_per_octave),
+
+            Error::WrongTempo(start_bpm, min_bpm, max_bpm) => write!(f, "Cannot estimate a tempo starting from {} BPM between {} BPM and {} BPM. The lowest tempo must be positive and below the highest, and the starting tempo must be between them.", start_bpm, min_bpm, max_bpm),
+
+            Error::WrongFrameLength(frame_length, min_frame_length) => write!(f, "Cannot track pitch with frames of {} frames. Each frame must hold two periods of the lowest pitch, which takes at least {} frames. Use longer frames or raise the lowest pitch.", frame_length, min_frame_length),
+            Error::WrongFramesPerBucket(frames_per_bucket) => write!(f, "Cannot summarize peaks in buckets of {} frames. Ask for at least one frame per bucket.", frames_per_bucket),
+            Error::WrongNumBuckets(num_buckets) => write!(f, "Cannot summarize peaks in {} buckets. Ask for at least one bucket.", num_buckets),
+            Error::CannotParsePeaks(msg) => write!(f, "Could not read the peak summary: {}", msg),
+            Error::WrongImageSize(width, height) => write!(f, "Cannot render an image that is {} by {} pixels. Images must be between 1 and {} pixels wide and tall.", width, height, crate::backend::constants::MAX_RENDER_SIZE),
+            Error::WrongDecibelRange(min_db, max_db) => write!(f, "Cannot render levels from {} dB to {} dB. The lowest level must be below the highest level.", min_db, max_db),
+            Error::FrameRateTooLow(frame_rate_hz, min_frame_rate_hz) => write!(f, "Cannot measure audio with a frame rate of {} hz. It needs a frame rate of at least {} hz.", frame_rate_hz, min_frame_rate_hz),
+
             Error::NoSuitableAudioStreams(num_streams) => write!(f, "We probed {} audio streams but could not decode any of them.", num_streams),
 
             Error::UnknownDecodingBackend(b) => write!(f, "Could not recognize the audio decoding backend `{}`.", b),
@@ -202,7 +374,23 @@ impl fmt::Display for Error {
 
             Error::CannotAppendSourcesWithDifferentNumChannels(c1, c2) => write!(f, "We cannot append two sources because they have a different number of channels ({} channels and {} channels).", c1, c2),
 
-            Error::CannotAppendSourcesWithDifferentFrameRates(f1, f2) => write!(f, "We cannot append two sources because they have a different frame rates ({} hz and {} hz).", f1, f2)
+            Error::CannotAppendSourcesWithDifferentFrameRates(f1, f2) => write!(f, "We cannot append two sources because they have a different frame rates ({} hz and {} hz).", f1, f2),
+
+            Error::CannotMixSourcesWithDifferentNumChannels(c1, c2) => write!(f, "We cannot mix these sources because they have a different number of channels ({} channels and {} channels).", c1, c2),
+
+            Error::CannotMixSourcesWithDifferentFrameRates(f1, f2) => write!(f, "We cannot mix these sources because they have different frame rates ({} hz and {} hz).", f1, f2),
+
+            Error::CannotPanWithoutTwoChannels(c) => write!(f, "We can only pan stereo audio, but this audio has {} channels.", c),
+
+            Error::CannotConvolveWithDifferentFrameRates(f1, f2) => write!(f, "Cannot convolve audio with a frame rate of {} hz with an impulse response with a frame rate of {} hz. Resample one of them first.", f1, f2),
+
+            Error::CannotConvolveWithImpulseResponseChannels(c1, c2) => write!(f, "Cannot convolve audio with {} channels with an impulse response with {} channels. The impulse response should have 1 channel, the same number of channels as the audio, or 4 channels for true-stereo convolution of stereo audio.", c1, c2),
+
+            Error::CannotAlignWithDifferentFrameRates(f1, f2) => write!(f, "Cannot line up audio with a frame rate of {} hz with audio with a frame rate of {} hz. Resample one of them first.", f1, f2),
+
+            Error::CannotAlignWithoutWaveforms => write!(f, "Cannot line up and stack an empty list of waveforms. Pass at least one waveform."),
+
+            Error::CannotStackTooManyChannels(c) => write!(f, "Cannot stack waveforms with {} channels in total. A waveform can have at most 65535 channels.", c),
         }
     }
 }
@@ -0,0 +1,390 @@
+//! IIR filters built from cascaded biquads.
+//!
+//! A [`FilterDesign`] is a cascade of one or more [`Biquad`] sections.
+//! Single sections are designed with the formulas from Robert Bristow-Johnson's
+//! *Audio EQ Cookbook*. Steeper Butterworth and Linkwitz-Riley designs are
+//! built by cascading several sections.
+//!
+//! Filter coefficients depend on the frame rate, so a design should be
+//! created with the same frame rate as the audio it is applied to.
+//! Apply a design with [`Source::apply_filter()`][crate::Source::apply_filter] or
+//! [`Waveform::apply_filter()`][crate::Waveform::apply_filter].
+//!
+//! # Examples
+//! ```
+//! use babycat::filter::{Biquad, FilterDesign};
+//!
+//! // A 4th-order Butterworth highpass at 80 hz followed by a 60 hz notch.
+//! let design = FilterDesign::butterworth_highpass(44100, 80.0, 4)
+//!     .unwrap()
+//!     .then(Biquad::notch(44100, 60.0, 30.0).unwrap());
+//! assert_eq!(design.stages().len(), 3);
+//!
+//! // The Butterworth cutoff is 3 dB down.
+//! let gain = design.magnitude_response(44100, 80.0);
+//! assert!((20.0 * gain.log10() + 3.01).abs() < 0.1);
+//! ```
+use std::f64::consts::PI;
+
+use crate::backend::Error;
+
+/// Returns an error unless `frequency_hz` is between 0 hz and the Nyquist frequency.
+fn check_frequency(frame_rate_hz: u32, frequency_hz: f32) -> Result<f64, Error> {
+    let frequency = f64::from(frequency_hz);
+    if !frequency.is_finite() || frequency <= 0.0 || frequency >= f64::from(frame_rate_hz) / 2.0 {
+        return Err(Error::WrongFilterFrequency(frequency_hz, frame_rate_hz));
+    }
+    Ok(frequency)
+}
+
+/// Returns the `cos(w0)` and `alpha` intermediate values from the cookbook formulas.
+fn cookbook_terms(frame_rate_hz: u32, frequency_hz: f32, q: f32) -> Result<(f64, f64), Error> {
+    let frequency = check_frequency(frame_rate_hz, frequency_hz)?;
+    if !(q > 0.0 && q.is_finite()) {
+        return Err(Error::WrongFilterQ(q));
+    }
+    let (sin, cos) = (2.0 * PI * frequency / f64::from(frame_rate_hz)).sin_cos();
+    Ok((cos, sin / (2.0 * f64::from(q))))
+}
+
+/// A single second-order IIR filter section.
+///
+/// The coefficients are normalized so that `a0` is `1.0`, giving the
+/// difference equation
+/// `y[n] = b0*x[n] + b1*x[n-1] + b2*x[n-2] - a1*y[n-1] - a2*y[n-2]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Biquad {
+    pub b0: f64,
+    pub b1: f64,
+    pub b2: f64,
+    pub a1: f64,
+    pub a2: f64,
+}
+
+impl Biquad {
+    /// Creates a section from unnormalized coefficients.
+    pub fn new(b0: f64, b1: f64, b2: f64, a0: f64, a1: f64, a2: f64) -> Self {
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+        }
+    }
+
+    /// A second-order lowpass. A `q` of `std::f32::consts::FRAC_1_SQRT_2` is Butterworth.
+    pub fn lowpass(frame_rate_hz: u32, frequency_hz: f32, q: f32) -> Result<Self, Error> {
+        let (cos, alpha) = cookbook_terms(frame_rate_hz, frequency_hz, q)?;
+        Ok(Self::new(
+            (1.0 - cos) / 2.0,
+            1.0 - cos,
+            (1.0 - cos) / 2.0,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        ))
+    }
+
+    /// A second-order highpass. A `q` of `std::f32::consts::FRAC_1_SQRT_2` is Butterworth.
+    pub fn highpass(frame_rate_hz: u32, frequency_hz: f32, q: f32) -> Result<Self, Error> {
+        let (cos, alpha) = cookbook_terms(frame_rate_hz, frequency_hz, q)?;
+        Ok(Self::new(
+            f64::midpoint(1.0, cos),
+            -(1.0 + cos),
+            f64::midpoint(1.0, cos),
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        ))
+    }
+
+    /// A bandpass with a peak gain of 0 dB at `frequency_hz`.
+    pub fn bandpass(frame_rate_hz: u32, frequency_hz: f32, q: f32) -> Result<Self, Error> {
+        let (cos, alpha) = cookbook_terms(frame_rate_hz, frequency_hz, q)?;
+        Ok(Self::new(
+            alpha,
+            0.0,
+            -alpha,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        ))
+    }
+
+    /// A notch that removes `frequency_hz`. Higher values of `q` give a narrower notch.
+    pub fn notch(frame_rate_hz: u32, frequency_hz: f32, q: f32) -> Result<Self, Error> {
+        let (cos, alpha) = cookbook_terms(frame_rate_hz, frequency_hz, q)?;
+        Ok(Self::new(
+            1.0,
+            -2.0 * cos,
+            1.0,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        ))
+    }
+
+    /// An allpass that shifts the phase around `frequency_hz` without changing the magnitude.
+    pub fn allpass(frame_rate_hz: u32, frequency_hz: f32, q: f32) -> Result<Self, Error> {
+        let (cos, alpha) = cookbook_terms(frame_rate_hz, frequency_hz, q)?;
+        Ok(Self::new(
+            1.0 - alpha,
+            -2.0 * cos,
+            1.0 + alpha,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        ))
+    }
+
+    /// A peaking EQ that boosts or cuts by `gain_db` around `frequency_hz`.
+    pub fn peaking(
+        frame_rate_hz: u32,
+        frequency_hz: f32,
+        q: f32,
+        gain_db: f32,
+    ) -> Result<Self, Error> {
+        let (cos, alpha) = cookbook_terms(frame_rate_hz, frequency_hz, q)?;
+        let a = 10.0_f64.powf(f64::from(gain_db) / 40.0);
+        Ok(Self::new(
+            1.0 + alpha * a,
+            -2.0 * cos,
+            1.0 - alpha * a,
+            1.0 + alpha / a,
+            -2.0 * cos,
+            1.0 - alpha / a,
+        ))
+    }
+
+    /// A low shelf that boosts or cuts everything below `frequency_hz` by `gain_db`.
+    ///
+    /// A `q` of `std::f32::consts::FRAC_1_SQRT_2` gives the steepest slope without overshoot.
+    pub fn low_shelf(
+        frame_rate_hz: u32,
+        frequency_hz: f32,
+        q: f32,
+        gain_db: f32,
+    ) -> Result<Self, Error> {
+        let (cos, alpha) = cookbook_terms(frame_rate_hz, frequency_hz, q)?;
+        let a = 10.0_f64.powf(f64::from(gain_db) / 40.0);
+        let two_sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+        Ok(Self::new(
+            a * ((a + 1.0) - (a - 1.0) * cos + two_sqrt_a_alpha),
+            2.0 * a * ((a - 1.0) - (a + 1.0) * cos),
+            a * ((a + 1.0) - (a - 1.0) * cos - two_sqrt_a_alpha),
+            (a + 1.0) + (a - 1.0) * cos + two_sqrt_a_alpha,
+            -2.0 * ((a - 1.0) + (a + 1.0) * cos),
+            (a + 1.0) + (a - 1.0) * cos - two_sqrt_a_alpha,
+        ))
+    }
+
+    /// A high shelf that boosts or cuts everything above `frequency_hz` by `gain_db`.
+    ///
+    /// A `q` of `std::f32::consts::FRAC_1_SQRT_2` gives the steepest slope without overshoot.
+    pub fn high_shelf(
+        frame_rate_hz: u32,
+        frequency_hz: f32,
+        q: f32,
+        gain_db: f32,
+    ) -> Result<Self, Error> {
+        let (cos, alpha) = cookbook_terms(frame_rate_hz, frequency_hz, q)?;
+        let a = 10.0_f64.powf(f64::from(gain_db) / 40.0);
+        let two_sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+        Ok(Self::new(
+            a * ((a + 1.0) + (a - 1.0) * cos + two_sqrt_a_alpha),
+            -2.0 * a * ((a - 1.0) + (a + 1.0) * cos),
+            a * ((a + 1.0) + (a - 1.0) * cos - two_sqrt_a_alpha),
+            (a + 1.0) - (a - 1.0) * cos + two_sqrt_a_alpha,
+            2.0 * ((a - 1.0) - (a + 1.0) * cos),
+            (a + 1.0) - (a - 1.0) * cos - two_sqrt_a_alpha,
+        ))
+    }
+
+    /// A first-order lowpass, designed with the bilinear transform.
+    pub fn first_order_lowpass(frame_rate_hz: u32, frequency_hz: f32) -> Result<Self, Error> {
+        let frequency = check_frequency(frame_rate_hz, frequency_hz)?;
+        let k = (PI * frequency / f64::from(frame_rate_hz)).tan();
+        Ok(Self::new(k, k, 0.0, k + 1.0, k - 1.0, 0.0))
+    }
+
+    /// A first-order highpass, designed with the bilinear transform.
+    pub fn first_order_highpass(frame_rate_hz: u32, frequency_hz: f32) -> Result<Self, Error> {
+        let frequency = check_frequency(frame_rate_hz, frequency_hz)?;
+        let k = (PI * frequency / f64::from(frame_rate_hz)).tan();
+        Ok(Self::new(1.0, -1.0, 0.0, k + 1.0, k - 1.0, 0.0))
+    }
+
+    /// The pre-emphasis filter `y[n] = x[n] - coefficient * x[n-1]`,
+    /// commonly used with a `coefficient` of `0.97` before speech analysis.
+    pub fn pre_emphasis(coefficient: f32) -> Self {
+        Self::new(1.0, -f64::from(coefficient), 0.0, 1.0, 0.0, 0.0)
+    }
+
+    /// Returns the linear gain of this section at `frequency_hz`.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn magnitude_response(&self, frame_rate_hz: u32, frequency_hz: f32) -> f32 {
+        let w = 2.0 * PI * f64::from(frequency_hz) / f64::from(frame_rate_hz);
+        // Evaluate the numerator and denominator polynomials at z = e^(jw).
+        let (sin1, cos1) = w.sin_cos();
+        let (sin2, cos2) = (2.0 * w).sin_cos();
+        let num_re = self.b0 + self.b1 * cos1 + self.b2 * cos2;
+        let num_im = -self.b1 * sin1 - self.b2 * sin2;
+        let den_re = 1.0 + self.a1 * cos1 + self.a2 * cos2;
+        let den_im = -self.a1 * sin1 - self.a2 * sin2;
+        (num_re.hypot(num_im) / den_re.hypot(den_im)) as f32
+    }
+}
+
+/// A cascade of [`Biquad`] sections, applied one after another.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FilterDesign {
+    stages: Vec<Biquad>,
+}
+
+impl From<Biquad> for FilterDesign {
+    fn from(biquad: Biquad) -> Self {
+        Self {
+            stages: vec![biquad],
+        }
+    }
+}
+
+impl FilterDesign {
+    /// Creates a design from a list of sections. An empty list passes audio through unchanged.
+    pub fn new(stages: Vec<Biquad>) -> Self {
+        Self { stages }
+    }
+
+    /// Returns the sections in this design, in the order they are applied.
+    pub fn stages(&self) -> &[Biquad] {
+        &self.stages
+    }
+
+    /// Returns a design that applies `other` after this one.
+    #[must_use]
+    pub fn then<F: Into<FilterDesign>>(mut self, other: F) -> Self {
+        self.stages.extend(other.into().stages);
+        self
+    }
+
+    /// Returns the Q factor of each second-order section of an `order`-th order Butterworth filter.
+    #[allow(clippy::cast_precision_loss)]
+    fn butterworth_qs(order: usize) -> impl Iterator<Item = f32> {
+        (0..order / 2).map(move |k| {
+            let theta = PI * (2 * k + 1) as f64 / (2 * order) as f64;
+            #[allow(clippy::cast_possible_truncation)]
+            let q = (1.0 / (2.0 * theta.sin())) as f32;
+            q
+        })
+    }
+
+    /// An `order`-th order Butterworth lowpass. The response is 3 dB down at `frequency_hz`.
+    pub fn butterworth_lowpass(
+        frame_rate_hz: u32,
+        frequency_hz: f32,
+        order: usize,
+    ) -> Result<Self, Error> {
+        if order == 0 {
+            return Err(Error::WrongFilterOrder(order));
+        }
+        let mut stages = Vec::with_capacity(order.div_ceil(2));
+        for q in Self::butterworth_qs(order) {
+            stages.push(Biquad::lowpass(frame_rate_hz, frequency_hz, q)?);
+        }
+        if !order.is_multiple_of(2) {
+            stages.push(Biquad::first_order_lowpass(frame_rate_hz, frequency_hz)?);
+        }
+        Ok(Self { stages })
+    }
+
+    /// An `order`-th order Butterworth highpass. The response is 3 dB down at `frequency_hz`.
+    pub fn butterworth_highpass(
+        frame_rate_hz: u32,
+        frequency_hz: f32,
+        order: usize,
+    ) -> Result<Self, Error> {
+        if order == 0 {
+            return Err(Error::WrongFilterOrder(order));
+        }
+        let mut stages = Vec::with_capacity(order.div_ceil(2));
+        for q in Self::butterworth_qs(order) {
+            stages.push(Biquad::highpass(frame_rate_hz, frequency_hz, q)?);
+        }
+        if !order.is_multiple_of(2) {
+            stages.push(Biquad::first_order_highpass(frame_rate_hz, frequency_hz)?);
+        }
+        Ok(Self { stages })
+    }
+
+    /// An `order`-th order Linkwitz-Riley lowpass, made of two cascaded
+    /// Butterworth filters of half the order. `order` must be even.
+    ///
+    /// The response is 6 dB down at `frequency_hz`, so it sums flat with
+    /// [`FilterDesign::linkwitz_riley_highpass()`] at the same frequency.
+    pub fn linkwitz_riley_lowpass(
+        frame_rate_hz: u32,
+        frequency_hz: f32,
+        order: usize,
+    ) -> Result<Self, Error> {
+        if order == 0 || !order.is_multiple_of(2) {
+            return Err(Error::WrongFilterOrder(order));
+        }
+        let half = Self::butterworth_lowpass(frame_rate_hz, frequency_hz, order / 2)?;
+        Ok(half.clone().then(half))
+    }
+
+    /// An `order`-th order Linkwitz-Riley highpass, made of two cascaded
+    /// Butterworth filters of half the order. `order` must be even.
+    pub fn linkwitz_riley_highpass(
+        frame_rate_hz: u32,
+        frequency_hz: f32,
+        order: usize,
+    ) -> Result<Self, Error> {
+        if order == 0 || !order.is_multiple_of(2) {
+            return Err(Error::WrongFilterOrder(order));
+        }
+        let half = Self::butterworth_highpass(frame_rate_hz, frequency_hz, order / 2)?;
+        Ok(half.clone().then(half))
+    }
+
+    /// Returns the linear gain of the whole cascade at `frequency_hz`.
+    pub fn magnitude_response(&self, frame_rate_hz: u32, frequency_hz: f32) -> f32 {
+        self.stages
+            .iter()
+            .map(|stage| stage.magnitude_response(frame_rate_hz, frequency_hz))
+            .product()
+    }
+}
+
+/// The running state of a [`FilterDesign`], kept separately for every channel.
+#[derive(Clone, Debug)]
+pub(crate) struct FilterState {
+    stages: Vec<Biquad>,
+    /// Transposed direct form II memory, indexed by `channel_idx * num_stages + stage_idx`.
+    memory: Vec<[f64; 2]>,
+}
+
+impl FilterState {
+    pub(crate) fn new(design: &FilterDesign, num_channels: u16) -> Self {
+        let stages = design.stages.clone();
+        let memory = vec![[0.0; 2]; stages.len() * num_channels as usize];
+        Self { stages, memory }
+    }
+
+    /// Filters one sample from the given channel.
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn process(&mut self, channel_idx: usize, sample: f32) -> f32 {
+        let num_stages = self.stages.len();
+        let memory = &mut self.memory[channel_idx * num_stages..(channel_idx + 1) * num_stages];
+        let mut x = f64::from(sample);
+        for (stage, m) in self.stages.iter().zip(memory.iter_mut()) {
+            let y = stage.b0 * x + m[0];
+            m[0] = stage.b1 * x - stage.a1 * y + m[1];
+            m[1] = stage.b2 * x - stage.a2 * y;
+            x = y;
+        }
+        x as f32
+    }
+}
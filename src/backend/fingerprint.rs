@@ -0,0 +1,430 @@
+//! Audio fingerprints for finding copies of the same recording.
+//!
+//! A fingerprint follows the design of Chromaprint. The audio is mixed
+//! down to one channel and cut into overlapping frames of about 0.37
+//! seconds. Every frame is folded into a 12-bin chroma vector, and the
+//! chroma vectors are smoothed over time and normalized. This image of
+//! pitch classes over time is then read by 16 filters that compare the
+//! sums of neighbouring rectangles, and every filter contributes 2
+//! bits to a 32-bit word for every frame.
+//!
+//! Because the filters only compare parts of the image with each other,
+//! the words do not change when the audio is re-encoded, resampled or
+//! made louder or quieter. Frames are measured in seconds rather than
+//! in samples, so fingerprints of the same audio at different frame
+//! rates line up. [`Fingerprint::similarity()`] lines two fingerprints
+//! up at every offset of up to 10 seconds, so small trims at the start
+//! of a file do not matter either.
+
+use std::sync::Arc;
+
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::constants::{NUM_CHROMA, WINDOW_TYPE_HANN};
+use crate::backend::spectral::window;
+use crate::backend::Error;
+use crate::backend::Signal;
+use crate::backend::Source;
+use crate::backend::Waveform;
+
+/// The length of every frame in seconds, which is 4096 frames at 11025 hz.
+const FRAME_SECONDS: f64 = 4096.0 / 11025.0;
+
+/// The time between the starts of consecutive frames in seconds.
+const HOP_SECONDS: f64 = FRAME_SECONDS / 3.0;
+
+/// The lowest frequency that is folded into the chroma vectors.
+const MIN_FREQUENCY_HZ: f64 = 28.0;
+
+/// The highest frequency that is folded into the chroma vectors.
+const MAX_FREQUENCY_HZ: f64 = 3520.0;
+
+/// The weights of the moving average that smooths the chroma vectors over time.
+const SMOOTHING: [f32; 5] = [0.25, 0.75, 1.0, 0.75, 0.25];
+
+/// Chroma vectors quieter than a sine wave at about -90 dBFS are treated as silence.
+const MIN_CHROMA_NORM: f32 = 1e-10;
+
+/// Filter outputs further than this from zero fall into the outer two
+/// of the four quantization levels.
+const QUANTIZATION_THRESHOLD: f32 = 0.05;
+
+/// How far apart two fingerprints can be shifted when comparing them, in seconds.
+const MAX_OFFSET_SECONDS: f64 = 10.0;
+
+/// The number of bits in every fingerprint word.
+const BITS_PER_WORD: usize = 32;
+
+/// How an [`ImageFilter`] splits its rectangle into two parts to compare.
+#[derive(Clone, Copy, Debug)]
+enum FilterKind {
+    /// The lower half of the pitch classes against the upper half.
+    PitchHalves,
+    /// The middle third of the pitch classes against the outer thirds.
+    PitchThirds,
+    /// The first half of the frames against the second half.
+    TimeHalves,
+    /// The top-left and bottom-right quarters against the other two.
+    Checkerboard,
+}
+
+/// A comparison of two parts of a rectangle in the chroma image.
+#[derive(Clone, Copy, Debug)]
+struct ImageFilter {
+    kind: FilterKind,
+    /// The lowest pitch class of the rectangle. Pitch classes wrap around from B to C.
+    pitch_class: usize,
+    /// The number of pitch classes in the rectangle.
+    height: usize,
+    /// The number of frames in the rectangle.
+    width: usize,
+}
+
+impl ImageFilter {
+    const fn new(kind: FilterKind, pitch_class: usize, height: usize, width: usize) -> Self {
+        Self {
+            kind,
+            pitch_class,
+            height,
+            width,
+        }
+    }
+
+    /// Compares the two parts of the rectangle that starts at `frame_idx`.
+    fn apply(&self, image: &[[f32; NUM_CHROMA]], frame_idx: usize) -> f32 {
+        // Sums the rectangle that starts `start` frames after `frame_idx`.
+        let area = |start: usize, width: usize, low: usize, height: usize| -> f32 {
+            image[frame_idx + start..frame_idx + start + width]
+                .iter()
+                .map(|chroma| {
+                    (low..low + height)
+                        .map(|pitch_class| chroma[pitch_class % NUM_CHROMA])
+                        .sum::<f32>()
+                })
+                .sum()
+        };
+        let (low, height, width) = (self.pitch_class, self.height, self.width);
+        let (half_height, third_height, half_width) = (height / 2, height / 3, width / 2);
+        let (first, second) = match self.kind {
+            FilterKind::PitchHalves => (
+                area(0, width, low, half_height),
+                area(0, width, low + half_height, half_height),
+            ),
+            FilterKind::PitchThirds => (
+                area(0, width, low + third_height, third_height),
+                f32::midpoint(
+                    area(0, width, low, third_height),
+                    area(0, width, low + 2 * third_height, third_height),
+                ),
+            ),
+            FilterKind::TimeHalves => (
+                area(0, half_width, low, height),
+                area(half_width, half_width, low, height),
+            ),
+            FilterKind::Checkerboard => (
+                area(0, half_width, low, half_height)
+                    + area(half_width, half_width, low + half_height, half_height),
+                area(half_width, half_width, low, half_height)
+                    + area(0, half_width, low + half_height, half_height),
+            ),
+        };
+        first.ln_1p() - second.ln_1p()
+    }
+}
+
+/// The filters that make up every fingerprint word, 2 bits each.
+const FILTERS: [ImageFilter; 16] = [
+    ImageFilter::new(FilterKind::PitchHalves, 0, 6, 1),
+    ImageFilter::new(FilterKind::PitchHalves, 3, 6, 1),
+    ImageFilter::new(FilterKind::PitchHalves, 6, 6, 1),
+    ImageFilter::new(FilterKind::PitchHalves, 9, 6, 1),
+    ImageFilter::new(FilterKind::PitchThirds, 0, 3, 2),
+    ImageFilter::new(FilterKind::PitchThirds, 3, 3, 2),
+    ImageFilter::new(FilterKind::PitchThirds, 6, 3, 2),
+    ImageFilter::new(FilterKind::PitchThirds, 9, 3, 2),
+    ImageFilter::new(FilterKind::TimeHalves, 0, 3, 8),
+    ImageFilter::new(FilterKind::TimeHalves, 3, 3, 8),
+    ImageFilter::new(FilterKind::TimeHalves, 6, 3, 8),
+    ImageFilter::new(FilterKind::TimeHalves, 9, 3, 8),
+    ImageFilter::new(FilterKind::Checkerboard, 0, 6, 4),
+    ImageFilter::new(FilterKind::Checkerboard, 3, 6, 4),
+    ImageFilter::new(FilterKind::Checkerboard, 6, 6, 4),
+    ImageFilter::new(FilterKind::Checkerboard, 9, 6, 4),
+];
+
+/// The number of frames that the widest filter reads.
+const MAX_FILTER_WIDTH: usize = 8;
+
+/// Quantizes a filter output into 2 bits. Neighbouring levels differ
+/// in one bit, so a value near a threshold costs at most one bit.
+fn quantize(value: f32) -> u32 {
+    if value < -QUANTIZATION_THRESHOLD {
+        0b00
+    } else if value < 0.0 {
+        0b01
+    } else if value < QUANTIZATION_THRESHOLD {
+        0b11
+    } else {
+        0b10
+    }
+}
+
+/// A compact fingerprint of a recording.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    /// The length of the fingerprinted audio in seconds.
+    pub duration_seconds: f64,
+    /// One 32-bit word for about every 0.12 seconds of audio. Audio
+    /// that is shorter than about 1.6 seconds has no words.
+    pub words: Vec<u32>,
+}
+
+impl Fingerprint {
+    /// Fingerprints a [`Waveform`].
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::fingerprint::Fingerprint;
+    /// use babycat::Waveform;
+    ///
+    /// let waveform = Waveform::from_file(
+    ///     "audio-for-tests/log-sweep/pcm-16.flac",
+    ///     Default::default(),
+    /// ).unwrap();
+    /// let fingerprint = Fingerprint::from_waveform(&waveform);
+    /// assert!((fingerprint.duration_seconds - 10.0).abs() < 0.01);
+    /// assert_eq!(fingerprint.words.len(), 67);
+    /// assert_eq!(fingerprint.similarity(&fingerprint), 1.0);
+    /// ```
+    pub fn from_waveform(waveform: &Waveform) -> Self {
+        let mut fingerprinter =
+            Fingerprinter::new(waveform.frame_rate_hz(), waveform.num_channels());
+        for sample in waveform.to_interleaved_samples() {
+            fingerprinter.push_sample(*sample);
+        }
+        fingerprinter.finish()
+    }
+
+    /// Fingerprints any [`Source`] in a single streaming pass, without
+    /// holding all of its samples in memory.
+    pub fn from_source<S: Source>(source: S) -> Self {
+        let mut fingerprinter = Fingerprinter::new(source.frame_rate_hz(), source.num_channels());
+        for sample in source {
+            fingerprinter.push_sample(sample);
+        }
+        fingerprinter.finish()
+    }
+
+    /// Measures how likely it is that two fingerprints come from the
+    /// same recording, from 0 to 1.
+    ///
+    /// The fingerprints are lined up at every offset of up to 10 seconds
+    /// where at least half of the shorter one overlaps the other, and the
+    /// offset with the fewest differing bits is kept. Identical audio
+    /// scores 1, re-encoded, resampled or trimmed copies usually score
+    /// above 0.75, and unrelated audio, whose bits differ half of the
+    /// time, scores close to 0. Fingerprints without words score 0.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss,
+        clippy::cast_possible_wrap
+    )]
+    pub fn similarity(&self, other: &Self) -> f32 {
+        let (a, b) = (&self.words, &other.words);
+        let min_overlap = std::cmp::max(std::cmp::min(a.len(), b.len()) / 2, 1);
+        let max_offset = (MAX_OFFSET_SECONDS / HOP_SECONDS).round() as isize;
+        let mut best_error_rate = 1.0_f64;
+        for offset in -max_offset..=max_offset {
+            // Word `i` of `a` lines up with word `i + offset` of `b`.
+            let a_start = std::cmp::max(-offset, 0) as usize;
+            let b_start = std::cmp::max(offset, 0) as usize;
+            if a_start >= a.len() || b_start >= b.len() {
+                continue;
+            }
+            let overlap = std::cmp::min(a.len() - a_start, b.len() - b_start);
+            if overlap < min_overlap {
+                continue;
+            }
+            let differing_bits: u32 = a[a_start..a_start + overlap]
+                .iter()
+                .zip(&b[b_start..b_start + overlap])
+                .map(|(x, y)| (x ^ y).count_ones())
+                .sum();
+            let error_rate = f64::from(differing_bits) / (overlap * BITS_PER_WORD) as f64;
+            best_error_rate = best_error_rate.min(error_rate);
+        }
+        (1.0 - 2.0 * best_error_rate).max(0.0) as f32
+    }
+}
+
+/// Builds a [`Fingerprint`] from interleaved samples, one at a time.
+struct Fingerprinter {
+    frame_rate_hz: u32,
+    num_channels: u16,
+    frame_length: usize,
+    window: Vec<f32>,
+    fft: Arc<dyn Fft<f32>>,
+    buffer: Vec<Complex<f32>>,
+    /// The pitch class of every FFT bin, or `None` if the bin is out of range.
+    pitch_classes: Vec<Option<usize>>,
+    /// The mono samples that have not been analyzed yet.
+    mono: Vec<f32>,
+    /// The index of the first sample of `mono` in the whole signal.
+    mono_start: usize,
+    frame_sum: f32,
+    channel_idx: u16,
+    chroma: Vec<[f32; NUM_CHROMA]>,
+}
+
+impl Fingerprinter {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss,
+        clippy::cast_possible_wrap
+    )]
+    fn new(frame_rate_hz: u32, num_channels: u16) -> Self {
+        let frame_length = std::cmp::max(
+            (FRAME_SECONDS * f64::from(frame_rate_hz)).round() as usize,
+            1,
+        );
+        let pitch_classes = (0..=frame_length / 2)
+            .map(|bin_idx| {
+                let frequency_hz = bin_idx as f64 * f64::from(frame_rate_hz) / frame_length as f64;
+                if !(MIN_FREQUENCY_HZ..=MAX_FREQUENCY_HZ).contains(&frequency_hz) {
+                    return None;
+                }
+                let midi = 69.0 + 12.0 * (frequency_hz / 440.0).log2();
+                Some((midi.round() as i64).rem_euclid(NUM_CHROMA as i64) as usize)
+            })
+            .collect();
+        Self {
+            frame_rate_hz,
+            num_channels,
+            frame_length,
+            window: window(WINDOW_TYPE_HANN, frame_length).unwrap(),
+            fft: FftPlanner::new().plan_fft_forward(frame_length),
+            buffer: vec![Complex::new(0.0, 0.0); frame_length],
+            pitch_classes,
+            mono: Vec::new(),
+            mono_start: 0,
+            frame_sum: 0.0,
+            channel_idx: 0,
+            chroma: Vec::new(),
+        }
+    }
+
+    /// Returns the index of the first sample of a frame in the whole signal.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn frame_start(&self, frame_idx: usize) -> usize {
+        // Rounding every start on its own keeps the frames from drifting
+        // when the hop is not a whole number of samples.
+        (frame_idx as f64 * HOP_SECONDS * f64::from(self.frame_rate_hz)).round() as usize
+    }
+
+    fn push_sample(&mut self, sample: f32) {
+        if self.num_channels == 0 || self.frame_rate_hz == 0 {
+            return;
+        }
+        self.frame_sum += sample;
+        self.channel_idx += 1;
+        if self.channel_idx < self.num_channels {
+            return;
+        }
+        self.mono
+            .push(self.frame_sum / f32::from(self.num_channels));
+        self.frame_sum = 0.0;
+        self.channel_idx = 0;
+
+        let start = self.frame_start(self.chroma.len());
+        if start + self.frame_length > self.mono_start + self.mono.len() {
+            return;
+        }
+        self.analyze_frame(start - self.mono_start);
+        let next_start = self.frame_start(self.chroma.len());
+        self.mono.drain(..next_start - self.mono_start);
+        self.mono_start = next_start;
+    }
+
+    /// Folds the frame at `offset` in `mono` into a chroma vector.
+    #[allow(clippy::cast_precision_loss)]
+    fn analyze_frame(&mut self, offset: usize) {
+        let frame = &self.mono[offset..offset + self.frame_length];
+        for ((out, sample), weight) in self.buffer.iter_mut().zip(frame).zip(&self.window) {
+            *out = Complex::new(sample * weight, 0.0);
+        }
+        self.fft.process(&mut self.buffer);
+        // Dividing by the frame length squared keeps the power the same
+        // at every frame rate.
+        let scale = 1.0 / (self.frame_length as f32).powi(2);
+        let mut chroma = [0.0_f32; NUM_CHROMA];
+        for (bin, pitch_class) in self.buffer.iter().zip(&self.pitch_classes) {
+            if let Some(pitch_class) = pitch_class {
+                chroma[*pitch_class] += bin.norm_sqr() * scale;
+            }
+        }
+        self.chroma.push(chroma);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn finish(self) -> Fingerprint {
+        let duration_seconds = if self.frame_rate_hz == 0 {
+            0.0
+        } else {
+            (self.mono_start + self.mono.len()) as f64 / f64::from(self.frame_rate_hz)
+        };
+        let image: Vec<[f32; NUM_CHROMA]> = self
+            .chroma
+            .windows(SMOOTHING.len())
+            .map(|chromas| {
+                let mut smoothed = [0.0_f32; NUM_CHROMA];
+                for (chroma, weight) in chromas.iter().zip(SMOOTHING) {
+                    for (out, value) in smoothed.iter_mut().zip(chroma) {
+                        *out += weight * value;
+                    }
+                }
+                let norm = smoothed
+                    .iter()
+                    .map(|value| value * value)
+                    .sum::<f32>()
+                    .sqrt();
+                for value in &mut smoothed {
+                    *value = if norm < MIN_CHROMA_NORM {
+                        0.0
+                    } else {
+                        *value / norm
+                    };
+                }
+                smoothed
+            })
+            .collect();
+        let num_words = (image.len() + 1).saturating_sub(MAX_FILTER_WIDTH);
+        let words = (0..num_words)
+            .map(|frame_idx| {
+                FILTERS.iter().fold(0_u32, |word, filter| {
+                    (word << 2) | quantize(filter.apply(&image, frame_idx))
+                })
+            })
+            .collect();
+        Fingerprint {
+            duration_seconds,
+            words,
+        }
+    }
+}
+
+/// The fingerprint of one audio file, or the error that stopped us.
+#[derive(Clone, Debug)]
+pub struct FingerprintNamedResult {
+    /// The name of the audio file.
+    pub name: String,
+    /// The fingerprint, or the error raised while decoding the file.
+    pub result: Result<Fingerprint, Error>,
+}
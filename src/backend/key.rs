@@ -0,0 +1,245 @@
+//! Musical key estimation.
+//!
+//! The key is found with the Krumhansl-Schmuckler algorithm. The
+//! [chromagram][crate::spectral::chroma] of the audio is averaged into
+//! one 12-bin pitch class profile, which is then correlated with the
+//! major and minor key profiles from Carol L. Krumhansl and Edward J.
+//! Kessler, "Tracing the dynamic changes in perceived tonal organization
+//! in a spatial representation of musical keys" (1982), rotated to start
+//! on every pitch class. The key whose profile correlates best is the
+//! most likely key.
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::constants::NUM_CHROMA;
+use crate::backend::spectral::{chroma, CqtArgs, Features};
+use crate::backend::Error;
+use crate::backend::Waveform;
+
+/// The number of keys that are compared: 12 major and 12 minor.
+pub const NUM_KEYS: usize = 2 * NUM_CHROMA;
+
+/// How well every pitch class fits a major key, starting on its tonic.
+const MAJOR_PROFILE: [f64; NUM_CHROMA] = [
+    6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88,
+];
+
+/// How well every pitch class fits a minor key, starting on its tonic.
+const MINOR_PROFILE: [f64; NUM_CHROMA] = [
+    6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17,
+];
+
+/// The name of every pitch class, starting on C.
+const PITCH_CLASS_NAMES: [&str; NUM_CHROMA] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+/// The smallest variance of a pitch class profile that is not silence.
+const MIN_VARIANCE: f64 = 1e-12;
+
+/// The number of chromagram bins in each octave for [`key_cqt_args()`].
+const KEY_BINS_PER_OCTAVE: usize = 36;
+
+/// The most octaves of chromagram bins for [`key_cqt_args()`].
+const KEY_MAX_NUM_OCTAVES: usize = 7;
+
+/// Whether a key is major or minor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    Major,
+    Minor,
+}
+
+/// The estimated key of a waveform.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Key {
+    /// The pitch class of the tonic, with C as 0, C# as 1, and so on up to B as 11.
+    pub tonic: usize,
+    /// Whether the key is major or minor.
+    pub mode: Mode,
+    /// The name of the key, such as `"C major"` or `"F# minor"`.
+    pub name: String,
+    /// The key in Camelot notation, such as `"8B"` for C major or
+    /// `"8A"` for A minor. Keys next to each other on the Camelot
+    /// wheel mix well together.
+    pub camelot: String,
+    /// The correlation of the audio with the chosen key, from 0 to 1.
+    pub confidence: f32,
+    /// The correlation of the audio with every key, from -1 to 1. The
+    /// first 12 values are the major keys and the last 12 are the minor
+    /// keys, each starting on C. Use [`key_index()`] to find a key here.
+    pub correlations: Vec<f32>,
+}
+
+/// Returns the position of a key in [`Key.correlations`](Key#structfield.correlations).
+pub fn key_index(tonic: usize, mode: Mode) -> usize {
+    match mode {
+        Mode::Major => tonic % NUM_CHROMA,
+        Mode::Minor => NUM_CHROMA + tonic % NUM_CHROMA,
+    }
+}
+
+/// Returns a key in Camelot notation.
+fn camelot(tonic: usize, mode: Mode) -> String {
+    // The Camelot wheel follows the circle of fifths, with C major at 8B
+    // and every minor key next to its relative major.
+    let (relative_major, letter) = match mode {
+        Mode::Major => (tonic, 'B'),
+        Mode::Minor => ((tonic + 3) % NUM_CHROMA, 'A'),
+    };
+    let number = (relative_major * 7 + 7) % NUM_CHROMA + 1;
+    format!("{number}{letter}")
+}
+
+/// Returns the Pearson correlation of two equally long sequences, or
+/// `None` if either of them is constant.
+#[allow(clippy::cast_precision_loss)]
+fn correlation(a: &[f64], b: &[f64]) -> Option<f64> {
+    let len = a.len() as f64;
+    let mean_a = a.iter().sum::<f64>() / len;
+    let mean_b = b.iter().sum::<f64>() / len;
+    let (mut covariance, mut variance_a, mut variance_b) = (0.0, 0.0, 0.0);
+    for (x, y) in a.iter().zip(b) {
+        covariance += (x - mean_a) * (y - mean_b);
+        variance_a += (x - mean_a).powi(2);
+        variance_b += (y - mean_b).powi(2);
+    }
+    if variance_a < MIN_VARIANCE || variance_b < MIN_VARIANCE {
+        return None;
+    }
+    Some(covariance / (variance_a * variance_b).sqrt())
+}
+
+/// Estimates the key from a chromagram.
+///
+/// The chromagram is averaged over every channel and time step before
+/// it is compared with the key profiles. Returns `None` if every pitch
+/// class is equally strong, such as in silence.
+///
+/// # Errors
+/// Returns [`Error::WrongNumFeatures`] if `chromagram` does not have
+/// 12 features.
+#[allow(clippy::cast_possible_truncation)]
+pub fn key_from_chroma(chromagram: &Features) -> Result<Option<Key>, Error> {
+    if chromagram.num_features() != NUM_CHROMA {
+        return Err(Error::WrongNumFeatures(
+            chromagram.num_features(),
+            NUM_CHROMA,
+        ));
+    }
+    let num_time_steps = chromagram.num_time_steps();
+    let mut profile = [0.0_f64; NUM_CHROMA];
+    if num_time_steps > 0 {
+        for (row_idx, row) in chromagram
+            .as_slice()
+            .chunks_exact(num_time_steps)
+            .enumerate()
+        {
+            profile[row_idx % NUM_CHROMA] += row.iter().map(|value| f64::from(*value)).sum::<f64>();
+        }
+    }
+
+    let mut correlations = Vec::with_capacity(NUM_KEYS);
+    for key_profile in [MAJOR_PROFILE, MINOR_PROFILE] {
+        for tonic in 0..NUM_CHROMA {
+            let rotated: Vec<f64> = (0..NUM_CHROMA)
+                .map(|degree| profile[(tonic + degree) % NUM_CHROMA])
+                .collect();
+            match correlation(&rotated, &key_profile) {
+                Some(value) => correlations.push(value as f32),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    let best = (0..NUM_KEYS)
+        .max_by(|a, b| correlations[*a].total_cmp(&correlations[*b]))
+        .unwrap_or(0);
+    let tonic = best % NUM_CHROMA;
+    let mode = if best < NUM_CHROMA {
+        Mode::Major
+    } else {
+        Mode::Minor
+    };
+    let mode_name = match mode {
+        Mode::Major => "major",
+        Mode::Minor => "minor",
+    };
+    Ok(Some(Key {
+        tonic,
+        mode,
+        name: format!("{} {}", PITCH_CLASS_NAMES[tonic], mode_name),
+        camelot: camelot(tonic, mode),
+        confidence: correlations[key_index(tonic, mode)].clamp(0.0, 1.0),
+        correlations,
+    }))
+}
+
+/// Returns the [`CqtArgs`] for estimating the key of audio at `frame_rate_hz`.
+///
+/// The chromagram gets 36 bins per octave, which makes it less sensitive
+/// to tuning, over up to 7 octaves from the default lowest frequency.
+/// Octaves that would reach past the Nyquist frequency are left out.
+/// Returns `None` if not even one octave fits.
+///
+/// # Examples
+/// ```
+/// use babycat::key::key_cqt_args;
+///
+/// assert_eq!(key_cqt_args(44100).unwrap().num_bins, 252);
+/// assert_eq!(key_cqt_args(8000).unwrap().num_bins, 216);
+/// assert_eq!(key_cqt_args(100), None);
+/// ```
+pub fn key_cqt_args(frame_rate_hz: u32) -> Option<CqtArgs> {
+    #[allow(clippy::cast_precision_loss)]
+    let nyquist_hz = frame_rate_hz as f32 / 2.0;
+    (1..=KEY_MAX_NUM_OCTAVES)
+        .rev()
+        .map(|num_octaves| {
+            CqtArgs::default()
+                .set_bins_per_octave(KEY_BINS_PER_OCTAVE)
+                .set_num_bins(num_octaves * KEY_BINS_PER_OCTAVE)
+        })
+        // The upper edge of the highest bin is below the center of the
+        // bin after it.
+        .find(|args| args.bin_frequency_hz(args.num_bins) < nyquist_hz)
+}
+
+/// Estimates the key of a waveform.
+///
+/// This computes the [chromagram][crate::spectral::chroma] of the
+/// waveform with `args` and passes it to [`key_from_chroma()`]. Using
+/// 36 bins per octave makes the chromagram less sensitive to tuning.
+/// Returns `None` if the waveform has no pitched content, such as
+/// silence.
+///
+/// # Errors
+/// Returns the same errors as [`crate::spectral::cqt()`].
+///
+/// # Examples
+/// ```
+/// use babycat::key::{key, Mode};
+/// use babycat::spectral::CqtArgs;
+/// use babycat::Waveform;
+///
+/// // The notes of a C major scale, one after another.
+/// let scale_hz = [261.63, 293.66, 329.63, 349.23, 392.0, 440.0, 493.88, 523.25];
+/// let samples: Vec<f32> = (0..scale_hz.len() * 11025)
+///     .map(|i| {
+///         let hz = scale_hz[i / 11025];
+///         0.5 * (2.0 * std::f32::consts::PI * hz * i as f32 / 22050.0).sin()
+///     })
+///     .collect();
+/// let waveform = Waveform::from_interleaved_samples(22050, 1, &samples);
+///
+/// let args = CqtArgs::default().set_bins_per_octave(36).set_num_bins(252);
+/// let estimate = key(&waveform, args).unwrap().unwrap();
+/// assert_eq!(estimate.name, "C major");
+/// assert_eq!(estimate.mode, Mode::Major);
+/// assert_eq!(estimate.camelot, "8B");
+/// assert_eq!(estimate.correlations.len(), 24);
+/// ```
+pub fn key(waveform: &Waveform, args: CqtArgs) -> Result<Option<Key>, Error> {
+    key_from_chroma(&chroma(waveform, args)?)
+}
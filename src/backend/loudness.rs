@@ -0,0 +1,611 @@
+//! Loudness and peak measurements, following ITU-R BS.1770 and EBU R128.
+//!
+//! Loudness is measured by K-weighting every channel, summing the
+//! weighted mean square of each channel over overlapping 400 millisecond
+//! gating blocks, and then discarding blocks that are silent or much
+//! quieter than the rest of the audio.
+//!
+//! Use [`LoudnessReport`] to measure everything at once, either from a
+//! [`Waveform`] or in a single streaming pass over any [`Source`].
+
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::filter::{Biquad, FilterDesign, FilterState};
+use crate::backend::Error;
+use crate::backend::Signal;
+use crate::backend::Source;
+use crate::backend::Waveform;
+
+/// Blocks quieter than this are never counted towards the integrated loudness.
+pub const ABSOLUTE_GATE_LUFS: f32 = -70.0;
+/// Blocks this far below the absolute-gated loudness are not counted either.
+pub const RELATIVE_GATE_LU: f32 = -10.0;
+/// Short-term values this far below the absolute-gated loudness are
+/// not counted towards the loudness range.
+pub const LOUDNESS_RANGE_RELATIVE_GATE_LU: f32 = -20.0;
+
+/// The length of the window behind each momentary loudness value.
+pub const MOMENTARY_WINDOW_MILLISECONDS: usize = 400;
+/// The length of the window behind each short-term loudness value.
+pub const SHORT_TERM_WINDOW_MILLISECONDS: usize = 3000;
+/// The time between consecutive momentary or short-term loudness values.
+pub const SERIES_INTERVAL_MILLISECONDS: usize = 100;
+
+/// Audio with a lower frame rate cannot be measured. The K-weighting
+/// shelf sits at about 1682 hz, and this also keeps the true peak
+/// oversampling factor at 32 or below.
+pub const LOUDNESS_MIN_FRAME_RATE_HZ: u32 = 8000;
+
+/// Levels in a [`LoudnessReport`] are never reported below this value,
+/// so that silence can be written out as JSON.
+pub const MIN_REPORTED_LEVEL: f32 = -144.0;
+
+/// The offset in the loudness formula that makes a 0 dBFS, 997 hz sine in
+/// the left or right channel read as -3.01 LUFS.
+const LOUDNESS_OFFSET: f64 = -0.691;
+
+/// The number of 100 millisecond steps in a momentary window.
+const MOMENTARY_STEPS: usize = MOMENTARY_WINDOW_MILLISECONDS / SERIES_INTERVAL_MILLISECONDS;
+/// The number of 100 millisecond steps in a short-term window.
+const SHORT_TERM_STEPS: usize = SHORT_TERM_WINDOW_MILLISECONDS / SERIES_INTERVAL_MILLISECONDS;
+
+/// True peaks are found by upsampling until the frame rate is at least this high.
+/// This means 4x oversampling at 44100 and 48000 hz.
+const TRUE_PEAK_MIN_FRAME_RATE_HZ: u32 = 176_400;
+/// The number of input samples that each polyphase branch of the true peak
+/// interpolator looks at.
+const TRUE_PEAK_TAPS_PER_PHASE: usize = 12;
+
+/// Converts a level in decibels relative to full scale to a linear amplitude.
+#[inline]
+pub fn dbfs_to_ratio(dbfs: f32) -> f32 {
+    (10.0_f32).powf(dbfs / 20.0_f32)
+}
+
+/// Converts a linear amplitude to a level in decibels relative to full scale.
+///
+/// Silence is `f32::NEG_INFINITY`.
+#[inline]
+pub fn ratio_to_dbfs(ratio: f32) -> f32 {
+    20.0_f32 * ratio.log10()
+}
+
+/// Returns the two-stage K-weighting filter for the given frame rate.
+///
+/// The first stage is a high shelf that models the acoustic effect of
+/// the head. The second stage is the "RLB" highpass. The coefficients
+/// in BS.1770 are only given at 48000 hz, so we derive them here from
+/// the analog prototypes that they were designed from.
+pub fn k_weighting(frame_rate_hz: u32) -> FilterDesign {
+    let fs = f64::from(frame_rate_hz);
+
+    let f0 = 1_681.974_450_955_533;
+    let gain_db = 3.999_843_853_973_347;
+    let q = 0.707_175_236_955_419_6;
+    let k = (PI * f0 / fs).tan();
+    let vh = 10.0_f64.powf(gain_db / 20.0);
+    let vb = vh.powf(0.499_666_774_154_541_6);
+    let shelf = Biquad::new(
+        vh + vb * k / q + k * k,
+        2.0 * (k * k - vh),
+        vh - vb * k / q + k * k,
+        1.0 + k / q + k * k,
+        2.0 * (k * k - 1.0),
+        1.0 - k / q + k * k,
+    );
+
+    let f0 = 38.135_470_876_024_44;
+    let q = 0.500_327_037_323_877_3;
+    let k = (PI * f0 / fs).tan();
+    let a0 = 1.0 + k / q + k * k;
+    // The numerator is deliberately left unnormalized, as in the standard.
+    let highpass = Biquad {
+        b0: 1.0,
+        b1: -2.0,
+        b2: 1.0,
+        a1: 2.0 * (k * k - 1.0) / a0,
+        a2: (1.0 - k / q + k * k) / a0,
+    };
+
+    FilterDesign::new(vec![shelf, highpass])
+}
+
+/// Returns the weight that each channel contributes to the loudness.
+///
+/// Five and six channel audio is assumed to be in the
+/// L, R, C, (LFE,) Ls, Rs order. The surround channels are
+/// weighted up and the LFE channel is ignored. Every other
+/// layout gives every channel equal weight.
+pub fn channel_weights(num_channels: u16) -> Vec<f64> {
+    match num_channels {
+        5 => vec![1.0, 1.0, 1.0, 1.41, 1.41],
+        6 => vec![1.0, 1.0, 1.0, 0.0, 1.41, 1.41],
+        n => vec![1.0; n as usize],
+    }
+}
+
+/// Checks that audio with this frame rate and number of channels can be measured.
+fn check_format(frame_rate_hz: u32, num_channels: u16) -> Result<(), Error> {
+    if num_channels == 0 {
+        return Err(Error::WrongNumChannels(1, num_channels));
+    }
+    if frame_rate_hz < LOUDNESS_MIN_FRAME_RATE_HZ {
+        return Err(Error::FrameRateTooLow(
+            frame_rate_hz,
+            LOUDNESS_MIN_FRAME_RATE_HZ,
+        ));
+    }
+    Ok(())
+}
+
+/// Accumulates the K-weighted, channel-weighted energy of a stream of
+/// interleaved samples over consecutive 100 millisecond steps.
+///
+/// Gating blocks and the momentary and short-term windows are all made
+/// from whole steps, so one pass over the audio is enough for all of them.
+/// A partial step at the end of the audio is dropped.
+#[derive(Clone, Debug)]
+struct GatingSteps {
+    step_frames: usize,
+    weights: Vec<f64>,
+    filter: FilterState,
+    frames_in_step: usize,
+    current_sum: f64,
+    step_sums: Vec<f64>,
+}
+
+impl GatingSteps {
+    fn new(frame_rate_hz: u32, num_channels: u16) -> Self {
+        let steps_per_second = 1000 / SERIES_INTERVAL_MILLISECONDS;
+        let step_frames = (frame_rate_hz as usize).div_ceil(steps_per_second).max(1);
+        Self {
+            step_frames,
+            weights: channel_weights(num_channels),
+            filter: FilterState::new(&k_weighting(frame_rate_hz), num_channels),
+            frames_in_step: 0,
+            current_sum: 0.0,
+            step_sums: Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, channel_idx: usize, sample: f32) {
+        let y = f64::from(self.filter.process(channel_idx, sample));
+        self.current_sum += self.weights[channel_idx] * y * y;
+        if channel_idx + 1 == self.weights.len() {
+            self.frames_in_step += 1;
+            if self.frames_in_step == self.step_frames {
+                self.step_sums.push(self.current_sum);
+                self.frames_in_step = 0;
+                self.current_sum = 0.0;
+            }
+        }
+    }
+
+    /// Returns the mean energy of every window that is `window_steps` steps
+    /// long, advancing one step at a time.
+    #[allow(clippy::cast_precision_loss)]
+    fn window_energies(&self, window_steps: usize) -> Vec<f64> {
+        let window_frames = (window_steps * self.step_frames) as f64;
+        self.step_sums
+            .windows(window_steps)
+            .map(|window| window.iter().sum::<f64>() / window_frames)
+            .collect()
+    }
+}
+
+/// Returns the windowed-sinc interpolation filter for upsampling by
+/// `factor`, arranged so that `coefficients[phase][tap]` is applied to
+/// the input sample `tap` samples before the current one.
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+fn interpolation_filter(factor: usize) -> Vec<Vec<f32>> {
+    let len = factor * TRUE_PEAK_TAPS_PER_PHASE;
+    let center = (len - 1) as f64 / 2.0;
+    let mut coefficients = vec![vec![0.0_f32; TRUE_PEAK_TAPS_PER_PHASE]; factor];
+    for n in 0..len {
+        let x = (n as f64 - center) / factor as f64;
+        let sinc = if x == 0.0 {
+            1.0
+        } else {
+            (PI * x).sin() / (PI * x)
+        };
+        let window = 0.5 - 0.5 * (2.0 * PI * (n as f64 + 1.0) / (len as f64 + 1.0)).cos();
+        coefficients[n % factor][n / factor] = (sinc * window) as f32;
+    }
+    coefficients
+}
+
+/// Tracks the sample peak and the true peak of every channel.
+///
+/// Audio is upsampled by a power of two until it is at least 176400 hz,
+/// and the true peak is taken from the upsampled signal. The true peak
+/// is never lower than the sample peak.
+#[derive(Clone, Debug)]
+struct PeakDetector {
+    coefficients: Vec<Vec<f32>>,
+    /// The most recent input samples of each channel, newest first.
+    histories: Vec<Vec<f32>>,
+    sample_peaks: Vec<f32>,
+    true_peaks: Vec<f32>,
+}
+
+impl PeakDetector {
+    /// The frame rate must already have been checked with [`check_format()`],
+    /// so that the upsampling factor is bounded.
+    fn new(frame_rate_hz: u32, num_channels: u16) -> Self {
+        let mut factor = 1_u32;
+        while frame_rate_hz.saturating_mul(factor) < TRUE_PEAK_MIN_FRAME_RATE_HZ {
+            factor *= 2;
+        }
+        let coefficients = if factor == 1 {
+            Vec::new()
+        } else {
+            interpolation_filter(factor as usize)
+        };
+        let num_channels = num_channels as usize;
+        Self {
+            coefficients,
+            histories: vec![vec![0.0; TRUE_PEAK_TAPS_PER_PHASE]; num_channels],
+            sample_peaks: vec![0.0; num_channels],
+            true_peaks: vec![0.0; num_channels],
+        }
+    }
+
+    /// Feeds one sample into the interpolator for `channel_idx`
+    /// and updates that channel's true peak.
+    #[inline]
+    fn interpolate(&mut self, channel_idx: usize, sample: f32) {
+        let history = &mut self.histories[channel_idx];
+        history.rotate_right(1);
+        history[0] = sample;
+        let true_peak = &mut self.true_peaks[channel_idx];
+        for phase in &self.coefficients {
+            let value: f32 = phase.iter().zip(history.iter()).map(|(c, x)| c * x).sum();
+            *true_peak = true_peak.max(value.abs());
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, channel_idx: usize, sample: f32) {
+        let sample_peak = &mut self.sample_peaks[channel_idx];
+        *sample_peak = sample_peak.max(sample.abs());
+        let true_peak = &mut self.true_peaks[channel_idx];
+        *true_peak = true_peak.max(sample.abs());
+        if !self.coefficients.is_empty() {
+            self.interpolate(channel_idx, sample);
+        }
+    }
+
+    /// Flushes the interpolator so that the tail after the last sample
+    /// is measured, and returns the sample peaks and true peaks.
+    fn finish(mut self) -> (Vec<f32>, Vec<f32>) {
+        if !self.coefficients.is_empty() {
+            for channel_idx in 0..self.histories.len() {
+                for _ in 0..TRUE_PEAK_TAPS_PER_PHASE {
+                    self.interpolate(channel_idx, 0.0);
+                }
+            }
+        }
+        (self.sample_peaks, self.true_peaks)
+    }
+}
+
+/// Converts a mean energy to LUFS.
+#[inline]
+#[allow(clippy::cast_possible_truncation)]
+fn energy_to_lufs(energy: f64) -> f32 {
+    (LOUDNESS_OFFSET + 10.0 * energy.log10()) as f32
+}
+
+/// Converts LUFS to a mean energy.
+#[inline]
+fn lufs_to_energy(lufs: f32) -> f64 {
+    10.0_f64.powf((f64::from(lufs) - LOUDNESS_OFFSET) / 10.0)
+}
+
+/// Returns the mean of the energies above `threshold`, or `None`
+/// if there are none.
+#[allow(clippy::cast_precision_loss)]
+fn gated_mean(energies: &[f64], threshold: f64) -> Option<f64> {
+    let (sum, count) = energies
+        .iter()
+        .filter(|&&energy| energy > threshold)
+        .fold((0.0, 0_usize), |(sum, count), energy| {
+            (sum + energy, count + 1)
+        });
+    if count == 0 {
+        None
+    } else {
+        Some(sum / count as f64)
+    }
+}
+
+/// Returns the integrated loudness of already-measured gating blocks.
+fn gated_loudness(block_energies: &[f64]) -> Option<f32> {
+    let absolute_mean = gated_mean(block_energies, lufs_to_energy(ABSOLUTE_GATE_LUFS))?;
+    let relative_gate = energy_to_lufs(absolute_mean) + RELATIVE_GATE_LU;
+    gated_mean(block_energies, lufs_to_energy(relative_gate)).map(energy_to_lufs)
+}
+
+/// Returns the EBU Tech 3342 loudness range of already-measured
+/// short-term windows, which is the spread between the 10th and 95th
+/// percentiles of the gated short-term loudness.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn loudness_range(short_term_energies: &[f64]) -> Option<f32> {
+    let absolute_gate = lufs_to_energy(ABSOLUTE_GATE_LUFS);
+    let absolute_mean = gated_mean(short_term_energies, absolute_gate)?;
+    let relative_gate =
+        lufs_to_energy(energy_to_lufs(absolute_mean) + LOUDNESS_RANGE_RELATIVE_GATE_LU);
+    let threshold = relative_gate.max(absolute_gate);
+    let mut levels: Vec<f32> = short_term_energies
+        .iter()
+        .filter(|&&energy| energy > threshold)
+        .map(|&energy| energy_to_lufs(energy))
+        .collect();
+    if levels.is_empty() {
+        return None;
+    }
+    levels.sort_by(f32::total_cmp);
+    let percentile = |p: f64| levels[((levels.len() - 1) as f64 * p).round() as usize];
+    Some(percentile(0.95) - percentile(0.10))
+}
+
+/// Measures the integrated loudness of a waveform in LUFS.
+///
+/// This returns `None` if the waveform is shorter than a single
+/// 400 millisecond gating block or if it is entirely below the
+/// -70 LUFS absolute gate.
+///
+/// # Errors
+/// Returns [`Error::FrameRateTooLow`] if the frame rate of the waveform
+/// is below [`LOUDNESS_MIN_FRAME_RATE_HZ`].
+///
+/// # Examples
+/// ```
+/// use babycat::Waveform;
+/// use babycat::loudness::integrated_loudness;
+///
+/// // A full-scale 997 hz sine in one channel measures -3.01 LUFS.
+/// let samples: Vec<f32> = (0..48000)
+///     .map(|i| (2.0 * std::f32::consts::PI * 997.0 * i as f32 / 48000.0).sin())
+///     .collect();
+/// let waveform = Waveform::from_interleaved_samples(48000, 1, &samples);
+/// let loudness = integrated_loudness(&waveform).unwrap().unwrap();
+/// assert!((loudness + 3.01).abs() < 0.05);
+///
+/// // Silence has no loudness.
+/// let silence = Waveform::from_frames_of_silence(48000, 1, 48000);
+/// assert_eq!(integrated_loudness(&silence), Ok(None));
+/// ```
+pub fn integrated_loudness(waveform: &Waveform) -> Result<Option<f32>, Error> {
+    check_format(waveform.frame_rate_hz(), waveform.num_channels())?;
+    let num_channels = waveform.num_channels() as usize;
+    let mut steps = GatingSteps::new(waveform.frame_rate_hz(), waveform.num_channels());
+    for frame in waveform.to_interleaved_samples().chunks_exact(num_channels) {
+        for (channel_idx, sample) in frame.iter().enumerate() {
+            steps.push(channel_idx, *sample);
+        }
+    }
+    Ok(gated_loudness(&steps.window_energies(MOMENTARY_STEPS)))
+}
+
+/// Returns the largest absolute sample value in each channel.
+pub fn sample_peaks(waveform: &Waveform) -> Vec<f32> {
+    let num_channels = waveform.num_channels() as usize;
+    let mut peaks = vec![0.0_f32; num_channels];
+    for frame in waveform.to_interleaved_samples().chunks_exact(num_channels) {
+        for (peak, sample) in peaks.iter_mut().zip(frame) {
+            *peak = peak.max(sample.abs());
+        }
+    }
+    peaks
+}
+
+/// Returns the largest absolute sample value across all channels, in dBFS.
+///
+/// Silence is `f32::NEG_INFINITY`.
+pub fn sample_peak_dbfs(waveform: &Waveform) -> f32 {
+    ratio_to_dbfs(sample_peaks(waveform).into_iter().fold(0.0, f32::max))
+}
+
+/// Returns the true peak of each channel as a linear amplitude.
+///
+/// Audio below 176400 hz is upsampled by a power of two until it
+/// is at least 176400 hz, and the peak is taken from the upsampled
+/// signal. The true peak is never lower than the sample peak.
+///
+/// # Errors
+/// Returns [`Error::FrameRateTooLow`] if the frame rate of the waveform
+/// is below [`LOUDNESS_MIN_FRAME_RATE_HZ`].
+pub fn true_peaks(waveform: &Waveform) -> Result<Vec<f32>, Error> {
+    check_format(waveform.frame_rate_hz(), waveform.num_channels())?;
+    let num_channels = waveform.num_channels() as usize;
+    let mut peaks = PeakDetector::new(waveform.frame_rate_hz(), waveform.num_channels());
+    for frame in waveform.to_interleaved_samples().chunks_exact(num_channels) {
+        for (channel_idx, sample) in frame.iter().enumerate() {
+            peaks.push(channel_idx, *sample);
+        }
+    }
+    Ok(peaks.finish().1)
+}
+
+/// Returns the largest true peak across all channels, in dBTP.
+///
+/// Silence is `f32::NEG_INFINITY`.
+///
+/// # Errors
+/// Returns [`Error::FrameRateTooLow`] if the frame rate of the waveform
+/// is below [`LOUDNESS_MIN_FRAME_RATE_HZ`].
+pub fn true_peak_dbtp(waveform: &Waveform) -> Result<f32, Error> {
+    Ok(ratio_to_dbfs(
+        true_peaks(waveform)?.into_iter().fold(0.0, f32::max),
+    ))
+}
+
+/// Measures loudness and peaks incrementally, one interleaved sample at a time.
+///
+/// Call [`finish()`](Self::finish) once the audio has ended to get a
+/// [`LoudnessReport`]. Memory use only grows by one number for every
+/// 100 milliseconds of audio, so arbitrarily long streams can be measured.
+#[derive(Clone, Debug)]
+pub struct LoudnessMeter {
+    frame_rate_hz: u32,
+    num_channels: u16,
+    num_samples: usize,
+    channel_idx: usize,
+    steps: GatingSteps,
+    peaks: PeakDetector,
+}
+
+impl LoudnessMeter {
+    /// Starts measuring audio with `num_channels` channels at `frame_rate_hz`.
+    ///
+    /// # Errors
+    /// Returns [`Error::WrongNumChannels`] if `num_channels` is zero, and
+    /// [`Error::FrameRateTooLow`] if `frame_rate_hz` is below
+    /// [`LOUDNESS_MIN_FRAME_RATE_HZ`].
+    pub fn new(frame_rate_hz: u32, num_channels: u16) -> Result<Self, Error> {
+        check_format(frame_rate_hz, num_channels)?;
+        Ok(Self {
+            frame_rate_hz,
+            num_channels,
+            num_samples: 0,
+            channel_idx: 0,
+            steps: GatingSteps::new(frame_rate_hz, num_channels),
+            peaks: PeakDetector::new(frame_rate_hz, num_channels),
+        })
+    }
+
+    /// Measures the next interleaved sample.
+    #[inline]
+    pub fn push_sample(&mut self, sample: f32) {
+        self.steps.push(self.channel_idx, sample);
+        self.peaks.push(self.channel_idx, sample);
+        self.num_samples += 1;
+        self.channel_idx += 1;
+        if self.channel_idx == self.num_channels as usize {
+            self.channel_idx = 0;
+        }
+    }
+
+    /// Measures a slice of interleaved samples.
+    pub fn push_interleaved_samples(&mut self, interleaved_samples: &[f32]) {
+        for sample in interleaved_samples {
+            self.push_sample(*sample);
+        }
+    }
+
+    /// Finishes measuring and returns the report.
+    pub fn finish(self) -> LoudnessReport {
+        let floor = |level: f32| level.max(MIN_REPORTED_LEVEL);
+        let momentary = self.steps.window_energies(MOMENTARY_STEPS);
+        let short_term = self.steps.window_energies(SHORT_TERM_STEPS);
+        let (sample_peaks, true_peaks) = self.peaks.finish();
+        LoudnessReport {
+            frame_rate_hz: self.frame_rate_hz,
+            num_channels: self.num_channels,
+            num_frames: self.num_samples / self.num_channels as usize,
+            integrated_lufs: gated_loudness(&momentary),
+            loudness_range_lu: loudness_range(&short_term),
+            momentary_lufs: momentary
+                .iter()
+                .map(|&energy| floor(energy_to_lufs(energy)))
+                .collect(),
+            short_term_lufs: short_term
+                .iter()
+                .map(|&energy| floor(energy_to_lufs(energy)))
+                .collect(),
+            sample_peaks_dbfs: sample_peaks
+                .into_iter()
+                .map(|peak| floor(ratio_to_dbfs(peak)))
+                .collect(),
+            true_peaks_dbtp: true_peaks
+                .into_iter()
+                .map(|peak| floor(ratio_to_dbfs(peak)))
+                .collect(),
+        }
+    }
+}
+
+/// An EBU R128 loudness and peak report.
+///
+/// Levels are floored at [`MIN_REPORTED_LEVEL`], so silence is
+/// reported as -144 rather than negative infinity.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LoudnessReport {
+    /// The frame rate of the measured audio.
+    pub frame_rate_hz: u32,
+    /// The number of channels in the measured audio.
+    pub num_channels: u16,
+    /// The number of frames in the measured audio.
+    pub num_frames: usize,
+    /// The gated integrated loudness in LUFS, or `None` if the audio
+    /// is shorter than 400 milliseconds or entirely below -70 LUFS.
+    pub integrated_lufs: Option<f32>,
+    /// The loudness range in LU, or `None` if the audio is shorter
+    /// than 3 seconds or entirely below -70 LUFS.
+    pub loudness_range_lu: Option<f32>,
+    /// The loudness of every 400 millisecond window, in LUFS.
+    /// The `i`-th value covers the window that starts `i * 100` milliseconds in.
+    pub momentary_lufs: Vec<f32>,
+    /// The loudness of every 3 second window, in LUFS.
+    /// The `i`-th value covers the window that starts `i * 100` milliseconds in.
+    pub short_term_lufs: Vec<f32>,
+    /// The largest absolute sample value in each channel, in dBFS.
+    pub sample_peaks_dbfs: Vec<f32>,
+    /// The oversampled true peak of each channel, in dBTP.
+    pub true_peaks_dbtp: Vec<f32>,
+}
+
+impl LoudnessReport {
+    /// Measures a waveform that is already in memory.
+    ///
+    /// # Errors
+    /// Returns [`Error::FrameRateTooLow`] if the frame rate of the waveform
+    /// is below [`LOUDNESS_MIN_FRAME_RATE_HZ`].
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::Waveform;
+    /// use babycat::loudness::LoudnessReport;
+    ///
+    /// let samples: Vec<f32> = (0..4 * 48000)
+    ///     .map(|i| 0.1 * (2.0 * std::f32::consts::PI * 997.0 * i as f32 / 48000.0).sin())
+    ///     .flat_map(|s| vec![s, s])
+    ///     .collect();
+    /// let waveform = Waveform::from_interleaved_samples(48000, 2, &samples);
+    /// let report = LoudnessReport::from_waveform(&waveform).unwrap();
+    ///
+    /// assert!((report.integrated_lufs.unwrap() + 20.0).abs() < 0.05);
+    /// assert!(report.loudness_range_lu.unwrap() < 0.1);
+    /// assert_eq!(report.momentary_lufs.len(), 37);
+    /// assert_eq!(report.short_term_lufs.len(), 11);
+    /// assert!((report.sample_peaks_dbfs[0] + 20.0).abs() < 0.01);
+    /// ```
+    pub fn from_waveform(waveform: &Waveform) -> Result<Self, Error> {
+        let mut meter = LoudnessMeter::new(waveform.frame_rate_hz(), waveform.num_channels())?;
+        meter.push_interleaved_samples(waveform.to_interleaved_samples());
+        Ok(meter.finish())
+    }
+
+    /// Measures any [`Source`] in a single streaming pass, without
+    /// holding all of its samples in memory.
+    ///
+    /// # Errors
+    /// Returns [`Error::WrongNumChannels`] if the source has no channels, and
+    /// [`Error::FrameRateTooLow`] if its frame rate is below
+    /// [`LOUDNESS_MIN_FRAME_RATE_HZ`].
+    pub fn from_source<S: Source>(source: S) -> Result<Self, Error> {
+        let mut meter = LoudnessMeter::new(source.frame_rate_hz(), source.num_channels())?;
+        for sample in source {
+            meter.push_sample(sample);
+        }
+        Ok(meter.finish())
+    }
+}
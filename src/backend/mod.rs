@@ -4,13 +4,29 @@ pub mod batch;
 #[doc(hidden)]
 pub mod resample;
 
+pub mod align;
 pub mod assertions;
+pub mod beat;
 pub mod build_info;
 pub mod constants;
 pub mod decoder;
 pub mod display;
+pub mod dtmf;
+pub mod envelope;
+pub mod filter;
+pub mod fingerprint;
+pub mod key;
+pub mod loudness;
+pub mod onset;
+pub mod peaks;
+pub mod pitch;
+pub mod render;
+pub mod silence;
 pub mod source;
+pub mod spectral;
+pub mod stats;
 pub mod units;
+pub mod vad;
 
 mod batch_args;
 mod errors;
@@ -0,0 +1,557 @@
+//! Onset strength and onset detection.
+//!
+//! An onset is the start of a musical event, such as a drum hit or a new
+//! note. Onsets are found in two steps:
+//!
+//! 1. An **onset detection function** turns the audio into an envelope
+//!    with one value per time step, which peaks at the onsets. Choose one
+//!    with [`OnsetArgs.onset_function`](OnsetArgs#structfield.onset_function).
+//!    The default spectral flux is close to the [`onset_strength()`]
+//!    envelope, which is the starting point for tempo estimation and beat
+//!    tracking in [`beat`][crate::beat].
+//! 2. **Peak picking** keeps the peaks of the envelope that stand out
+//!    from their surroundings, and that are not too close to the previous onset.
+
+use crate::backend::constants::{
+    DEFAULT_FFT_SIZE, DEFAULT_HOP_LENGTH, DEFAULT_ONSET_FUNCTION, ONSET_FUNCTION_COMPLEX_DOMAIN,
+    ONSET_FUNCTION_HIGH_FREQUENCY_CONTENT, ONSET_FUNCTION_SPECTRAL_FLUX,
+};
+use crate::backend::spectral::{mel_spectrogram, Complex, MelArgs, Stft, StftArgs};
+use crate::backend::units::milliseconds_to_frames;
+use crate::backend::Error;
+use crate::backend::Signal;
+use crate::backend::Waveform;
+
+/// Mel bands more than this many decibels below the loudest value are
+/// raised to that floor before measuring how much louder they get.
+const ONSET_TOP_DB: f32 = 80.0;
+
+/// Settings for onset detection.
+///
+/// The peak picking windows are given in milliseconds and rounded to the
+/// nearest time step. The defaults match the defaults of most audio libraries.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OnsetArgs {
+    /// The number of frames in each FFT.
+    pub fft_size: usize,
+    /// The number of frames between consecutive values of the envelope.
+    /// Onsets are placed on multiples of this.
+    pub hop_length: usize,
+    /// The onset detection function. Use one of the `ONSET_FUNCTION_*`
+    /// values in [`constants`][crate::constants].
+    pub onset_function: u32,
+    /// How far above the local average a peak has to be to count as an
+    /// onset. The envelope is scaled to between 0 and 1 first.
+    pub threshold: f32,
+    /// The smallest envelope value, in decibels, that can be an onset.
+    /// This keeps steady sounds from turning small wobbles into onsets.
+    pub min_strength_db: f32,
+    /// A peak has to be the largest value from this long before it...
+    pub pre_max_milliseconds: usize,
+    /// ...until this long after it.
+    pub post_max_milliseconds: usize,
+    /// The local average starts this long before each peak...
+    pub pre_avg_milliseconds: usize,
+    /// ...and ends this long after it.
+    pub post_avg_milliseconds: usize,
+    /// The shortest time between two onsets.
+    pub wait_milliseconds: usize,
+    /// If `true`, every onset is moved back to the last local minimum
+    /// of the envelope before it. This is where the sound starts to get
+    /// louder, which is a better place to cut the audio.
+    pub backtrack: bool,
+}
+
+impl Default for OnsetArgs {
+    fn default() -> Self {
+        OnsetArgs {
+            fft_size: DEFAULT_FFT_SIZE,
+            hop_length: DEFAULT_HOP_LENGTH,
+            onset_function: DEFAULT_ONSET_FUNCTION,
+            threshold: 0.07,
+            min_strength_db: 1.0,
+            pre_max_milliseconds: 30,
+            post_max_milliseconds: 0,
+            pre_avg_milliseconds: 100,
+            post_avg_milliseconds: 100,
+            wait_milliseconds: 30,
+            backtrack: false,
+        }
+    }
+}
+
+impl OnsetArgs {
+    /// Set the [`fft_size`](#structfield.fft_size) field.
+    #[must_use]
+    pub fn set_fft_size(&mut self, fft_size: usize) -> Self {
+        self.fft_size = fft_size;
+        *self
+    }
+
+    /// Set the [`hop_length`](#structfield.hop_length) field.
+    #[must_use]
+    pub fn set_hop_length(&mut self, hop_length: usize) -> Self {
+        self.hop_length = hop_length;
+        *self
+    }
+
+    /// Set the [`onset_function`](#structfield.onset_function) field.
+    #[must_use]
+    pub fn set_onset_function(&mut self, onset_function: u32) -> Self {
+        self.onset_function = onset_function;
+        *self
+    }
+
+    /// Set the [`threshold`](#structfield.threshold) field.
+    #[must_use]
+    pub fn set_threshold(&mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        *self
+    }
+
+    /// Set the [`min_strength_db`](#structfield.min_strength_db) field.
+    #[must_use]
+    pub fn set_min_strength_db(&mut self, min_strength_db: f32) -> Self {
+        self.min_strength_db = min_strength_db;
+        *self
+    }
+
+    /// Set the [`pre_max_milliseconds`](#structfield.pre_max_milliseconds) field.
+    #[must_use]
+    pub fn set_pre_max_milliseconds(&mut self, pre_max_milliseconds: usize) -> Self {
+        self.pre_max_milliseconds = pre_max_milliseconds;
+        *self
+    }
+
+    /// Set the [`post_max_milliseconds`](#structfield.post_max_milliseconds) field.
+    #[must_use]
+    pub fn set_post_max_milliseconds(&mut self, post_max_milliseconds: usize) -> Self {
+        self.post_max_milliseconds = post_max_milliseconds;
+        *self
+    }
+
+    /// Set the [`pre_avg_milliseconds`](#structfield.pre_avg_milliseconds) field.
+    #[must_use]
+    pub fn set_pre_avg_milliseconds(&mut self, pre_avg_milliseconds: usize) -> Self {
+        self.pre_avg_milliseconds = pre_avg_milliseconds;
+        *self
+    }
+
+    /// Set the [`post_avg_milliseconds`](#structfield.post_avg_milliseconds) field.
+    #[must_use]
+    pub fn set_post_avg_milliseconds(&mut self, post_avg_milliseconds: usize) -> Self {
+        self.post_avg_milliseconds = post_avg_milliseconds;
+        *self
+    }
+
+    /// Set the [`wait_milliseconds`](#structfield.wait_milliseconds) field.
+    #[must_use]
+    pub fn set_wait_milliseconds(&mut self, wait_milliseconds: usize) -> Self {
+        self.wait_milliseconds = wait_milliseconds;
+        *self
+    }
+
+    /// Set the [`backtrack`](#structfield.backtrack) field.
+    #[must_use]
+    pub fn set_backtrack(&mut self, backtrack: bool) -> Self {
+        self.backtrack = backtrack;
+        *self
+    }
+
+    /// Returns the settings for the short-time Fourier transform.
+    fn stft_args(&self) -> StftArgs {
+        StftArgs::default()
+            .set_fft_size(self.fft_size)
+            .set_hop_length(self.hop_length)
+    }
+}
+
+/// Computes the onset strength envelope of a waveform, with one value per time step.
+///
+/// The channels are averaged together, and a mel spectrogram with the
+/// default [`MelArgs`] is taken of the result and converted to decibels.
+/// The onset strength of each time step is how much louder the mel bands
+/// got since the previous time step, averaged over every band. Bands that
+/// got quieter count as zero.
+///
+/// The `t`-th value belongs to frame `t * hop_length`. When
+/// [`StftArgs.center`](StftArgs#structfield.center) is `true`, every
+/// window hears an onset before its center reaches it, so the envelope
+/// is delayed by a quarter of the FFT size to line its peaks up with the onsets.
+///
+/// # Errors
+/// Returns the same errors as [`Stft::from_waveform()`].
+///
+/// # Examples
+/// ```
+/// use babycat::onset::onset_strength;
+/// use babycat::spectral::StftArgs;
+/// use babycat::Waveform;
+///
+/// // A click every half second.
+/// let samples: Vec<f32> = (0..22050 * 2)
+///     .map(|i| if i % 11025 < 64 { 0.5 } else { 0.0 })
+///     .collect();
+/// let waveform = Waveform::from_interleaved_samples(22050, 1, &samples);
+///
+/// let envelope = onset_strength(&waveform, StftArgs::default()).unwrap();
+/// assert_eq!(envelope.len(), 1 + samples.len() / 512);
+/// // The strongest onset is within a time step of a click.
+/// let loudest = (0..envelope.len())
+///     .max_by(|&a, &b| envelope[a].total_cmp(&envelope[b]))
+///     .unwrap();
+/// let offset = loudest * 512 % 11025;
+/// assert!(offset <= 512 || offset >= 11025 - 512);
+/// ```
+#[allow(clippy::cast_precision_loss)]
+pub fn onset_strength(waveform: &Waveform, args: StftArgs) -> Result<Vec<f32>, Error> {
+    let stft = Stft::from_waveform(&waveform.to_mono(), args)?;
+    let mel = mel_spectrogram(&stft, MelArgs::default())?.to_db(Some(ONSET_TOP_DB));
+    let num_time_steps = mel.num_time_steps();
+    let mut delay = 1;
+    if args.center {
+        delay += (args.fft_size / 4 + args.hop_length / 2) / args.hop_length;
+    }
+    let mut envelope = vec![0.0_f32; num_time_steps];
+    if num_time_steps <= delay {
+        return Ok(envelope);
+    }
+    for row in mel.as_slice().chunks_exact(num_time_steps) {
+        for (out, pair) in envelope[delay..].iter_mut().zip(row.windows(2)) {
+            *out += (pair[1] - pair[0]).max(0.0);
+        }
+    }
+    let num_mels = mel.num_features() as f32;
+    for value in &mut envelope {
+        *value /= num_mels;
+    }
+    Ok(envelope)
+}
+
+/// Delays an envelope whose `t`-th value measures the change from time
+/// step `t - 1` to `t`, so that its peaks line up with the onsets.
+///
+/// Time steps whose windows run past the end of the audio hear the
+/// padding, which can sound like an onset, so their changes are dropped.
+fn align_with_onsets(mut changes: Vec<f32>, args: StftArgs, num_frames: usize) -> Vec<f32> {
+    if !args.center {
+        return changes;
+    }
+    let half_window = args.fft_size / 2;
+    let first_padded = match num_frames.checked_sub(half_window) {
+        Some(last_unpadded_frame) => last_unpadded_frame / args.hop_length + 1,
+        None => 0,
+    };
+    if let Some(padded) = changes.get_mut(first_padded..) {
+        padded.fill(0.0);
+    }
+    let delay = std::cmp::min(
+        (args.fft_size / 4 + args.hop_length / 2) / args.hop_length,
+        changes.len(),
+    );
+    changes.rotate_right(delay);
+    changes[..delay].fill(0.0);
+    changes
+}
+
+/// Returns the spectral magnitudes of a single-channel transform,
+/// one time step at a time.
+fn magnitudes_by_time_step(stft: &Stft) -> Vec<Vec<f32>> {
+    let num_time_steps = stft.num_time_steps();
+    let mut columns = vec![Vec::with_capacity(stft.num_bins()); num_time_steps];
+    for row in stft.channel(0).chunks_exact(num_time_steps.max(1)) {
+        for (column, value) in columns.iter_mut().zip(row) {
+            column.push(value.norm());
+        }
+    }
+    columns
+}
+
+/// Converts power values to decibels, raising values more than
+/// [`ONSET_TOP_DB`] below the loudest one to that floor.
+fn to_db(values: &[f32]) -> Vec<f32> {
+    let max = values.iter().copied().fold(0.0_f32, f32::max);
+    let floor = max * 10.0_f32.powf(-ONSET_TOP_DB / 10.0);
+    values
+        .iter()
+        .map(|value| 10.0 * value.max(floor).max(f32::MIN_POSITIVE).log10())
+        .collect()
+}
+
+/// Measures how many decibels the mel bands grow from one time step to
+/// the next, like [`onset_strength()`], averaged over every band.
+///
+/// Unlike [`onset_strength()`], time steps that are quieter overall than
+/// the one before count as zero, so that the click at the abrupt end of
+/// a sound is not mistaken for an onset.
+#[allow(clippy::cast_precision_loss)]
+fn spectral_flux(stft: &Stft) -> Result<Vec<f32>, Error> {
+    let mel = mel_spectrogram(stft, MelArgs::default())?;
+    let num_time_steps = mel.num_time_steps();
+    let mut power = vec![0.0_f32; num_time_steps];
+    for row in mel.as_slice().chunks_exact(num_time_steps.max(1)) {
+        for (total, value) in power.iter_mut().zip(row) {
+            *total += value;
+        }
+    }
+    let mel = mel.to_db(Some(ONSET_TOP_DB));
+    let mut changes = vec![0.0_f32; num_time_steps];
+    if num_time_steps > 1 {
+        for row in mel.as_slice().chunks_exact(num_time_steps) {
+            for (out, pair) in changes[1..].iter_mut().zip(row.windows(2)) {
+                *out += (pair[1] - pair[0]).max(0.0);
+            }
+        }
+    }
+    let num_mels = mel.num_features() as f32;
+    for (t, value) in changes.iter_mut().enumerate() {
+        if t > 0 && power[t] < power[t - 1] {
+            *value = 0.0;
+        } else {
+            *value /= num_mels;
+        }
+    }
+    Ok(changes)
+}
+
+/// Measures how many decibels the energy of each time step, with every
+/// bin weighted by its index, grows from one time step to the next.
+#[allow(clippy::cast_precision_loss)]
+fn high_frequency_content(stft: &Stft) -> Vec<f32> {
+    let content: Vec<f32> = magnitudes_by_time_step(stft)
+        .iter()
+        .map(|column| {
+            column
+                .iter()
+                .enumerate()
+                .map(|(bin_idx, magnitude)| bin_idx as f32 * magnitude * magnitude)
+                .sum()
+        })
+        .collect();
+    let content = to_db(&content);
+    let mut changes = vec![0.0_f32; content.len()];
+    for (out, pair) in changes.iter_mut().skip(1).zip(content.windows(2)) {
+        *out = (pair[1] - pair[0]).max(0.0);
+    }
+    changes
+}
+
+/// Measures how far each time step strays from a prediction made from
+/// the two before it, which keeps the magnitude of every bin and
+/// advances its phase at the same rate. Bins that got quieter are left
+/// out, so that the ends of notes are not mistaken for onsets.
+///
+/// The distance is compared to the magnitude of the previous time step
+/// and given in decibels, so that it does not depend on how loud the audio is.
+fn complex_domain(stft: &Stft) -> Vec<f32> {
+    let num_time_steps = stft.num_time_steps();
+    let mut deviations = vec![0.0_f32; num_time_steps];
+    let mut magnitudes = vec![0.0_f32; num_time_steps];
+    if num_time_steps < 3 {
+        return deviations;
+    }
+    for row in stft.channel(0).chunks_exact(num_time_steps) {
+        for (total, value) in magnitudes.iter_mut().zip(row) {
+            *total += value.norm();
+        }
+        for (t, out) in deviations.iter_mut().enumerate().skip(2) {
+            let current = row[t];
+            let previous = row[t - 1];
+            if current.norm() < previous.norm() {
+                continue;
+            }
+            let phase = 2.0 * previous.arg() - row[t - 2].arg();
+            let predicted = Complex::from_polar(previous.norm(), phase);
+            *out += (current - predicted).norm();
+        }
+    }
+    let floor =
+        magnitudes.iter().copied().fold(0.0_f32, f32::max) * 10.0_f32.powf(-ONSET_TOP_DB / 20.0);
+    let mut changes = vec![0.0_f32; num_time_steps];
+    for t in 2..num_time_steps {
+        let reference = magnitudes[t - 1].max(floor).max(f32::MIN_POSITIVE);
+        changes[t] = 20.0 * (1.0 + deviations[t] / reference).log10();
+    }
+    changes
+}
+
+/// Computes an onset detection function of a waveform, with one value
+/// per time step.
+///
+/// The channels are averaged together first. Every detection function
+/// measures change in decibels, so the envelope does not depend on how
+/// loud the audio is. The `t`-th value belongs to frame `t * hop_length`,
+/// and like [`onset_strength()`], the envelope is delayed so that its
+/// peaks line up with the onsets.
+///
+/// An onset at the very start of the audio has nothing to be compared
+/// with, so it may not show up in the envelope.
+///
+/// # Errors
+/// Returns [`Error::FeatureNotCompiled`] if
+/// [`onset_function`](OnsetArgs#structfield.onset_function) is unknown,
+/// and the same errors as [`Stft::from_waveform()`].
+pub fn onset_envelope(waveform: &Waveform, args: OnsetArgs) -> Result<Vec<f32>, Error> {
+    let stft_args = args.stft_args();
+    let changes = match args.onset_function {
+        DEFAULT_ONSET_FUNCTION | ONSET_FUNCTION_SPECTRAL_FLUX => {
+            spectral_flux(&Stft::from_waveform(&waveform.to_mono(), stft_args)?)?
+        }
+        ONSET_FUNCTION_HIGH_FREQUENCY_CONTENT => {
+            high_frequency_content(&Stft::from_waveform(&waveform.to_mono(), stft_args)?)
+        }
+        ONSET_FUNCTION_COMPLEX_DOMAIN => {
+            complex_domain(&Stft::from_waveform(&waveform.to_mono(), stft_args)?)
+        }
+        _ => return Err(Error::FeatureNotCompiled("unknown-onset-function")),
+    };
+    Ok(align_with_onsets(changes, stft_args, waveform.num_frames()))
+}
+
+/// Returns the time steps of the peaks in an onset envelope that count as onsets.
+///
+/// The envelope is scaled to between 0 and 1. A time step is an onset if
+/// it is the largest value in its peak picking window, it is at least
+/// [`threshold`](OnsetArgs#structfield.threshold) above the local
+/// average, its unscaled value is at least
+/// [`min_strength_db`](OnsetArgs#structfield.min_strength_db), and it is more than
+/// [`wait_milliseconds`](OnsetArgs#structfield.wait_milliseconds)
+/// after the previous onset.
+///
+/// # Errors
+/// Returns [`Error::WrongHopLength`] if
+/// [`hop_length`](OnsetArgs#structfield.hop_length) is zero.
+#[allow(clippy::cast_precision_loss)]
+pub fn pick_peaks(
+    envelope: &[f32],
+    frame_rate_hz: u32,
+    args: OnsetArgs,
+) -> Result<Vec<usize>, Error> {
+    if args.hop_length == 0 {
+        return Err(Error::WrongHopLength(args.hop_length));
+    }
+    let min = envelope.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = envelope.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    if max.is_nan() || max <= min {
+        return Ok(Vec::new());
+    }
+    let scaled: Vec<f32> = envelope.iter().map(|v| (v - min) / (max - min)).collect();
+    let num_time_steps = scaled.len();
+    let to_time_steps = |milliseconds: usize| {
+        let num_frames = milliseconds_to_frames(milliseconds, frame_rate_hz);
+        (num_frames + args.hop_length / 2) / args.hop_length
+    };
+    let pre_max = to_time_steps(args.pre_max_milliseconds);
+    let post_max = to_time_steps(args.post_max_milliseconds) + 1;
+    let pre_avg = to_time_steps(args.pre_avg_milliseconds);
+    let post_avg = to_time_steps(args.post_avg_milliseconds) + 1;
+    let wait = to_time_steps(args.wait_milliseconds);
+
+    let mut peaks: Vec<usize> = Vec::new();
+    for (t, value) in scaled.iter().enumerate() {
+        if peaks.last().is_some_and(|&previous| t <= previous + wait) {
+            continue;
+        }
+        let max_window =
+            &scaled[t.saturating_sub(pre_max)..std::cmp::min(t + post_max, num_time_steps)];
+        if max_window.iter().any(|v| v > value) {
+            continue;
+        }
+        let avg_window =
+            &scaled[t.saturating_sub(pre_avg)..std::cmp::min(t + post_avg, num_time_steps)];
+        let average = avg_window.iter().sum::<f32>() / avg_window.len() as f32;
+        if *value >= average + args.threshold && envelope[t] >= args.min_strength_db {
+            peaks.push(t);
+        }
+    }
+    if args.backtrack {
+        backtrack(&scaled, &mut peaks);
+    }
+    Ok(peaks)
+}
+
+/// Moves every peak back to the last local minimum of `envelope` at or before it.
+fn backtrack(envelope: &[f32], peaks: &mut [usize]) {
+    let minima: Vec<usize> = (1..envelope.len().saturating_sub(1))
+        .filter(|&t| envelope[t] <= envelope[t - 1] && envelope[t] < envelope[t + 1])
+        .collect();
+    for peak in peaks.iter_mut() {
+        *peak = match minima.partition_point(|&t| t <= *peak) {
+            0 => 0,
+            idx => minima[idx - 1],
+        };
+    }
+}
+
+/// Finds the onsets in a waveform, as frame indices in order.
+///
+/// The channels are averaged together. See [`onset_envelope()`] for the
+/// detection functions and [`pick_peaks()`] for how onsets are chosen.
+/// Onsets fall on multiples of [`hop_length`](OnsetArgs#structfield.hop_length).
+/// Use [`frames_to_milliseconds()`][crate::units::frames_to_milliseconds]
+/// to turn them into timestamps.
+///
+/// # Errors
+/// Returns the same errors as [`onset_envelope()`].
+///
+/// # Examples
+/// ```
+/// use babycat::onset::{onsets, OnsetArgs};
+/// use babycat::Waveform;
+///
+/// // A 440 hz note starts at half a second, and a 660 hz note at one second.
+/// let samples: Vec<f32> = (0..22050 * 3 / 2)
+///     .map(|i| {
+///         let t = i as f32 / 22050.0;
+///         let freq_hz = if i < 22050 { 440.0 } else { 660.0 };
+///         let amplitude = if i < 11025 { 0.0 } else { 0.5 };
+///         amplitude * (2.0 * std::f32::consts::PI * freq_hz * t).sin()
+///     })
+///     .collect();
+/// let waveform = Waveform::from_interleaved_samples(22050, 1, &samples);
+///
+/// let frame_idxs = onsets(&waveform, OnsetArgs::default()).unwrap();
+/// assert_eq!(frame_idxs.len(), 2);
+/// assert!((frame_idxs[0] as i64 - 11025).abs() <= 512);
+/// assert!((frame_idxs[1] as i64 - 22050).abs() <= 512);
+/// ```
+pub fn onsets(waveform: &Waveform, args: OnsetArgs) -> Result<Vec<usize>, Error> {
+    let envelope = onset_envelope(waveform, args)?;
+    let last_frame_idx = waveform.num_frames().saturating_sub(1);
+    Ok(pick_peaks(&envelope, waveform.frame_rate_hz(), args)?
+        .into_iter()
+        .map(|t| std::cmp::min(t * args.hop_length, last_frame_idx))
+        .collect())
+}
+
+/// Cuts a waveform into one piece per onset.
+///
+/// Each piece starts at an onset and ends at the next one, and the last
+/// piece ends at the end of the waveform. Audio before the first onset
+/// is left out. Setting [`backtrack`](OnsetArgs#structfield.backtrack)
+/// keeps the start of each attack in its own piece.
+///
+/// # Errors
+/// Returns the same errors as [`onset_envelope()`].
+pub fn split_at_onsets(waveform: &Waveform, args: OnsetArgs) -> Result<Vec<Waveform>, Error> {
+    let mut frame_idxs = onsets(waveform, args)?;
+    frame_idxs.dedup();
+    let num_channels = waveform.num_channels() as usize;
+    let samples = waveform.to_interleaved_samples();
+    let ends = frame_idxs
+        .iter()
+        .skip(1)
+        .copied()
+        .chain(std::iter::once(waveform.num_frames()));
+    Ok(frame_idxs
+        .iter()
+        .zip(ends)
+        .map(|(&start, end)| {
+            Waveform::new(
+                waveform.frame_rate_hz(),
+                waveform.num_channels(),
+                samples[start * num_channels..end * num_channels].to_vec(),
+            )
+        })
+        .collect())
+}
@@ -0,0 +1,384 @@
+//! Peak summaries for drawing waveforms.
+//!
+//! A [`Peaks`] summary splits the audio into buckets of consecutive
+//! frames and keeps the smallest sample, the largest sample and the
+//! root mean square of every channel in every bucket. That is all that
+//! is needed to draw a waveform at one bucket per pixel, and it is
+//! measured in a single streaming pass over any [`Source`].
+//!
+//! # Binary format
+//! [`Peaks::to_bytes()`] writes a compact little-endian format for
+//! caching summaries on disk or sending them over the network:
+//!
+//! | Bytes    | Contents                                      |
+//! |----------|-----------------------------------------------|
+//! | 0..4     | The magic bytes `BCPK`.                       |
+//! | 4..6     | The format version as a `u16`, currently 1.   |
+//! | 6..8     | The number of channels as a `u16`.            |
+//! | 8..12    | The frame rate in hz as a `u32`.              |
+//! | 12..20   | The number of frames as a `u64`.              |
+//! | 20..28   | The number of frames per bucket as a `u64`.   |
+//! | 28..     | The minimums, then the maximums, then the RMS values. |
+//!
+//! Each of the three arrays holds one `i16` for every bucket of every
+//! channel, in the same channel-major order as [`Peaks::min()`], with
+//! full scale stored as 32767. This is 6 bytes per bucket per channel,
+//! so a three minute stereo song summarized at 256 frames per bucket
+//! takes up about 370 kilobytes.
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::Error;
+use crate::backend::Signal;
+use crate::backend::Source;
+use crate::backend::Waveform;
+
+/// The magic bytes at the start of the binary format.
+const MAGIC: &[u8; 4] = b"BCPK";
+
+/// The version of the binary format that this module writes.
+const FORMAT_VERSION: u16 = 1;
+
+/// The length of the binary format header, in bytes.
+const HEADER_LENGTH: usize = 28;
+
+/// The value that full scale is stored as in the binary format.
+const FULL_SCALE: f32 = 32767.0;
+
+/// The minimum, maximum and RMS of every channel over consecutive
+/// buckets of frames.
+///
+/// The values are stored channel-major with the shape
+/// `(channels, buckets)`. The last bucket is shorter than the others
+/// when the number of frames is not a multiple of the bucket length.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Peaks {
+    frame_rate_hz: u32,
+    num_channels: u16,
+    num_frames: usize,
+    frames_per_bucket: usize,
+    min: Vec<f32>,
+    max: Vec<f32>,
+    rms: Vec<f32>,
+}
+
+/// Running values for the current bucket of one channel.
+#[derive(Clone, Copy, Debug)]
+struct Bucket {
+    min: f32,
+    max: f32,
+    sum_of_squares: f64,
+}
+
+impl Default for Bucket {
+    fn default() -> Self {
+        Self {
+            min: f32::INFINITY,
+            max: f32::NEG_INFINITY,
+            sum_of_squares: 0.0,
+        }
+    }
+}
+
+/// Summarizes peaks incrementally, one interleaved sample at a time.
+#[derive(Clone, Debug)]
+struct PeaksMeter {
+    frame_rate_hz: u32,
+    num_channels: u16,
+    frames_per_bucket: usize,
+    num_frames: usize,
+    channel_idx: usize,
+    frames_in_bucket: usize,
+    buckets: Vec<Bucket>,
+    min: Vec<Vec<f32>>,
+    max: Vec<Vec<f32>>,
+    rms: Vec<Vec<f32>>,
+}
+
+impl PeaksMeter {
+    fn new(
+        frame_rate_hz: u32,
+        num_channels: u16,
+        frames_per_bucket: usize,
+        num_frames_estimate: Option<usize>,
+    ) -> Result<Self, Error> {
+        if frames_per_bucket == 0 {
+            return Err(Error::WrongFramesPerBucket(frames_per_bucket));
+        }
+        let num_channels_usize = num_channels as usize;
+        let capacity =
+            num_frames_estimate.map_or(0, |num_frames| num_frames.div_ceil(frames_per_bucket));
+        Ok(Self {
+            frame_rate_hz,
+            num_channels,
+            frames_per_bucket,
+            num_frames: 0,
+            channel_idx: 0,
+            frames_in_bucket: 0,
+            buckets: vec![Bucket::default(); num_channels_usize],
+            min: vec![Vec::with_capacity(capacity); num_channels_usize],
+            max: vec![Vec::with_capacity(capacity); num_channels_usize],
+            rms: vec![Vec::with_capacity(capacity); num_channels_usize],
+        })
+    }
+
+    #[inline]
+    fn push_sample(&mut self, sample: f32) {
+        let bucket = &mut self.buckets[self.channel_idx];
+        bucket.min = bucket.min.min(sample);
+        bucket.max = bucket.max.max(sample);
+        bucket.sum_of_squares += f64::from(sample) * f64::from(sample);
+        self.channel_idx += 1;
+        if self.channel_idx == self.num_channels as usize {
+            self.channel_idx = 0;
+            self.num_frames += 1;
+            self.frames_in_bucket += 1;
+            if self.frames_in_bucket == self.frames_per_bucket {
+                self.finish_bucket();
+            }
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn finish_bucket(&mut self) {
+        for (channel_idx, bucket) in self.buckets.iter_mut().enumerate() {
+            self.min[channel_idx].push(bucket.min);
+            self.max[channel_idx].push(bucket.max);
+            self.rms[channel_idx]
+                .push((bucket.sum_of_squares / self.frames_in_bucket as f64).sqrt() as f32);
+            *bucket = Bucket::default();
+        }
+        self.frames_in_bucket = 0;
+    }
+
+    /// Finishes the last bucket, leaving out a partial frame at the end.
+    fn finish(mut self) -> Peaks {
+        if self.frames_in_bucket > 0 {
+            self.finish_bucket();
+        }
+        Peaks {
+            frame_rate_hz: self.frame_rate_hz,
+            num_channels: self.num_channels,
+            num_frames: self.num_frames,
+            frames_per_bucket: self.frames_per_bucket,
+            min: self.min.concat(),
+            max: self.max.concat(),
+            rms: self.rms.concat(),
+        }
+    }
+}
+
+impl Peaks {
+    /// Summarizes any [`Source`] in buckets of `frames_per_bucket`
+    /// frames, in a single streaming pass without holding all of its
+    /// samples in memory.
+    ///
+    /// # Errors
+    /// Returns [`Error::WrongFramesPerBucket`] if `frames_per_bucket` is 0.
+    pub fn from_source<S: Source>(source: S, frames_per_bucket: usize) -> Result<Self, Error> {
+        let mut meter = PeaksMeter::new(
+            source.frame_rate_hz(),
+            source.num_channels(),
+            frames_per_bucket,
+            source.num_frames_estimate(),
+        )?;
+        for sample in source {
+            meter.push_sample(sample);
+        }
+        Ok(meter.finish())
+    }
+
+    /// Summarizes a waveform in buckets of `frames_per_bucket` frames.
+    ///
+    /// # Errors
+    /// Returns [`Error::WrongFramesPerBucket`] if `frames_per_bucket` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::peaks::Peaks;
+    /// use babycat::Waveform;
+    ///
+    /// let waveform = Waveform::from_interleaved_samples(
+    ///     1000,
+    ///     2,
+    ///     &[0.5, -0.1, -0.5, 0.1, 0.25, 0.0, 0.0, 0.0, -1.0, 0.2],
+    /// );
+    /// let peaks = Peaks::from_waveform(&waveform, 2).unwrap();
+    /// assert_eq!(peaks.num_buckets(), 3);
+    /// assert_eq!(peaks.min(), &[-0.5, 0.0, -1.0, -0.1, 0.0, 0.2]);
+    /// assert_eq!(peaks.max(), &[0.5, 0.25, -1.0, 0.1, 0.0, 0.2]);
+    /// assert_eq!(peaks.rms(), &[0.5, 0.17677669, 1.0, 0.1, 0.0, 0.2]);
+    /// ```
+    pub fn from_waveform(waveform: &Waveform, frames_per_bucket: usize) -> Result<Self, Error> {
+        let mut meter = PeaksMeter::new(
+            waveform.frame_rate_hz(),
+            waveform.num_channels(),
+            frames_per_bucket,
+            Some(waveform.num_frames()),
+        )?;
+        for sample in waveform.to_interleaved_samples() {
+            meter.push_sample(*sample);
+        }
+        Ok(meter.finish())
+    }
+
+    /// Summarizes a waveform in at most `num_buckets` buckets of equal length.
+    ///
+    /// This is convenient for drawing a waveform that is `num_buckets`
+    /// pixels wide. The bucket length is rounded up to a whole number of
+    /// frames, so there can be fewer than `num_buckets` buckets.
+    ///
+    /// # Errors
+    /// Returns [`Error::WrongNumBuckets`] if `num_buckets` is 0.
+    pub fn from_waveform_with_num_buckets(
+        waveform: &Waveform,
+        num_buckets: usize,
+    ) -> Result<Self, Error> {
+        if num_buckets == 0 {
+            return Err(Error::WrongNumBuckets(num_buckets));
+        }
+        let frames_per_bucket = std::cmp::max(waveform.num_frames().div_ceil(num_buckets), 1);
+        Self::from_waveform(waveform, frames_per_bucket)
+    }
+
+    /// Returns the frame rate of the summarized audio.
+    pub fn frame_rate_hz(&self) -> u32 {
+        self.frame_rate_hz
+    }
+
+    /// Returns the number of channels.
+    pub fn num_channels(&self) -> u16 {
+        self.num_channels
+    }
+
+    /// Returns the number of frames in the summarized audio.
+    pub fn num_frames(&self) -> usize {
+        self.num_frames
+    }
+
+    /// Returns the number of frames in every bucket but the last.
+    pub fn frames_per_bucket(&self) -> usize {
+        self.frames_per_bucket
+    }
+
+    /// Returns the number of buckets in every channel.
+    pub fn num_buckets(&self) -> usize {
+        self.num_frames.div_ceil(self.frames_per_bucket)
+    }
+
+    /// Returns the smallest sample in every bucket, with the shape `(channels, buckets)`.
+    pub fn min(&self) -> &[f32] {
+        &self.min
+    }
+
+    /// Returns the largest sample in every bucket, with the shape `(channels, buckets)`.
+    pub fn max(&self) -> &[f32] {
+        &self.max
+    }
+
+    /// Returns the root mean square of every bucket, with the shape `(channels, buckets)`.
+    pub fn rms(&self) -> &[f32] {
+        &self.rms
+    }
+
+    /// Writes the summary in the compact binary format described in the
+    /// [module documentation](self).
+    ///
+    /// Values are rounded to 16 bits and clamped to full scale, so
+    /// reading them back with [`from_bytes()`](Self::from_bytes) is only
+    /// accurate to within 1/32767.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LENGTH + 2 * 3 * self.min.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&self.num_channels.to_le_bytes());
+        bytes.extend_from_slice(&self.frame_rate_hz.to_le_bytes());
+        bytes.extend_from_slice(&(self.num_frames as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.frames_per_bucket as u64).to_le_bytes());
+        for value in self.min.iter().chain(&self.max).chain(&self.rms) {
+            let quantized = (value.clamp(-1.0, 1.0) * FULL_SCALE).round() as i16;
+            bytes.extend_from_slice(&quantized.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Reads a summary written by [`to_bytes()`](Self::to_bytes).
+    ///
+    /// # Errors
+    /// Returns [`Error::CannotParsePeaks`] if `bytes` does not hold a
+    /// summary in the binary format.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::peaks::Peaks;
+    /// use babycat::Waveform;
+    ///
+    /// let waveform = Waveform::from_interleaved_samples(1000, 1, &[0.5, -0.5, 0.25, 0.0]);
+    /// let peaks = Peaks::from_waveform(&waveform, 2).unwrap();
+    /// let bytes = peaks.to_bytes();
+    /// assert_eq!(bytes.len(), 28 + 2 * 3 * 2);
+    ///
+    /// let cached = Peaks::from_bytes(&bytes).unwrap();
+    /// assert_eq!(cached.num_buckets(), 2);
+    /// assert!((cached.max()[1] - 0.25).abs() < 1.0 / 32767.0);
+    /// ```
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < HEADER_LENGTH || &bytes[0..4] != MAGIC {
+            return Err(Error::CannotParsePeaks(
+                "The bytes do not start with a peak summary header.",
+            ));
+        }
+        let read_u16 = |start: usize| u16::from_le_bytes([bytes[start], bytes[start + 1]]);
+        let read_u64 = |start: usize| {
+            let mut value = [0_u8; 8];
+            value.copy_from_slice(&bytes[start..start + 8]);
+            u64::from_le_bytes(value)
+        };
+        if read_u16(4) != FORMAT_VERSION {
+            return Err(Error::CannotParsePeaks(
+                "The peak summary format version is not supported.",
+            ));
+        }
+        let num_channels = read_u16(6);
+        let frame_rate_hz = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+        let num_frames = read_u64(12) as usize;
+        let frames_per_bucket = read_u64(20) as usize;
+        if frames_per_bucket == 0 {
+            return Err(Error::CannotParsePeaks(
+                "The peak summary has zero frames per bucket.",
+            ));
+        }
+        // A hostile header can call for more values than fit in memory.
+        let num_values = num_frames
+            .div_ceil(frames_per_bucket)
+            .checked_mul(num_channels as usize);
+        let num_bytes = num_values
+            .and_then(|num_values| num_values.checked_mul(2 * 3))
+            .and_then(|num_bytes| num_bytes.checked_add(HEADER_LENGTH));
+        let num_values = match num_values {
+            Some(num_values) if num_bytes == Some(bytes.len()) => num_values,
+            _ => {
+                return Err(Error::CannotParsePeaks(
+                    "The peak summary does not have as many values as its header calls for.",
+                ))
+            }
+        };
+        let values: Vec<f32> = bytes[HEADER_LENGTH..]
+            .as_chunks::<2>()
+            .0
+            .iter()
+            .map(|pair| f32::from(i16::from_le_bytes(*pair)) / FULL_SCALE)
+            .collect();
+        Ok(Self {
+            frame_rate_hz,
+            num_channels,
+            num_frames,
+            frames_per_bucket,
+            min: values[..num_values].to_vec(),
+            max: values[num_values..2 * num_values].to_vec(),
+            rms: values[2 * num_values..].to_vec(),
+        })
+    }
+}
@@ -0,0 +1,613 @@
+//! Monophonic pitch tracking with YIN and pYIN.
+//!
+//! Both methods cut the audio into overlapping frames and measure how
+//! closely each frame matches itself shifted by every candidate period,
+//! which is the cumulative mean normalized difference function from
+//! Alain de Cheveigné and Hideki Kawahara, "YIN, a fundamental frequency
+//! estimator for speech and music" (2002). The dips in this function
+//! are the candidate periods.
+//!
+//! - **YIN** picks the first dip below
+//!   [`PitchArgs.threshold`](PitchArgs#structfield.threshold) in every frame
+//!   on its own.
+//! - **pYIN** tries many thresholds and turns the dips into pitch
+//!   probabilities, and then picks the most likely path of pitches and
+//!   voicing across every frame with a hidden Markov model. This follows
+//!   Matthias Mauch and Simon Dixon, "pYIN: A fundamental frequency
+//!   estimator using probabilistic threshold distributions" (2014), and
+//!   gives a smoother contour with fewer octave jumps.
+
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::constants::{
+    DEFAULT_HOP_LENGTH, DEFAULT_PITCH_FRAME_LENGTH, DEFAULT_PITCH_MAX_FREQUENCY_HZ,
+    DEFAULT_PITCH_METHOD, DEFAULT_PITCH_MIN_FREQUENCY_HZ, PITCH_METHOD_PYIN, PITCH_METHOD_YIN,
+};
+use crate::backend::units::frames_to_milliseconds;
+use crate::backend::Error;
+use crate::backend::Signal;
+use crate::backend::Waveform;
+
+/// The number of thresholds that pYIN tries, evenly spaced from 0.01 to 1.
+const PYIN_NUM_THRESHOLDS: usize = 100;
+
+/// The shape of the beta distribution over pYIN thresholds, whose mean is 0.1.
+const PYIN_BETA_SHAPE: i32 = 18;
+
+/// How much of a threshold's probability goes to the lowest dip when
+/// no dip is below the threshold.
+const PYIN_NO_TROUGH_PROB: f64 = 0.01;
+
+/// The width of the pitch states in the hidden Markov model.
+const PYIN_BINS_PER_SEMITONE: f64 = 5.0;
+
+/// How fast the pitch can move from one frame to the next, in octaves per second.
+const PYIN_MAX_TRANSITION_OCTAVES_PER_SECOND: f64 = 35.92;
+
+/// The probability of switching between voiced and unvoiced from one frame to the next.
+const PYIN_SWITCH_PROB: f64 = 0.01;
+
+/// Settings for pitch tracking.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PitchArgs {
+    /// The number of frames in each analysis frame. It has to hold at
+    /// least two periods of [`min_frequency_hz`](#structfield.min_frequency_hz).
+    pub frame_length: usize,
+    /// The number of frames between the starts of consecutive analysis frames.
+    pub hop_length: usize,
+    /// The lowest pitch to look for, in hertz.
+    pub min_frequency_hz: f32,
+    /// The highest pitch to look for, in hertz.
+    pub max_frequency_hz: f32,
+    /// The pitch tracking method. Use one of the `PITCH_METHOD_*`
+    /// values in [`constants`][crate::constants].
+    pub method: u32,
+    /// With YIN, how deep a dip has to be for the frame to count as
+    /// voiced, from 0 to 1. Smaller values are stricter. pYIN tries
+    /// many thresholds instead, so it ignores this.
+    pub threshold: f32,
+}
+
+impl Default for PitchArgs {
+    fn default() -> Self {
+        PitchArgs {
+            frame_length: DEFAULT_PITCH_FRAME_LENGTH,
+            hop_length: DEFAULT_HOP_LENGTH,
+            min_frequency_hz: DEFAULT_PITCH_MIN_FREQUENCY_HZ,
+            max_frequency_hz: DEFAULT_PITCH_MAX_FREQUENCY_HZ,
+            method: DEFAULT_PITCH_METHOD,
+            threshold: 0.1,
+        }
+    }
+}
+
+impl PitchArgs {
+    /// Set the [`frame_length`](#structfield.frame_length) field.
+    #[must_use]
+    pub fn set_frame_length(&mut self, frame_length: usize) -> Self {
+        self.frame_length = frame_length;
+        *self
+    }
+
+    /// Set the [`hop_length`](#structfield.hop_length) field.
+    #[must_use]
+    pub fn set_hop_length(&mut self, hop_length: usize) -> Self {
+        self.hop_length = hop_length;
+        *self
+    }
+
+    /// Set the [`min_frequency_hz`](#structfield.min_frequency_hz) field.
+    #[must_use]
+    pub fn set_min_frequency_hz(&mut self, min_frequency_hz: f32) -> Self {
+        self.min_frequency_hz = min_frequency_hz;
+        *self
+    }
+
+    /// Set the [`max_frequency_hz`](#structfield.max_frequency_hz) field.
+    #[must_use]
+    pub fn set_max_frequency_hz(&mut self, max_frequency_hz: f32) -> Self {
+        self.max_frequency_hz = max_frequency_hz;
+        *self
+    }
+
+    /// Set the [`method`](#structfield.method) field.
+    #[must_use]
+    pub fn set_method(&mut self, method: u32) -> Self {
+        self.method = method;
+        *self
+    }
+
+    /// Set the [`threshold`](#structfield.threshold) field.
+    #[must_use]
+    pub fn set_threshold(&mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        *self
+    }
+
+    /// Returns the shortest and longest candidate periods, in frames.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn validate(&self, frame_rate_hz: u32) -> Result<(usize, usize), Error> {
+        if self.hop_length == 0 {
+            return Err(Error::WrongHopLength(self.hop_length));
+        }
+        let nyquist_hz = frame_rate_hz as f32 / 2.0;
+        if !(self.max_frequency_hz > 0.0 && self.max_frequency_hz <= nyquist_hz) {
+            return Err(Error::WrongFilterFrequency(
+                self.max_frequency_hz,
+                frame_rate_hz,
+            ));
+        }
+        if !(self.min_frequency_hz > 0.0 && self.min_frequency_hz < self.max_frequency_hz) {
+            return Err(Error::WrongFilterFrequency(
+                self.min_frequency_hz,
+                frame_rate_hz,
+            ));
+        }
+        let min_period = (frame_rate_hz as f32 / self.max_frequency_hz).floor() as usize;
+        let max_period = (frame_rate_hz as f32 / self.min_frequency_hz).ceil() as usize;
+        // The last period needs one more lag after it to refine the dip.
+        let min_frame_length = 2 * (max_period + 1);
+        if self.frame_length < min_frame_length {
+            return Err(Error::WrongFrameLength(self.frame_length, min_frame_length));
+        }
+        Ok((std::cmp::max(min_period, 1), max_period))
+    }
+}
+
+/// The pitch of one analysis frame.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Pitch {
+    /// The frame that this pitch describes.
+    pub frame_idx: usize,
+    /// The time of [`frame_idx`](#structfield.frame_idx) in milliseconds.
+    pub milliseconds: usize,
+    /// The fundamental frequency in hertz, or NaN if the frame is unvoiced.
+    pub f0_hz: f32,
+    /// The probability that the frame is voiced, from 0 to 1.
+    pub voiced_prob: f32,
+}
+
+impl Pitch {
+    /// Returns `true` if the frame has a pitch.
+    pub fn is_voiced(&self) -> bool {
+        !self.f0_hz.is_nan()
+    }
+}
+
+/// A dip in the cumulative mean normalized difference function.
+#[derive(Clone, Copy, Debug)]
+struct Trough {
+    /// The depth of the dip, where 0 is a perfect match.
+    value: f64,
+    /// The frequency of the dip's period in hertz, refined between lags.
+    f0_hz: f64,
+}
+
+/// Computes the cumulative mean normalized difference function of one
+/// frame, for every lag up to and including `max_lag`.
+///
+/// The frame is compared with itself over its first `frame.len() / 2` frames.
+struct DifferenceFunction {
+    fft_size: usize,
+    forward: std::sync::Arc<dyn rustfft::Fft<f32>>,
+    inverse: std::sync::Arc<dyn rustfft::Fft<f32>>,
+    window: Vec<Complex<f32>>,
+    whole: Vec<Complex<f32>>,
+    energy: Vec<f64>,
+}
+
+impl DifferenceFunction {
+    fn new(frame_length: usize) -> Self {
+        let fft_size = frame_length.next_power_of_two();
+        let mut planner = FftPlanner::new();
+        Self {
+            fft_size,
+            forward: planner.plan_fft_forward(fft_size),
+            inverse: planner.plan_fft_inverse(fft_size),
+            window: vec![Complex::new(0.0, 0.0); fft_size],
+            whole: vec![Complex::new(0.0, 0.0); fft_size],
+            energy: Vec::with_capacity(frame_length + 1),
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn compute(&mut self, frame: &[f32], max_lag: usize, out: &mut Vec<f64>) {
+        let window_length = frame.len() / 2;
+        // The sums of squares of every stretch of `window_length` frames
+        // come from a running total.
+        self.energy.clear();
+        self.energy.push(0.0);
+        let mut total = 0.0_f64;
+        for value in frame {
+            total += f64::from(*value) * f64::from(*value);
+            self.energy.push(total);
+        }
+
+        // The cross-correlation of the first half of the frame with the
+        // whole frame, from the FFT. The FFT is long enough that lags up
+        // to `max_lag` do not wrap around.
+        for (k, (w, x)) in self.window.iter_mut().zip(&mut self.whole).enumerate() {
+            let value = frame.get(k).copied().unwrap_or(0.0);
+            *x = Complex::new(value, 0.0);
+            *w = Complex::new(if k < window_length { value } else { 0.0 }, 0.0);
+        }
+        self.forward.process(&mut self.window);
+        self.forward.process(&mut self.whole);
+        for (x, w) in self.whole.iter_mut().zip(&self.window) {
+            *x *= w.conj();
+        }
+        self.inverse.process(&mut self.whole);
+        let scale = 1.0 / self.fft_size as f64;
+
+        out.clear();
+        out.push(1.0);
+        let first_energy = self.energy[window_length];
+        let mut running_sum = 0.0_f64;
+        for lag in 1..=max_lag {
+            let shifted_energy = self.energy[lag + window_length] - self.energy[lag];
+            let correlation = f64::from(self.whole[lag].re) * scale;
+            let difference = (first_energy + shifted_energy - 2.0 * correlation).max(0.0);
+            running_sum += difference;
+            out.push(if running_sum > 0.0 {
+                difference * lag as f64 / running_sum
+            } else {
+                1.0
+            });
+        }
+    }
+}
+
+/// Returns the dips of `cmndf` with lags from `min_period` to `max_period`,
+/// from the shortest period to the longest.
+#[allow(clippy::cast_precision_loss)]
+fn find_troughs(
+    cmndf: &[f64],
+    min_period: usize,
+    max_period: usize,
+    frame_rate_hz: u32,
+) -> Vec<Trough> {
+    let first = std::cmp::max(min_period, 1);
+    let last = std::cmp::min(max_period, cmndf.len().saturating_sub(2));
+    let mut troughs = Vec::new();
+    for lag in first..=last {
+        let (before, value, after) = (cmndf[lag - 1], cmndf[lag], cmndf[lag + 1]);
+        if !(value < before && value <= after) {
+            continue;
+        }
+        // Fit a parabola through the dip and its neighbors.
+        let curvature = before - 2.0 * value + after;
+        let shift = if curvature > 0.0 {
+            (0.5 * (before - after) / curvature).clamp(-0.5, 0.5)
+        } else {
+            0.0
+        };
+        let refined_value = value - 0.25 * (before - after) * shift;
+        troughs.push(Trough {
+            value: refined_value.max(0.0),
+            f0_hz: f64::from(frame_rate_hz) / (lag as f64 + shift),
+        });
+    }
+    troughs
+}
+
+/// Returns the probability of each pYIN threshold, from a beta
+/// distribution with a mean of 0.1.
+#[allow(clippy::cast_precision_loss)]
+fn threshold_probabilities() -> Vec<(f64, f64)> {
+    // The CDF of a beta distribution with shapes 2 and `b` is
+    // 1 - (1 - x)^(b + 1) - (b + 1) x (1 - x)^b.
+    let cdf = |x: f64| {
+        let b = PYIN_BETA_SHAPE;
+        1.0 - (1.0 - x).powi(b + 1) - f64::from(b + 1) * x * (1.0 - x).powi(b)
+    };
+    (1..=PYIN_NUM_THRESHOLDS)
+        .map(|i| {
+            let threshold = i as f64 / PYIN_NUM_THRESHOLDS as f64;
+            let previous = (i - 1) as f64 / PYIN_NUM_THRESHOLDS as f64;
+            (threshold, cdf(threshold) - cdf(previous))
+        })
+        .collect()
+}
+
+/// Spreads the probability of every threshold over the dips of one
+/// frame. Returns each dip's frequency and probability.
+fn candidate_probabilities(troughs: &[Trough], thresholds: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut probs: Vec<(f64, f64)> = troughs.iter().map(|t| (t.f0_hz, 0.0)).collect();
+    let Some(lowest) =
+        (0..troughs.len()).min_by(|&a, &b| troughs[a].value.total_cmp(&troughs[b].value))
+    else {
+        return probs;
+    };
+    for (threshold, prob) in thresholds {
+        match troughs.iter().position(|t| t.value < *threshold) {
+            Some(idx) => probs[idx].1 += prob,
+            None => probs[lowest].1 += prob * PYIN_NO_TROUGH_PROB,
+        }
+    }
+    probs
+}
+
+/// Picks the most likely voicing and pitch bin of every frame with the
+/// Viterbi algorithm. Returns the pitch bin of every voiced frame.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn viterbi(
+    candidates: &[Vec<(f64, f64)>],
+    num_bins: usize,
+    to_bin: impl Fn(f64) -> Option<usize>,
+    max_step: usize,
+) -> Vec<Option<usize>> {
+    let num_states = 2 * num_bins;
+    // Moving by `k` bins is likelier the smaller `k` is.
+    let weights: Vec<f64> = (0..=max_step).map(|k| (max_step + 1 - k) as f64).collect();
+    let total_weight = weights[0] + 2.0 * weights[1..].iter().sum::<f64>();
+    let log_stay: Vec<f64> = weights
+        .iter()
+        .map(|w| (w / total_weight * (1.0 - PYIN_SWITCH_PROB)).ln())
+        .collect();
+    let log_switch = PYIN_SWITCH_PROB.ln();
+
+    let mut observations = vec![0.0_f64; num_states];
+    let observe = |frame: &[(f64, f64)], observations: &mut [f64]| {
+        observations.fill(0.0);
+        let mut voiced = 0.0;
+        for (f0_hz, prob) in frame {
+            if let Some(bin) = to_bin(*f0_hz) {
+                observations[bin] += prob;
+                voiced += prob;
+            }
+        }
+        let unvoiced = (1.0 - voiced).max(0.0) / num_bins as f64;
+        observations[num_bins..].fill(unvoiced);
+        for value in observations.iter_mut() {
+            *value = value.max(f64::MIN_POSITIVE).ln();
+        }
+    };
+
+    let mut scores = vec![(1.0 / num_states as f64).ln(); num_states];
+    let mut next = vec![0.0_f64; num_states];
+    let mut backpointers: Vec<Vec<u32>> = Vec::with_capacity(candidates.len());
+    for (frame_idx, frame) in candidates.iter().enumerate() {
+        observe(frame, &mut observations);
+        if frame_idx == 0 {
+            for (score, observation) in scores.iter_mut().zip(&observations) {
+                *score += observation;
+            }
+            backpointers.push(Vec::new());
+            continue;
+        }
+        let mut pointers = vec![0_u32; num_states];
+        for state in 0..num_states {
+            let (offset, bin) = if state < num_bins {
+                (0, state)
+            } else {
+                (num_bins, state - num_bins)
+            };
+            let mut best = f64::NEG_INFINITY;
+            let mut best_state = state;
+            let low = bin.saturating_sub(max_step);
+            let high = std::cmp::min(bin + max_step, num_bins - 1);
+            for source_bin in low..=high {
+                let step = source_bin.abs_diff(bin);
+                let score = scores[offset + source_bin] + log_stay[step];
+                if score > best {
+                    best = score;
+                    best_state = offset + source_bin;
+                }
+            }
+            let other = (num_bins - offset) + bin;
+            let score = scores[other] + log_switch;
+            if score > best {
+                best = score;
+                best_state = other;
+            }
+            next[state] = best + observations[state];
+            pointers[state] = best_state as u32;
+        }
+        std::mem::swap(&mut scores, &mut next);
+        backpointers.push(pointers);
+    }
+
+    let mut path = vec![None; candidates.len()];
+    let Some(mut state) = (0..num_states).max_by(|&a, &b| scores[a].total_cmp(&scores[b])) else {
+        return path;
+    };
+    for frame_idx in (0..candidates.len()).rev() {
+        if state < num_bins {
+            path[frame_idx] = Some(state);
+        }
+        if frame_idx > 0 {
+            state = backpointers[frame_idx][state] as usize;
+        }
+    }
+    path
+}
+
+/// Tracks the fundamental frequency of a waveform.
+///
+/// The channels are averaged together first. Result `t` describes frame
+/// `t * hop_length`: its analysis frame is compared with itself over the
+/// half that is centered there. The audio is padded with silence at both
+/// ends, so there are `1 + num_frames / hop_length` results. Unvoiced frames, such as silence, noise or the gaps between
+/// notes, have an [`f0_hz`](Pitch#structfield.f0_hz) of NaN.
+///
+/// With YIN, the voicing probability is one minus the depth of the
+/// chosen dip. With pYIN, it is the share of thresholds that found a
+/// dip, and the hidden Markov model decides which frames are voiced.
+///
+/// # Errors
+/// Returns [`Error::WrongHopLength`] if `hop_length` is zero,
+/// [`Error::WrongFilterFrequency`] if the frequency range is empty or
+/// does not fit below the Nyquist frequency,
+/// [`Error::WrongFrameLength`] if
+/// [`frame_length`](PitchArgs#structfield.frame_length) cannot hold two
+/// periods of the lowest frequency, and [`Error::FeatureNotCompiled`]
+/// if [`method`](PitchArgs#structfield.method) is unknown.
+///
+/// # Examples
+/// ```
+/// use babycat::pitch::{pitch, PitchArgs};
+/// use babycat::Waveform;
+///
+/// // One second of a 220 hz sine wave.
+/// let samples: Vec<f32> = (0..22050)
+///     .map(|i| 0.5 * (2.0 * std::f32::consts::PI * 220.0 * i as f32 / 22050.0).sin())
+///     .collect();
+/// let waveform = Waveform::from_interleaved_samples(22050, 1, &samples);
+///
+/// let contour = pitch(&waveform, PitchArgs::default()).unwrap();
+/// assert_eq!(contour.len(), 1 + 22050 / 512);
+/// let middle = contour[contour.len() / 2];
+/// assert!((middle.f0_hz - 220.0).abs() < 1.0);
+/// assert!(middle.voiced_prob > 0.9);
+/// ```
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn pitch(waveform: &Waveform, args: PitchArgs) -> Result<Vec<Pitch>, Error> {
+    let frame_rate_hz = waveform.frame_rate_hz();
+    let (min_period, max_period) = args.validate(frame_rate_hz)?;
+    if args.method != DEFAULT_PITCH_METHOD
+        && args.method != PITCH_METHOD_PYIN
+        && args.method != PITCH_METHOD_YIN
+    {
+        return Err(Error::FeatureNotCompiled("unknown-pitch-method"));
+    }
+    let mono = waveform.to_mono();
+    let samples = mono.to_interleaved_samples();
+    let num_frames = samples.len();
+    let num_results = 1 + num_frames / args.hop_length;
+    // Every frame is compared with itself over its first half, so the
+    // frame starts a quarter of its length before the frame it describes.
+    let lead = args.frame_length / 4;
+
+    let mut difference = DifferenceFunction::new(args.frame_length);
+    let mut frame = vec![0.0_f32; args.frame_length];
+    let mut cmndf: Vec<f64> = Vec::with_capacity(max_period + 2);
+    let troughs: Vec<Vec<Trough>> = (0..num_results)
+        .map(|t| {
+            let center = t * args.hop_length;
+            for (k, value) in frame.iter_mut().enumerate() {
+                *value = (center + k)
+                    .checked_sub(lead)
+                    .and_then(|idx| samples.get(idx))
+                    .copied()
+                    .unwrap_or(0.0);
+            }
+            difference.compute(&frame, max_period + 1, &mut cmndf);
+            find_troughs(&cmndf, min_period, max_period, frame_rate_hz)
+        })
+        .collect();
+
+    let time = |t: usize| {
+        let frame_idx = t * args.hop_length;
+        (frame_idx, frames_to_milliseconds(frame_idx, frame_rate_hz))
+    };
+
+    if args.method == PITCH_METHOD_YIN {
+        let threshold = f64::from(args.threshold);
+        return Ok(troughs
+            .iter()
+            .enumerate()
+            .map(|(t, frame_troughs)| {
+                let (frame_idx, milliseconds) = time(t);
+                let lowest = frame_troughs
+                    .iter()
+                    .min_by(|a, b| a.value.total_cmp(&b.value));
+                let chosen = frame_troughs
+                    .iter()
+                    .find(|trough| trough.value < threshold)
+                    .or(lowest);
+                let (f0_hz, voiced_prob) = match chosen {
+                    Some(trough) => (
+                        if trough.value < threshold {
+                            trough.f0_hz as f32
+                        } else {
+                            f32::NAN
+                        },
+                        (1.0 - trough.value).clamp(0.0, 1.0) as f32,
+                    ),
+                    None => (f32::NAN, 0.0),
+                };
+                Pitch {
+                    frame_idx,
+                    milliseconds,
+                    f0_hz,
+                    voiced_prob,
+                }
+            })
+            .collect());
+    }
+
+    let thresholds = threshold_probabilities();
+    let candidates: Vec<Vec<(f64, f64)>> = troughs
+        .iter()
+        .map(|frame_troughs| candidate_probabilities(frame_troughs, &thresholds))
+        .collect();
+
+    let min_frequency_hz = f64::from(args.min_frequency_hz);
+    let bins_per_octave = 12.0 * PYIN_BINS_PER_SEMITONE;
+    let num_bins = 1
+        + (bins_per_octave * (f64::from(args.max_frequency_hz) / min_frequency_hz).log2()).ceil()
+            as usize;
+    let to_bin = |f0_hz: f64| {
+        let bin = (bins_per_octave * (f0_hz / min_frequency_hz).log2()).round();
+        if bin >= 0.0 && (bin as usize) < num_bins {
+            Some(bin as usize)
+        } else {
+            None
+        }
+    };
+    let max_step =
+        (PYIN_MAX_TRANSITION_OCTAVES_PER_SECOND * bins_per_octave * args.hop_length as f64
+            / f64::from(frame_rate_hz))
+        .round()
+        .max(1.0) as usize;
+    let path = viterbi(&candidates, num_bins, to_bin, max_step);
+
+    Ok(candidates
+        .iter()
+        .zip(path)
+        .enumerate()
+        .map(|(t, (frame_candidates, bin))| {
+            let (frame_idx, milliseconds) = time(t);
+            let voiced_prob = frame_candidates
+                .iter()
+                .map(|(_, prob)| prob)
+                .sum::<f64>()
+                .clamp(0.0, 1.0) as f32;
+            // The reported pitch is the likeliest dip near the chosen
+            // bin, which is more precise than the bin itself.
+            let f0_hz = match bin {
+                Some(bin) => frame_candidates
+                    .iter()
+                    .filter(|(f0_hz, _)| {
+                        to_bin(*f0_hz).is_some_and(|other| other.abs_diff(bin) <= 1)
+                    })
+                    .max_by(|a, b| a.1.total_cmp(&b.1))
+                    .map_or_else(
+                        || min_frequency_hz * 2.0_f64.powf(bin as f64 / bins_per_octave),
+                        |(f0_hz, _)| *f0_hz,
+                    ) as f32,
+                None => f32::NAN,
+            };
+            Pitch {
+                frame_idx,
+                milliseconds,
+                f0_hz,
+                voiced_prob,
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,585 @@
+//! Rendering waveforms and spectrograms as images.
+//!
+//! [`render_waveform()`] draws a [`Peaks`] summary of every channel,
+//! [`render_peaks()`] draws a summary that was measured beforehand, and
+//! [`render_spectrogram()`] draws the short-time Fourier transform of
+//! every channel. Channels are stacked from top to bottom. They all
+//! return an [`Image`], which can be written as SVG with [`Image::to_svg()`],
+//! or as PNG with [`Image::to_png()`] when the `enable-png` feature
+//! is compiled.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::constants::{
+    COLOR_MAP_GRAYSCALE, COLOR_MAP_MAGMA, COLOR_MAP_VIRIDIS, DEFAULT_COLOR_MAP,
+    DEFAULT_RENDER_HEIGHT, DEFAULT_RENDER_MAX_DB, DEFAULT_RENDER_MIN_DB, DEFAULT_RENDER_WIDTH,
+    MAX_RENDER_SIZE,
+};
+use crate::backend::loudness::ratio_to_dbfs;
+use crate::backend::peaks::Peaks;
+use crate::backend::spectral::{window, Stft, StftArgs};
+use crate::backend::Error;
+use crate::backend::Waveform;
+
+/// Where the waveform envelope is drawn on the color map. The background
+/// is drawn at 0 and the RMS level at 1.
+const ENVELOPE_COLOR_POSITION: f32 = 0.6;
+
+/// Evenly spaced colors from the viridis color map.
+const VIRIDIS: [[u8; 3]; 9] = [
+    [68, 1, 84],
+    [72, 40, 120],
+    [62, 73, 137],
+    [49, 104, 142],
+    [38, 130, 142],
+    [31, 158, 137],
+    [53, 183, 121],
+    [110, 206, 88],
+    [253, 231, 37],
+];
+
+/// Evenly spaced colors from the magma color map.
+const MAGMA: [[u8; 3]; 9] = [
+    [0, 0, 4],
+    [28, 16, 68],
+    [79, 18, 123],
+    [129, 37, 129],
+    [181, 54, 122],
+    [229, 80, 100],
+    [251, 135, 97],
+    [254, 194, 135],
+    [252, 253, 191],
+];
+
+const GRAYSCALE: [[u8; 3]; 2] = [[0, 0, 0], [255, 255, 255]];
+
+fn default_width() -> u32 {
+    DEFAULT_RENDER_WIDTH
+}
+
+fn default_height() -> u32 {
+    DEFAULT_RENDER_HEIGHT
+}
+
+fn default_min_db() -> f32 {
+    DEFAULT_RENDER_MIN_DB
+}
+
+fn default_max_db() -> f32 {
+    DEFAULT_RENDER_MAX_DB
+}
+
+/// Settings for rendering an image.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RenderArgs {
+    /// The width of the image in pixels, up to
+    /// [`MAX_RENDER_SIZE`][crate::constants::MAX_RENDER_SIZE].
+    #[serde(default = "default_width")]
+    pub width: u32,
+    /// The height of the image in pixels, up to
+    /// [`MAX_RENDER_SIZE`][crate::constants::MAX_RENDER_SIZE]. Every
+    /// channel gets an equal share of the height.
+    #[serde(default = "default_height")]
+    pub height: u32,
+    /// The colors to draw with. Use one of the `COLOR_MAP_*` values
+    /// in [`constants`][crate::constants].
+    #[serde(default)]
+    pub color_map: u32,
+    /// Spectrogram levels at or below this many dBFS are drawn with the
+    /// first color of the color map. Waveforms ignore this field.
+    #[serde(default = "default_min_db")]
+    pub min_db: f32,
+    /// Spectrogram levels at or above this many dBFS are drawn with the
+    /// last color of the color map. Waveforms ignore this field.
+    #[serde(default = "default_max_db")]
+    pub max_db: f32,
+}
+
+impl Default for RenderArgs {
+    fn default() -> Self {
+        RenderArgs {
+            width: DEFAULT_RENDER_WIDTH,
+            height: DEFAULT_RENDER_HEIGHT,
+            color_map: DEFAULT_COLOR_MAP,
+            min_db: DEFAULT_RENDER_MIN_DB,
+            max_db: DEFAULT_RENDER_MAX_DB,
+        }
+    }
+}
+
+impl RenderArgs {
+    /// Set the [`width`](#structfield.width) field.
+    #[must_use]
+    pub fn set_width(&mut self, width: u32) -> Self {
+        self.width = width;
+        *self
+    }
+
+    /// Set the [`height`](#structfield.height) field.
+    #[must_use]
+    pub fn set_height(&mut self, height: u32) -> Self {
+        self.height = height;
+        *self
+    }
+
+    /// Set the [`color_map`](#structfield.color_map) field.
+    #[must_use]
+    pub fn set_color_map(&mut self, color_map: u32) -> Self {
+        self.color_map = color_map;
+        *self
+    }
+
+    /// Set the [`min_db`](#structfield.min_db) field.
+    #[must_use]
+    pub fn set_min_db(&mut self, min_db: f32) -> Self {
+        self.min_db = min_db;
+        *self
+    }
+
+    /// Set the [`max_db`](#structfield.max_db) field.
+    #[must_use]
+    pub fn set_max_db(&mut self, max_db: f32) -> Self {
+        self.max_db = max_db;
+        *self
+    }
+
+    /// Checks the settings and returns the colors of the color map.
+    fn validate(&self) -> Result<&'static [[u8; 3]], Error> {
+        if self.width == 0
+            || self.height == 0
+            || self.width > MAX_RENDER_SIZE
+            || self.height > MAX_RENDER_SIZE
+        {
+            return Err(Error::WrongImageSize(self.width, self.height));
+        }
+        if self.min_db.is_nan() || self.max_db.is_nan() || self.min_db >= self.max_db {
+            return Err(Error::WrongDecibelRange(self.min_db, self.max_db));
+        }
+        match self.color_map {
+            DEFAULT_COLOR_MAP | COLOR_MAP_VIRIDIS => Ok(&VIRIDIS),
+            COLOR_MAP_MAGMA => Ok(&MAGMA),
+            COLOR_MAP_GRAYSCALE => Ok(&GRAYSCALE),
+            _ => Err(Error::FeatureNotCompiled("unknown-color-map")),
+        }
+    }
+}
+
+/// Returns the color at `position` between 0 and 1 on a color map.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn interpolate(colors: &[[u8; 3]], position: f32) -> [u8; 3] {
+    let position = if position.is_nan() {
+        0.0
+    } else {
+        position.clamp(0.0, 1.0)
+    };
+    let scaled = position * (colors.len() - 1) as f32;
+    let idx = (scaled as usize).min(colors.len() - 2);
+    let fraction = scaled - idx as f32;
+    let mut color = [0_u8; 3];
+    for (component, (low, high)) in color
+        .iter_mut()
+        .zip(colors[idx].iter().zip(&colors[idx + 1]))
+    {
+        *component =
+            (f32::from(*low) + fraction * (f32::from(*high) - f32::from(*low))).round() as u8;
+    }
+    color
+}
+
+/// Returns the range of `len` items that the `idx`-th of `count` even
+/// slices covers. Every slice covers at least one item, so items are
+/// repeated when there are more slices than items.
+fn slice_range(idx: usize, count: usize, len: usize) -> std::ops::Range<usize> {
+    let start = idx * len / count;
+    let end = ((idx + 1) * len / count).max(start + 1);
+    start..end
+}
+
+/// Returns the rows of an image of `height` pixels that the
+/// `channel_idx`-th of `num_channels` channels is drawn in.
+#[allow(clippy::cast_possible_truncation)]
+fn lane(height: u32, num_channels: u16, channel_idx: u16) -> std::ops::Range<u32> {
+    let row = |idx: u16| (u64::from(height) * u64::from(idx) / u64::from(num_channels)) as u32;
+    row(channel_idx)..row(channel_idx + 1)
+}
+
+/// An RGB image with 8 bits per color component.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    fn filled(width: u32, height: u32, color: [u8; 3]) -> Self {
+        let num_pixels = width as usize * height as usize;
+        Self {
+            width,
+            height,
+            pixels: color.repeat(num_pixels),
+        }
+    }
+
+    fn set_pixel(&mut self, x: u32, y: u32, color: [u8; 3]) {
+        let offset = 3 * (y as usize * self.width as usize + x as usize);
+        self.pixels[offset..offset + 3].copy_from_slice(&color);
+    }
+
+    /// Returns the width of the image in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of the image in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the color of the pixel at column `x` and row `y`,
+    /// counting rows from the top, or `None` if it is outside the image.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 3]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.color(x, y))
+    }
+
+    /// Returns the red, green and blue components of every pixel,
+    /// row by row from the top.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Returns the color of the pixel at column `x` and row `y`, which
+    /// must be inside the image.
+    fn color(&self, x: u32, y: u32) -> [u8; 3] {
+        let offset = 3 * (y as usize * self.width as usize + x as usize);
+        [
+            self.pixels[offset],
+            self.pixels[offset + 1],
+            self.pixels[offset + 2],
+        ]
+    }
+
+    /// Returns the color that the most pixels have.
+    fn most_common_color(&self) -> [u8; 3] {
+        let mut counts = HashMap::new();
+        for pixel in self.pixels.as_chunks::<3>().0 {
+            *counts.entry(*pixel).or_insert(0_usize) += 1;
+        }
+        counts
+            .into_iter()
+            .max_by_key(|(color, count)| (*count, *color))
+            .map_or([0, 0, 0], |(color, _)| color)
+    }
+
+    /// Returns every run of neighboring pixels of the same color that is
+    /// not `background`, as `(x, y, length, color)`. Runs go down the
+    /// columns when `vertical` is true and along the rows otherwise.
+    fn runs(&self, vertical: bool, background: [u8; 3]) -> Vec<(u32, u32, u32, [u8; 3])> {
+        let (num_lines, line_length) = if vertical {
+            (self.width, self.height)
+        } else {
+            (self.height, self.width)
+        };
+        let position = |line: u32, offset: u32| {
+            if vertical {
+                (line, offset)
+            } else {
+                (offset, line)
+            }
+        };
+        let mut runs = Vec::new();
+        for line in 0..num_lines {
+            let mut start = 0;
+            while start < line_length {
+                let (x, y) = position(line, start);
+                let color = self.color(x, y);
+                let mut end = start + 1;
+                while end < line_length {
+                    let (next_x, next_y) = position(line, end);
+                    if self.color(next_x, next_y) != color {
+                        break;
+                    }
+                    end += 1;
+                }
+                if color != background {
+                    runs.push((x, y, end - start, color));
+                }
+                start = end;
+            }
+        }
+        runs
+    }
+
+    /// Encodes the image as an SVG document.
+    ///
+    /// The most common color fills the background, and every other pixel
+    /// is drawn with a `<rect>` for each run of same-colored pixels.
+    /// Runs follow the columns or the rows, whichever takes fewer
+    /// rectangles.
+    pub fn to_svg(&self) -> String {
+        let background = self.most_common_color();
+        let vertical_runs = self.runs(true, background);
+        let horizontal_runs = self.runs(false, background);
+        let vertical = vertical_runs.len() <= horizontal_runs.len();
+        let runs = if vertical {
+            vertical_runs
+        } else {
+            horizontal_runs
+        };
+        let hex = |color: [u8; 3]| format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2]);
+        let mut svg = format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" "#,
+                r#"width="{width}" height="{height}" viewBox="0 0 {width} {height}" "#,
+                r#"shape-rendering="crispEdges">"#,
+                r#"<rect width="{width}" height="{height}" fill="{background}"/>"#,
+            ),
+            width = self.width,
+            height = self.height,
+            background = hex(background),
+        );
+        for (x, y, length, color) in runs {
+            let (width, height) = if vertical { (1, length) } else { (length, 1) };
+            // Writing to a String cannot fail.
+            let _ = write!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                x,
+                y,
+                width,
+                height,
+                hex(color)
+            );
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Encodes the image as a PNG file.
+    ///
+    /// This returns [`Error::FeatureNotCompiled`] unless Babycat was
+    /// compiled with the `enable-png` feature.
+    pub fn to_png(&self) -> Result<Vec<u8>, Error> {
+        #[cfg(feature = "enable-png")]
+        {
+            let mut bytes = Vec::new();
+            let mut encoder = png::Encoder::new(&mut bytes, self.width, self.height);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+            let encode = |_| Error::UnknownEncodeError;
+            let mut writer = encoder.write_header().map_err(encode)?;
+            writer.write_image_data(&self.pixels).map_err(encode)?;
+            writer.finish().map_err(encode)?;
+            Ok(bytes)
+        }
+        #[cfg(not(feature = "enable-png"))]
+        {
+            Err(Error::FeatureNotCompiled("enable-png"))
+        }
+    }
+}
+
+/// Draws the waveform of every channel.
+///
+/// Each column of pixels shows the smallest and largest samples of a
+/// bucket of frames, with the RMS level of the bucket drawn in a
+/// brighter color on top.
+///
+/// # Examples
+/// ```
+/// use babycat::render::{render_waveform, RenderArgs};
+/// use babycat::Waveform;
+///
+/// let waveform = Waveform::from_interleaved_samples(1000, 2, &[0.5, 0.0, -0.5, 0.0]);
+/// let args = RenderArgs::default().set_width(2).set_height(10);
+/// let image = render_waveform(&waveform, args).unwrap();
+/// assert_eq!((image.width(), image.height()), (2, 10));
+///
+/// // The left channel is drawn above the right channel,
+/// // and the right channel is silent.
+/// let background = image.pixel(0, 0).unwrap();
+/// assert_ne!(image.pixel(0, 1), Some(background));
+/// assert_eq!(image.pixel(0, 5), Some(background));
+/// ```
+pub fn render_waveform(waveform: &Waveform, args: RenderArgs) -> Result<Image, Error> {
+    args.validate()?;
+    let peaks = Peaks::from_waveform_with_num_buckets(waveform, args.width as usize)?;
+    render_peaks(&peaks, args)
+}
+
+/// Draws the waveform of every channel from a [`Peaks`] summary.
+///
+/// This draws the same image as [`render_waveform()`] without holding
+/// the audio in memory, since the summary can be measured with
+/// [`Peaks::from_source()`]. When there are more buckets than columns
+/// of pixels, every column shows the buckets it covers combined.
+///
+/// # Examples
+/// ```
+/// use babycat::peaks::Peaks;
+/// use babycat::render::{render_peaks, RenderArgs};
+/// use babycat::WaveformSource;
+///
+/// let source = WaveformSource::from_interleaved_samples(1000, 1, &[0.5, -0.5, 0.0, 0.0]);
+/// let peaks = Peaks::from_source(source, 1).unwrap();
+/// let args = RenderArgs::default().set_width(2).set_height(10);
+/// let image = render_peaks(&peaks, args).unwrap();
+///
+/// // The first column covers the loud buckets and the second
+/// // column covers the silent buckets.
+/// let background = image.pixel(0, 0).unwrap();
+/// assert_ne!(image.pixel(0, 3), Some(background));
+/// assert_eq!(image.pixel(1, 3), Some(background));
+/// ```
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn render_peaks(peaks: &Peaks, args: RenderArgs) -> Result<Image, Error> {
+    let colors = args.validate()?;
+    let envelope_color = interpolate(colors, ENVELOPE_COLOR_POSITION);
+    let rms_color = interpolate(colors, 1.0);
+    let mut image = Image::filled(args.width, args.height, interpolate(colors, 0.0));
+    let num_buckets = peaks.num_buckets();
+    if num_buckets == 0 {
+        return Ok(image);
+    }
+    // The last bucket is shorter than the others.
+    let bucket_length = |bucket_idx: usize| {
+        std::cmp::min(
+            peaks.frames_per_bucket(),
+            peaks.num_frames() - bucket_idx * peaks.frames_per_bucket(),
+        )
+    };
+    let num_channels = peaks.num_channels();
+    for channel_idx in 0..num_channels {
+        let rows = lane(args.height, num_channels, channel_idx);
+        if rows.is_empty() {
+            continue;
+        }
+        let max_offset = (rows.end - rows.start - 1) as f32;
+        let to_row = |value: f32| {
+            rows.start + ((1.0 - value.clamp(-1.0, 1.0)) / 2.0 * max_offset).round() as u32
+        };
+        let channel_start = channel_idx as usize * num_buckets;
+        for x in 0..args.width {
+            let buckets = slice_range(x as usize, args.width as usize, num_buckets);
+            let mut min = f32::INFINITY;
+            let mut max = f32::NEG_INFINITY;
+            let mut sum_of_squares = 0.0_f32;
+            let mut num_frames = 0_usize;
+            for bucket_idx in buckets {
+                min = min.min(peaks.min()[channel_start + bucket_idx]);
+                max = max.max(peaks.max()[channel_start + bucket_idx]);
+                let rms = peaks.rms()[channel_start + bucket_idx];
+                sum_of_squares += rms * rms * bucket_length(bucket_idx) as f32;
+                num_frames += bucket_length(bucket_idx);
+            }
+            let rms = (sum_of_squares / num_frames as f32).sqrt();
+            for y in to_row(max)..=to_row(min) {
+                image.set_pixel(x, y, envelope_color);
+            }
+            for y in to_row(rms.min(max))..=to_row((-rms).max(min)) {
+                image.set_pixel(x, y, rms_color);
+            }
+        }
+    }
+    Ok(image)
+}
+
+/// Draws the magnitude spectrogram of every channel, with low
+/// frequencies at the bottom.
+///
+/// Levels are in dBFS, so a full-scale sine wave peaks at about 0 dB.
+/// When there are more time steps or frequency bins than pixels, every
+/// pixel shows the loudest of the values it covers.
+///
+/// # Examples
+/// ```
+/// use babycat::render::{render_spectrogram, RenderArgs};
+/// use babycat::spectral::StftArgs;
+/// use babycat::Waveform;
+///
+/// // A 2756.25 hz tone sits an eighth of the way up the spectrum.
+/// let samples: Vec<f32> = (0..44100)
+///     .map(|i| (2.0 * std::f32::consts::PI * 2756.25 * i as f32 / 44100.0).sin())
+///     .collect();
+/// let waveform = Waveform::from_interleaved_samples(44100, 1, &samples);
+/// let args = RenderArgs::default().set_width(100).set_height(64);
+/// let image = render_spectrogram(&waveform, StftArgs::default(), args).unwrap();
+///
+/// let brightest_row = (0..64)
+///     .max_by_key(|y| image.pixel(50, *y).unwrap()[1])
+///     .unwrap();
+/// assert_eq!(brightest_row, 55);
+/// ```
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn render_spectrogram(
+    waveform: &Waveform,
+    stft_args: StftArgs,
+    args: RenderArgs,
+) -> Result<Image, Error> {
+    let colors = args.validate()?;
+    let mut image = Image::filled(args.width, args.height, interpolate(colors, 0.0));
+    if waveform.num_frames() == 0 {
+        return Ok(image);
+    }
+    let stft = Stft::from_waveform(waveform, stft_args)?;
+    // A full-scale sine wave has a magnitude of half the window sum.
+    let window_length = if stft_args.window_length == 0 {
+        stft_args.fft_size
+    } else {
+        stft_args.window_length
+    };
+    let reference = window(stft_args.window_type, window_length)?
+        .iter()
+        .sum::<f32>()
+        / 2.0;
+    let num_bins = stft.num_bins();
+    let num_time_steps = stft.num_time_steps();
+    if num_time_steps == 0 {
+        return Ok(image);
+    }
+    let magnitudes = stft.magnitudes();
+    let num_channels = stft.num_channels();
+    for channel_idx in 0..num_channels {
+        let rows = lane(args.height, num_channels, channel_idx);
+        let lane_height = (rows.end - rows.start) as usize;
+        let channel_start = channel_idx as usize * num_bins * num_time_steps;
+        let channel = &magnitudes[channel_start..channel_start + num_bins * num_time_steps];
+        for (row_offset, y) in rows.enumerate() {
+            // The top row of the lane holds the highest frequencies.
+            let bins = slice_range(lane_height - 1 - row_offset, lane_height, num_bins);
+            for x in 0..args.width {
+                let time_steps = slice_range(x as usize, args.width as usize, num_time_steps);
+                let loudest = bins
+                    .clone()
+                    .flat_map(|bin_idx| {
+                        channel[bin_idx * num_time_steps..(bin_idx + 1) * num_time_steps]
+                            [time_steps.clone()]
+                        .iter()
+                    })
+                    .fold(0.0_f32, |loudest, magnitude| loudest.max(*magnitude));
+                let level = ratio_to_dbfs(loudest / reference);
+                let position = (level - args.min_db) / (args.max_db - args.min_db);
+                image.set_pixel(x, y, interpolate(colors, position));
+            }
+        }
+    }
+    Ok(image)
+}
@@ -0,0 +1,106 @@
+//! Functions for finding silence in audio.
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::loudness::dbfs_to_ratio;
+use crate::backend::units::{frames_to_milliseconds, milliseconds_to_frames};
+use crate::backend::Signal;
+use crate::backend::Waveform;
+
+/// A range of frames, from `start_frame_idx` up to but not including `end_frame_idx`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interval {
+    pub start_frame_idx: usize,
+    pub end_frame_idx: usize,
+}
+
+impl Interval {
+    /// The number of frames in the interval.
+    #[inline]
+    pub fn num_frames(&self) -> usize {
+        self.end_frame_idx - self.start_frame_idx
+    }
+
+    /// The start of the interval, in milliseconds.
+    #[inline]
+    pub fn start_milliseconds(&self, frame_rate_hz: u32) -> usize {
+        frames_to_milliseconds(self.start_frame_idx, frame_rate_hz)
+    }
+
+    /// The end of the interval, in milliseconds.
+    #[inline]
+    pub fn end_milliseconds(&self, frame_rate_hz: u32) -> usize {
+        frames_to_milliseconds(self.end_frame_idx, frame_rate_hz)
+    }
+}
+
+/// Returns `true` if every sample in the frame is at or below `threshold`,
+/// a linear amplitude.
+#[inline]
+pub(crate) fn is_silent_frame(frame: &[f32], threshold: f32) -> bool {
+    frame.iter().all(|sample| sample.abs() <= threshold)
+}
+
+/// Returns the intervals of a [`Waveform`] that are not silent.
+///
+/// A frame is silent when every channel is at or below `threshold_dbfs`.
+/// Inside the audio, a run of silent frames only splits two intervals
+/// if it lasts at least `min_silence_milliseconds`. Silence at the very
+/// start and end of the audio is always left out, however short it is.
+///
+/// # Examples
+/// ```
+/// use babycat::silence::{non_silent_intervals, Interval};
+/// use babycat::Waveform;
+///
+/// let samples = [0.0, 0.5, 0.5, 0.0, 0.5, 0.0, 0.0, 0.0, 0.5, 0.0];
+/// let waveform = Waveform::from_interleaved_samples(1000, 1, &samples);
+///
+/// // Only the three frames of silence are long enough to split the audio.
+/// let intervals = non_silent_intervals(&waveform, -60.0, 2);
+/// assert_eq!(
+///     intervals,
+///     &[
+///         Interval { start_frame_idx: 1, end_frame_idx: 5 },
+///         Interval { start_frame_idx: 8, end_frame_idx: 9 },
+///     ]
+/// );
+/// ```
+pub fn non_silent_intervals(
+    waveform: &Waveform,
+    threshold_dbfs: f32,
+    min_silence_milliseconds: usize,
+) -> Vec<Interval> {
+    let threshold = dbfs_to_ratio(threshold_dbfs);
+    let min_silence_frames = std::cmp::max(
+        milliseconds_to_frames(min_silence_milliseconds, waveform.frame_rate_hz()),
+        1,
+    );
+    let mut intervals: Vec<Interval> = Vec::new();
+    // The interval that we are in the middle of, if any.
+    let mut current: Option<Interval> = None;
+    let frames = waveform
+        .to_interleaved_samples()
+        .chunks(waveform.num_channels() as usize);
+    for (frame_idx, frame) in frames.enumerate() {
+        if is_silent_frame(frame, threshold) {
+            continue;
+        }
+        current = match current {
+            // The silence since the end of the current interval was too short.
+            Some(mut interval) if frame_idx - interval.end_frame_idx < min_silence_frames => {
+                interval.end_frame_idx = frame_idx + 1;
+                Some(interval)
+            }
+            previous => {
+                intervals.extend(previous);
+                Some(Interval {
+                    start_frame_idx: frame_idx,
+                    end_frame_idx: frame_idx + 1,
+                })
+            }
+        };
+    }
+    intervals.extend(current);
+    intervals
+}
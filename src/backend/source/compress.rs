@@ -0,0 +1,231 @@
+use crate::backend::display::est_num_frames_to_str;
+use crate::backend::loudness::{dbfs_to_ratio, ratio_to_dbfs};
+use crate::backend::source::dynamics::{read_frame, smoothing_coefficient, ChannelLink};
+use crate::backend::units::milliseconds_to_frames;
+use crate::backend::Signal;
+use crate::backend::Source;
+
+/// Settings for [`Source::compress()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CompressArgs {
+    /// The level, in dBFS, above which the audio is compressed.
+    pub threshold_dbfs: f32,
+    /// How many decibels the input has to rise above the threshold
+    /// for the output to rise by one decibel.
+    pub ratio: f32,
+    /// The width, in decibels, of the soft knee centered on the threshold.
+    /// A knee of `0.0` is a hard knee.
+    pub knee_db: f32,
+    /// How long it takes for the gain reduction to be applied.
+    pub attack_milliseconds: usize,
+    /// How long it takes for the gain reduction to be let go.
+    pub release_milliseconds: usize,
+    /// The gain, in decibels, applied after compression.
+    pub makeup_db: f32,
+    /// How the channels share gain reduction.
+    pub link: ChannelLink,
+}
+
+impl Default for CompressArgs {
+    fn default() -> Self {
+        CompressArgs {
+            threshold_dbfs: -20.0,
+            ratio: 4.0,
+            knee_db: 6.0,
+            attack_milliseconds: 10,
+            release_milliseconds: 100,
+            makeup_db: 0.0,
+            link: ChannelLink::Max,
+        }
+    }
+}
+
+impl CompressArgs {
+    /// Set the [`threshold_dbfs`](#structfield.threshold_dbfs) field.
+    #[must_use]
+    pub fn set_threshold_dbfs(&mut self, threshold_dbfs: f32) -> Self {
+        self.threshold_dbfs = threshold_dbfs;
+        *self
+    }
+
+    /// Set the [`ratio`](#structfield.ratio) field.
+    #[must_use]
+    pub fn set_ratio(&mut self, ratio: f32) -> Self {
+        self.ratio = ratio;
+        *self
+    }
+
+    /// Set the [`knee_db`](#structfield.knee_db) field.
+    #[must_use]
+    pub fn set_knee_db(&mut self, knee_db: f32) -> Self {
+        self.knee_db = knee_db;
+        *self
+    }
+
+    /// Set the [`attack_milliseconds`](#structfield.attack_milliseconds) field.
+    #[must_use]
+    pub fn set_attack_milliseconds(&mut self, attack_milliseconds: usize) -> Self {
+        self.attack_milliseconds = attack_milliseconds;
+        *self
+    }
+
+    /// Set the [`release_milliseconds`](#structfield.release_milliseconds) field.
+    #[must_use]
+    pub fn set_release_milliseconds(&mut self, release_milliseconds: usize) -> Self {
+        self.release_milliseconds = release_milliseconds;
+        *self
+    }
+
+    /// Set the [`makeup_db`](#structfield.makeup_db) field.
+    #[must_use]
+    pub fn set_makeup_db(&mut self, makeup_db: f32) -> Self {
+        self.makeup_db = makeup_db;
+        *self
+    }
+
+    /// Set the [`link`](#structfield.link) field.
+    #[must_use]
+    pub fn set_link(&mut self, link: ChannelLink) -> Self {
+        self.link = link;
+        *self
+    }
+
+    /// Returns how many decibels a signal at `level_dbfs` should be lowered by.
+    #[inline]
+    fn gain_reduction_db(&self, level_dbfs: f32) -> f32 {
+        let slope = 1.0 - 1.0 / self.ratio.max(1.0);
+        let over_db = level_dbfs - self.threshold_dbfs;
+        let half_knee_db = self.knee_db.max(0.0) / 2.0;
+        if over_db <= -half_knee_db {
+            0.0
+        } else if over_db < half_knee_db {
+            // Inside the knee, the reduction grows quadratically.
+            slope * (over_db + half_knee_db).powi(2) / (4.0 * half_knee_db)
+        } else {
+            slope * over_db
+        }
+    }
+}
+
+/// [`Source::compress()`]
+///
+/// This is a feed-forward compressor. The level of every frame goes
+/// through a static gain curve, and the resulting gain reduction is
+/// smoothed with separate attack and release times.
+pub struct Compress<S: Source> {
+    iter: S,
+    args: CompressArgs,
+    attack_coef: f32,
+    release_coef: f32,
+    /// The current gain reduction of each level detector, in decibels.
+    reduction_db: Vec<f32>,
+    levels: Vec<f32>,
+    frame: Vec<f32>,
+    frame_idx: usize,
+}
+
+impl<S: Source> std::fmt::Debug for Compress<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Compress {{ {} frames,  {} channels,  {} hz,  {}; threshold: {} dbfs,  ratio: {} }}",
+            est_num_frames_to_str(self.num_frames_estimate()),
+            self.num_channels(),
+            self.frame_rate_hz(),
+            self.duration_estimate_to_str(),
+            self.args.threshold_dbfs,
+            self.args.ratio,
+        )
+    }
+}
+
+impl<S: Source> Compress<S> {
+    #[inline]
+    pub fn new(iter: S, args: CompressArgs) -> Self {
+        let frame_rate_hz = iter.frame_rate_hz();
+        let num_channels = iter.num_channels() as usize;
+        let num_detectors = args.link.num_detectors(num_channels);
+        Self {
+            iter,
+            args,
+            attack_coef: smoothing_coefficient(milliseconds_to_frames(
+                args.attack_milliseconds,
+                frame_rate_hz,
+            )),
+            release_coef: smoothing_coefficient(milliseconds_to_frames(
+                args.release_milliseconds,
+                frame_rate_hz,
+            )),
+            reduction_db: vec![0.0; num_detectors],
+            levels: vec![0.0; num_detectors],
+            frame: Vec::with_capacity(num_channels),
+            frame_idx: num_channels,
+        }
+    }
+
+    /// Reads the next frame and fills `self.frame` with output samples.
+    /// Returns `false` once the input has ended.
+    fn fill_frame(&mut self) -> bool {
+        if !read_frame(&mut self.iter, &mut self.frame) {
+            return false;
+        }
+        self.args.link.detect(&self.frame, &mut self.levels);
+        for (reduction_db, &level) in self.reduction_db.iter_mut().zip(&self.levels) {
+            let target_db = self.args.gain_reduction_db(ratio_to_dbfs(level));
+            let coef = if target_db > *reduction_db {
+                self.attack_coef
+            } else {
+                self.release_coef
+            };
+            *reduction_db = target_db + coef * (*reduction_db - target_db);
+        }
+        for (channel_idx, sample) in self.frame.iter_mut().enumerate() {
+            let reduction_db = self.reduction_db[self.args.link.detector_idx(channel_idx)];
+            *sample *= dbfs_to_ratio(self.args.makeup_db - reduction_db);
+        }
+        true
+    }
+}
+
+impl<S: Source> Source for Compress<S> {}
+
+impl<S: Source> Signal for Compress<S> {
+    #[inline]
+    fn frame_rate_hz(&self) -> u32 {
+        self.iter.frame_rate_hz()
+    }
+
+    #[inline]
+    fn num_channels(&self) -> u16 {
+        self.iter.num_channels()
+    }
+
+    #[inline]
+    fn num_frames_estimate(&self) -> Option<usize> {
+        self.iter.num_frames_estimate()
+    }
+}
+
+impl<S: Source> Iterator for Compress<S> {
+    type Item = f32;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.frame.len() - std::cmp::min(self.frame_idx, self.frame.len());
+        let (lower, upper) = self.iter.size_hint();
+        (lower + buffered, upper.map(|u| u + buffered))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.frame_idx >= self.frame.len() {
+            if !self.fill_frame() {
+                return None;
+            }
+            self.frame_idx = 0;
+        }
+        let val = self.frame[self.frame_idx];
+        self.frame_idx += 1;
+        Some(val)
+    }
+}
@@ -0,0 +1,310 @@
+use std::sync::Arc;
+
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+
+use crate::backend::display::est_num_frames_to_str;
+use crate::backend::Error;
+use crate::backend::Signal;
+use crate::backend::Source;
+use crate::backend::Waveform;
+
+/// The smallest and largest partition sizes, in frames.
+const MIN_BLOCK_FRAMES: usize = 64;
+const MAX_BLOCK_FRAMES: usize = 4096;
+
+/// Sends one input channel through one impulse response channel into one output channel.
+#[derive(Clone, Copy, Debug)]
+struct Route {
+    input: usize,
+    ir: usize,
+    output: usize,
+}
+
+/// Works out how the input channels are routed through the impulse response channels.
+fn routes(num_channels: u16, ir_num_channels: u16) -> Result<Vec<Route>, Error> {
+    let route = |input, ir, output| Route { input, ir, output };
+    let channels = 0..num_channels as usize;
+    match ir_num_channels {
+        // A mono impulse response is applied to every channel.
+        1 => Ok(channels.map(|c| route(c, 0, c)).collect()),
+        // One impulse response channel per input channel.
+        n if n == num_channels => Ok(channels.map(|c| route(c, c, c)).collect()),
+        // A true-stereo impulse response in L->L, L->R, R->L, R->R order.
+        4 if num_channels == 2 => Ok(vec![
+            route(0, 0, 0),
+            route(0, 1, 1),
+            route(1, 2, 0),
+            route(1, 3, 1),
+        ]),
+        _ => Err(Error::CannotConvolveWithImpulseResponseChannels(
+            num_channels,
+            ir_num_channels,
+        )),
+    }
+}
+
+/// [`Source::convolve()`]
+///
+/// This is a uniformly-partitioned overlap-add convolution. The impulse
+/// response is split into blocks that are transformed once up front, and
+/// the spectra of recent input blocks are kept in a frequency-domain delay line.
+/// The output is aligned with the input, and the full tail of the impulse
+/// response is played after the input ends.
+pub struct Convolve<S: Source> {
+    iter: S,
+    wet_dry: f32,
+    routes: Vec<Route>,
+    ir_num_frames: usize,
+    block_frames: usize,
+    fft: Arc<dyn Fft<f32>>,
+    ifft: Arc<dyn Fft<f32>>,
+    /// `ir_spectra[ir_channel][partition_idx]`
+    ir_spectra: Vec<Vec<Vec<Complex<f32>>>>,
+    /// `input_spectra[input_channel][partition_idx]`, most recent block first.
+    input_spectra: Vec<Vec<Vec<Complex<f32>>>>,
+    /// The second half of each output channel's last inverse transform.
+    overlap: Vec<Vec<f32>>,
+    scratch: Vec<Complex<f32>>,
+    accumulator: Vec<Complex<f32>>,
+    /// Interleaved output samples for the current block.
+    buffer: Vec<f32>,
+    buffer_idx: usize,
+    input_drained: bool,
+    input_frames_read: usize,
+    frames_emitted: usize,
+}
+
+impl<S: Source> std::fmt::Debug for Convolve<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Convolve {{ {} frames,  {} channels,  {} hz,  {}; {} frame impulse response }}",
+            est_num_frames_to_str(self.num_frames_estimate()),
+            self.num_channels(),
+            self.frame_rate_hz(),
+            self.duration_estimate_to_str(),
+            self.ir_num_frames,
+        )
+    }
+}
+
+impl<S: Source> Convolve<S> {
+    pub fn new(iter: S, ir: &Waveform, wet_dry: f32) -> Result<Self, Error> {
+        if ir.frame_rate_hz() != iter.frame_rate_hz() {
+            return Err(Error::CannotConvolveWithDifferentFrameRates(
+                iter.frame_rate_hz(),
+                ir.frame_rate_hz(),
+            ));
+        }
+        let routes = routes(iter.num_channels(), ir.num_channels())?;
+        let num_channels = iter.num_channels() as usize;
+        let ir_num_channels = ir.num_channels() as usize;
+        let ir_num_frames = ir.num_frames();
+
+        let block_frames = ir_num_frames
+            .next_power_of_two()
+            .clamp(MIN_BLOCK_FRAMES, MAX_BLOCK_FRAMES);
+        let fft_len = 2 * block_frames;
+        let num_partitions = ir_num_frames.div_ceil(block_frames);
+        let mut planner = FftPlanner::new();
+        let fft = planner.plan_fft_forward(fft_len);
+        let ifft = planner.plan_fft_inverse(fft_len);
+
+        // Transform every partition of every impulse response channel.
+        let ir_samples = ir.to_interleaved_samples();
+        let mut ir_spectra = vec![Vec::with_capacity(num_partitions); ir_num_channels];
+        for (ir_channel, spectra) in ir_spectra.iter_mut().enumerate() {
+            for partition_idx in 0..num_partitions {
+                let start = partition_idx * block_frames;
+                let end = std::cmp::min(start + block_frames, ir_num_frames);
+                let mut spectrum = vec![Complex::new(0.0, 0.0); fft_len];
+                for (frame_idx, value) in (start..end).zip(spectrum.iter_mut()) {
+                    value.re = ir_samples[frame_idx * ir_num_channels + ir_channel];
+                }
+                fft.process(&mut spectrum);
+                spectra.push(spectrum);
+            }
+        }
+
+        Ok(Self {
+            iter,
+            wet_dry: wet_dry.clamp(0.0, 1.0),
+            routes,
+            ir_num_frames,
+            block_frames,
+            input_spectra: vec![
+                vec![vec![Complex::new(0.0, 0.0); fft_len]; num_partitions];
+                num_channels
+            ],
+            overlap: vec![vec![0.0; block_frames]; num_channels],
+            scratch: vec![Complex::new(0.0, 0.0); fft_len],
+            accumulator: vec![Complex::new(0.0, 0.0); fft_len],
+            buffer: Vec::with_capacity(block_frames * num_channels),
+            buffer_idx: 0,
+            input_drained: false,
+            input_frames_read: 0,
+            frames_emitted: 0,
+            fft,
+            ifft,
+            ir_spectra,
+        })
+    }
+
+    /// The number of frames of tail that follow the last input frame.
+    #[inline]
+    fn tail_frames(&self) -> usize {
+        self.ir_num_frames.saturating_sub(1)
+    }
+
+    /// Reads the next block of input and fills `self.buffer` with output samples.
+    /// Returns `false` once the input and the tail have both run out.
+    #[allow(clippy::cast_precision_loss)]
+    fn fill_buffer(&mut self) -> bool {
+        let num_channels = self.num_channels() as usize;
+        let block_frames = self.block_frames;
+
+        // Read one block of dry input, padding with silence once the input ends.
+        let mut dry = vec![0.0_f32; block_frames * num_channels];
+        let mut block_samples_read: usize = 0;
+        if !self.input_drained {
+            for sample in &mut dry {
+                if let Some(val) = self.iter.next() {
+                    *sample = val;
+                    block_samples_read += 1;
+                } else {
+                    self.input_drained = true;
+                    break;
+                }
+            }
+        }
+        self.input_frames_read += block_samples_read.div_ceil(num_channels);
+        if self.input_drained {
+            let total_frames = self.input_frames_read + self.tail_frames();
+            if self.frames_emitted >= total_frames {
+                return false;
+            }
+        }
+
+        // Push each input channel's spectrum onto the front of its delay line.
+        for (input_channel, spectra) in self.input_spectra.iter_mut().enumerate() {
+            if spectra.is_empty() {
+                continue;
+            }
+            spectra.rotate_right(1);
+            let spectrum = &mut spectra[0];
+            for (frame_idx, value) in spectrum.iter_mut().enumerate() {
+                *value = if frame_idx < block_frames {
+                    Complex::new(dry[frame_idx * num_channels + input_channel], 0.0)
+                } else {
+                    Complex::new(0.0, 0.0)
+                };
+            }
+            self.fft.process(spectrum);
+        }
+
+        // Multiply and accumulate every route, one output channel at a time.
+        let scale = 1.0 / (2 * block_frames) as f32;
+        let wet_gain = self.wet_dry;
+        let dry_gain = 1.0 - self.wet_dry;
+        let mut wet = vec![0.0_f32; block_frames * num_channels];
+        for output_channel in 0..num_channels {
+            self.accumulator
+                .iter_mut()
+                .for_each(|value| *value = Complex::new(0.0, 0.0));
+            for route in self.routes.iter().filter(|r| r.output == output_channel) {
+                let inputs = &self.input_spectra[route.input];
+                let irs = &self.ir_spectra[route.ir];
+                for (input, ir) in inputs.iter().zip(irs.iter()) {
+                    for ((acc, x), h) in self.accumulator.iter_mut().zip(input).zip(ir) {
+                        *acc += x * h;
+                    }
+                }
+            }
+            self.scratch.copy_from_slice(&self.accumulator);
+            self.ifft.process(&mut self.scratch);
+            let overlap = &mut self.overlap[output_channel];
+            for frame_idx in 0..block_frames {
+                wet[frame_idx * num_channels + output_channel] =
+                    self.scratch[frame_idx].re * scale + overlap[frame_idx];
+                overlap[frame_idx] = self.scratch[frame_idx + block_frames].re * scale;
+            }
+        }
+
+        // Mix, and only keep as many frames as are left in the output.
+        let frames_left = if self.input_drained {
+            self.input_frames_read + self.tail_frames() - self.frames_emitted
+        } else {
+            block_frames
+        };
+        let num_frames = std::cmp::min(block_frames, frames_left);
+        self.buffer.clear();
+        self.buffer.extend(
+            dry.iter()
+                .zip(wet.iter())
+                .take(num_frames * num_channels)
+                .map(|(d, w)| d * dry_gain + w * wet_gain),
+        );
+        self.buffer_idx = 0;
+        self.frames_emitted += num_frames;
+        true
+    }
+}
+
+impl<S: Source> Source for Convolve<S> {}
+
+impl<S: Source> Signal for Convolve<S> {
+    #[inline]
+    fn frame_rate_hz(&self) -> u32 {
+        self.iter.frame_rate_hz()
+    }
+
+    #[inline]
+    fn num_channels(&self) -> u16 {
+        self.iter.num_channels()
+    }
+
+    #[inline]
+    fn num_frames_estimate(&self) -> Option<usize> {
+        self.iter
+            .num_frames_estimate()
+            .map(|n| n + self.tail_frames())
+    }
+}
+
+impl<S: Source> Iterator for Convolve<S> {
+    type Item = f32;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let num_channels = self.num_channels() as usize;
+        let buffered = self.buffer.len() - self.buffer_idx;
+        // Samples that will come from frames we have read but not yet emitted.
+        let pending = (self.input_frames_read + self.tail_frames())
+            .saturating_sub(self.frames_emitted)
+            * num_channels;
+        if self.input_drained {
+            return (buffered + pending, Some(buffered + pending));
+        }
+        let (lower, upper) = self.iter.size_hint();
+        (
+            buffered + pending + lower,
+            upper.map(|u| buffered + pending + u),
+        )
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer_idx >= self.buffer.len() {
+            if !self.fill_buffer() {
+                return None;
+            }
+            if self.buffer.is_empty() {
+                return None;
+            }
+        }
+        let val = self.buffer[self.buffer_idx];
+        self.buffer_idx += 1;
+        Some(val)
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::VecDeque;
+
+use crate::backend::display::est_num_frames_to_str;
+use crate::backend::source::FadeCurve;
+use crate::backend::units::frames_to_samples;
+use crate::backend::Error;
+use crate::backend::Signal;
+use crate::backend::Source;
+
+/// [`Source::append_with_crossfade()`]
+pub struct Crossfade<S1: Source, S2: Source> {
+    first: S1,
+    second: S2,
+    curve: FadeCurve,
+    fade_num_samples: usize,
+    buffer: VecDeque<f32>,
+    first_drained: bool,
+    overlap_num_samples: usize,
+    overlap_current_sample: usize,
+}
+
+impl<S1: Source, S2: Source> std::fmt::Debug for Crossfade<S1, S2> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Crossfade {{ {} + {} = {} frames,  {} channels,  {} hz,  {}; curve: {:?} }}",
+            est_num_frames_to_str(self.first.num_frames_estimate()),
+            est_num_frames_to_str(self.second.num_frames_estimate()),
+            est_num_frames_to_str(self.num_frames_estimate()),
+            self.num_channels(),
+            self.frame_rate_hz(),
+            self.duration_estimate_to_str(),
+            self.curve,
+        )
+    }
+}
+
+impl<S1: Source, S2: Source> Crossfade<S1, S2> {
+    #[inline]
+    pub fn new(first: S1, second: S2, num_frames: usize, curve: FadeCurve) -> Result<Self, Error> {
+        let f_nc = first.num_channels();
+        let s_nc = second.num_channels();
+        if f_nc != s_nc {
+            return Err(Error::CannotAppendSourcesWithDifferentNumChannels(
+                f_nc, s_nc,
+            ));
+        }
+        let f_fr = first.frame_rate_hz();
+        let s_fr = second.frame_rate_hz();
+        if f_fr != s_fr {
+            return Err(Error::CannotAppendSourcesWithDifferentFrameRates(
+                f_fr, s_fr,
+            ));
+        }
+        let fade_num_samples = frames_to_samples(num_frames, f_nc);
+        Ok(Self {
+            first,
+            second,
+            curve,
+            fade_num_samples,
+            buffer: VecDeque::with_capacity(fade_num_samples),
+            first_drained: false,
+            overlap_num_samples: 0,
+            overlap_current_sample: 0,
+        })
+    }
+
+    /// Pulls from the first [`Source`] until it is drained or the buffer holds
+    /// `num_frames` frames of it. Returns a sample that is definitely not part
+    /// of the crossfade, if there is one.
+    #[inline]
+    fn next_from_first(&mut self) -> Option<f32> {
+        while !self.first_drained && self.buffer.len() < self.fade_num_samples {
+            match self.first.next() {
+                Some(val) => self.buffer.push_back(val),
+                None => self.first_drained = true,
+            }
+        }
+        if self.first_drained {
+            return None;
+        }
+        if let Some(val) = self.first.next() {
+            self.buffer.push_back(val);
+            return self.buffer.pop_front();
+        }
+        self.first_drained = true;
+        None
+    }
+}
+
+impl<S1: Source, S2: Source> Source for Crossfade<S1, S2> {}
+
+impl<S1: Source, S2: Source> Signal for Crossfade<S1, S2> {
+    #[inline]
+    fn frame_rate_hz(&self) -> u32 {
+        self.first.frame_rate_hz()
+    }
+
+    #[inline]
+    fn num_channels(&self) -> u16 {
+        self.first.num_channels()
+    }
+
+    #[inline]
+    fn num_frames_estimate(&self) -> Option<usize> {
+        let fade_num_frames = self.fade_num_samples / self.num_channels() as usize;
+        match (
+            self.first.num_frames_estimate(),
+            self.second.num_frames_estimate(),
+        ) {
+            (Some(first), Some(second)) => {
+                let overlap = std::cmp::min(std::cmp::min(first, second), fade_num_frames);
+                Some(first + second - overlap)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<S1: Source, S2: Source> Iterator for Crossfade<S1, S2> {
+    type Item = f32;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (first_lower, first_upper) = self.first.size_hint();
+        let (second_lower, second_upper) = self.second.size_hint();
+        let first_lower = first_lower + self.buffer.len();
+        let first_upper = first_upper.map(|u| u + self.buffer.len());
+        let lower = std::cmp::max(
+            first_lower + second_lower.saturating_sub(self.fade_num_samples),
+            second_lower,
+        );
+        let upper = match (first_upper, second_upper) {
+            (Some(first), Some(second)) => Some(first + second),
+            _ => None,
+        };
+        (lower, upper)
+    }
+
+    #[inline]
+    #[allow(clippy::cast_precision_loss)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.first_drained {
+            if let Some(val) = self.next_from_first() {
+                return Some(val);
+            }
+            // Whatever is left in the buffer overlaps with the start of `second`.
+            self.overlap_num_samples = self.buffer.len();
+        }
+        match self.buffer.pop_front() {
+            None => self.second.next(),
+            Some(first_val) => {
+                let num_channels = self.num_channels() as usize;
+                let frame_idx = self.overlap_current_sample / num_channels;
+                let overlap_num_frames = self.overlap_num_samples / num_channels;
+                self.overlap_current_sample += 1;
+                let t = frame_idx as f32 / overlap_num_frames as f32;
+                let second_val = self.second.next().unwrap_or(0.0_f32);
+                Some(first_val * self.curve.gain(1.0 - t) + second_val * self.curve.gain(t))
+            }
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use crate::backend::display::est_num_frames_to_str;
+use crate::backend::source::dynamics::smoothing_coefficient;
+use crate::backend::units::milliseconds_to_frames;
+use crate::backend::Error;
+use crate::backend::Signal;
+use crate::backend::Source;
+
+/// How much [`Source::duck()`] lowers the program while the sidechain is active.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DuckAmount {
+    /// Lower the program by a fixed number of decibels whenever the
+    /// sidechain is above the threshold.
+    Depth(f32),
+    /// Lower the program by `(sidechain_dbfs - threshold_dbfs) * (1 - 1 / ratio)`
+    /// decibels, the way a compressor keyed from the sidechain would.
+    Ratio(f32),
+}
+
+/// What [`Source::duck()`] should output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DuckOutput {
+    /// Only the ducked program.
+    Ducked,
+    /// The ducked program summed with the sidechain.
+    /// This requires both inputs to have the same number of channels.
+    Mix,
+}
+
+/// Settings for [`Source::duck()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DuckArgs {
+    /// The sidechain level, in dBFS, above which the program is lowered.
+    pub threshold_dbfs: f32,
+    /// How long it takes for the sidechain level detector to rise to a new peak.
+    pub detector_attack_milliseconds: usize,
+    /// How long it takes for the sidechain level detector to fall after a peak.
+    /// This keeps the level steady between the peaks of each waveform cycle.
+    pub detector_release_milliseconds: usize,
+    /// How far to lower the program.
+    pub amount: DuckAmount,
+    /// How long it takes for the program to be lowered.
+    pub attack_milliseconds: usize,
+    /// How long it takes for the program to come back up.
+    pub release_milliseconds: usize,
+    /// How long to keep the program lowered after the sidechain
+    /// drops below the threshold, before releasing.
+    pub hold_milliseconds: usize,
+    /// Whether to output the ducked program or the finished mix.
+    pub output: DuckOutput,
+}
+
+impl Default for DuckArgs {
+    fn default() -> Self {
+        DuckArgs {
+            threshold_dbfs: -30.0,
+            detector_attack_milliseconds: 0,
+            detector_release_milliseconds: 100,
+            amount: DuckAmount::Depth(12.0),
+            attack_milliseconds: 10,
+            release_milliseconds: 300,
+            hold_milliseconds: 100,
+            output: DuckOutput::Ducked,
+        }
+    }
+}
+
+impl DuckArgs {
+    /// Set the [`threshold_dbfs`](#structfield.threshold_dbfs) field.
+    #[must_use]
+    pub fn set_threshold_dbfs(&mut self, threshold_dbfs: f32) -> Self {
+        self.threshold_dbfs = threshold_dbfs;
+        *self
+    }
+
+    /// Set the [`detector_attack_milliseconds`](#structfield.detector_attack_milliseconds) field.
+    #[must_use]
+    pub fn set_detector_attack_milliseconds(
+        &mut self,
+        detector_attack_milliseconds: usize,
+    ) -> Self {
+        self.detector_attack_milliseconds = detector_attack_milliseconds;
+        *self
+    }
+
+    /// Set the [`detector_release_milliseconds`](#structfield.detector_release_milliseconds) field.
+    #[must_use]
+    pub fn set_detector_release_milliseconds(
+        &mut self,
+        detector_release_milliseconds: usize,
+    ) -> Self {
+        self.detector_release_milliseconds = detector_release_milliseconds;
+        *self
+    }
+
+    /// Set the [`amount`](#structfield.amount) field.
+    #[must_use]
+    pub fn set_amount(&mut self, amount: DuckAmount) -> Self {
+        self.amount = amount;
+        *self
+    }
+
+    /// Set the [`attack_milliseconds`](#structfield.attack_milliseconds) field.
+    #[must_use]
+    pub fn set_attack_milliseconds(&mut self, attack_milliseconds: usize) -> Self {
+        self.attack_milliseconds = attack_milliseconds;
+        *self
+    }
+
+    /// Set the [`release_milliseconds`](#structfield.release_milliseconds) field.
+    #[must_use]
+    pub fn set_release_milliseconds(&mut self, release_milliseconds: usize) -> Self {
+        self.release_milliseconds = release_milliseconds;
+        *self
+    }
+
+    /// Set the [`hold_milliseconds`](#structfield.hold_milliseconds) field.
+    #[must_use]
+    pub fn set_hold_milliseconds(&mut self, hold_milliseconds: usize) -> Self {
+        self.hold_milliseconds = hold_milliseconds;
+        *self
+    }
+
+    /// Set the [`output`](#structfield.output) field.
+    #[must_use]
+    pub fn set_output(&mut self, output: DuckOutput) -> Self {
+        self.output = output;
+        *self
+    }
+}
+
+/// [`Source::duck()`]
+pub struct Duck<P: Source, S: Source> {
+    program: P,
+    sidechain: S,
+    args: DuckArgs,
+    detector_attack_coef: f32,
+    detector_release_coef: f32,
+    /// The smoothed peak level of the sidechain, as a linear amplitude.
+    sidechain_level: f32,
+    attack_coef: f32,
+    release_coef: f32,
+    hold_frames: usize,
+    hold_remaining: usize,
+    target_db: f32,
+    reduction_db: f32,
+    program_drained: bool,
+    sidechain_drained: bool,
+    frame: Vec<f32>,
+    sidechain_frame: Vec<f32>,
+    frame_idx: usize,
+}
+
+impl<P: Source, S: Source> std::fmt::Debug for Duck<P, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Duck {{ {} frames,  {} channels,  {} hz,  {}; threshold: {} dbfs }}",
+            est_num_frames_to_str(self.num_frames_estimate()),
+            self.num_channels(),
+            self.frame_rate_hz(),
+            self.duration_estimate_to_str(),
+            self.args.threshold_dbfs,
+        )
+    }
+}
+
+impl<P: Source, S: Source> Duck<P, S> {
+    #[inline]
+    pub fn new(program: P, sidechain: S, args: DuckArgs) -> Result<Self, Error> {
+        let frame_rate_hz = program.frame_rate_hz();
+        if sidechain.frame_rate_hz() != frame_rate_hz {
+            return Err(Error::CannotMixSourcesWithDifferentFrameRates(
+                frame_rate_hz,
+                sidechain.frame_rate_hz(),
+            ));
+        }
+        if args.output == DuckOutput::Mix && sidechain.num_channels() != program.num_channels() {
+            return Err(Error::CannotMixSourcesWithDifferentNumChannels(
+                program.num_channels(),
+                sidechain.num_channels(),
+            ));
+        }
+        let num_channels = program.num_channels() as usize;
+        let sidechain_num_channels = sidechain.num_channels() as usize;
+        Ok(Self {
+            program,
+            sidechain,
+            args,
+            detector_attack_coef: smoothing_coefficient(milliseconds_to_frames(
+                args.detector_attack_milliseconds,
+                frame_rate_hz,
+            )),
+            detector_release_coef: smoothing_coefficient(milliseconds_to_frames(
+                args.detector_release_milliseconds,
+                frame_rate_hz,
+            )),
+            sidechain_level: 0.0,
+            attack_coef: smoothing_coefficient(milliseconds_to_frames(
+                args.attack_milliseconds,
+                frame_rate_hz,
+            )),
+            release_coef: smoothing_coefficient(milliseconds_to_frames(
+                args.release_milliseconds,
+                frame_rate_hz,
+            )),
+            hold_frames: milliseconds_to_frames(args.hold_milliseconds, frame_rate_hz),
+            hold_remaining: 0,
+            target_db: 0.0,
+            reduction_db: 0.0,
+            program_drained: false,
+            sidechain_drained: false,
+            frame: Vec::with_capacity(num_channels),
+            sidechain_frame: vec![0.0; sidechain_num_channels],
+            frame_idx: num_channels,
+        })
+    }
+
+    /// Returns how many decibels the program should be lowered by
+    /// for the given sidechain level.
+    #[inline]
+    fn target_reduction_db(&self, sidechain_dbfs: f32) -> f32 {
+        if sidechain_dbfs <= self.args.threshold_dbfs {
+            return 0.0;
+        }
+        match self.args.amount {
+            DuckAmount::Depth(depth) => depth.max(0.0),
+            DuckAmount::Ratio(ratio) => {
+                let ratio = ratio.max(1.0);
+                (sidechain_dbfs - self.args.threshold_dbfs) * (1.0 - 1.0 / ratio)
+            }
+        }
+    }
+
+    /// Reads the next frame from both inputs and fills `self.frame` with output samples.
+    /// Returns `false` once the output has ended.
+    fn fill_frame(&mut self) -> bool {
+        self.frame.clear();
+        let num_channels = self.program.num_channels() as usize;
+        if !self.program_drained {
+            for _ in 0..num_channels {
+                if let Some(val) = self.program.next() {
+                    self.frame.push(val);
+                } else {
+                    self.program_drained = true;
+                    break;
+                }
+            }
+        }
+        let mut sidechain_peak: f32 = 0.0;
+        for sample in &mut self.sidechain_frame {
+            *sample = if self.sidechain_drained {
+                0.0
+            } else if let Some(val) = self.sidechain.next() {
+                val
+            } else {
+                self.sidechain_drained = true;
+                0.0
+            };
+            sidechain_peak = sidechain_peak.max(sample.abs());
+        }
+        let program_ended = self.program_drained && self.frame.len() < num_channels;
+        let output_ended = match self.args.output {
+            DuckOutput::Ducked => program_ended,
+            DuckOutput::Mix => program_ended && self.sidechain_drained,
+        };
+        if output_ended && self.frame.is_empty() {
+            return false;
+        }
+        self.frame.resize(num_channels, 0.0);
+
+        // Follow the peaks of the sidechain, so that its level does not
+        // drop to nothing at every zero crossing.
+        let detector_coef = if sidechain_peak > self.sidechain_level {
+            self.detector_attack_coef
+        } else {
+            self.detector_release_coef
+        };
+        self.sidechain_level =
+            sidechain_peak + detector_coef * (self.sidechain_level - sidechain_peak);
+
+        // Work out how far to lower the program, holding the previous
+        // reduction for a while after the sidechain goes quiet.
+        let target = self.target_reduction_db(20.0 * self.sidechain_level.log10());
+        if target > 0.0 {
+            self.target_db = target;
+            self.hold_remaining = self.hold_frames;
+        } else if self.hold_remaining > 0 {
+            self.hold_remaining -= 1;
+        } else {
+            self.target_db = 0.0;
+        }
+        let coef = if self.target_db > self.reduction_db {
+            self.attack_coef
+        } else {
+            self.release_coef
+        };
+        self.reduction_db = self.target_db + coef * (self.reduction_db - self.target_db);
+        let ratio = (10.0_f32).powf(-self.reduction_db / 20.0_f32);
+
+        for (channel_idx, sample) in self.frame.iter_mut().enumerate() {
+            *sample *= ratio;
+            if self.args.output == DuckOutput::Mix {
+                *sample += self.sidechain_frame[channel_idx];
+            }
+        }
+        true
+    }
+}
+
+impl<P: Source, S: Source> Source for Duck<P, S> {}
+
+impl<P: Source, S: Source> Signal for Duck<P, S> {
+    #[inline]
+    fn frame_rate_hz(&self) -> u32 {
+        self.program.frame_rate_hz()
+    }
+
+    #[inline]
+    fn num_channels(&self) -> u16 {
+        self.program.num_channels()
+    }
+
+    #[inline]
+    fn num_frames_estimate(&self) -> Option<usize> {
+        match self.args.output {
+            DuckOutput::Ducked => self.program.num_frames_estimate(),
+            DuckOutput::Mix => match (
+                self.program.num_frames_estimate(),
+                self.sidechain.num_frames_estimate(),
+            ) {
+                (Some(program), Some(sidechain)) => Some(std::cmp::max(program, sidechain)),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl<P: Source, S: Source> Iterator for Duck<P, S> {
+    type Item = f32;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.frame.len() - std::cmp::min(self.frame_idx, self.frame.len());
+        let (program_lower, program_upper) = self.program.size_hint();
+        match self.args.output {
+            DuckOutput::Ducked => (
+                program_lower + buffered,
+                program_upper.map(|u| u + buffered),
+            ),
+            DuckOutput::Mix => {
+                let (sidechain_lower, sidechain_upper) = self.sidechain.size_hint();
+                let lower = std::cmp::max(program_lower, sidechain_lower) + buffered;
+                let upper = match (program_upper, sidechain_upper) {
+                    (Some(p), Some(s)) => Some(std::cmp::max(p, s) + buffered),
+                    _ => None,
+                };
+                (lower, upper)
+            }
+        }
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.frame_idx >= self.frame.len() {
+            if !self.fill_frame() {
+                return None;
+            }
+            self.frame_idx = 0;
+        }
+        let val = self.frame[self.frame_idx];
+        self.frame_idx += 1;
+        Some(val)
+    }
+}
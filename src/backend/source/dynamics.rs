@@ -0,0 +1,85 @@
+use crate::backend::Source;
+
+/// How [`Source::compress()`], [`Source::limit()`], and [`Source::gate()`]
+/// share gain changes between the channels of a [`Source`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChannelLink {
+    /// Every channel has its own level detector and its own gain.
+    Independent,
+    /// Every channel follows the loudest channel. This keeps the stereo image steady.
+    #[default]
+    Max,
+    /// Every channel follows the average level of all the channels.
+    Average,
+}
+
+impl ChannelLink {
+    /// Returns how many level detectors are needed for `num_channels` channels.
+    #[inline]
+    pub(crate) fn num_detectors(self, num_channels: usize) -> usize {
+        match self {
+            ChannelLink::Independent => num_channels,
+            ChannelLink::Max | ChannelLink::Average => 1,
+        }
+    }
+
+    /// Returns which level detector controls the gain of the given channel.
+    #[inline]
+    pub(crate) fn detector_idx(self, channel_idx: usize) -> usize {
+        match self {
+            ChannelLink::Independent => channel_idx,
+            ChannelLink::Max | ChannelLink::Average => 0,
+        }
+    }
+
+    /// Writes the linear level seen by each level detector for one frame.
+    #[inline]
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn detect(self, frame: &[f32], levels: &mut [f32]) {
+        match self {
+            ChannelLink::Independent => {
+                for (level, sample) in levels.iter_mut().zip(frame) {
+                    *level = sample.abs();
+                }
+            }
+            ChannelLink::Max => {
+                levels[0] = frame.iter().fold(0.0_f32, |acc, s| acc.max(s.abs()));
+            }
+            ChannelLink::Average => {
+                levels[0] = frame.iter().map(|s| s.abs()).sum::<f32>() / frame.len() as f32;
+            }
+        }
+    }
+}
+
+/// Returns the per-frame smoothing coefficient for a one-pole filter
+/// that settles in roughly `num_frames` frames.
+#[inline]
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn smoothing_coefficient(num_frames: usize) -> f32 {
+    if num_frames == 0 {
+        return 0.0;
+    }
+    (-1.0 / num_frames as f32).exp()
+}
+
+/// Reads the next frame from `iter` into `frame`.
+///
+/// A partial frame at the end of `iter` is padded with silence.
+/// Returns `false` once `iter` has run out.
+#[inline]
+pub(crate) fn read_frame<S: Source>(iter: &mut S, frame: &mut Vec<f32>) -> bool {
+    let num_channels = iter.num_channels() as usize;
+    frame.clear();
+    for _ in 0..num_channels {
+        match iter.next() {
+            Some(val) => frame.push(val),
+            None => break,
+        }
+    }
+    if frame.is_empty() {
+        return false;
+    }
+    frame.resize(num_channels, 0.0);
+    true
+}
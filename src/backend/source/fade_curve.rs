@@ -0,0 +1,55 @@
+use std::f32::consts::FRAC_PI_2;
+use std::f32::consts::PI;
+
+/// The dynamic range covered by [`FadeCurve::Logarithmic`], in decibels.
+const LOGARITHMIC_FADE_RANGE_DB: f32 = 60.0;
+
+/// The shape of a fade, as used by [`Source::fade_in()`](crate::Source::fade_in),
+/// [`Source::fade_out()`](crate::Source::fade_out), and
+/// [`Source::append_with_crossfade()`](crate::Source::append_with_crossfade).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FadeCurve {
+    /// The gain rises in a straight line.
+    Linear,
+    /// The gain follows a quarter sine wave. Crossfading two uncorrelated
+    /// signals with this curve keeps their combined power constant.
+    EqualPower,
+    /// The gain rises evenly in decibels across a 60 dB range, which
+    /// sounds even to the ear.
+    Logarithmic,
+    /// The gain rises slowly, then quickly, then slowly again along half a cosine.
+    SCurve,
+}
+
+impl FadeCurve {
+    /// Returns the fade-in gain at the position `t`, where `0.0`
+    /// is the start of the fade and `1.0` is the end.
+    ///
+    /// The fade-out gain at the same position is `gain(1.0 - t)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::source::FadeCurve;
+    ///
+    /// assert_eq!(FadeCurve::Linear.gain(0.25), 0.25);
+    /// assert_eq!(FadeCurve::SCurve.gain(0.0), 0.0);
+    /// assert_eq!(FadeCurve::EqualPower.gain(1.0), 1.0);
+    /// ```
+    #[inline]
+    pub fn gain(&self, t: f32) -> f32 {
+        if t <= 0.0 {
+            return 0.0;
+        }
+        if t >= 1.0 {
+            return 1.0;
+        }
+        match self {
+            FadeCurve::Linear => t,
+            FadeCurve::EqualPower => (t * FRAC_PI_2).sin(),
+            FadeCurve::Logarithmic => {
+                (10.0_f32).powf(LOGARITHMIC_FADE_RANGE_DB * (t - 1.0) / 20.0_f32)
+            }
+            FadeCurve::SCurve => 0.5 - 0.5 * (t * PI).cos(),
+        }
+    }
+}
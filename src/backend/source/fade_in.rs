@@ -0,0 +1,83 @@
+use crate::backend::display::est_num_frames_to_str;
+use crate::backend::source::FadeCurve;
+use crate::backend::units::frames_to_samples;
+use crate::backend::Signal;
+use crate::backend::Source;
+
+/// [`Source::fade_in()`]
+pub struct FadeIn<S: Source> {
+    iter: S,
+    curve: FadeCurve,
+    fade_num_samples: usize,
+    current_sample: usize,
+}
+
+impl<S: Source> std::fmt::Debug for FadeIn<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "FadeIn {{ {} frames,  {} channels,  {} hz,  {}; curve: {:?} }}",
+            est_num_frames_to_str(self.num_frames_estimate()),
+            self.num_channels(),
+            self.frame_rate_hz(),
+            self.duration_estimate_to_str(),
+            self.curve,
+        )
+    }
+}
+
+impl<S: Source> FadeIn<S> {
+    #[inline]
+    pub fn new(iter: S, num_frames: usize, curve: FadeCurve) -> Self {
+        let fade_num_samples = frames_to_samples(num_frames, iter.num_channels());
+        Self {
+            iter,
+            curve,
+            fade_num_samples,
+            current_sample: 0,
+        }
+    }
+}
+
+impl<S: Source> Source for FadeIn<S> {}
+
+impl<S: Source> Signal for FadeIn<S> {
+    #[inline]
+    fn frame_rate_hz(&self) -> u32 {
+        self.iter.frame_rate_hz()
+    }
+
+    #[inline]
+    fn num_channels(&self) -> u16 {
+        self.iter.num_channels()
+    }
+
+    #[inline]
+    fn num_frames_estimate(&self) -> Option<usize> {
+        self.iter.num_frames_estimate()
+    }
+}
+
+impl<S: Source> Iterator for FadeIn<S> {
+    type Item = f32;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    #[allow(clippy::cast_precision_loss)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let val = self.iter.next()?;
+        if self.current_sample >= self.fade_num_samples {
+            return Some(val);
+        }
+        let num_channels = self.iter.num_channels() as usize;
+        let frame_idx = self.current_sample / num_channels;
+        let fade_num_frames = self.fade_num_samples / num_channels;
+        self.current_sample += 1;
+        let t = frame_idx as f32 / fade_num_frames as f32;
+        Some(val * self.curve.gain(t))
+    }
+}
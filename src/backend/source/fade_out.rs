@@ -0,0 +1,113 @@
+use std::collections::VecDeque;
+
+use crate::backend::display::est_num_frames_to_str;
+use crate::backend::source::FadeCurve;
+use crate::backend::units::frames_to_samples;
+use crate::backend::Signal;
+use crate::backend::Source;
+
+/// [`Source::fade_out()`]
+///
+/// Because the end of a [`Source`] is only known once it has been reached,
+/// this adapter holds the most recent `num_frames` frames in a buffer.
+pub struct FadeOut<S: Source> {
+    iter: S,
+    curve: FadeCurve,
+    fade_num_samples: usize,
+    buffer: VecDeque<f32>,
+    drained: bool,
+}
+
+impl<S: Source> std::fmt::Debug for FadeOut<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "FadeOut {{ {} frames,  {} channels,  {} hz,  {}; curve: {:?} }}",
+            est_num_frames_to_str(self.num_frames_estimate()),
+            self.num_channels(),
+            self.frame_rate_hz(),
+            self.duration_estimate_to_str(),
+            self.curve,
+        )
+    }
+}
+
+impl<S: Source> FadeOut<S> {
+    #[inline]
+    pub fn new(iter: S, num_frames: usize, curve: FadeCurve) -> Self {
+        let fade_num_samples = frames_to_samples(num_frames, iter.num_channels());
+        Self {
+            iter,
+            curve,
+            fade_num_samples,
+            buffer: VecDeque::with_capacity(fade_num_samples),
+            drained: false,
+        }
+    }
+}
+
+impl<S: Source> Source for FadeOut<S> {}
+
+impl<S: Source> Signal for FadeOut<S> {
+    #[inline]
+    fn frame_rate_hz(&self) -> u32 {
+        self.iter.frame_rate_hz()
+    }
+
+    #[inline]
+    fn num_channels(&self) -> u16 {
+        self.iter.num_channels()
+    }
+
+    #[inline]
+    fn num_frames_estimate(&self) -> Option<usize> {
+        self.iter.num_frames_estimate()
+    }
+}
+
+impl<S: Source> Iterator for FadeOut<S> {
+    type Item = f32;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.buffer.len();
+        if self.drained {
+            return (buffered, Some(buffered));
+        }
+        let (lower, upper) = self.iter.size_hint();
+        (lower + buffered, upper.map(|u| u + buffered))
+    }
+
+    #[inline]
+    #[allow(clippy::cast_precision_loss)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.fade_num_samples == 0 {
+            return self.iter.next();
+        }
+        while !self.drained && self.buffer.len() < self.fade_num_samples {
+            match self.iter.next() {
+                Some(val) => self.buffer.push_back(val),
+                None => self.drained = true,
+            }
+        }
+        if !self.drained {
+            // The buffer is full, so the front sample is not in the fade region yet.
+            let retval = self.buffer.pop_front();
+            match self.iter.next() {
+                Some(val) => self.buffer.push_back(val),
+                None => self.drained = true,
+            }
+            if !self.drained {
+                return retval;
+            }
+            self.buffer.push_front(retval?);
+        }
+        let num_channels = self.iter.num_channels() as usize;
+        let remaining_frames = self.buffer.len().div_ceil(num_channels);
+        let fade_num_frames = self.fade_num_samples / num_channels;
+        let val = self.buffer.pop_front()?;
+        // Mirror the fade-in, so that the very last frame is silent.
+        let t = (remaining_frames - 1) as f32 / fade_num_frames as f32;
+        Some(val * self.curve.gain(t))
+    }
+}
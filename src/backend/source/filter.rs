@@ -0,0 +1,75 @@
+use crate::backend::display::est_num_frames_to_str;
+use crate::backend::filter::{FilterDesign, FilterState};
+use crate::backend::Signal;
+use crate::backend::Source;
+
+/// [`Source::apply_filter()`]
+pub struct Filter<S: Source> {
+    iter: S,
+    state: FilterState,
+    channel_idx: usize,
+}
+
+impl<S: Source> std::fmt::Debug for Filter<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Filter {{ {} frames,  {} channels,  {} hz,  {} }}",
+            est_num_frames_to_str(self.num_frames_estimate()),
+            self.num_channels(),
+            self.frame_rate_hz(),
+            self.duration_estimate_to_str(),
+        )
+    }
+}
+
+impl<S: Source> Filter<S> {
+    #[inline]
+    pub fn new(iter: S, design: &FilterDesign) -> Self {
+        let state = FilterState::new(design, iter.num_channels());
+        Self {
+            iter,
+            state,
+            channel_idx: 0,
+        }
+    }
+}
+
+impl<S: Source> Source for Filter<S> {}
+
+impl<S: Source> Signal for Filter<S> {
+    #[inline]
+    fn frame_rate_hz(&self) -> u32 {
+        self.iter.frame_rate_hz()
+    }
+
+    #[inline]
+    fn num_channels(&self) -> u16 {
+        self.iter.num_channels()
+    }
+
+    #[inline]
+    fn num_frames_estimate(&self) -> Option<usize> {
+        self.iter.num_frames_estimate()
+    }
+}
+
+impl<S: Source> Iterator for Filter<S> {
+    type Item = f32;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.iter.next()?;
+        let filtered = self.state.process(self.channel_idx, sample);
+        self.channel_idx += 1;
+        if self.channel_idx == self.iter.num_channels() as usize {
+            self.channel_idx = 0;
+        }
+        Some(filtered)
+    }
+}
@@ -0,0 +1,78 @@
+use crate::backend::display::est_num_frames_to_str;
+use crate::backend::envelope::{GainEnvelope as Envelope, ResolvedGainEnvelope};
+use crate::backend::Signal;
+use crate::backend::Source;
+
+/// [`Source::gain_envelope()`]
+pub struct GainEnvelope<S: Source> {
+    iter: S,
+    envelope: ResolvedGainEnvelope,
+    current_sample: usize,
+    ratio: f32,
+}
+
+impl<S: Source> std::fmt::Debug for GainEnvelope<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "GainEnvelope {{ {} frames,  {} channels,  {} hz,  {} }}",
+            est_num_frames_to_str(self.num_frames_estimate()),
+            self.num_channels(),
+            self.frame_rate_hz(),
+            self.duration_estimate_to_str(),
+        )
+    }
+}
+
+impl<S: Source> GainEnvelope<S> {
+    #[inline]
+    pub fn new(iter: S, envelope: &Envelope) -> Self {
+        let envelope = envelope.resolve(iter.frame_rate_hz());
+        Self {
+            iter,
+            envelope,
+            current_sample: 0,
+            ratio: 1.0,
+        }
+    }
+}
+
+impl<S: Source> Source for GainEnvelope<S> {}
+
+impl<S: Source> Signal for GainEnvelope<S> {
+    #[inline]
+    fn frame_rate_hz(&self) -> u32 {
+        self.iter.frame_rate_hz()
+    }
+
+    #[inline]
+    fn num_channels(&self) -> u16 {
+        self.iter.num_channels()
+    }
+
+    #[inline]
+    fn num_frames_estimate(&self) -> Option<usize> {
+        self.iter.num_frames_estimate()
+    }
+}
+
+impl<S: Source> Iterator for GainEnvelope<S> {
+    type Item = f32;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let val = self.iter.next()?;
+        let num_channels = self.iter.num_channels() as usize;
+        // Every channel in a frame shares the same gain.
+        if self.current_sample.is_multiple_of(num_channels) {
+            self.ratio = self.envelope.gain_at(self.current_sample / num_channels);
+        }
+        self.current_sample += 1;
+        Some(val * self.ratio)
+    }
+}
@@ -0,0 +1,242 @@
+use crate::backend::display::est_num_frames_to_str;
+use crate::backend::loudness::{dbfs_to_ratio, ratio_to_dbfs};
+use crate::backend::source::dynamics::{read_frame, smoothing_coefficient, ChannelLink};
+use crate::backend::units::milliseconds_to_frames;
+use crate::backend::Signal;
+use crate::backend::Source;
+
+/// Settings for [`Source::gate()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GateArgs {
+    /// The level, in dBFS, below which the audio is lowered.
+    pub threshold_dbfs: f32,
+    /// How many decibels the output falls for every decibel that the
+    /// input falls below the threshold. `f32::INFINITY` is a hard gate,
+    /// and smaller ratios make a gentler downward expander.
+    pub ratio: f32,
+    /// The most that the audio is lowered, in decibels.
+    /// `f32::INFINITY` silences the audio completely.
+    pub range_db: f32,
+    /// How long it takes for the gate to open.
+    pub attack_milliseconds: usize,
+    /// How long to keep the gate open after the audio falls below
+    /// the threshold, before releasing.
+    pub hold_milliseconds: usize,
+    /// How long it takes for the gate to close.
+    pub release_milliseconds: usize,
+    /// How the channels share gain changes.
+    pub link: ChannelLink,
+}
+
+impl Default for GateArgs {
+    fn default() -> Self {
+        GateArgs {
+            threshold_dbfs: -50.0,
+            ratio: f32::INFINITY,
+            range_db: 80.0,
+            attack_milliseconds: 1,
+            hold_milliseconds: 50,
+            release_milliseconds: 100,
+            link: ChannelLink::Max,
+        }
+    }
+}
+
+impl GateArgs {
+    /// Set the [`threshold_dbfs`](#structfield.threshold_dbfs) field.
+    #[must_use]
+    pub fn set_threshold_dbfs(&mut self, threshold_dbfs: f32) -> Self {
+        self.threshold_dbfs = threshold_dbfs;
+        *self
+    }
+
+    /// Set the [`ratio`](#structfield.ratio) field.
+    #[must_use]
+    pub fn set_ratio(&mut self, ratio: f32) -> Self {
+        self.ratio = ratio;
+        *self
+    }
+
+    /// Set the [`range_db`](#structfield.range_db) field.
+    #[must_use]
+    pub fn set_range_db(&mut self, range_db: f32) -> Self {
+        self.range_db = range_db;
+        *self
+    }
+
+    /// Set the [`attack_milliseconds`](#structfield.attack_milliseconds) field.
+    #[must_use]
+    pub fn set_attack_milliseconds(&mut self, attack_milliseconds: usize) -> Self {
+        self.attack_milliseconds = attack_milliseconds;
+        *self
+    }
+
+    /// Set the [`hold_milliseconds`](#structfield.hold_milliseconds) field.
+    #[must_use]
+    pub fn set_hold_milliseconds(&mut self, hold_milliseconds: usize) -> Self {
+        self.hold_milliseconds = hold_milliseconds;
+        *self
+    }
+
+    /// Set the [`release_milliseconds`](#structfield.release_milliseconds) field.
+    #[must_use]
+    pub fn set_release_milliseconds(&mut self, release_milliseconds: usize) -> Self {
+        self.release_milliseconds = release_milliseconds;
+        *self
+    }
+
+    /// Set the [`link`](#structfield.link) field.
+    #[must_use]
+    pub fn set_link(&mut self, link: ChannelLink) -> Self {
+        self.link = link;
+        *self
+    }
+
+    /// Returns how many decibels a signal at `level_dbfs` should be lowered by.
+    #[inline]
+    fn gain_reduction_db(&self, level_dbfs: f32) -> f32 {
+        let slope = self.ratio.max(1.0) - 1.0;
+        if level_dbfs >= self.threshold_dbfs || slope <= 0.0 {
+            return 0.0;
+        }
+        // Keep the reduction finite so that it can be smoothed.
+        let range_db = self.range_db.clamp(0.0, f32::MAX);
+        ((self.threshold_dbfs - level_dbfs) * slope).min(range_db)
+    }
+}
+
+/// [`Source::gate()`]
+pub struct Gate<S: Source> {
+    iter: S,
+    args: GateArgs,
+    attack_coef: f32,
+    release_coef: f32,
+    hold_frames: usize,
+    /// How many more frames each level detector holds the gate open for.
+    hold_remaining: Vec<usize>,
+    /// The current gain reduction of each level detector, in decibels.
+    reduction_db: Vec<f32>,
+    levels: Vec<f32>,
+    frame: Vec<f32>,
+    frame_idx: usize,
+}
+
+impl<S: Source> std::fmt::Debug for Gate<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Gate {{ {} frames,  {} channels,  {} hz,  {}; threshold: {} dbfs }}",
+            est_num_frames_to_str(self.num_frames_estimate()),
+            self.num_channels(),
+            self.frame_rate_hz(),
+            self.duration_estimate_to_str(),
+            self.args.threshold_dbfs,
+        )
+    }
+}
+
+impl<S: Source> Gate<S> {
+    #[inline]
+    pub fn new(iter: S, args: GateArgs) -> Self {
+        let frame_rate_hz = iter.frame_rate_hz();
+        let num_channels = iter.num_channels() as usize;
+        let num_detectors = args.link.num_detectors(num_channels);
+        Self {
+            iter,
+            args,
+            attack_coef: smoothing_coefficient(milliseconds_to_frames(
+                args.attack_milliseconds,
+                frame_rate_hz,
+            )),
+            release_coef: smoothing_coefficient(milliseconds_to_frames(
+                args.release_milliseconds,
+                frame_rate_hz,
+            )),
+            hold_frames: milliseconds_to_frames(args.hold_milliseconds, frame_rate_hz),
+            hold_remaining: vec![0; num_detectors],
+            reduction_db: vec![0.0; num_detectors],
+            levels: vec![0.0; num_detectors],
+            frame: Vec::with_capacity(num_channels),
+            frame_idx: num_channels,
+        }
+    }
+
+    /// Reads the next frame and fills `self.frame` with output samples.
+    /// Returns `false` once the input has ended.
+    fn fill_frame(&mut self) -> bool {
+        if !read_frame(&mut self.iter, &mut self.frame) {
+            return false;
+        }
+        self.args.link.detect(&self.frame, &mut self.levels);
+        for ((reduction_db, hold_remaining), &level) in self
+            .reduction_db
+            .iter_mut()
+            .zip(self.hold_remaining.iter_mut())
+            .zip(&self.levels)
+        {
+            let mut target_db = self.args.gain_reduction_db(ratio_to_dbfs(level));
+            // Keep the gate open for a while after the audio goes quiet.
+            if target_db <= 0.0 {
+                *hold_remaining = self.hold_frames;
+            } else if *hold_remaining > 0 {
+                *hold_remaining -= 1;
+                target_db = 0.0;
+            }
+            // Opening the gate is the attack, and closing it is the release.
+            let coef = if target_db < *reduction_db {
+                self.attack_coef
+            } else {
+                self.release_coef
+            };
+            *reduction_db = target_db + coef * (*reduction_db - target_db);
+        }
+        for (channel_idx, sample) in self.frame.iter_mut().enumerate() {
+            let reduction_db = self.reduction_db[self.args.link.detector_idx(channel_idx)];
+            *sample *= dbfs_to_ratio(-reduction_db);
+        }
+        true
+    }
+}
+
+impl<S: Source> Source for Gate<S> {}
+
+impl<S: Source> Signal for Gate<S> {
+    #[inline]
+    fn frame_rate_hz(&self) -> u32 {
+        self.iter.frame_rate_hz()
+    }
+
+    #[inline]
+    fn num_channels(&self) -> u16 {
+        self.iter.num_channels()
+    }
+
+    #[inline]
+    fn num_frames_estimate(&self) -> Option<usize> {
+        self.iter.num_frames_estimate()
+    }
+}
+
+impl<S: Source> Iterator for Gate<S> {
+    type Item = f32;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.frame.len() - std::cmp::min(self.frame_idx, self.frame.len());
+        let (lower, upper) = self.iter.size_hint();
+        (lower + buffered, upper.map(|u| u + buffered))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.frame_idx >= self.frame.len() {
+            if !self.fill_frame() {
+                return None;
+            }
+            self.frame_idx = 0;
+        }
+        let val = self.frame[self.frame_idx];
+        self.frame_idx += 1;
+        Some(val)
+    }
+}
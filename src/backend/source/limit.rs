@@ -0,0 +1,301 @@
+use std::collections::VecDeque;
+
+use crate::backend::display::est_num_frames_to_str;
+use crate::backend::loudness::dbfs_to_ratio;
+use crate::backend::source::dynamics::{read_frame, smoothing_coefficient, ChannelLink};
+use crate::backend::units::milliseconds_to_frames;
+use crate::backend::Signal;
+use crate::backend::Source;
+
+/// Settings for [`Source::limit()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LimitArgs {
+    /// The level, in dBFS, that the output never goes above.
+    pub ceiling_dbfs: f32,
+    /// How far ahead the limiter looks for peaks, so that it can
+    /// start lowering the gain before they arrive.
+    pub lookahead_milliseconds: usize,
+    /// How long it takes for the gain to come back up after a peak.
+    pub release_milliseconds: usize,
+    /// How the channels share gain reduction. The ceiling is always
+    /// honored, so [`ChannelLink::Average`] behaves like [`ChannelLink::Max`].
+    pub link: ChannelLink,
+}
+
+impl Default for LimitArgs {
+    fn default() -> Self {
+        LimitArgs {
+            ceiling_dbfs: -1.0,
+            lookahead_milliseconds: 5,
+            release_milliseconds: 50,
+            link: ChannelLink::Max,
+        }
+    }
+}
+
+impl LimitArgs {
+    /// Set the [`ceiling_dbfs`](#structfield.ceiling_dbfs) field.
+    #[must_use]
+    pub fn set_ceiling_dbfs(&mut self, ceiling_dbfs: f32) -> Self {
+        self.ceiling_dbfs = ceiling_dbfs;
+        *self
+    }
+
+    /// Set the [`lookahead_milliseconds`](#structfield.lookahead_milliseconds) field.
+    #[must_use]
+    pub fn set_lookahead_milliseconds(&mut self, lookahead_milliseconds: usize) -> Self {
+        self.lookahead_milliseconds = lookahead_milliseconds;
+        *self
+    }
+
+    /// Set the [`release_milliseconds`](#structfield.release_milliseconds) field.
+    #[must_use]
+    pub fn set_release_milliseconds(&mut self, release_milliseconds: usize) -> Self {
+        self.release_milliseconds = release_milliseconds;
+        *self
+    }
+
+    /// Set the [`link`](#structfield.link) field.
+    #[must_use]
+    pub fn set_link(&mut self, link: ChannelLink) -> Self {
+        self.link = link;
+        *self
+    }
+}
+
+/// Works out the gain for one level detector of [`Limit`].
+///
+/// The gain needed to keep each frame under the ceiling is run through
+/// a minimum filter and then a moving average, both as long as the
+/// lookahead window. Every value in the average is at or below the
+/// gain needed by the oldest frame in the window, so the smoothed gain
+/// never lets that frame through above the ceiling.
+#[derive(Clone, Debug)]
+struct LimitDetector {
+    /// `(frame_idx, gain)` pairs with increasing gains, for the minimum filter.
+    minimums: VecDeque<(usize, f32)>,
+    /// The output of the minimum filter after the release is applied.
+    held: f32,
+    /// The last `window_frames` values of `held`.
+    window: VecDeque<f32>,
+    window_sum: f64,
+}
+
+impl LimitDetector {
+    #[allow(clippy::cast_precision_loss)]
+    fn new(window_frames: usize) -> Self {
+        Self {
+            minimums: VecDeque::with_capacity(window_frames),
+            held: 1.0,
+            window: std::iter::repeat_n(1.0, window_frames).collect(),
+            window_sum: window_frames as f64,
+        }
+    }
+
+    /// Takes the gain needed by the newest frame, and returns the gain
+    /// for the frame that is leaving the lookahead window.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn push(&mut self, frame_idx: usize, needed_gain: f32, release_coef: f32) -> f32 {
+        let window_frames = self.window.len();
+        while matches!(self.minimums.back(), Some(&(_, gain)) if gain >= needed_gain) {
+            self.minimums.pop_back();
+        }
+        self.minimums.push_back((frame_idx, needed_gain));
+        while matches!(self.minimums.front(), Some(&(idx, _)) if idx + window_frames <= frame_idx) {
+            self.minimums.pop_front();
+        }
+        let minimum = self.minimums.front().map_or(1.0, |&(_, gain)| gain);
+        self.held = if minimum < self.held {
+            minimum
+        } else {
+            minimum + release_coef * (self.held - minimum)
+        };
+        self.window.push_back(self.held);
+        self.window_sum += f64::from(self.held);
+        if let Some(oldest) = self.window.pop_front() {
+            self.window_sum -= f64::from(oldest);
+        }
+        (self.window_sum / window_frames as f64) as f32
+    }
+}
+
+/// [`Source::limit()`]
+///
+/// This is a lookahead brickwall limiter. The input is delayed by the
+/// lookahead internally, but the output is aligned with the input and
+/// has the same number of frames.
+pub struct Limit<S: Source> {
+    iter: S,
+    args: LimitArgs,
+    link: ChannelLink,
+    ceiling: f32,
+    release_coef: f32,
+    lookahead_frames: usize,
+    detectors: Vec<LimitDetector>,
+    levels: Vec<f32>,
+    gains: Vec<f32>,
+    /// Interleaved samples waiting for the lookahead window to pass.
+    delay: VecDeque<f32>,
+    input_frame: Vec<f32>,
+    input_drained: bool,
+    /// How many frames of silence are still needed to flush the delay.
+    flush_frames_remaining: usize,
+    frames_read: usize,
+    frame: Vec<f32>,
+    frame_idx: usize,
+}
+
+impl<S: Source> std::fmt::Debug for Limit<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Limit {{ {} frames,  {} channels,  {} hz,  {}; ceiling: {} dbfs }}",
+            est_num_frames_to_str(self.num_frames_estimate()),
+            self.num_channels(),
+            self.frame_rate_hz(),
+            self.duration_estimate_to_str(),
+            self.args.ceiling_dbfs,
+        )
+    }
+}
+
+impl<S: Source> Limit<S> {
+    #[inline]
+    pub fn new(iter: S, args: LimitArgs) -> Self {
+        let frame_rate_hz = iter.frame_rate_hz();
+        let num_channels = iter.num_channels() as usize;
+        let link = match args.link {
+            ChannelLink::Average => ChannelLink::Max,
+            link => link,
+        };
+        let num_detectors = link.num_detectors(num_channels);
+        let lookahead_frames = milliseconds_to_frames(args.lookahead_milliseconds, frame_rate_hz);
+        Self {
+            iter,
+            args,
+            link,
+            ceiling: dbfs_to_ratio(args.ceiling_dbfs),
+            release_coef: smoothing_coefficient(milliseconds_to_frames(
+                args.release_milliseconds,
+                frame_rate_hz,
+            )),
+            lookahead_frames,
+            detectors: vec![LimitDetector::new(lookahead_frames + 1); num_detectors],
+            levels: vec![0.0; num_detectors],
+            gains: vec![1.0; num_detectors],
+            delay: VecDeque::with_capacity((lookahead_frames + 1) * num_channels),
+            input_frame: Vec::with_capacity(num_channels),
+            input_drained: false,
+            flush_frames_remaining: lookahead_frames,
+            frames_read: 0,
+            frame: Vec::with_capacity(num_channels),
+            frame_idx: num_channels,
+        }
+    }
+
+    /// Reads input until a frame leaves the lookahead window, and fills
+    /// `self.frame` with output samples. Returns `false` once the output has ended.
+    fn fill_frame(&mut self) -> bool {
+        let num_channels = self.num_channels() as usize;
+        loop {
+            if !self.input_drained && !read_frame(&mut self.iter, &mut self.input_frame) {
+                self.input_drained = true;
+            }
+            if self.input_drained {
+                if self.flush_frames_remaining == 0 {
+                    return false;
+                }
+                self.flush_frames_remaining -= 1;
+                self.input_frame.clear();
+                self.input_frame.resize(num_channels, 0.0);
+            }
+            self.delay.extend(self.input_frame.iter().copied());
+
+            self.link.detect(&self.input_frame, &mut self.levels);
+            for ((detector, gain), &level) in self
+                .detectors
+                .iter_mut()
+                .zip(self.gains.iter_mut())
+                .zip(&self.levels)
+            {
+                let needed_gain = if level > self.ceiling {
+                    self.ceiling / level
+                } else {
+                    1.0
+                };
+                *gain = detector.push(self.frames_read, needed_gain, self.release_coef);
+            }
+            self.frames_read += 1;
+
+            if self.delay.len() > self.lookahead_frames * num_channels {
+                self.frame.clear();
+                for channel_idx in 0..num_channels {
+                    let sample = self.delay.pop_front().unwrap_or_default();
+                    let gain = self.gains[self.link.detector_idx(channel_idx)];
+                    // Guard against rounding in the moving average.
+                    self.frame
+                        .push((sample * gain).clamp(-self.ceiling, self.ceiling));
+                }
+                return true;
+            }
+        }
+    }
+}
+
+impl<S: Source> Source for Limit<S> {}
+
+impl<S: Source> Signal for Limit<S> {
+    #[inline]
+    fn frame_rate_hz(&self) -> u32 {
+        self.iter.frame_rate_hz()
+    }
+
+    #[inline]
+    fn num_channels(&self) -> u16 {
+        self.iter.num_channels()
+    }
+
+    #[inline]
+    fn num_frames_estimate(&self) -> Option<usize> {
+        self.iter.num_frames_estimate()
+    }
+}
+
+impl<S: Source> Iterator for Limit<S> {
+    type Item = f32;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.frame.len() - std::cmp::min(self.frame_idx, self.frame.len());
+        let delayed = if self.input_drained {
+            // Only the flushing frames are left in the delay line.
+            self.delay.len().saturating_sub(
+                (self.lookahead_frames - self.flush_frames_remaining)
+                    * self.num_channels() as usize,
+            )
+        } else {
+            self.delay.len()
+        };
+        if self.input_drained {
+            return (buffered + delayed, Some(buffered + delayed));
+        }
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower + buffered + delayed,
+            upper.map(|u| u + buffered + delayed),
+        )
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.frame_idx >= self.frame.len() {
+            if !self.fill_frame() {
+                return None;
+            }
+            self.frame_idx = 0;
+        }
+        let val = self.frame[self.frame_idx];
+        self.frame_idx += 1;
+        Some(val)
+    }
+}
@@ -0,0 +1,205 @@
+use crate::backend::display::est_num_frames_to_str;
+use crate::backend::units::frames_to_samples;
+use crate::backend::Signal;
+use crate::backend::Source;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LoopPhase {
+    /// Passing through samples before the loop region.
+    Before,
+    /// Reading the loop region from the source for the first time.
+    FirstPass,
+    /// Replaying the loop region from the cache.
+    Replay,
+    /// Passing through samples after the loop region.
+    After,
+}
+
+/// [`Source::repeat()`], [`Source::loop_forever()`], and [`Source::loop_frames()`]
+///
+/// The loop region is cached the first time it is read, so only the
+/// region (and not the whole source) is held in memory.
+pub struct Loop<S: Source> {
+    iter: S,
+    start_sample: usize,
+    end_sample: Option<usize>,
+    num_times: Option<usize>,
+    remaining_times: Option<usize>,
+    phase: LoopPhase,
+    cache: Vec<f32>,
+    cache_idx: usize,
+    samples_read: usize,
+    samples_emitted: usize,
+}
+
+impl<S: Source> std::fmt::Debug for Loop<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Loop {{ {} frames,  {} channels,  {} hz,  {} }}",
+            est_num_frames_to_str(self.num_frames_estimate()),
+            self.num_channels(),
+            self.frame_rate_hz(),
+            self.duration_estimate_to_str(),
+        )
+    }
+}
+
+impl<S: Source> Loop<S> {
+    /// Plays `iter` until `end_frame_idx`, plays the frames between
+    /// `start_frame_idx` and `end_frame_idx` a total of `num_times` times,
+    /// and then continues to the end of `iter`.
+    ///
+    /// An `end_frame_idx` of `None` loops until the end of `iter`.
+    /// A `num_times` of `None` loops forever.
+    #[inline]
+    pub fn new(
+        iter: S,
+        start_frame_idx: usize,
+        end_frame_idx: Option<usize>,
+        num_times: Option<usize>,
+    ) -> Self {
+        let num_channels = iter.num_channels();
+        let start_sample = frames_to_samples(start_frame_idx, num_channels);
+        let end_sample = end_frame_idx
+            .map(|end| frames_to_samples(std::cmp::max(start_frame_idx, end), num_channels));
+        Self {
+            iter,
+            start_sample,
+            end_sample,
+            num_times,
+            remaining_times: num_times,
+            phase: if start_sample == 0 {
+                LoopPhase::FirstPass
+            } else {
+                LoopPhase::Before
+            },
+            cache: Vec::new(),
+            cache_idx: 0,
+            samples_read: 0,
+            samples_emitted: 0,
+        }
+    }
+
+    /// Returns the total output length given the total length of the input,
+    /// or `None` if the output never ends.
+    #[inline]
+    fn output_len(&self, input_len: usize) -> Option<usize> {
+        let start = std::cmp::min(self.start_sample, input_len);
+        let end = std::cmp::min(self.end_sample.unwrap_or(input_len), input_len);
+        let region_len = end - start;
+        match self.num_times {
+            Some(num_times) => Some(input_len - region_len + region_len * num_times),
+            None if region_len == 0 => Some(input_len),
+            None => None,
+        }
+    }
+
+    /// Called when the first pass through the loop region has ended.
+    #[inline]
+    fn finish_first_pass(&mut self) {
+        if let Some(remaining) = self.remaining_times.as_mut() {
+            *remaining = remaining.saturating_sub(1);
+        }
+        self.cache_idx = 0;
+        self.phase = if self.cache.is_empty() || self.remaining_times == Some(0) {
+            self.cache = Vec::new();
+            LoopPhase::After
+        } else {
+            LoopPhase::Replay
+        };
+    }
+}
+
+impl<S: Source> Source for Loop<S> {}
+
+impl<S: Source> Signal for Loop<S> {
+    #[inline]
+    fn frame_rate_hz(&self) -> u32 {
+        self.iter.frame_rate_hz()
+    }
+
+    #[inline]
+    fn num_channels(&self) -> u16 {
+        self.iter.num_channels()
+    }
+
+    #[inline]
+    fn num_frames_estimate(&self) -> Option<usize> {
+        let num_channels = self.num_channels() as usize;
+        let input_len = frames_to_samples(self.iter.num_frames_estimate()?, self.num_channels());
+        self.output_len(input_len).map(|len| len / num_channels)
+    }
+}
+
+impl<S: Source> Iterator for Loop<S> {
+    type Item = f32;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let lower = self
+            .output_len(self.samples_read + lower)
+            .map_or(usize::MAX, |len| len.saturating_sub(self.samples_emitted));
+        let upper = upper
+            .and_then(|upper| self.output_len(self.samples_read + upper))
+            .map(|len| len.saturating_sub(self.samples_emitted));
+        (lower, upper)
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.phase {
+                LoopPhase::Before => {
+                    let val = self.iter.next()?;
+                    self.samples_read += 1;
+                    if self.samples_read == self.start_sample {
+                        self.phase = LoopPhase::FirstPass;
+                    }
+                    self.samples_emitted += 1;
+                    return Some(val);
+                }
+                LoopPhase::FirstPass => {
+                    if self.end_sample == Some(self.samples_read) {
+                        self.finish_first_pass();
+                        continue;
+                    }
+                    if let Some(val) = self.iter.next() {
+                        self.samples_read += 1;
+                        self.cache.push(val);
+                        // A loop that plays zero times skips the region entirely.
+                        if self.num_times == Some(0) {
+                            continue;
+                        }
+                        self.samples_emitted += 1;
+                        return Some(val);
+                    }
+                    self.finish_first_pass();
+                }
+                LoopPhase::Replay => {
+                    let val = self.cache[self.cache_idx];
+                    self.cache_idx += 1;
+                    if self.cache_idx == self.cache.len() {
+                        self.cache_idx = 0;
+                        if let Some(remaining) = self.remaining_times.as_mut() {
+                            *remaining -= 1;
+                            if *remaining == 0 {
+                                self.cache = Vec::new();
+                                self.phase = LoopPhase::After;
+                            }
+                        }
+                    }
+                    self.samples_emitted += 1;
+                    return Some(val);
+                }
+                LoopPhase::After => {
+                    let val = self.iter.next()?;
+                    self.samples_read += 1;
+                    self.samples_emitted += 1;
+                    return Some(val);
+                }
+            }
+        }
+    }
+}
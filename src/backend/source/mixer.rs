@@ -0,0 +1,320 @@
+use crate::backend::display::est_num_frames_to_str;
+use crate::backend::units::frames_to_samples;
+use crate::backend::Error;
+use crate::backend::Signal;
+use crate::backend::Source;
+
+/// How [`Mixer`] should handle summed samples that exceed `[-1.0, 1.0]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClipPolicy {
+    /// Leave the summed samples as-is, even if they exceed `[-1.0, 1.0]`.
+    #[default]
+    None,
+    /// Clamp every summed sample to `[-1.0, 1.0]`.
+    HardClip,
+    /// Scale the whole mix down by the sum of the input gains so that
+    /// full-scale inputs can never add up to more than full scale.
+    AutoHeadroom,
+}
+
+/// The curve used to turn a pan position into left and right gains.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PanLaw {
+    /// Gains fall linearly towards the opposite side. Center is -6 dB.
+    Linear,
+    /// Gains follow a quarter sine/cosine. Center is -3 dB.
+    ConstantPower,
+    /// The near side stays at unity while the far side is attenuated. Center is 0 dB.
+    Balance,
+}
+
+/// A stereo pan position and the [`PanLaw`] used to apply it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pan {
+    /// `-1.0` is hard left, `0.0` is center, and `1.0` is hard right.
+    pub position: f32,
+    pub law: PanLaw,
+}
+
+impl Pan {
+    /// Returns the `(left, right)` gain factors for this pan position.
+    pub fn gains(&self) -> (f32, f32) {
+        let p = self.position.clamp(-1.0, 1.0);
+        match self.law {
+            PanLaw::Linear => (0.5 - 0.5 * p, 0.5 + 0.5 * p),
+            PanLaw::ConstantPower => {
+                let theta = (p + 1.0) * std::f32::consts::FRAC_PI_4;
+                (theta.cos(), theta.sin())
+            }
+            PanLaw::Balance => ((1.0 - p).min(1.0), (1.0 + p).min(1.0)),
+        }
+    }
+}
+
+/// Per-input settings for [`Mixer::add_input()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MixerInputArgs {
+    /// The number of frames of silence before this input starts playing.
+    pub offset_frames: usize,
+    /// The gain to apply to this input, in decibels relative to full scale.
+    pub gain_dbfs: f32,
+    /// An optional stereo pan. Panning requires a 2-channel [`Mixer`].
+    pub pan: Option<Pan>,
+}
+
+impl Default for MixerInputArgs {
+    fn default() -> Self {
+        MixerInputArgs {
+            offset_frames: 0,
+            gain_dbfs: 0.0,
+            pan: None,
+        }
+    }
+}
+
+impl MixerInputArgs {
+    /// Set the [`offset_frames`](#structfield.offset_frames) field.
+    #[must_use]
+    pub fn set_offset_frames(&mut self, offset_frames: usize) -> Self {
+        self.offset_frames = offset_frames;
+        *self
+    }
+
+    /// Set the [`gain_dbfs`](#structfield.gain_dbfs) field.
+    #[must_use]
+    pub fn set_gain_dbfs(&mut self, gain_dbfs: f32) -> Self {
+        self.gain_dbfs = gain_dbfs;
+        *self
+    }
+
+    /// Set the [`pan`](#structfield.pan) field.
+    #[must_use]
+    pub fn set_pan(&mut self, pan: Pan) -> Self {
+        self.pan = Some(pan);
+        *self
+    }
+}
+
+struct MixerInput<'a> {
+    source: Box<dyn Source + 'a>,
+    offset_samples: usize,
+    channel_gains: Vec<f32>,
+    drained: bool,
+}
+
+impl MixerInput<'_> {
+    #[inline]
+    fn peak_gain(&self) -> f32 {
+        self.channel_gains.iter().copied().fold(0.0_f32, f32::max)
+    }
+
+    #[inline]
+    fn num_frames_estimate(&self, num_channels: u16) -> Option<usize> {
+        let offset_frames = self.offset_samples / num_channels as usize;
+        self.source.num_frames_estimate().map(|n| n + offset_frames)
+    }
+}
+
+/// Mixes any number of [`Source`]s together, each with its own offset, gain, and pan.
+///
+/// Every input must have the same frame rate and number of channels as the `Mixer`.
+/// The mix ends once every input has run out of samples.
+///
+/// # Examples
+/// ```
+/// use babycat::{Source, WaveformSource};
+/// use babycat::source::{ClipPolicy, Mixer, MixerInputArgs};
+///
+/// let a = WaveformSource::from_interleaved_samples(44100, 1, &[0.5, 0.5, 0.5]);
+/// let b = WaveformSource::from_interleaved_samples(44100, 1, &[0.25, 0.25]);
+///
+/// let mut mixer = Mixer::new(44100, 1, ClipPolicy::None).unwrap();
+/// mixer.add_input(Box::new(a), Default::default()).unwrap();
+/// mixer
+///     .add_input(Box::new(b), MixerInputArgs::default().set_offset_frames(2))
+///     .unwrap();
+///
+/// assert_eq!(
+///     mixer.collect_interleaved_samples(),
+///     &[0.5, 0.5, 0.75, 0.25],
+/// );
+/// ```
+pub struct Mixer<'a> {
+    frame_rate_hz: u32,
+    num_channels: u16,
+    clip_policy: ClipPolicy,
+    headroom: f32,
+    inputs: Vec<MixerInput<'a>>,
+    current_sample: usize,
+}
+
+impl std::fmt::Debug for Mixer<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Mixer {{ {} inputs,  {} frames,  {} channels,  {} hz,  {} }}",
+            self.inputs.len(),
+            est_num_frames_to_str(self.num_frames_estimate()),
+            self.num_channels(),
+            self.frame_rate_hz(),
+            self.duration_estimate_to_str(),
+        )
+    }
+}
+
+impl<'a> Mixer<'a> {
+    /// Creates an empty `Mixer`. Use [`Mixer::add_input()`] to add sources to it.
+    ///
+    /// # Errors
+    /// Returns [`Error::WrongNumChannels`] if `num_channels` is zero.
+    #[inline]
+    pub fn new(
+        frame_rate_hz: u32,
+        num_channels: u16,
+        clip_policy: ClipPolicy,
+    ) -> Result<Self, Error> {
+        if num_channels == 0 {
+            return Err(Error::WrongNumChannels(1, num_channels));
+        }
+        Ok(Self {
+            frame_rate_hz,
+            num_channels,
+            clip_policy,
+            headroom: 1.0,
+            inputs: Vec::new(),
+            current_sample: 0,
+        })
+    }
+
+    /// Adds a [`Source`] to the mix.
+    ///
+    /// Inputs should be added before the `Mixer` starts producing samples.
+    /// The input's offset is measured from the beginning of the mix.
+    pub fn add_input(
+        &mut self,
+        source: Box<dyn Source + 'a>,
+        args: MixerInputArgs,
+    ) -> Result<(), Error> {
+        if source.num_channels() != self.num_channels {
+            return Err(Error::CannotMixSourcesWithDifferentNumChannels(
+                self.num_channels,
+                source.num_channels(),
+            ));
+        }
+        if source.frame_rate_hz() != self.frame_rate_hz {
+            return Err(Error::CannotMixSourcesWithDifferentFrameRates(
+                self.frame_rate_hz,
+                source.frame_rate_hz(),
+            ));
+        }
+        let ratio: f32 = (10.0_f32).powf(args.gain_dbfs / 20.0_f32);
+        let channel_gains: Vec<f32> = match args.pan {
+            None => vec![ratio; self.num_channels as usize],
+            Some(pan) => {
+                if self.num_channels != 2 {
+                    return Err(Error::CannotPanWithoutTwoChannels(self.num_channels));
+                }
+                let (left, right) = pan.gains();
+                vec![left * ratio, right * ratio]
+            }
+        };
+        self.inputs.push(MixerInput {
+            source,
+            offset_samples: frames_to_samples(args.offset_frames, self.num_channels),
+            channel_gains,
+            drained: false,
+        });
+        self.headroom = match self.clip_policy {
+            ClipPolicy::AutoHeadroom => {
+                let total: f32 = self.inputs.iter().map(MixerInput::peak_gain).sum();
+                if total > 1.0 {
+                    1.0 / total
+                } else {
+                    1.0
+                }
+            }
+            _ => 1.0,
+        };
+        Ok(())
+    }
+
+    /// Returns the number of inputs that have been added to this `Mixer`.
+    #[inline]
+    pub fn num_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+}
+
+impl Source for Mixer<'_> {}
+
+impl Signal for Mixer<'_> {
+    #[inline]
+    fn frame_rate_hz(&self) -> u32 {
+        self.frame_rate_hz
+    }
+
+    #[inline]
+    fn num_channels(&self) -> u16 {
+        self.num_channels
+    }
+
+    #[inline]
+    fn num_frames_estimate(&self) -> Option<usize> {
+        let mut longest: usize = 0;
+        for input in &self.inputs {
+            longest = std::cmp::max(longest, input.num_frames_estimate(self.num_channels)?);
+        }
+        Some(longest)
+    }
+}
+
+impl Iterator for Mixer<'_> {
+    type Item = f32;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let mut lower: usize = 0;
+        let mut upper: Option<usize> = Some(0);
+        for input in &self.inputs {
+            let (in_lower, in_upper) = if input.drained {
+                (0, Some(0))
+            } else {
+                input.source.size_hint()
+            };
+            let delay = input.offset_samples.saturating_sub(self.current_sample);
+            lower = std::cmp::max(lower, in_lower + delay);
+            upper = match (upper, in_upper) {
+                (Some(u), Some(in_u)) => Some(std::cmp::max(u, in_u + delay)),
+                _ => None,
+            };
+        }
+        (lower, upper)
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let channel_idx = self.current_sample % self.num_channels as usize;
+        let mut total: f32 = 0.0;
+        for input in &mut self.inputs {
+            if input.drained {
+                continue;
+            }
+            if self.current_sample < input.offset_samples {
+                continue;
+            }
+            match input.source.next() {
+                Some(sample) => total += sample * input.channel_gains[channel_idx],
+                None => input.drained = true,
+            }
+        }
+        if self.inputs.iter().all(|input| input.drained) {
+            return None;
+        }
+        self.current_sample += 1;
+        let total = total * self.headroom;
+        match self.clip_policy {
+            ClipPolicy::HardClip => Some(total.clamp(-1.0, 1.0)),
+            _ => Some(total),
+        }
+    }
+}
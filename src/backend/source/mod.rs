@@ -6,28 +6,61 @@ use either::Either;
 
 mod append;
 mod append_zeros;
+mod compress;
 mod convert_to_mono;
+mod convolve;
+mod crossfade;
+mod duck;
+mod dynamics;
+mod fade_curve;
+mod fade_in;
+mod fade_out;
+mod filter;
 mod gain;
+mod gain_envelope;
+mod gate;
+mod limit;
+mod looping;
+mod mixer;
 mod prepend_zeros;
 mod scale;
 mod select_channels;
 mod skip_frames;
 mod sum;
 mod take_frames;
+mod trim_silence;
 mod waveform_source;
 
 pub use append::Append;
 pub use append_zeros::AppendZeros;
+pub use compress::{Compress, CompressArgs};
 pub use convert_to_mono::ConvertToMono;
+pub use convolve::Convolve;
+pub use crossfade::Crossfade;
+pub use duck::{Duck, DuckAmount, DuckArgs, DuckOutput};
+pub use dynamics::ChannelLink;
+pub use fade_curve::FadeCurve;
+pub use fade_in::FadeIn;
+pub use fade_out::FadeOut;
+pub use filter::Filter;
 pub use gain::Gain;
+pub use gain_envelope::GainEnvelope;
+pub use gate::{Gate, GateArgs};
+pub use limit::{Limit, LimitArgs};
+pub use looping::Loop;
+pub use mixer::{ClipPolicy, Mixer, MixerInputArgs, Pan, PanLaw};
 pub use prepend_zeros::PrependZeros;
 pub use scale::Scale;
 pub use select_channels::SelectChannels;
 pub use skip_frames::SkipFrames;
 pub use sum::Sum;
 pub use take_frames::TakeFrames;
+pub use trim_silence::TrimSilence;
+pub(crate) use waveform_source::BorrowedWaveformSource;
 pub use waveform_source::WaveformSource;
 
+use crate::backend::envelope::GainEnvelope as Envelope;
+use crate::backend::filter::FilterDesign;
 use crate::backend::Error;
 use crate::backend::Signal;
 use crate::backend::Waveform;
@@ -79,6 +112,92 @@ pub trait Source: Signal + Iterator<Item = f32> + Debug + Send {
         Append::new(self, second)
     }
 
+    /// Append one [`Source`] after another [`Source`], overlapping them by
+    /// `num_frames` frames while fading the first out and the second in.
+    ///
+    /// Both Sources are required to have the same frame rate and number
+    /// of channels. If the first [`Source`] is shorter than `num_frames`,
+    /// then the crossfade is as long as the first [`Source`].
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Source, WaveformSource};
+    /// use babycat::source::FadeCurve;
+    ///
+    /// let first = WaveformSource::from_interleaved_samples(44100, 1, &[1.0, 1.0, 1.0, 1.0]);
+    /// let second = WaveformSource::from_interleaved_samples(44100, 1, &[0.5, 0.5, 0.5, 0.5]);
+    ///
+    /// // Overlap the last 2 frames of `first` with the first 2 frames of `second`.
+    /// let out = first.append_with_crossfade(second, 2, FadeCurve::Linear).unwrap();
+    ///
+    /// assert_eq!(
+    ///     out.collect_interleaved_samples(),
+    ///     &[1.0, 1.0, 1.0, 0.75, 0.5, 0.5],
+    /// );
+    /// ```
+    #[inline]
+    fn append_with_crossfade<S2: Source + Sized>(
+        self,
+        second: S2,
+        num_frames: usize,
+        curve: FadeCurve,
+    ) -> Result<Crossfade<Self, S2>, Error>
+    where
+        Self: Sized,
+    {
+        Crossfade::new(self, second, num_frames, curve)
+    }
+
+    /// Fade in the first `num_frames` frames of the [`Source`], starting from silence.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Source, WaveformSource};
+    /// use babycat::source::FadeCurve;
+    ///
+    /// let inp = vec![1.0, -1.0, 1.0, -1.0, 1.0, -1.0];
+    /// let source = WaveformSource::from_interleaved_samples(44100, 2, &inp);
+    ///
+    /// let out = source.fade_in(2, FadeCurve::Linear);
+    /// assert_eq!(
+    ///     out.collect_interleaved_samples(),
+    ///     &[0.0, 0.0, 0.5, -0.5, 1.0, -1.0],
+    /// );
+    /// ```
+    #[inline]
+    fn fade_in(self, num_frames: usize, curve: FadeCurve) -> FadeIn<Self>
+    where
+        Self: Sized,
+    {
+        FadeIn::new(self, num_frames, curve)
+    }
+
+    /// Fade out the last `num_frames` frames of the [`Source`].
+    ///
+    /// The end of the [`Source`] is detected by buffering `num_frames` frames ahead.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Source, WaveformSource};
+    /// use babycat::source::FadeCurve;
+    ///
+    /// let inp = vec![1.0, 1.0, 1.0, 1.0, 1.0];
+    /// let source = WaveformSource::from_interleaved_samples(44100, 1, &inp);
+    ///
+    /// let out = source.fade_out(4, FadeCurve::Linear);
+    /// assert_eq!(
+    ///     out.collect_interleaved_samples(),
+    ///     &[1.0, 0.75, 0.5, 0.25, 0.0],
+    /// );
+    /// ```
+    #[inline]
+    fn fade_out(self, num_frames: usize, curve: FadeCurve) -> FadeOut<Self>
+    where
+        Self: Sized,
+    {
+        FadeOut::new(self, num_frames, curve)
+    }
+
     /// Pad the *beginning* of the [`Source`] with silence.
     ///
     /// # Examples
@@ -143,6 +262,80 @@ pub trait Source: Signal + Iterator<Item = f32> + Debug + Send {
         AppendZeros::new(self, num_frames)
     }
 
+    /// Play the whole [`Source`] `num_times` times in a row.
+    ///
+    /// The samples are cached as they are read for the first time,
+    /// so the underlying [`Source`] is only decoded once.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Source, WaveformSource};
+    ///
+    /// let source = WaveformSource::from_interleaved_samples(44100, 2, &[0.1, 0.2, 0.3, 0.4]);
+    ///
+    /// let out_samples = source.repeat(3).collect_interleaved_samples();
+    /// assert_eq!(
+    ///     &out_samples,
+    ///     &[0.1, 0.2, 0.3, 0.4, 0.1, 0.2, 0.3, 0.4, 0.1, 0.2, 0.3, 0.4],
+    /// );
+    /// ```
+    #[inline]
+    fn repeat(self, num_times: usize) -> Loop<Self>
+    where
+        Self: Sized,
+    {
+        Loop::new(self, 0, None, Some(num_times))
+    }
+
+    /// Play the whole [`Source`] over and over again, forever.
+    ///
+    /// Use [`Source::take_frames()`] to turn this back into a finite [`Source`].
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Source, WaveformSource};
+    ///
+    /// let source = WaveformSource::from_interleaved_samples(44100, 1, &[0.1, 0.2, 0.3]);
+    ///
+    /// let out_samples = source.loop_forever().take_frames(7).collect_interleaved_samples();
+    /// assert_eq!(&out_samples, &[0.1, 0.2, 0.3, 0.1, 0.2, 0.3, 0.1]);
+    /// ```
+    #[inline]
+    fn loop_forever(self) -> Loop<Self>
+    where
+        Self: Sized,
+    {
+        Loop::new(self, 0, None, None)
+    }
+
+    /// Play the frames between `start_frame_idx` and `end_frame_idx` a total of
+    /// `num_times` times, then continue on to the end of the [`Source`].
+    ///
+    /// Pass `None` for `num_times` to loop between the two frames forever.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Source, WaveformSource};
+    ///
+    /// let source = WaveformSource::from_interleaved_samples(44100, 1, &[0.0, 0.1, 0.2, 0.3]);
+    ///
+    /// // Play frames 1 and 2 three times.
+    /// let out_samples = source.loop_frames(1, 3, Some(3)).collect_interleaved_samples();
+    /// assert_eq!(&out_samples, &[0.0, 0.1, 0.2, 0.1, 0.2, 0.1, 0.2, 0.3]);
+    /// ```
+    #[inline]
+    fn loop_frames(
+        self,
+        start_frame_idx: usize,
+        end_frame_idx: usize,
+        num_times: Option<usize>,
+    ) -> Loop<Self>
+    where
+        Self: Sized,
+    {
+        Loop::new(self, start_frame_idx, Some(end_frame_idx), num_times)
+    }
+
     ///
     /// # Examples
     /// ```
@@ -174,6 +367,104 @@ pub trait Source: Signal + Iterator<Item = f32> + Debug + Send {
         Gain::new(self, dbfs)
     }
 
+    /// Apply time-varying gain from a breakpoint [`GainEnvelope`](crate::envelope::GainEnvelope).
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Source, WaveformSource};
+    /// use babycat::envelope::{EnvelopeInterpolation, EnvelopePoint, GainEnvelope};
+    ///
+    /// let inp = vec![1.0, 1.0, 1.0, 1.0];
+    /// let source = WaveformSource::from_interleaved_samples(44100, 1, &inp);
+    ///
+    /// // Ramp from full volume down to silence over 4 frames.
+    /// let envelope = GainEnvelope::new(
+    ///     vec![
+    ///         EnvelopePoint::at_frame(0, 0.0),
+    ///         EnvelopePoint::at_frame(4, -200.0),
+    ///     ],
+    ///     EnvelopeInterpolation::Linear,
+    /// );
+    /// let out = source.gain_envelope(&envelope);
+    ///
+    /// assert_eq!(
+    ///     out.collect_interleaved_samples(),
+    ///     &[1.0, 0.75, 0.5, 0.25],
+    /// );
+    /// ```
+    #[inline]
+    fn gain_envelope(self, envelope: &Envelope) -> GainEnvelope<Self>
+    where
+        Self: Sized,
+    {
+        GainEnvelope::new(self, envelope)
+    }
+
+    /// Convolve this [`Source`] with an impulse response, such as a room reverb
+    /// or a telephone-channel simulation.
+    ///
+    /// The impulse response must have the same frame rate as this [`Source`], and
+    /// it can have:
+    /// - 1 channel, which is applied to every channel of this [`Source`].
+    /// - The same number of channels as this [`Source`], applied channel-by-channel.
+    /// - 4 channels when this [`Source`] is stereo, for true-stereo convolution.
+    ///   The channels are the left-to-left, left-to-right, right-to-left, and
+    ///   right-to-right responses, in that order.
+    ///
+    /// `wet_dry` mixes between the original audio at `0.0` and the fully
+    /// convolved audio at `1.0`. The output is aligned with the input, without
+    /// any added latency. It is followed by the tail of the impulse response,
+    /// so it is `ir.num_frames() - 1` frames longer than the input.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Source, Waveform, WaveformSource};
+    ///
+    /// let source = WaveformSource::from_interleaved_samples(8000, 1, &[1.0, 0.0, 0.5]);
+    /// // An echo two frames after the original.
+    /// let ir = Waveform::from_interleaved_samples(8000, 1, &[1.0, 0.0, 0.5]);
+    ///
+    /// let out: Vec<f32> = source
+    ///     .convolve(&ir, 1.0)
+    ///     .unwrap()
+    ///     .map(|s| (s * 1000.0).round() / 1000.0)
+    ///     .collect();
+    /// assert_eq!(out, &[1.0, 0.0, 1.0, 0.0, 0.25]);
+    /// ```
+    #[inline]
+    fn convolve(self, ir: &Waveform, wet_dry: f32) -> Result<Convolve<Self>, Error>
+    where
+        Self: Sized,
+    {
+        Convolve::new(self, ir, wet_dry)
+    }
+
+    /// Run every channel through a [`FilterDesign`], keeping separate filter state per channel.
+    ///
+    /// The design's coefficients should have been computed for this [`Source`]'s frame rate.
+    /// This is not called `filter()` because every [`Source`] is also an
+    /// [`Iterator`], and that name is taken by [`Iterator::filter()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Source, WaveformSource};
+    /// use babycat::filter::Biquad;
+    ///
+    /// let source = WaveformSource::from_interleaved_samples(16000, 2, &[1.0, 0.0, 1.0, 0.0, 1.0, 0.0]);
+    ///
+    /// let out = source
+    ///     .apply_filter(&Biquad::pre_emphasis(0.5).into())
+    ///     .collect_interleaved_samples();
+    /// assert_eq!(out, &[1.0, 0.0, 0.5, 0.0, 0.5, 0.0]);
+    /// ```
+    #[inline]
+    fn apply_filter(self, design: &FilterDesign) -> Filter<Self>
+    where
+        Self: Sized,
+    {
+        Filter::new(self, design)
+    }
+
     /// Multiply each sample by a constant factor.
     ///
     /// # Examples
@@ -257,6 +548,139 @@ pub trait Source: Signal + Iterator<Item = f32> + Debug + Send {
         Sum::new(self, second, offset_frames)
     }
 
+    /// Lower this [`Source`] (the *program*) whenever the `sidechain` [`Source`]
+    /// rises above a threshold, such as a music bed under a voice track.
+    ///
+    /// Both Sources are required to have the same frame rate. They can have a
+    /// different number of channels unless [`DuckOutput::Mix`] is requested.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Source, WaveformSource};
+    /// use babycat::source::{DuckAmount, DuckArgs};
+    ///
+    /// let music = WaveformSource::from_interleaved_samples(1000, 1, &[0.5; 6]);
+    /// let voice = WaveformSource::from_interleaved_samples(1000, 1, &[0.0, 0.0, 1.0, 1.0]);
+    ///
+    /// // Lower the music by 6 dB instantly while the voice is above -20 dBFS.
+    /// let args = DuckArgs::default()
+    ///     .set_threshold_dbfs(-20.0)
+    ///     .set_amount(DuckAmount::Depth(6.0206))
+    ///     .set_detector_release_milliseconds(0)
+    ///     .set_attack_milliseconds(0)
+    ///     .set_release_milliseconds(0)
+    ///     .set_hold_milliseconds(0);
+    /// let out = music.duck(voice, args).unwrap().collect_interleaved_samples();
+    ///
+    /// let rounded: Vec<f32> = out.iter().map(|s| (s * 100.0).round() / 100.0).collect();
+    /// assert_eq!(rounded, &[0.5, 0.5, 0.25, 0.25, 0.5, 0.5]);
+    /// ```
+    #[inline]
+    fn duck<S2: Source + Sized>(
+        self,
+        sidechain: S2,
+        args: DuckArgs,
+    ) -> Result<Duck<Self, S2>, Error>
+    where
+        Self: Sized,
+    {
+        Duck::new(self, sidechain, args)
+    }
+
+    /// Compress the dynamic range of this [`Source`], lowering the audio
+    /// that rises above a threshold.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Source, WaveformSource};
+    /// use babycat::source::CompressArgs;
+    ///
+    /// let source = WaveformSource::from_interleaved_samples(1000, 1, &[0.01, 1.0, 1.0]);
+    ///
+    /// // Full scale is 20 dB over the threshold, so a 2:1 ratio lowers it by 10 dB.
+    /// let args = CompressArgs::default()
+    ///     .set_threshold_dbfs(-20.0)
+    ///     .set_ratio(2.0)
+    ///     .set_knee_db(0.0)
+    ///     .set_attack_milliseconds(0);
+    /// let out = source.compress(args).collect_interleaved_samples();
+    ///
+    /// let rounded: Vec<f32> = out.iter().map(|s| (s * 1000.0).round() / 1000.0).collect();
+    /// assert_eq!(rounded, &[0.01, 0.316, 0.316]);
+    /// ```
+    #[inline]
+    fn compress(self, args: CompressArgs) -> Compress<Self>
+    where
+        Self: Sized,
+    {
+        Compress::new(self, args)
+    }
+
+    /// Keep this [`Source`] under a ceiling with a lookahead brickwall limiter.
+    ///
+    /// The output has the same number of frames as the input, and no
+    /// sample in the output is louder than the ceiling.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Source, WaveformSource};
+    /// use babycat::source::LimitArgs;
+    ///
+    /// let source = WaveformSource::from_interleaved_samples(1000, 1, &[0.25, 0.25, 1.0, 0.25]);
+    ///
+    /// // Limit to -6 dBFS, looking 1 millisecond ahead.
+    /// let args = LimitArgs::default()
+    ///     .set_ceiling_dbfs(-6.0206)
+    ///     .set_lookahead_milliseconds(1);
+    /// let out = source.limit(args).collect_interleaved_samples();
+    ///
+    /// assert_eq!(out.len(), 4);
+    /// assert!(out.iter().all(|s| s.abs() <= 0.5));
+    /// // The gain starts coming down one frame before the peak.
+    /// assert_eq!(out[0], 0.25);
+    /// assert!(out[1] < 0.25);
+    /// ```
+    #[inline]
+    fn limit(self, args: LimitArgs) -> Limit<Self>
+    where
+        Self: Sized,
+    {
+        Limit::new(self, args)
+    }
+
+    /// Lower the audio in this [`Source`] that falls below a threshold,
+    /// such as the background noise between words.
+    ///
+    /// This is a noise gate by default. Set
+    /// [`GateArgs::ratio`](crate::source::GateArgs::ratio) to a
+    /// finite value for a downward expander.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Source, WaveformSource};
+    /// use babycat::source::GateArgs;
+    ///
+    /// let source = WaveformSource::from_interleaved_samples(1000, 1, &[1.0, 0.001, 0.001, 1.0]);
+    ///
+    /// // Silence everything below -40 dBFS instantly.
+    /// let args = GateArgs::default()
+    ///     .set_threshold_dbfs(-40.0)
+    ///     .set_range_db(f32::INFINITY)
+    ///     .set_attack_milliseconds(0)
+    ///     .set_hold_milliseconds(0)
+    ///     .set_release_milliseconds(0);
+    /// let out = source.gate(args).collect_interleaved_samples();
+    ///
+    /// assert_eq!(out, &[1.0, 0.0, 0.0, 1.0]);
+    /// ```
+    #[inline]
+    fn gate(self, args: GateArgs) -> Gate<Self>
+    where
+        Self: Sized,
+    {
+        Gate::new(self, args)
+    }
+
     /// Take the first `n` frames.
     ///
     /// # Examples
@@ -338,6 +762,32 @@ pub trait Source: Signal + Iterator<Item = f32> + Debug + Send {
         }
     }
 
+    /// Remove the silence from the start and end of this [`Source`].
+    ///
+    /// A frame is silent when every channel is at or below `threshold_dbfs`.
+    /// Silence in the middle of the audio is kept.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Source, WaveformSource};
+    ///
+    /// let source = WaveformSource::from_interleaved_samples(
+    ///     1000,
+    ///     1,
+    ///     &[0.0, 0.0001, 0.5, 0.0, 0.25, 0.0, 0.0],
+    /// );
+    ///
+    /// let out = source.trim_silence(-60.0).collect_interleaved_samples();
+    /// assert_eq!(out, &[0.5, 0.0, 0.25]);
+    /// ```
+    #[inline]
+    fn trim_silence(self, threshold_dbfs: f32) -> TrimSilence<Self>
+    where
+        Self: Sized,
+    {
+        TrimSilence::new(self, threshold_dbfs)
+    }
+
     /// Select the first `n` channels.
     ///
     /// # Examples
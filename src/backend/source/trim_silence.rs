@@ -0,0 +1,115 @@
+use std::collections::VecDeque;
+
+use crate::backend::display::est_num_frames_to_str;
+use crate::backend::loudness::dbfs_to_ratio;
+use crate::backend::silence::is_silent_frame;
+use crate::backend::source::dynamics::read_frame;
+use crate::backend::Signal;
+use crate::backend::Source;
+
+/// [`Source::trim_silence()`]
+///
+/// Leading silence is skipped as it is read. Silence in the middle of the
+/// audio is held back until more audio arrives, so trailing silence is
+/// never emitted. Only as much silence as the longest gap is buffered.
+pub struct TrimSilence<S: Source> {
+    iter: S,
+    threshold_dbfs: f32,
+    threshold: f32,
+    started: bool,
+    input_drained: bool,
+    /// Silent samples that follow the most recent non-silent frame.
+    held: Vec<f32>,
+    /// Samples that are ready to be emitted.
+    ready: VecDeque<f32>,
+    frame: Vec<f32>,
+}
+
+impl<S: Source> std::fmt::Debug for TrimSilence<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "TrimSilence {{ {} frames,  {} channels,  {} hz,  {}; threshold: {} dbfs }}",
+            est_num_frames_to_str(self.num_frames_estimate()),
+            self.num_channels(),
+            self.frame_rate_hz(),
+            self.duration_estimate_to_str(),
+            self.threshold_dbfs,
+        )
+    }
+}
+
+impl<S: Source> TrimSilence<S> {
+    #[inline]
+    pub fn new(iter: S, threshold_dbfs: f32) -> Self {
+        let num_channels = iter.num_channels() as usize;
+        Self {
+            iter,
+            threshold_dbfs,
+            threshold: dbfs_to_ratio(threshold_dbfs),
+            started: false,
+            input_drained: false,
+            held: Vec::new(),
+            ready: VecDeque::new(),
+            frame: Vec::with_capacity(num_channels),
+        }
+    }
+
+    /// Reads until the next non-silent frame and moves it, along with any
+    /// silence held before it, into `self.ready`.
+    /// Returns `false` once the input has ended.
+    fn fill_ready(&mut self) -> bool {
+        while !self.input_drained && read_frame(&mut self.iter, &mut self.frame) {
+            if !is_silent_frame(&self.frame, self.threshold) {
+                self.started = true;
+                self.ready.extend(self.held.drain(..));
+                self.ready.extend(self.frame.iter().copied());
+                return true;
+            }
+            if self.started {
+                self.held.extend(self.frame.iter().copied());
+            }
+        }
+        self.input_drained = true;
+        false
+    }
+}
+
+impl<S: Source> Source for TrimSilence<S> {}
+
+impl<S: Source> Signal for TrimSilence<S> {
+    #[inline]
+    fn frame_rate_hz(&self) -> u32 {
+        self.iter.frame_rate_hz()
+    }
+
+    #[inline]
+    fn num_channels(&self) -> u16 {
+        self.iter.num_channels()
+    }
+
+    /// We cannot know how much silence there is without reading the input.
+    #[inline]
+    fn num_frames_estimate(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<S: Source> Iterator for TrimSilence<S> {
+    type Item = f32;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.iter.size_hint();
+        let buffered = self.ready.len();
+        (buffered, upper.map(|u| u + buffered + self.held.len()))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.ready.is_empty() && !self.fill_ready() {
+            return None;
+        }
+        self.ready.pop_front()
+    }
+}
@@ -106,3 +106,85 @@ impl Iterator for WaveformSource {
         self.next()
     }
 }
+
+/// A [`Source`] that reads the samples of a borrowed [`Waveform`].
+///
+/// Functions that take a `&Waveform` use this to run their streaming
+/// implementation without cloning the samples into a [`WaveformSource`].
+#[derive(Clone)]
+pub(crate) struct BorrowedWaveformSource<'a> {
+    waveform: &'a Waveform,
+    current_sample: usize,
+}
+
+impl std::fmt::Debug for BorrowedWaveformSource<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "BorrowedWaveformSource {{ {} frames,  {} channels,  {} hz,  {} }}",
+            est_num_frames_to_str(self.num_frames_estimate()),
+            self.num_channels(),
+            self.frame_rate_hz(),
+            self.duration_estimate_to_str(),
+        )
+    }
+}
+
+impl<'a> BorrowedWaveformSource<'a> {
+    #[inline]
+    pub(crate) fn new(waveform: &'a Waveform) -> Self {
+        Self {
+            waveform,
+            current_sample: 0,
+        }
+    }
+
+    #[inline]
+    fn remaining_samples(&self) -> usize {
+        self.waveform
+            .num_samples()
+            .saturating_sub(self.current_sample)
+    }
+}
+
+impl Source for BorrowedWaveformSource<'_> {}
+
+impl Signal for BorrowedWaveformSource<'_> {
+    #[inline]
+    fn frame_rate_hz(&self) -> u32 {
+        self.waveform.frame_rate_hz()
+    }
+
+    #[inline]
+    fn num_channels(&self) -> u16 {
+        self.waveform.num_channels()
+    }
+
+    #[inline]
+    fn num_frames_estimate(&self) -> Option<usize> {
+        Some(self.remaining_samples() / self.waveform.num_channels() as usize)
+    }
+}
+
+impl Iterator for BorrowedWaveformSource<'_> {
+    type Item = f32;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining_samples();
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let retval = self.waveform.get_interleaved_sample(self.current_sample)?;
+        self.current_sample += 1;
+        Some(retval)
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.current_sample += n;
+        self.next()
+    }
+}
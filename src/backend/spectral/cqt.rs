@@ -0,0 +1,511 @@
+//! The constant-Q transform and chromagrams.
+
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+
+use crate::backend::constants::{
+    DEFAULT_BINS_PER_OCTAVE, DEFAULT_CQT_MIN_FREQUENCY_HZ, DEFAULT_CQT_NUM_BINS,
+    DEFAULT_HOP_LENGTH, DEFAULT_WINDOW_TYPE, NUM_CHROMA,
+};
+use crate::backend::spectral::features::Features;
+use crate::backend::spectral::window::window;
+use crate::backend::Error;
+use crate::backend::Signal;
+use crate::backend::Waveform;
+
+/// Spectral values smaller than this fraction of a kernel's largest
+/// value are left out of the kernel, which makes it much faster to apply.
+const KERNEL_THRESHOLD: f64 = 1e-3;
+/// The number of taps in the lowpass filter used to halve the frame rate.
+const HALVING_FILTER_LENGTH: usize = 129;
+/// The cutoff of the halving filter, as a fraction of the frame rate.
+/// This leaves some room below the new Nyquist frequency of 0.25.
+const HALVING_FILTER_CUTOFF: f64 = 0.225;
+/// Chroma frames whose loudest pitch class is below this are left unnormalized.
+const MIN_CHROMA: f32 = 1e-10;
+
+fn default_hop_length() -> usize {
+    DEFAULT_HOP_LENGTH
+}
+
+fn default_min_frequency_hz() -> f32 {
+    DEFAULT_CQT_MIN_FREQUENCY_HZ
+}
+
+fn default_num_bins() -> usize {
+    DEFAULT_CQT_NUM_BINS
+}
+
+fn default_bins_per_octave() -> usize {
+    DEFAULT_BINS_PER_OCTAVE
+}
+
+/// Settings for a constant-Q transform.
+///
+/// The default values match the defaults of librosa: 84 bins, 12 to an
+/// octave, starting at C1 (about 32.7 hz), advancing 512 frames at a time.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CqtArgs {
+    /// The number of frames between consecutive time steps.
+    #[serde(default = "default_hop_length")]
+    pub hop_length: usize,
+    /// The center frequency, in hz, of the lowest bin.
+    #[serde(default = "default_min_frequency_hz")]
+    pub min_frequency_hz: f32,
+    /// The number of bins.
+    #[serde(default = "default_num_bins")]
+    pub num_bins: usize,
+    /// The number of bins in each octave. Use 12 for one bin per semitone.
+    #[serde(default = "default_bins_per_octave")]
+    pub bins_per_octave: usize,
+    /// The shape of the window of every bin. Use one of the
+    /// `WINDOW_TYPE_*` values in [`constants`][crate::constants].
+    #[serde(default)]
+    pub window_type: u32,
+}
+
+impl Default for CqtArgs {
+    fn default() -> Self {
+        CqtArgs {
+            hop_length: DEFAULT_HOP_LENGTH,
+            min_frequency_hz: DEFAULT_CQT_MIN_FREQUENCY_HZ,
+            num_bins: DEFAULT_CQT_NUM_BINS,
+            bins_per_octave: DEFAULT_BINS_PER_OCTAVE,
+            window_type: DEFAULT_WINDOW_TYPE,
+        }
+    }
+}
+
+impl CqtArgs {
+    /// Set the [`hop_length`](#structfield.hop_length) field.
+    #[must_use]
+    pub fn set_hop_length(&mut self, hop_length: usize) -> Self {
+        self.hop_length = hop_length;
+        *self
+    }
+
+    /// Set the [`min_frequency_hz`](#structfield.min_frequency_hz) field.
+    #[must_use]
+    pub fn set_min_frequency_hz(&mut self, min_frequency_hz: f32) -> Self {
+        self.min_frequency_hz = min_frequency_hz;
+        *self
+    }
+
+    /// Set the [`num_bins`](#structfield.num_bins) field.
+    #[must_use]
+    pub fn set_num_bins(&mut self, num_bins: usize) -> Self {
+        self.num_bins = num_bins;
+        *self
+    }
+
+    /// Set the [`bins_per_octave`](#structfield.bins_per_octave) field.
+    #[must_use]
+    pub fn set_bins_per_octave(&mut self, bins_per_octave: usize) -> Self {
+        self.bins_per_octave = bins_per_octave;
+        *self
+    }
+
+    /// Set the [`window_type`](#structfield.window_type) field.
+    #[must_use]
+    pub fn set_window_type(&mut self, window_type: u32) -> Self {
+        self.window_type = window_type;
+        *self
+    }
+
+    /// Returns the center frequency of the given bin, in hz.
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    pub fn bin_frequency_hz(&self, bin_idx: usize) -> f32 {
+        (f64::from(self.min_frequency_hz)
+            * 2.0_f64.powf(bin_idx as f64 / self.bins_per_octave as f64)) as f32
+    }
+
+    /// Returns how many time steps a signal of `num_frames` frames has.
+    pub fn num_time_steps(&self, num_frames: usize) -> usize {
+        if self.hop_length == 0 {
+            return 0;
+        }
+        1 + num_frames / self.hop_length
+    }
+
+    /// The quality factor, which is each bin's center frequency
+    /// divided by its bandwidth.
+    #[allow(clippy::cast_precision_loss)]
+    fn quality_factor(&self) -> f64 {
+        1.0 / (2.0_f64.powf(1.0 / self.bins_per_octave as f64) - 1.0)
+    }
+
+    fn validate(&self, frame_rate_hz: u32) -> Result<(), Error> {
+        if self.hop_length == 0 {
+            return Err(Error::WrongHopLength(self.hop_length));
+        }
+        if self.bins_per_octave == 0 {
+            return Err(Error::WrongBinsPerOctave(self.bins_per_octave));
+        }
+        if self.num_bins == 0 {
+            return Err(Error::WrongNumFeatures(self.num_bins, self.num_bins));
+        }
+        // The highest bin, including half of its bandwidth, must fit
+        // below the Nyquist frequency.
+        let max_frequency_hz = self.bin_frequency_hz(self.num_bins - 1);
+        #[allow(clippy::cast_possible_truncation)]
+        let upper_edge_hz = max_frequency_hz * (1.0 + 0.5 / self.quality_factor() as f32);
+        #[allow(clippy::cast_precision_loss)]
+        let nyquist_hz = frame_rate_hz as f32 / 2.0;
+        if self.min_frequency_hz <= 0.0 || upper_edge_hz >= nyquist_hz {
+            return Err(Error::WrongFilterFrequency(max_frequency_hz, frame_rate_hz));
+        }
+        // Check the window type before building any kernels.
+        window(self.window_type, 1)?;
+        Ok(())
+    }
+}
+
+/// The spectral kernels of one octave, at one frame rate.
+struct OctaveKernels {
+    fft_size: usize,
+    /// The index of the lowest bin in this octave.
+    first_bin_idx: usize,
+    /// For every bin, the nonzero FFT bins of its conjugated kernel,
+    /// already divided by the FFT size.
+    kernels: Vec<Vec<(usize, Complex<f32>)>>,
+}
+
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn octave_kernels(
+    args: &CqtArgs,
+    frame_rate_hz: f64,
+    first_bin_idx: usize,
+    num_bins: usize,
+) -> Result<OctaveKernels, Error> {
+    let quality_factor = args.quality_factor();
+    let lengths: Vec<usize> = (first_bin_idx..first_bin_idx + num_bins)
+        .map(|bin_idx| {
+            let frequency_hz = f64::from(args.bin_frequency_hz(bin_idx));
+            ((quality_factor * frame_rate_hz / frequency_hz).ceil() as usize).max(1)
+        })
+        .collect();
+    let fft_size = lengths
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or(1)
+        .next_power_of_two();
+    let fft = FftPlanner::new().plan_fft_forward(fft_size);
+    let mut kernels = Vec::with_capacity(num_bins);
+    let mut buffer = vec![Complex::new(0.0_f32, 0.0_f32); fft_size];
+    for (offset, length) in lengths.iter().enumerate() {
+        let frequency_hz = f64::from(args.bin_frequency_hz(first_bin_idx + offset));
+        let window = window(args.window_type, *length)?;
+        let window_sum: f64 = window.iter().map(|w| f64::from(*w)).sum();
+        buffer.fill(Complex::new(0.0, 0.0));
+        // Center the kernel in the FFT buffer, so that it lines up with
+        // the middle of each segment of audio.
+        let start = fft_size / 2 - length / 2;
+        for (n, w) in window.iter().enumerate() {
+            let phase = 2.0 * PI * frequency_hz * (n as f64 - (length / 2) as f64) / frame_rate_hz;
+            let scale = f64::from(*w) / window_sum;
+            buffer[start + n] =
+                Complex::new((scale * phase.cos()) as f32, (scale * phase.sin()) as f32);
+        }
+        fft.process(&mut buffer);
+        let max_norm = buffer.iter().map(|v| v.norm()).fold(0.0_f32, f32::max);
+        let threshold = max_norm * KERNEL_THRESHOLD as f32;
+        kernels.push(
+            buffer
+                .iter()
+                .enumerate()
+                .filter(|(_, value)| value.norm() >= threshold)
+                .map(|(j, value)| (j, value.conj() / fft_size as f32))
+                .collect(),
+        );
+    }
+    Ok(OctaveKernels {
+        fft_size,
+        first_bin_idx,
+        kernels,
+    })
+}
+
+/// Lowpass filters a signal and keeps every other sample.
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+fn halve_frame_rate(signal: &[f32]) -> Vec<f32> {
+    let middle = HALVING_FILTER_LENGTH / 2;
+    let mut taps: Vec<f64> = (0..HALVING_FILTER_LENGTH)
+        .map(|n| {
+            let x = n as f64 - middle as f64;
+            let sinc = if n == middle {
+                2.0 * HALVING_FILTER_CUTOFF
+            } else {
+                (2.0 * PI * HALVING_FILTER_CUTOFF * x).sin() / (PI * x)
+            };
+            let phase = 2.0 * PI * n as f64 / (HALVING_FILTER_LENGTH - 1) as f64;
+            sinc * (0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos())
+        })
+        .collect();
+    let taps_sum: f64 = taps.iter().sum();
+    for tap in &mut taps {
+        *tap /= taps_sum;
+    }
+    (0..signal.len().div_ceil(2))
+        .map(|out_idx| {
+            let center = 2 * out_idx;
+            let value: f64 = taps
+                .iter()
+                .enumerate()
+                .filter_map(|(n, tap)| {
+                    (center + n)
+                        .checked_sub(middle)
+                        .and_then(|idx| signal.get(idx))
+                        .map(|x| tap * f64::from(*x))
+                })
+                .sum();
+            value as f32
+        })
+        .collect()
+}
+
+/// Computes the magnitude of the constant-Q transform of every channel.
+///
+/// Bin `k` is centered on `min_frequency_hz * 2^(k / bins_per_octave)`,
+/// and its window is long enough that its bandwidth is a constant fraction
+/// of its center frequency. Each kernel is normalized so that a sine wave
+/// of amplitude `a` on a bin's center frequency gives that bin a
+/// magnitude of about `a / 2`.
+///
+/// The `t`-th time step is centered on frame `t * hop_length`, and the
+/// audio is padded with silence on both sides. To keep the FFTs short,
+/// each octave is computed at half the frame rate of the octave above it.
+///
+/// The result has one feature per bin.
+///
+/// # Errors
+/// Returns [`Error::WrongHopLength`] if `hop_length` is zero,
+/// [`Error::WrongBinsPerOctave`] if `bins_per_octave` is zero,
+/// [`Error::WrongNumFeatures`] if `num_bins` is zero,
+/// and [`Error::WrongFilterFrequency`] if the highest bin does not fit
+/// below the Nyquist frequency.
+///
+/// # Examples
+/// ```
+/// use babycat::spectral::{cqt, CqtArgs};
+/// use babycat::Waveform;
+///
+/// // A4, which is 45 semitones above C1.
+/// let samples: Vec<f32> = (0..22050)
+///     .map(|i| (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 22050.0).sin())
+///     .collect();
+/// let waveform = Waveform::from_interleaved_samples(22050, 1, &samples);
+///
+/// let transform = cqt(&waveform, CqtArgs::default()).unwrap();
+/// assert_eq!(transform.num_features(), 84);
+/// let t = transform.num_time_steps() / 2;
+/// let loudest_bin = (0..84)
+///     .max_by(|&a, &b| {
+///         let a = transform.get(0, a, t).unwrap();
+///         let b = transform.get(0, b, t).unwrap();
+///         a.total_cmp(&b)
+///     })
+///     .unwrap();
+/// assert_eq!(loudest_bin, 45);
+/// assert!((transform.get(0, 45, t).unwrap() - 0.5).abs() < 0.02);
+/// ```
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_wrap
+)]
+pub fn cqt(waveform: &Waveform, args: CqtArgs) -> Result<Features, Error> {
+    let frame_rate_hz = waveform.frame_rate_hz();
+    args.validate(frame_rate_hz)?;
+    let num_channels = waveform.num_channels() as usize;
+    let num_frames = waveform.num_frames();
+    let num_time_steps = args.num_time_steps(num_frames);
+    let num_bins = args.num_bins;
+
+    // Octaves are counted down from the highest bin, so that the
+    // highest octave is computed at the original frame rate.
+    let num_octaves = num_bins.div_ceil(args.bins_per_octave);
+    let mut octaves = Vec::with_capacity(num_octaves);
+    for octave_idx in 0..num_octaves {
+        let last_bin_idx = num_bins - octave_idx * args.bins_per_octave;
+        let first_bin_idx = last_bin_idx.saturating_sub(args.bins_per_octave);
+        let octave_frame_rate_hz = f64::from(frame_rate_hz) / 2.0_f64.powi(octave_idx as i32);
+        octaves.push(octave_kernels(
+            &args,
+            octave_frame_rate_hz,
+            first_bin_idx,
+            last_bin_idx - first_bin_idx,
+        )?);
+    }
+
+    let mut planner = FftPlanner::new();
+    let mut data = vec![0.0_f32; num_channels * num_bins * num_time_steps];
+    let interleaved = waveform.to_interleaved_samples();
+    for channel_idx in 0..num_channels {
+        let mut signal: Vec<f32> = interleaved
+            .iter()
+            .skip(channel_idx)
+            .step_by(num_channels)
+            .copied()
+            .collect();
+        let out = &mut data[channel_idx * num_bins * num_time_steps..][..num_bins * num_time_steps];
+        for (octave_idx, octave) in octaves.iter().enumerate() {
+            if octave_idx > 0 {
+                signal = halve_frame_rate(&signal);
+            }
+            let fft = planner.plan_fft_forward(octave.fft_size);
+            let mut buffer = vec![Complex::new(0.0_f32, 0.0_f32); octave.fft_size];
+            let decimation = 2.0_f64.powi(octave_idx as i32);
+            for time_step_idx in 0..num_time_steps {
+                let center =
+                    ((time_step_idx * args.hop_length) as f64 / decimation).round() as isize;
+                let start = center - (octave.fft_size / 2) as isize;
+                for (n, value) in buffer.iter_mut().enumerate() {
+                    let idx = start + n as isize;
+                    let sample = if idx >= 0 {
+                        signal.get(idx as usize).copied().unwrap_or(0.0)
+                    } else {
+                        0.0
+                    };
+                    *value = Complex::new(sample, 0.0);
+                }
+                fft.process(&mut buffer);
+                for (offset, kernel) in octave.kernels.iter().enumerate() {
+                    let value: Complex<f32> = kernel.iter().map(|(j, k)| buffer[*j] * k).sum();
+                    out[(octave.first_bin_idx + offset) * num_time_steps + time_step_idx] =
+                        value.norm();
+                }
+            }
+        }
+    }
+
+    Ok(Features::from_parts(
+        frame_rate_hz,
+        args.hop_length,
+        waveform.num_channels(),
+        num_bins,
+        num_time_steps,
+        data,
+    ))
+}
+
+/// Folds a constant-Q transform into a 12-bin chromagram.
+///
+/// Every bin is added to the pitch class of the nearest semitone,
+/// with C as pitch class 0, C# as 1, and so on up to B as 11. Each
+/// time step is then divided by its loudest pitch class, so the
+/// loudest pitch class in every time step is 1.
+///
+/// `args` must be the settings that `cqt` was computed with.
+/// Using 3 bins per octave for every pitch class, which is 36 bins
+/// per octave, makes the chromagram less sensitive to tuning.
+///
+/// # Errors
+/// Returns [`Error::WrongNumFeatures`] if `cqt` does not have
+/// `args.num_bins` features.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_wrap
+)]
+pub fn chroma_from_cqt(cqt: &Features, args: CqtArgs) -> Result<Features, Error> {
+    if cqt.num_features() != args.num_bins {
+        return Err(Error::WrongNumFeatures(args.num_bins, cqt.num_features()));
+    }
+    let num_time_steps = cqt.num_time_steps();
+    let pitch_classes: Vec<usize> = (0..args.num_bins)
+        .map(|bin_idx| {
+            let midi = 69.0 + 12.0 * (f64::from(args.bin_frequency_hz(bin_idx)) / 440.0).log2();
+            (midi.round() as i64).rem_euclid(NUM_CHROMA as i64) as usize
+        })
+        .collect();
+    let mut data = vec![0.0_f32; cqt.num_channels() as usize * NUM_CHROMA * num_time_steps];
+    if num_time_steps == 0 {
+        return Ok(Features::from_parts(
+            cqt.frame_rate_hz(),
+            cqt.hop_length(),
+            cqt.num_channels(),
+            NUM_CHROMA,
+            num_time_steps,
+            data,
+        ));
+    }
+    for (channel_in, channel_out) in cqt
+        .as_slice()
+        .chunks_exact(args.num_bins * num_time_steps)
+        .zip(data.chunks_exact_mut(NUM_CHROMA * num_time_steps))
+    {
+        for (row_in, pitch_class) in channel_in.chunks_exact(num_time_steps).zip(&pitch_classes) {
+            let row_out = &mut channel_out[pitch_class * num_time_steps..][..num_time_steps];
+            for (out, value) in row_out.iter_mut().zip(row_in) {
+                *out += value;
+            }
+        }
+        for time_step_idx in 0..num_time_steps {
+            let max = (0..NUM_CHROMA)
+                .map(|pitch_class| channel_out[pitch_class * num_time_steps + time_step_idx])
+                .fold(0.0_f32, f32::max);
+            if max > MIN_CHROMA {
+                for pitch_class in 0..NUM_CHROMA {
+                    channel_out[pitch_class * num_time_steps + time_step_idx] /= max;
+                }
+            }
+        }
+    }
+    Ok(Features::from_parts(
+        cqt.frame_rate_hz(),
+        cqt.hop_length(),
+        cqt.num_channels(),
+        NUM_CHROMA,
+        num_time_steps,
+        data,
+    ))
+}
+
+/// Computes a 12-bin chromagram of every channel from its constant-Q transform.
+///
+/// This is [`cqt()`] followed by [`chroma_from_cqt()`].
+///
+/// # Errors
+/// Returns the same errors as [`cqt()`].
+///
+/// # Examples
+/// ```
+/// use babycat::spectral::{chroma, CqtArgs};
+/// use babycat::Waveform;
+///
+/// // An A major triad: A4, C#5, and E5.
+/// let samples: Vec<f32> = (0..22050)
+///     .map(|i| {
+///         let t = i as f32 / 22050.0;
+///         [440.0, 554.37, 659.26]
+///             .iter()
+///             .map(|hz| 0.2 * (2.0 * std::f32::consts::PI * hz * t).sin())
+///             .sum()
+///     })
+///     .collect();
+/// let waveform = Waveform::from_interleaved_samples(22050, 1, &samples);
+///
+/// let args = CqtArgs::default().set_bins_per_octave(36).set_num_bins(252);
+/// let chromagram = chroma(&waveform, args).unwrap();
+/// assert_eq!(chromagram.num_features(), 12);
+/// let t = chromagram.num_time_steps() / 2;
+/// for pitch_class in [9, 1, 4] {
+///     assert!(chromagram.get(0, pitch_class, t).unwrap() > 0.5);
+/// }
+/// for pitch_class in [0, 2, 3, 5, 6, 7, 8, 10, 11] {
+///     assert!(chromagram.get(0, pitch_class, t).unwrap() < 0.5);
+/// }
+/// ```
+pub fn chroma(waveform: &Waveform, args: CqtArgs) -> Result<Features, Error> {
+    chroma_from_cqt(&cqt(waveform, args)?, args)
+}
@@ -0,0 +1,248 @@
+//! Feature matrices, such as mel spectrograms and MFCCs.
+
+use crate::backend::Error;
+
+/// The smallest power that [`Features::to_db()`] takes the logarithm of.
+const MIN_POWER: f32 = 1e-10;
+
+/// Values computed for every channel and time step of a signal, such as
+/// a mel spectrogram or MFCCs.
+///
+/// The values are stored as `(channels, features, time steps)`, so the
+/// value for channel `c`, feature `f` and time step `t` is at index
+/// `(c * num_features + f) * num_time_steps + t`.
+#[derive(Clone, Debug, PartialEq)]
+#[allow(clippy::struct_field_names)]
+pub struct Features {
+    frame_rate_hz: u32,
+    hop_length: usize,
+    num_channels: u16,
+    num_features: usize,
+    num_time_steps: usize,
+    data: Vec<f32>,
+}
+
+impl Features {
+    pub(crate) fn from_parts(
+        frame_rate_hz: u32,
+        hop_length: usize,
+        num_channels: u16,
+        num_features: usize,
+        num_time_steps: usize,
+        data: Vec<f32>,
+    ) -> Self {
+        debug_assert_eq!(
+            data.len(),
+            num_channels as usize * num_features * num_time_steps
+        );
+        Self {
+            frame_rate_hz,
+            hop_length,
+            num_channels,
+            num_features,
+            num_time_steps,
+            data,
+        }
+    }
+
+    /// Returns the frame rate of the signal that the features were computed from.
+    #[inline]
+    pub fn frame_rate_hz(&self) -> u32 {
+        self.frame_rate_hz
+    }
+
+    /// Returns the number of frames between consecutive time steps.
+    #[inline]
+    pub fn hop_length(&self) -> usize {
+        self.hop_length
+    }
+
+    /// Returns the number of channels.
+    #[inline]
+    pub fn num_channels(&self) -> u16 {
+        self.num_channels
+    }
+
+    /// Returns the number of features in each time step.
+    #[inline]
+    pub fn num_features(&self) -> usize {
+        self.num_features
+    }
+
+    /// Returns the number of time steps.
+    #[inline]
+    pub fn num_time_steps(&self) -> usize {
+        self.num_time_steps
+    }
+
+    /// Returns the value for one channel, feature, and time step,
+    /// or `None` if any of them are out of bounds.
+    #[inline]
+    pub fn get(&self, channel_idx: u16, feature_idx: usize, time_step_idx: usize) -> Option<f32> {
+        if channel_idx >= self.num_channels
+            || feature_idx >= self.num_features
+            || time_step_idx >= self.num_time_steps
+        {
+            return None;
+        }
+        let row = channel_idx as usize * self.num_features + feature_idx;
+        Some(self.data[row * self.num_time_steps + time_step_idx])
+    }
+
+    /// Returns the values of one channel, with the shape `(features, time steps)`.
+    #[inline]
+    pub fn channel(&self, channel_idx: usize) -> &[f32] {
+        let len = self.num_features * self.num_time_steps;
+        &self.data[channel_idx * len..(channel_idx + 1) * len]
+    }
+
+    /// Returns every value, with the shape `(channels, features, time steps)`.
+    #[inline]
+    pub fn as_slice(&self) -> &[f32] {
+        &self.data
+    }
+
+    /// Returns every value mutably.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [f32] {
+        &mut self.data
+    }
+
+    /// Converts power values to decibels.
+    ///
+    /// Every value becomes `10 * log10(max(value, 1e-10))`. If `top_db`
+    /// is given, values more than `top_db` decibels below the loudest
+    /// value, across every channel, are raised to that floor.
+    #[must_use]
+    pub fn to_db(&self, top_db: Option<f32>) -> Self {
+        let mut data: Vec<f32> = self
+            .data
+            .iter()
+            .map(|value| 10.0 * value.max(MIN_POWER).log10())
+            .collect();
+        if let Some(top_db) = top_db {
+            let floor = data.iter().copied().fold(f32::NEG_INFINITY, f32::max) - top_db;
+            for value in &mut data {
+                *value = value.max(floor);
+            }
+        }
+        Self { data, ..*self }
+    }
+
+    /// Estimates the derivative of every feature across time.
+    ///
+    /// Each value is the `order`-th derivative of a degree-`order`
+    /// polynomial fitted to the `width` time steps around it, which is a
+    /// Savitzky-Golay filter. Time steps near the edges use the polynomial
+    /// fitted to the first or last `width` time steps. Use an `order` of 1
+    /// for deltas and 2 for delta-deltas.
+    ///
+    /// # Errors
+    /// Returns [`Error::WrongDeltaWidth`] if `width` is even, smaller
+    /// than 3, or longer than the number of time steps, and
+    /// [`Error::WrongDeltaOrder`] if `order` is zero or not smaller than `width`.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn deltas(&self, width: usize, order: usize) -> Result<Self, Error> {
+        if width < 3 || width.is_multiple_of(2) || width > self.num_time_steps {
+            return Err(Error::WrongDeltaWidth(width, self.num_time_steps));
+        }
+        if order == 0 || order >= width {
+            return Err(Error::WrongDeltaOrder(order, width));
+        }
+        let coefficients = savgol_coefficients(width, order);
+        let half_width = width / 2;
+        let num_time_steps = self.num_time_steps;
+        let mut data = vec![0.0_f32; self.data.len()];
+        for (row_in, row_out) in self
+            .data
+            .chunks_exact(num_time_steps)
+            .zip(data.chunks_exact_mut(num_time_steps))
+        {
+            for (time_step_idx, out) in row_out.iter_mut().enumerate() {
+                // The fitted polynomial's highest derivative is constant,
+                // so the edges take the value of the nearest full window.
+                let center = time_step_idx.clamp(half_width, num_time_steps - 1 - half_width);
+                let window = &row_in[center - half_width..=center + half_width];
+                let value: f64 = window
+                    .iter()
+                    .zip(&coefficients)
+                    .map(|(x, c)| f64::from(*x) * c)
+                    .sum();
+                *out = value as f32;
+            }
+        }
+        Ok(Self { data, ..*self })
+    }
+}
+
+impl From<Features> for Vec<f32> {
+    fn from(item: Features) -> Self {
+        item.data
+    }
+}
+
+/// Returns the weights that estimate the `order`-th derivative from
+/// `width` evenly-spaced values, by least-squares fitting a polynomial
+/// of degree `order`.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_wrap,
+    clippy::cast_possible_truncation
+)]
+fn savgol_coefficients(width: usize, order: usize) -> Vec<f64> {
+    let half_width = (width / 2) as i64;
+    let positions: Vec<f64> = (-half_width..=half_width).map(|n| n as f64).collect();
+    let size = order + 1;
+    // Solve the normal equations with Gauss-Jordan elimination, keeping
+    // only the row of the inverse that gives the leading coefficient.
+    let mut matrix = vec![vec![0.0_f64; 2 * size]; size];
+    for (i, row) in matrix.iter_mut().enumerate() {
+        for (j, value) in row[..size].iter_mut().enumerate() {
+            *value = positions.iter().map(|x| x.powi((i + j) as i32)).sum();
+        }
+        row[size + i] = 1.0;
+    }
+    for col in 0..size {
+        let pivot_idx = (col..size)
+            .max_by(|&a, &b| matrix[a][col].abs().total_cmp(&matrix[b][col].abs()))
+            .unwrap_or(col);
+        matrix.swap(col, pivot_idx);
+        let pivot = matrix[col][col];
+        for value in &mut matrix[col] {
+            *value /= pivot;
+        }
+        for row_idx in 0..size {
+            if row_idx != col {
+                let factor = matrix[row_idx][col];
+                if factor != 0.0 {
+                    let pivot_row = matrix[col].clone();
+                    for (value, p) in matrix[row_idx].iter_mut().zip(&pivot_row) {
+                        *value -= factor * p;
+                    }
+                }
+            }
+        }
+    }
+    let factorial: f64 = (1..=order).map(|k| k as f64).product();
+    let inverse_row = &matrix[order][size..];
+    positions
+        .iter()
+        .map(|x| {
+            factorial
+                * inverse_row
+                    .iter()
+                    .enumerate()
+                    .map(|(j, m)| m * x.powi(j as i32))
+                    .sum::<f64>()
+        })
+        .collect()
+}
+
+/// The features computed from one audio file, or the error that stopped us.
+#[derive(Clone, Debug)]
+pub struct FeaturesNamedResult {
+    /// The name of the audio file.
+    pub name: String,
+    /// The features, or the error raised while decoding or analyzing the file.
+    pub result: Result<Features, Error>,
+}
@@ -0,0 +1,453 @@
+//! Mel filterbanks, mel spectrograms, and mel-frequency cepstral coefficients.
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::constants::{
+    DEFAULT_MEL_NORMALIZE, DEFAULT_MEL_POWER, DEFAULT_MEL_SCALE, DEFAULT_MFCC_LIFTER,
+    DEFAULT_MFCC_TOP_DB, DEFAULT_NUM_MELS, DEFAULT_NUM_MFCC, MEL_SCALE_HTK, MEL_SCALE_SLANEY,
+};
+use crate::backend::spectral::features::Features;
+use crate::backend::spectral::stft::Stft;
+use crate::backend::Error;
+
+/// Below this frequency, the Slaney mel scale is linear.
+const SLANEY_MIN_LOG_HZ: f64 = 1000.0;
+/// The width of one mel, in hz, in the linear part of the Slaney mel scale.
+const SLANEY_HZ_PER_MEL: f64 = 200.0 / 3.0;
+
+fn default_num_mels() -> usize {
+    DEFAULT_NUM_MELS
+}
+
+fn default_mel_normalize() -> bool {
+    DEFAULT_MEL_NORMALIZE
+}
+
+fn default_mel_power() -> f32 {
+    DEFAULT_MEL_POWER
+}
+
+fn default_num_mfcc() -> usize {
+    DEFAULT_NUM_MFCC
+}
+
+fn default_mfcc_top_db() -> Option<f32> {
+    DEFAULT_MFCC_TOP_DB
+}
+
+/// Settings for a mel filterbank and the mel spectrograms made with it.
+///
+/// The default values match the defaults of librosa: 128 Slaney-scale
+/// mel bands from 0 hz to the Nyquist frequency, each normalized by its
+/// width, applied to a power spectrogram.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MelArgs {
+    /// The number of mel bands.
+    #[serde(default = "default_num_mels")]
+    pub num_mels: usize,
+    /// The lowest frequency, in hz, of the filterbank.
+    #[serde(default)]
+    pub min_frequency_hz: f32,
+    /// The highest frequency, in hz, of the filterbank.
+    /// Set this to 0 to use the Nyquist frequency.
+    #[serde(default)]
+    pub max_frequency_hz: f32,
+    /// The mel scale to space the bands with. Use one of the
+    /// `MEL_SCALE_*` values in [`constants`][crate::constants].
+    #[serde(default)]
+    pub mel_scale: u32,
+    /// If `true`, each triangular filter is divided by its width in hz,
+    /// so that every band has about the same energy for white noise.
+    /// This is the normalization that Slaney's Auditory Toolbox uses.
+    #[serde(default = "default_mel_normalize")]
+    pub normalize: bool,
+    /// The exponent that the STFT magnitudes are raised to before
+    /// applying the filterbank. Use 2 for power and 1 for magnitude.
+    #[serde(default = "default_mel_power")]
+    pub power: f32,
+}
+
+impl Default for MelArgs {
+    fn default() -> Self {
+        MelArgs {
+            num_mels: DEFAULT_NUM_MELS,
+            min_frequency_hz: 0.0,
+            max_frequency_hz: 0.0,
+            mel_scale: DEFAULT_MEL_SCALE,
+            normalize: DEFAULT_MEL_NORMALIZE,
+            power: DEFAULT_MEL_POWER,
+        }
+    }
+}
+
+impl MelArgs {
+    /// Set the [`num_mels`](#structfield.num_mels) field.
+    #[must_use]
+    pub fn set_num_mels(&mut self, num_mels: usize) -> Self {
+        self.num_mels = num_mels;
+        *self
+    }
+
+    /// Set the [`min_frequency_hz`](#structfield.min_frequency_hz) field.
+    #[must_use]
+    pub fn set_min_frequency_hz(&mut self, min_frequency_hz: f32) -> Self {
+        self.min_frequency_hz = min_frequency_hz;
+        *self
+    }
+
+    /// Set the [`max_frequency_hz`](#structfield.max_frequency_hz) field.
+    #[must_use]
+    pub fn set_max_frequency_hz(&mut self, max_frequency_hz: f32) -> Self {
+        self.max_frequency_hz = max_frequency_hz;
+        *self
+    }
+
+    /// Set the [`mel_scale`](#structfield.mel_scale) field.
+    #[must_use]
+    pub fn set_mel_scale(&mut self, mel_scale: u32) -> Self {
+        self.mel_scale = mel_scale;
+        *self
+    }
+
+    /// Set the [`normalize`](#structfield.normalize) field.
+    #[must_use]
+    pub fn set_normalize(&mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        *self
+    }
+
+    /// Set the [`power`](#structfield.power) field.
+    #[must_use]
+    pub fn set_power(&mut self, power: f32) -> Self {
+        self.power = power;
+        *self
+    }
+}
+
+/// Settings for computing mel-frequency cepstral coefficients.
+///
+/// The default values match the defaults of librosa: 20 coefficients
+/// from an orthonormal type-II DCT of the log-power mel spectrogram,
+/// clipped to 80 decibels below its loudest value, with no liftering.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MfccArgs {
+    /// The number of coefficients to keep in each time step.
+    #[serde(default = "default_num_mfcc")]
+    pub num_mfcc: usize,
+    /// If above 0, coefficient `k` is multiplied by
+    /// `1 + (lifter / 2) * sin(pi * (k + 1) / lifter)`,
+    /// which raises the higher coefficients.
+    #[serde(default)]
+    pub lifter: f32,
+    /// How far below the loudest value, in decibels, the log-power
+    /// mel spectrogram is clipped. Set this to `None` to not clip.
+    #[serde(default = "default_mfcc_top_db")]
+    pub top_db: Option<f32>,
+}
+
+impl Default for MfccArgs {
+    fn default() -> Self {
+        MfccArgs {
+            num_mfcc: DEFAULT_NUM_MFCC,
+            lifter: DEFAULT_MFCC_LIFTER,
+            top_db: DEFAULT_MFCC_TOP_DB,
+        }
+    }
+}
+
+impl MfccArgs {
+    /// Set the [`num_mfcc`](#structfield.num_mfcc) field.
+    #[must_use]
+    pub fn set_num_mfcc(&mut self, num_mfcc: usize) -> Self {
+        self.num_mfcc = num_mfcc;
+        *self
+    }
+
+    /// Set the [`lifter`](#structfield.lifter) field.
+    #[must_use]
+    pub fn set_lifter(&mut self, lifter: f32) -> Self {
+        self.lifter = lifter;
+        *self
+    }
+
+    /// Set the [`top_db`](#structfield.top_db) field.
+    #[must_use]
+    pub fn set_top_db(&mut self, top_db: Option<f32>) -> Self {
+        self.top_db = top_db;
+        *self
+    }
+}
+
+/// Converts a frequency in hz to mels.
+///
+/// Pass [`MEL_SCALE_HTK`][crate::constants::MEL_SCALE_HTK] for the HTK
+/// formula `2595 * log10(1 + hz / 700)`. Any other value uses the Slaney
+/// scale, which is linear below 1000 hz and logarithmic above it.
+///
+/// # Examples
+/// ```
+/// use babycat::constants::{MEL_SCALE_HTK, MEL_SCALE_SLANEY};
+/// use babycat::spectral::mel::hz_to_mel;
+///
+/// assert!((hz_to_mel(60.0, MEL_SCALE_SLANEY) - 0.9).abs() < 1e-5);
+/// assert!((hz_to_mel(1000.0, MEL_SCALE_SLANEY) - 15.0).abs() < 1e-5);
+/// assert!((hz_to_mel(1000.0, MEL_SCALE_HTK) - 999.9855).abs() < 1e-3);
+/// ```
+#[allow(clippy::cast_possible_truncation)]
+pub fn hz_to_mel(hz: f32, mel_scale: u32) -> f32 {
+    hz_to_mel_f64(f64::from(hz), mel_scale) as f32
+}
+
+/// Converts a frequency in mels to hz. This is the inverse of [`hz_to_mel()`].
+#[allow(clippy::cast_possible_truncation)]
+pub fn mel_to_hz(mel: f32, mel_scale: u32) -> f32 {
+    mel_to_hz_f64(f64::from(mel), mel_scale) as f32
+}
+
+#[inline]
+fn slaney_log_step() -> f64 {
+    6.4_f64.ln() / 27.0
+}
+
+fn hz_to_mel_f64(hz: f64, mel_scale: u32) -> f64 {
+    if mel_scale == MEL_SCALE_HTK {
+        return 2595.0 * (1.0 + hz / 700.0).log10();
+    }
+    if hz < SLANEY_MIN_LOG_HZ {
+        hz / SLANEY_HZ_PER_MEL
+    } else {
+        SLANEY_MIN_LOG_HZ / SLANEY_HZ_PER_MEL + (hz / SLANEY_MIN_LOG_HZ).ln() / slaney_log_step()
+    }
+}
+
+fn mel_to_hz_f64(mel: f64, mel_scale: u32) -> f64 {
+    if mel_scale == MEL_SCALE_HTK {
+        return 700.0 * (10.0_f64.powf(mel / 2595.0) - 1.0);
+    }
+    let min_log_mel = SLANEY_MIN_LOG_HZ / SLANEY_HZ_PER_MEL;
+    if mel < min_log_mel {
+        mel * SLANEY_HZ_PER_MEL
+    } else {
+        SLANEY_MIN_LOG_HZ * (slaney_log_step() * (mel - min_log_mel)).exp()
+    }
+}
+
+/// Builds a filterbank of triangular filters, evenly spaced on the mel scale.
+///
+/// The filterbank has the shape `(mels, bins)`, where there are
+/// `fft_size / 2 + 1` bins. Multiplying it by a spectrogram with the
+/// shape `(bins, time steps)` gives a mel spectrogram.
+///
+/// # Errors
+/// Returns [`Error::WrongFftSize`] if `fft_size` is zero,
+/// [`Error::WrongNumFeatures`] if `num_mels` is zero,
+/// and [`Error::WrongFilterFrequency`] if the frequency range is empty
+/// or does not fit between 0 hz and the Nyquist frequency.
+///
+/// # Examples
+/// ```
+/// use babycat::spectral::mel::{mel_filterbank, MelArgs};
+///
+/// let filterbank = mel_filterbank(22050, 2048, MelArgs::default()).unwrap();
+/// assert_eq!(filterbank.len(), 128 * 1025);
+/// // The first band peaks at about 26 hz, so it only covers the first few bins.
+/// assert!((filterbank[1] - 0.016).abs() < 5e-4);
+/// assert_eq!(filterbank[10], 0.0);
+/// ```
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::float_cmp
+)]
+pub fn mel_filterbank(
+    frame_rate_hz: u32,
+    fft_size: usize,
+    args: MelArgs,
+) -> Result<Vec<f32>, Error> {
+    if fft_size == 0 {
+        return Err(Error::WrongFftSize(fft_size, 0));
+    }
+    let num_bins = fft_size / 2 + 1;
+    if args.num_mels == 0 {
+        return Err(Error::WrongNumFeatures(args.num_mels, num_bins));
+    }
+    if args.mel_scale != DEFAULT_MEL_SCALE
+        && args.mel_scale != MEL_SCALE_SLANEY
+        && args.mel_scale != MEL_SCALE_HTK
+    {
+        return Err(Error::FeatureNotCompiled("unknown-mel-scale"));
+    }
+    let nyquist_hz = frame_rate_hz as f32 / 2.0;
+    let max_frequency_hz = if args.max_frequency_hz == 0.0 {
+        nyquist_hz
+    } else {
+        args.max_frequency_hz
+    };
+    if !(0.0..=nyquist_hz).contains(&max_frequency_hz) {
+        return Err(Error::WrongFilterFrequency(max_frequency_hz, frame_rate_hz));
+    }
+    if !(0.0..max_frequency_hz).contains(&args.min_frequency_hz) {
+        return Err(Error::WrongFilterFrequency(
+            args.min_frequency_hz,
+            frame_rate_hz,
+        ));
+    }
+
+    // Every band is a triangle that rises from the center of the band
+    // below it and falls to the center of the band above it.
+    let min_mel = hz_to_mel_f64(f64::from(args.min_frequency_hz), args.mel_scale);
+    let max_mel = hz_to_mel_f64(f64::from(max_frequency_hz), args.mel_scale);
+    let num_edges = args.num_mels + 2;
+    let edges_hz: Vec<f64> = (0..num_edges)
+        .map(|i| {
+            let mel = min_mel + (max_mel - min_mel) * i as f64 / (num_edges - 1) as f64;
+            mel_to_hz_f64(mel, args.mel_scale)
+        })
+        .collect();
+    let bin_width_hz = f64::from(frame_rate_hz) / fft_size as f64;
+
+    let mut filterbank = vec![0.0_f32; args.num_mels * num_bins];
+    for (mel_idx, row) in filterbank.chunks_exact_mut(num_bins).enumerate() {
+        let (lower, center, upper) = (
+            edges_hz[mel_idx],
+            edges_hz[mel_idx + 1],
+            edges_hz[mel_idx + 2],
+        );
+        let scale = if args.normalize {
+            2.0 / (upper - lower)
+        } else {
+            1.0
+        };
+        for (bin_idx, weight) in row.iter_mut().enumerate() {
+            let hz = bin_idx as f64 * bin_width_hz;
+            let rising = (hz - lower) / (center - lower);
+            let falling = (upper - hz) / (upper - center);
+            *weight = (rising.min(falling).max(0.0) * scale) as f32;
+        }
+    }
+    Ok(filterbank)
+}
+
+/// Computes a mel spectrogram from a short-time Fourier transform.
+///
+/// The STFT magnitudes are raised to [`MelArgs.power`](MelArgs#structfield.power)
+/// and multiplied by a [`mel_filterbank()`], giving [`Features`] with
+/// one feature per mel band.
+///
+/// # Errors
+/// Returns the same errors as [`mel_filterbank()`].
+pub fn mel_spectrogram(stft: &Stft, args: MelArgs) -> Result<Features, Error> {
+    let filterbank = mel_filterbank(stft.frame_rate_hz(), stft.args().fft_size, args)?;
+    let num_bins = stft.num_bins();
+    let num_time_steps = stft.num_time_steps();
+    let spectrogram = stft.spectrogram(args.power);
+    let mut data = vec![0.0_f32; stft.num_channels() as usize * args.num_mels * num_time_steps];
+    if num_time_steps > 0 {
+        for (channel_in, channel_out) in spectrogram
+            .chunks_exact(num_bins * num_time_steps)
+            .zip(data.chunks_exact_mut(args.num_mels * num_time_steps))
+        {
+            for (filter, row_out) in filterbank
+                .chunks_exact(num_bins)
+                .zip(channel_out.chunks_exact_mut(num_time_steps))
+            {
+                for (weight, row_in) in filter.iter().zip(channel_in.chunks_exact(num_time_steps)) {
+                    if *weight == 0.0 {
+                        continue;
+                    }
+                    for (out, value) in row_out.iter_mut().zip(row_in) {
+                        *out += weight * value;
+                    }
+                }
+            }
+        }
+    }
+    Ok(Features::from_parts(
+        stft.frame_rate_hz(),
+        stft.args().hop_length,
+        stft.num_channels(),
+        args.num_mels,
+        num_time_steps,
+        data,
+    ))
+}
+
+/// Computes mel-frequency cepstral coefficients from a mel spectrogram.
+///
+/// The power mel spectrogram is converted to decibels with
+/// [`Features::to_db()`], and every time step is then transformed with
+/// an orthonormal type-II discrete cosine transform, keeping the first
+/// [`MfccArgs.num_mfcc`](MfccArgs#structfield.num_mfcc) coefficients.
+///
+/// # Errors
+/// Returns [`Error::WrongNumFeatures`] if `num_mfcc` is zero or
+/// larger than the number of mel bands.
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+pub fn mfcc(mel_spectrogram: &Features, args: MfccArgs) -> Result<Features, Error> {
+    let num_mels = mel_spectrogram.num_features();
+    if args.num_mfcc == 0 || args.num_mfcc > num_mels {
+        return Err(Error::WrongNumFeatures(args.num_mfcc, num_mels));
+    }
+    let log_mel = mel_spectrogram.to_db(args.top_db);
+    let num_time_steps = log_mel.num_time_steps();
+
+    // The DCT matrix has the shape `(coefficients, mels)`, with the
+    // liftering folded into it.
+    let n = num_mels as f64;
+    let dct: Vec<f64> = (0..args.num_mfcc)
+        .flat_map(|k| {
+            let norm = if k == 0 {
+                (1.0 / n).sqrt()
+            } else {
+                (2.0 / n).sqrt()
+            };
+            let lifter = f64::from(args.lifter);
+            let lift = if lifter > 0.0 {
+                1.0 + (lifter / 2.0) * (std::f64::consts::PI * (k + 1) as f64 / lifter).sin()
+            } else {
+                1.0
+            };
+            (0..num_mels).map(move |m| {
+                norm * lift
+                    * (std::f64::consts::PI * k as f64 * (2 * m + 1) as f64 / (2.0 * n)).cos()
+            })
+        })
+        .collect();
+
+    let mut data =
+        vec![0.0_f32; mel_spectrogram.num_channels() as usize * args.num_mfcc * num_time_steps];
+    if num_time_steps > 0 {
+        for (channel_in, channel_out) in log_mel
+            .as_slice()
+            .chunks_exact(num_mels * num_time_steps)
+            .zip(data.chunks_exact_mut(args.num_mfcc * num_time_steps))
+        {
+            for (basis, row_out) in dct
+                .chunks_exact(num_mels)
+                .zip(channel_out.chunks_exact_mut(num_time_steps))
+            {
+                for (time_step_idx, out) in row_out.iter_mut().enumerate() {
+                    let value: f64 = basis
+                        .iter()
+                        .zip(
+                            channel_in
+                                .iter()
+                                .skip(time_step_idx)
+                                .step_by(num_time_steps),
+                        )
+                        .map(|(b, x)| b * f64::from(*x))
+                        .sum();
+                    *out = value as f32;
+                }
+            }
+        }
+    }
+    Ok(Features::from_parts(
+        mel_spectrogram.frame_rate_hz(),
+        mel_spectrogram.hop_length(),
+        mel_spectrogram.num_channels(),
+        args.num_mfcc,
+        num_time_steps,
+        data,
+    ))
+}
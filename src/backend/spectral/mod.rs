@@ -0,0 +1,26 @@
+//! Spectral analysis.
+//!
+//! Use [`Stft`] to compute a short-time Fourier transform of a
+//! [`Waveform`][crate::Waveform], get magnitude or power spectrograms
+//! from it, and turn it back into a waveform with overlap-add.
+//!
+//! Use [`mel_spectrogram()`] and [`mfcc()`] to turn a transform into
+//! [`Features`] for speech and music models. For music, [`cqt()`] and
+//! [`chroma()`] give features on a logarithmic frequency scale.
+//!
+//! Spectral data is stored channel by channel. Within each channel,
+//! every frequency bin holds one value per time step, so the data has
+//! the shape `(channels, bins, time steps)`. [`Features`] use the
+//! shape `(channels, features, time steps)`.
+pub mod cqt;
+pub mod features;
+pub mod mel;
+pub mod stft;
+pub mod window;
+
+pub use cqt::{chroma, chroma_from_cqt, cqt, CqtArgs};
+pub use features::{Features, FeaturesNamedResult};
+pub use mel::{mel_filterbank, mel_spectrogram, mfcc, MelArgs, MfccArgs};
+pub use rustfft::num_complex::Complex;
+pub use stft::{Stft, StftArgs};
+pub use window::window;
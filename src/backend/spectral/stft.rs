@@ -0,0 +1,532 @@
+//! The short-time Fourier transform and its inverse.
+
+use serde::{Deserialize, Serialize};
+
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+
+use crate::backend::constants::{
+    DEFAULT_CENTER, DEFAULT_FFT_SIZE, DEFAULT_HOP_LENGTH, DEFAULT_PAD_MODE, DEFAULT_WINDOW_LENGTH,
+    DEFAULT_WINDOW_TYPE, PAD_MODE_REFLECT, PAD_MODE_ZEROS,
+};
+use crate::backend::spectral::window::window;
+use crate::backend::Error;
+use crate::backend::Signal;
+use crate::backend::Waveform;
+
+/// Overlap-added window weights smaller than this are treated as zero
+/// when inverting, so that frames no window covers come out silent.
+const MIN_WINDOW_SUM: f32 = 1e-8;
+
+fn default_fft_size() -> usize {
+    DEFAULT_FFT_SIZE
+}
+
+fn default_hop_length() -> usize {
+    DEFAULT_HOP_LENGTH
+}
+
+fn default_center() -> bool {
+    DEFAULT_CENTER
+}
+
+/// Settings for a short-time Fourier transform.
+///
+/// The default values match the defaults of most audio libraries:
+/// a 2048-point FFT with a Hann window, advancing 512 frames at a time,
+/// with every window centered on its time step.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StftArgs {
+    /// The number of frames in each FFT.
+    /// Each time step has `fft_size / 2 + 1` frequency bins.
+    #[serde(default = "default_fft_size")]
+    pub fft_size: usize,
+    /// The number of frames between the starts of consecutive windows.
+    #[serde(default = "default_hop_length")]
+    pub hop_length: usize,
+    /// The length of the window, which is zero-padded on both sides
+    /// to [`fft_size`](#structfield.fft_size). Set this to 0 to make
+    /// the window as long as the FFT.
+    #[serde(default)]
+    pub window_length: usize,
+    /// The shape of the window. Use one of the `WINDOW_TYPE_*`
+    /// values in [`constants`][crate::constants].
+    #[serde(default)]
+    pub window_type: u32,
+    /// If `true`, the audio is padded with `fft_size / 2` frames on
+    /// both sides, so that the `t`-th window is centered on frame `t * hop_length`.
+    /// If `false`, the `t`-th window starts at frame `t * hop_length`,
+    /// and frames after the last full window are left out.
+    #[serde(default = "default_center")]
+    pub center: bool,
+    /// How the audio is padded when [`center`](#structfield.center) is `true`.
+    /// Use one of the `PAD_MODE_*` values in [`constants`][crate::constants].
+    #[serde(default)]
+    pub pad_mode: u32,
+}
+
+impl Default for StftArgs {
+    fn default() -> Self {
+        StftArgs {
+            fft_size: DEFAULT_FFT_SIZE,
+            hop_length: DEFAULT_HOP_LENGTH,
+            window_length: DEFAULT_WINDOW_LENGTH,
+            window_type: DEFAULT_WINDOW_TYPE,
+            center: DEFAULT_CENTER,
+            pad_mode: DEFAULT_PAD_MODE,
+        }
+    }
+}
+
+impl StftArgs {
+    /// Set the [`fft_size`](#structfield.fft_size) field.
+    #[must_use]
+    pub fn set_fft_size(&mut self, fft_size: usize) -> Self {
+        self.fft_size = fft_size;
+        *self
+    }
+
+    /// Set the [`hop_length`](#structfield.hop_length) field.
+    #[must_use]
+    pub fn set_hop_length(&mut self, hop_length: usize) -> Self {
+        self.hop_length = hop_length;
+        *self
+    }
+
+    /// Set the [`window_length`](#structfield.window_length) field.
+    #[must_use]
+    pub fn set_window_length(&mut self, window_length: usize) -> Self {
+        self.window_length = window_length;
+        *self
+    }
+
+    /// Set the [`window_type`](#structfield.window_type) field.
+    #[must_use]
+    pub fn set_window_type(&mut self, window_type: u32) -> Self {
+        self.window_type = window_type;
+        *self
+    }
+
+    /// Set the [`center`](#structfield.center) field.
+    #[must_use]
+    pub fn set_center(&mut self, center: bool) -> Self {
+        self.center = center;
+        *self
+    }
+
+    /// Set the [`pad_mode`](#structfield.pad_mode) field.
+    #[must_use]
+    pub fn set_pad_mode(&mut self, pad_mode: u32) -> Self {
+        self.pad_mode = pad_mode;
+        *self
+    }
+
+    /// Returns the number of frequency bins in each time step.
+    #[inline]
+    pub fn num_bins(&self) -> usize {
+        self.fft_size / 2 + 1
+    }
+
+    /// Returns how many time steps a signal of `num_frames` frames has.
+    pub fn num_time_steps(&self, num_frames: usize) -> usize {
+        let padded_len = num_frames + 2 * self.padding();
+        if self.hop_length == 0 || padded_len < self.fft_size {
+            return 0;
+        }
+        1 + (padded_len - self.fft_size) / self.hop_length
+    }
+
+    /// Returns the shortest signal, in frames, that has `num_time_steps` time steps.
+    pub fn min_num_frames(&self, num_time_steps: usize) -> usize {
+        if num_time_steps == 0 {
+            return 0;
+        }
+        ((num_time_steps - 1) * self.hop_length + self.fft_size).saturating_sub(2 * self.padding())
+    }
+
+    /// The number of frames of padding on each side of the signal.
+    #[inline]
+    fn padding(&self) -> usize {
+        if self.center {
+            self.fft_size / 2
+        } else {
+            0
+        }
+    }
+
+    /// Checks the settings and returns the window, zero-padded to the FFT size.
+    fn validate(&self) -> Result<Vec<f32>, Error> {
+        let window_length = if self.window_length == 0 {
+            self.fft_size
+        } else {
+            self.window_length
+        };
+        if self.fft_size == 0 || window_length > self.fft_size {
+            return Err(Error::WrongFftSize(self.fft_size, self.window_length));
+        }
+        if self.hop_length == 0 {
+            return Err(Error::WrongHopLength(self.hop_length));
+        }
+        if self.pad_mode != DEFAULT_PAD_MODE
+            && self.pad_mode != PAD_MODE_ZEROS
+            && self.pad_mode != PAD_MODE_REFLECT
+        {
+            return Err(Error::FeatureNotCompiled("unknown-pad-mode"));
+        }
+        let mut padded = vec![0.0_f32; self.fft_size];
+        let offset = (self.fft_size - window_length) / 2;
+        padded[offset..offset + window_length]
+            .copy_from_slice(&window(self.window_type, window_length)?);
+        Ok(padded)
+    }
+}
+
+/// Returns the sample at `idx` in a channel that has been padded with `padding` frames.
+#[inline]
+#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+fn padded_sample(channel: &[f32], idx: usize, padding: usize, pad_mode: u32) -> f32 {
+    let len = channel.len();
+    let mut j = idx as isize - padding as isize;
+    if (0..len as isize).contains(&j) {
+        return channel[j as usize];
+    }
+    if pad_mode == PAD_MODE_ZEROS || len == 0 {
+        return 0.0;
+    }
+    if len == 1 {
+        return channel[0];
+    }
+    // Bounce back and forth off the edges, without repeating the edge frames.
+    let last = len as isize - 1;
+    let period = 2 * last;
+    j = j.rem_euclid(period);
+    if j > last {
+        j = period - j;
+    }
+    channel[j as usize]
+}
+
+/// A short-time Fourier transform of every channel of a [`Waveform`].
+///
+/// The values are stored as `(channels, bins, time steps)`, so the value
+/// for channel `c`, bin `b` and time step `t` is at index
+/// `(c * num_bins + b) * num_time_steps + t`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stft {
+    args: StftArgs,
+    frame_rate_hz: u32,
+    num_channels: u16,
+    num_frames: usize,
+    num_time_steps: usize,
+    data: Vec<Complex<f32>>,
+}
+
+impl Stft {
+    /// Creates a short-time Fourier transform from values that were
+    /// computed elsewhere, or changed after computing them.
+    ///
+    /// `num_frames` is the length of the signal that the transform
+    /// describes, and is the length that [`to_waveform()`](Self::to_waveform)
+    /// returns. `data` must have the shape `(channels, bins, time steps)`.
+    pub fn new(
+        args: StftArgs,
+        frame_rate_hz: u32,
+        num_channels: u16,
+        num_frames: usize,
+        data: Vec<Complex<f32>>,
+    ) -> Result<Self, Error> {
+        args.validate()?;
+        let num_time_steps = args.num_time_steps(num_frames);
+        let expected_len = num_channels as usize * args.num_bins() * num_time_steps;
+        if data.len() != expected_len {
+            return Err(Error::WrongStftShape(expected_len, data.len()));
+        }
+        Ok(Self {
+            args,
+            frame_rate_hz,
+            num_channels,
+            num_frames,
+            num_time_steps,
+            data,
+        })
+    }
+
+    /// Computes the short-time Fourier transform of every channel of a waveform.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::spectral::{Stft, StftArgs};
+    /// use babycat::Waveform;
+    ///
+    /// // One second of a 1000 hz sine.
+    /// let samples: Vec<f32> = (0..8000)
+    ///     .map(|i| (2.0 * std::f32::consts::PI * 1000.0 * i as f32 / 8000.0).sin())
+    ///     .collect();
+    /// let waveform = Waveform::from_interleaved_samples(8000, 1, &samples);
+    ///
+    /// let args = StftArgs::default().set_fft_size(256).set_hop_length(64);
+    /// let stft = Stft::from_waveform(&waveform, args).unwrap();
+    /// assert_eq!(stft.num_bins(), 129);
+    /// assert_eq!(stft.num_time_steps(), 126);
+    ///
+    /// // Each bin is 8000 / 256 = 31.25 hz wide, so 1000 hz is bin 32.
+    /// let magnitudes = stft.magnitudes();
+    /// let loudest_bin = (0..stft.num_bins())
+    ///     .max_by(|&a, &b| {
+    ///         let t = 60;
+    ///         magnitudes[a * stft.num_time_steps() + t]
+    ///             .total_cmp(&magnitudes[b * stft.num_time_steps() + t])
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(loudest_bin, 32);
+    ///
+    /// // The inverse gives back the original signal.
+    /// let inverse = stft.to_waveform().unwrap();
+    /// for (a, b) in inverse.to_interleaved_samples().iter().zip(&samples) {
+    ///     assert!((a - b).abs() < 1e-4);
+    /// }
+    /// ```
+    pub fn from_waveform(waveform: &Waveform, args: StftArgs) -> Result<Self, Error> {
+        let window = args.validate()?;
+        let fft_size = args.fft_size;
+        let num_bins = args.num_bins();
+        let num_channels = waveform.num_channels() as usize;
+        let num_frames = waveform.num_frames();
+        let num_time_steps = args.num_time_steps(num_frames);
+        let padding = args.padding();
+
+        let fft = FftPlanner::new().plan_fft_forward(fft_size);
+        let mut buffer = vec![Complex::new(0.0_f32, 0.0_f32); fft_size];
+        let mut data =
+            vec![Complex::new(0.0_f32, 0.0_f32); num_channels * num_bins * num_time_steps];
+        let mut channel: Vec<f32> = Vec::with_capacity(num_frames);
+
+        for channel_idx in 0..num_channels {
+            channel.clear();
+            channel.extend(
+                waveform
+                    .to_interleaved_samples()
+                    .iter()
+                    .skip(channel_idx)
+                    .step_by(num_channels),
+            );
+            let out =
+                &mut data[channel_idx * num_bins * num_time_steps..][..num_bins * num_time_steps];
+            for time_step_idx in 0..num_time_steps {
+                let start = time_step_idx * args.hop_length;
+                for (k, (value, w)) in buffer.iter_mut().zip(&window).enumerate() {
+                    *value = Complex::new(
+                        padded_sample(&channel, start + k, padding, args.pad_mode) * w,
+                        0.0,
+                    );
+                }
+                fft.process(&mut buffer);
+                for (bin_idx, value) in buffer[..num_bins].iter().enumerate() {
+                    out[bin_idx * num_time_steps + time_step_idx] = *value;
+                }
+            }
+        }
+
+        Ok(Self {
+            args,
+            frame_rate_hz: waveform.frame_rate_hz(),
+            num_channels: waveform.num_channels(),
+            num_frames,
+            num_time_steps,
+            data,
+        })
+    }
+
+    /// Turns the transform back into a waveform with a windowed overlap-add.
+    ///
+    /// Each time step is inverted, multiplied by the window again, and added
+    /// into place. The sum is then divided by the overlapping squared windows,
+    /// so an unchanged transform gives back the original signal wherever the
+    /// windows cover it.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn to_waveform(&self) -> Result<Waveform, Error> {
+        let window = self.args.validate()?;
+        let fft_size = self.args.fft_size;
+        let num_bins = self.num_bins();
+        let num_channels = self.num_channels as usize;
+        let num_time_steps = self.num_time_steps;
+        let hop_length = self.args.hop_length;
+        let padding = self.args.padding();
+        let padded_len = if num_time_steps == 0 {
+            0
+        } else {
+            (num_time_steps - 1) * hop_length + fft_size
+        };
+
+        // The overlap-added squared window is the same for every channel.
+        let mut window_sums = vec![0.0_f32; padded_len];
+        for time_step_idx in 0..num_time_steps {
+            let start = time_step_idx * hop_length;
+            for (sum, w) in window_sums[start..start + fft_size].iter_mut().zip(&window) {
+                *sum += w * w;
+            }
+        }
+
+        let ifft = FftPlanner::new().plan_fft_inverse(fft_size);
+        let mut buffer = vec![Complex::new(0.0_f32, 0.0_f32); fft_size];
+        let mut overlapped = vec![0.0_f32; padded_len];
+        let mut interleaved_samples = vec![0.0_f32; self.num_frames * num_channels];
+
+        for channel_idx in 0..num_channels {
+            overlapped.fill(0.0);
+            let channel = self.channel(channel_idx);
+            for time_step_idx in 0..num_time_steps {
+                // Rebuild the full spectrum from the non-negative frequencies.
+                for (k, slot) in buffer.iter_mut().enumerate() {
+                    let bin_idx = if k < num_bins { k } else { fft_size - k };
+                    let value = channel[bin_idx * num_time_steps + time_step_idx];
+                    *slot = if k < num_bins { value } else { value.conj() };
+                }
+                ifft.process(&mut buffer);
+                let start = time_step_idx * hop_length;
+                for ((out, value), w) in overlapped[start..start + fft_size]
+                    .iter_mut()
+                    .zip(&buffer)
+                    .zip(&window)
+                {
+                    *out += value.re / fft_size as f32 * w;
+                }
+            }
+            for frame_idx in 0..self.num_frames {
+                let idx = frame_idx + padding;
+                if idx < padded_len && window_sums[idx] > MIN_WINDOW_SUM {
+                    interleaved_samples[frame_idx * num_channels + channel_idx] =
+                        overlapped[idx] / window_sums[idx];
+                }
+            }
+        }
+
+        Ok(Waveform::new(
+            self.frame_rate_hz,
+            self.num_channels,
+            interleaved_samples,
+        ))
+    }
+
+    /// Returns the settings that the transform was computed with.
+    #[inline]
+    pub fn args(&self) -> StftArgs {
+        self.args
+    }
+
+    /// Returns the frame rate of the signal.
+    #[inline]
+    pub fn frame_rate_hz(&self) -> u32 {
+        self.frame_rate_hz
+    }
+
+    /// Returns the number of channels.
+    #[inline]
+    pub fn num_channels(&self) -> u16 {
+        self.num_channels
+    }
+
+    /// Returns the number of frames in the signal.
+    #[inline]
+    pub fn num_frames(&self) -> usize {
+        self.num_frames
+    }
+
+    /// Returns the number of frequency bins in each time step.
+    #[inline]
+    pub fn num_bins(&self) -> usize {
+        self.args.num_bins()
+    }
+
+    /// Returns the number of time steps.
+    #[inline]
+    pub fn num_time_steps(&self) -> usize {
+        self.num_time_steps
+    }
+
+    /// Returns the center frequency of the given bin, in hz.
+    #[inline]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn bin_frequency_hz(&self, bin_idx: usize) -> f32 {
+        bin_idx as f32 * self.frame_rate_hz as f32 / self.args.fft_size as f32
+    }
+
+    /// Returns the value for one channel, bin, and time step,
+    /// or `None` if any of them are out of bounds.
+    #[inline]
+    pub fn get(
+        &self,
+        channel_idx: u16,
+        bin_idx: usize,
+        time_step_idx: usize,
+    ) -> Option<Complex<f32>> {
+        if channel_idx >= self.num_channels
+            || bin_idx >= self.num_bins()
+            || time_step_idx >= self.num_time_steps
+        {
+            return None;
+        }
+        Some(
+            self.data[(channel_idx as usize * self.num_bins() + bin_idx) * self.num_time_steps
+                + time_step_idx],
+        )
+    }
+
+    /// Returns the values of one channel, with the shape `(bins, time steps)`.
+    #[inline]
+    pub fn channel(&self, channel_idx: usize) -> &[Complex<f32>] {
+        let len = self.num_bins() * self.num_time_steps;
+        &self.data[channel_idx * len..(channel_idx + 1) * len]
+    }
+
+    /// Returns every value, with the shape `(channels, bins, time steps)`.
+    #[inline]
+    pub fn as_slice(&self) -> &[Complex<f32>] {
+        &self.data
+    }
+
+    /// Returns every value mutably, so that the spectrum can be
+    /// changed in place before calling [`to_waveform()`](Self::to_waveform).
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [Complex<f32>] {
+        &mut self.data
+    }
+
+    /// Returns the magnitude of every value raised to `power`, with the
+    /// shape `(channels, bins, time steps)`.
+    ///
+    /// A `power` of 1 gives the magnitude spectrogram,
+    /// and a `power` of 2 gives the power spectrogram.
+    pub fn spectrogram(&self, power: f32) -> Vec<f32> {
+        #[allow(clippy::float_cmp)]
+        if power == 2.0 {
+            return self.data.iter().map(Complex::norm_sqr).collect();
+        }
+        #[allow(clippy::float_cmp)]
+        if power == 1.0 {
+            return self.data.iter().map(|value| value.norm()).collect();
+        }
+        self.data
+            .iter()
+            .map(|value| value.norm().powf(power))
+            .collect()
+    }
+
+    /// Returns the magnitude spectrogram, with the shape `(channels, bins, time steps)`.
+    #[inline]
+    pub fn magnitudes(&self) -> Vec<f32> {
+        self.spectrogram(1.0)
+    }
+
+    /// Returns the power spectrogram, with the shape `(channels, bins, time steps)`.
+    #[inline]
+    pub fn powers(&self) -> Vec<f32> {
+        self.spectrogram(2.0)
+    }
+}
+
+impl From<Stft> for Vec<Complex<f32>> {
+    fn from(item: Stft) -> Self {
+        item.data
+    }
+}
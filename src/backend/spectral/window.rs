@@ -0,0 +1,45 @@
+//! Window functions for spectral analysis.
+
+use std::f32::consts::PI;
+
+use crate::backend::constants::{
+    DEFAULT_WINDOW_TYPE, WINDOW_TYPE_BLACKMAN, WINDOW_TYPE_HAMMING, WINDOW_TYPE_HANN,
+    WINDOW_TYPE_RECTANGULAR,
+};
+use crate::backend::Error;
+
+/// Returns a window of the given type and length.
+///
+/// Windows are periodic rather than symmetric, which is what
+/// spectral analysis with overlapping windows needs. A periodic
+/// window of length `n` is the first `n` values of a symmetric
+/// window of length `n + 1`.
+///
+/// # Examples
+/// ```
+/// use babycat::constants::WINDOW_TYPE_HANN;
+/// use babycat::spectral::window;
+///
+/// let hann = window(WINDOW_TYPE_HANN, 4).unwrap();
+/// assert_eq!(hann.len(), 4);
+/// assert!((hann[0] - 0.0).abs() < 1e-6);
+/// assert!((hann[1] - 0.5).abs() < 1e-6);
+/// assert!((hann[2] - 1.0).abs() < 1e-6);
+/// assert!((hann[3] - 0.5).abs() < 1e-6);
+/// ```
+#[allow(clippy::cast_precision_loss)]
+pub fn window(window_type: u32, length: usize) -> Result<Vec<f32>, Error> {
+    let phase = |i: usize| 2.0 * PI * i as f32 / length as f32;
+    let window = match window_type {
+        DEFAULT_WINDOW_TYPE | WINDOW_TYPE_HANN => {
+            (0..length).map(|i| 0.5 - 0.5 * phase(i).cos()).collect()
+        }
+        WINDOW_TYPE_HAMMING => (0..length).map(|i| 0.54 - 0.46 * phase(i).cos()).collect(),
+        WINDOW_TYPE_BLACKMAN => (0..length)
+            .map(|i| 0.42 - 0.5 * phase(i).cos() + 0.08 * (2.0 * phase(i)).cos())
+            .collect(),
+        WINDOW_TYPE_RECTANGULAR => vec![1.0; length],
+        _ => return Err(Error::FeatureNotCompiled("unknown-window-type")),
+    };
+    Ok(window)
+}
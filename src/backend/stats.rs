@@ -0,0 +1,313 @@
+//! Per-channel signal statistics and channel layout diagnostics.
+//!
+//! Use [`StatsReport`] to measure the peak, RMS level, DC offset, crest
+//! factor, clipping and silence of every channel, and to spot common
+//! channel layout problems: channels that are copies of each other
+//! ("fake stereo"), channels that are polarity-inverted copies of each
+//! other, silent channels, and stereo audio that only has sound in one
+//! channel. Everything is measured in a single streaming pass over any
+//! [`Source`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::loudness::{dbfs_to_ratio, ratio_to_dbfs, MIN_REPORTED_LEVEL};
+use crate::backend::Error;
+use crate::backend::Signal;
+use crate::backend::Source;
+use crate::backend::Waveform;
+
+/// Samples at least this loud count as clipped. This is the largest
+/// positive 16-bit sample, so full-scale 16-bit audio counts as clipped.
+pub const CLIP_THRESHOLD: f32 = 32767.0 / 32768.0;
+
+/// Two channels count as copies of each other when the energy of their
+/// difference is at least this many decibels below the quieter channel.
+pub const CHANNEL_MATCH_THRESHOLD_DB: f64 = -60.0;
+
+/// Statistics for a single channel.
+///
+/// Levels are floored at [`MIN_REPORTED_LEVEL`], so silence is
+/// reported as -144 rather than negative infinity.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChannelStats {
+    /// The largest absolute sample value, in dBFS.
+    pub peak_dbfs: f32,
+    /// The root mean square of the samples, in dBFS.
+    pub rms_dbfs: f32,
+    /// The mean of the samples. Anything far from 0 means that the
+    /// channel has a DC offset.
+    pub dc_offset: f32,
+    /// The ratio of the peak to the RMS level, in decibels, or `None`
+    /// if the channel is silent.
+    pub crest_factor_db: Option<f32>,
+    /// The number of samples at or above [`CLIP_THRESHOLD`] in either
+    /// direction.
+    pub num_clipped_samples: usize,
+    /// The percentage of samples at or below the silence threshold,
+    /// from 0 to 100.
+    pub silence_percent: f32,
+}
+
+/// Per-channel statistics and channel layout diagnostics for a stream of audio.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StatsReport {
+    /// The frame rate of the measured audio.
+    pub frame_rate_hz: u32,
+    /// The number of channels in the measured audio.
+    pub num_channels: u16,
+    /// The number of frames in the measured audio.
+    pub num_frames: usize,
+    /// The statistics of every channel, in order.
+    pub channels: Vec<ChannelStats>,
+    /// Every pair of channels that are copies of each other, such as
+    /// mono audio that was saved as stereo. Silent channels are never
+    /// counted as copies.
+    pub identical_channels: Vec<[u16; 2]>,
+    /// Every pair of channels where one is a polarity-inverted copy of
+    /// the other. Summing these channels to mono cancels them out.
+    pub inverted_channels: Vec<[u16; 2]>,
+    /// Every channel whose peak is at or below the silence threshold.
+    pub silent_channels: Vec<u16>,
+    /// Whether the audio is stereo with sound only in the left channel.
+    pub left_only: bool,
+    /// Whether the audio is stereo with sound only in the right channel.
+    pub right_only: bool,
+}
+
+impl StatsReport {
+    /// Measures a waveform that is already in memory.
+    ///
+    /// Samples at or below `silence_threshold_dbfs` count as silence.
+    ///
+    /// # Errors
+    /// Returns [`Error::WrongNumChannels`] if the waveform has no channels.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::stats::StatsReport;
+    /// use babycat::Waveform;
+    ///
+    /// // A tone in the left channel, and nothing in the right channel.
+    /// let samples: Vec<f32> = (0..44100)
+    ///     .map(|i| 0.5 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 44100.0).sin())
+    ///     .flat_map(|s| vec![s, 0.0])
+    ///     .collect();
+    /// let waveform = Waveform::from_interleaved_samples(44100, 2, &samples);
+    /// let report = StatsReport::from_waveform(&waveform, -60.0).unwrap();
+    ///
+    /// assert!((report.channels[0].peak_dbfs + 6.02).abs() < 0.01);
+    /// assert!((report.channels[0].crest_factor_db.unwrap() - 3.01).abs() < 0.01);
+    /// assert_eq!(report.silent_channels, vec![1]);
+    /// assert!(report.left_only);
+    /// ```
+    pub fn from_waveform(waveform: &Waveform, silence_threshold_dbfs: f32) -> Result<Self, Error> {
+        let mut meter = StatsMeter::new(
+            waveform.frame_rate_hz(),
+            waveform.num_channels(),
+            silence_threshold_dbfs,
+        )?;
+        meter.push_interleaved_samples(waveform.to_interleaved_samples());
+        Ok(meter.finish())
+    }
+
+    /// Measures any [`Source`] in a single streaming pass, without
+    /// holding all of its samples in memory.
+    ///
+    /// Samples at or below `silence_threshold_dbfs` count as silence.
+    ///
+    /// # Errors
+    /// Returns [`Error::WrongNumChannels`] if the source has no channels.
+    pub fn from_source<S: Source>(source: S, silence_threshold_dbfs: f32) -> Result<Self, Error> {
+        let mut meter = StatsMeter::new(
+            source.frame_rate_hz(),
+            source.num_channels(),
+            silence_threshold_dbfs,
+        )?;
+        for sample in source {
+            meter.push_sample(sample);
+        }
+        Ok(meter.finish())
+    }
+}
+
+/// Running sums for a single channel.
+#[derive(Clone, Debug, Default)]
+struct ChannelSums {
+    peak: f32,
+    sum: f64,
+    sum_of_squares: f64,
+    num_clipped_samples: usize,
+    num_silent_samples: usize,
+}
+
+/// Measures channel statistics incrementally, one interleaved sample at a time.
+///
+/// Call [`finish()`](Self::finish) once the audio has ended to get a
+/// [`StatsReport`]. Memory use does not grow with the length of the
+/// audio, but comparing every pair of channels makes the work per frame
+/// grow with the square of the number of channels.
+#[derive(Clone, Debug)]
+pub struct StatsMeter {
+    frame_rate_hz: u32,
+    num_channels: u16,
+    num_frames: usize,
+    silence_threshold: f32,
+    frame: Vec<f32>,
+    channels: Vec<ChannelSums>,
+    /// The sum of the products of every pair of channels, in the order
+    /// (0, 1), (0, 2), ..., (1, 2), ...
+    pair_products: Vec<f64>,
+}
+
+impl StatsMeter {
+    /// Starts measuring audio with `num_channels` channels.
+    ///
+    /// Samples at or below `silence_threshold_dbfs` count as silence.
+    ///
+    /// # Errors
+    /// Returns [`Error::WrongNumChannels`] if `num_channels` is zero.
+    pub fn new(
+        frame_rate_hz: u32,
+        num_channels: u16,
+        silence_threshold_dbfs: f32,
+    ) -> Result<Self, Error> {
+        if num_channels == 0 {
+            return Err(Error::WrongNumChannels(1, num_channels));
+        }
+        let num_channels_usize = num_channels as usize;
+        Ok(Self {
+            frame_rate_hz,
+            num_channels,
+            num_frames: 0,
+            silence_threshold: dbfs_to_ratio(silence_threshold_dbfs),
+            frame: Vec::with_capacity(num_channels_usize),
+            channels: vec![ChannelSums::default(); num_channels_usize],
+            pair_products: vec![0.0; num_channels_usize * num_channels_usize.saturating_sub(1) / 2],
+        })
+    }
+
+    /// Measures the next interleaved sample.
+    #[inline]
+    pub fn push_sample(&mut self, sample: f32) {
+        let channel = &mut self.channels[self.frame.len()];
+        let magnitude = sample.abs();
+        channel.peak = channel.peak.max(magnitude);
+        channel.sum += f64::from(sample);
+        channel.sum_of_squares += f64::from(sample) * f64::from(sample);
+        if magnitude >= CLIP_THRESHOLD {
+            channel.num_clipped_samples += 1;
+        }
+        if magnitude <= self.silence_threshold {
+            channel.num_silent_samples += 1;
+        }
+        self.frame.push(sample);
+        if self.frame.len() == self.num_channels as usize {
+            let mut pair_idx = 0;
+            for (first_idx, first) in self.frame.iter().enumerate() {
+                for second in &self.frame[first_idx + 1..] {
+                    self.pair_products[pair_idx] += f64::from(*first) * f64::from(*second);
+                    pair_idx += 1;
+                }
+            }
+            self.frame.clear();
+            self.num_frames += 1;
+        }
+    }
+
+    /// Measures a slice of interleaved samples.
+    pub fn push_interleaved_samples(&mut self, interleaved_samples: &[f32]) {
+        for sample in interleaved_samples {
+            self.push_sample(*sample);
+        }
+    }
+
+    /// Finishes measuring and returns the report.
+    ///
+    /// A partial frame at the end of the audio is left out of the
+    /// cross-channel checks.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    pub fn finish(self) -> StatsReport {
+        let floor = |level: f32| level.max(MIN_REPORTED_LEVEL);
+        let channels: Vec<ChannelStats> = self
+            .channels
+            .iter()
+            .enumerate()
+            .map(|(channel_idx, sums)| {
+                // A partial frame at the end still counts towards the
+                // channels that it has samples for.
+                let num_samples = self.num_frames + usize::from(channel_idx < self.frame.len());
+                if num_samples == 0 {
+                    return ChannelStats {
+                        peak_dbfs: MIN_REPORTED_LEVEL,
+                        rms_dbfs: MIN_REPORTED_LEVEL,
+                        dc_offset: 0.0,
+                        crest_factor_db: None,
+                        num_clipped_samples: 0,
+                        silence_percent: 0.0,
+                    };
+                }
+                let rms = (sums.sum_of_squares / num_samples as f64).sqrt() as f32;
+                let peak_dbfs = ratio_to_dbfs(sums.peak);
+                let rms_dbfs = ratio_to_dbfs(rms);
+                ChannelStats {
+                    peak_dbfs: floor(peak_dbfs),
+                    rms_dbfs: floor(rms_dbfs),
+                    dc_offset: (sums.sum / num_samples as f64) as f32,
+                    crest_factor_db: if rms > 0.0 {
+                        Some(peak_dbfs - rms_dbfs)
+                    } else {
+                        None
+                    },
+                    num_clipped_samples: sums.num_clipped_samples,
+                    silence_percent: 100.0 * sums.num_silent_samples as f32 / num_samples as f32,
+                }
+            })
+            .collect();
+
+        let silent_channels: Vec<u16> = (0..self.num_channels)
+            .filter(|channel_idx| {
+                self.channels[*channel_idx as usize].peak <= self.silence_threshold
+            })
+            .collect();
+        let is_silent = |channel_idx: u16| silent_channels.contains(&channel_idx);
+
+        // Two channels are copies when the energy of their difference,
+        // a^2 + b^2 - 2ab, is tiny next to the energy of the quieter
+        // channel. They are inverted copies when the same is true of
+        // their sum, a^2 + b^2 + 2ab.
+        let match_ratio = 10.0_f64.powf(CHANNEL_MATCH_THRESHOLD_DB / 10.0);
+        let mut identical_channels = Vec::new();
+        let mut inverted_channels = Vec::new();
+        let mut pair_idx = 0;
+        for first_idx in 0..self.num_channels {
+            for second_idx in first_idx + 1..self.num_channels {
+                let product = self.pair_products[pair_idx];
+                pair_idx += 1;
+                if is_silent(first_idx) || is_silent(second_idx) {
+                    continue;
+                }
+                let first_energy = self.channels[first_idx as usize].sum_of_squares;
+                let second_energy = self.channels[second_idx as usize].sum_of_squares;
+                let tolerance = match_ratio * first_energy.min(second_energy);
+                if first_energy + second_energy - 2.0 * product <= tolerance {
+                    identical_channels.push([first_idx, second_idx]);
+                } else if first_energy + second_energy + 2.0 * product <= tolerance {
+                    inverted_channels.push([first_idx, second_idx]);
+                }
+            }
+        }
+
+        let is_stereo = self.num_channels == 2;
+        StatsReport {
+            frame_rate_hz: self.frame_rate_hz,
+            num_channels: self.num_channels,
+            num_frames: self.num_frames,
+            left_only: is_stereo && !is_silent(0) && is_silent(1),
+            right_only: is_stereo && is_silent(0) && !is_silent(1),
+            channels,
+            identical_channels,
+            inverted_channels,
+            silent_channels,
+        }
+    }
+}
@@ -0,0 +1,488 @@
+//! Voice activity detection.
+//!
+//! The audio is mixed down to mono and resampled to [`VAD_FRAME_RATE_HZ`].
+//! It is then cut into short analysis frames, and three features are
+//! measured for each frame:
+//!
+//! - **Energy**, compared against both an absolute floor and the noise
+//!   floor of the whole recording.
+//! - **Spectral flatness** in the speech band. Voiced speech has strong
+//!   harmonics and a low flatness, while broadband noise is close to flat.
+//! - **Zero-crossing rate**. Voiced speech crosses zero far less often
+//!   than hiss and other broadband noise.
+//!
+//! Hum, tones and drones pass both of those tests too, so the energy of
+//! the loud frames around each frame is also measured. Speech rises and
+//! falls with every syllable, a few times a second, while a steady sound
+//! stays at the same level.
+//!
+//! A frame is speech when it is loud enough, at least one of spectral
+//! flatness and zero-crossing rate agrees, and the frames around it rise
+//! and fall like syllables. Short gaps are bridged with a hangover, and
+//! segments that are too short to be speech are dropped.
+
+use serde::{Deserialize, Serialize};
+
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+
+use crate::backend::constants::DEFAULT_RESAMPLE_MODE;
+use crate::backend::resample::resample;
+use crate::backend::source::BorrowedWaveformSource;
+use crate::backend::units::{frames_to_milliseconds, milliseconds_to_frames};
+use crate::backend::Error;
+use crate::backend::Signal;
+use crate::backend::Source;
+use crate::backend::Waveform;
+
+/// The frame rate that audio is resampled to before it is analyzed.
+pub const VAD_FRAME_RATE_HZ: u32 = 16_000;
+
+/// The lowest frequency counted when measuring spectral flatness.
+const SPEECH_BAND_MIN_HZ: f32 = 100.0;
+/// The highest frequency counted when measuring spectral flatness.
+const SPEECH_BAND_MAX_HZ: f32 = 4_000.0;
+/// The noise floor is estimated as this quantile of the frame energies.
+const NOISE_FLOOR_QUANTILE: f32 = 0.1;
+/// The energy of the loud frames around a frame is measured
+/// from this quantile to the one opposite it.
+const MODULATION_QUANTILE: f32 = 0.1;
+/// Keeps logarithms finite for silent frames and bins.
+const EPSILON: f32 = 1e-12;
+
+/// Settings for voice activity detection.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VadArgs {
+    /// The length of each analysis frame.
+    pub frame_milliseconds: usize,
+    /// Frames quieter than this, in dBFS, are never speech.
+    pub min_energy_dbfs: f32,
+    /// How many decibels above the noise floor of the recording
+    /// a frame has to be to count as speech.
+    pub energy_margin_db: f32,
+    /// Frames with a spectral flatness at or below this value, between
+    /// 0 for a pure tone and 1 for white noise, sound like voiced speech.
+    pub max_spectral_flatness: f32,
+    /// Frames where at most this fraction of consecutive samples
+    /// change sign sound like voiced speech.
+    pub max_zero_crossing_rate: f32,
+    /// How many decibels the energy of the loud frames around a frame has
+    /// to span, from the quietest tenth to the loudest tenth, for the frame
+    /// to be speech. Syllables make speech rise and fall by much more than
+    /// this, while steady hum and tones barely change.
+    pub min_modulation_db: f32,
+    /// How much audio, centered on each frame, is measured for
+    /// [`min_modulation_db`](#structfield.min_modulation_db).
+    /// This should hold at least a couple of syllables.
+    pub modulation_window_milliseconds: usize,
+    /// How long to keep a segment open after the last speech frame.
+    /// This bridges the short pauses between words.
+    pub hangover_milliseconds: usize,
+    /// Segments shorter than this, not counting the hangover, are dropped.
+    pub min_speech_milliseconds: usize,
+}
+
+impl Default for VadArgs {
+    fn default() -> Self {
+        VadArgs {
+            frame_milliseconds: 20,
+            min_energy_dbfs: -50.0,
+            energy_margin_db: 10.0,
+            max_spectral_flatness: 0.3,
+            max_zero_crossing_rate: 0.25,
+            min_modulation_db: 6.0,
+            modulation_window_milliseconds: 500,
+            hangover_milliseconds: 200,
+            min_speech_milliseconds: 100,
+        }
+    }
+}
+
+impl VadArgs {
+    /// Set the [`frame_milliseconds`](#structfield.frame_milliseconds) field.
+    #[must_use]
+    pub fn set_frame_milliseconds(&mut self, frame_milliseconds: usize) -> Self {
+        self.frame_milliseconds = frame_milliseconds;
+        *self
+    }
+
+    /// Set the [`min_energy_dbfs`](#structfield.min_energy_dbfs) field.
+    #[must_use]
+    pub fn set_min_energy_dbfs(&mut self, min_energy_dbfs: f32) -> Self {
+        self.min_energy_dbfs = min_energy_dbfs;
+        *self
+    }
+
+    /// Set the [`energy_margin_db`](#structfield.energy_margin_db) field.
+    #[must_use]
+    pub fn set_energy_margin_db(&mut self, energy_margin_db: f32) -> Self {
+        self.energy_margin_db = energy_margin_db;
+        *self
+    }
+
+    /// Set the [`max_spectral_flatness`](#structfield.max_spectral_flatness) field.
+    #[must_use]
+    pub fn set_max_spectral_flatness(&mut self, max_spectral_flatness: f32) -> Self {
+        self.max_spectral_flatness = max_spectral_flatness;
+        *self
+    }
+
+    /// Set the [`max_zero_crossing_rate`](#structfield.max_zero_crossing_rate) field.
+    #[must_use]
+    pub fn set_max_zero_crossing_rate(&mut self, max_zero_crossing_rate: f32) -> Self {
+        self.max_zero_crossing_rate = max_zero_crossing_rate;
+        *self
+    }
+
+    /// Set the [`min_modulation_db`](#structfield.min_modulation_db) field.
+    #[must_use]
+    pub fn set_min_modulation_db(&mut self, min_modulation_db: f32) -> Self {
+        self.min_modulation_db = min_modulation_db;
+        *self
+    }
+
+    /// Set the [`modulation_window_milliseconds`](#structfield.modulation_window_milliseconds) field.
+    #[must_use]
+    pub fn set_modulation_window_milliseconds(
+        &mut self,
+        modulation_window_milliseconds: usize,
+    ) -> Self {
+        self.modulation_window_milliseconds = modulation_window_milliseconds;
+        *self
+    }
+
+    /// Set the [`hangover_milliseconds`](#structfield.hangover_milliseconds) field.
+    #[must_use]
+    pub fn set_hangover_milliseconds(&mut self, hangover_milliseconds: usize) -> Self {
+        self.hangover_milliseconds = hangover_milliseconds;
+        *self
+    }
+
+    /// Set the [`min_speech_milliseconds`](#structfield.min_speech_milliseconds) field.
+    #[must_use]
+    pub fn set_min_speech_milliseconds(&mut self, min_speech_milliseconds: usize) -> Self {
+        self.min_speech_milliseconds = min_speech_milliseconds;
+        *self
+    }
+}
+
+/// A stretch of speech.
+///
+/// Frame indices are at the frame rate of the audio that was analyzed,
+/// not at [`VAD_FRAME_RATE_HZ`]. The end is exclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpeechSegment {
+    pub start_frame_idx: usize,
+    pub end_frame_idx: usize,
+    pub start_milliseconds: usize,
+    pub end_milliseconds: usize,
+}
+
+/// The features measured for one analysis frame.
+#[derive(Clone, Copy, Debug)]
+struct FrameFeatures {
+    energy_dbfs: f32,
+    spectral_flatness: f32,
+    zero_crossing_rate: f32,
+}
+
+/// Returns the fraction of consecutive samples that change sign.
+#[allow(clippy::cast_precision_loss)]
+fn zero_crossing_rate(frame: &[f32]) -> f32 {
+    if frame.len() < 2 {
+        return 0.0;
+    }
+    let crossings = frame
+        .windows(2)
+        .filter(|pair| (pair[0] >= 0.0) != (pair[1] >= 0.0))
+        .count();
+    crossings as f32 / (frame.len() - 1) as f32
+}
+
+/// Measures every analysis frame of mono audio at [`VAD_FRAME_RATE_HZ`].
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn measure_frames(samples: &[f32], frame_len: usize) -> Vec<FrameFeatures> {
+    let fft_len = frame_len.next_power_of_two();
+    let fft = FftPlanner::new().plan_fft_forward(fft_len);
+    let bin_hz = VAD_FRAME_RATE_HZ as f32 / fft_len as f32;
+    let min_bin = std::cmp::max((SPEECH_BAND_MIN_HZ / bin_hz).ceil() as usize, 1);
+    let max_bin = std::cmp::min((SPEECH_BAND_MAX_HZ / bin_hz).floor() as usize, fft_len / 2);
+    let window: Vec<f32> = (0..frame_len)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / frame_len as f32).cos())
+        .collect();
+    let mut spectrum = vec![Complex::new(0.0_f32, 0.0_f32); fft_len];
+
+    samples
+        .chunks(frame_len)
+        .map(|frame| {
+            let mean_square = frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32;
+
+            spectrum.fill(Complex::new(0.0, 0.0));
+            for ((bin, sample), w) in spectrum.iter_mut().zip(frame).zip(&window) {
+                bin.re = sample * w;
+            }
+            fft.process(&mut spectrum);
+            let band = &spectrum[min_bin..=max_bin];
+            let powers = band.iter().map(|bin| bin.norm_sqr() + EPSILON);
+            let log_mean = powers.clone().map(f32::ln).sum::<f32>() / band.len() as f32;
+            let mean = powers.sum::<f32>() / band.len() as f32;
+
+            FrameFeatures {
+                energy_dbfs: 10.0 * mean_square.log10(),
+                spectral_flatness: (log_mean.exp() / mean).min(1.0),
+                zero_crossing_rate: zero_crossing_rate(frame),
+            }
+        })
+        .collect()
+}
+
+/// Returns the value at `quantile` of energies that are already sorted.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn quantile_dbfs(sorted_energies: &[f32], quantile: f32) -> f32 {
+    let idx = ((sorted_energies.len() as f32 - 1.0) * quantile).round() as usize;
+    sorted_energies
+        .get(idx)
+        .copied()
+        .unwrap_or(f32::NEG_INFINITY)
+}
+
+/// Returns the energy below which a frame is treated as background noise.
+fn noise_floor_dbfs(features: &[FrameFeatures]) -> f32 {
+    let mut energies: Vec<f32> = features.iter().map(|f| f.energy_dbfs).collect();
+    energies.sort_by(f32::total_cmp);
+    quantile_dbfs(&energies, NOISE_FLOOR_QUANTILE)
+}
+
+/// Decides whether each analysis frame is speech, before any smoothing.
+///
+/// `modulation_radius` is how many analysis frames on either side of a
+/// frame are measured for [`VadArgs::min_modulation_db`].
+fn classify_frames(
+    features: &[FrameFeatures],
+    args: &VadArgs,
+    modulation_radius: usize,
+) -> Vec<bool> {
+    let energy_threshold_dbfs = args
+        .min_energy_dbfs
+        .max(noise_floor_dbfs(features) + args.energy_margin_db);
+    let is_loud: Vec<bool> = features
+        .iter()
+        .map(|f| f.energy_dbfs > energy_threshold_dbfs)
+        .collect();
+    let mut nearby_energies: Vec<f32> = Vec::with_capacity(2 * modulation_radius + 1);
+    features
+        .iter()
+        .enumerate()
+        .map(|(frame_idx, f)| {
+            if !is_loud[frame_idx]
+                || (f.spectral_flatness > args.max_spectral_flatness
+                    && f.zero_crossing_rate > args.max_zero_crossing_rate)
+            {
+                return false;
+            }
+            let start = frame_idx.saturating_sub(modulation_radius);
+            let end = std::cmp::min(frame_idx + modulation_radius + 1, features.len());
+            nearby_energies.clear();
+            nearby_energies.extend(
+                (start..end)
+                    .filter(|&idx| is_loud[idx])
+                    .map(|idx| features[idx].energy_dbfs),
+            );
+            nearby_energies.sort_by(f32::total_cmp);
+            let modulation_db = quantile_dbfs(&nearby_energies, 1.0 - MODULATION_QUANTILE)
+                - quantile_dbfs(&nearby_energies, MODULATION_QUANTILE);
+            modulation_db >= args.min_modulation_db
+        })
+        .collect()
+}
+
+/// Groups frame decisions into `(start, end)` runs of analysis frames,
+/// bridging gaps of up to `hangover_frames` and dropping runs shorter
+/// than `min_speech_frames`.
+fn smooth_frames(
+    is_speech: &[bool],
+    hangover_frames: usize,
+    min_speech_frames: usize,
+) -> Vec<(usize, usize)> {
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    // The run that we are in the middle of, and how many speech frames it has.
+    let mut current: Option<(usize, usize, usize)> = None;
+    for (frame_idx, &speech) in is_speech.iter().enumerate() {
+        if !speech {
+            continue;
+        }
+        current = match current {
+            Some((start, end, count)) if frame_idx - end <= hangover_frames => {
+                Some((start, frame_idx + 1, count + 1))
+            }
+            previous => {
+                runs.extend(previous.filter(|&(_, _, c)| c >= min_speech_frames).map(
+                    |(start, end, _)| (start, std::cmp::min(end + hangover_frames, frame_idx)),
+                ));
+                Some((frame_idx, frame_idx + 1, 1))
+            }
+        };
+    }
+    runs.extend(
+        current
+            .filter(|&(_, _, c)| c >= min_speech_frames)
+            .map(|(start, end, _)| (start, std::cmp::min(end + hangover_frames, is_speech.len()))),
+    );
+    runs
+}
+
+/// Finds the speech in any [`Source`], at any frame rate.
+///
+/// The source is read to the end. Its channels are mixed down to mono and
+/// resampled to [`VAD_FRAME_RATE_HZ`] for analysis, but the segments are
+/// given at the frame rate of the source.
+///
+/// The noise floor is estimated from the quietest tenth of the recording,
+/// so a recording that is speech from start to finish needs a smaller
+/// [`energy_margin_db`](VadArgs#structfield.energy_margin_db).
+///
+/// # Examples
+/// ```
+/// use babycat::vad::{speech_segments, VadArgs};
+/// use babycat::WaveformSource;
+///
+/// // One second of silence, half a second of a 200 hz buzz that rises and
+/// // falls four times a second like syllables, and one more second of silence.
+/// let samples: Vec<f32> = (0..20000)
+///     .map(|i| {
+///         let t = i as f32 / 8000.0;
+///         if (8000..12000).contains(&i) {
+///             let syllables = 0.6 - 0.4 * (2.0 * std::f32::consts::PI * 4.0 * t).cos();
+///             syllables * (2.0 * std::f32::consts::PI * 200.0 * t).sin()
+///         } else {
+///             0.0
+///         }
+///     })
+///     .collect();
+/// let source = WaveformSource::from_interleaved_samples(8000, 1, &samples);
+///
+/// let segments = speech_segments(source, VadArgs::default()).unwrap();
+/// assert_eq!(segments.len(), 1);
+/// assert_eq!(segments[0].start_milliseconds, 1000);
+/// // The hangover keeps the segment open for another 200 milliseconds.
+/// assert_eq!(segments[0].end_milliseconds, 1700);
+/// ```
+pub fn speech_segments<S: Source>(source: S, args: VadArgs) -> Result<Vec<SpeechSegment>, Error> {
+    let frame_rate_hz = source.frame_rate_hz();
+    let samples: Vec<f32> = if source.num_channels() == 1 {
+        source.collect()
+    } else {
+        source.convert_to_mono().collect()
+    };
+    speech_segments_of_mono(&samples, frame_rate_hz, args)
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn speech_segments_of_mono(
+    samples: &[f32],
+    frame_rate_hz: u32,
+    args: VadArgs,
+) -> Result<Vec<SpeechSegment>, Error> {
+    let num_frames = samples.len();
+    let resampled;
+    let samples = if frame_rate_hz == VAD_FRAME_RATE_HZ {
+        samples
+    } else {
+        resampled = resample(
+            frame_rate_hz,
+            VAD_FRAME_RATE_HZ,
+            1,
+            samples,
+            DEFAULT_RESAMPLE_MODE,
+        )?;
+        &resampled
+    };
+
+    let frame_len = std::cmp::max(
+        milliseconds_to_frames(args.frame_milliseconds, VAD_FRAME_RATE_HZ),
+        1,
+    );
+    let to_analysis_frames =
+        |ms: usize| milliseconds_to_frames(ms, VAD_FRAME_RATE_HZ).div_ceil(frame_len);
+
+    let features = measure_frames(samples, frame_len);
+    let is_speech = classify_frames(
+        &features,
+        &args,
+        to_analysis_frames(args.modulation_window_milliseconds) / 2,
+    );
+    let runs = smooth_frames(
+        &is_speech,
+        to_analysis_frames(args.hangover_milliseconds),
+        std::cmp::max(to_analysis_frames(args.min_speech_milliseconds), 1),
+    );
+
+    // Convert from analysis frames back to frames of the original audio.
+    let scale = f64::from(frame_rate_hz) / f64::from(VAD_FRAME_RATE_HZ);
+    let to_frame_idx = |analysis_frame_idx: usize| {
+        let idx = ((analysis_frame_idx * frame_len) as f64 * scale).round() as usize;
+        std::cmp::min(idx, num_frames)
+    };
+    Ok(runs
+        .into_iter()
+        .map(|(start, end)| {
+            let start_frame_idx = to_frame_idx(start);
+            let end_frame_idx = to_frame_idx(end);
+            SpeechSegment {
+                start_frame_idx,
+                end_frame_idx,
+                start_milliseconds: frames_to_milliseconds(start_frame_idx, frame_rate_hz),
+                end_milliseconds: frames_to_milliseconds(end_frame_idx, frame_rate_hz),
+            }
+        })
+        .collect())
+}
+
+/// Returns a new [`Waveform`] with only the speech from `waveform`.
+///
+/// The speech segments are joined end to end, in order.
+pub fn extract_speech(waveform: &Waveform, args: VadArgs) -> Result<Waveform, Error> {
+    let segments = waveform_speech_segments(waveform, args)?;
+    Ok(extract_segments(waveform, &segments))
+}
+
+/// Finds the speech in a [`Waveform`] that is already in memory.
+///
+/// This gives the same segments as [`speech_segments()`].
+pub fn waveform_speech_segments(
+    waveform: &Waveform,
+    args: VadArgs,
+) -> Result<Vec<SpeechSegment>, Error> {
+    speech_segments(BorrowedWaveformSource::new(waveform), args)
+}
+
+/// Returns a new [`Waveform`] that joins the given segments of `waveform` end to end.
+pub fn extract_segments(waveform: &Waveform, segments: &[SpeechSegment]) -> Waveform {
+    let num_channels = waveform.num_channels() as usize;
+    let samples = waveform.to_interleaved_samples();
+    let interleaved_samples: Vec<f32> = segments
+        .iter()
+        .flat_map(|segment| {
+            samples[segment.start_frame_idx * num_channels..segment.end_frame_idx * num_channels]
+                .iter()
+                .copied()
+        })
+        .collect();
+    Waveform::new(
+        waveform.frame_rate_hz(),
+        waveform.num_channels(),
+        interleaved_samples,
+    )
+}
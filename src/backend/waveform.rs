@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::io::Read;
 use std::marker::Send;
 use std::marker::Sync;
@@ -5,16 +6,31 @@ use std::marker::Sync;
 use either::Either::{Left, Right};
 use serde::{Deserialize, Serialize};
 
+use crate::backend::align;
+use crate::backend::beat;
 use crate::backend::constants::{
-    DEFAULT_END_TIME_MILLISECONDS, DEFAULT_FRAME_RATE_HZ, DEFAULT_NUM_CHANNELS,
-    DEFAULT_RESAMPLE_MODE, DEFAULT_START_TIME_MILLISECONDS,
+    DEFAULT_END_TIME_MILLISECONDS, DEFAULT_FRAME_RATE_HZ, DEFAULT_NORMALIZE_MODE,
+    DEFAULT_NORMALIZE_TRUE_PEAK_CEILING_DBTP, DEFAULT_NUM_CHANNELS, DEFAULT_RESAMPLE_MODE,
+    DEFAULT_START_TIME_MILLISECONDS, NORMALIZE_MODE_LOUDNESS, NORMALIZE_MODE_PEAK,
 };
 use crate::backend::decoder;
 use crate::backend::display::est_num_frames_to_str;
+use crate::backend::dtmf;
+use crate::backend::envelope::GainEnvelope;
 use crate::backend::errors::Error;
+use crate::backend::filter::{Biquad, FilterDesign, FilterState};
+use crate::backend::fingerprint::Fingerprint;
+use crate::backend::key;
+use crate::backend::loudness;
+use crate::backend::onset;
+use crate::backend::peaks::Peaks;
+use crate::backend::pitch;
 use crate::backend::resample::resample;
-use crate::backend::source::WaveformSource;
+use crate::backend::silence;
+use crate::backend::source::{BorrowedWaveformSource, WaveformSource};
+use crate::backend::spectral;
 use crate::backend::units::milliseconds_to_frames;
+use crate::backend::vad;
 use crate::backend::Signal;
 use crate::backend::Source;
 use crate::backend::WaveformArgs;
@@ -92,6 +108,14 @@ impl Waveform {
             return Err(Error::WrongNumChannelsAndMono);
         }
 
+        // Check the normalization mode before we spend time decoding.
+        if args.normalize_mode != DEFAULT_NORMALIZE_MODE
+            && args.normalize_mode != NORMALIZE_MODE_PEAK
+            && args.normalize_mode != NORMALIZE_MODE_LOUDNESS
+        {
+            return Err(Error::FeatureNotCompiled("unknown-normalize-mode"));
+        }
+
         // This is the first n channels that we want to read from.
         // If the user wants to convert the output to mono, we do that after
         // reading from the first n channels.
@@ -150,26 +174,28 @@ impl Waveform {
             Right(source)
         };
 
+        // Remove silence from the start and end.
+        let source = if args.trim_silence {
+            Left(source.trim_silence(args.trim_silence_threshold_dbfs))
+        } else {
+            Right(source)
+        };
+
+        // Pad with frames from the beginning, looping multiple times if necessary.
+        let source = if args.repeat_pad_ending && take_frames != 0 {
+            Left(source.loop_forever().take_frames(take_frames))
+        } else {
+            Right(source)
+        };
+
         let mut interleaved_samples: Vec<f32> = source.collect();
 
-        // Pad the waveform if necessary.
-        if (args.zero_pad_ending || args.repeat_pad_ending) && end_frame_idx > start_frame_idx {
+        // Pad with zeros if necessary.
+        if args.zero_pad_ending && end_frame_idx > start_frame_idx {
             let expected_buffer_len_from_user: usize =
                 (end_frame_idx - start_frame_idx) * output_num_channels as usize;
-            let actual_buffer_len = interleaved_samples.len();
-            if expected_buffer_len_from_user > actual_buffer_len {
-                // Pad with zeros.
-                if args.zero_pad_ending {
-                    interleaved_samples.resize(expected_buffer_len_from_user, 0.0_f32);
-                }
-                // Pad with elements from the beginning, looping multiple times if necessary.
-                else if args.repeat_pad_ending {
-                    let buffer_padding_len = expected_buffer_len_from_user - actual_buffer_len;
-                    for idx in 0..buffer_padding_len {
-                        let rounded_idx = idx % actual_buffer_len;
-                        interleaved_samples.push(interleaved_samples[rounded_idx]);
-                    }
-                }
+            if expected_buffer_len_from_user > interleaved_samples.len() {
+                interleaved_samples.resize(expected_buffer_len_from_user, 0.0_f32);
             }
         }
 
@@ -191,11 +217,27 @@ impl Waveform {
             output_frame_rate_hz = original_frame_rate_hz;
         }
 
-        Ok(Self::new(
+        let mut waveform = Self::new(
             output_frame_rate_hz,
             output_num_channels,
             interleaved_samples,
-        ))
+        );
+
+        // Normalize last, so that we measure exactly what we return.
+        // This scales the freshly decoded samples in place instead of
+        // copying them through the public normalize methods.
+        if args.normalize_mode == NORMALIZE_MODE_PEAK {
+            let gain_db = waveform.peak_normalization_gain_db(args.normalize_target);
+            waveform.apply_gain_db(gain_db);
+        } else if args.normalize_mode == NORMALIZE_MODE_LOUDNESS {
+            let gain_db = waveform.loudness_normalization_gain_db(
+                args.normalize_target,
+                args.normalize_true_peak_ceiling_dbtp,
+            )?;
+            waveform.apply_gain_db(gain_db);
+        }
+
+        Ok(waveform)
     }
 
     /// Decodes audio stored in an in-memory byte array.
@@ -509,6 +551,489 @@ impl Waveform {
         })
     }
 
+    /// Returns a copy of this waveform with time-varying gain applied
+    /// from a breakpoint [`GainEnvelope`].
+    ///
+    /// # Arguments
+    /// - `envelope`: The breakpoints to apply.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::Waveform;
+    /// use babycat::envelope::GainEnvelope;
+    ///
+    /// let waveform = Waveform::from_interleaved_samples(1000, 1, &[1.0, 1.0, 1.0]);
+    /// let envelope = GainEnvelope::from_json(r#"{
+    ///     "points": [{"milliseconds": 1, "dbfs": 0.0}, {"milliseconds": 2, "dbfs": -6.0}],
+    ///     "interpolation": "exponential"
+    /// }"#).unwrap();
+    ///
+    /// let ducked = waveform.gain_envelope(&envelope);
+    /// assert_eq!(ducked.to_interleaved_samples(), &[1.0, 1.0, 0.5011872]);
+    /// ```
+    #[must_use]
+    pub fn gain_envelope(&self, envelope: &GainEnvelope) -> Self {
+        self.clone()
+            .into_source()
+            .gain_envelope(envelope)
+            .to_waveform()
+    }
+
+    /// Returns a copy of this waveform convolved with an impulse response.
+    ///
+    /// See [`Source::convolve()`] for which impulse response layouts are supported.
+    ///
+    /// # Arguments
+    /// - `ir`: The impulse response, with the same frame rate as this waveform.
+    /// - `wet_dry`: `0.0` for only the original audio, `1.0` for only the convolved audio.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Signal, Waveform};
+    ///
+    /// let waveform = Waveform::from_interleaved_samples(8000, 2, &[1.0, -1.0]);
+    /// let ir = Waveform::from_interleaved_samples(8000, 1, &[0.5, 0.25]);
+    ///
+    /// let convolved = waveform.convolve(&ir, 1.0).unwrap();
+    /// assert_eq!(convolved.num_frames(), 2);
+    ///
+    /// let rounded: Vec<f32> = convolved
+    ///     .to_interleaved_samples()
+    ///     .iter()
+    ///     .map(|s| (s * 1000.0).round() / 1000.0)
+    ///     .collect();
+    /// assert_eq!(rounded, &[0.5, -0.5, 0.25, -0.25]);
+    /// ```
+    pub fn convolve(&self, ir: &Waveform, wet_dry: f32) -> Result<Self, Error> {
+        Ok(self
+            .clone()
+            .into_source()
+            .convolve(ir, wet_dry)?
+            .to_waveform())
+    }
+
+    /// Returns a copy of this waveform with every channel run through a
+    /// [`FilterDesign`].
+    ///
+    /// This has the same name as [`Source::apply_filter()`], which cannot
+    /// be called `filter()` without clashing with [`Iterator::filter()`].
+    ///
+    /// # Arguments
+    /// - `design`: The filter to apply. Its coefficients should have been
+    ///   computed for this waveform's frame rate.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::Waveform;
+    /// use babycat::filter::FilterDesign;
+    ///
+    /// let waveform = Waveform::from_interleaved_samples(44100, 1, &[1.0; 44100]);
+    /// let design = FilterDesign::butterworth_highpass(44100, 20.0, 2).unwrap();
+    /// let filtered = waveform.apply_filter(&design);
+    ///
+    /// // The DC offset has been removed.
+    /// assert!(filtered.to_interleaved_samples()[44099].abs() < 1e-3);
+    /// ```
+    #[must_use]
+    pub fn apply_filter(&self, design: &FilterDesign) -> Self {
+        let num_channels = self.num_channels as usize;
+        let mut state = FilterState::new(design, self.num_channels);
+        let mut filtered = self.clone();
+        for frame in filtered.interleaved_samples.chunks_mut(num_channels) {
+            for (channel_idx, sample) in frame.iter_mut().enumerate() {
+                *sample = state.process(channel_idx, *sample);
+            }
+        }
+        filtered
+    }
+
+    /// Returns a copy of this waveform with an `order`-th order
+    /// Butterworth lowpass filter applied.
+    ///
+    /// # Arguments
+    /// - `frequency_hz`: The cutoff frequency, where the response is 3 dB down.
+    /// - `order`: The filter order. Each order adds 6 dB per octave of rolloff.
+    pub fn lowpass(&self, frequency_hz: f32, order: usize) -> Result<Self, Error> {
+        let design = FilterDesign::butterworth_lowpass(self.frame_rate_hz, frequency_hz, order)?;
+        Ok(self.apply_filter(&design))
+    }
+
+    /// Returns a copy of this waveform with an `order`-th order
+    /// Butterworth highpass filter applied.
+    ///
+    /// # Arguments
+    /// - `frequency_hz`: The cutoff frequency, where the response is 3 dB down.
+    /// - `order`: The filter order. Each order adds 6 dB per octave of rolloff.
+    pub fn highpass(&self, frequency_hz: f32, order: usize) -> Result<Self, Error> {
+        let design = FilterDesign::butterworth_highpass(self.frame_rate_hz, frequency_hz, order)?;
+        Ok(self.apply_filter(&design))
+    }
+
+    /// Returns a copy of this waveform without a single frequency, such
+    /// as 50 or 60 hz mains hum.
+    ///
+    /// # Arguments
+    /// - `frequency_hz`: The frequency to remove.
+    /// - `q`: The quality factor. Higher values give a narrower notch.
+    pub fn notch(&self, frequency_hz: f32, q: f32) -> Result<Self, Error> {
+        let design = Biquad::notch(self.frame_rate_hz, frequency_hz, q)?.into();
+        Ok(self.apply_filter(&design))
+    }
+
+    /// Returns a copy of this waveform with the pre-emphasis filter
+    /// `y[n] = x[n] - coefficient * x[n-1]` applied.
+    #[must_use]
+    pub fn pre_emphasis(&self, coefficient: f32) -> Self {
+        self.apply_filter(&Biquad::pre_emphasis(coefficient).into())
+    }
+
+    /// Returns the intervals of this waveform that are not silent.
+    ///
+    /// See [`silence::non_silent_intervals()`] for the details.
+    pub fn non_silent_intervals(
+        &self,
+        threshold_dbfs: f32,
+        min_silence_milliseconds: usize,
+    ) -> Vec<silence::Interval> {
+        silence::non_silent_intervals(self, threshold_dbfs, min_silence_milliseconds)
+    }
+
+    /// Returns a copy of this waveform without the silence at its start and end.
+    ///
+    /// A frame is silent when every channel is at or below `threshold_dbfs`.
+    /// If the whole waveform is silent, the result has no frames.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::{Signal, Waveform};
+    ///
+    /// let waveform = Waveform::from_interleaved_samples(
+    ///     1000,
+    ///     2,
+    ///     &[0.0, 0.0, 0.0, 0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 0.0],
+    /// );
+    /// let trimmed = waveform.trim_silence(-60.0);
+    /// assert_eq!(trimmed.num_frames(), 3);
+    /// assert_eq!(
+    ///     trimmed.to_interleaved_samples(),
+    ///     &[0.0, 0.5, 0.0, 0.0, 0.5, 0.0]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn trim_silence(&self, threshold_dbfs: f32) -> Self {
+        let intervals = silence::non_silent_intervals(self, threshold_dbfs, 0);
+        let (start_frame_idx, end_frame_idx) = match (intervals.first(), intervals.last()) {
+            (Some(first), Some(last)) => (first.start_frame_idx, last.end_frame_idx),
+            _ => (0, 0),
+        };
+        let num_channels = self.num_channels as usize;
+        Self::new(
+            self.frame_rate_hz,
+            self.num_channels,
+            self.interleaved_samples[start_frame_idx * num_channels..end_frame_idx * num_channels]
+                .to_vec(),
+        )
+    }
+
+    /// Returns the stretches of this waveform that contain speech.
+    ///
+    /// See [`vad::speech_segments()`] for the details.
+    pub fn speech_segments(&self, args: vad::VadArgs) -> Result<Vec<vad::SpeechSegment>, Error> {
+        vad::waveform_speech_segments(self, args)
+    }
+
+    /// Returns a new waveform with only the speech in this one,
+    /// joined end to end.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::vad::VadArgs;
+    /// use babycat::{Signal, Waveform};
+    ///
+    /// // Half a second of a 200 hz buzz that rises and falls four times
+    /// // a second like syllables, between two seconds of silence.
+    /// let samples: Vec<f32> = (0..48000 * 5 / 2)
+    ///     .map(|i| {
+    ///         let t = i as f32 / 48000.0;
+    ///         if (48000..72000).contains(&i) {
+    ///             let syllables = 0.6 - 0.4 * (2.0 * std::f32::consts::PI * 4.0 * t).cos();
+    ///             syllables * (2.0 * std::f32::consts::PI * 200.0 * t).sin()
+    ///         } else {
+    ///             0.0
+    ///         }
+    ///     })
+    ///     .collect();
+    /// let waveform = Waveform::from_interleaved_samples(48000, 1, &samples);
+    ///
+    /// let args = VadArgs::default().set_hangover_milliseconds(0);
+    /// let speech = waveform.extract_speech(args).unwrap();
+    /// assert_eq!(speech.num_frames(), 24000);
+    /// ```
+    pub fn extract_speech(&self, args: vad::VadArgs) -> Result<Self, Error> {
+        vad::extract_speech(self, args)
+    }
+
+    /// Returns the DTMF telephone keypad digits in this waveform, in order.
+    ///
+    /// See [`dtmf::dtmf_digits()`] for the details.
+    ///
+    /// # Errors
+    /// Returns [`Error::FrameRateTooLow`] if the frame rate of the waveform
+    /// is below [`dtmf::DTMF_MIN_FRAME_RATE_HZ`].
+    pub fn dtmf_digits(&self, args: dtmf::DtmfArgs) -> Result<Vec<dtmf::DtmfDigit>, Error> {
+        dtmf::waveform_dtmf_digits(self, args)
+    }
+
+    /// Computes the short-time Fourier transform of every channel.
+    ///
+    /// See [`spectral::Stft::from_waveform()`] for the details.
+    pub fn stft(&self, args: spectral::StftArgs) -> Result<spectral::Stft, Error> {
+        spectral::Stft::from_waveform(self, args)
+    }
+
+    /// Computes a mel spectrogram of every channel.
+    ///
+    /// See [`spectral::mel_spectrogram()`] for the details.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::spectral::{MelArgs, StftArgs};
+    /// use babycat::{Signal, Waveform};
+    ///
+    /// let waveform = Waveform::from_frames_of_silence(22050, 2, 22050);
+    /// let mel = waveform
+    ///     .mel_spectrogram(StftArgs::default(), MelArgs::default())
+    ///     .unwrap();
+    /// assert_eq!(mel.num_channels(), 2);
+    /// assert_eq!(mel.num_features(), 128);
+    /// assert_eq!(mel.num_time_steps(), 44);
+    /// ```
+    pub fn mel_spectrogram(
+        &self,
+        stft_args: spectral::StftArgs,
+        mel_args: spectral::MelArgs,
+    ) -> Result<spectral::Features, Error> {
+        spectral::mel_spectrogram(&self.stft(stft_args)?, mel_args)
+    }
+
+    /// Computes mel-frequency cepstral coefficients of every channel.
+    ///
+    /// See [`spectral::mfcc()`] for the details.
+    pub fn mfcc(
+        &self,
+        stft_args: spectral::StftArgs,
+        mel_args: spectral::MelArgs,
+        mfcc_args: spectral::MfccArgs,
+    ) -> Result<spectral::Features, Error> {
+        spectral::mfcc(&self.mel_spectrogram(stft_args, mel_args)?, mfcc_args)
+    }
+
+    /// Computes the magnitude of the constant-Q transform of every channel.
+    ///
+    /// See [`spectral::cqt()`] for the details.
+    pub fn cqt(&self, args: spectral::CqtArgs) -> Result<spectral::Features, Error> {
+        spectral::cqt(self, args)
+    }
+
+    /// Computes a 12-bin chromagram of every channel.
+    ///
+    /// See [`spectral::chroma()`] for the details.
+    pub fn chroma(&self, args: spectral::CqtArgs) -> Result<spectral::Features, Error> {
+        spectral::chroma(self, args)
+    }
+
+    /// Computes the onset strength envelope of this waveform, with all
+    /// channels averaged together.
+    ///
+    /// See [`onset::onset_strength()`] for the details.
+    pub fn onset_strength(&self, args: spectral::StftArgs) -> Result<Vec<f32>, Error> {
+        onset::onset_strength(self, args)
+    }
+
+    /// Finds the onsets in this waveform, as frame indices in order.
+    ///
+    /// See [`onset::onsets()`] for the details.
+    pub fn onsets(&self, args: onset::OnsetArgs) -> Result<Vec<usize>, Error> {
+        onset::onsets(self, args)
+    }
+
+    /// Cuts this waveform into one piece per onset.
+    ///
+    /// See [`onset::split_at_onsets()`] for the details.
+    pub fn split_at_onsets(&self, args: onset::OnsetArgs) -> Result<Vec<Self>, Error> {
+        onset::split_at_onsets(self, args)
+    }
+
+    /// Estimates the tempo of this waveform.
+    ///
+    /// See [`beat::tempo()`] for the details.
+    pub fn tempo(&self, args: beat::BeatArgs) -> Result<beat::Tempo, Error> {
+        beat::tempo(self, args)
+    }
+
+    /// Estimates the tempo of this waveform and finds its beats.
+    ///
+    /// See [`beat::beat_track()`] for the details.
+    pub fn beat_track(&self, args: beat::BeatArgs) -> Result<beat::BeatTrack, Error> {
+        beat::beat_track(self, args)
+    }
+
+    /// Tracks the fundamental frequency of this waveform.
+    ///
+    /// See [`pitch::pitch()`] for the details.
+    pub fn pitch(&self, args: pitch::PitchArgs) -> Result<Vec<pitch::Pitch>, Error> {
+        pitch::pitch(self, args)
+    }
+
+    /// Estimates the musical key of this waveform, or returns `None` if it
+    /// has no pitched content.
+    ///
+    /// See [`key::key()`] for the details.
+    pub fn key(&self, args: spectral::CqtArgs) -> Result<Option<key::Key>, Error> {
+        key::key(self, args)
+    }
+
+    /// Computes a compact fingerprint of this waveform for finding copies
+    /// of the same recording.
+    ///
+    /// See [`Fingerprint`] for the details.
+    pub fn fingerprint(&self) -> Fingerprint {
+        Fingerprint::from_waveform(self)
+    }
+
+    /// Finds how many frames later a sound appears in `other` than in
+    /// this waveform, searching up to `max_lag_frames` in either direction.
+    ///
+    /// See [`align::find_offset()`] for the details.
+    pub fn find_offset(
+        &self,
+        other: &Waveform,
+        max_lag_frames: usize,
+    ) -> Result<align::Offset, Error> {
+        align::find_offset(self, other, max_lag_frames)
+    }
+
+    /// Summarizes the minimum, maximum and RMS of every channel in
+    /// buckets of `frames_per_bucket` frames, for drawing the waveform.
+    ///
+    /// See [`Peaks`] for the details.
+    pub fn peaks(&self, frames_per_bucket: usize) -> Result<Peaks, Error> {
+        Peaks::from_waveform(self, frames_per_bucket)
+    }
+
+    /// Summarizes the minimum, maximum and RMS of every channel in at
+    /// most `num_buckets` buckets of equal length.
+    ///
+    /// See [`Peaks::from_waveform_with_num_buckets()`] for the details.
+    pub fn peaks_with_num_buckets(&self, num_buckets: usize) -> Result<Peaks, Error> {
+        Peaks::from_waveform_with_num_buckets(self, num_buckets)
+    }
+
+    /// Multiplies every sample by a gain given in decibels, in place.
+    fn apply_gain_db(&mut self, gain_db: f32) {
+        let ratio = loudness::dbfs_to_ratio(gain_db);
+        self.interleaved_samples
+            .iter_mut()
+            .for_each(|sample| *sample *= ratio);
+    }
+
+    /// Returns the gain, in decibels, that moves the sample peak to `target_dbfs`.
+    fn peak_normalization_gain_db(&self, target_dbfs: f32) -> f32 {
+        let peak_dbfs = loudness::sample_peak_dbfs(self);
+        if !peak_dbfs.is_finite() {
+            return 0.0;
+        }
+        target_dbfs - peak_dbfs
+    }
+
+    /// Returns the gain, in decibels, that moves the integrated loudness to
+    /// `target_lufs` without pushing the true peak above `ceiling_dbtp`.
+    fn loudness_normalization_gain_db(
+        &self,
+        target_lufs: f32,
+        ceiling_dbtp: f32,
+    ) -> Result<f32, Error> {
+        let Some(current_lufs) = loudness::integrated_loudness(self)? else {
+            return Ok(0.0);
+        };
+        let true_peak_dbtp = loudness::true_peak_dbtp(self)?;
+        Ok((target_lufs - current_lufs).min(ceiling_dbtp - true_peak_dbtp))
+    }
+
+    /// Returns a copy of this waveform scaled so that its sample peak is at `target_dbfs`.
+    ///
+    /// Silent waveforms are returned unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::Waveform;
+    ///
+    /// let waveform = Waveform::from_interleaved_samples(44100, 1, &[0.25, -0.5, 0.125]);
+    /// let normalized = waveform.normalize_peak(0.0);
+    /// assert_eq!(normalized.to_interleaved_samples(), &[0.5, -1.0, 0.25]);
+    /// ```
+    #[must_use]
+    pub fn normalize_peak(&self, target_dbfs: f32) -> Self {
+        let mut normalized = self.clone();
+        normalized.apply_gain_db(self.peak_normalization_gain_db(target_dbfs));
+        normalized
+    }
+
+    /// Returns a copy of this waveform scaled so that its ITU-R BS.1770
+    /// integrated loudness is at `target_lufs`, without letting its true
+    /// peak exceed 0 dBTP.
+    ///
+    /// Waveforms that are silent or shorter than 400 milliseconds are
+    /// returned unchanged.
+    /// See [`normalize_loudness_with_ceiling()`](Self::normalize_loudness_with_ceiling)
+    /// to choose a different ceiling.
+    ///
+    /// # Errors
+    /// Returns [`Error::FrameRateTooLow`] if the frame rate is below
+    /// [`LOUDNESS_MIN_FRAME_RATE_HZ`](crate::loudness::LOUDNESS_MIN_FRAME_RATE_HZ).
+    pub fn normalize_loudness(&self, target_lufs: f32) -> Result<Self, Error> {
+        self.normalize_loudness_with_ceiling(target_lufs, DEFAULT_NORMALIZE_TRUE_PEAK_CEILING_DBTP)
+    }
+
+    /// Returns a copy of this waveform scaled so that its ITU-R BS.1770
+    /// integrated loudness is at `target_lufs`, without letting its true
+    /// peak exceed `ceiling_dbtp`.
+    ///
+    /// If the ceiling would be exceeded, then the gain is reduced until the
+    /// true peak sits on the ceiling, and the result will be quieter than
+    /// `target_lufs`.
+    ///
+    /// # Errors
+    /// Returns [`Error::FrameRateTooLow`] if the frame rate is below
+    /// [`LOUDNESS_MIN_FRAME_RATE_HZ`](crate::loudness::LOUDNESS_MIN_FRAME_RATE_HZ).
+    ///
+    /// # Examples
+    /// ```
+    /// use babycat::Waveform;
+    /// use babycat::loudness::{integrated_loudness, true_peak_dbtp};
+    ///
+    /// let samples: Vec<f32> = (0..96000)
+    ///     .map(|i| 0.1 * (2.0 * std::f32::consts::PI * 1000.0 * i as f32 / 48000.0).sin())
+    ///     .collect();
+    /// let waveform = Waveform::from_interleaved_samples(48000, 1, &samples);
+    ///
+    /// let normalized = waveform.normalize_loudness_with_ceiling(-16.0, -1.0).unwrap();
+    /// assert!((integrated_loudness(&normalized).unwrap().unwrap() + 16.0).abs() < 0.1);
+    ///
+    /// // Reaching -2 LUFS would clip, so the true peak is held at -1 dBTP instead.
+    /// let limited = waveform.normalize_loudness_with_ceiling(-2.0, -1.0).unwrap();
+    /// assert!((true_peak_dbtp(&limited).unwrap() + 1.0).abs() < 0.1);
+    /// ```
+    pub fn normalize_loudness_with_ceiling(
+        &self,
+        target_lufs: f32,
+        ceiling_dbtp: f32,
+    ) -> Result<Self, Error> {
+        let mut normalized = self.clone();
+        normalized.apply_gain_db(self.loudness_normalization_gain_db(target_lufs, ceiling_dbtp)?);
+        Ok(normalized)
+    }
+
     /// Encodes the waveform into a WAV-encoded byte array.
     pub fn to_wav_buffer(&self) -> Result<Vec<u8>, Error> {
         let writer_spec = hound::WavSpec {
@@ -711,6 +1236,19 @@ impl Waveform {
         self.num_frames
     }
 
+    /// Returns this waveform with its channels averaged into one, only
+    /// copying the samples when there is more than one channel.
+    pub(crate) fn to_mono(&self) -> Cow<'_, Self> {
+        if self.num_channels == 1 {
+            return Cow::Borrowed(self);
+        }
+        Cow::Owned(
+            BorrowedWaveformSource::new(self)
+                .convert_to_mono()
+                .to_waveform(),
+        )
+    }
+
     /// Returns the waveform as a slice of channel-interleaved `f32` samples.
     pub fn to_interleaved_samples(&self) -> &[f32] {
         &self.interleaved_samples
@@ -2,16 +2,21 @@ use serde::{Deserialize, Serialize};
 
 use crate::backend::constants::{
     DEFAULT_CONVERT_TO_MONO, DEFAULT_DECODING_BACKEND, DEFAULT_END_TIME_MILLISECONDS,
-    DEFAULT_FRAME_RATE_HZ, DEFAULT_NUM_CHANNELS, DEFAULT_REPEAT_PAD_ENDING, DEFAULT_RESAMPLE_MODE,
-    DEFAULT_START_TIME_MILLISECONDS, DEFAULT_ZERO_PAD_ENDING,
+    DEFAULT_FRAME_RATE_HZ, DEFAULT_NORMALIZE_MODE, DEFAULT_NORMALIZE_TARGET,
+    DEFAULT_NORMALIZE_TRUE_PEAK_CEILING_DBTP, DEFAULT_NUM_CHANNELS, DEFAULT_REPEAT_PAD_ENDING,
+    DEFAULT_RESAMPLE_MODE, DEFAULT_START_TIME_MILLISECONDS, DEFAULT_TRIM_SILENCE,
+    DEFAULT_TRIM_SILENCE_THRESHOLD_DBFS, DEFAULT_ZERO_PAD_ENDING,
 };
 /// Specifies what transformations to apply to the audio during the decoding
 /// process.
 ///
 /// The default value for this struct will tell Babycat to decode audio
 /// as-is and not change anything.
+///
+/// The decibel fields are compared by their bit patterns, which lets
+/// this struct implement [`Eq`] even though they are floats.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct WaveformArgs {
     /// We discard any audio before this millisecond
     /// offset. By default, this does nothing and the
@@ -93,8 +98,69 @@ pub struct WaveformArgs {
     /// corresponds to the [`SymphoniaDecoder`](crate::decoder::SymphoniaDecoder), which
     /// is a wrapper for the [`symphonia`](https://github.com/pdeljanov/Symphonia/) library.
     pub decoding_backend: u32,
+    /// Sets how the decoded audio is scaled after every other
+    /// transformation, including resampling, has been applied.
+    /// By default, the audio is not scaled. The available choices are:
+    /// * [`NORMALIZE_MODE_PEAK`](crate::constants::NORMALIZE_MODE_PEAK)
+    /// * [`NORMALIZE_MODE_LOUDNESS`](crate::constants::NORMALIZE_MODE_LOUDNESS)
+    ///
+    #[serde(default)]
+    pub normalize_mode: u32,
+    /// The level to normalize to. This is in dBFS when
+    /// [`normalize_mode`](#structfield.normalize_mode) is
+    /// [`NORMALIZE_MODE_PEAK`](crate::constants::NORMALIZE_MODE_PEAK)
+    /// and in LUFS when it is
+    /// [`NORMALIZE_MODE_LOUDNESS`](crate::constants::NORMALIZE_MODE_LOUDNESS).
+    #[serde(default)]
+    pub normalize_target: f32,
+    /// The highest true peak, in dBTP, that loudness normalization
+    /// is allowed to produce. If reaching
+    /// [`normalize_target`](#structfield.normalize_target) would push
+    /// the true peak above this ceiling, then less gain is applied.
+    /// This defaults to 0 dBTP.
+    #[serde(default)]
+    pub normalize_true_peak_ceiling_dbtp: f32,
+    /// Set this to `true` to remove silence from the start and end of
+    /// the audio. This happens after
+    /// [`start_time_milliseconds`](#structfield.start_time_milliseconds) and
+    /// [`end_time_milliseconds`](#structfield.end_time_milliseconds) select
+    /// the audio, and before any padding.
+    #[serde(default)]
+    pub trim_silence: bool,
+    /// The level, in dBFS, at or below which a frame counts as silence
+    /// when [`trim_silence`](#structfield.trim_silence) is set.
+    /// A frame is only silent when every channel is at or below this level.
+    #[serde(default = "default_trim_silence_threshold_dbfs")]
+    pub trim_silence_threshold_dbfs: f32,
+}
+
+fn default_trim_silence_threshold_dbfs() -> f32 {
+    DEFAULT_TRIM_SILENCE_THRESHOLD_DBFS
 }
 
+impl PartialEq for WaveformArgs {
+    fn eq(&self, other: &Self) -> bool {
+        self.start_time_milliseconds == other.start_time_milliseconds
+            && self.end_time_milliseconds == other.end_time_milliseconds
+            && self.frame_rate_hz == other.frame_rate_hz
+            && self.num_channels == other.num_channels
+            && self.convert_to_mono == other.convert_to_mono
+            && self.zero_pad_ending == other.zero_pad_ending
+            && self.repeat_pad_ending == other.repeat_pad_ending
+            && self.resample_mode == other.resample_mode
+            && self.decoding_backend == other.decoding_backend
+            && self.normalize_mode == other.normalize_mode
+            && self.normalize_target.to_bits() == other.normalize_target.to_bits()
+            && self.normalize_true_peak_ceiling_dbtp.to_bits()
+                == other.normalize_true_peak_ceiling_dbtp.to_bits()
+            && self.trim_silence == other.trim_silence
+            && self.trim_silence_threshold_dbfs.to_bits()
+                == other.trim_silence_threshold_dbfs.to_bits()
+    }
+}
+
+impl Eq for WaveformArgs {}
+
 impl Default for WaveformArgs {
     fn default() -> Self {
         WaveformArgs {
@@ -107,6 +173,11 @@ impl Default for WaveformArgs {
             repeat_pad_ending: DEFAULT_REPEAT_PAD_ENDING,
             resample_mode: DEFAULT_RESAMPLE_MODE,
             decoding_backend: DEFAULT_DECODING_BACKEND,
+            normalize_mode: DEFAULT_NORMALIZE_MODE,
+            normalize_target: DEFAULT_NORMALIZE_TARGET,
+            normalize_true_peak_ceiling_dbtp: DEFAULT_NORMALIZE_TRUE_PEAK_CEILING_DBTP,
+            trim_silence: DEFAULT_TRIM_SILENCE,
+            trim_silence_threshold_dbfs: DEFAULT_TRIM_SILENCE_THRESHOLD_DBFS,
         }
     }
 }
@@ -167,4 +238,42 @@ impl WaveformArgs {
         self.decoding_backend = decoding_backend;
         *self
     }
+
+    /// Set the [`normalize_mode`](#structfield.normalize_mode) field.
+    #[must_use]
+    pub fn set_normalize_mode(&mut self, normalize_mode: u32) -> Self {
+        self.normalize_mode = normalize_mode;
+        *self
+    }
+
+    /// Set the [`normalize_target`](#structfield.normalize_target) field.
+    #[must_use]
+    pub fn set_normalize_target(&mut self, normalize_target: f32) -> Self {
+        self.normalize_target = normalize_target;
+        *self
+    }
+
+    /// Set the [`normalize_true_peak_ceiling_dbtp`](#structfield.normalize_true_peak_ceiling_dbtp) field.
+    #[must_use]
+    pub fn set_normalize_true_peak_ceiling_dbtp(
+        &mut self,
+        normalize_true_peak_ceiling_dbtp: f32,
+    ) -> Self {
+        self.normalize_true_peak_ceiling_dbtp = normalize_true_peak_ceiling_dbtp;
+        *self
+    }
+
+    /// Set the [`trim_silence`](#structfield.trim_silence) field.
+    #[must_use]
+    pub fn set_trim_silence(&mut self, trim_silence: bool) -> Self {
+        self.trim_silence = trim_silence;
+        *self
+    }
+
+    /// Set the [`trim_silence_threshold_dbfs`](#structfield.trim_silence_threshold_dbfs) field.
+    #[must_use]
+    pub fn set_trim_silence_threshold_dbfs(&mut self, trim_silence_threshold_dbfs: f32) -> Self {
+        self.trim_silence_threshold_dbfs = trim_silence_threshold_dbfs;
+        *self
+    }
 }
@@ -104,6 +104,263 @@ pub enum SubcommandArgs {
             help = "Select the backend to use for audio decoding. Valid values are: symphonia"
         )]
         decoding_backend: String,
+
+        #[structopt(
+            long,
+            display_order = 12,
+            default_value = "none",
+            help = "Scale the output audio to `--normalize-target`. Valid values are: none, peak, loudness"
+        )]
+        normalize_mode: String,
+
+        #[structopt(
+            long,
+            display_order = 13,
+            default_value = "0",
+            allow_hyphen_values = true,
+            help = "The level to normalize to, in dBFS for peak normalization or LUFS for loudness normalization"
+        )]
+        normalize_target: f32,
+
+        #[structopt(
+            long,
+            display_order = 14,
+            default_value = "0",
+            allow_hyphen_values = true,
+            help = "The highest true peak in dBTP that loudness normalization is allowed to produce"
+        )]
+        normalize_true_peak_ceiling_dbtp: f32,
+
+        #[structopt(
+            long,
+            display_order = 15,
+            help = "Remove silence from the start and end of the audio"
+        )]
+        trim_silence: bool,
+
+        #[structopt(
+            long,
+            display_order = 16,
+            default_value = "-60",
+            allow_hyphen_values = true,
+            help = "The level in dBFS at or below which audio counts as silence for `--trim-silence`"
+        )]
+        trim_silence_threshold_dbfs: f32,
+    },
+    Analyze {
+        #[structopt(
+            long,
+            display_order = 1,
+            help = "The input audio file to measure. The results are printed as JSON"
+        )]
+        input_filename: String,
+    },
+    Stats {
+        #[structopt(
+            long,
+            display_order = 1,
+            help = "The input audio file to measure. The statistics of every channel are printed as JSON"
+        )]
+        input_filename: String,
+
+        #[structopt(
+            long,
+            display_order = 2,
+            default_value = "-60",
+            allow_hyphen_values = true,
+            help = "The level in dBFS at or below which audio counts as silence"
+        )]
+        silence_threshold_dbfs: f32,
+    },
+    Render {
+        #[structopt(
+            long,
+            alias = "input",
+            display_order = 1,
+            help = "The input audio file to draw"
+        )]
+        input_filename: String,
+
+        #[structopt(
+            long,
+            alias = "output",
+            display_order = 2,
+            help = "The image file to write. The format is chosen by the extension, which can be .svg or .png"
+        )]
+        output_filename: String,
+
+        #[structopt(
+            long,
+            display_order = 3,
+            default_value = "waveform",
+            help = "What to draw. Valid values are: waveform, spectrogram"
+        )]
+        kind: String,
+
+        #[structopt(
+            long,
+            display_order = 4,
+            default_value = "1024",
+            help = "The width of the image in pixels"
+        )]
+        width: u32,
+
+        #[structopt(
+            long,
+            display_order = 5,
+            default_value = "256",
+            help = "The height of the image in pixels. Every channel gets an equal share of the height"
+        )]
+        height: u32,
+
+        #[structopt(
+            long,
+            display_order = 6,
+            default_value = "viridis",
+            help = "The colors to draw with. Valid values are: viridis, magma, grayscale"
+        )]
+        color_map: String,
+
+        #[structopt(
+            long,
+            display_order = 7,
+            default_value = "-100",
+            allow_hyphen_values = true,
+            help = "Spectrogram levels at or below this many dBFS are drawn with the first color of the color map"
+        )]
+        min_db: f32,
+
+        #[structopt(
+            long,
+            display_order = 8,
+            default_value = "0",
+            allow_hyphen_values = true,
+            help = "Spectrogram levels at or above this many dBFS are drawn with the last color of the color map"
+        )]
+        max_db: f32,
+
+        #[structopt(
+            long,
+            display_order = 9,
+            default_value = "0",
+            help = "Draw only the audio after this millisecond timestamp"
+        )]
+        start_time_milliseconds: usize,
+
+        #[structopt(
+            long,
+            display_order = 10,
+            default_value = "0",
+            help = "Draw only the audio before this millisecond timestamp"
+        )]
+        end_time_milliseconds: usize,
+    },
+    Split {
+        #[structopt(long, display_order = 1, help = "The input audio file to split")]
+        input_filename: String,
+
+        #[structopt(
+            long,
+            display_order = 2,
+            help = "Each piece is written as a WAV file named `<output-prefix>-001.wav`, `<output-prefix>-002.wav`, and so on"
+        )]
+        output_prefix: String,
+
+        #[structopt(
+            long,
+            display_order = 3,
+            default_value = "-50",
+            allow_hyphen_values = true,
+            help = "The level in dBFS at or below which audio counts as silence"
+        )]
+        threshold_dbfs: f32,
+
+        #[structopt(
+            long,
+            display_order = 4,
+            default_value = "500",
+            help = "Only split on silences that last at least this many milliseconds"
+        )]
+        min_silence_milliseconds: usize,
+
+        #[structopt(
+            long,
+            display_order = 5,
+            default_value = "0",
+            help = "Keep up to this many milliseconds of the surrounding silence at each end of every piece"
+        )]
+        keep_silence_milliseconds: usize,
+    },
+    Beats {
+        #[structopt(
+            long,
+            display_order = 1,
+            help = "The input audio file to find the tempo and beats of. The results are printed as JSON"
+        )]
+        input_filename: String,
+
+        #[structopt(
+            long,
+            display_order = 2,
+            default_value = "120",
+            help = "The tempo in beats per minute that the estimate favors"
+        )]
+        start_bpm: f32,
+
+        #[structopt(
+            long,
+            display_order = 3,
+            default_value = "30",
+            help = "The lowest tempo in beats per minute to consider"
+        )]
+        min_bpm: f32,
+
+        #[structopt(
+            long,
+            display_order = 4,
+            default_value = "300",
+            help = "The highest tempo in beats per minute to consider"
+        )]
+        max_bpm: f32,
+
+        #[structopt(
+            long,
+            display_order = 5,
+            default_value = "100",
+            help = "How strongly to prefer evenly-spaced beats over strong onsets"
+        )]
+        tightness: f32,
+
+        #[structopt(
+            long,
+            display_order = 6,
+            default_value = "512",
+            help = "The number of frames between the points where a beat can be placed"
+        )]
+        hop_length: usize,
+    },
+    Duplicates {
+        #[structopt(
+            required = true,
+            help = "The input audio files to compare. The groups of copies are printed as JSON"
+        )]
+        input_filenames: Vec<String>,
+
+        #[structopt(
+            long,
+            display_order = 1,
+            default_value = "0.7",
+            help = "The fingerprint similarity from 0 to 1 at or above which two files count as copies"
+        )]
+        threshold: f32,
+
+        #[structopt(
+            long,
+            display_order = 2,
+            default_value = "0",
+            help = "The number of threads to fingerprint with. 0 uses one thread per logical CPU core"
+        )]
+        num_workers: usize,
     },
     Play {
         #[structopt(long, display_order = 1, help = "The audio file to play")]
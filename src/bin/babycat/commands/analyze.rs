@@ -0,0 +1,48 @@
+use log::info;
+use serde::Serialize;
+
+use babycat::decoder;
+use babycat::key::{key_cqt_args, Key};
+use babycat::loudness::LoudnessReport;
+use babycat::{Waveform, WaveformArgs};
+
+use crate::common::UnwrapOrExit;
+
+/// Everything that `babycat analyze` measures about a file.
+#[derive(Serialize)]
+struct Analysis {
+    loudness: LoudnessReport,
+    key: Option<Key>,
+}
+
+pub fn analyze(input_filename: &str) {
+    //
+    // Measure the loudness while decoding, without holding the audio in memory.
+    let analysis_start_time = std::time::Instant::now();
+    let source = decoder::from_file(input_filename).unwrap_or_exit();
+    let loudness = LoudnessReport::from_source(source).unwrap_or_exit();
+    //
+    // The key estimate needs the whole file, but only one channel of it.
+    // There is no key if the frame rate is too low for a chromagram.
+    let key = key_cqt_args(loudness.frame_rate_hz).and_then(|key_args| {
+        let waveform_args = WaveformArgs::default().set_convert_to_mono(true);
+        let waveform = Waveform::from_file(input_filename, waveform_args).unwrap_or_exit();
+        waveform.key(key_args).unwrap_or_exit()
+    });
+    let analysis = Analysis { loudness, key };
+    let analysis_elapsed = std::time::Instant::now() - analysis_start_time;
+    info!(
+        "Analyzed {} frames of {} channels at {} hz in {} seconds from {}",
+        analysis.loudness.num_frames,
+        analysis.loudness.num_channels,
+        analysis.loudness.frame_rate_hz,
+        analysis_elapsed.as_secs_f64(),
+        input_filename,
+    );
+    //
+    // Print the report as JSON to standard output.
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&analysis).unwrap_or_exit()
+    );
+}
@@ -0,0 +1,40 @@
+use log::info;
+
+use babycat::beat::BeatArgs;
+use babycat::Waveform;
+
+use crate::common::UnwrapOrExit;
+
+pub fn beats(
+    input_filename: &str,
+    start_bpm: f32,
+    min_bpm: f32,
+    max_bpm: f32,
+    tightness: f32,
+    hop_length: usize,
+) {
+    //
+    // Decode from filesystem.
+    let waveform = Waveform::from_file(input_filename, Default::default()).unwrap_or_exit();
+    //
+    // Estimate the tempo and find the beats.
+    let args = BeatArgs::default()
+        .set_start_bpm(start_bpm)
+        .set_min_bpm(min_bpm)
+        .set_max_bpm(max_bpm)
+        .set_tightness(tightness)
+        .set_hop_length(hop_length);
+    let analysis_start_time = std::time::Instant::now();
+    let track = waveform.beat_track(args).unwrap_or_exit();
+    let analysis_elapsed = std::time::Instant::now() - analysis_start_time;
+    info!(
+        "Found {} beats at {:.1} BPM in {} seconds from {}",
+        track.beats.len(),
+        track.bpm,
+        analysis_elapsed.as_secs_f64(),
+        input_filename,
+    );
+    //
+    // Print the tempo and beats as JSON to standard output.
+    println!("{}", serde_json::to_string_pretty(&track).unwrap_or_exit());
+}
@@ -22,6 +22,11 @@ pub fn convert(
     repeat_pad_ending: bool,
     resample_mode: &str,
     decoding_backend: &str,
+    normalize_mode: &str,
+    normalize_target: f32,
+    normalize_true_peak_ceiling_dbtp: f32,
+    trim_silence: bool,
+    trim_silence_threshold_dbfs: f32,
 ) {
     //
     // Input validation.
@@ -49,6 +54,15 @@ pub fn convert(
             panic!("NO");
         }
     };
+    let normalize_mode_int = match normalize_mode {
+        "none" => DEFAULT_NORMALIZE_MODE,
+        "peak" => NORMALIZE_MODE_PEAK,
+        "loudness" => NORMALIZE_MODE_LOUDNESS,
+        _ => exit_with_msg(&format!(
+            "Unsupported normalization mode: {}",
+            normalize_mode
+        )),
+    };
     //
     // Set up decoding.
     let waveform_args = WaveformArgs {
@@ -61,6 +75,11 @@ pub fn convert(
         repeat_pad_ending,
         resample_mode: resample_mode_int,
         decoding_backend: decoding_backend_int,
+        normalize_mode: normalize_mode_int,
+        normalize_target,
+        normalize_true_peak_ceiling_dbtp,
+        trim_silence,
+        trim_silence_threshold_dbfs,
     };
     //
     // Decode from filesystem.
@@ -0,0 +1,33 @@
+use log::{info, warn};
+
+use babycat::batch::{cluster_duplicates, fingerprints_from_files};
+use babycat::BatchArgs;
+
+use crate::common::UnwrapOrExit;
+
+pub fn duplicates(input_filenames: &[String], threshold: f32, num_workers: usize) {
+    let filenames: Vec<&str> = input_filenames.iter().map(String::as_str).collect();
+    let batch_args = BatchArgs { num_workers };
+    //
+    // Decode and fingerprint every file.
+    let analysis_start_time = std::time::Instant::now();
+    let fingerprints = fingerprints_from_files(&filenames, batch_args);
+    for named_result in &fingerprints {
+        if let Err(err) = &named_result.result {
+            warn!("Skipping {}: {}", named_result.name, err);
+        }
+    }
+    //
+    // Compare every pair of fingerprints and group the copies.
+    let groups = cluster_duplicates(&fingerprints, threshold, batch_args);
+    let analysis_elapsed = std::time::Instant::now() - analysis_start_time;
+    info!(
+        "Found {} groups of copies among {} files in {} seconds",
+        groups.len(),
+        filenames.len(),
+        analysis_elapsed.as_secs_f64(),
+    );
+    //
+    // Print the groups as JSON to standard output.
+    println!("{}", serde_json::to_string_pretty(&groups).unwrap_or_exit());
+}
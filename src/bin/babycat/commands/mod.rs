@@ -1,2 +1,8 @@
+pub mod analyze;
+pub mod beats;
 pub mod convert;
+pub mod duplicates;
 pub mod play;
+pub mod render;
+pub mod split;
+pub mod stats;
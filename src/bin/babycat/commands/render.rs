@@ -0,0 +1,132 @@
+use log::info;
+
+use babycat::constants::*;
+use babycat::decoder;
+use babycat::peaks::Peaks;
+use babycat::render::{render_peaks, render_spectrogram, RenderArgs};
+use babycat::spectral::StftArgs;
+use babycat::units::milliseconds_to_frames;
+use babycat::Error;
+use babycat::Signal;
+use babycat::Source;
+use babycat::Waveform;
+use babycat::WaveformArgs;
+
+use crate::common::exit_with_msg;
+use crate::common::UnwrapOrExit;
+
+/// The bucket length for summarizing files whose length is not known
+/// up front. The buckets are combined to fit the width of the image.
+const UNKNOWN_LENGTH_FRAMES_PER_BUCKET: usize = 256;
+
+#[allow(clippy::too_many_arguments)]
+pub fn render(
+    input_filename: &str,
+    output_filename: &str,
+    kind: &str,
+    width: u32,
+    height: u32,
+    color_map: &str,
+    min_db: f32,
+    max_db: f32,
+    start_time_milliseconds: usize,
+    end_time_milliseconds: usize,
+) {
+    //
+    // Input validation.
+    let extension = std::path::Path::new(output_filename)
+        .extension()
+        .and_then(std::ffi::OsStr::to_str)
+        .map(str::to_ascii_lowercase);
+    let output_format = match extension.as_deref() {
+        Some("svg") => "svg",
+        Some("png") => "png",
+        _ => exit_with_msg(&format!(
+            "Unsupported output file format: {}. Use a filename ending in .svg or .png",
+            output_filename
+        )),
+    };
+    if kind != "waveform" && kind != "spectrogram" {
+        exit_with_msg(&format!("Unsupported render kind: {}", kind));
+    }
+    let color_map_int = match color_map {
+        "viridis" => COLOR_MAP_VIRIDIS,
+        "magma" => COLOR_MAP_MAGMA,
+        "grayscale" => COLOR_MAP_GRAYSCALE,
+        _ => exit_with_msg(&format!("Unsupported color map: {}", color_map)),
+    };
+    let render_args = RenderArgs {
+        width,
+        height,
+        color_map: color_map_int,
+        min_db,
+        max_db,
+    };
+    if start_time_milliseconds != 0
+        && end_time_milliseconds != 0
+        && start_time_milliseconds >= end_time_milliseconds
+    {
+        exit_with_msg(
+            &Error::WrongTimeOffset(start_time_milliseconds, end_time_milliseconds).to_string(),
+        );
+    }
+    let rendering_start_time = std::time::Instant::now();
+    let image = if kind == "spectrogram" {
+        //
+        // Decode the part of the file to draw.
+        let waveform_args = WaveformArgs {
+            start_time_milliseconds,
+            end_time_milliseconds,
+            ..Default::default()
+        };
+        let waveform = Waveform::from_file(input_filename, waveform_args).unwrap_or_exit();
+        info!(
+            "Decoded {} frames with {} channels at {} hz from {}",
+            waveform.num_frames(),
+            waveform.num_channels(),
+            waveform.frame_rate_hz(),
+            input_filename,
+        );
+        render_spectrogram(&waveform, StftArgs::default(), render_args)
+    } else {
+        //
+        // Summarize the part of the file to draw in a single streaming pass,
+        // with about one bucket of frames for every column of pixels.
+        let source = decoder::from_file(input_filename).unwrap_or_exit();
+        let frame_rate_hz = source.frame_rate_hz();
+        let source = source.select_frames(
+            milliseconds_to_frames(start_time_milliseconds, frame_rate_hz),
+            milliseconds_to_frames(end_time_milliseconds, frame_rate_hz),
+        );
+        let frames_per_bucket = source
+            .num_frames_estimate()
+            .map_or(UNKNOWN_LENGTH_FRAMES_PER_BUCKET, |num_frames| {
+                std::cmp::max(num_frames.div_ceil(width.max(1) as usize), 1)
+            });
+        let peaks = Peaks::from_source(source, frames_per_bucket).unwrap_or_exit();
+        info!(
+            "Summarized {} frames with {} channels at {} hz from {}",
+            peaks.num_frames(),
+            peaks.num_channels(),
+            peaks.frame_rate_hz(),
+            input_filename,
+        );
+        render_peaks(&peaks, render_args)
+    }
+    .unwrap_or_exit();
+    let bytes = if output_format == "png" {
+        image.to_png().unwrap_or_exit()
+    } else {
+        image.to_svg().into_bytes()
+    };
+    std::fs::write(output_filename, bytes).unwrap_or_exit();
+    let rendering_elapsed = std::time::Instant::now() - rendering_start_time;
+    info!(
+        "Rendered a {} by {} pixel {} in {} seconds to {}",
+        image.width(),
+        image.height(),
+        kind,
+        rendering_elapsed.as_secs_f64(),
+        output_filename,
+    );
+}
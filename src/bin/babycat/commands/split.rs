@@ -0,0 +1,51 @@
+use log::info;
+
+use babycat::units::milliseconds_to_frames;
+use babycat::Signal;
+use babycat::Waveform;
+
+use crate::common::UnwrapOrExit;
+
+pub fn split(
+    input_filename: &str,
+    output_prefix: &str,
+    threshold_dbfs: f32,
+    min_silence_milliseconds: usize,
+    keep_silence_milliseconds: usize,
+) {
+    //
+    // Decode from filesystem.
+    let waveform = Waveform::from_file(input_filename, Default::default()).unwrap_or_exit();
+    let frame_rate_hz = waveform.frame_rate_hz();
+    let num_channels = waveform.num_channels();
+    let num_frames = waveform.num_frames();
+    //
+    // Find where the audio is not silent.
+    let intervals = waveform.non_silent_intervals(threshold_dbfs, min_silence_milliseconds);
+    info!(
+        "Found {} pieces separated by at least {} milliseconds of silence in {}",
+        intervals.len(),
+        min_silence_milliseconds,
+        input_filename,
+    );
+    //
+    // Write every piece to its own file.
+    let keep_frames = milliseconds_to_frames(keep_silence_milliseconds, frame_rate_hz);
+    let samples = waveform.to_interleaved_samples();
+    for (piece_idx, interval) in intervals.iter().enumerate() {
+        let start_frame_idx = interval.start_frame_idx.saturating_sub(keep_frames);
+        let end_frame_idx = std::cmp::min(interval.end_frame_idx + keep_frames, num_frames);
+        let piece = Waveform::new(
+            frame_rate_hz,
+            num_channels,
+            samples[start_frame_idx * num_channels as usize..end_frame_idx * num_channels as usize]
+                .to_vec(),
+        );
+        let output_filename = format!("{}-{:03}.wav", output_prefix, piece_idx + 1);
+        piece.to_wav_file(&output_filename).unwrap_or_exit();
+        info!(
+            "Saved frames {} to {} to {}",
+            start_frame_idx, end_frame_idx, output_filename,
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use log::info;
+
+use babycat::decoder;
+use babycat::stats::StatsReport;
+
+use crate::common::UnwrapOrExit;
+
+pub fn stats(input_filename: &str, silence_threshold_dbfs: f32) {
+    //
+    // Measure every channel in a single streaming pass over the file.
+    let analysis_start_time = std::time::Instant::now();
+    let source = decoder::from_file(input_filename).unwrap_or_exit();
+    let report = StatsReport::from_source(source, silence_threshold_dbfs).unwrap_or_exit();
+    let analysis_elapsed = std::time::Instant::now() - analysis_start_time;
+    info!(
+        "Measured {} frames of {} channels at {} hz in {} seconds from {}",
+        report.num_frames,
+        report.num_channels,
+        report.frame_rate_hz,
+        analysis_elapsed.as_secs_f64(),
+        input_filename,
+    );
+    //
+    // Print the report as JSON to standard output.
+    println!("{}", serde_json::to_string_pretty(&report).unwrap_or_exit());
+}
@@ -37,6 +37,11 @@ fn main() {
             repeat_pad_ending,
             resample_mode,
             decoding_backend,
+            normalize_mode,
+            normalize_target,
+            normalize_true_peak_ceiling_dbtp,
+            trim_silence,
+            trim_silence_threshold_dbfs,
         } => commands::convert::convert(
             &input_filename,
             &output_filename,
@@ -50,7 +55,75 @@ fn main() {
             repeat_pad_ending,
             &resample_mode,
             &decoding_backend,
+            &normalize_mode,
+            normalize_target,
+            normalize_true_peak_ceiling_dbtp,
+            trim_silence,
+            trim_silence_threshold_dbfs,
         ),
+        command_args::SubcommandArgs::Analyze { input_filename } => {
+            commands::analyze::analyze(&input_filename)
+        }
+        command_args::SubcommandArgs::Stats {
+            input_filename,
+            silence_threshold_dbfs,
+        } => commands::stats::stats(&input_filename, silence_threshold_dbfs),
+        command_args::SubcommandArgs::Render {
+            input_filename,
+            output_filename,
+            kind,
+            width,
+            height,
+            color_map,
+            min_db,
+            max_db,
+            start_time_milliseconds,
+            end_time_milliseconds,
+        } => commands::render::render(
+            &input_filename,
+            &output_filename,
+            &kind,
+            width,
+            height,
+            &color_map,
+            min_db,
+            max_db,
+            start_time_milliseconds,
+            end_time_milliseconds,
+        ),
+        command_args::SubcommandArgs::Split {
+            input_filename,
+            output_prefix,
+            threshold_dbfs,
+            min_silence_milliseconds,
+            keep_silence_milliseconds,
+        } => commands::split::split(
+            &input_filename,
+            &output_prefix,
+            threshold_dbfs,
+            min_silence_milliseconds,
+            keep_silence_milliseconds,
+        ),
+        command_args::SubcommandArgs::Beats {
+            input_filename,
+            start_bpm,
+            min_bpm,
+            max_bpm,
+            tightness,
+            hop_length,
+        } => commands::beats::beats(
+            &input_filename,
+            start_bpm,
+            min_bpm,
+            max_bpm,
+            tightness,
+            hop_length,
+        ),
+        command_args::SubcommandArgs::Duplicates {
+            input_filenames,
+            threshold,
+            num_workers,
+        } => commands::duplicates::duplicates(&input_filenames, threshold, num_workers),
         command_args::SubcommandArgs::Play { input_filename } => {
             commands::play::play(input_filename).unwrap_or_exit()
         }
@@ -20,6 +20,46 @@ pub const ERROR_CANNOT_ZERO_PAD: u32 = 203;
 pub const ERROR_CANNOT_REPEAT_PAD: u32 = 204;
 /// Returned if you try setting both `zero_pad_ending` and  `repeat_pad_ending` as `true`.
 pub const ERROR_CANNOT_ZERO_PAD_AND_REPEAT_PAD: u32 = 205;
+/// Returned if a gain envelope could not be parsed.
+pub const ERROR_CANNOT_PARSE_GAIN_ENVELOPE: u32 = 206;
+/// Returned when a filter frequency is not between 0 hz and the Nyquist frequency.
+pub const ERROR_WRONG_FILTER_FREQUENCY: u32 = 207;
+/// Returned when a filter order is not supported by the requested filter design.
+pub const ERROR_WRONG_FILTER_ORDER: u32 = 208;
+/// Returned when an FFT size is zero or shorter than the window length.
+pub const ERROR_WRONG_FFT_SIZE: u32 = 209;
+/// Returned when a short-time Fourier transform hop length is zero.
+pub const ERROR_WRONG_HOP_LENGTH: u32 = 210;
+/// Returned when a short-time Fourier transform does not have the expected number of values.
+pub const ERROR_WRONG_STFT_SHAPE: u32 = 211;
+/// Returned when asking for zero features, or for more features than there are values.
+pub const ERROR_WRONG_NUM_FEATURES: u32 = 212;
+/// Returned when a delta width is even, smaller than 3, or longer than the number of time steps.
+pub const ERROR_WRONG_DELTA_WIDTH: u32 = 213;
+/// Returned when a delta order is zero or not smaller than the delta width.
+pub const ERROR_WRONG_DELTA_ORDER: u32 = 214;
+/// Returned when a constant-Q transform has zero bins per octave.
+pub const ERROR_WRONG_BINS_PER_OCTAVE: u32 = 215;
+/// Returned when a tempo range is empty or not positive, or when the
+/// starting tempo is outside of it.
+pub const ERROR_WRONG_TEMPO: u32 = 216;
+/// Returned when a pitch tracking frame is too short to hold two
+/// periods of the lowest pitch.
+pub const ERROR_WRONG_FRAME_LENGTH: u32 = 217;
+/// Returned when asking for peak summary buckets of zero frames.
+pub const ERROR_WRONG_FRAMES_PER_BUCKET: u32 = 218;
+/// Returned when a peak summary could not be read from its binary format.
+pub const ERROR_CANNOT_PARSE_PEAKS: u32 = 219;
+/// Returned when asking for an image that is zero pixels wide or tall, or more than 8192 pixels wide or tall.
+pub const ERROR_WRONG_IMAGE_SIZE: u32 = 220;
+/// Returned when the lowest level of a rendered decibel range is not below the highest level.
+pub const ERROR_WRONG_DECIBEL_RANGE: u32 = 221;
+/// Returned when audio has too low a frame rate to hold the frequencies being measured.
+pub const ERROR_FRAME_RATE_TOO_LOW: u32 = 222;
+/// Returned when a filter Q factor is not a positive, finite number.
+pub const ERROR_WRONG_FILTER_Q: u32 = 223;
+/// Returned when asking for a peak summary with zero buckets.
+pub const ERROR_WRONG_NUM_BUCKETS: u32 = 224;
 //
 /// Returned when we were not able to detect the encoded input as decodable audio.
 pub const ERROR_UNKNOWN_INPUT_ENCODING: u32 = 300;
@@ -52,6 +92,30 @@ pub const ERROR_CANNOT_APPEND_SOURCES_WITH_DIFFERENT_NUM_CHANNELS: u32 = 701;
 /// Returned when we try to append two audio sources that have different frame rates.
 pub const ERROR_CANNOT_APPEND_SOURCES_WITH_DIFFERENT_FRAME_RATES: u32 = 702;
 
+/// Returned when we try to mix audio sources that have a different number of channels.
+pub const ERROR_CANNOT_MIX_SOURCES_WITH_DIFFERENT_NUM_CHANNELS: u32 = 703;
+
+/// Returned when we try to mix audio sources that have different frame rates.
+pub const ERROR_CANNOT_MIX_SOURCES_WITH_DIFFERENT_FRAME_RATES: u32 = 704;
+
+/// Returned when we try to pan audio that does not have exactly two channels.
+pub const ERROR_CANNOT_PAN_WITHOUT_TWO_CHANNELS: u32 = 705;
+
+/// Returned when we try to convolve audio with an impulse response that has a different frame rate.
+pub const ERROR_CANNOT_CONVOLVE_WITH_DIFFERENT_FRAME_RATES: u32 = 706;
+
+/// Returned when we try to convolve audio with an impulse response that has an unsupported number of channels.
+pub const ERROR_CANNOT_CONVOLVE_WITH_IMPULSE_RESPONSE_CHANNELS: u32 = 707;
+
+/// Returned when we try to line up waveforms that have different frame rates.
+pub const ERROR_CANNOT_ALIGN_WITH_DIFFERENT_FRAME_RATES: u32 = 708;
+
+/// Returned when we try to line up and stack an empty list of waveforms.
+pub const ERROR_CANNOT_ALIGN_WITHOUT_WAVEFORMS: u32 = 709;
+
+/// Returned when stacking waveforms would give more than 65535 channels.
+pub const ERROR_CANNOT_STACK_TOO_MANY_CHANNELS: u32 = 710;
+
 pub fn error_to_num(err: Error) -> u32 {
     match err {
         Error::FeatureNotCompiled(..) => ERROR_FEATURE_NOT_COMPILED,
@@ -68,6 +132,38 @@ pub fn error_to_num(err: Error) -> u32 {
 
         Error::CannotSetZeroPadEndingAndRepeatPadEnding => ERROR_CANNOT_ZERO_PAD_AND_REPEAT_PAD,
 
+        Error::CannotParseGainEnvelope(..) => ERROR_CANNOT_PARSE_GAIN_ENVELOPE,
+
+        Error::WrongFilterFrequency(..) => ERROR_WRONG_FILTER_FREQUENCY,
+
+        Error::WrongFilterOrder(..) => ERROR_WRONG_FILTER_ORDER,
+
+        Error::WrongFilterQ(..) => ERROR_WRONG_FILTER_Q,
+
+        Error::WrongFftSize(..) => ERROR_WRONG_FFT_SIZE,
+
+        Error::WrongHopLength(..) => ERROR_WRONG_HOP_LENGTH,
+
+        Error::WrongStftShape(..) => ERROR_WRONG_STFT_SHAPE,
+
+        Error::WrongNumFeatures(..) => ERROR_WRONG_NUM_FEATURES,
+
+        Error::WrongDeltaWidth(..) => ERROR_WRONG_DELTA_WIDTH,
+
+        Error::WrongDeltaOrder(..) => ERROR_WRONG_DELTA_ORDER,
+
+        Error::WrongBinsPerOctave(..) => ERROR_WRONG_BINS_PER_OCTAVE,
+
+        Error::WrongTempo(..) => ERROR_WRONG_TEMPO,
+
+        Error::WrongFrameLength(..) => ERROR_WRONG_FRAME_LENGTH,
+        Error::WrongFramesPerBucket(..) => ERROR_WRONG_FRAMES_PER_BUCKET,
+        Error::WrongNumBuckets(..) => ERROR_WRONG_NUM_BUCKETS,
+        Error::CannotParsePeaks(..) => ERROR_CANNOT_PARSE_PEAKS,
+        Error::WrongImageSize(..) => ERROR_WRONG_IMAGE_SIZE,
+        Error::WrongDecibelRange(..) => ERROR_WRONG_DECIBEL_RANGE,
+        Error::FrameRateTooLow(..) => ERROR_FRAME_RATE_TOO_LOW,
+
         Error::UnknownDecodingBackend(..) => ERROR_UNKNOWN_DECODING_BACKEND,
 
         Error::NoSuitableAudioStreams(..) => ERROR_NO_SUITABLE_AUDIO_STREAMS,
@@ -99,5 +195,31 @@ pub fn error_to_num(err: Error) -> u32 {
         Error::CannotAppendSourcesWithDifferentFrameRates(..) => {
             ERROR_CANNOT_APPEND_SOURCES_WITH_DIFFERENT_FRAME_RATES
         }
+
+        Error::CannotMixSourcesWithDifferentNumChannels(..) => {
+            ERROR_CANNOT_MIX_SOURCES_WITH_DIFFERENT_NUM_CHANNELS
+        }
+
+        Error::CannotMixSourcesWithDifferentFrameRates(..) => {
+            ERROR_CANNOT_MIX_SOURCES_WITH_DIFFERENT_FRAME_RATES
+        }
+
+        Error::CannotPanWithoutTwoChannels(..) => ERROR_CANNOT_PAN_WITHOUT_TWO_CHANNELS,
+
+        Error::CannotConvolveWithDifferentFrameRates(..) => {
+            ERROR_CANNOT_CONVOLVE_WITH_DIFFERENT_FRAME_RATES
+        }
+
+        Error::CannotConvolveWithImpulseResponseChannels(..) => {
+            ERROR_CANNOT_CONVOLVE_WITH_IMPULSE_RESPONSE_CHANNELS
+        }
+
+        Error::CannotAlignWithDifferentFrameRates(..) => {
+            ERROR_CANNOT_ALIGN_WITH_DIFFERENT_FRAME_RATES
+        }
+
+        Error::CannotAlignWithoutWaveforms => ERROR_CANNOT_ALIGN_WITHOUT_WAVEFORMS,
+
+        Error::CannotStackTooManyChannels(..) => ERROR_CANNOT_STACK_TOO_MANY_CHANNELS,
     }
 }
@@ -117,6 +117,29 @@ use crate::frontends::python::waveform::PyArraySamples;
 ///         `libsamplerate <http://www.mega-nerd.com/SRC/>`_ at its
 ///         highest-quality setting.
 ///
+///     normalize_mode(int, optional): Set this to scale the decoded
+///         audio to a target level after every other transformation.
+///         The :py:mod:`babycat.normalize_mode` submodule contains
+///         the available choices. By default, the audio is not scaled.
+///
+///     normalize_target(float, optional): The level to normalize to.
+///         This is in dBFS for peak normalization and in LUFS for
+///         ITU-R BS.1770 loudness normalization.
+///
+///     normalize_true_peak_ceiling_dbtp(float, optional): The highest
+///         true peak, in dBTP, that loudness normalization may produce.
+///         If reaching ``normalize_target`` would exceed this ceiling,
+///         less gain is applied. Defaults to 0 dBTP.
+///
+///     trim_silence(bool, optional): Set this to ``True`` to remove
+///         silence from the start and end of the audio, after
+///         ``start_time_milliseconds`` and ``end_time_milliseconds``
+///         are applied and before any padding.
+///
+///     trim_silence_threshold_dbfs(float, optional): The level, in dBFS,
+///         at or below which every channel has to be for a frame
+///         to count as silence. Defaults to -60 dBFS.
+///
 ///     decoding_backend(int, optional): Sets the audio decoding
 ///         backend to use. Defaults to the Symphonia backend.
 ///
@@ -146,6 +169,11 @@ use crate::frontends::python::waveform::PyArraySamples;
     repeat_pad_ending = false,
     resample_mode = 0,
     decoding_backend = 0,
+    normalize_mode = 0,
+    normalize_target = "0.0",
+    normalize_true_peak_ceiling_dbtp = "0.0",
+    trim_silence = false,
+    trim_silence_threshold_dbfs = "-60.0",
     num_workers = 0
 )]
 #[pyo3(text_signature = "(
@@ -159,6 +187,11 @@ use crate::frontends::python::waveform::PyArraySamples;
     repeat_pad_ending = False,
     resample_mode = 0,
     decoding_backend = 0,
+    normalize_mode = 0,
+    normalize_target = 0.0,
+    normalize_true_peak_ceiling_dbtp = 0.0,
+    trim_silence = False,
+    trim_silence_threshold_dbfs = -60.0,
     num_workers = 0,
 )")]
 #[allow(clippy::too_many_arguments)]
@@ -175,6 +208,11 @@ pub fn waveforms_from_files(
     repeat_pad_ending: bool,
     resample_mode: u32,
     decoding_backend: u32,
+    normalize_mode: u32,
+    normalize_target: f32,
+    normalize_true_peak_ceiling_dbtp: f32,
+    trim_silence: bool,
+    trim_silence_threshold_dbfs: f32,
     num_workers: usize,
 ) -> Vec<crate::frontends::python::waveform_named_result::WaveformNamedResult> {
     let waveform_named_results = py.allow_threads(move || {
@@ -188,6 +226,11 @@ pub fn waveforms_from_files(
             repeat_pad_ending,
             resample_mode,
             decoding_backend,
+            normalize_mode,
+            normalize_target,
+            normalize_true_peak_ceiling_dbtp,
+            trim_silence,
+            trim_silence_threshold_dbfs,
         };
         let batch_args = crate::backend::BatchArgs { num_workers };
         let filenames_ref: Vec<&str> = filenames.iter().map(String::as_str).collect();
@@ -269,6 +312,29 @@ pub fn waveforms_from_files(
 ///         `libsamplerate <http://www.mega-nerd.com/SRC/>`_ at its
 ///         highest-quality setting.
 ///
+///     normalize_mode(int, optional): Set this to scale the decoded
+///         audio to a target level after every other transformation.
+///         The :py:mod:`babycat.normalize_mode` submodule contains
+///         the available choices. By default, the audio is not scaled.
+///
+///     normalize_target(float, optional): The level to normalize to.
+///         This is in dBFS for peak normalization and in LUFS for
+///         ITU-R BS.1770 loudness normalization.
+///
+///     normalize_true_peak_ceiling_dbtp(float, optional): The highest
+///         true peak, in dBTP, that loudness normalization may produce.
+///         If reaching ``normalize_target`` would exceed this ceiling,
+///         less gain is applied. Defaults to 0 dBTP.
+///
+///     trim_silence(bool, optional): Set this to ``True`` to remove
+///         silence from the start and end of the audio, after
+///         ``start_time_milliseconds`` and ``end_time_milliseconds``
+///         are applied and before any padding.
+///
+///     trim_silence_threshold_dbfs(float, optional): The level, in dBFS,
+///         at or below which every channel has to be for a frame
+///         to count as silence. Defaults to -60 dBFS.
+///
 ///     decoding_backend(int, optional): Sets the audio decoding
 ///         backend to use. Defaults to the Symphonia backend.
 ///
@@ -298,6 +364,11 @@ pub fn waveforms_from_files(
     repeat_pad_ending = false,
     resample_mode = 0,
     decoding_backend = 0,
+    normalize_mode = 0,
+    normalize_target = "0.0",
+    normalize_true_peak_ceiling_dbtp = "0.0",
+    trim_silence = false,
+    trim_silence_threshold_dbfs = "-60.0",
     num_workers = 0
 )]
 #[pyo3(text_signature = "(
@@ -311,6 +382,11 @@ pub fn waveforms_from_files(
     repeat_pad_ending = False,
     resample_mode = 0,
     decoding_backend = 0,
+    normalize_mode = 0,
+    normalize_target = 0.0,
+    normalize_true_peak_ceiling_dbtp = 0.0,
+    trim_silence = False,
+    trim_silence_threshold_dbfs = -60.0,
     num_workers = 0,
 )")]
 #[allow(clippy::too_many_arguments)]
@@ -327,6 +403,11 @@ pub fn waveforms_from_files_into_numpys(
     repeat_pad_ending: bool,
     resample_mode: u32,
     decoding_backend: u32,
+    normalize_mode: u32,
+    normalize_target: f32,
+    normalize_true_peak_ceiling_dbtp: f32,
+    trim_silence: bool,
+    trim_silence_threshold_dbfs: f32,
     num_workers: usize,
 ) -> Vec<crate::frontends::python::numpy_named_result::NumPyNamedResult> {
     let waveform_named_results = py.allow_threads(move || {
@@ -340,6 +421,11 @@ pub fn waveforms_from_files_into_numpys(
             repeat_pad_ending,
             resample_mode,
             decoding_backend,
+            normalize_mode,
+            normalize_target,
+            normalize_true_peak_ceiling_dbtp,
+            trim_silence,
+            trim_silence_threshold_dbfs,
         };
         let batch_args = crate::backend::BatchArgs { num_workers };
         let filenames_ref: Vec<&str> = filenames.iter().map(String::as_str).collect();
@@ -417,6 +503,29 @@ pub fn waveforms_from_files_into_numpys(
 ///         `libsamplerate <http://www.mega-nerd.com/SRC/>`_ at its
 ///         highest-quality setting.
 ///
+///     normalize_mode(int, optional): Set this to scale the decoded
+///         audio to a target level after every other transformation.
+///         The :py:mod:`babycat.normalize_mode` submodule contains
+///         the available choices. By default, the audio is not scaled.
+///
+///     normalize_target(float, optional): The level to normalize to.
+///         This is in dBFS for peak normalization and in LUFS for
+///         ITU-R BS.1770 loudness normalization.
+///
+///     normalize_true_peak_ceiling_dbtp(float, optional): The highest
+///         true peak, in dBTP, that loudness normalization may produce.
+///         If reaching ``normalize_target`` would exceed this ceiling,
+///         less gain is applied. Defaults to 0 dBTP.
+///
+///     trim_silence(bool, optional): Set this to ``True`` to remove
+///         silence from the start and end of the audio, after
+///         ``start_time_milliseconds`` and ``end_time_milliseconds``
+///         are applied and before any padding.
+///
+///     trim_silence_threshold_dbfs(float, optional): The level, in dBFS,
+///         at or below which every channel has to be for a frame
+///         to count as silence. Defaults to -60 dBFS.
+///
 ///     decoding_backend(int, optional): Sets the audio decoding
 ///         backend to use. Defaults to the Symphonia backend.
 ///
@@ -447,6 +556,11 @@ pub fn waveforms_from_files_into_numpys(
     repeat_pad_ending = false,
     resample_mode = 0,
     decoding_backend = 0,
+    normalize_mode = 0,
+    normalize_target = "0.0",
+    normalize_true_peak_ceiling_dbtp = "0.0",
+    trim_silence = false,
+    trim_silence_threshold_dbfs = "-60.0",
     num_workers = 0
 )]
 #[pyo3(text_signature = "(
@@ -460,6 +574,11 @@ pub fn waveforms_from_files_into_numpys(
     repeat_pad_ending = False,
     resample_mode = 0,
     decoding_backend = 0,
+    normalize_mode = 0,
+    normalize_target = 0.0,
+    normalize_true_peak_ceiling_dbtp = 0.0,
+    trim_silence = False,
+    trim_silence_threshold_dbfs = -60.0,
     num_workers = 0,
 )")]
 #[allow(clippy::too_many_arguments)]
@@ -476,6 +595,11 @@ pub fn waveforms_from_files_into_numpys_unwrapped(
     repeat_pad_ending: bool,
     resample_mode: u32,
     decoding_backend: u32,
+    normalize_mode: u32,
+    normalize_target: f32,
+    normalize_true_peak_ceiling_dbtp: f32,
+    trim_silence: bool,
+    trim_silence_threshold_dbfs: f32,
     num_workers: usize,
 ) -> Vec<PyArraySamples> {
     let waveforms: Vec<Waveform> = py.allow_threads(move || {
@@ -489,6 +613,11 @@ pub fn waveforms_from_files_into_numpys_unwrapped(
             repeat_pad_ending,
             resample_mode,
             decoding_backend,
+            normalize_mode,
+            normalize_target,
+            normalize_true_peak_ceiling_dbtp,
+            trim_silence,
+            trim_silence_threshold_dbfs,
         };
         let thread_pool: rayon::ThreadPool = rayon::ThreadPoolBuilder::new()
             .num_threads(num_workers)
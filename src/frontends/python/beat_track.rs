@@ -0,0 +1,81 @@
+use pyo3::prelude::*;
+
+/// The tempo and beats of a :py:class:`~babycat.Waveform`.
+///
+#[pyclass(module = "babycat")]
+#[derive(Clone, Debug)]
+pub struct BeatTrack {
+    /// The frame rate of the analyzed audio.
+    #[pyo3(get)]
+    pub frame_rate_hz: u32,
+    /// The estimated tempo in beats per minute, or ``0.0`` if the
+    /// audio has no onsets.
+    #[pyo3(get)]
+    pub bpm: f32,
+    /// How periodic the onsets are at :py:attr:`bpm`, from 0 to 1.
+    #[pyo3(get)]
+    pub confidence: f32,
+    /// A :py:class:`list` of the frame index of every beat.
+    #[pyo3(get)]
+    pub beat_frame_idxs: Vec<usize>,
+    /// A :py:class:`list` of the millisecond timestamp of every beat.
+    #[pyo3(get)]
+    pub beat_milliseconds: Vec<usize>,
+}
+
+#[pymethods]
+impl BeatTrack {
+    /// Returns the tempo and beats as a JSON :py:class:`str`.
+    #[pyo3(text_signature = "()")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&crate::backend::beat::BeatTrack::from(self.clone())).unwrap()
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("{}", self))
+    }
+}
+
+impl From<crate::backend::beat::BeatTrack> for BeatTrack {
+    fn from(inner: crate::backend::beat::BeatTrack) -> Self {
+        Self {
+            frame_rate_hz: inner.frame_rate_hz,
+            bpm: inner.bpm,
+            confidence: inner.confidence,
+            beat_frame_idxs: inner.beats.iter().map(|beat| beat.frame_idx).collect(),
+            beat_milliseconds: inner.beats.iter().map(|beat| beat.milliseconds).collect(),
+        }
+    }
+}
+
+impl From<BeatTrack> for crate::backend::beat::BeatTrack {
+    fn from(track: BeatTrack) -> Self {
+        Self {
+            frame_rate_hz: track.frame_rate_hz,
+            bpm: track.bpm,
+            confidence: track.confidence,
+            beats: track
+                .beat_frame_idxs
+                .iter()
+                .zip(&track.beat_milliseconds)
+                .map(|(&frame_idx, &milliseconds)| crate::backend::beat::Beat {
+                    frame_idx,
+                    milliseconds,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for BeatTrack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<babycat.BeatTrack: bpm={:.1} confidence={:.2} {} beats>",
+            self.bpm,
+            self.confidence,
+            self.beat_frame_idxs.len(),
+        )
+    }
+}
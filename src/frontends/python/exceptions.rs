@@ -21,6 +21,25 @@ create_exception!(
     CannotSetZeroPadEndingAndRepeatPadEnding,
     BabycatError
 );
+create_exception!(babycat, CannotParseGainEnvelope, BabycatError);
+create_exception!(babycat, WrongFilterFrequency, BabycatError);
+create_exception!(babycat, WrongFilterOrder, BabycatError);
+create_exception!(babycat, WrongFilterQ, BabycatError);
+create_exception!(babycat, WrongFftSize, BabycatError);
+create_exception!(babycat, WrongHopLength, BabycatError);
+create_exception!(babycat, WrongStftShape, BabycatError);
+create_exception!(babycat, WrongNumFeatures, BabycatError);
+create_exception!(babycat, WrongDeltaWidth, BabycatError);
+create_exception!(babycat, WrongDeltaOrder, BabycatError);
+create_exception!(babycat, WrongBinsPerOctave, BabycatError);
+create_exception!(babycat, WrongTempo, BabycatError);
+create_exception!(babycat, WrongFrameLength, BabycatError);
+create_exception!(babycat, WrongFramesPerBucket, BabycatError);
+create_exception!(babycat, WrongNumBuckets, BabycatError);
+create_exception!(babycat, CannotParsePeaks, BabycatError);
+create_exception!(babycat, WrongImageSize, BabycatError);
+create_exception!(babycat, WrongDecibelRange, BabycatError);
+create_exception!(babycat, FrameRateTooLow, BabycatError);
 //
 // Decoding errors
 create_exception!(babycat, NoSuitableAudioStreams, BabycatError);
@@ -51,6 +70,26 @@ create_exception!(
     CannotAppendSourcesWithDifferentFrameRates,
     SourceError
 );
+create_exception!(
+    babycat,
+    CannotMixSourcesWithDifferentNumChannels,
+    SourceError
+);
+create_exception!(
+    babycat,
+    CannotMixSourcesWithDifferentFrameRates,
+    SourceError
+);
+create_exception!(babycat, CannotPanWithoutTwoChannels, SourceError);
+create_exception!(babycat, CannotConvolveWithDifferentFrameRates, SourceError);
+create_exception!(
+    babycat,
+    CannotConvolveWithImpulseResponseChannels,
+    SourceError
+);
+create_exception!(babycat, CannotAlignWithDifferentFrameRates, SourceError);
+create_exception!(babycat, CannotAlignWithoutWaveforms, SourceError);
+create_exception!(babycat, CannotStackTooManyChannels, SourceError);
 
 impl std::convert::From<Error> for PyErr {
     fn from(err: Error) -> PyErr {
@@ -75,6 +114,38 @@ impl std::convert::From<Error> for PyErr {
                 CannotSetZeroPadEndingAndRepeatPadEnding::new_err(err.to_string())
             }
 
+            Error::CannotParseGainEnvelope(..) => CannotParseGainEnvelope::new_err(err.to_string()),
+
+            Error::WrongFilterFrequency(..) => WrongFilterFrequency::new_err(err.to_string()),
+
+            Error::WrongFilterOrder(..) => WrongFilterOrder::new_err(err.to_string()),
+
+            Error::WrongFilterQ(..) => WrongFilterQ::new_err(err.to_string()),
+
+            Error::WrongFftSize(..) => WrongFftSize::new_err(err.to_string()),
+
+            Error::WrongHopLength(..) => WrongHopLength::new_err(err.to_string()),
+
+            Error::WrongStftShape(..) => WrongStftShape::new_err(err.to_string()),
+
+            Error::WrongNumFeatures(..) => WrongNumFeatures::new_err(err.to_string()),
+
+            Error::WrongDeltaWidth(..) => WrongDeltaWidth::new_err(err.to_string()),
+
+            Error::WrongDeltaOrder(..) => WrongDeltaOrder::new_err(err.to_string()),
+
+            Error::WrongBinsPerOctave(..) => WrongBinsPerOctave::new_err(err.to_string()),
+
+            Error::WrongTempo(..) => WrongTempo::new_err(err.to_string()),
+
+            Error::WrongFrameLength(..) => WrongFrameLength::new_err(err.to_string()),
+            Error::WrongFramesPerBucket(..) => WrongFramesPerBucket::new_err(err.to_string()),
+            Error::WrongNumBuckets(..) => WrongNumBuckets::new_err(err.to_string()),
+            Error::CannotParsePeaks(..) => CannotParsePeaks::new_err(err.to_string()),
+            Error::WrongImageSize(..) => WrongImageSize::new_err(err.to_string()),
+            Error::WrongDecibelRange(..) => WrongDecibelRange::new_err(err.to_string()),
+            Error::FrameRateTooLow(..) => FrameRateTooLow::new_err(err.to_string()),
+
             Error::UnknownDecodingBackend(..) => UnknownDecodingBackend::new_err(err.to_string()),
 
             Error::NoSuitableAudioStreams(..) => NoSuitableAudioStreams::new_err(err.to_string()),
@@ -113,6 +184,30 @@ impl std::convert::From<Error> for PyErr {
             Error::CannotAppendSourcesWithDifferentFrameRates(..) => {
                 CannotAppendSourcesWithDifferentFrameRates::new_err(err.to_string())
             }
+            Error::CannotMixSourcesWithDifferentNumChannels(..) => {
+                CannotMixSourcesWithDifferentNumChannels::new_err(err.to_string())
+            }
+            Error::CannotMixSourcesWithDifferentFrameRates(..) => {
+                CannotMixSourcesWithDifferentFrameRates::new_err(err.to_string())
+            }
+            Error::CannotPanWithoutTwoChannels(..) => {
+                CannotPanWithoutTwoChannels::new_err(err.to_string())
+            }
+            Error::CannotConvolveWithDifferentFrameRates(..) => {
+                CannotConvolveWithDifferentFrameRates::new_err(err.to_string())
+            }
+            Error::CannotConvolveWithImpulseResponseChannels(..) => {
+                CannotConvolveWithImpulseResponseChannels::new_err(err.to_string())
+            }
+            Error::CannotAlignWithDifferentFrameRates(..) => {
+                CannotAlignWithDifferentFrameRates::new_err(err.to_string())
+            }
+            Error::CannotAlignWithoutWaveforms => {
+                CannotAlignWithoutWaveforms::new_err(err.to_string())
+            }
+            Error::CannotStackTooManyChannels(..) => {
+                CannotStackTooManyChannels::new_err(err.to_string())
+            }
         }
     }
 }
@@ -232,6 +327,158 @@ pub fn make_exceptions_submodule(py: Python) -> PyResult<&PyModule> {
         cannot_set_zero_pad_ending_and_repeat_pad_ending,
     )?;
 
+    let cannot_parse_gain_envelope = py.get_type::<CannotParseGainEnvelope>();
+    cannot_parse_gain_envelope.setattr("__module__", "babycat.exceptions")?;
+    cannot_parse_gain_envelope.setattr(
+        "__doc__",
+        "Raised when a gain envelope could not be parsed from JSON.",
+    )?;
+    exceptions_submodule.add("CannotParseGainEnvelope", cannot_parse_gain_envelope)?;
+
+    let wrong_filter_frequency = py.get_type::<WrongFilterFrequency>();
+    wrong_filter_frequency.setattr("__module__", "babycat.exceptions")?;
+    wrong_filter_frequency.setattr(
+        "__doc__",
+        "Raised when a filter frequency is not between 0 hz and the Nyquist frequency.",
+    )?;
+    exceptions_submodule.add("WrongFilterFrequency", wrong_filter_frequency)?;
+
+    let wrong_filter_order = py.get_type::<WrongFilterOrder>();
+    wrong_filter_order.setattr("__module__", "babycat.exceptions")?;
+    wrong_filter_order.setattr(
+        "__doc__",
+        "Raised when a filter order is not supported by the requested filter design.",
+    )?;
+    exceptions_submodule.add("WrongFilterOrder", wrong_filter_order)?;
+
+    let wrong_filter_q = py.get_type::<WrongFilterQ>();
+    wrong_filter_q.setattr("__module__", "babycat.exceptions")?;
+    wrong_filter_q.setattr(
+        "__doc__",
+        "Raised when a filter Q factor is not a positive, finite number.",
+    )?;
+    exceptions_submodule.add("WrongFilterQ", wrong_filter_q)?;
+
+    let wrong_fft_size = py.get_type::<WrongFftSize>();
+    wrong_fft_size.setattr("__module__", "babycat.exceptions")?;
+    wrong_fft_size.setattr(
+        "__doc__",
+        "Raised when an FFT size is zero or shorter than the window length.",
+    )?;
+    exceptions_submodule.add("WrongFftSize", wrong_fft_size)?;
+
+    let wrong_hop_length = py.get_type::<WrongHopLength>();
+    wrong_hop_length.setattr("__module__", "babycat.exceptions")?;
+    wrong_hop_length.setattr(
+        "__doc__",
+        "Raised when a short-time Fourier transform hop length is zero.",
+    )?;
+    exceptions_submodule.add("WrongHopLength", wrong_hop_length)?;
+
+    let wrong_stft_shape = py.get_type::<WrongStftShape>();
+    wrong_stft_shape.setattr("__module__", "babycat.exceptions")?;
+    wrong_stft_shape.setattr(
+        "__doc__",
+        "Raised when a short-time Fourier transform array does not have the expected shape.",
+    )?;
+    exceptions_submodule.add("WrongStftShape", wrong_stft_shape)?;
+
+    let wrong_num_features = py.get_type::<WrongNumFeatures>();
+    wrong_num_features.setattr("__module__", "babycat.exceptions")?;
+    wrong_num_features.setattr(
+        "__doc__",
+        "Raised when asking for zero features, or for more features than there are values to compute them from.",
+    )?;
+    exceptions_submodule.add("WrongNumFeatures", wrong_num_features)?;
+
+    let wrong_delta_width = py.get_type::<WrongDeltaWidth>();
+    wrong_delta_width.setattr("__module__", "babycat.exceptions")?;
+    wrong_delta_width.setattr(
+        "__doc__",
+        "Raised when a delta width is even, smaller than 3, or longer than the number of time steps.",
+    )?;
+    exceptions_submodule.add("WrongDeltaWidth", wrong_delta_width)?;
+
+    let wrong_delta_order = py.get_type::<WrongDeltaOrder>();
+    wrong_delta_order.setattr("__module__", "babycat.exceptions")?;
+    wrong_delta_order.setattr(
+        "__doc__",
+        "Raised when a delta order is zero or not smaller than the delta width.",
+    )?;
+    exceptions_submodule.add("WrongDeltaOrder", wrong_delta_order)?;
+
+    let wrong_bins_per_octave = py.get_type::<WrongBinsPerOctave>();
+    wrong_bins_per_octave.setattr("__module__", "babycat.exceptions")?;
+    wrong_bins_per_octave.setattr(
+        "__doc__",
+        "Raised when a constant-Q transform has zero bins per octave.",
+    )?;
+    exceptions_submodule.add("WrongBinsPerOctave", wrong_bins_per_octave)?;
+
+    let wrong_tempo = py.get_type::<WrongTempo>();
+    wrong_tempo.setattr("__module__", "babycat.exceptions")?;
+    wrong_tempo.setattr(
+        "__doc__",
+        "Raised when a tempo range is empty or not positive, or when the starting tempo is outside of it.",
+    )?;
+    exceptions_submodule.add("WrongTempo", wrong_tempo)?;
+
+    let wrong_frame_length = py.get_type::<WrongFrameLength>();
+    wrong_frame_length.setattr("__module__", "babycat.exceptions")?;
+    wrong_frame_length.setattr(
+        "__doc__",
+        "Raised when a pitch tracking frame is too short to hold two periods of the lowest pitch.",
+    )?;
+    exceptions_submodule.add("WrongFrameLength", wrong_frame_length)?;
+
+    let wrong_frames_per_bucket = py.get_type::<WrongFramesPerBucket>();
+    wrong_frames_per_bucket.setattr("__module__", "babycat.exceptions")?;
+    wrong_frames_per_bucket.setattr(
+        "__doc__",
+        "Raised when asking for peak summary buckets of zero frames.",
+    )?;
+    exceptions_submodule.add("WrongFramesPerBucket", wrong_frames_per_bucket)?;
+
+    let wrong_num_buckets = py.get_type::<WrongNumBuckets>();
+    wrong_num_buckets.setattr("__module__", "babycat.exceptions")?;
+    wrong_num_buckets.setattr(
+        "__doc__",
+        "Raised when asking for a peak summary with zero buckets.",
+    )?;
+    exceptions_submodule.add("WrongNumBuckets", wrong_num_buckets)?;
+
+    let cannot_parse_peaks = py.get_type::<CannotParsePeaks>();
+    cannot_parse_peaks.setattr("__module__", "babycat.exceptions")?;
+    cannot_parse_peaks.setattr(
+        "__doc__",
+        "Raised when a peak summary could not be read from its binary format.",
+    )?;
+    exceptions_submodule.add("CannotParsePeaks", cannot_parse_peaks)?;
+
+    let wrong_image_size = py.get_type::<WrongImageSize>();
+    wrong_image_size.setattr("__module__", "babycat.exceptions")?;
+    wrong_image_size.setattr(
+        "__doc__",
+        "Raised when asking for an image that is zero pixels wide or tall, or more than 8192 pixels wide or tall.",
+    )?;
+    exceptions_submodule.add("WrongImageSize", wrong_image_size)?;
+
+    let wrong_decibel_range = py.get_type::<WrongDecibelRange>();
+    wrong_decibel_range.setattr("__module__", "babycat.exceptions")?;
+    wrong_decibel_range.setattr(
+        "__doc__",
+        "Raised when the lowest level of a rendered decibel range is not below the highest level.",
+    )?;
+    exceptions_submodule.add("WrongDecibelRange", wrong_decibel_range)?;
+
+    let frame_rate_too_low = py.get_type::<FrameRateTooLow>();
+    frame_rate_too_low.setattr("__module__", "babycat.exceptions")?;
+    frame_rate_too_low.setattr(
+        "__doc__",
+        "Raised when audio has too low a frame rate to hold the frequencies being measured.",
+    )?;
+    exceptions_submodule.add("FrameRateTooLow", frame_rate_too_low)?;
+
     let unknown_input_encoding = py.get_type::<UnknownInputEncoding>();
     unknown_input_encoding.setattr("__module__", "babycat.exceptions")?;
     unknown_input_encoding.setattr(
@@ -316,5 +563,95 @@ pub fn make_exceptions_submodule(py: Python) -> PyResult<&PyModule> {
         cannot_append_sources_with_different_frame_rates,
     )?;
 
+    let cannot_mix_sources_with_different_num_channels =
+        py.get_type::<CannotMixSourcesWithDifferentNumChannels>();
+    cannot_mix_sources_with_different_num_channels.setattr("__module__", "babycat.exceptions")?;
+    cannot_mix_sources_with_different_num_channels.setattr(
+        "__doc__",
+        "Raised when we try to mix audio sources with a different number of channels.",
+    )?;
+    exceptions_submodule.add(
+        "CannotMixSourcesWithDifferentNumChannels",
+        cannot_mix_sources_with_different_num_channels,
+    )?;
+
+    let cannot_mix_sources_with_different_frame_rates =
+        py.get_type::<CannotMixSourcesWithDifferentFrameRates>();
+    cannot_mix_sources_with_different_frame_rates.setattr("__module__", "babycat.exceptions")?;
+    cannot_mix_sources_with_different_frame_rates.setattr(
+        "__doc__",
+        "Raised when we try to mix audio sources with different frame rates.",
+    )?;
+    exceptions_submodule.add(
+        "CannotMixSourcesWithDifferentFrameRates",
+        cannot_mix_sources_with_different_frame_rates,
+    )?;
+
+    let cannot_pan_without_two_channels = py.get_type::<CannotPanWithoutTwoChannels>();
+    cannot_pan_without_two_channels.setattr("__module__", "babycat.exceptions")?;
+    cannot_pan_without_two_channels.setattr(
+        "__doc__",
+        "Raised when we try to pan audio that does not have exactly two channels.",
+    )?;
+    exceptions_submodule.add(
+        "CannotPanWithoutTwoChannels",
+        cannot_pan_without_two_channels,
+    )?;
+
+    let cannot_convolve_with_different_frame_rates =
+        py.get_type::<CannotConvolveWithDifferentFrameRates>();
+    cannot_convolve_with_different_frame_rates.setattr("__module__", "babycat.exceptions")?;
+    cannot_convolve_with_different_frame_rates.setattr(
+        "__doc__",
+        "Raised when we try to convolve audio with an impulse response that has a different frame rate.",
+    )?;
+    exceptions_submodule.add(
+        "CannotConvolveWithDifferentFrameRates",
+        cannot_convolve_with_different_frame_rates,
+    )?;
+
+    let cannot_convolve_with_impulse_response_channels =
+        py.get_type::<CannotConvolveWithImpulseResponseChannels>();
+    cannot_convolve_with_impulse_response_channels.setattr("__module__", "babycat.exceptions")?;
+    cannot_convolve_with_impulse_response_channels.setattr(
+        "__doc__",
+        "Raised when we try to convolve audio with an impulse response that has an unsupported number of channels.",
+    )?;
+    exceptions_submodule.add(
+        "CannotConvolveWithImpulseResponseChannels",
+        cannot_convolve_with_impulse_response_channels,
+    )?;
+
+    let cannot_align_with_different_frame_rates =
+        py.get_type::<CannotAlignWithDifferentFrameRates>();
+    cannot_align_with_different_frame_rates.setattr("__module__", "babycat.exceptions")?;
+    cannot_align_with_different_frame_rates.setattr(
+        "__doc__",
+        "Raised when we try to line up waveforms that have different frame rates.",
+    )?;
+    exceptions_submodule.add(
+        "CannotAlignWithDifferentFrameRates",
+        cannot_align_with_different_frame_rates,
+    )?;
+
+    let cannot_align_without_waveforms = py.get_type::<CannotAlignWithoutWaveforms>();
+    cannot_align_without_waveforms.setattr("__module__", "babycat.exceptions")?;
+    cannot_align_without_waveforms.setattr(
+        "__doc__",
+        "Raised when we try to line up and stack an empty list of waveforms.",
+    )?;
+    exceptions_submodule.add(
+        "CannotAlignWithoutWaveforms",
+        cannot_align_without_waveforms,
+    )?;
+
+    let cannot_stack_too_many_channels = py.get_type::<CannotStackTooManyChannels>();
+    cannot_stack_too_many_channels.setattr("__module__", "babycat.exceptions")?;
+    cannot_stack_too_many_channels.setattr(
+        "__doc__",
+        "Raised when stacking waveforms would give more than 65535 channels.",
+    )?;
+    exceptions_submodule.add("CannotStackTooManyChannels", cannot_stack_too_many_channels)?;
+
     Ok(exceptions_submodule)
 }
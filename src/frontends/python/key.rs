@@ -0,0 +1,89 @@
+use pyo3::prelude::*;
+
+use crate::backend::key::Mode;
+
+/// The estimated musical key of a :py:class:`~babycat.Waveform`.
+///
+#[pyclass(module = "babycat")]
+#[derive(Clone, Debug)]
+pub struct Key {
+    /// The pitch class of the tonic, with C as ``0``, C# as ``1``,
+    /// and so on up to B as ``11``.
+    #[pyo3(get)]
+    pub tonic: usize,
+    /// Either ``"major"`` or ``"minor"``.
+    #[pyo3(get)]
+    pub mode: String,
+    /// The name of the key, such as ``"C major"`` or ``"F# minor"``.
+    #[pyo3(get)]
+    pub name: String,
+    /// The key in Camelot notation, such as ``"8B"`` for C major or
+    /// ``"8A"`` for A minor.
+    #[pyo3(get)]
+    pub camelot: String,
+    /// The correlation of the audio with the chosen key, from 0 to 1.
+    #[pyo3(get)]
+    pub confidence: f32,
+    /// A :py:class:`list` of the correlation of the audio with every key,
+    /// from -1 to 1. The first 12 values are the major keys and the last
+    /// 12 are the minor keys, each starting on C.
+    #[pyo3(get)]
+    pub correlations: Vec<f32>,
+}
+
+#[pymethods]
+impl Key {
+    /// Returns the key as a JSON :py:class:`str`.
+    #[pyo3(text_signature = "()")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&crate::backend::key::Key::from(self.clone())).unwrap()
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("{}", self))
+    }
+}
+
+impl From<crate::backend::key::Key> for Key {
+    fn from(inner: crate::backend::key::Key) -> Self {
+        Self {
+            tonic: inner.tonic,
+            mode: match inner.mode {
+                Mode::Major => "major".to_owned(),
+                Mode::Minor => "minor".to_owned(),
+            },
+            name: inner.name,
+            camelot: inner.camelot,
+            confidence: inner.confidence,
+            correlations: inner.correlations,
+        }
+    }
+}
+
+impl From<Key> for crate::backend::key::Key {
+    fn from(key: Key) -> Self {
+        Self {
+            tonic: key.tonic,
+            mode: if key.mode == "minor" {
+                Mode::Minor
+            } else {
+                Mode::Major
+            },
+            name: key.name,
+            camelot: key.camelot,
+            confidence: key.confidence,
+            correlations: key.correlations,
+        }
+    }
+}
+
+impl std::fmt::Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<babycat.Key: {} ({}) confidence={:.2}>",
+            self.name, self.camelot, self.confidence,
+        )
+    }
+}
@@ -0,0 +1,110 @@
+use pyo3::prelude::*;
+
+/// EBU R128 loudness and peak measurements for a :py:class:`~babycat.Waveform`.
+///
+/// Levels are never reported below -144, so silent channels
+/// and windows read as -144 instead of negative infinity.
+///
+#[pyclass(module = "babycat")]
+#[derive(Clone, Debug)]
+pub struct LoudnessReport {
+    /// The frame rate of the measured audio.
+    #[pyo3(get)]
+    pub frame_rate_hz: u32,
+    /// The number of channels in the measured audio.
+    #[pyo3(get)]
+    pub num_channels: u16,
+    /// The number of frames in the measured audio.
+    #[pyo3(get)]
+    pub num_frames: usize,
+    /// The gated integrated loudness in LUFS... or ``None`` if the audio
+    /// is shorter than 400 milliseconds or entirely below -70 LUFS.
+    #[pyo3(get)]
+    pub integrated_lufs: Option<f32>,
+    /// The loudness range in LU... or ``None`` if the audio is shorter
+    /// than 3 seconds or entirely below -70 LUFS.
+    #[pyo3(get)]
+    pub loudness_range_lu: Option<f32>,
+    /// A :py:class:`list` of the loudness of every 400 millisecond window
+    /// in LUFS, advancing 100 milliseconds at a time.
+    #[pyo3(get)]
+    pub momentary_lufs: Vec<f32>,
+    /// A :py:class:`list` of the loudness of every 3 second window
+    /// in LUFS, advancing 100 milliseconds at a time.
+    #[pyo3(get)]
+    pub short_term_lufs: Vec<f32>,
+    /// A :py:class:`list` of the sample peak of each channel in dBFS.
+    #[pyo3(get)]
+    pub sample_peaks_dbfs: Vec<f32>,
+    /// A :py:class:`list` of the 4x oversampled true peak of each channel in dBTP.
+    #[pyo3(get)]
+    pub true_peaks_dbtp: Vec<f32>,
+}
+
+#[pymethods]
+impl LoudnessReport {
+    /// Returns this report as a JSON :py:class:`str`.
+    #[pyo3(text_signature = "()")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&crate::backend::loudness::LoudnessReport::from(
+            self.clone(),
+        ))
+        .unwrap()
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("{}", self))
+    }
+}
+
+impl From<crate::backend::loudness::LoudnessReport> for LoudnessReport {
+    fn from(inner: crate::backend::loudness::LoudnessReport) -> Self {
+        Self {
+            frame_rate_hz: inner.frame_rate_hz,
+            num_channels: inner.num_channels,
+            num_frames: inner.num_frames,
+            integrated_lufs: inner.integrated_lufs,
+            loudness_range_lu: inner.loudness_range_lu,
+            momentary_lufs: inner.momentary_lufs,
+            short_term_lufs: inner.short_term_lufs,
+            sample_peaks_dbfs: inner.sample_peaks_dbfs,
+            true_peaks_dbtp: inner.true_peaks_dbtp,
+        }
+    }
+}
+
+impl From<LoudnessReport> for crate::backend::loudness::LoudnessReport {
+    fn from(report: LoudnessReport) -> Self {
+        Self {
+            frame_rate_hz: report.frame_rate_hz,
+            num_channels: report.num_channels,
+            num_frames: report.num_frames,
+            integrated_lufs: report.integrated_lufs,
+            loudness_range_lu: report.loudness_range_lu,
+            momentary_lufs: report.momentary_lufs,
+            short_term_lufs: report.short_term_lufs,
+            sample_peaks_dbfs: report.sample_peaks_dbfs,
+            true_peaks_dbtp: report.true_peaks_dbtp,
+        }
+    }
+}
+
+impl std::fmt::Display for LoudnessReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format_level = |level: Option<f32>| match level {
+            Some(level) => format!("{:.1}", level),
+            None => "None".to_string(),
+        };
+        write!(
+            f,
+            "<babycat.LoudnessReport: integrated_lufs={} loudness_range_lu={} true_peak_dbtp={:.1}>",
+            format_level(self.integrated_lufs),
+            format_level(self.loudness_range_lu),
+            self.true_peaks_dbtp
+                .iter()
+                .copied()
+                .fold(crate::backend::loudness::MIN_REPORTED_LEVEL, f32::max),
+        )
+    }
+}
@@ -0,0 +1,35 @@
+use pyo3::prelude::*;
+
+use crate::backend::constants::{DEFAULT_MEL_SCALE, MEL_SCALE_HTK, MEL_SCALE_SLANEY};
+
+/// Creates the `babycat.mel_scale` submodule, which is used to
+/// store constants pointing to mel scales.
+pub fn make_mel_scale_submodule(py: Python) -> PyResult<&PyModule> {
+    let mel_scale_submodule = PyModule::new(py, "mel_scale")?;
+
+    mel_scale_submodule.setattr(
+        "__doc__",
+        "
+A Python submodule to hold constants representing different mel scales.
+
+Pass one of these constants as ``mel_scale`` to
+:py:meth:`babycat.Waveform.mel_spectrogram` or
+:py:meth:`babycat.Waveform.mfcc` to choose how the mel bands are spaced.
+
+- :py:attr:`DEFAULT_MEL_SCALE`: The same as :py:attr:`MEL_SCALE_SLANEY`.
+
+- :py:attr:`MEL_SCALE_SLANEY`: The mel scale from Slaney's Auditory
+  Toolbox, which is linear below 1000 hz and logarithmic above it.
+  This is the default in librosa.
+
+- :py:attr:`MEL_SCALE_HTK`: The mel scale from the Hidden Markov Model
+  Toolkit (HTK), which is ``2595 * log10(1 + hz / 700)``.
+
+",
+    )?;
+    mel_scale_submodule.setattr("DEFAULT_MEL_SCALE", DEFAULT_MEL_SCALE)?;
+    mel_scale_submodule.setattr("MEL_SCALE_SLANEY", MEL_SCALE_SLANEY)?;
+    mel_scale_submodule.setattr("MEL_SCALE_HTK", MEL_SCALE_HTK)?;
+
+    Ok(mel_scale_submodule)
+}
@@ -1,13 +1,24 @@
 use pyo3::prelude::*;
 
 pub mod batch;
+pub mod beat_track;
 pub mod build_info;
 pub mod decoding_backend;
 pub mod exceptions;
+pub mod key;
+pub mod loudness_report;
+pub mod mel_scale;
+pub mod normalize_mode;
 pub mod numpy_named_result;
+pub mod onset_function;
+pub mod pad_mode;
+pub mod peaks;
+pub mod pitch_method;
 pub mod resample_mode;
+pub mod stats_report;
 pub mod waveform;
 pub mod waveform_named_result;
+pub mod window_type;
 
 /// Module docstring first line
 ///
@@ -34,6 +45,30 @@ pub fn babycat(py: Python, m: &PyModule) -> PyResult<()> {
     let decoding_backend_submodule = decoding_backend::make_decoding_backend_submodule(py)?;
     m.add_submodule(decoding_backend_submodule)?;
 
+    // Configure the normalization mode submodule.
+    let normalize_mode_submodule = normalize_mode::make_normalize_mode_submodule(py)?;
+    m.add_submodule(normalize_mode_submodule)?;
+
+    // Configure the window type submodule.
+    let window_type_submodule = window_type::make_window_type_submodule(py)?;
+    m.add_submodule(window_type_submodule)?;
+
+    // Configure the padding mode submodule.
+    let pad_mode_submodule = pad_mode::make_pad_mode_submodule(py)?;
+    m.add_submodule(pad_mode_submodule)?;
+
+    // Configure the mel scale submodule.
+    let mel_scale_submodule = mel_scale::make_mel_scale_submodule(py)?;
+    m.add_submodule(mel_scale_submodule)?;
+
+    // Configure the onset function submodule.
+    let onset_function_submodule = onset_function::make_onset_function_submodule(py)?;
+    m.add_submodule(onset_function_submodule)?;
+
+    // Configure the pitch method submodule.
+    let pitch_method_submodule = pitch_method::make_pitch_method_submodule(py)?;
+    m.add_submodule(pitch_method_submodule)?;
+
     // Configure the Waveform class, which does most of the heavy lifting.
     m.add_class::<waveform::Waveform>()?;
 
@@ -43,6 +78,17 @@ pub fn babycat(py: Python, m: &PyModule) -> PyResult<()> {
 
     m.add_class::<numpy_named_result::NumPyNamedResult>()?;
 
+    m.add_class::<loudness_report::LoudnessReport>()?;
+
+    m.add_class::<peaks::Peaks>()?;
+
+    m.add_class::<stats_report::ChannelStats>()?;
+    m.add_class::<stats_report::StatsReport>()?;
+
+    m.add_class::<beat_track::BeatTrack>()?;
+
+    m.add_class::<key::Key>()?;
+
     // End of the module
     Ok(())
 }
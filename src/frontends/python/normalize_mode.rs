@@ -0,0 +1,50 @@
+use pyo3::prelude::*;
+
+use crate::backend::constants::{
+    DEFAULT_NORMALIZE_MODE, NORMALIZE_MODE_LOUDNESS, NORMALIZE_MODE_PEAK,
+};
+
+/// Creates the `babycat.normalize_mode` submodule, which is used to
+/// store constants pointing to normalization modes.
+pub fn make_normalize_mode_submodule(py: Python) -> PyResult<&PyModule> {
+    let normalize_mode_submodule = PyModule::new(py, "normalize_mode")?;
+
+    normalize_mode_submodule.setattr(
+        "__doc__",
+        "
+A Python submodule to hold constants representing different normalization modes.
+
+Babycat can scale audio to a target level while decoding it. Pass one of
+these constants as ``normalize_mode`` and the level as ``normalize_target``.
+
+- :py:attr:`DEFAULT_NORMALIZE_MODE`: Do not scale the audio.
+
+- :py:attr:`NORMALIZE_MODE_PEAK`: Scale the audio so that its loudest
+  sample is at ``normalize_target`` dBFS.
+
+- :py:attr:`NORMALIZE_MODE_LOUDNESS`: Scale the audio so that its
+  `ITU-R BS.1770 <https://www.itu.int/rec/R-REC-BS.1770>`_ integrated
+  loudness is at ``normalize_target`` LUFS. The gain is reduced if the
+  true peak would otherwise exceed ``normalize_true_peak_ceiling_dbtp``.
+
+Example:
+    **Decode a file at -16 LUFS with a -1 dBTP ceiling.**
+
+    >>> from babycat import Waveform
+    >>> from babycat.normalize_mode import *
+    >>>
+    >>> waveform = Waveform.from_file(
+    ...     \"audio-for-tests/circus-of-freaks/track.flac\",
+    ...     normalize_mode=NORMALIZE_MODE_LOUDNESS,
+    ...     normalize_target=-16.0,
+    ...     normalize_true_peak_ceiling_dbtp=-1.0,
+    ... )
+
+",
+    )?;
+    normalize_mode_submodule.setattr("DEFAULT_NORMALIZE_MODE", DEFAULT_NORMALIZE_MODE)?;
+    normalize_mode_submodule.setattr("NORMALIZE_MODE_PEAK", NORMALIZE_MODE_PEAK)?;
+    normalize_mode_submodule.setattr("NORMALIZE_MODE_LOUDNESS", NORMALIZE_MODE_LOUDNESS)?;
+
+    Ok(normalize_mode_submodule)
+}
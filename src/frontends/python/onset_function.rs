@@ -0,0 +1,65 @@
+use pyo3::prelude::*;
+
+use crate::backend::constants::{
+    DEFAULT_ONSET_FUNCTION, ONSET_FUNCTION_COMPLEX_DOMAIN, ONSET_FUNCTION_HIGH_FREQUENCY_CONTENT,
+    ONSET_FUNCTION_SPECTRAL_FLUX,
+};
+
+/// Creates the `babycat.onset_function` submodule, which is used to
+/// store constants pointing to onset detection functions.
+pub fn make_onset_function_submodule(py: Python) -> PyResult<&PyModule> {
+    let onset_function_submodule = PyModule::new(py, "onset_function")?;
+
+    onset_function_submodule.setattr(
+        "__doc__",
+        "
+A Python submodule to hold constants representing different onset detection functions.
+
+Pass one of these constants as ``onset_function`` to
+:py:meth:`babycat.Waveform.onset_strength`,
+:py:meth:`babycat.Waveform.onsets` or
+:py:meth:`babycat.Waveform.split_at_onsets` to choose how
+change in the audio is measured.
+
+- :py:attr:`DEFAULT_ONSET_FUNCTION`: The same as :py:attr:`ONSET_FUNCTION_SPECTRAL_FLUX`.
+
+- :py:attr:`ONSET_FUNCTION_SPECTRAL_FLUX`: How much louder a mel spectrogram
+  gets in decibels. This works well for most music.
+
+- :py:attr:`ONSET_FUNCTION_HIGH_FREQUENCY_CONTENT`: How much the spectrum
+  grows, with high frequencies counting the most. This favors percussive onsets.
+
+- :py:attr:`ONSET_FUNCTION_COMPLEX_DOMAIN`: How far the spectrum strays from
+  a prediction of its magnitude and phase. This also hears soft onsets,
+  such as a new note that is no louder than the one before it.
+
+Example:
+    **Find onsets from the high-frequency content.**
+
+    >>> from babycat import Waveform
+    >>> from babycat.onset_function import *
+    >>>
+    >>> waveform = Waveform.from_frames_of_silence(
+    ...     frame_rate_hz=44100,
+    ...     num_channels=2,
+    ...     num_frames=44100,
+    ... )
+    >>> waveform.onsets(onset_function=ONSET_FUNCTION_HIGH_FREQUENCY_CONTENT)
+    []
+
+",
+    )?;
+    onset_function_submodule.setattr("DEFAULT_ONSET_FUNCTION", DEFAULT_ONSET_FUNCTION)?;
+    onset_function_submodule
+        .setattr("ONSET_FUNCTION_SPECTRAL_FLUX", ONSET_FUNCTION_SPECTRAL_FLUX)?;
+    onset_function_submodule.setattr(
+        "ONSET_FUNCTION_HIGH_FREQUENCY_CONTENT",
+        ONSET_FUNCTION_HIGH_FREQUENCY_CONTENT,
+    )?;
+    onset_function_submodule.setattr(
+        "ONSET_FUNCTION_COMPLEX_DOMAIN",
+        ONSET_FUNCTION_COMPLEX_DOMAIN,
+    )?;
+
+    Ok(onset_function_submodule)
+}
@@ -0,0 +1,34 @@
+use pyo3::prelude::*;
+
+use crate::backend::constants::{DEFAULT_PAD_MODE, PAD_MODE_REFLECT, PAD_MODE_ZEROS};
+
+/// Creates the `babycat.pad_mode` submodule, which is used to
+/// store constants pointing to padding modes.
+pub fn make_pad_mode_submodule(py: Python) -> PyResult<&PyModule> {
+    let pad_mode_submodule = PyModule::new(py, "pad_mode")?;
+
+    pad_mode_submodule.setattr(
+        "__doc__",
+        "
+A Python submodule to hold constants representing different padding modes.
+
+When :py:meth:`babycat.Waveform.stft` is called with ``center=True``,
+the audio is padded on both sides so that every window is centered
+on its time step. Pass one of these constants as ``pad_mode`` to
+choose what the padding contains.
+
+- :py:attr:`DEFAULT_PAD_MODE`: The same as :py:attr:`PAD_MODE_REFLECT`.
+
+- :py:attr:`PAD_MODE_ZEROS`: Pad with silence.
+
+- :py:attr:`PAD_MODE_REFLECT`: Pad with a mirror image of the audio,
+  not repeating the first or last frame.
+
+",
+    )?;
+    pad_mode_submodule.setattr("DEFAULT_PAD_MODE", DEFAULT_PAD_MODE)?;
+    pad_mode_submodule.setattr("PAD_MODE_ZEROS", PAD_MODE_ZEROS)?;
+    pad_mode_submodule.setattr("PAD_MODE_REFLECT", PAD_MODE_REFLECT)?;
+
+    Ok(pad_mode_submodule)
+}
@@ -0,0 +1,143 @@
+use numpy::{IntoPyArray, PyArray2};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+/// The minimum, maximum and RMS of every channel of a
+/// :py:class:`~babycat.Waveform` over consecutive buckets of frames.
+///
+/// This is everything needed to draw a waveform at one bucket per
+/// pixel. The last bucket is shorter than the others when the number
+/// of frames is not a multiple of the bucket length.
+///
+#[pyclass(module = "babycat")]
+#[derive(Clone, Debug)]
+pub struct Peaks {
+    inner: crate::backend::peaks::Peaks,
+}
+
+impl From<crate::backend::peaks::Peaks> for Peaks {
+    fn from(inner: crate::backend::peaks::Peaks) -> Self {
+        Self { inner }
+    }
+}
+
+impl Peaks {
+    /// Turns channel-major values into a NumPy array with the shape
+    /// ``(channels, buckets)``.
+    ///
+    /// # Panics
+    /// This function panics if we cannot create a NumPy array of that shape.
+    fn to_pyarray(&self, py: Python<'_>, values: &[f32]) -> Py<PyArray2<f32>> {
+        values
+            .to_vec()
+            .into_pyarray(py)
+            .reshape([self.inner.num_channels() as usize, self.inner.num_buckets()])
+            .unwrap()
+            .into()
+    }
+}
+
+#[pymethods]
+impl Peaks {
+    /// Reads a summary from the compact binary format written by
+    /// :py:meth:`to_bytes`.
+    ///
+    /// Args:
+    ///     data(bytes): The binary summary.
+    ///
+    /// Returns:
+    ///     Peaks: The summary.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.CannotParsePeaks: Raised when ``data`` does
+    ///         not hold a summary in the binary format.
+    ///
+    #[staticmethod]
+    #[pyo3(text_signature = "(data)")]
+    pub fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Ok(crate::backend::peaks::Peaks::from_bytes(data)?.into())
+    }
+
+    /// Writes the summary in a compact binary format for caching.
+    ///
+    /// The format is a 28 byte header followed by the minimums, maximums
+    /// and RMS values as 16-bit integers, so values read back with
+    /// :py:meth:`from_bytes` are only accurate to within 1/32767.
+    ///
+    /// Returns:
+    ///     bytes: The binary summary.
+    ///
+    #[pyo3(text_signature = "()")]
+    pub fn to_bytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, &self.inner.to_bytes())
+    }
+
+    /// The frame rate of the summarized audio.
+    #[getter]
+    pub fn frame_rate_hz(&self) -> u32 {
+        self.inner.frame_rate_hz()
+    }
+
+    /// The number of channels.
+    #[getter]
+    pub fn num_channels(&self) -> u16 {
+        self.inner.num_channels()
+    }
+
+    /// The number of frames in the summarized audio.
+    #[getter]
+    pub fn num_frames(&self) -> usize {
+        self.inner.num_frames()
+    }
+
+    /// The number of frames in every bucket but the last.
+    #[getter]
+    pub fn frames_per_bucket(&self) -> usize {
+        self.inner.frames_per_bucket()
+    }
+
+    /// The number of buckets in every channel.
+    #[getter]
+    pub fn num_buckets(&self) -> usize {
+        self.inner.num_buckets()
+    }
+
+    /// A NumPy array of the smallest sample in every bucket, with the
+    /// shape ``(channels, buckets)``.
+    #[getter]
+    pub fn min(&self, py: Python<'_>) -> Py<PyArray2<f32>> {
+        self.to_pyarray(py, self.inner.min())
+    }
+
+    /// A NumPy array of the largest sample in every bucket, with the
+    /// shape ``(channels, buckets)``.
+    #[getter]
+    pub fn max(&self, py: Python<'_>) -> Py<PyArray2<f32>> {
+        self.to_pyarray(py, self.inner.max())
+    }
+
+    /// A NumPy array of the root mean square of every bucket, with the
+    /// shape ``(channels, buckets)``.
+    #[getter]
+    pub fn rms(&self, py: Python<'_>) -> Py<PyArray2<f32>> {
+        self.to_pyarray(py, self.inner.rms())
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("{}", self))
+    }
+}
+
+impl std::fmt::Display for Peaks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<babycat.Peaks: {} buckets of {} frames, {} channels, {} hz>",
+            self.inner.num_buckets(),
+            self.inner.frames_per_bucket(),
+            self.inner.num_channels(),
+            self.inner.frame_rate_hz(),
+        )
+    }
+}
@@ -0,0 +1,47 @@
+use pyo3::prelude::*;
+
+use crate::backend::constants::{DEFAULT_PITCH_METHOD, PITCH_METHOD_PYIN, PITCH_METHOD_YIN};
+
+/// Creates the `babycat.pitch_method` submodule, which is used to
+/// store constants pointing to pitch tracking methods.
+pub fn make_pitch_method_submodule(py: Python) -> PyResult<&PyModule> {
+    let pitch_method_submodule = PyModule::new(py, "pitch_method")?;
+
+    pitch_method_submodule.setattr(
+        "__doc__",
+        "
+A Python submodule to hold constants representing different pitch tracking methods.
+
+Pass one of these constants as ``method`` to
+:py:meth:`babycat.Waveform.pitch`.
+
+- :py:attr:`DEFAULT_PITCH_METHOD`: The same as :py:attr:`PITCH_METHOD_PYIN`.
+
+- :py:attr:`PITCH_METHOD_PYIN`: pYIN, which tries many thresholds and
+  smooths the pitch and voicing of every frame with a hidden Markov model.
+
+- :py:attr:`PITCH_METHOD_YIN`: YIN, which looks at every frame on its own.
+  It is faster than pYIN, but more likely to jump by an octave.
+
+Example:
+    **Track pitch with YIN.**
+
+    >>> from babycat import Waveform
+    >>> from babycat.pitch_method import *
+    >>>
+    >>> waveform = Waveform.from_frames_of_silence(
+    ...     frame_rate_hz=44100,
+    ...     num_channels=2,
+    ...     num_frames=44100,
+    ... )
+    >>> waveform.pitch(method=PITCH_METHOD_YIN).shape
+    (87,)
+
+",
+    )?;
+    pitch_method_submodule.setattr("DEFAULT_PITCH_METHOD", DEFAULT_PITCH_METHOD)?;
+    pitch_method_submodule.setattr("PITCH_METHOD_PYIN", PITCH_METHOD_PYIN)?;
+    pitch_method_submodule.setattr("PITCH_METHOD_YIN", PITCH_METHOD_YIN)?;
+
+    Ok(pitch_method_submodule)
+}
@@ -0,0 +1,172 @@
+use pyo3::prelude::*;
+
+/// Signal statistics for one channel of a :py:class:`~babycat.Waveform`.
+///
+/// Levels are never reported below -144, so silent channels read as
+/// -144 instead of negative infinity.
+///
+#[pyclass(module = "babycat")]
+#[derive(Clone, Debug)]
+pub struct ChannelStats {
+    /// The largest absolute sample value, in dBFS.
+    #[pyo3(get)]
+    pub peak_dbfs: f32,
+    /// The root mean square of the samples, in dBFS.
+    #[pyo3(get)]
+    pub rms_dbfs: f32,
+    /// The mean of the samples. Anything far from 0 means that the
+    /// channel has a DC offset.
+    #[pyo3(get)]
+    pub dc_offset: f32,
+    /// The ratio of the peak to the RMS level in decibels... or ``None``
+    /// if the channel is silent.
+    #[pyo3(get)]
+    pub crest_factor_db: Option<f32>,
+    /// The number of samples within one 16-bit step of full scale.
+    #[pyo3(get)]
+    pub num_clipped_samples: usize,
+    /// The percentage of samples at or below the silence threshold,
+    /// from 0 to 100.
+    #[pyo3(get)]
+    pub silence_percent: f32,
+}
+
+#[pymethods]
+impl ChannelStats {
+    #[allow(clippy::unnecessary_wraps)]
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("{}", self))
+    }
+}
+
+impl From<crate::backend::stats::ChannelStats> for ChannelStats {
+    fn from(inner: crate::backend::stats::ChannelStats) -> Self {
+        Self {
+            peak_dbfs: inner.peak_dbfs,
+            rms_dbfs: inner.rms_dbfs,
+            dc_offset: inner.dc_offset,
+            crest_factor_db: inner.crest_factor_db,
+            num_clipped_samples: inner.num_clipped_samples,
+            silence_percent: inner.silence_percent,
+        }
+    }
+}
+
+impl From<ChannelStats> for crate::backend::stats::ChannelStats {
+    fn from(stats: ChannelStats) -> Self {
+        Self {
+            peak_dbfs: stats.peak_dbfs,
+            rms_dbfs: stats.rms_dbfs,
+            dc_offset: stats.dc_offset,
+            crest_factor_db: stats.crest_factor_db,
+            num_clipped_samples: stats.num_clipped_samples,
+            silence_percent: stats.silence_percent,
+        }
+    }
+}
+
+impl std::fmt::Display for ChannelStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<babycat.ChannelStats: peak_dbfs={:.1} rms_dbfs={:.1} num_clipped_samples={}>",
+            self.peak_dbfs, self.rms_dbfs, self.num_clipped_samples,
+        )
+    }
+}
+
+/// Per-channel signal statistics and channel layout diagnostics for a
+/// :py:class:`~babycat.Waveform`.
+///
+#[pyclass(module = "babycat")]
+#[derive(Clone, Debug)]
+pub struct StatsReport {
+    /// The frame rate of the measured audio.
+    #[pyo3(get)]
+    pub frame_rate_hz: u32,
+    /// The number of channels in the measured audio.
+    #[pyo3(get)]
+    pub num_channels: u16,
+    /// The number of frames in the measured audio.
+    #[pyo3(get)]
+    pub num_frames: usize,
+    /// A :py:class:`list` of the :py:class:`ChannelStats` of every channel.
+    #[pyo3(get)]
+    pub channels: Vec<ChannelStats>,
+    /// A :py:class:`list` of every pair of channels that are copies of
+    /// each other, such as mono audio that was saved as stereo.
+    #[pyo3(get)]
+    pub identical_channels: Vec<(u16, u16)>,
+    /// A :py:class:`list` of every pair of channels where one is a
+    /// polarity-inverted copy of the other.
+    #[pyo3(get)]
+    pub inverted_channels: Vec<(u16, u16)>,
+    /// A :py:class:`list` of every channel whose peak is at or below the
+    /// silence threshold.
+    #[pyo3(get)]
+    pub silent_channels: Vec<u16>,
+    /// ``True`` if the audio is stereo with sound only in the left channel.
+    #[pyo3(get)]
+    pub left_only: bool,
+    /// ``True`` if the audio is stereo with sound only in the right channel.
+    #[pyo3(get)]
+    pub right_only: bool,
+}
+
+#[pymethods]
+impl StatsReport {
+    /// Returns this report as a JSON :py:class:`str`.
+    #[pyo3(text_signature = "()")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&crate::backend::stats::StatsReport::from(self.clone())).unwrap()
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("{}", self))
+    }
+}
+
+impl From<crate::backend::stats::StatsReport> for StatsReport {
+    fn from(inner: crate::backend::stats::StatsReport) -> Self {
+        let to_tuples = |pairs: Vec<[u16; 2]>| pairs.into_iter().map(|[a, b]| (a, b)).collect();
+        Self {
+            frame_rate_hz: inner.frame_rate_hz,
+            num_channels: inner.num_channels,
+            num_frames: inner.num_frames,
+            channels: inner.channels.into_iter().map(ChannelStats::from).collect(),
+            identical_channels: to_tuples(inner.identical_channels),
+            inverted_channels: to_tuples(inner.inverted_channels),
+            silent_channels: inner.silent_channels,
+            left_only: inner.left_only,
+            right_only: inner.right_only,
+        }
+    }
+}
+
+impl From<StatsReport> for crate::backend::stats::StatsReport {
+    fn from(report: StatsReport) -> Self {
+        let to_arrays = |pairs: Vec<(u16, u16)>| pairs.into_iter().map(|(a, b)| [a, b]).collect();
+        Self {
+            frame_rate_hz: report.frame_rate_hz,
+            num_channels: report.num_channels,
+            num_frames: report.num_frames,
+            channels: report.channels.into_iter().map(Into::into).collect(),
+            identical_channels: to_arrays(report.identical_channels),
+            inverted_channels: to_arrays(report.inverted_channels),
+            silent_channels: report.silent_channels,
+            left_only: report.left_only,
+            right_only: report.right_only,
+        }
+    }
+}
+
+impl std::fmt::Display for StatsReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<babycat.StatsReport: {} channels, silent_channels={:?} identical_channels={:?} inverted_channels={:?}>",
+            self.num_channels, self.silent_channels, self.identical_channels, self.inverted_channels,
+        )
+    }
+}
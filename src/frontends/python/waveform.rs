@@ -1,8 +1,20 @@
-use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+use numpy::{Complex32, Element, IntoPyArray, PyArray2, PyReadonlyArray2, PyReadonlyArrayDyn};
+use pyo3::exceptions::PyTypeError;
 use pyo3::prelude::*;
 use pyo3::types::PyByteArray;
 
+use crate::backend::align;
+use crate::backend::beat::BeatArgs;
+use crate::backend::onset::{self, OnsetArgs};
+use crate::backend::pitch::PitchArgs;
+use crate::backend::spectral::{CqtArgs, Features, MelArgs, MfccArgs, Stft, StftArgs};
+use crate::backend::Error;
 use crate::backend::Signal;
+use crate::frontends::python::beat_track::BeatTrack;
+use crate::frontends::python::key::Key;
+use crate::frontends::python::loudness_report::LoudnessReport;
+use crate::frontends::python::peaks::Peaks;
+use crate::frontends::python::stats_report::StatsReport;
 
 pub type PyArraySamples = Py<PyArray2<f32>>;
 
@@ -45,6 +57,54 @@ impl IntoPy<PyArraySamples> for crate::backend::Waveform {
     }
 }
 
+/// Turns spectral data with the shape `(channels, rows, time steps)` into
+/// a 3D NumPy array, or into a 2D NumPy array of one channel if
+/// `channel_idx` is given. The rows are frequency bins or features.
+///
+/// # Panics
+/// This function panics if we cannot create a NumPy array of the given shape.
+fn spectral_to_pyarray<T: Element>(
+    py: Python<'_>,
+    shape: (u16, usize, usize),
+    values: Vec<T>,
+    channel_idx: Option<u16>,
+) -> PyResult<PyObject> {
+    let (num_channels, num_bins, num_time_steps) = shape;
+    match channel_idx {
+        None => Ok(values
+            .into_pyarray(py)
+            .reshape([num_channels as usize, num_bins, num_time_steps])
+            .unwrap()
+            .to_object(py)),
+        Some(channel_idx) if channel_idx < num_channels => {
+            let len = num_bins * num_time_steps;
+            let start = channel_idx as usize * len;
+            Ok(values
+                .into_iter()
+                .skip(start)
+                .take(len)
+                .collect::<Vec<T>>()
+                .into_pyarray(py)
+                .reshape([num_bins, num_time_steps])
+                .unwrap()
+                .to_object(py))
+        }
+        Some(channel_idx) => Err(Error::WrongNumChannels(channel_idx + 1, num_channels).into()),
+    }
+}
+
+fn stft_shape(stft: &Stft) -> (u16, usize, usize) {
+    (stft.num_channels(), stft.num_bins(), stft.num_time_steps())
+}
+
+fn features_shape(features: &Features) -> (u16, usize, usize) {
+    (
+        features.num_channels(),
+        features.num_features(),
+        features.num_time_steps(),
+    )
+}
+
 impl std::fmt::Display for Waveform {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -335,6 +395,29 @@ impl Waveform {
     ///         `libsamplerate <http://www.mega-nerd.com/SRC/>`_ at its
     ///         highest-quality setting.
     ///
+    ///     normalize_mode(int, optional): Set this to scale the decoded
+    ///         audio to a target level after every other transformation.
+    ///         The :py:mod:`babycat.normalize_mode` submodule contains
+    ///         the available choices. By default, the audio is not scaled.
+    ///
+    ///     normalize_target(float, optional): The level to normalize to.
+    ///         This is in dBFS for peak normalization and in LUFS for
+    ///         ITU-R BS.1770 loudness normalization.
+    ///
+    ///     normalize_true_peak_ceiling_dbtp(float, optional): The highest
+    ///         true peak, in dBTP, that loudness normalization may produce.
+    ///         If reaching ``normalize_target`` would exceed this ceiling,
+    ///         less gain is applied. Defaults to 0 dBTP.
+    ///
+    ///     trim_silence(bool, optional): Set this to ``True`` to remove
+    ///         silence from the start and end of the audio, after
+    ///         ``start_time_milliseconds`` and ``end_time_milliseconds``
+    ///         are applied and before any padding.
+    ///
+    ///     trim_silence_threshold_dbfs(float, optional): The level, in dBFS,
+    ///         at or below which every channel has to be for a frame
+    ///         to count as silence. Defaults to -60 dBFS.
+    ///
     ///     file_extension(str, optional): An *optional hint* of the input audio file's
     ///         encoding. An example of a valid value is ``"mp3"``. Babycat
     ///         will automatically detect the correct encoding of ``input_audio``,
@@ -401,6 +484,11 @@ impl Waveform {
         repeat_pad_ending = false,
         resample_mode = 0,
         decoding_backend = 0,
+        normalize_mode = 0,
+        normalize_target = "0.0",
+        normalize_true_peak_ceiling_dbtp = "0.0",
+        trim_silence = false,
+        trim_silence_threshold_dbfs = "-60.0",
         file_extension = "\"\"",
         mime_type = "\"\""
     )]
@@ -415,6 +503,11 @@ impl Waveform {
         repeat_pad_ending = False,
         resample_mode = 0,
         decoding_backend = 0,
+        normalize_mode = 0,
+        normalize_target = 0.0,
+        normalize_true_peak_ceiling_dbtp = 0.0,
+        trim_silence = False,
+        trim_silence_threshold_dbfs = -60.0,
         file_extension = \"\",
         mime_type = \"\",
     )")]
@@ -431,6 +524,11 @@ impl Waveform {
         repeat_pad_ending: bool,
         resample_mode: u32,
         decoding_backend: u32,
+        normalize_mode: u32,
+        normalize_target: f32,
+        normalize_true_peak_ceiling_dbtp: f32,
+        trim_silence: bool,
+        trim_silence_threshold_dbfs: f32,
         file_extension: &str,
         mime_type: &str,
     ) -> PyResult<Self> {
@@ -445,6 +543,11 @@ impl Waveform {
                 repeat_pad_ending,
                 resample_mode,
                 decoding_backend,
+                normalize_mode,
+                normalize_target,
+                normalize_true_peak_ceiling_dbtp,
+                trim_silence,
+                trim_silence_threshold_dbfs,
             };
             crate::backend::Waveform::from_encoded_bytes_with_hint(
                 encoded_bytes,
@@ -531,6 +634,29 @@ impl Waveform {
     ///         `libsamplerate <http://www.mega-nerd.com/SRC/>`_ at its
     ///         highest-quality setting.
     ///
+    ///     normalize_mode(int, optional): Set this to scale the decoded
+    ///         audio to a target level after every other transformation.
+    ///         The :py:mod:`babycat.normalize_mode` submodule contains
+    ///         the available choices. By default, the audio is not scaled.
+    ///
+    ///     normalize_target(float, optional): The level to normalize to.
+    ///         This is in dBFS for peak normalization and in LUFS for
+    ///         ITU-R BS.1770 loudness normalization.
+    ///
+    ///     normalize_true_peak_ceiling_dbtp(float, optional): The highest
+    ///         true peak, in dBTP, that loudness normalization may produce.
+    ///         If reaching ``normalize_target`` would exceed this ceiling,
+    ///         less gain is applied. Defaults to 0 dBTP.
+    ///
+    ///     trim_silence(bool, optional): Set this to ``True`` to remove
+    ///         silence from the start and end of the audio, after
+    ///         ``start_time_milliseconds`` and ``end_time_milliseconds``
+    ///         are applied and before any padding.
+    ///
+    ///     trim_silence_threshold_dbfs(float, optional): The level, in dBFS,
+    ///         at or below which every channel has to be for a frame
+    ///         to count as silence. Defaults to -60 dBFS.
+    ///
     ///     decoding_backend(int, optional): Sets the audio decoding
     ///         backend to use. Defaults to the Symphonia backend.
     ///
@@ -551,6 +677,11 @@ impl Waveform {
         repeat_pad_ending = false,
         resample_mode = 0,
         decoding_backend = 0,
+        normalize_mode = 0,
+        normalize_target = "0.0",
+        normalize_true_peak_ceiling_dbtp = "0.0",
+        trim_silence = false,
+        trim_silence_threshold_dbfs = "-60.0",
         file_extension = "\"\"",
         mime_type = "\"\""
     )]
@@ -565,6 +696,11 @@ impl Waveform {
         repeat_pad_ending = False,
         resample_mode = 0,
         decoding_backend = 0,
+        normalize_mode = 0,
+        normalize_target = 0.0,
+        normalize_true_peak_ceiling_dbtp = 0.0,
+        trim_silence = False,
+        trim_silence_threshold_dbfs = -60.0,
         file_extension = \"\",
         mime_type = \"\",
     )")]
@@ -581,6 +717,11 @@ impl Waveform {
         repeat_pad_ending: bool,
         resample_mode: u32,
         decoding_backend: u32,
+        normalize_mode: u32,
+        normalize_target: f32,
+        normalize_true_peak_ceiling_dbtp: f32,
+        trim_silence: bool,
+        trim_silence_threshold_dbfs: f32,
         file_extension: &str,
         mime_type: &str,
     ) -> PyResult<PyArraySamples> {
@@ -595,6 +736,11 @@ impl Waveform {
                 repeat_pad_ending,
                 resample_mode,
                 decoding_backend,
+                normalize_mode,
+                normalize_target,
+                normalize_true_peak_ceiling_dbtp,
+                trim_silence,
+                trim_silence_threshold_dbfs,
             };
             crate::backend::Waveform::from_encoded_bytes_with_hint(
                 encoded_bytes,
@@ -723,6 +869,29 @@ impl Waveform {
     ///         `libsamplerate <http://www.mega-nerd.com/SRC/>`_ at its
     ///         highest-quality setting.
     ///
+    ///     normalize_mode(int, optional): Set this to scale the decoded
+    ///         audio to a target level after every other transformation.
+    ///         The :py:mod:`babycat.normalize_mode` submodule contains
+    ///         the available choices. By default, the audio is not scaled.
+    ///
+    ///     normalize_target(float, optional): The level to normalize to.
+    ///         This is in dBFS for peak normalization and in LUFS for
+    ///         ITU-R BS.1770 loudness normalization.
+    ///
+    ///     normalize_true_peak_ceiling_dbtp(float, optional): The highest
+    ///         true peak, in dBTP, that loudness normalization may produce.
+    ///         If reaching ``normalize_target`` would exceed this ceiling,
+    ///         less gain is applied. Defaults to 0 dBTP.
+    ///
+    ///     trim_silence(bool, optional): Set this to ``True`` to remove
+    ///         silence from the start and end of the audio, after
+    ///         ``start_time_milliseconds`` and ``end_time_milliseconds``
+    ///         are applied and before any padding.
+    ///
+    ///     trim_silence_threshold_dbfs(float, optional): The level, in dBFS,
+    ///         at or below which every channel has to be for a frame
+    ///         to count as silence. Defaults to -60 dBFS.
+    ///
     ///     decoding_backend(int, optional): Sets the audio decoding
     ///         backend to use. Defaults to the Symphonia backend.
     ///
@@ -789,7 +958,12 @@ impl Waveform {
         zero_pad_ending = false,
         repeat_pad_ending = false,
         resample_mode = 0,
-        decoding_backend = 0
+        decoding_backend = 0,
+        normalize_mode = 0,
+        normalize_target = "0.0",
+        normalize_true_peak_ceiling_dbtp = "0.0",
+        trim_silence = false,
+        trim_silence_threshold_dbfs = "-60.0"
     )]
     #[pyo3(text_signature = "(
         filename,
@@ -802,6 +976,11 @@ impl Waveform {
         repeat_pad_ending = False,
         resample_mode = 0,
         decoding_backend = 0,
+        normalize_mode = 0,
+        normalize_target = 0.0,
+        normalize_true_peak_ceiling_dbtp = 0.0,
+        trim_silence = False,
+        trim_silence_threshold_dbfs = -60.0,
     )")]
     #[allow(clippy::too_many_arguments)]
     pub fn from_file(
@@ -816,6 +995,11 @@ impl Waveform {
         repeat_pad_ending: bool,
         resample_mode: u32,
         decoding_backend: u32,
+        normalize_mode: u32,
+        normalize_target: f32,
+        normalize_true_peak_ceiling_dbtp: f32,
+        trim_silence: bool,
+        trim_silence_threshold_dbfs: f32,
     ) -> PyResult<Self> {
         let wr = py.allow_threads(move || {
             let waveform_args = crate::backend::WaveformArgs {
@@ -828,6 +1012,11 @@ impl Waveform {
                 repeat_pad_ending,
                 resample_mode,
                 decoding_backend,
+                normalize_mode,
+                normalize_target,
+                normalize_true_peak_ceiling_dbtp,
+                trim_silence,
+                trim_silence_threshold_dbfs,
             };
             crate::backend::Waveform::from_file(filename, waveform_args)
         });
@@ -910,6 +1099,29 @@ impl Waveform {
     ///         `libsamplerate <http://www.mega-nerd.com/SRC/>`_ at its
     ///         highest-quality setting.
     ///
+    ///     normalize_mode(int, optional): Set this to scale the decoded
+    ///         audio to a target level after every other transformation.
+    ///         The :py:mod:`babycat.normalize_mode` submodule contains
+    ///         the available choices. By default, the audio is not scaled.
+    ///
+    ///     normalize_target(float, optional): The level to normalize to.
+    ///         This is in dBFS for peak normalization and in LUFS for
+    ///         ITU-R BS.1770 loudness normalization.
+    ///
+    ///     normalize_true_peak_ceiling_dbtp(float, optional): The highest
+    ///         true peak, in dBTP, that loudness normalization may produce.
+    ///         If reaching ``normalize_target`` would exceed this ceiling,
+    ///         less gain is applied. Defaults to 0 dBTP.
+    ///
+    ///     trim_silence(bool, optional): Set this to ``True`` to remove
+    ///         silence from the start and end of the audio, after
+    ///         ``start_time_milliseconds`` and ``end_time_milliseconds``
+    ///         are applied and before any padding.
+    ///
+    ///     trim_silence_threshold_dbfs(float, optional): The level, in dBFS,
+    ///         at or below which every channel has to be for a frame
+    ///         to count as silence. Defaults to -60 dBFS.
+    ///
     ///     decoding_backend(int, optional): Sets the audio decoding
     ///         backend to use. Defaults to the Symphonia backend.
     ///
@@ -930,7 +1142,12 @@ impl Waveform {
         zero_pad_ending = false,
         repeat_pad_ending = false,
         resample_mode = 0,
-        decoding_backend = 0
+        decoding_backend = 0,
+        normalize_mode = 0,
+        normalize_target = "0.0",
+        normalize_true_peak_ceiling_dbtp = "0.0",
+        trim_silence = false,
+        trim_silence_threshold_dbfs = "-60.0"
     )]
     #[pyo3(text_signature = "(
         filename,
@@ -943,6 +1160,11 @@ impl Waveform {
         repeat_pad_ending = False,
         resample_mode = 0,
         decoding_backend = 0,
+        normalize_mode = 0,
+        normalize_target = 0.0,
+        normalize_true_peak_ceiling_dbtp = 0.0,
+        trim_silence = False,
+        trim_silence_threshold_dbfs = -60.0,
     )")]
     #[allow(clippy::too_many_arguments)]
     pub fn from_file_into_numpy(
@@ -957,6 +1179,11 @@ impl Waveform {
         repeat_pad_ending: bool,
         resample_mode: u32,
         decoding_backend: u32,
+        normalize_mode: u32,
+        normalize_target: f32,
+        normalize_true_peak_ceiling_dbtp: f32,
+        trim_silence: bool,
+        trim_silence_threshold_dbfs: f32,
     ) -> PyResult<PyArraySamples> {
         let wr = py.allow_threads(move || {
             let waveform_args = crate::backend::WaveformArgs {
@@ -969,6 +1196,11 @@ impl Waveform {
                 repeat_pad_ending,
                 resample_mode,
                 decoding_backend,
+                normalize_mode,
+                normalize_target,
+                normalize_true_peak_ceiling_dbtp,
+                trim_silence,
+                trim_silence_threshold_dbfs,
             };
             crate::backend::Waveform::from_file(filename, waveform_args)
         });
@@ -1134,6 +1366,1800 @@ impl Waveform {
         Ok(waveform.into())
     }
 
+    /// Returns a copy of the waveform without the silence at its start and end.
+    ///
+    /// A frame is silent when every channel is at or below
+    /// ``threshold_dbfs``. Silence in the middle of the waveform is kept.
+    ///
+    /// Example:
+    ///     **Trim leading and trailing silence.**
+    ///
+    ///     >>> from babycat import Waveform
+    ///     >>>
+    ///     >>> waveform = Waveform.from_interleaved_samples(
+    ///     ...     frame_rate_hz=1000,
+    ///     ...     num_channels=1,
+    ///     ...     interleaved_samples=[0.0, 0.0, 0.5, 0.0, 0.5, 0.0],
+    ///     ... )
+    ///     >>> waveform.trim_silence()
+    ///     <babycat.Waveform: 3 frames, 1 channels, 1000 hz>
+    ///
+    /// Args:
+    ///     threshold_dbfs(float, optional): The level, in dBFS, at or
+    ///         below which a frame counts as silence. Defaults to -60 dBFS.
+    ///
+    /// Returns:
+    ///     Waveform: A new waveform without leading or trailing silence.
+    ///
+    #[args(threshold_dbfs = "-60.0")]
+    #[pyo3(text_signature = "(
+        threshold_dbfs = -60.0,
+    )")]
+    pub fn trim_silence(&self, py: Python<'_>, threshold_dbfs: f32) -> Self {
+        py.allow_threads(move || self.inner.trim_silence(threshold_dbfs).into())
+    }
+
+    /// Measures the loudness and peaks of the waveform.
+    ///
+    /// This follows ITU-R BS.1770 and EBU R128. The integrated loudness,
+    /// loudness range, momentary and short-term loudness series,
+    /// and per-channel sample and true peaks are all measured
+    /// in a single pass.
+    ///
+    /// Example:
+    ///     **Measure a file decoded from disk.**
+    ///
+    ///     >>> from babycat import Waveform
+    ///     >>> waveform = Waveform.from_file("audio-for-tests/log-sweep/pcm-24.flac")
+    ///     >>> report = waveform.loudness_report()
+    ///     >>> len(report.true_peaks_dbtp) == waveform.num_channels
+    ///     True
+    ///
+    /// Returns:
+    ///     LoudnessReport: The measurements.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.FrameRateTooLow: Raised when the frame rate
+    ///         is below 8000 hz.
+    ///
+    #[args()]
+    #[pyo3(text_signature = "()")]
+    pub fn loudness_report(&self, py: Python<'_>) -> PyResult<LoudnessReport> {
+        let report = py.allow_threads(move || {
+            crate::backend::loudness::LoudnessReport::from_waveform(&self.inner)
+        })?;
+        Ok(report.into())
+    }
+
+    /// Measures the signal statistics of every channel and checks how
+    /// the channels relate to each other.
+    ///
+    /// The peak, RMS level, DC offset, crest factor, number of clipped
+    /// samples, and percentage of silence are measured for every channel.
+    /// The channels are also checked for copies of each other ("fake
+    /// stereo"), polarity-inverted copies, silence, and stereo audio with
+    /// sound in only one channel.
+    ///
+    /// Example:
+    ///     **Find the silent channel of a file decoded from disk.**
+    ///
+    ///     >>> from babycat import Waveform
+    ///     >>> waveform = Waveform.from_file("audio-for-tests/left-channel-tone/track.flac")
+    ///     >>> report = waveform.stats()
+    ///     >>> report.silent_channels
+    ///     [1]
+    ///     >>> report.left_only
+    ///     True
+    ///
+    /// Args:
+    ///     silence_threshold_dbfs(float, optional): The level, in dBFS, at
+    ///         or below which a sample counts as silence. Defaults to
+    ///         -60 dBFS.
+    ///
+    /// Returns:
+    ///     StatsReport: The measurements.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.WrongNumChannels: Raised when the waveform
+    ///         has no channels.
+    ///
+    #[args(silence_threshold_dbfs = "-60.0")]
+    #[pyo3(text_signature = "(
+        silence_threshold_dbfs = -60.0,
+    )")]
+    pub fn stats(&self, py: Python<'_>, silence_threshold_dbfs: f32) -> PyResult<StatsReport> {
+        let report = py.allow_threads(move || {
+            crate::backend::stats::StatsReport::from_waveform(&self.inner, silence_threshold_dbfs)
+        })?;
+        Ok(report.into())
+    }
+
+    /// Summarizes the minimum, maximum and RMS of every channel in
+    /// buckets of ``frames_per_bucket`` frames, for drawing the waveform.
+    ///
+    /// Example:
+    ///     **Summarize a waveform in buckets of 2 frames.**
+    ///
+    ///     >>> from babycat import Waveform
+    ///     >>> waveform = Waveform.from_interleaved_samples(
+    ///     ...     frame_rate_hz=1000,
+    ///     ...     num_channels=1,
+    ///     ...     interleaved_samples=[0.5, -0.5, 0.25, 0.0, -1.0],
+    ///     ... )
+    ///     >>> peaks = waveform.peaks(frames_per_bucket=2)
+    ///     >>> peaks.max.tolist()
+    ///     [[0.5, 0.25, -1.0]]
+    ///     >>> peaks.min.tolist()
+    ///     [[-0.5, 0.0, -1.0]]
+    ///
+    /// Args:
+    ///     frames_per_bucket(int): The number of frames in every bucket.
+    ///
+    /// Returns:
+    ///     Peaks: The summary.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.WrongFramesPerBucket: Raised when
+    ///         ``frames_per_bucket`` is 0.
+    ///
+    #[args("*", frames_per_bucket)]
+    #[pyo3(text_signature = "(frames_per_bucket)")]
+    pub fn peaks(&self, py: Python<'_>, frames_per_bucket: usize) -> PyResult<Peaks> {
+        Ok(py
+            .allow_threads(move || self.inner.peaks(frames_per_bucket))?
+            .into())
+    }
+
+    /// Summarizes the minimum, maximum and RMS of every channel in at
+    /// most ``num_buckets`` buckets of equal length.
+    ///
+    /// This is convenient for drawing a waveform that is ``num_buckets``
+    /// pixels wide. The bucket length is rounded up to a whole number of
+    /// frames, so there can be fewer than ``num_buckets`` buckets.
+    ///
+    /// Example:
+    ///     **Summarize a file for drawing 800 pixels wide.**
+    ///
+    ///     >>> from babycat import Waveform
+    ///     >>> waveform = Waveform.from_file("audio-for-tests/log-sweep/pcm-24.flac")
+    ///     >>> peaks = waveform.peaks_with_num_buckets(num_buckets=800)
+    ///     >>> peaks.frames_per_bucket
+    ///     552
+    ///     >>> peaks.max.shape
+    ///     (2, 799)
+    ///
+    /// Args:
+    ///     num_buckets(int): The largest number of buckets to return.
+    ///
+    /// Returns:
+    ///     Peaks: The summary.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.WrongNumBuckets: Raised when
+    ///         ``num_buckets`` is 0.
+    ///
+    #[args("*", num_buckets)]
+    #[pyo3(text_signature = "(num_buckets)")]
+    pub fn peaks_with_num_buckets(&self, py: Python<'_>, num_buckets: usize) -> PyResult<Peaks> {
+        Ok(py
+            .allow_threads(move || self.inner.peaks_with_num_buckets(num_buckets))?
+            .into())
+    }
+
+    /// Computes the short-time Fourier transform of the waveform.
+    ///
+    /// Example:
+    ///     **Transform one second of stereo audio.**
+    ///
+    ///     >>> from babycat import Waveform
+    ///     >>> waveform = Waveform.from_frames_of_silence(
+    ///     ...     frame_rate_hz=44100,
+    ///     ...     num_channels=2,
+    ///     ...     num_frames=44100,
+    ///     ... )
+    ///     >>> stft = waveform.stft()
+    ///     >>> stft.shape
+    ///     (2, 1025, 87)
+    ///     >>> stft.dtype
+    ///     dtype('complex64')
+    ///     >>> waveform.stft(fft_size=512, hop_length=256, channel_idx=0).shape
+    ///     (257, 173)
+    ///
+    /// Args:
+    ///     fft_size(int, optional): The number of frames in each FFT.
+    ///         Each time step has ``fft_size // 2 + 1`` frequency bins.
+    ///
+    ///     hop_length(int, optional): The number of frames between the
+    ///         starts of consecutive windows.
+    ///
+    ///     window_length(int, optional): The length of the window, which is
+    ///         zero-padded on both sides to ``fft_size``. Defaults to
+    ///         ``fft_size``.
+    ///
+    ///     window_type(int, optional): A constant from
+    ///         :py:mod:`babycat.window_type`. Defaults to a Hann window.
+    ///
+    ///     center(bool, optional): If ``True``, the audio is padded with
+    ///         ``fft_size // 2`` frames on both sides so that the ``t``-th
+    ///         window is centered on frame ``t * hop_length``.
+    ///
+    ///     pad_mode(int, optional): A constant from :py:mod:`babycat.pad_mode`
+    ///         that chooses how to pad the audio when ``center`` is ``True``.
+    ///
+    ///     channel_idx(int, optional): If given, only this channel is returned.
+    ///
+    /// Returns:
+    ///     numpy.ndarray: A ``complex64`` NumPy array with the shape
+    ///     ``(channels, bins, time_steps)``, or ``(bins, time_steps)``
+    ///     if ``channel_idx`` is given.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.WrongFftSize: Raised when ``fft_size`` is zero
+    ///         or shorter than ``window_length``.
+    ///
+    ///     babycat.exceptions.WrongHopLength: Raised when ``hop_length`` is zero.
+    ///
+    ///     babycat.exceptions.WrongNumChannels: Raised when ``channel_idx``
+    ///         is not a channel of the waveform.
+    ///
+    #[args(
+        "*",
+        fft_size = 2048,
+        hop_length = 512,
+        window_length = 0,
+        window_type = 0,
+        center = true,
+        pad_mode = 0,
+        channel_idx = "None"
+    )]
+    #[pyo3(text_signature = "(
+        fft_size = 2048,
+        hop_length = 512,
+        window_length = 0,
+        window_type = 0,
+        center = True,
+        pad_mode = 0,
+        channel_idx = None,
+    )")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn stft(
+        &self,
+        py: Python<'_>,
+        fft_size: usize,
+        hop_length: usize,
+        window_length: usize,
+        window_type: u32,
+        center: bool,
+        pad_mode: u32,
+        channel_idx: Option<u16>,
+    ) -> PyResult<PyObject> {
+        let args = StftArgs {
+            fft_size,
+            hop_length,
+            window_length,
+            window_type,
+            center,
+            pad_mode,
+        };
+        let stft = py.allow_threads(move || self.inner.stft(args))?;
+        let values: Vec<Complex32> = stft.as_slice().to_vec();
+        spectral_to_pyarray(py, stft_shape(&stft), values, channel_idx)
+    }
+
+    /// Computes a magnitude or power spectrogram of the waveform.
+    ///
+    /// This is the magnitude of :py:meth:`stft`, raised to ``power``.
+    ///
+    /// Example:
+    ///     **Compute a magnitude spectrogram of the first channel.**
+    ///
+    ///     >>> from babycat import Waveform
+    ///     >>> waveform = Waveform.from_frames_of_silence(
+    ///     ...     frame_rate_hz=44100,
+    ///     ...     num_channels=2,
+    ///     ...     num_frames=44100,
+    ///     ... )
+    ///     >>> waveform.spectrogram(power=1.0, channel_idx=0).shape
+    ///     (1025, 87)
+    ///
+    /// Args:
+    ///     fft_size(int, optional): See :py:meth:`stft`.
+    ///
+    ///     hop_length(int, optional): See :py:meth:`stft`.
+    ///
+    ///     window_length(int, optional): See :py:meth:`stft`.
+    ///
+    ///     window_type(int, optional): See :py:meth:`stft`.
+    ///
+    ///     center(bool, optional): See :py:meth:`stft`.
+    ///
+    ///     pad_mode(int, optional): See :py:meth:`stft`.
+    ///
+    ///     power(float, optional): Use ``1.0`` for a magnitude
+    ///         spectrogram and ``2.0`` for a power spectrogram.
+    ///         Defaults to ``2.0``.
+    ///
+    ///     channel_idx(int, optional): If given, only this channel is returned.
+    ///
+    /// Returns:
+    ///     numpy.ndarray: A ``float32`` NumPy array with the shape
+    ///     ``(channels, bins, time_steps)``, or ``(bins, time_steps)``
+    ///     if ``channel_idx`` is given.
+    ///
+    #[args(
+        "*",
+        fft_size = 2048,
+        hop_length = 512,
+        window_length = 0,
+        window_type = 0,
+        center = true,
+        pad_mode = 0,
+        power = "2.0",
+        channel_idx = "None"
+    )]
+    #[pyo3(text_signature = "(
+        fft_size = 2048,
+        hop_length = 512,
+        window_length = 0,
+        window_type = 0,
+        center = True,
+        pad_mode = 0,
+        power = 2.0,
+        channel_idx = None,
+    )")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn spectrogram(
+        &self,
+        py: Python<'_>,
+        fft_size: usize,
+        hop_length: usize,
+        window_length: usize,
+        window_type: u32,
+        center: bool,
+        pad_mode: u32,
+        power: f32,
+        channel_idx: Option<u16>,
+    ) -> PyResult<PyObject> {
+        let args = StftArgs {
+            fft_size,
+            hop_length,
+            window_length,
+            window_type,
+            center,
+            pad_mode,
+        };
+        let (stft, values) = py.allow_threads(move || {
+            self.inner.stft(args).map(|stft| {
+                let values = stft.spectrogram(power);
+                (stft, values)
+            })
+        })?;
+        spectral_to_pyarray(py, stft_shape(&stft), values, channel_idx)
+    }
+
+    /// Computes a mel spectrogram of the waveform.
+    ///
+    /// The defaults match ``librosa.feature.melspectrogram()``, except
+    /// that the audio is padded with reflections instead of silence.
+    /// Pass ``pad_mode=babycat.pad_mode.PAD_MODE_ZEROS`` to pad with silence.
+    ///
+    /// Example:
+    ///     **Compute a log-power mel spectrogram with 64 bands.**
+    ///
+    ///     >>> from babycat import Waveform
+    ///     >>> waveform = Waveform.from_frames_of_silence(
+    ///     ...     frame_rate_hz=22050,
+    ///     ...     num_channels=2,
+    ///     ...     num_frames=22050,
+    ///     ... )
+    ///     >>> waveform.mel_spectrogram().shape
+    ///     (2, 128, 44)
+    ///     >>> log_mel = waveform.mel_spectrogram(num_mels=64, to_db=True, channel_idx=0)
+    ///     >>> log_mel.shape
+    ///     (64, 44)
+    ///     >>> float(log_mel.max())
+    ///     -100.0
+    ///
+    /// Args:
+    ///     fft_size(int, optional): See :py:meth:`stft`.
+    ///
+    ///     hop_length(int, optional): See :py:meth:`stft`.
+    ///
+    ///     window_length(int, optional): See :py:meth:`stft`.
+    ///
+    ///     window_type(int, optional): See :py:meth:`stft`.
+    ///
+    ///     center(bool, optional): See :py:meth:`stft`.
+    ///
+    ///     pad_mode(int, optional): See :py:meth:`stft`.
+    ///
+    ///     num_mels(int, optional): The number of mel bands.
+    ///
+    ///     min_frequency_hz(float, optional): The lowest frequency of
+    ///         the filterbank.
+    ///
+    ///     max_frequency_hz(float, optional): The highest frequency of
+    ///         the filterbank. Defaults to the Nyquist frequency.
+    ///
+    ///     mel_scale(int, optional): A constant from :py:mod:`babycat.mel_scale`.
+    ///         Defaults to the Slaney mel scale.
+    ///
+    ///     normalize(bool, optional): If ``True``, each mel filter is
+    ///         divided by its width in hz.
+    ///
+    ///     power(float, optional): The exponent that the STFT magnitudes
+    ///         are raised to. Defaults to ``2.0``.
+    ///
+    ///     to_db(bool, optional): If ``True``, the mel spectrogram is
+    ///         converted to decibels.
+    ///
+    ///     top_db(float, optional): When ``to_db`` is ``True``, values more
+    ///         than this many decibels below the loudest value are raised
+    ///         to that floor. Set to ``None`` to not clip.
+    ///
+    ///     channel_idx(int, optional): If given, only this channel is returned.
+    ///
+    /// Returns:
+    ///     numpy.ndarray: A ``float32`` NumPy array with the shape
+    ///     ``(channels, mels, time_steps)``, or ``(mels, time_steps)``
+    ///     if ``channel_idx`` is given.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.WrongNumFeatures: Raised when ``num_mels`` is zero.
+    ///
+    ///     babycat.exceptions.WrongFilterFrequency: Raised when the frequency
+    ///         range is empty or above the Nyquist frequency.
+    ///
+    #[args(
+        "*",
+        fft_size = 2048,
+        hop_length = 512,
+        window_length = 0,
+        window_type = 0,
+        center = true,
+        pad_mode = 0,
+        num_mels = 128,
+        min_frequency_hz = "0.0",
+        max_frequency_hz = "0.0",
+        mel_scale = 0,
+        normalize = true,
+        power = "2.0",
+        to_db = false,
+        top_db = "80.0",
+        channel_idx = "None"
+    )]
+    #[pyo3(text_signature = "(
+        fft_size = 2048,
+        hop_length = 512,
+        window_length = 0,
+        window_type = 0,
+        center = True,
+        pad_mode = 0,
+        num_mels = 128,
+        min_frequency_hz = 0.0,
+        max_frequency_hz = 0.0,
+        mel_scale = 0,
+        normalize = True,
+        power = 2.0,
+        to_db = False,
+        top_db = 80.0,
+        channel_idx = None,
+    )")]
+    #[allow(
+        clippy::too_many_arguments,
+        clippy::fn_params_excessive_bools,
+        clippy::similar_names
+    )]
+    pub fn mel_spectrogram(
+        &self,
+        py: Python<'_>,
+        fft_size: usize,
+        hop_length: usize,
+        window_length: usize,
+        window_type: u32,
+        center: bool,
+        pad_mode: u32,
+        num_mels: usize,
+        min_frequency_hz: f32,
+        max_frequency_hz: f32,
+        mel_scale: u32,
+        normalize: bool,
+        power: f32,
+        to_db: bool,
+        top_db: Option<f32>,
+        channel_idx: Option<u16>,
+    ) -> PyResult<PyObject> {
+        let stft_args = StftArgs {
+            fft_size,
+            hop_length,
+            window_length,
+            window_type,
+            center,
+            pad_mode,
+        };
+        let mel_args = MelArgs {
+            num_mels,
+            min_frequency_hz,
+            max_frequency_hz,
+            mel_scale,
+            normalize,
+            power,
+        };
+        let mel = py.allow_threads(move || {
+            self.inner.mel_spectrogram(stft_args, mel_args).map(|mel| {
+                if to_db {
+                    mel.to_db(top_db)
+                } else {
+                    mel
+                }
+            })
+        })?;
+        let shape = features_shape(&mel);
+        spectral_to_pyarray(py, shape, Vec::from(mel), channel_idx)
+    }
+
+    /// Computes mel-frequency cepstral coefficients (MFCCs) of the waveform.
+    ///
+    /// The power mel spectrogram from :py:meth:`mel_spectrogram` is
+    /// converted to decibels, and each time step is transformed with an
+    /// orthonormal type-II discrete cosine transform. The defaults match
+    /// ``librosa.feature.mfcc()``, except for the padding mode.
+    ///
+    /// Example:
+    ///     **Compute 13 MFCCs and their deltas.**
+    ///
+    ///     >>> from babycat import Waveform
+    ///     >>> waveform = Waveform.from_frames_of_silence(
+    ///     ...     frame_rate_hz=16000,
+    ///     ...     num_channels=1,
+    ///     ...     num_frames=16000,
+    ///     ... )
+    ///     >>> waveform.mfcc(num_mfcc=13, channel_idx=0).shape
+    ///     (13, 32)
+    ///     >>> waveform.mfcc(num_mfcc=13, delta_order=1).shape
+    ///     (1, 13, 32)
+    ///
+    /// Args:
+    ///     fft_size(int, optional): See :py:meth:`stft`.
+    ///
+    ///     hop_length(int, optional): See :py:meth:`stft`.
+    ///
+    ///     window_length(int, optional): See :py:meth:`stft`.
+    ///
+    ///     window_type(int, optional): See :py:meth:`stft`.
+    ///
+    ///     center(bool, optional): See :py:meth:`stft`.
+    ///
+    ///     pad_mode(int, optional): See :py:meth:`stft`.
+    ///
+    ///     num_mels(int, optional): See :py:meth:`mel_spectrogram`.
+    ///
+    ///     min_frequency_hz(float, optional): See :py:meth:`mel_spectrogram`.
+    ///
+    ///     max_frequency_hz(float, optional): See :py:meth:`mel_spectrogram`.
+    ///
+    ///     mel_scale(int, optional): See :py:meth:`mel_spectrogram`.
+    ///
+    ///     normalize(bool, optional): See :py:meth:`mel_spectrogram`.
+    ///
+    ///     num_mfcc(int, optional): The number of coefficients to keep.
+    ///
+    ///     lifter(float, optional): If above ``0``, coefficient ``k`` is
+    ///         multiplied by ``1 + (lifter / 2) * sin(pi * (k + 1) / lifter)``.
+    ///
+    ///     top_db(float, optional): Values of the log-power mel spectrogram
+    ///         more than this many decibels below its loudest value are
+    ///         raised to that floor. Set to ``None`` to not clip.
+    ///
+    ///     delta_order(int, optional): If above ``0``, return this
+    ///         derivative of the coefficients across time instead. Use ``1``
+    ///         for deltas and ``2`` for delta-deltas.
+    ///
+    ///     delta_width(int, optional): The odd number of time steps that
+    ///         each delta is estimated over.
+    ///
+    ///     channel_idx(int, optional): If given, only this channel is returned.
+    ///
+    /// Returns:
+    ///     numpy.ndarray: A ``float32`` NumPy array with the shape
+    ///     ``(channels, num_mfcc, time_steps)``, or ``(num_mfcc, time_steps)``
+    ///     if ``channel_idx`` is given.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.WrongNumFeatures: Raised when ``num_mfcc`` is
+    ///         zero or larger than ``num_mels``.
+    ///
+    ///     babycat.exceptions.WrongDeltaWidth: Raised when ``delta_width`` is
+    ///         even, smaller than 3, or longer than the number of time steps.
+    ///
+    ///     babycat.exceptions.WrongDeltaOrder: Raised when ``delta_order`` is
+    ///         not smaller than ``delta_width``.
+    ///
+    #[args(
+        "*",
+        fft_size = 2048,
+        hop_length = 512,
+        window_length = 0,
+        window_type = 0,
+        center = true,
+        pad_mode = 0,
+        num_mels = 128,
+        min_frequency_hz = "0.0",
+        max_frequency_hz = "0.0",
+        mel_scale = 0,
+        normalize = true,
+        num_mfcc = 20,
+        lifter = "0.0",
+        top_db = "80.0",
+        delta_order = 0,
+        delta_width = 9,
+        channel_idx = "None"
+    )]
+    #[pyo3(text_signature = "(
+        fft_size = 2048,
+        hop_length = 512,
+        window_length = 0,
+        window_type = 0,
+        center = True,
+        pad_mode = 0,
+        num_mels = 128,
+        min_frequency_hz = 0.0,
+        max_frequency_hz = 0.0,
+        mel_scale = 0,
+        normalize = True,
+        num_mfcc = 20,
+        lifter = 0.0,
+        top_db = 80.0,
+        delta_order = 0,
+        delta_width = 9,
+        channel_idx = None,
+    )")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn mfcc(
+        &self,
+        py: Python<'_>,
+        fft_size: usize,
+        hop_length: usize,
+        window_length: usize,
+        window_type: u32,
+        center: bool,
+        pad_mode: u32,
+        num_mels: usize,
+        min_frequency_hz: f32,
+        max_frequency_hz: f32,
+        mel_scale: u32,
+        normalize: bool,
+        num_mfcc: usize,
+        lifter: f32,
+        top_db: Option<f32>,
+        delta_order: usize,
+        delta_width: usize,
+        channel_idx: Option<u16>,
+    ) -> PyResult<PyObject> {
+        let stft_args = StftArgs {
+            fft_size,
+            hop_length,
+            window_length,
+            window_type,
+            center,
+            pad_mode,
+        };
+        let mel_args = MelArgs {
+            num_mels,
+            min_frequency_hz,
+            max_frequency_hz,
+            mel_scale,
+            normalize,
+            ..Default::default()
+        };
+        let mfcc_args = MfccArgs {
+            num_mfcc,
+            lifter,
+            top_db,
+        };
+        let coefficients = py.allow_threads(move || {
+            let coefficients = self.inner.mfcc(stft_args, mel_args, mfcc_args)?;
+            if delta_order == 0 {
+                Ok(coefficients)
+            } else {
+                coefficients.deltas(delta_width, delta_order)
+            }
+        })?;
+        let shape = features_shape(&coefficients);
+        spectral_to_pyarray(py, shape, Vec::from(coefficients), channel_idx)
+    }
+
+    /// Computes the magnitude of the constant-Q transform of the waveform.
+    ///
+    /// Bin ``k`` is centered on ``min_frequency_hz * 2 ** (k / bins_per_octave)``,
+    /// and every bin's bandwidth is the same fraction of its center frequency.
+    /// A sine wave of amplitude ``a`` on a bin's center frequency gives that
+    /// bin a magnitude of about ``a / 2``. The ``t``-th time step is centered
+    /// on frame ``t * hop_length``.
+    ///
+    /// Example:
+    ///     **Find the loudest semitone of an A4.**
+    ///
+    ///     >>> import numpy as np
+    ///     >>> from babycat import Waveform
+    ///     >>> t = np.arange(22050) / 22050
+    ///     >>> waveform = Waveform.from_numpy(
+    ///     ...     frame_rate_hz=22050,
+    ///     ...     arr=np.sin(2 * np.pi * 440 * t).astype("float32").reshape(-1, 1),
+    ///     ... )
+    ///     >>> cqt = waveform.cqt(channel_idx=0)
+    ///     >>> cqt.shape
+    ///     (84, 44)
+    ///     >>> int(cqt[:, 22].argmax())
+    ///     45
+    ///
+    /// Args:
+    ///     hop_length(int, optional): The number of frames between
+    ///         consecutive time steps.
+    ///
+    ///     min_frequency_hz(float, optional): The center frequency of the
+    ///         lowest bin. Defaults to C1, which is about 32.7 hz.
+    ///
+    ///     num_bins(int, optional): The number of bins.
+    ///
+    ///     bins_per_octave(int, optional): The number of bins in each octave.
+    ///
+    ///     window_type(int, optional): A constant from
+    ///         :py:mod:`babycat.window_type`. Defaults to a Hann window.
+    ///
+    ///     channel_idx(int, optional): If given, only this channel is returned.
+    ///
+    /// Returns:
+    ///     numpy.ndarray: A ``float32`` NumPy array with the shape
+    ///     ``(channels, bins, time_steps)``, or ``(bins, time_steps)``
+    ///     if ``channel_idx`` is given. Use ``.T`` on a single channel to
+    ///     get a ``(time_steps, bins)`` matrix.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.WrongHopLength: Raised when ``hop_length`` is zero.
+    ///
+    ///     babycat.exceptions.WrongBinsPerOctave: Raised when
+    ///         ``bins_per_octave`` is zero.
+    ///
+    ///     babycat.exceptions.WrongNumFeatures: Raised when ``num_bins`` is zero.
+    ///
+    ///     babycat.exceptions.WrongFilterFrequency: Raised when the highest
+    ///         bin does not fit below the Nyquist frequency.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    #[args(
+        "*",
+        hop_length = 512,
+        min_frequency_hz = "32.703_197",
+        num_bins = 84,
+        bins_per_octave = 12,
+        window_type = 0,
+        channel_idx = "None"
+    )]
+    #[pyo3(text_signature = "(
+        hop_length = 512,
+        min_frequency_hz = 32.703197,
+        num_bins = 84,
+        bins_per_octave = 12,
+        window_type = 0,
+        channel_idx = None,
+    )")]
+    pub fn cqt(
+        &self,
+        py: Python<'_>,
+        hop_length: usize,
+        min_frequency_hz: f32,
+        num_bins: usize,
+        bins_per_octave: usize,
+        window_type: u32,
+        channel_idx: Option<u16>,
+    ) -> PyResult<PyObject> {
+        let args = CqtArgs {
+            hop_length,
+            min_frequency_hz,
+            num_bins,
+            bins_per_octave,
+            window_type,
+        };
+        let transform = py.allow_threads(move || self.inner.cqt(args))?;
+        let shape = features_shape(&transform);
+        spectral_to_pyarray(py, shape, Vec::from(transform), channel_idx)
+    }
+
+    /// Computes a 12-bin chromagram of the waveform from its constant-Q transform.
+    ///
+    /// Every bin of :py:meth:`cqt` is added to the pitch class of its
+    /// nearest semitone, with C as ``0``, C# as ``1``, and so on up to B as
+    /// ``11``. Each time step is then divided by its loudest pitch class.
+    /// The defaults use 3 bins for every semitone over 7 octaves, which
+    /// makes the chromagram less sensitive to tuning.
+    ///
+    /// Example:
+    ///     **Find the loudest pitch class of a D4.**
+    ///
+    ///     >>> import numpy as np
+    ///     >>> from babycat import Waveform
+    ///     >>> t = np.arange(22050) / 22050
+    ///     >>> waveform = Waveform.from_numpy(
+    ///     ...     frame_rate_hz=22050,
+    ///     ...     arr=np.sin(2 * np.pi * 293.66 * t).astype("float32").reshape(-1, 1),
+    ///     ... )
+    ///     >>> chroma = waveform.chroma(channel_idx=0)
+    ///     >>> chroma.shape
+    ///     (12, 44)
+    ///     >>> int(chroma[:, 22].argmax())
+    ///     2
+    ///
+    /// Args:
+    ///     hop_length(int, optional): See :py:meth:`cqt`.
+    ///
+    ///     min_frequency_hz(float, optional): See :py:meth:`cqt`.
+    ///
+    ///     num_bins(int, optional): See :py:meth:`cqt`.
+    ///
+    ///     bins_per_octave(int, optional): See :py:meth:`cqt`.
+    ///
+    ///     window_type(int, optional): See :py:meth:`cqt`.
+    ///
+    ///     channel_idx(int, optional): If given, only this channel is returned.
+    ///
+    /// Returns:
+    ///     numpy.ndarray: A ``float32`` NumPy array with the shape
+    ///     ``(channels, 12, time_steps)``, or ``(12, time_steps)``
+    ///     if ``channel_idx`` is given.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    #[args(
+        "*",
+        hop_length = 512,
+        min_frequency_hz = "32.703_197",
+        num_bins = 252,
+        bins_per_octave = 36,
+        window_type = 0,
+        channel_idx = "None"
+    )]
+    #[pyo3(text_signature = "(
+        hop_length = 512,
+        min_frequency_hz = 32.703197,
+        num_bins = 252,
+        bins_per_octave = 36,
+        window_type = 0,
+        channel_idx = None,
+    )")]
+    pub fn chroma(
+        &self,
+        py: Python<'_>,
+        hop_length: usize,
+        min_frequency_hz: f32,
+        num_bins: usize,
+        bins_per_octave: usize,
+        window_type: u32,
+        channel_idx: Option<u16>,
+    ) -> PyResult<PyObject> {
+        let args = CqtArgs {
+            hop_length,
+            min_frequency_hz,
+            num_bins,
+            bins_per_octave,
+            window_type,
+        };
+        let chromagram = py.allow_threads(move || self.inner.chroma(args))?;
+        let shape = features_shape(&chromagram);
+        spectral_to_pyarray(py, shape, Vec::from(chromagram), channel_idx)
+    }
+
+    /// Computes the onset strength envelope of the waveform.
+    ///
+    /// The channels are averaged together, and the envelope measures how
+    /// much louder a mel spectrogram of the result gets at every time step.
+    /// Its peaks are at the starts of drum hits, notes and other
+    /// musical events. The ``t``-th value belongs to frame ``t * hop_length``.
+    ///
+    /// Pass a constant from :py:mod:`babycat.onset_function` as
+    /// ``onset_function`` to measure change in a different way. Every
+    /// onset detection function measures change in decibels.
+    ///
+    /// Example:
+    ///     **Compute the onset strength of one second of audio.**
+    ///
+    ///     >>> from babycat import Waveform
+    ///     >>> waveform = Waveform.from_frames_of_silence(
+    ///     ...     frame_rate_hz=22050,
+    ///     ...     num_channels=2,
+    ///     ...     num_frames=22050,
+    ///     ... )
+    ///     >>> waveform.onset_strength().shape
+    ///     (44,)
+    ///
+    /// Args:
+    ///     fft_size(int, optional): The number of frames in each FFT.
+    ///
+    ///     hop_length(int, optional): The number of frames between
+    ///         consecutive values.
+    ///
+    ///     onset_function(int, optional): The onset detection function.
+    ///
+    /// Returns:
+    ///     numpy.ndarray: A 1D ``float32`` NumPy array with one value per time step.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.WrongFftSize: Raised when ``fft_size`` is zero.
+    ///
+    ///     babycat.exceptions.WrongHopLength: Raised when ``hop_length`` is zero.
+    ///
+    ///     babycat.exceptions.FeatureNotCompiled: Raised when
+    ///         ``onset_function`` is not a known onset detection function.
+    ///
+    #[args("*", fft_size = 2048, hop_length = 512, onset_function = 0)]
+    #[pyo3(text_signature = "(fft_size = 2048, hop_length = 512, onset_function = 0)")]
+    pub fn onset_strength(
+        &self,
+        py: Python<'_>,
+        fft_size: usize,
+        hop_length: usize,
+        onset_function: u32,
+    ) -> PyResult<PyObject> {
+        let args = OnsetArgs::default()
+            .set_fft_size(fft_size)
+            .set_hop_length(hop_length)
+            .set_onset_function(onset_function);
+        let envelope = py.allow_threads(move || onset::onset_envelope(&self.inner, args))?;
+        Ok(envelope.into_pyarray(py).to_object(py))
+    }
+
+    /// Finds the onsets in the waveform.
+    ///
+    /// An onset is the start of a drum hit, a note or another musical
+    /// event. The peaks of :py:meth:`onset_strength` that stand out from
+    /// the local average by at least ``threshold``, after scaling the
+    /// envelope to between 0 and 1, are onsets. Onsets fall on multiples
+    /// of ``hop_length``.
+    ///
+    /// Example:
+    ///     **Find the starts of two notes.**
+    ///
+    ///     >>> import numpy as np
+    ///     >>> from babycat import Waveform
+    ///     >>> t = np.arange(22050 * 3 // 2) / 22050
+    ///     >>> freq_hz = np.where(t < 1.0, 440.0, 660.0)
+    ///     >>> arr = np.where(t < 0.5, 0.0, 0.5 * np.sin(2 * np.pi * freq_hz * t))
+    ///     >>> arr = arr.astype("float32").reshape(-1, 1)
+    ///     >>> waveform = Waveform.from_numpy(frame_rate_hz=22050, arr=arr)
+    ///     >>> [round(frame_idx / 22050, 1) for frame_idx in waveform.onsets()]
+    ///     [0.5, 1.0]
+    ///
+    /// Args:
+    ///     fft_size(int, optional): The number of frames in each FFT.
+    ///
+    ///     hop_length(int, optional): The number of frames between
+    ///         consecutive values of the onset envelope.
+    ///
+    ///     onset_function(int, optional): The onset detection function,
+    ///         from :py:mod:`babycat.onset_function`.
+    ///
+    ///     threshold(float, optional): How far above the local average
+    ///         a peak of the scaled envelope has to be.
+    ///
+    ///     min_strength_db(float, optional): The smallest envelope value,
+    ///         in decibels, that can be an onset.
+    ///
+    ///     pre_max_milliseconds(int, optional): How long before a peak
+    ///         it has to be the largest value.
+    ///
+    ///     post_max_milliseconds(int, optional): How long after a peak
+    ///         it has to be the largest value.
+    ///
+    ///     pre_avg_milliseconds(int, optional): How long before a peak
+    ///         the local average starts.
+    ///
+    ///     post_avg_milliseconds(int, optional): How long after a peak
+    ///         the local average ends.
+    ///
+    ///     wait_milliseconds(int, optional): The shortest time between
+    ///         two onsets.
+    ///
+    ///     backtrack(bool, optional): Set to ``True`` to move every onset
+    ///         back to the last local minimum of the envelope before it.
+    ///
+    /// Returns:
+    ///     list[int]: The frame indices of the onsets, in order.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.FeatureNotCompiled: Raised when
+    ///         ``onset_function`` is not a known onset detection function.
+    ///
+    #[args(
+        "*",
+        fft_size = 2048,
+        hop_length = 512,
+        onset_function = 0,
+        threshold = "0.07",
+        min_strength_db = "1.0",
+        pre_max_milliseconds = 30,
+        post_max_milliseconds = 0,
+        pre_avg_milliseconds = 100,
+        post_avg_milliseconds = 100,
+        wait_milliseconds = 30,
+        backtrack = false
+    )]
+    #[pyo3(text_signature = "(
+        fft_size = 2048,
+        hop_length = 512,
+        onset_function = 0,
+        threshold = 0.07,
+        min_strength_db = 1.0,
+        pre_max_milliseconds = 30,
+        post_max_milliseconds = 0,
+        pre_avg_milliseconds = 100,
+        post_avg_milliseconds = 100,
+        wait_milliseconds = 30,
+        backtrack = False,
+    )")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn onsets(
+        &self,
+        py: Python<'_>,
+        fft_size: usize,
+        hop_length: usize,
+        onset_function: u32,
+        threshold: f32,
+        min_strength_db: f32,
+        pre_max_milliseconds: usize,
+        post_max_milliseconds: usize,
+        pre_avg_milliseconds: usize,
+        post_avg_milliseconds: usize,
+        wait_milliseconds: usize,
+        backtrack: bool,
+    ) -> PyResult<Vec<usize>> {
+        let args = OnsetArgs {
+            fft_size,
+            hop_length,
+            onset_function,
+            threshold,
+            min_strength_db,
+            pre_max_milliseconds,
+            post_max_milliseconds,
+            pre_avg_milliseconds,
+            post_avg_milliseconds,
+            wait_milliseconds,
+            backtrack,
+        };
+        Ok(py.allow_threads(move || self.inner.onsets(args))?)
+    }
+
+    /// Cuts the waveform into one :py:class:`Waveform` per onset.
+    ///
+    /// The onsets are found like :py:meth:`onsets`, which takes the same
+    /// arguments. Each piece starts at an onset and ends at the next one,
+    /// and the last piece ends at the end of the waveform. Audio before
+    /// the first onset is left out. Setting ``backtrack`` keeps the start
+    /// of each attack in its own piece.
+    ///
+    /// Example:
+    ///     **Cut two notes apart.**
+    ///
+    ///     >>> import numpy as np
+    ///     >>> from babycat import Waveform
+    ///     >>> t = np.arange(22050 * 3 // 2) / 22050
+    ///     >>> freq_hz = np.where(t < 1.0, 440.0, 660.0)
+    ///     >>> arr = np.where(t < 0.5, 0.0, 0.5 * np.sin(2 * np.pi * freq_hz * t))
+    ///     >>> arr = arr.astype("float32").reshape(-1, 1)
+    ///     >>> waveform = Waveform.from_numpy(frame_rate_hz=22050, arr=arr)
+    ///     >>> [piece.num_frames for piece in waveform.split_at_onsets()]
+    ///     [11264, 11059]
+    ///
+    /// Args:
+    ///     fft_size(int, optional): The number of frames in each FFT.
+    ///
+    ///     hop_length(int, optional): The number of frames between
+    ///         consecutive values of the onset envelope.
+    ///
+    ///     onset_function(int, optional): The onset detection function,
+    ///         from :py:mod:`babycat.onset_function`.
+    ///
+    ///     threshold(float, optional): How far above the local average
+    ///         a peak of the scaled envelope has to be.
+    ///
+    ///     min_strength_db(float, optional): The smallest envelope value,
+    ///         in decibels, that can be an onset.
+    ///
+    ///     pre_max_milliseconds(int, optional): How long before a peak
+    ///         it has to be the largest value.
+    ///
+    ///     post_max_milliseconds(int, optional): How long after a peak
+    ///         it has to be the largest value.
+    ///
+    ///     pre_avg_milliseconds(int, optional): How long before a peak
+    ///         the local average starts.
+    ///
+    ///     post_avg_milliseconds(int, optional): How long after a peak
+    ///         the local average ends.
+    ///
+    ///     wait_milliseconds(int, optional): The shortest time between
+    ///         two onsets.
+    ///
+    ///     backtrack(bool, optional): Set to ``True`` to move every onset
+    ///         back to the last local minimum of the envelope before it.
+    ///
+    /// Returns:
+    ///     list[Waveform]: One waveform per onset, in order.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.FeatureNotCompiled: Raised when
+    ///         ``onset_function`` is not a known onset detection function.
+    ///
+    #[args(
+        "*",
+        fft_size = 2048,
+        hop_length = 512,
+        onset_function = 0,
+        threshold = "0.07",
+        min_strength_db = "1.0",
+        pre_max_milliseconds = 30,
+        post_max_milliseconds = 0,
+        pre_avg_milliseconds = 100,
+        post_avg_milliseconds = 100,
+        wait_milliseconds = 30,
+        backtrack = false
+    )]
+    #[pyo3(text_signature = "(
+        fft_size = 2048,
+        hop_length = 512,
+        onset_function = 0,
+        threshold = 0.07,
+        min_strength_db = 1.0,
+        pre_max_milliseconds = 30,
+        post_max_milliseconds = 0,
+        pre_avg_milliseconds = 100,
+        post_avg_milliseconds = 100,
+        wait_milliseconds = 30,
+        backtrack = False,
+    )")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn split_at_onsets(
+        &self,
+        py: Python<'_>,
+        fft_size: usize,
+        hop_length: usize,
+        onset_function: u32,
+        threshold: f32,
+        min_strength_db: f32,
+        pre_max_milliseconds: usize,
+        post_max_milliseconds: usize,
+        pre_avg_milliseconds: usize,
+        post_avg_milliseconds: usize,
+        wait_milliseconds: usize,
+        backtrack: bool,
+    ) -> PyResult<Vec<Waveform>> {
+        let args = OnsetArgs {
+            fft_size,
+            hop_length,
+            onset_function,
+            threshold,
+            min_strength_db,
+            pre_max_milliseconds,
+            post_max_milliseconds,
+            pre_avg_milliseconds,
+            post_avg_milliseconds,
+            wait_milliseconds,
+            backtrack,
+        };
+        let pieces = py.allow_threads(move || self.inner.split_at_onsets(args))?;
+        Ok(pieces.into_iter().map(Waveform::from).collect())
+    }
+
+    /// Estimates the tempo of the waveform.
+    ///
+    /// The tempo is the beat period that best matches the autocorrelation
+    /// of :py:meth:`onset_strength`. Tempos far from ``start_bpm`` are
+    /// less likely, which keeps the estimate from jumping to half or
+    /// double the tempo.
+    ///
+    /// Example:
+    ///     **Estimate the tempo of a click every half second.**
+    ///
+    ///     >>> import numpy as np
+    ///     >>> from babycat import Waveform
+    ///     >>> arr = np.zeros((22050 * 10, 1), dtype="float32")
+    ///     >>> arr[np.arange(len(arr)) % 11025 < 64] = 0.5
+    ///     >>> waveform = Waveform.from_numpy(frame_rate_hz=22050, arr=arr)
+    ///     >>> bpm, confidence = waveform.tempo()
+    ///     >>> round(bpm)
+    ///     120
+    ///
+    /// Args:
+    ///     fft_size(int, optional): The number of frames in each FFT.
+    ///
+    ///     hop_length(int, optional): The number of frames between
+    ///         consecutive values of the onset strength envelope.
+    ///
+    ///     start_bpm(float, optional): The tempo in beats per minute
+    ///         that the estimate favors.
+    ///
+    ///     min_bpm(float, optional): The lowest tempo to consider.
+    ///
+    ///     max_bpm(float, optional): The highest tempo to consider.
+    ///
+    /// Returns:
+    ///     (float, float): The tempo in beats per minute and a confidence
+    ///     from 0 to 1. Both are ``0.0`` if the audio has no onsets.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.WrongTempo: Raised when ``min_bpm`` is not
+    ///         positive, ``max_bpm`` is not above ``min_bpm``, or
+    ///         ``start_bpm`` is not between them.
+    ///
+    #[args(
+        "*",
+        fft_size = 2048,
+        hop_length = 512,
+        start_bpm = "120.0",
+        min_bpm = "30.0",
+        max_bpm = "300.0"
+    )]
+    #[pyo3(text_signature = "(
+        fft_size = 2048,
+        hop_length = 512,
+        start_bpm = 120.0,
+        min_bpm = 30.0,
+        max_bpm = 300.0,
+    )")]
+    pub fn tempo(
+        &self,
+        py: Python<'_>,
+        fft_size: usize,
+        hop_length: usize,
+        start_bpm: f32,
+        min_bpm: f32,
+        max_bpm: f32,
+    ) -> PyResult<(f32, f32)> {
+        let args = BeatArgs::default()
+            .set_fft_size(fft_size)
+            .set_hop_length(hop_length)
+            .set_start_bpm(start_bpm)
+            .set_min_bpm(min_bpm)
+            .set_max_bpm(max_bpm);
+        let estimate = py.allow_threads(move || self.inner.tempo(args))?;
+        Ok((estimate.bpm, estimate.confidence))
+    }
+
+    /// Estimates the tempo of the waveform and finds its beats.
+    ///
+    /// The tempo is estimated like :py:meth:`tempo`. The beats are the
+    /// onsets that are strong while staying close to one beat period
+    /// apart, found with dynamic programming. Beats fall on multiples of
+    /// ``hop_length``, and weak beats at the start and end are left out.
+    ///
+    /// Example:
+    ///     **Find the beats of a click every half second.**
+    ///
+    ///     >>> import numpy as np
+    ///     >>> from babycat import Waveform
+    ///     >>> arr = np.zeros((22050 * 10, 1), dtype="float32")
+    ///     >>> arr[np.arange(len(arr)) % 11025 < 64] = 0.5
+    ///     >>> waveform = Waveform.from_numpy(frame_rate_hz=22050, arr=arr)
+    ///     >>> track = waveform.beat_track()
+    ///     >>> round(track.bpm)
+    ///     120
+    ///     >>> np.diff(track.beat_milliseconds).min() >= 480
+    ///     True
+    ///
+    /// Args:
+    ///     fft_size(int, optional): The number of frames in each FFT.
+    ///
+    ///     hop_length(int, optional): The number of frames between
+    ///         consecutive values of the onset strength envelope.
+    ///
+    ///     start_bpm(float, optional): The tempo in beats per minute
+    ///         that the estimate favors.
+    ///
+    ///     min_bpm(float, optional): The lowest tempo to consider.
+    ///
+    ///     max_bpm(float, optional): The highest tempo to consider.
+    ///
+    ///     tightness(float, optional): How strongly to prefer
+    ///         evenly-spaced beats over strong onsets.
+    ///
+    /// Returns:
+    ///     BeatTrack: The tempo and the frame indices and millisecond
+    ///     timestamps of the beats.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.WrongTempo: Raised when ``min_bpm`` is not
+    ///         positive, ``max_bpm`` is not above ``min_bpm``, or
+    ///         ``start_bpm`` is not between them.
+    ///
+    #[args(
+        "*",
+        fft_size = 2048,
+        hop_length = 512,
+        start_bpm = "120.0",
+        min_bpm = "30.0",
+        max_bpm = "300.0",
+        tightness = "100.0"
+    )]
+    #[pyo3(text_signature = "(
+        fft_size = 2048,
+        hop_length = 512,
+        start_bpm = 120.0,
+        min_bpm = 30.0,
+        max_bpm = 300.0,
+        tightness = 100.0,
+    )")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn beat_track(
+        &self,
+        py: Python<'_>,
+        fft_size: usize,
+        hop_length: usize,
+        start_bpm: f32,
+        min_bpm: f32,
+        max_bpm: f32,
+        tightness: f32,
+    ) -> PyResult<BeatTrack> {
+        let args = BeatArgs {
+            fft_size,
+            hop_length,
+            start_bpm,
+            min_bpm,
+            max_bpm,
+            tightness,
+        };
+        let track = py.allow_threads(move || self.inner.beat_track(args))?;
+        Ok(track.into())
+    }
+
+    /// Tracks the fundamental frequency of the waveform.
+    ///
+    /// The channels are averaged together first. Every frame is compared
+    /// with copies of itself shifted by every period between
+    /// ``1 / max_frequency_hz`` and ``1 / min_frequency_hz``, and the
+    /// closest match gives the pitch. By default, pYIN smooths the pitch
+    /// and voicing across frames. Pass a constant from
+    /// :py:mod:`babycat.pitch_method` as ``method`` to choose another method.
+    ///
+    /// Example:
+    ///     **Track the pitch of a 220 hz sine wave.**
+    ///
+    ///     >>> import numpy as np
+    ///     >>> from babycat import Waveform
+    ///     >>> t = np.arange(22050) / 22050
+    ///     >>> arr = (0.5 * np.sin(2 * np.pi * 220.0 * t)).astype("float32")
+    ///     >>> waveform = Waveform.from_numpy(frame_rate_hz=22050, arr=arr.reshape(-1, 1))
+    ///     >>> contour = waveform.pitch()
+    ///     >>> contour.dtype.names
+    ///     ('frame_idx', 'milliseconds', 'f0_hz', 'voiced_prob')
+    ///     >>> round(float(np.nanmedian(contour["f0_hz"])))
+    ///     220
+    ///
+    /// Args:
+    ///     frame_length(int, optional): The number of frames in each
+    ///         analysis frame. It has to hold at least two periods of
+    ///         ``min_frequency_hz``.
+    ///
+    ///     hop_length(int, optional): The number of frames between
+    ///         consecutive results.
+    ///
+    ///     min_frequency_hz(float, optional): The lowest pitch to look for.
+    ///
+    ///     max_frequency_hz(float, optional): The highest pitch to look for.
+    ///
+    ///     method(int, optional): The pitch tracking method.
+    ///
+    ///     threshold(float, optional): With YIN, how deep a dip in the
+    ///         difference function has to be for the frame to count as voiced.
+    ///
+    /// Returns:
+    ///     numpy.ndarray: A 1D NumPy structured array with one row per
+    ///     ``hop_length`` frames. The ``frame_idx`` and ``milliseconds``
+    ///     fields are the position of each row, ``f0_hz`` is the
+    ///     fundamental frequency, or NaN when the frame is unvoiced, and
+    ///     ``voiced_prob`` is the probability that the frame is voiced.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.WrongHopLength: Raised when ``hop_length`` is zero.
+    ///
+    ///     babycat.exceptions.WrongFilterFrequency: Raised when the
+    ///         frequency range is empty or above the Nyquist frequency.
+    ///
+    ///     babycat.exceptions.WrongFrameLength: Raised when
+    ///         ``frame_length`` is too short for ``min_frequency_hz``.
+    ///
+    ///     babycat.exceptions.FeatureNotCompiled: Raised when ``method``
+    ///         is not a known pitch tracking method.
+    ///
+    #[args(
+        "*",
+        frame_length = 2048,
+        hop_length = 512,
+        min_frequency_hz = "65.406_39",
+        max_frequency_hz = "2_093.004_5",
+        method = 0,
+        threshold = "0.1"
+    )]
+    #[pyo3(text_signature = "(
+        frame_length = 2048,
+        hop_length = 512,
+        min_frequency_hz = 65.40639,
+        max_frequency_hz = 2093.0045,
+        method = 0,
+        threshold = 0.1,
+    )")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn pitch(
+        &self,
+        py: Python<'_>,
+        frame_length: usize,
+        hop_length: usize,
+        min_frequency_hz: f32,
+        max_frequency_hz: f32,
+        method: u32,
+        threshold: f32,
+    ) -> PyResult<PyObject> {
+        let args = PitchArgs {
+            frame_length,
+            hop_length,
+            min_frequency_hz,
+            max_frequency_hz,
+            method,
+            threshold,
+        };
+        let contour = py.allow_threads(move || self.inner.pitch(args))?;
+        let frame_idxs: Vec<u64> = contour.iter().map(|p| p.frame_idx as u64).collect();
+        let milliseconds: Vec<u64> = contour.iter().map(|p| p.milliseconds as u64).collect();
+        let f0_hz: Vec<f32> = contour.iter().map(|p| p.f0_hz).collect();
+        let voiced_prob: Vec<f32> = contour.iter().map(|p| p.voiced_prob).collect();
+        let dtype = vec![
+            ("frame_idx", "<u8"),
+            ("milliseconds", "<u8"),
+            ("f0_hz", "<f4"),
+            ("voiced_prob", "<f4"),
+        ];
+        let arr = py
+            .import("numpy")?
+            .call_method1("empty", (contour.len(), dtype))?;
+        arr.set_item("frame_idx", frame_idxs.into_pyarray(py))?;
+        arr.set_item("milliseconds", milliseconds.into_pyarray(py))?;
+        arr.set_item("f0_hz", f0_hz.into_pyarray(py))?;
+        arr.set_item("voiced_prob", voiced_prob.into_pyarray(py))?;
+        Ok(arr.to_object(py))
+    }
+
+    /// Estimates the musical key of the waveform.
+    ///
+    /// The :py:meth:`chroma` of every channel is averaged into one
+    /// profile of how strong every pitch class is. That profile is
+    /// correlated with the Krumhansl-Kessler major and minor key
+    /// profiles, starting on every pitch class, and the key with the
+    /// highest correlation is returned.
+    ///
+    /// Example:
+    ///     **Find the key of an A minor triad.**
+    ///
+    ///     >>> import numpy as np
+    ///     >>> from babycat import Waveform
+    ///     >>> t = np.arange(22050) / 22050
+    ///     >>> arr = sum(np.sin(2 * np.pi * hz * t) for hz in [220.0, 261.63, 329.63])
+    ///     >>> waveform = Waveform.from_numpy(
+    ///     ...     frame_rate_hz=22050,
+    ///     ...     arr=(0.2 * arr).astype("float32").reshape(-1, 1),
+    ///     ... )
+    ///     >>> key = waveform.key()
+    ///     >>> key.name
+    ///     'A minor'
+    ///     >>> key.camelot
+    ///     '8A'
+    ///     >>> len(key.correlations)
+    ///     24
+    ///
+    /// Args:
+    ///     hop_length(int, optional): See :py:meth:`cqt`.
+    ///
+    ///     min_frequency_hz(float, optional): See :py:meth:`cqt`.
+    ///
+    ///     num_bins(int, optional): See :py:meth:`cqt`.
+    ///
+    ///     bins_per_octave(int, optional): See :py:meth:`cqt`.
+    ///
+    ///     window_type(int, optional): See :py:meth:`cqt`.
+    ///
+    /// Returns:
+    ///     Key: The most likely key, or ``None`` if the waveform has no
+    ///     pitched content, such as silence.
+    ///
+    #[args(
+        "*",
+        hop_length = 512,
+        min_frequency_hz = "32.703_197",
+        num_bins = 252,
+        bins_per_octave = 36,
+        window_type = 0
+    )]
+    #[pyo3(text_signature = "(
+        hop_length = 512,
+        min_frequency_hz = 32.703197,
+        num_bins = 252,
+        bins_per_octave = 36,
+        window_type = 0,
+    )")]
+    pub fn key(
+        &self,
+        py: Python<'_>,
+        hop_length: usize,
+        min_frequency_hz: f32,
+        num_bins: usize,
+        bins_per_octave: usize,
+        window_type: u32,
+    ) -> PyResult<Option<Key>> {
+        let args = CqtArgs {
+            hop_length,
+            min_frequency_hz,
+            num_bins,
+            bins_per_octave,
+            window_type,
+        };
+        let key = py.allow_threads(move || self.inner.key(args))?;
+        Ok(key.map(Key::from))
+    }
+
+    /// Finds the offset between this waveform and another recording of
+    /// the same sound.
+    ///
+    /// The offset is found with the generalized cross-correlation with
+    /// phase transform (GCC-PHAT), which still finds a sharp peak when the
+    /// recordings were made with different microphones or at different
+    /// levels. Waveforms with more than one channel are mixed down to one
+    /// channel first.
+    ///
+    /// Example:
+    ///     **Find how much later a noise burst starts in another waveform.**
+    ///
+    ///     >>> import numpy as np
+    ///     >>> from babycat import Waveform
+    ///     >>> noise = np.random.uniform(-0.5, 0.5, (12000, 1)).astype("float32")
+    ///     >>> first = Waveform.from_numpy(frame_rate_hz=8000, arr=noise[2000:10000])
+    ///     >>> second = Waveform.from_numpy(frame_rate_hz=8000, arr=noise[1500:9500])
+    ///     >>> lag_frames, confidence = first.find_offset(second, max_lag_frames=1000)
+    ///     >>> lag_frames
+    ///     500
+    ///
+    /// Args:
+    ///     other(Waveform): The other recording.
+    ///
+    ///     max_lag_frames(int): The largest offset to search for, in
+    ///         frames, in either direction.
+    ///
+    /// Returns:
+    ///     (int, float): How many frames later a sound appears in
+    ///     ``other`` than in this waveform, and how much higher that
+    ///     cross-correlation peak is than the next highest one, from 0
+    ///     to 1. Frame ``t`` of this waveform matches frame
+    ///     ``t + lag_frames`` of ``other``.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.CannotAlignWithDifferentFrameRates: Raised
+    ///         when the waveforms do not have the same frame rate.
+    ///
+    #[args("*", other, max_lag_frames)]
+    #[pyo3(text_signature = "(other, max_lag_frames)")]
+    pub fn find_offset(
+        &self,
+        py: Python<'_>,
+        other: &Waveform,
+        max_lag_frames: usize,
+    ) -> PyResult<(i64, f32)> {
+        let offset = py.allow_threads(|| self.inner.find_offset(&other.inner, max_lag_frames))?;
+        Ok((offset.lag_frames, offset.confidence))
+    }
+
+    /// Lines up recordings of the same event and trims them to the
+    /// stretch of time that all of them cover.
+    ///
+    /// The offset of every waveform from the first one is found with
+    /// :py:meth:`find_offset`. Every returned waveform starts at the same
+    /// moment and has the same number of frames, and keeps its own
+    /// channels.
+    ///
+    /// Example:
+    ///     **Line up two overlapping pieces of the same noise.**
+    ///
+    ///     >>> import numpy as np
+    ///     >>> from babycat import Waveform
+    ///     >>> noise = np.random.uniform(-0.5, 0.5, (4000, 1)).astype("float32")
+    ///     >>> early = Waveform.from_numpy(frame_rate_hz=8000, arr=noise[:3000])
+    ///     >>> late = Waveform.from_numpy(frame_rate_hz=8000, arr=noise[1000:])
+    ///     >>> aligned = Waveform.align([early, late], max_lag_frames=2000)
+    ///     >>> [waveform.num_frames for waveform in aligned]
+    ///     [2000, 2000]
+    ///
+    /// Args:
+    ///     waveforms(list[Waveform]): The recordings to line up. The
+    ///         first one is the reference that the others are compared to.
+    ///
+    ///     max_lag_frames(int): The largest offset to search for, in
+    ///         frames, in either direction.
+    ///
+    /// Returns:
+    ///     list[Waveform]: The trimmed waveforms, in the same order.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.CannotAlignWithDifferentFrameRates: Raised
+    ///         when the waveforms do not all have the same frame rate.
+    ///
+    #[staticmethod]
+    #[args("*", waveforms, max_lag_frames)]
+    #[pyo3(text_signature = "(waveforms, max_lag_frames)")]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn align(
+        py: Python<'_>,
+        waveforms: Vec<Waveform>,
+        max_lag_frames: usize,
+    ) -> PyResult<Vec<Waveform>> {
+        let waveforms: Vec<crate::backend::Waveform> = waveforms
+            .into_iter()
+            .map(|waveform| waveform.inner)
+            .collect();
+        let aligned = py.allow_threads(move || align::align(&waveforms, max_lag_frames))?;
+        Ok(aligned.into_iter().map(Waveform::from).collect())
+    }
+
+    /// Lines up recordings of the same event with :py:meth:`align` and
+    /// stacks their channels into one waveform.
+    ///
+    /// The channels of the first waveform come first, followed by the
+    /// channels of the second waveform, and so on.
+    ///
+    /// Args:
+    ///     waveforms(list[Waveform]): The recordings to line up. The
+    ///         first one is the reference that the others are compared to.
+    ///
+    ///     max_lag_frames(int): The largest offset to search for, in
+    ///         frames, in either direction.
+    ///
+    /// Returns:
+    ///     Waveform: One waveform with the channels of every recording.
+    ///
+    /// Raises:
+    ///     babycat.exceptions.CannotAlignWithDifferentFrameRates: Raised
+    ///         when the waveforms do not all have the same frame rate.
+    ///
+    ///     babycat.exceptions.CannotAlignWithoutWaveforms: Raised when
+    ///         ``waveforms`` is empty.
+    ///
+    ///     babycat.exceptions.CannotStackTooManyChannels: Raised when the
+    ///         recordings have more than 65535 channels in total.
+    ///
+    #[staticmethod]
+    #[args("*", waveforms, max_lag_frames)]
+    #[pyo3(text_signature = "(waveforms, max_lag_frames)")]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn align_and_stack(
+        py: Python<'_>,
+        waveforms: Vec<Waveform>,
+        max_lag_frames: usize,
+    ) -> PyResult<Self> {
+        let waveforms: Vec<crate::backend::Waveform> = waveforms
+            .into_iter()
+            .map(|waveform| waveform.inner)
+            .collect();
+        let stacked =
+            py.allow_threads(move || align::align_and_stack(&waveforms, max_lag_frames))?;
+        Ok(stacked.into())
+    }
+
+    /// Creates a :py:class:`Waveform` from a short-time Fourier transform.
+    ///
+    /// This is the inverse of :py:meth:`stft`. Each time step is inverted
+    /// and overlap-added with the same window. Pass the same arguments that
+    /// were given to :py:meth:`stft`. The FFT size is worked out from the
+    /// number of bins, so it is assumed to be even.
+    ///
+    /// Example:
+    ///     **Round-trip a waveform through the frequency domain.**
+    ///
+    ///     >>> import numpy as np
+    ///     >>> from babycat import Waveform
+    ///     >>> waveform = Waveform.from_numpy(
+    ///     ...     frame_rate_hz=8000,
+    ///     ...     arr=np.random.uniform(-0.5, 0.5, (8000, 2)).astype("float32"),
+    ///     ... )
+    ///     >>> stft = waveform.stft(fft_size=512, hop_length=128)
+    ///     >>> inverse = Waveform.from_stft(
+    ///     ...     frame_rate_hz=8000,
+    ///     ...     stft=stft,
+    ///     ...     hop_length=128,
+    ///     ...     num_frames=8000,
+    ///     ... )
+    ///     >>> bool(np.allclose(inverse.to_numpy(), waveform.to_numpy(), atol=1e-4))
+    ///     True
+    ///
+    /// Args:
+    ///     frame_rate_hz(int): The frame rate of the audio.
+    ///
+    ///     stft: A ``complex64`` NumPy array with the shape
+    ///         ``(channels, bins, time_steps)``, or ``(bins, time_steps)``
+    ///         for a single channel.
+    ///
+    ///     hop_length(int, optional): See :py:meth:`stft`.
+    ///
+    ///     window_length(int, optional): See :py:meth:`stft`.
+    ///
+    ///     window_type(int, optional): See :py:meth:`stft`.
+    ///
+    ///     center(bool, optional): See :py:meth:`stft`.
+    ///
+    ///     num_frames(int, optional): The number of frames in the output.
+    ///         Defaults to the shortest length with that many time steps.
+    ///
+    /// Returns:
+    ///     Waveform: The reconstructed waveform.
+    ///
+    /// Raises:
+    ///     TypeError: Raised when ``stft`` has the wrong dtype, or does not
+    ///         have two or three dimensions.
+    ///
+    ///     babycat.exceptions.WrongStftShape: Raised when ``stft`` does not
+    ///         have the number of time steps that ``num_frames`` calls for.
+    ///
+    #[staticmethod]
+    #[args(
+        "*",
+        frame_rate_hz,
+        stft,
+        hop_length = 512,
+        window_length = 0,
+        window_type = 0,
+        center = true,
+        num_frames = "None"
+    )]
+    #[pyo3(text_signature = "(
+        frame_rate_hz,
+        stft,
+        hop_length = 512,
+        window_length = 0,
+        window_type = 0,
+        center = True,
+        num_frames = None,
+    )")]
+    #[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
+    pub fn from_stft(
+        py: Python<'_>,
+        frame_rate_hz: u32,
+        stft: PyReadonlyArrayDyn<Complex32>,
+        hop_length: usize,
+        window_length: usize,
+        window_type: u32,
+        center: bool,
+        num_frames: Option<usize>,
+    ) -> PyResult<Self> {
+        let shape = stft.shape().to_vec();
+        let (num_channels, num_bins, num_time_steps) = match shape[..] {
+            [num_bins, num_time_steps] => (1, num_bins, num_time_steps),
+            [num_channels, num_bins, num_time_steps] => (num_channels, num_bins, num_time_steps),
+            _ => {
+                return Err(PyTypeError::new_err(format!(
+                    "Expected a 2D or 3D array, but got a {}D array.",
+                    shape.len()
+                )))
+            }
+        };
+        let args = StftArgs {
+            fft_size: num_bins.saturating_sub(1) * 2,
+            hop_length,
+            window_length,
+            window_type,
+            center,
+            pad_mode: 0,
+        };
+        let num_frames = num_frames.unwrap_or_else(|| args.min_num_frames(num_time_steps));
+        let data: Vec<Complex32> = stft.as_array().iter().copied().collect();
+        let waveform = py.allow_threads(move || {
+            #[allow(clippy::cast_possible_truncation)]
+            Stft::new(args, frame_rate_hz, num_channels as u16, num_frames, data)?.to_waveform()
+        })?;
+        Ok(waveform.into())
+    }
+
     /// Return a given audio sample belonging to a specific frame and channel.
     ///
     /// This method performs bounds checks. If you want an unsafe
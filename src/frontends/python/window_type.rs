@@ -0,0 +1,55 @@
+use pyo3::prelude::*;
+
+use crate::backend::constants::{
+    DEFAULT_WINDOW_TYPE, WINDOW_TYPE_BLACKMAN, WINDOW_TYPE_HAMMING, WINDOW_TYPE_HANN,
+    WINDOW_TYPE_RECTANGULAR,
+};
+
+/// Creates the `babycat.window_type` submodule, which is used to
+/// store constants pointing to window functions.
+pub fn make_window_type_submodule(py: Python) -> PyResult<&PyModule> {
+    let window_type_submodule = PyModule::new(py, "window_type")?;
+
+    window_type_submodule.setattr(
+        "__doc__",
+        "
+A Python submodule to hold constants representing different window functions.
+
+Pass one of these constants as ``window_type`` when computing a
+short-time Fourier transform with :py:meth:`babycat.Waveform.stft`.
+
+- :py:attr:`DEFAULT_WINDOW_TYPE`: The same as :py:attr:`WINDOW_TYPE_HANN`.
+
+- :py:attr:`WINDOW_TYPE_HANN`: A Hann window.
+
+- :py:attr:`WINDOW_TYPE_HAMMING`: A Hamming window.
+
+- :py:attr:`WINDOW_TYPE_BLACKMAN`: A Blackman window.
+
+- :py:attr:`WINDOW_TYPE_RECTANGULAR`: A rectangular window,
+  which does not taper the audio at all.
+
+Example:
+    **Compute a short-time Fourier transform with a Hamming window.**
+
+    >>> from babycat import Waveform
+    >>> from babycat.window_type import *
+    >>>
+    >>> waveform = Waveform.from_frames_of_silence(
+    ...     frame_rate_hz=44100,
+    ...     num_channels=2,
+    ...     num_frames=44100,
+    ... )
+    >>> waveform.stft(window_type=WINDOW_TYPE_HAMMING).shape
+    (2, 1025, 87)
+
+",
+    )?;
+    window_type_submodule.setattr("DEFAULT_WINDOW_TYPE", DEFAULT_WINDOW_TYPE)?;
+    window_type_submodule.setattr("WINDOW_TYPE_HANN", WINDOW_TYPE_HANN)?;
+    window_type_submodule.setattr("WINDOW_TYPE_HAMMING", WINDOW_TYPE_HAMMING)?;
+    window_type_submodule.setattr("WINDOW_TYPE_BLACKMAN", WINDOW_TYPE_BLACKMAN)?;
+    window_type_submodule.setattr("WINDOW_TYPE_RECTANGULAR", WINDOW_TYPE_RECTANGULAR)?;
+
+    Ok(window_type_submodule)
+}
@@ -1,8 +1,12 @@
 #![allow(clippy::needless_pass_by_value)]
 mod build_info;
 mod error;
+mod peaks;
+mod stft;
 mod waveform;
 
 pub use build_info::*;
 pub use error::*;
+pub use peaks::*;
+pub use stft::*;
 pub use waveform::*;
@@ -0,0 +1,80 @@
+#![allow(non_snake_case)]
+
+use js_sys::Float32Array;
+use js_sys::Uint8Array;
+use wasm_bindgen::prelude::*;
+
+use crate::frontends::wasm::error::throw_js_error;
+
+/// The minimum, maximum and RMS of every channel of a waveform over
+/// consecutive buckets of frames, for drawing the waveform.
+///
+/// Values are returned as flat arrays with the shape `(channels, buckets)`.
+#[allow(clippy::unused_unit)]
+#[wasm_bindgen]
+pub struct Peaks {
+    inner: crate::backend::peaks::Peaks,
+}
+
+#[allow(clippy::unused_unit)]
+#[wasm_bindgen]
+impl Peaks {
+    /// Reads a summary from the compact binary format written by `toBytes()`.
+    pub fn fromBytes(bytes: Uint8Array) -> Result<Peaks, JsValue> {
+        match crate::backend::peaks::Peaks::from_bytes(&bytes.to_vec()) {
+            Ok(inner) => Ok(inner.into()),
+            Err(err) => Err(throw_js_error(err)),
+        }
+    }
+
+    /// Writes the summary in a compact binary format for caching.
+    pub fn toBytes(&self) -> Uint8Array {
+        Uint8Array::from(self.inner.to_bytes().as_slice())
+    }
+
+    /// Returns the frame rate of the summarized audio.
+    pub fn frameRateHz(&self) -> u32 {
+        self.inner.frame_rate_hz()
+    }
+
+    /// Returns the number of channels.
+    pub fn numChannels(&self) -> u16 {
+        self.inner.num_channels()
+    }
+
+    /// Returns the number of frames in the summarized audio.
+    pub fn numFrames(&self) -> usize {
+        self.inner.num_frames()
+    }
+
+    /// Returns the number of frames in every bucket but the last.
+    pub fn framesPerBucket(&self) -> usize {
+        self.inner.frames_per_bucket()
+    }
+
+    /// Returns the number of buckets in every channel.
+    pub fn numBuckets(&self) -> usize {
+        self.inner.num_buckets()
+    }
+
+    /// Returns the smallest sample in every bucket.
+    pub fn min(&self) -> Float32Array {
+        Float32Array::from(self.inner.min())
+    }
+
+    /// Returns the largest sample in every bucket.
+    pub fn max(&self) -> Float32Array {
+        Float32Array::from(self.inner.max())
+    }
+
+    /// Returns the root mean square of every bucket.
+    pub fn rms(&self) -> Float32Array {
+        Float32Array::from(self.inner.rms())
+    }
+}
+
+impl From<crate::backend::peaks::Peaks> for Peaks {
+    fn from(inner: crate::backend::peaks::Peaks) -> Self {
+        Peaks { inner }
+    }
+}
@@ -0,0 +1,81 @@
+#![allow(non_snake_case)]
+
+use js_sys::Float32Array;
+use wasm_bindgen::prelude::*;
+
+use crate::frontends::wasm::error::throw_js_error;
+use crate::frontends::wasm::waveform::Waveform;
+
+/// A short-time Fourier transform of every channel of a waveform.
+///
+/// Spectral values are returned as flat arrays with the shape
+/// `(channels, bins, time steps)`.
+#[allow(clippy::unused_unit)]
+#[wasm_bindgen]
+pub struct Stft {
+    inner: crate::backend::spectral::Stft,
+}
+
+#[allow(clippy::unused_unit)]
+#[wasm_bindgen]
+impl Stft {
+    /// Returns the frame rate of the transformed audio.
+    pub fn frameRateHz(&self) -> u32 {
+        self.inner.frame_rate_hz()
+    }
+
+    /// Returns the number of channels.
+    pub fn numChannels(&self) -> u16 {
+        self.inner.num_channels()
+    }
+
+    /// Returns the number of frames in the transformed audio.
+    pub fn numFrames(&self) -> usize {
+        self.inner.num_frames()
+    }
+
+    /// Returns the number of frequency bins in each time step.
+    pub fn numBins(&self) -> usize {
+        self.inner.num_bins()
+    }
+
+    /// Returns the number of time steps.
+    pub fn numTimeSteps(&self) -> usize {
+        self.inner.num_time_steps()
+    }
+
+    /// Returns the values as interleaved real and imaginary parts.
+    pub fn toInterleavedComplex(&self) -> Float32Array {
+        let values: Vec<f32> = self
+            .inner
+            .as_slice()
+            .iter()
+            .flat_map(|value| [value.re, value.im])
+            .collect();
+        Float32Array::from(values.as_slice())
+    }
+
+    /// Returns the magnitude spectrogram.
+    pub fn magnitudes(&self) -> Float32Array {
+        Float32Array::from(self.inner.magnitudes().as_slice())
+    }
+
+    /// Returns the power spectrogram.
+    pub fn powers(&self) -> Float32Array {
+        Float32Array::from(self.inner.powers().as_slice())
+    }
+
+    /// Turns the transform back into a waveform with a windowed overlap-add.
+    pub fn toWaveform(&self) -> Result<Waveform, JsValue> {
+        match self.inner.to_waveform() {
+            Ok(inner) => Ok(inner.into()),
+            Err(err) => Err(throw_js_error(err)),
+        }
+    }
+}
+
+impl From<crate::backend::spectral::Stft> for Stft {
+    fn from(inner: crate::backend::spectral::Stft) -> Self {
+        Stft { inner }
+    }
+}
@@ -7,6 +7,8 @@ use wasm_bindgen::prelude::*;
 use crate::backend::Signal;
 
 use crate::frontends::wasm::error::throw_js_error;
+use crate::frontends::wasm::peaks::Peaks;
+use crate::frontends::wasm::stft::Stft;
 
 /// Docs for Waveform struct.
 #[allow(clippy::unused_unit)]
@@ -142,6 +144,48 @@ impl Waveform {
             Err(err) => Err(throw_js_error(err)),
         }
     }
+
+    /// Measures EBU R128 loudness and peaks, returning the report as a plain object.
+    pub fn loudnessReport(&self) -> Result<JsValue, JsValue> {
+        let report = match crate::backend::loudness::LoudnessReport::from_waveform(&self.inner) {
+            Ok(report) => report,
+            Err(err) => return Err(throw_js_error(err)),
+        };
+        match JsValue::from_serde(&report) {
+            Ok(report) => Ok(report),
+            Err(err) => Err(throw_js_error(err)),
+        }
+    }
+
+    /// Summarizes the minimum, maximum and RMS of every channel in
+    /// buckets of `framesPerBucket` frames.
+    pub fn peaks(&self, framesPerBucket: usize) -> Result<Peaks, JsValue> {
+        match self.inner.peaks(framesPerBucket) {
+            Ok(inner) => Ok(inner.into()),
+            Err(err) => Err(throw_js_error(err)),
+        }
+    }
+
+    /// Summarizes the minimum, maximum and RMS of every channel in at
+    /// most `numBuckets` buckets of equal length.
+    pub fn peaksWithNumBuckets(&self, numBuckets: usize) -> Result<Peaks, JsValue> {
+        match self.inner.peaks_with_num_buckets(numBuckets) {
+            Ok(inner) => Ok(inner.into()),
+            Err(err) => Err(throw_js_error(err)),
+        }
+    }
+
+    /// Computes the short-time Fourier transform of every channel.
+    pub fn stft(&self, stftArgs: JsValue) -> Result<Stft, JsValue> {
+        let parsedStftArgs: crate::backend::spectral::StftArgs = match stftArgs.into_serde() {
+            Ok(parsed) => parsed,
+            Err(err) => return Err(throw_js_error(err)),
+        };
+        match self.inner.stft(parsedStftArgs) {
+            Ok(inner) => Ok(inner.into()),
+            Err(err) => Err(throw_js_error(err)),
+        }
+    }
 }
 
 impl From<crate::backend::Waveform> for Waveform {
@@ -0,0 +1,57 @@
+import numpy as np
+import pytest
+
+import babycat
+from babycat import Waveform
+
+
+def noise(num_frames, num_channels=1, seed=0):
+    rng = np.random.default_rng(seed)
+    return rng.uniform(-0.5, 0.5, (num_frames, num_channels)).astype("float32")
+
+
+def test_find_offset_both_directions():
+    arr = noise(20000)
+    first = Waveform.from_numpy(frame_rate_hz=8000, arr=arr[1000:9000])
+    for start in [0, 400, 1000, 3000]:
+        second = Waveform.from_numpy(frame_rate_hz=8000, arr=arr[start : start + 8000])
+        lag_frames, confidence = first.find_offset(second, max_lag_frames=2500)
+        assert lag_frames == 1000 - start
+        assert confidence > 0.5
+
+
+def test_find_offset_different_frame_rates():
+    first = Waveform.from_numpy(frame_rate_hz=8000, arr=noise(100))
+    second = Waveform.from_numpy(frame_rate_hz=16000, arr=noise(100))
+    with pytest.raises(babycat.exceptions.CannotAlignWithDifferentFrameRates):
+        first.find_offset(second, max_lag_frames=10)
+
+
+def test_align_trims_to_common_span():
+    arr = noise(30000)
+    waveforms = [
+        Waveform.from_numpy(frame_rate_hz=8000, arr=arr[2000:20000]),
+        Waveform.from_numpy(frame_rate_hz=8000, arr=arr[0:15000]),
+        Waveform.from_numpy(frame_rate_hz=8000, arr=arr[5000:30000]),
+    ]
+    aligned = Waveform.align(waveforms, max_lag_frames=8000)
+    assert len(aligned) == 3
+    for waveform in aligned:
+        np.testing.assert_array_equal(waveform.to_numpy(), arr[5000:15000])
+
+
+def test_align_and_stack():
+    stereo = noise(12000, num_channels=2, seed=1)
+    first = Waveform.from_numpy(frame_rate_hz=8000, arr=stereo[0:10000])
+    second = Waveform.from_numpy(frame_rate_hz=8000, arr=stereo[2000:12000, :1])
+    stacked = Waveform.align_and_stack([first, second], max_lag_frames=4000)
+    assert stacked.num_channels == 3
+    assert stacked.num_frames == 8000
+    arr = stacked.to_numpy()
+    np.testing.assert_array_equal(arr[:, :2], stereo[2000:10000])
+    np.testing.assert_array_equal(arr[:, 2], stereo[2000:10000, 0])
+
+
+def test_align_and_stack_without_waveforms():
+    with pytest.raises(babycat.exceptions.CannotAlignWithoutWaveforms):
+        Waveform.align_and_stack([], max_lag_frames=100)
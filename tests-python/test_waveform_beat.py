@@ -0,0 +1,90 @@
+import json
+
+import numpy as np
+import pytest
+
+import babycat
+from babycat import Waveform
+
+
+def click_track(frame_rate_hz, bpm, num_frames, offset_frames=0):
+    arr = np.zeros((num_frames, 1), dtype="float32")
+    period = 60.0 * frame_rate_hz / bpm
+    burst_len = frame_rate_hz // 50
+    t = np.arange(burst_len) / frame_rate_hz
+    burst = 0.8 * np.exp(-t * 200.0) * np.sin(2.0 * np.pi * 1000.0 * t)
+    start = float(offset_frames)
+    while start < num_frames:
+        idx = int(round(start))
+        end = min(idx + burst_len, num_frames)
+        arr[idx:end, 0] = burst[: end - idx]
+        start += period
+    return arr
+
+
+def test_onset_strength_shape():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=22050,
+        num_channels=2,
+        num_frames=22050,
+    )
+    envelope = waveform.onset_strength()
+    assert envelope.shape == (44,)
+    assert envelope.dtype == np.float32
+    assert not envelope.any()
+    assert waveform.onset_strength(hop_length=256).shape == (87,)
+
+
+@pytest.mark.parametrize("bpm", [90.0, 120.0, 150.0])
+def test_tempo_of_click_tracks(bpm):
+    waveform = Waveform.from_numpy(
+        frame_rate_hz=22050,
+        arr=click_track(22050, bpm, 22050 * 15),
+    )
+    estimate, confidence = waveform.tempo()
+    assert estimate == pytest.approx(bpm, abs=1.5)
+    assert 0.5 < confidence <= 1.0
+
+
+def test_beat_track_lines_up_with_clicks():
+    offset_frames = 13230
+    period = 60.0 * 44100 / 110.0
+    waveform = Waveform.from_numpy(
+        frame_rate_hz=44100,
+        arr=click_track(44100, 110.0, 44100 * 12, offset_frames),
+    )
+    track = waveform.beat_track()
+    assert track.frame_rate_hz == 44100
+    assert track.bpm == pytest.approx(110.0, abs=1.5)
+    frames = np.array(track.beat_frame_idxs)
+    beats_in = (frames - offset_frames) / period
+    assert np.all(np.abs(beats_in - np.round(beats_in)) * period <= 512)
+    assert track.beat_milliseconds == [f * 1000 // 44100 for f in track.beat_frame_idxs]
+    parsed = json.loads(track.to_json())
+    assert [beat["frame_idx"] for beat in parsed["beats"]] == track.beat_frame_idxs
+
+
+def test_silence_has_no_beats():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=44100,
+        num_channels=2,
+        num_frames=44100 * 5,
+    )
+    assert waveform.tempo() == (0.0, 0.0)
+    track = waveform.beat_track()
+    assert track.beat_frame_idxs == []
+    assert repr(track) == "<babycat.BeatTrack: bpm=0.0 confidence=0.00 0 beats>"
+
+
+def test_wrong_tempo():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=22050,
+        num_channels=1,
+        num_frames=22050,
+    )
+    with pytest.raises(babycat.exceptions.WrongTempo):
+        waveform.tempo(min_bpm=0.0)
+    with pytest.raises(babycat.exceptions.WrongTempo):
+        waveform.beat_track(start_bpm=400.0)
+    with pytest.raises(babycat.exceptions.WrongHopLength):
+        waveform.beat_track(hop_length=0)
@@ -0,0 +1,66 @@
+import numpy as np
+import pytest
+
+import babycat
+from babycat import Waveform
+
+
+def sine(frame_rate_hz, freq_hz, num_frames):
+    t = np.arange(num_frames, dtype="float64") / frame_rate_hz
+    return (0.5 * np.sin(2.0 * np.pi * freq_hz * t)).astype("float32")
+
+
+def test_cqt_shape():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=22050,
+        num_channels=2,
+        num_frames=22050,
+    )
+    cqt = waveform.cqt()
+    assert cqt.shape == (2, 84, 44)
+    assert cqt.dtype == np.float32
+    assert waveform.cqt(hop_length=256, channel_idx=1).shape == (84, 87)
+
+
+def test_cqt_sine():
+    waveform = Waveform.from_numpy(
+        frame_rate_hz=22050,
+        arr=sine(22050, 440.0, 22050).reshape(-1, 1),
+    )
+    cqt = waveform.cqt(channel_idx=0)
+    assert int(cqt[:, 22].argmax()) == 45
+    assert cqt[45, 22] == pytest.approx(0.25, abs=0.01)
+    cqt = waveform.cqt(bins_per_octave=24, num_bins=168, channel_idx=0)
+    assert int(cqt[:, 22].argmax()) == 90
+
+
+def test_cqt_wrong_args():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=22050,
+        num_channels=1,
+        num_frames=22050,
+    )
+    with pytest.raises(babycat.exceptions.WrongHopLength):
+        waveform.cqt(hop_length=0)
+    with pytest.raises(babycat.exceptions.WrongBinsPerOctave):
+        waveform.cqt(bins_per_octave=0)
+    with pytest.raises(babycat.exceptions.WrongNumFeatures):
+        waveform.cqt(num_bins=0)
+    with pytest.raises(babycat.exceptions.WrongFilterFrequency):
+        waveform.cqt(num_bins=120)
+    with pytest.raises(babycat.exceptions.WrongNumChannels):
+        waveform.cqt(channel_idx=1)
+
+
+def test_chroma():
+    # C4 on the left channel and G4 on the right.
+    arr = np.stack(
+        [sine(22050, 261.63, 22050), sine(22050, 392.0, 22050)],
+        axis=1,
+    )
+    waveform = Waveform.from_numpy(frame_rate_hz=22050, arr=arr)
+    chroma = waveform.chroma()
+    assert chroma.shape == (2, 12, 44)
+    assert int(chroma[0, :, 22].argmax()) == 0
+    assert int(chroma[1, :, 22].argmax()) == 7
+    np.testing.assert_allclose(chroma.max(axis=1), 1.0, rtol=1e-6)
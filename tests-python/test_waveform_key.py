@@ -0,0 +1,69 @@
+import json
+
+import numpy as np
+
+from babycat import Waveform
+
+MAJOR_SCALE = [0, 2, 4, 5, 7, 9, 11]
+HARMONIC_MINOR_SCALE = [0, 2, 3, 5, 7, 8, 11]
+
+
+def scale(tonic, steps, frame_rate_hz=22050):
+    notes = [(tonic + step) % 12 for step in steps] + [tonic]
+    t = np.arange(frame_rate_hz // 4) / frame_rate_hz
+    arr = np.concatenate(
+        [0.5 * np.sin(2 * np.pi * 261.63 * 2 ** (note / 12) * t) for note in notes]
+    )
+    return Waveform.from_numpy(
+        frame_rate_hz=frame_rate_hz, arr=arr.astype("float32").reshape(-1, 1)
+    )
+
+
+def test_every_major_key():
+    for tonic in range(12):
+        key = scale(tonic, MAJOR_SCALE).key()
+        assert key.tonic == tonic
+        assert key.mode == "major"
+        assert key.confidence > 0.7
+
+
+def test_every_minor_key():
+    for tonic in range(12):
+        key = scale(tonic, HARMONIC_MINOR_SCALE).key()
+        assert key.tonic == tonic
+        assert key.mode == "minor"
+
+
+def test_names_and_camelot():
+    key = scale(0, MAJOR_SCALE).key()
+    assert key.name == "C major"
+    assert key.camelot == "8B"
+    key = scale(9, HARMONIC_MINOR_SCALE).key()
+    assert key.name == "A minor"
+    assert key.camelot == "8A"
+
+
+def test_correlations():
+    key = scale(7, MAJOR_SCALE).key()
+    assert len(key.correlations) == 24
+    assert int(np.argmax(key.correlations)) == 7
+    assert key.confidence == max(key.correlations)
+
+
+def test_to_json():
+    key = scale(2, HARMONIC_MINOR_SCALE).key()
+    parsed = json.loads(key.to_json())
+    assert parsed["name"] == "D minor"
+    assert parsed["mode"] == "minor"
+    assert parsed["camelot"] == "7A"
+    assert len(parsed["correlations"]) == 24
+    assert "D minor" in repr(key)
+
+
+def test_silence_has_no_key():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=22050,
+        num_channels=2,
+        num_frames=22050,
+    )
+    assert waveform.key() is None
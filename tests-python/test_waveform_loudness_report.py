@@ -0,0 +1,44 @@
+"""
+Tests measuring the loudness of waveforms.
+
+These tests mirror the ones in ``../tests/test_loudness.rs``
+"""
+import json
+
+import numpy as np
+import pytest
+from fixtures import *
+
+import babycat
+
+Waveform = babycat.Waveform
+
+
+def test_mono_dtmf_loudness_report_1():
+    waveform = Waveform.from_file(MONO_DTMF_FILENAME)
+    report = waveform.loudness_report()
+    assert report.frame_rate_hz == MONO_DTMF_FRAME_RATE_HZ
+    assert report.num_channels == MONO_DTMF_NUM_CHANNELS
+    assert report.num_frames == MONO_DTMF_NUM_FRAMES
+    assert report.integrated_lufs is not None
+    peak = np.max(np.abs(waveform.to_numpy()))
+    assert report.sample_peaks_dbfs[0] == pytest.approx(20 * np.log10(peak), abs=1e-3)
+    assert report.true_peaks_dbtp[0] >= report.sample_peaks_dbfs[0]
+
+
+def test_silence_loudness_report_1():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=44100, num_channels=2, num_frames=44100
+    )
+    report = waveform.loudness_report()
+    assert report.integrated_lufs is None
+    assert report.loudness_range_lu is None
+    assert report.sample_peaks_dbfs == [-144.0, -144.0]
+
+
+def test_loudness_report_to_json_1():
+    waveform = Waveform.from_file(MONO_DTMF_FILENAME)
+    report = waveform.loudness_report()
+    parsed = json.loads(report.to_json())
+    assert parsed["num_frames"] == report.num_frames
+    assert parsed["integrated_lufs"] == pytest.approx(report.integrated_lufs)
@@ -0,0 +1,105 @@
+import numpy as np
+import pytest
+
+import babycat
+from babycat import Waveform
+
+LOG_SWEEP = "audio-for-tests/log-sweep/pcm-16.flac"
+
+
+def sine(frame_rate_hz, freq_hz, num_frames):
+    t = np.arange(num_frames, dtype="float32") / frame_rate_hz
+    return (0.5 * np.sin(2.0 * np.pi * freq_hz * t)).astype("float32")
+
+
+def test_mel_spectrogram_shape():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=22050,
+        num_channels=2,
+        num_frames=22050,
+    )
+    mel = waveform.mel_spectrogram()
+    assert mel.shape == (2, 128, 44)
+    assert mel.dtype == np.float32
+    assert waveform.mel_spectrogram(num_mels=40, channel_idx=1).shape == (40, 44)
+
+
+def test_mel_spectrogram_to_db():
+    waveform = Waveform.from_numpy(
+        frame_rate_hz=16000,
+        arr=sine(16000, 1000.0, 16000).reshape(-1, 1),
+    )
+    mel = waveform.mel_spectrogram(num_mels=40)
+    log_mel = waveform.mel_spectrogram(num_mels=40, to_db=True, top_db=None)
+    np.testing.assert_allclose(
+        log_mel, 10.0 * np.log10(np.maximum(mel, 1e-10)), rtol=1e-4, atol=1e-3
+    )
+    clipped = waveform.mel_spectrogram(num_mels=40, to_db=True)
+    assert clipped.min() == pytest.approx(clipped.max() - 80.0, abs=1e-3)
+
+
+def test_mel_spectrogram_htk():
+    waveform = Waveform.from_file(LOG_SWEEP)
+    slaney = waveform.mel_spectrogram(num_mels=40, channel_idx=0)
+    htk = waveform.mel_spectrogram(
+        num_mels=40,
+        mel_scale=babycat.mel_scale.MEL_SCALE_HTK,
+        channel_idx=0,
+    )
+    assert slaney.shape == htk.shape
+    assert not np.allclose(slaney, htk)
+
+
+def test_mel_spectrogram_wrong_args():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=16000,
+        num_channels=1,
+        num_frames=16000,
+    )
+    with pytest.raises(babycat.exceptions.WrongNumFeatures):
+        waveform.mel_spectrogram(num_mels=0)
+    with pytest.raises(babycat.exceptions.WrongFilterFrequency):
+        waveform.mel_spectrogram(max_frequency_hz=9000.0)
+    with pytest.raises(babycat.exceptions.FeatureNotCompiled):
+        waveform.mel_spectrogram(mel_scale=99)
+
+
+def test_mfcc_is_orthonormal_dct():
+    waveform = Waveform.from_file(LOG_SWEEP)
+    log_mel = waveform.mel_spectrogram(num_mels=40, to_db=True)
+    mfcc = waveform.mfcc(num_mels=40, num_mfcc=40)
+    assert mfcc.shape == log_mel.shape
+    np.testing.assert_allclose(
+        (mfcc**2).sum(axis=1), (log_mel**2).sum(axis=1), rtol=1e-3
+    )
+    np.testing.assert_allclose(
+        mfcc[:, 0, :], log_mel.sum(axis=1) / np.sqrt(40), rtol=1e-3, atol=1e-2
+    )
+    first_20 = waveform.mfcc(num_mels=40)
+    np.testing.assert_array_equal(first_20, mfcc[:, :20, :])
+
+
+def test_mfcc_deltas():
+    waveform = Waveform.from_file(LOG_SWEEP)
+    mfcc = waveform.mfcc(num_mfcc=13, channel_idx=0)
+    deltas = waveform.mfcc(num_mfcc=13, delta_order=1, channel_idx=0)
+    assert deltas.shape == mfcc.shape
+    slope = np.arange(-4, 5, dtype="float32") / 60.0
+    expected = mfcc[:, 96:105] @ slope
+    np.testing.assert_allclose(deltas[:, 100], expected, rtol=1e-3, atol=1e-3)
+    # The edges use the first full window.
+    np.testing.assert_array_equal(deltas[:, 0], deltas[:, 4])
+
+
+def test_mfcc_wrong_args():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=16000,
+        num_channels=1,
+        num_frames=16000,
+    )
+    with pytest.raises(babycat.exceptions.WrongNumFeatures):
+        waveform.mfcc(num_mels=40, num_mfcc=41)
+    with pytest.raises(babycat.exceptions.WrongDeltaWidth):
+        waveform.mfcc(delta_order=1, delta_width=4)
+    with pytest.raises(babycat.exceptions.WrongDeltaOrder):
+        waveform.mfcc(delta_order=3, delta_width=3)
@@ -0,0 +1,41 @@
+"""
+Tests normalizing waveforms while decoding.
+
+These tests mirror the ones in ``../tests/test_normalize.rs``
+"""
+import numpy as np
+import pytest
+from fixtures import *
+
+import babycat
+from babycat.normalize_mode import *
+
+Waveform = babycat.Waveform
+bexc = babycat.exceptions
+
+
+def test_mono_dtmf_normalize_peak_1():
+    waveform = Waveform.from_file(
+        MONO_DTMF_FILENAME,
+        normalize_mode=NORMALIZE_MODE_PEAK,
+        normalize_target=-6.0,
+    )
+    assert waveform.num_frames == MONO_DTMF_NUM_FRAMES
+    peak = np.max(np.abs(waveform.to_numpy()))
+    assert peak == pytest.approx(10 ** (-6.0 / 20.0), rel=1e-4)
+
+
+def test_mono_dtmf_normalize_loudness_ceiling_1():
+    waveform = Waveform.from_file(
+        MONO_DTMF_FILENAME,
+        normalize_mode=NORMALIZE_MODE_LOUDNESS,
+        normalize_target=0.0,
+        normalize_true_peak_ceiling_dbtp=-3.0,
+    )
+    peak = np.max(np.abs(waveform.to_numpy()))
+    assert peak <= 10 ** (-3.0 / 20.0)
+
+
+def test_mono_dtmf_unknown_normalize_mode_1():
+    with pytest.raises(bexc.FeatureNotCompiled):
+        Waveform.from_file(MONO_DTMF_FILENAME, normalize_mode=1000)
@@ -0,0 +1,99 @@
+import numpy as np
+import pytest
+from fixtures import *
+
+import babycat
+from babycat import Waveform
+from babycat.onset_function import *
+
+NOTE_STARTS = [22050, 44100, 66150, 88200]
+
+
+def melody(frame_rate_hz):
+    arr = np.zeros((44100 * 5 // 2, 1), dtype="float32")
+    t = np.arange(22050) / frame_rate_hz
+    for start, freq_hz in zip(NOTE_STARTS, [440.0, 554.37, 659.26, 880.0]):
+        note = 0.5 * np.exp(-t * 6.0) * np.sin(2.0 * np.pi * freq_hz * t)
+        arr[start : start + len(note), 0] = note
+    return Waveform.from_numpy(frame_rate_hz=frame_rate_hz, arr=arr)
+
+
+@pytest.mark.parametrize(
+    "onset_function",
+    [
+        ONSET_FUNCTION_SPECTRAL_FLUX,
+        ONSET_FUNCTION_HIGH_FREQUENCY_CONTENT,
+        ONSET_FUNCTION_COMPLEX_DOMAIN,
+    ],
+)
+def test_every_function_finds_the_notes(onset_function):
+    waveform = melody(44100)
+    frame_idxs = waveform.onsets(onset_function=onset_function)
+    assert len(frame_idxs) == 4
+    assert np.all(np.abs(np.array(frame_idxs) - NOTE_STARTS) <= 1024)
+    assert all(frame_idx % 512 == 0 for frame_idx in frame_idxs)
+    envelope = waveform.onset_strength(onset_function=onset_function)
+    assert envelope.shape == (1 + waveform.num_frames // 512,)
+
+
+def test_default_onset_function():
+    waveform = melody(44100)
+    assert DEFAULT_ONSET_FUNCTION == 0
+    assert waveform.onsets() == waveform.onsets(
+        onset_function=ONSET_FUNCTION_SPECTRAL_FLUX
+    )
+
+
+def test_backtrack():
+    waveform = melody(44100)
+    forward = np.array(waveform.onsets())
+    backward = np.array(waveform.onsets(backtrack=True))
+    assert len(forward) == len(backward)
+    assert np.all(backward <= forward)
+    assert np.all(forward - backward <= 2048)
+
+
+def test_split_at_onsets():
+    waveform = melody(44100)
+    frame_idxs = waveform.onsets()
+    pieces = waveform.split_at_onsets()
+    assert len(pieces) == len(frame_idxs)
+    arr = waveform.to_numpy()
+    for piece, start in zip(pieces, frame_idxs):
+        assert piece.frame_rate_hz == 44100
+        assert piece.num_channels == 1
+        np.testing.assert_array_equal(
+            piece.to_numpy(), arr[start : start + piece.num_frames]
+        )
+    assert sum(piece.num_frames for piece in pieces) == waveform.num_frames - frame_idxs[0]
+
+
+def test_dtmf_tones():
+    waveform = Waveform.from_file(MONO_DTMF_FILENAME)
+    frame_idxs = waveform.onsets()
+    assert len(frame_idxs) == 6
+    assert np.all((np.diff(frame_idxs) >= 63_000) & (np.diff(frame_idxs) <= 66_000))
+
+
+def test_steady_tone_has_no_onsets():
+    waveform = Waveform.from_file(LCT_FILENAME)
+    assert all(frame_idx <= 1024 for frame_idx in waveform.onsets())
+
+
+def test_silence_has_no_onsets():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=44100,
+        num_channels=2,
+        num_frames=44100,
+    )
+    assert waveform.onsets() == []
+    assert waveform.split_at_onsets() == []
+    assert not waveform.onset_strength(onset_function=ONSET_FUNCTION_COMPLEX_DOMAIN).any()
+
+
+def test_unknown_onset_function():
+    waveform = melody(22050)
+    with pytest.raises(babycat.exceptions.FeatureNotCompiled):
+        waveform.onsets(onset_function=99)
+    with pytest.raises(babycat.exceptions.FeatureNotCompiled):
+        waveform.onset_strength(onset_function=99)
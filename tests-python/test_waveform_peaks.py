@@ -0,0 +1,85 @@
+"""
+Tests summarizing waveforms into peaks for visualization.
+
+These tests mirror the ones in ``../tests/test_peaks.rs``
+"""
+import numpy as np
+import pytest
+from fixtures import *
+
+import babycat
+
+Waveform = babycat.Waveform
+
+
+def brute_force(waveform, frames_per_bucket):
+    arr = waveform.to_numpy()
+    mins, maxs, rmss = [], [], []
+    for start in range(0, waveform.num_frames, frames_per_bucket):
+        bucket = arr[start : start + frames_per_bucket].astype("float64")
+        mins.append(bucket.min(axis=0))
+        maxs.append(bucket.max(axis=0))
+        rmss.append(np.sqrt(np.mean(bucket**2, axis=0)))
+    return np.array(mins).T, np.array(maxs).T, np.array(rmss).T
+
+
+def test_matches_brute_force_1():
+    waveform = Waveform.from_file(MONO_DTMF_FILENAME)
+    for frames_per_bucket in [1, 256, 4410, 1_000_000]:
+        peaks = waveform.peaks(frames_per_bucket=frames_per_bucket)
+        mins, maxs, rmss = brute_force(waveform, frames_per_bucket)
+        assert peaks.frame_rate_hz == MONO_DTMF_FRAME_RATE_HZ
+        assert peaks.num_channels == MONO_DTMF_NUM_CHANNELS
+        assert peaks.num_frames == MONO_DTMF_NUM_FRAMES
+        assert peaks.frames_per_bucket == frames_per_bucket
+        assert peaks.num_buckets == -(-MONO_DTMF_NUM_FRAMES // frames_per_bucket)
+        assert peaks.min.shape == (MONO_DTMF_NUM_CHANNELS, peaks.num_buckets)
+        np.testing.assert_array_equal(peaks.min, mins)
+        np.testing.assert_array_equal(peaks.max, maxs)
+        np.testing.assert_allclose(peaks.rms, rmss, atol=1e-6)
+
+
+def test_left_channel_tone_1():
+    peaks = Waveform.from_file(LCT_FILENAME).peaks_with_num_buckets(num_buckets=800)
+    assert peaks.num_buckets <= 800
+    assert peaks.max.shape == (LCT_NUM_CHANNELS, peaks.num_buckets)
+    assert np.all(peaks.max[0] > 0.1)
+    assert np.all(np.abs(peaks.min[1]) < 0.001)
+    assert np.all(np.abs(peaks.max[1]) < 0.001)
+
+
+def test_bytes_round_trip_1():
+    peaks = Waveform.from_file(LCT_FILENAME).peaks(frames_per_bucket=1024)
+    data = peaks.to_bytes()
+    assert isinstance(data, bytes)
+    assert data[:4] == b"BCPK"
+    assert len(data) == 28 + 3 * 2 * LCT_NUM_CHANNELS * peaks.num_buckets
+    parsed = babycat.Peaks.from_bytes(data)
+    assert parsed.frame_rate_hz == peaks.frame_rate_hz
+    assert parsed.num_channels == peaks.num_channels
+    assert parsed.num_frames == peaks.num_frames
+    assert parsed.frames_per_bucket == peaks.frames_per_bucket
+    for attr in ["min", "max", "rms"]:
+        np.testing.assert_allclose(
+            getattr(parsed, attr), getattr(peaks, attr), atol=0.5 / 32767
+        )
+
+
+def test_malformed_bytes_1():
+    with pytest.raises(babycat.exceptions.CannotParsePeaks):
+        babycat.Peaks.from_bytes(b"not a peak summary")
+    data = Waveform.from_file(MONO_DTMF_FILENAME).peaks(frames_per_bucket=4410).to_bytes()
+    with pytest.raises(babycat.exceptions.CannotParsePeaks):
+        babycat.Peaks.from_bytes(data[:-1])
+
+
+def test_wrong_frames_per_bucket_1():
+    waveform = Waveform.from_file(MONO_DTMF_FILENAME)
+    with pytest.raises(babycat.exceptions.WrongFramesPerBucket):
+        waveform.peaks(frames_per_bucket=0)
+
+
+def test_wrong_num_buckets_1():
+    waveform = Waveform.from_file(MONO_DTMF_FILENAME)
+    with pytest.raises(babycat.exceptions.WrongNumBuckets):
+        waveform.peaks_with_num_buckets(num_buckets=0)
@@ -0,0 +1,87 @@
+import numpy as np
+import pytest
+
+import babycat
+from babycat import Waveform
+from babycat.pitch_method import *
+
+
+def sweep(frame_rate_hz, start_hz, end_hz, duration_seconds, silence_seconds):
+    rate = np.log(end_hz / start_hz) / duration_seconds
+    num_frames = int(frame_rate_hz * (duration_seconds + 2 * silence_seconds))
+    t = np.arange(num_frames) / frame_rate_hz - silence_seconds
+    inside = (t >= 0) & (t < duration_seconds)
+    phase = 2 * np.pi * start_hz * (np.exp(rate * t) - 1) / rate
+    arr = np.where(inside, 0.5 * np.sin(phase), 0.0).astype("float32")
+    waveform = Waveform.from_numpy(frame_rate_hz=frame_rate_hz, arr=arr.reshape(-1, 1))
+
+    def frequency_at(time_seconds):
+        t = time_seconds - silence_seconds
+        return np.where((t >= 0) & (t < duration_seconds), start_hz * np.exp(rate * t), np.nan)
+
+    return waveform, frequency_at
+
+
+@pytest.mark.parametrize("method", [PITCH_METHOD_PYIN, PITCH_METHOD_YIN])
+@pytest.mark.parametrize("start_hz,end_hz", [(110.0, 880.0), (1000.0, 100.0)])
+def test_sweeps_are_tracked(method, start_hz, end_hz):
+    waveform, frequency_at = sweep(22050, start_hz, end_hz, 2.0, 0.5)
+    contour = waveform.pitch(method=method)
+    assert contour.dtype.names == ("frame_idx", "milliseconds", "f0_hz", "voiced_prob")
+    assert contour["frame_idx"].dtype == np.uint64
+    assert contour["f0_hz"].dtype == np.float32
+    assert len(contour) == 1 + waveform.num_frames // 512
+    np.testing.assert_array_equal(contour["frame_idx"], np.arange(len(contour)) * 512)
+    np.testing.assert_array_equal(
+        contour["milliseconds"], np.arange(len(contour)) * 512 * 1000 // 22050
+    )
+
+    time = contour["frame_idx"] / 22050
+    expected = frequency_at(time)
+    # Leave out the frames that hear both the sweep and the silence.
+    hears_edge = np.isnan(frequency_at(time - 0.1)) != np.isnan(frequency_at(time + 0.1))
+    inside = ~np.isnan(expected) & ~hears_edge
+    outside = np.isnan(expected) & ~hears_edge
+    cents = 1200 * np.abs(np.log2(contour["f0_hz"][inside] / expected[inside]))
+    assert np.all(cents < 20.0)
+    assert np.all(contour["voiced_prob"][inside] > 0.9)
+    assert np.all(np.isnan(contour["f0_hz"][outside]))
+    assert np.all(contour["voiced_prob"][outside] == 0.0)
+
+
+def test_frequency_bounds():
+    t = np.arange(22050) / 22050
+    arr = (0.5 * np.sin(2 * np.pi * 440.0 * t)).astype("float32").reshape(-1, 1)
+    waveform = Waveform.from_numpy(frame_rate_hz=22050, arr=arr)
+    f0_hz = waveform.pitch(max_frequency_hz=300.0)["f0_hz"]
+    voiced = f0_hz[~np.isnan(f0_hz)]
+    assert len(voiced) > 0
+    np.testing.assert_allclose(voiced, 220.0, rtol=0.005)
+
+
+def test_hop_length():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=22050,
+        num_channels=2,
+        num_frames=22050,
+    )
+    contour = waveform.pitch(hop_length=256)
+    assert contour.shape == (87,)
+    assert np.all(np.isnan(contour["f0_hz"]))
+    assert not contour["voiced_prob"].any()
+
+
+def test_wrong_args():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=44100,
+        num_channels=1,
+        num_frames=44100,
+    )
+    with pytest.raises(babycat.exceptions.WrongHopLength):
+        waveform.pitch(hop_length=0)
+    with pytest.raises(babycat.exceptions.WrongFilterFrequency):
+        waveform.pitch(min_frequency_hz=500.0, max_frequency_hz=400.0)
+    with pytest.raises(babycat.exceptions.WrongFrameLength):
+        waveform.pitch(min_frequency_hz=30.0)
+    with pytest.raises(babycat.exceptions.FeatureNotCompiled):
+        waveform.pitch(method=99)
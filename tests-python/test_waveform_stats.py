@@ -0,0 +1,56 @@
+"""
+Tests measuring the per-channel statistics of waveforms.
+
+These tests mirror the ones in ``../tests/test_stats.rs``
+"""
+import json
+
+import numpy as np
+import pytest
+from fixtures import *
+
+import babycat
+
+Waveform = babycat.Waveform
+
+
+def tone(num_frames, amplitude):
+    t = np.arange(num_frames) / 44100
+    return (amplitude * np.sin(2 * np.pi * 440 * t)).astype("float32")
+
+
+def test_left_channel_tone_stats_1():
+    report = Waveform.from_file(LCT_FILENAME).stats()
+    assert report.frame_rate_hz == LCT_FRAME_RATE_HZ
+    assert report.num_channels == LCT_NUM_CHANNELS
+    assert report.num_frames == LCT_NUM_FRAMES
+    assert report.silent_channels == [1]
+    assert report.left_only
+    assert not report.right_only
+    assert report.channels[1].silence_percent == 100.0
+
+
+def test_sine_stats_1():
+    arr = tone(44100, 0.5).reshape(-1, 1)
+    report = Waveform.from_numpy(frame_rate_hz=44100, arr=arr).stats()
+    channel = report.channels[0]
+    assert channel.peak_dbfs == pytest.approx(-6.02, abs=0.01)
+    assert channel.rms_dbfs == pytest.approx(-9.03, abs=0.01)
+    assert channel.crest_factor_db == pytest.approx(3.01, abs=0.01)
+    assert channel.num_clipped_samples == 0
+
+
+def test_fake_stereo_and_inverted_stats_1():
+    left = tone(44100, 0.5)
+    arr = np.stack([left, left, -left], axis=1)
+    report = Waveform.from_numpy(frame_rate_hz=44100, arr=arr).stats()
+    assert report.identical_channels == [(0, 1)]
+    assert report.inverted_channels == [(0, 2), (1, 2)]
+
+
+def test_stats_to_json_1():
+    report = Waveform.from_file(LCT_FILENAME).stats()
+    parsed = json.loads(report.to_json())
+    assert parsed["left_only"] is True
+    assert parsed["silent_channels"] == [1]
+    assert len(parsed["channels"]) == 2
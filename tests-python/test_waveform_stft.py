@@ -0,0 +1,119 @@
+"""
+Tests short-time Fourier transforms of waveforms.
+
+These tests mirror the ones in ``../tests/test_stft.rs``
+"""
+import numpy as np
+import pytest
+
+import babycat
+from babycat.pad_mode import *
+from babycat.window_type import *
+
+Waveform = babycat.Waveform
+
+
+def sine(frame_rate_hz, freq_hz, num_frames):
+    t = np.arange(num_frames) / frame_rate_hz
+    return (0.5 * np.sin(2 * np.pi * freq_hz * t)).astype("float32")
+
+
+def test_stft_shape_1():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=44100,
+        num_channels=2,
+        num_frames=44100,
+    )
+    stft = waveform.stft()
+    assert stft.shape == (2, 1025, 87)
+    assert stft.dtype == np.complex64
+    assert waveform.stft(channel_idx=1).shape == (1025, 87)
+    assert waveform.stft(center=False).shape == (2, 1025, 83)
+
+
+def test_stft_wrong_channel_idx_1():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=44100,
+        num_channels=2,
+        num_frames=44100,
+    )
+    with pytest.raises(babycat.exceptions.WrongNumChannels):
+        waveform.stft(channel_idx=2)
+
+
+def test_stft_wrong_args_1():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=44100,
+        num_channels=1,
+        num_frames=100,
+    )
+    with pytest.raises(babycat.exceptions.WrongFftSize):
+        waveform.stft(fft_size=64, window_length=65)
+    with pytest.raises(babycat.exceptions.WrongHopLength):
+        waveform.stft(hop_length=0)
+    with pytest.raises(babycat.exceptions.FeatureNotCompiled):
+        waveform.stft(window_type=1000)
+
+
+def test_spectrogram_sine_peak_1():
+    waveform = Waveform.from_numpy(
+        frame_rate_hz=16000,
+        arr=sine(16000, 1000.0, 16000).reshape(-1, 1),
+    )
+    powers = waveform.spectrogram(fft_size=512, hop_length=128, channel_idx=0)
+    magnitudes = waveform.spectrogram(
+        fft_size=512, hop_length=128, power=1.0, channel_idx=0
+    )
+    stft = waveform.stft(fft_size=512, hop_length=128, channel_idx=0)
+    assert np.argmax(powers[:, powers.shape[1] // 2]) == 32
+    np.testing.assert_allclose(magnitudes, np.abs(stft), rtol=1e-5, atol=1e-5)
+    np.testing.assert_allclose(powers, np.abs(stft) ** 2, rtol=1e-4, atol=1e-4)
+
+
+@pytest.mark.parametrize(
+    "window_type,hop_length",
+    [
+        (WINDOW_TYPE_HANN, 64),
+        (WINDOW_TYPE_HAMMING, 128),
+        (WINDOW_TYPE_BLACKMAN, 64),
+        (WINDOW_TYPE_RECTANGULAR, 128),
+    ],
+)
+def test_from_stft_round_trip_1(window_type, hop_length):
+    arr = np.stack([sine(8000, 440.0, 4000), sine(8000, 1230.0, 4000)], axis=1)
+    waveform = Waveform.from_numpy(frame_rate_hz=8000, arr=arr)
+    stft = waveform.stft(
+        fft_size=256,
+        hop_length=hop_length,
+        window_type=window_type,
+        pad_mode=PAD_MODE_ZEROS,
+    )
+    inverse = Waveform.from_stft(
+        frame_rate_hz=8000,
+        stft=stft,
+        hop_length=hop_length,
+        window_type=window_type,
+        num_frames=4000,
+    )
+    assert inverse.num_channels == 2
+    np.testing.assert_allclose(inverse.to_numpy(), arr, atol=1e-4)
+
+
+def test_from_stft_single_channel_1():
+    # With a length that is a multiple of the hop length, the default
+    # ``num_frames`` gives back the original length.
+    arr = sine(8000, 440.0, 4032)
+    waveform = Waveform.from_numpy(frame_rate_hz=8000, arr=arr.reshape(-1, 1))
+    stft = waveform.stft(fft_size=256, hop_length=64, channel_idx=0)
+    inverse = Waveform.from_stft(frame_rate_hz=8000, stft=stft, hop_length=64)
+    assert inverse.num_channels == 1
+    assert inverse.num_frames == 4032
+    np.testing.assert_allclose(inverse.to_numpy()[:, 0], arr, atol=1e-4)
+
+
+def test_from_stft_wrong_shape_1():
+    stft = np.zeros((2, 129, 10), dtype="complex64")
+    with pytest.raises(babycat.exceptions.WrongStftShape):
+        Waveform.from_stft(frame_rate_hz=8000, stft=stft, num_frames=100)
+    with pytest.raises(TypeError):
+        Waveform.from_stft(frame_rate_hz=8000, stft=np.zeros(10, dtype="complex64"))
@@ -0,0 +1,61 @@
+"""
+Tests trimming silence from waveforms.
+
+These tests mirror the ones in ``../tests/test_silence.rs``
+"""
+import numpy as np
+from fixtures import *
+
+import babycat
+
+Waveform = babycat.Waveform
+
+
+def bursts():
+    samples = np.concatenate(
+        [
+            np.zeros(10),
+            np.full(20, 0.5),
+            np.zeros(5),
+            np.full(20, -0.5),
+            np.zeros(30),
+            np.full(10, 0.25),
+            np.zeros(10),
+        ]
+    ).astype("float32")
+    return Waveform.from_interleaved_samples(
+        frame_rate_hz=1000,
+        num_channels=1,
+        interleaved_samples=samples.tolist(),
+    )
+
+
+def test_trim_silence_1():
+    trimmed = bursts().trim_silence(threshold_dbfs=-60.0)
+    assert trimmed.num_frames == 85
+    samples = trimmed.to_numpy()
+    assert samples[0, 0] == 0.5
+    assert samples[-1, 0] == 0.25
+
+
+def test_trim_silence_all_silent_1():
+    waveform = Waveform.from_frames_of_silence(
+        frame_rate_hz=1000,
+        num_channels=2,
+        num_frames=100,
+    )
+    trimmed = waveform.trim_silence()
+    assert trimmed.num_frames == 0
+    assert trimmed.num_channels == 2
+
+
+def test_mono_dtmf_from_file_trim_silence_1():
+    waveform = Waveform.from_file(
+        MONO_DTMF_FILENAME,
+        trim_silence=True,
+        trim_silence_threshold_dbfs=-50.0,
+    )
+    assert waveform.num_channels == MONO_DTMF_NUM_CHANNELS
+    assert waveform.frame_rate_hz == MONO_DTMF_FRAME_RATE_HZ
+    expected = Waveform.from_file(MONO_DTMF_FILENAME).trim_silence(-50.0)
+    assert waveform.num_frames == expected.num_frames
@@ -40,6 +40,16 @@ pub const VR_NUM_CHANNELS: u16 = 2;
 pub const VR_NUM_FRAMES: usize = 5728367;
 pub const VR_FRAME_RATE_HZ: u32 = 44100;
 
+/// A 10-second logarithmic sine sweep from 20 hz to 20 khz.
+pub const LS_FILENAME: &str = "./audio-for-tests/log-sweep/pcm-24.flac";
+/// The same sweep, as 16-bit FLAC.
+pub const LS_PCM_16_FILENAME: &str = "./audio-for-tests/log-sweep/pcm-16.flac";
+pub const LS_NUM_CHANNELS: u16 = 2;
+pub const LS_NUM_FRAMES: usize = 441000;
+pub const LS_FRAME_RATE_HZ: u32 = 44100;
+pub const LS_START_FREQUENCY_HZ: f32 = 20.0;
+pub const LS_END_FREQUENCY_HZ: f32 = 20000.0;
+
 pub const ALL_FILENAMES: &[&str] = &[
     AT_FILENAME,
     BT_FILENAME,
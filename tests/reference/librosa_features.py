@@ -0,0 +1,298 @@
+"""Writes the librosa reference features that tests/test_mel.rs checks against.
+
+Run this from the root of the repository:
+
+    python3 tests/reference/librosa_features.py
+
+With librosa installed, the features come from librosa itself:
+
+    y, sr = librosa.load(path, sr=None, mono=False)
+    mel = librosa.feature.melspectrogram(
+        y=y, sr=sr, n_fft=2048, hop_length=512, window="hann",
+        center=True, pad_mode="reflect", power=2.0, n_mels=128,
+        fmin=0.0, fmax=sr / 2, htk=False, norm="slaney",
+    )
+    mfcc = librosa.feature.mfcc(
+        S=librosa.power_to_db(mel, ref=1.0, amin=1e-10, top_db=80.0),
+        n_mfcc=20, dct_type=2, norm="ortho", lifter=0,
+    )
+
+Without librosa, pass `--port` to compute the same features with the
+dependency-free port of that pipeline below, in double precision. The port
+reads WAV files, so decode the fixtures first:
+
+    babycat convert --input-filename audio-for-tests/log-sweep/pcm-16.flac \\
+        --output-filename /tmp/pcm-16.wav --output-format wav
+    python3 tests/reference/librosa_features.py --port \\
+        --wav audio-for-tests/log-sweep/pcm-16.flac=/tmp/pcm-16.wav
+
+Every output file records which of the two produced it.
+"""
+
+import argparse
+import cmath
+import json
+import math
+import os
+import struct
+
+FFT_SIZE = 2048
+HOP_LENGTH = 512
+NUM_MELS = 128
+NUM_MFCC = 20
+TOP_DB = 80.0
+AMIN = 1e-10
+
+FIXTURES = {
+    "log_sweep_pcm16": "audio-for-tests/log-sweep/pcm-16.flac",
+    "mono_dtmf": "audio-for-tests/mono-dtmf-tones/track.flac",
+}
+
+# The time steps that are written out. Negative values count from the end,
+# so the reflected padding at both edges is covered.
+TIME_STEPS = [0, 1, 2, 100, 250, 400, 550, 700, -2, -1]
+
+OUTPUT_DIR = os.path.dirname(os.path.abspath(__file__))
+
+
+def read_wav(path):
+    """Returns (frame_rate_hz, channels) for a 16-bit PCM or 32-bit float WAV file."""
+    with open(path, "rb") as f:
+        data = f.read()
+    if data[0:4] != b"RIFF" or data[8:12] != b"WAVE":
+        raise ValueError(f"{path} is not a WAV file")
+    pos = 12
+    fmt = None
+    samples = None
+    while pos + 8 <= len(data):
+        chunk_id = data[pos : pos + 4]
+        (chunk_len,) = struct.unpack("<I", data[pos + 4 : pos + 8])
+        body = data[pos + 8 : pos + 8 + chunk_len]
+        if chunk_id == b"fmt ":
+            fmt = struct.unpack("<HHIIHH", body[:16])
+        elif chunk_id == b"data":
+            samples = body
+        pos += 8 + chunk_len + (chunk_len & 1)
+    format_tag, num_channels, frame_rate_hz, _, _, bits = fmt
+    if bits == 16:
+        values = [v / 32768.0 for v in struct.unpack(f"<{len(samples) // 2}h", samples)]
+    elif bits == 32:
+        values = list(struct.unpack(f"<{len(samples) // 4}f", samples))
+    else:
+        raise ValueError(f"{path} has {bits}-bit samples (format {format_tag:#x})")
+    return frame_rate_hz, [values[c::num_channels] for c in range(num_channels)]
+
+
+def fft(values):
+    """An iterative radix-2 FFT of a list of complex numbers."""
+    n = len(values)
+    out = list(values)
+    j = 0
+    for i in range(1, n):
+        bit = n >> 1
+        while j & bit:
+            j ^= bit
+            bit >>= 1
+        j |= bit
+        if i < j:
+            out[i], out[j] = out[j], out[i]
+    size = 2
+    while size <= n:
+        step = cmath.exp(-2j * math.pi / size)
+        half = size // 2
+        twiddles = [step**k for k in range(half)]
+        for start in range(0, n, size):
+            for k in range(half):
+                a = out[start + k]
+                b = out[start + k + half] * twiddles[k]
+                out[start + k] = a + b
+                out[start + k + half] = a - b
+        size *= 2
+    return out
+
+
+def hz_to_mel(hz):
+    """The Slaney mel scale, as in librosa.hz_to_mel(htk=False)."""
+    f_sp = 200.0 / 3
+    min_log_hz = 1000.0
+    min_log_mel = min_log_hz / f_sp
+    logstep = math.log(6.4) / 27.0
+    if hz >= min_log_hz:
+        return min_log_mel + math.log(hz / min_log_hz) / logstep
+    return hz / f_sp
+
+
+def mel_to_hz(mel):
+    f_sp = 200.0 / 3
+    min_log_hz = 1000.0
+    min_log_mel = min_log_hz / f_sp
+    logstep = math.log(6.4) / 27.0
+    if mel >= min_log_mel:
+        return min_log_hz * math.exp(logstep * (mel - min_log_mel))
+    return f_sp * mel
+
+
+def mel_filterbank(frame_rate_hz):
+    """librosa.filters.mel(sr, n_fft=2048, n_mels=128, htk=False, norm="slaney")."""
+    num_bins = FFT_SIZE // 2 + 1
+    fft_hz = [i * frame_rate_hz / FFT_SIZE for i in range(num_bins)]
+    min_mel, max_mel = hz_to_mel(0.0), hz_to_mel(frame_rate_hz / 2.0)
+    mel_hz = [
+        mel_to_hz(min_mel + (max_mel - min_mel) * i / (NUM_MELS + 1))
+        for i in range(NUM_MELS + 2)
+    ]
+    filterbank = []
+    for m in range(NUM_MELS):
+        lower, center, upper = mel_hz[m], mel_hz[m + 1], mel_hz[m + 2]
+        enorm = 2.0 / (upper - lower)
+        row = {}
+        for b, hz in enumerate(fft_hz):
+            weight = max(0.0, min((hz - lower) / (center - lower), (upper - hz) / (upper - center)))
+            if weight > 0.0:
+                row[b] = weight * enorm
+        filterbank.append(row)
+    return filterbank
+
+
+def port_features(frame_rate_hz, channels):
+    """Returns (mel, mfcc), each indexed by [channel][feature][time step]."""
+    window = [0.5 - 0.5 * math.cos(2.0 * math.pi * i / FFT_SIZE) for i in range(FFT_SIZE)]
+    filterbank = mel_filterbank(frame_rate_hz)
+    pad = FFT_SIZE // 2
+    mels = []
+    for channel in channels:
+        padded = channel[pad:0:-1] + channel + channel[-2 : -pad - 2 : -1]
+        num_time_steps = 1 + (len(padded) - FFT_SIZE) // HOP_LENGTH
+        mel = [[0.0] * num_time_steps for _ in range(NUM_MELS)]
+        for t in range(num_time_steps):
+            start = t * HOP_LENGTH
+            spectrum = fft(
+                [complex(s * w, 0.0) for s, w in zip(padded[start : start + FFT_SIZE], window)]
+            )
+            power = [abs(v) ** 2 for v in spectrum[: FFT_SIZE // 2 + 1]]
+            for m, row in enumerate(filterbank):
+                mel[m][t] = sum(weight * power[b] for b, weight in row.items())
+        mels.append(mel)
+
+    # librosa.power_to_db() finds the loudest value across every channel.
+    db = [[[10.0 * math.log10(max(AMIN, v)) for v in row] for row in mel] for mel in mels]
+    floor = max(v for channel in db for row in channel for v in row) - TOP_DB
+    db = [[[max(v, floor) for v in row] for row in channel] for channel in db]
+    mfccs = []
+    for channel in db:
+        num_time_steps = len(channel[0])
+        mfcc = []
+        for k in range(NUM_MFCC):
+            norm = math.sqrt((1.0 if k == 0 else 2.0) / NUM_MELS)
+            basis = [
+                norm * math.cos(math.pi * k * (2 * m + 1) / (2 * NUM_MELS))
+                for m in range(NUM_MELS)
+            ]
+            mfcc.append(
+                [sum(b * channel[m][t] for m, b in enumerate(basis)) for t in range(num_time_steps)]
+            )
+        mfccs.append(mfcc)
+    return mels, mfccs
+
+
+def librosa_features(path):
+    import librosa
+    import numpy as np
+
+    y, sr = librosa.load(path, sr=None, mono=False)
+    y = np.atleast_2d(y)
+    mel = librosa.feature.melspectrogram(
+        y=y,
+        sr=sr,
+        n_fft=FFT_SIZE,
+        hop_length=HOP_LENGTH,
+        window="hann",
+        center=True,
+        pad_mode="reflect",
+        power=2.0,
+        n_mels=NUM_MELS,
+        fmin=0.0,
+        fmax=sr / 2,
+        htk=False,
+        norm="slaney",
+    )
+    mfcc = librosa.feature.mfcc(
+        S=librosa.power_to_db(mel, ref=1.0, amin=AMIN, top_db=TOP_DB),
+        n_mfcc=NUM_MFCC,
+        dct_type=2,
+        norm="ortho",
+        lifter=0,
+    )
+    generator = f"librosa {librosa.__version__}, numpy {np.__version__}"
+    return sr, mel.tolist(), mfcc.tolist(), generator
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("--port", action="store_true", help="Use the port instead of librosa")
+    parser.add_argument(
+        "--wav",
+        action="append",
+        default=[],
+        metavar="FIXTURE=WAV",
+        help="With --port, the decoded WAV file to read for a fixture",
+    )
+    args = parser.parse_args()
+    wavs = dict(pair.split("=", 1) for pair in args.wav)
+
+    for name, path in FIXTURES.items():
+        if args.port:
+            frame_rate_hz, channels = read_wav(wavs[path])
+            mel, mfcc = port_features(frame_rate_hz, channels)
+            generator = (
+                "dependency-free double-precision port of the librosa pipeline "
+                "in tests/reference/librosa_features.py, which mirrors librosa 0.10"
+            )
+        else:
+            frame_rate_hz, mel, mfcc, generator = librosa_features(path)
+        num_time_steps = len(mel[0][0])
+        time_steps = [t % num_time_steps for t in TIME_STEPS]
+        reference = {
+            "filename": path,
+            "generator": generator,
+            "parameters": {
+                "sr": frame_rate_hz,
+                "mono": False,
+                "n_fft": FFT_SIZE,
+                "hop_length": HOP_LENGTH,
+                "window": "hann",
+                "center": True,
+                "pad_mode": "reflect",
+                "power": 2.0,
+                "n_mels": NUM_MELS,
+                "fmin": 0.0,
+                "fmax": frame_rate_hz / 2,
+                "htk": False,
+                "norm": "slaney",
+                "power_to_db": {"ref": 1.0, "amin": AMIN, "top_db": TOP_DB},
+                "n_mfcc": NUM_MFCC,
+                "dct_type": 2,
+                "dct_norm": "ortho",
+                "lifter": 0,
+            },
+            "num_channels": len(mel),
+            "num_time_steps": num_time_steps,
+            "time_steps": time_steps,
+            "mel": [
+                [[float(f"{row[t]:.7g}") for row in channel] for t in time_steps]
+                for channel in mel
+            ],
+            "mfcc": [
+                [[float(f"{row[t]:.7g}") for row in channel] for t in time_steps]
+                for channel in mfcc
+            ],
+        }
+        output_path = os.path.join(OUTPUT_DIR, f"librosa_{name}.json")
+        with open(output_path, "w") as f:
+            json.dump(reference, f, indent=1)
+            f.write("\n")
+        print(f"Wrote {output_path} with {generator}")
+
+
+if __name__ == "__main__":
+    main()
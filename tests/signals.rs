@@ -0,0 +1,55 @@
+#![allow(dead_code)]
+
+/// Returns a mono sine wave.
+pub fn sine(frame_rate_hz: u32, frequency_hz: f32, amplitude: f32, num_frames: usize) -> Vec<f32> {
+    (0..num_frames)
+        .map(|frame_idx| {
+            amplitude
+                * (2.0 * std::f32::consts::PI * frequency_hz * frame_idx as f32
+                    / frame_rate_hz as f32)
+                    .sin()
+        })
+        .collect()
+}
+
+/// Deterministic white noise in `[-amplitude, amplitude)` from a
+/// linear congruential generator.
+pub fn noise(num_frames: usize, amplitude: f32, seed: u32) -> Vec<f32> {
+    let mut state = seed;
+    (0..num_frames)
+        .map(|_| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            amplitude * ((state >> 8) as f32 / (1 << 23) as f32 - 1.0)
+        })
+        .collect()
+}
+
+/// Interleaves `num_channels` copies of mono samples.
+pub fn duplicate_channels(samples: &[f32], num_channels: u16) -> Vec<f32> {
+    samples
+        .iter()
+        .flat_map(|sample| std::iter::repeat_n(*sample, num_channels as usize))
+        .collect()
+}
+
+pub fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "Expected {} but got {}",
+        expected,
+        actual
+    );
+}
+
+pub fn assert_all_close(actual: &[f32], expected: &[f32], tolerance: f32) {
+    assert_eq!(actual.len(), expected.len());
+    for (idx, (a, e)) in actual.iter().zip(expected).enumerate() {
+        assert!(
+            (a - e).abs() <= tolerance,
+            "Sample {} was {} but expected {}",
+            idx,
+            a,
+            e
+        );
+    }
+}
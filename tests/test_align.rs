@@ -0,0 +1,168 @@
+mod signals;
+
+mod test_align {
+    use crate::signals::noise;
+    use babycat::align::{align, align_and_stack, find_offset};
+    use babycat::{Error, Signal, Waveform, WaveformArgs};
+
+    const COF_MP3_FILENAME: &str = "./audio-for-tests/circus-of-freaks/track.mp3";
+
+    fn cof_segment(start_time_milliseconds: usize, end_time_milliseconds: usize) -> Waveform {
+        let waveform_args = WaveformArgs::default()
+            .set_start_time_milliseconds(start_time_milliseconds)
+            .set_end_time_milliseconds(end_time_milliseconds);
+        Waveform::from_file(COF_MP3_FILENAME, waveform_args).unwrap()
+    }
+
+    #[test]
+    fn test_find_offset_positive_and_negative_lags() {
+        let samples = noise(20000, 0.5, 1);
+        let first = Waveform::from_interleaved_samples(8000, 1, &samples[1000..9000]);
+        for start in [0, 400, 1000, 1777, 3000] {
+            let second = Waveform::from_interleaved_samples(8000, 1, &samples[start..start + 8000]);
+            let offset = first.find_offset(&second, 2500).unwrap();
+            assert_eq!(offset.lag_frames, 1000 - start as i64);
+            assert!(offset.confidence > 0.5, "{:?}", offset);
+        }
+    }
+
+    #[test]
+    fn test_find_offset_is_antisymmetric() {
+        let first = cof_segment(10000, 14000);
+        let second = cof_segment(10250, 14500);
+        let forward = find_offset(&first, &second, 22050).unwrap();
+        let backward = find_offset(&second, &first, 22050).unwrap();
+        assert_eq!(forward.lag_frames, -11025);
+        assert_eq!(backward.lag_frames, 11025);
+    }
+
+    #[test]
+    fn test_find_offset_survives_filtering_gain_and_noise() {
+        let first = cof_segment(10000, 14000);
+        let second = cof_segment(10100, 14000).lowpass(3000.0, 4).unwrap();
+        let hiss = noise(second.to_interleaved_samples().len(), 0.5, 7);
+        let second = Waveform::from_interleaved_samples(
+            second.frame_rate_hz(),
+            second.num_channels(),
+            &second
+                .to_interleaved_samples()
+                .iter()
+                .zip(&hiss)
+                .map(|(sample, hiss)| 0.3 * sample + 0.05 * hiss)
+                .collect::<Vec<f32>>(),
+        );
+        let offset = find_offset(&first, &second, 22050).unwrap();
+        // The lowpass filter delays the second waveform by a few frames.
+        assert!((offset.lag_frames + 4410).abs() <= 10, "{:?}", offset);
+        assert!(offset.confidence > 0.3, "{:?}", offset);
+    }
+
+    #[test]
+    fn test_find_offset_respects_max_lag() {
+        let samples = noise(20000, 0.5, 2);
+        let first = Waveform::from_interleaved_samples(8000, 1, &samples[0..8000]);
+        let second = Waveform::from_interleaved_samples(8000, 1, &samples[3000..11000]);
+        assert_eq!(
+            find_offset(&first, &second, 4000).unwrap().lag_frames,
+            -3000
+        );
+        let offset = find_offset(&first, &second, 1000).unwrap();
+        assert!(offset.lag_frames.abs() <= 1000);
+        assert!(offset.confidence < 0.5, "{:?}", offset);
+    }
+
+    #[test]
+    fn test_find_offset_with_empty_or_silent_waveforms() {
+        let samples = noise(1000, 0.5, 3);
+        let waveform = Waveform::from_interleaved_samples(8000, 1, &samples);
+        let empty = Waveform::from_interleaved_samples(8000, 1, &[]);
+        let silence = Waveform::from_interleaved_samples(8000, 1, &[0.0; 1000]);
+        for (first, second) in [
+            (&waveform, &empty),
+            (&empty, &waveform),
+            (&silence, &waveform),
+        ] {
+            let offset = find_offset(first, second, 100).unwrap();
+            assert_eq!(offset.lag_frames, 0);
+            assert_eq!(offset.confidence, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_find_offset_different_frame_rates() {
+        let first = Waveform::from_interleaved_samples(8000, 1, &[0.0; 100]);
+        let second = Waveform::from_interleaved_samples(16000, 1, &[0.0; 100]);
+        assert_eq!(
+            find_offset(&first, &second, 10),
+            Err(Error::CannotAlignWithDifferentFrameRates(8000, 16000))
+        );
+        assert_eq!(
+            align(&[first, second], 10),
+            Err(Error::CannotAlignWithDifferentFrameRates(8000, 16000))
+        );
+    }
+
+    #[test]
+    fn test_align_trims_to_common_span() {
+        let samples = noise(30000, 0.5, 4);
+        let waveforms = vec![
+            Waveform::from_interleaved_samples(8000, 1, &samples[2000..20000]),
+            Waveform::from_interleaved_samples(8000, 1, &samples[0..15000]),
+            Waveform::from_interleaved_samples(8000, 1, &samples[5000..30000]),
+        ];
+        let aligned = align(&waveforms, 8000).unwrap();
+        assert_eq!(aligned.len(), 3);
+        for waveform in &aligned {
+            assert_eq!(waveform.to_interleaved_samples(), &samples[5000..15000]);
+        }
+    }
+
+    #[test]
+    fn test_align_and_stack() {
+        let stereo = cof_segment(10000, 13000);
+        let mono = Waveform::from_interleaved_samples(
+            stereo.frame_rate_hz(),
+            1,
+            &cof_segment(10500, 14000)
+                .to_interleaved_samples()
+                .iter()
+                .step_by(2)
+                .copied()
+                .collect::<Vec<f32>>(),
+        );
+        let stacked = align_and_stack(&[stereo.clone(), mono.clone()], 44100).unwrap();
+        assert_eq!(stacked.num_channels(), 3);
+        assert_eq!(stacked.frame_rate_hz(), stereo.frame_rate_hz());
+        assert_eq!(stacked.num_frames(), stereo.num_frames() - 22050);
+        let stacked_samples = stacked.to_interleaved_samples();
+        for (frame_idx, frame) in stacked_samples.as_chunks::<3>().0.iter().enumerate() {
+            let stereo_frame = &stereo.to_interleaved_samples()[(frame_idx + 22050) * 2..];
+            assert_eq!(frame[0], stereo_frame[0]);
+            assert_eq!(frame[1], stereo_frame[1]);
+            assert_eq!(frame[2], mono.to_interleaved_samples()[frame_idx]);
+        }
+    }
+
+    #[test]
+    fn test_align_empty_input() {
+        assert_eq!(align(&[], 100).unwrap().len(), 0);
+        assert_eq!(
+            align_and_stack(&[], 100),
+            Err(Error::CannotAlignWithoutWaveforms)
+        );
+    }
+
+    #[test]
+    fn test_stack_too_many_channels() {
+        let wide = Waveform::from_frames_of_silence(44100, u16::MAX, 1);
+        let mono = Waveform::from_frames_of_silence(44100, 1, 1);
+        assert_eq!(
+            align_and_stack(&[wide.clone(), mono], 100),
+            Err(Error::CannotStackTooManyChannels(65536))
+        );
+        assert_eq!(
+            align_and_stack(&[wide], 100).unwrap().num_channels(),
+            u16::MAX
+        );
+    }
+}
@@ -0,0 +1,159 @@
+mod fixtures;
+
+mod test_beat {
+    use babycat::beat::{beat_track, tempo, tempo_from_onset_strength, BeatArgs};
+    use babycat::spectral::StftArgs;
+    use babycat::{Error, Waveform};
+
+    /// Returns a mono click track with a decaying 1 khz burst on every beat,
+    /// starting `offset_frames` into the audio.
+    fn click_track(
+        frame_rate_hz: u32,
+        bpm: f32,
+        offset_frames: usize,
+        num_frames: usize,
+    ) -> Vec<f32> {
+        let period = 60.0 * frame_rate_hz as f32 / bpm;
+        let burst_len = frame_rate_hz as usize / 50;
+        let mut samples = vec![0.0_f32; num_frames];
+        let mut beat_idx = 0;
+        loop {
+            let start = offset_frames + (beat_idx as f32 * period).round() as usize;
+            if start >= num_frames {
+                break;
+            }
+            for (i, sample) in samples[start..].iter_mut().take(burst_len).enumerate() {
+                let t = i as f32 / frame_rate_hz as f32;
+                *sample =
+                    0.8 * (-t * 200.0).exp() * (2.0 * std::f32::consts::PI * 1000.0 * t).sin();
+            }
+            beat_idx += 1;
+        }
+        samples
+    }
+
+    #[test]
+    fn test_onset_strength_shape() {
+        let waveform = Waveform::from_frames_of_silence(22050, 2, 22050);
+        let envelope = waveform.onset_strength(StftArgs::default()).unwrap();
+        assert_eq!(envelope.len(), 1 + 22050 / 512);
+        assert!(envelope.iter().all(|v| *v == 0.0));
+
+        let args = StftArgs::default().set_hop_length(256);
+        assert_eq!(
+            waveform.onset_strength(args).unwrap().len(),
+            1 + 22050 / 256
+        );
+        assert_eq!(
+            waveform.onset_strength(StftArgs::default().set_hop_length(0)),
+            Err(Error::WrongHopLength(0))
+        );
+    }
+
+    #[test]
+    fn test_tempo_of_click_tracks() {
+        for bpm in [72.0, 90.0, 120.0, 128.0, 150.0] {
+            let samples = click_track(22050, bpm, 0, 22050 * 15);
+            let waveform = Waveform::from_interleaved_samples(22050, 1, &samples);
+            let estimate = waveform.tempo(BeatArgs::default()).unwrap();
+            assert!(
+                (estimate.bpm - bpm).abs() < 1.5,
+                "{} bpm was estimated as {}",
+                bpm,
+                estimate.bpm
+            );
+            assert!(estimate.confidence > 0.5 && estimate.confidence <= 1.0);
+        }
+    }
+
+    #[test]
+    fn test_tempo_range() {
+        // Clicks at 200 bpm are also a beat at 100 bpm, so a range
+        // that leaves out 200 bpm finds half of it.
+        let samples = click_track(22050, 200.0, 0, 22050 * 15);
+        let waveform = Waveform::from_interleaved_samples(22050, 1, &samples);
+        let args = BeatArgs::default().set_max_bpm(160.0);
+        let estimate = waveform.tempo(args).unwrap();
+        assert!((estimate.bpm - 100.0).abs() < 1.5, "{}", estimate.bpm);
+    }
+
+    #[test]
+    fn test_beats_line_up_with_clicks() {
+        let frame_rate_hz = 44100;
+        let offset_frames = 13230;
+        let period_frames = 60.0 * frame_rate_hz as f32 / 110.0;
+        let samples = click_track(frame_rate_hz, 110.0, offset_frames, 44100 * 12);
+        let interleaved: Vec<f32> = samples.iter().flat_map(|s| [*s, 0.5 * s]).collect();
+        let waveform = Waveform::from_interleaved_samples(frame_rate_hz, 2, &interleaved);
+
+        let track = beat_track(&waveform, BeatArgs::default()).unwrap();
+        assert_eq!(track.frame_rate_hz, frame_rate_hz);
+        assert!((track.bpm - 110.0).abs() < 1.5, "{}", track.bpm);
+        // Almost every click is found.
+        let num_clicks = ((44100 * 12 - offset_frames) as f32 / period_frames).ceil() as usize;
+        assert!(track.beats.len() + 2 >= num_clicks, "{}", track.beats.len());
+        for beat in &track.beats {
+            // Every beat is within a hop of a click.
+            let beats_in = (beat.frame_idx as f32 - offset_frames as f32) / period_frames;
+            let error_frames = (beats_in - beats_in.round()).abs() * period_frames;
+            assert!(
+                error_frames <= 512.0,
+                "{:?} is {} frames off",
+                beat,
+                error_frames
+            );
+            assert_eq!(beat.frame_idx % 512, 0);
+            assert_eq!(beat.milliseconds, beat.frame_idx * 1000 / 44100);
+        }
+    }
+
+    #[test]
+    fn test_silence_has_no_beats() {
+        let waveform = Waveform::from_frames_of_silence(44100, 2, 44100 * 5);
+        let track = waveform.beat_track(BeatArgs::default()).unwrap();
+        assert_eq!(track.bpm, 0.0);
+        assert_eq!(track.confidence, 0.0);
+        assert!(track.beats.is_empty());
+
+        let waveform = Waveform::from_frames_of_silence(44100, 1, 0);
+        assert!(waveform
+            .beat_track(BeatArgs::default())
+            .unwrap()
+            .beats
+            .is_empty());
+    }
+
+    #[test]
+    fn test_wrong_args() {
+        let waveform = Waveform::from_frames_of_silence(22050, 1, 22050);
+        for args in [
+            BeatArgs::default().set_min_bpm(0.0),
+            BeatArgs::default().set_min_bpm(200.0).set_max_bpm(100.0),
+            BeatArgs::default().set_start_bpm(400.0),
+            BeatArgs::default().set_start_bpm(20.0),
+        ] {
+            assert_eq!(
+                tempo(&waveform, args),
+                Err(Error::WrongTempo(
+                    args.start_bpm,
+                    args.min_bpm,
+                    args.max_bpm
+                ))
+            );
+            assert_eq!(
+                beat_track(&waveform, args),
+                Err(Error::WrongTempo(
+                    args.start_bpm,
+                    args.min_bpm,
+                    args.max_bpm
+                ))
+            );
+        }
+        let args = BeatArgs::default().set_hop_length(0);
+        assert_eq!(tempo(&waveform, args), Err(Error::WrongHopLength(0)));
+        assert_eq!(
+            tempo_from_onset_strength(&[0.0, 1.0, 0.0, 1.0], 22050, args),
+            Err(Error::WrongHopLength(0))
+        );
+    }
+}
@@ -0,0 +1,129 @@
+mod fixtures;
+mod signals;
+
+mod test_compress {
+    use crate::signals::assert_close;
+    use babycat::source::{ChannelLink, CompressArgs};
+    use babycat::{Signal, Source, WaveformSource};
+
+    fn mono(samples: &[f32]) -> WaveformSource {
+        WaveformSource::from_interleaved_samples(1000, 1, samples)
+    }
+
+    fn gain(reduction_db: f32) -> f32 {
+        10.0_f32.powf(-reduction_db / 20.0)
+    }
+
+    fn hard_knee_args() -> CompressArgs {
+        CompressArgs::default()
+            .set_threshold_dbfs(-20.0)
+            .set_ratio(4.0)
+            .set_knee_db(0.0)
+    }
+
+    #[test]
+    fn test_attack_and_release_of_step() {
+        // -40 dBFS, then 0 dBFS, then -40 dBFS again.
+        let mut samples = vec![0.01_f32; 100];
+        samples.extend(vec![1.0_f32; 200]);
+        samples.extend(vec![0.01_f32; 300]);
+        let args = hard_knee_args()
+            .set_attack_milliseconds(10)
+            .set_release_milliseconds(100);
+        let compressed = mono(&samples).compress(args);
+        assert_eq!(compressed.num_frames_estimate(), Some(600));
+        let out = compressed.collect_interleaved_samples();
+        assert_eq!(out.len(), 600);
+
+        // Below the threshold, nothing changes.
+        assert!(out[..100].iter().all(|&s| s == 0.01));
+
+        // 0 dBFS is 20 dB over the threshold, so a 4:1 ratio lowers it by 15 dB.
+        // The reduction reaches 1 - 1/e of that after the 10 millisecond attack.
+        assert_close(out[109], gain(15.0 * (1.0 - (-1.0_f32).exp())), 1e-5);
+        assert_close(out[299], gain(15.0), 1e-5);
+        for pair in out[100..300].windows(2) {
+            assert!(pair[1] <= pair[0]);
+        }
+
+        // The reduction falls to 1/e of itself after the 100 millisecond release.
+        assert_close(out[399] / 0.01, gain(15.0 * (-1.0_f32).exp()), 1e-5);
+        for pair in out[300..].windows(2) {
+            assert!(pair[1] >= pair[0]);
+        }
+        assert_close(out[599] / 0.01, gain(15.0 * (-3.0_f32).exp()), 1e-5);
+    }
+
+    #[test]
+    fn test_soft_knee() {
+        let args = CompressArgs::default()
+            .set_threshold_dbfs(-20.0)
+            .set_ratio(4.0)
+            .set_knee_db(6.0)
+            .set_attack_milliseconds(0);
+        // At the threshold, a soft knee is already compressing a little.
+        let at_threshold = 0.1;
+        let out = mono(&[at_threshold])
+            .compress(args)
+            .collect_interleaved_samples();
+        assert_close(out[0], at_threshold * gain(0.75 * 9.0 / 12.0), 1e-5);
+        // At the top of the knee, it meets the hard-knee curve.
+        let at_knee = 10.0_f32.powf(-17.0 / 20.0);
+        let out = mono(&[at_knee])
+            .compress(args)
+            .collect_interleaved_samples();
+        assert_close(out[0], at_knee * gain(0.75 * 3.0), 1e-5);
+        // At the bottom of the knee, nothing changes.
+        let below_knee = 10.0_f32.powf(-23.0 / 20.0);
+        let out = mono(&[below_knee])
+            .compress(args)
+            .collect_interleaved_samples();
+        assert_close(out[0], below_knee, 1e-5);
+    }
+
+    #[test]
+    fn test_makeup_gain() {
+        let args = hard_knee_args()
+            .set_makeup_db(6.0)
+            .set_attack_milliseconds(0);
+        let out = mono(&[0.01, 1.0])
+            .compress(args)
+            .collect_interleaved_samples();
+        assert_close(out[0], 0.01 * gain(-6.0), 1e-5);
+        assert_close(out[1], gain(15.0 - 6.0), 1e-5);
+    }
+
+    #[test]
+    fn test_channel_linking() {
+        let stereo = || WaveformSource::from_interleaved_samples(1000, 2, &[1.0, 0.01]);
+        let mut args = hard_knee_args().set_attack_milliseconds(0);
+
+        let out = stereo()
+            .compress(args.set_link(ChannelLink::Max))
+            .collect_interleaved_samples();
+        assert_close(out[0], gain(15.0), 1e-5);
+        assert_close(out[1], 0.01 * gain(15.0), 1e-5);
+
+        let out = stereo()
+            .compress(args.set_link(ChannelLink::Independent))
+            .collect_interleaved_samples();
+        assert_close(out[0], gain(15.0), 1e-5);
+        assert_close(out[1], 0.01, 1e-5);
+
+        let out = stereo()
+            .compress(args.set_link(ChannelLink::Average))
+            .collect_interleaved_samples();
+        let average_dbfs = 20.0 * 0.505_f32.log10();
+        let reduction_db = (average_dbfs + 20.0) * 0.75;
+        assert_close(out[0], gain(reduction_db), 1e-5);
+        assert_close(out[1], 0.01 * gain(reduction_db), 1e-5);
+    }
+
+    #[test]
+    fn test_silence() {
+        let out = WaveformSource::from_interleaved_samples(1000, 2, &[0.0; 10])
+            .compress(CompressArgs::default())
+            .collect_interleaved_samples();
+        assert_eq!(out, &[0.0; 10]);
+    }
+}
@@ -0,0 +1,156 @@
+mod fixtures;
+mod signals;
+
+mod test_convolve {
+    use crate::fixtures::new_small_waveform;
+    use crate::signals::{assert_all_close, noise};
+    use babycat::{Error, Signal, Source, Waveform, WaveformSource};
+
+    /// Convolves one channel with one impulse response, the slow way.
+    fn direct_convolution(input: &[f32], ir: &[f32]) -> Vec<f32> {
+        let mut out = vec![0.0_f32; input.len() + ir.len() - 1];
+        for (i, x) in input.iter().enumerate() {
+            for (j, h) in ir.iter().enumerate() {
+                out[i + j] += x * h;
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_unit_impulse_is_passthrough() {
+        let waveform = new_small_waveform();
+        let ir = Waveform::from_interleaved_samples(waveform.frame_rate_hz(), 1, &[1.0]);
+        let convolved = waveform.clone().into_source().convolve(&ir, 1.0).unwrap();
+        assert_eq!(convolved.num_frames_estimate(), Some(10));
+        assert_eq!(convolved.size_hint(), (30, Some(30)));
+        assert_all_close(
+            &convolved.collect_interleaved_samples(),
+            waveform.to_interleaved_samples(),
+            1e-4,
+        );
+    }
+
+    #[test]
+    fn test_delay_and_tail() {
+        let source = WaveformSource::from_interleaved_samples(8000, 1, &[1.0, 2.0, 3.0]);
+        let ir = Waveform::from_interleaved_samples(8000, 1, &[0.0, 0.0, 0.0, 1.0]);
+        let convolved = source.convolve(&ir, 1.0).unwrap();
+        assert_eq!(convolved.num_frames_estimate(), Some(6));
+        assert_all_close(
+            &convolved.collect_interleaved_samples(),
+            &[0.0, 0.0, 0.0, 1.0, 2.0, 3.0],
+            1e-5,
+        );
+    }
+
+    #[test]
+    fn test_matches_direct_convolution_with_many_partitions() {
+        // The impulse response is longer than the largest partition, and the
+        // input does not line up with the partition boundaries.
+        let input = noise(9001, 1.0, 1);
+        let ir: Vec<f32> = noise(10_000, 1.0, 2).iter().map(|s| s * 0.01).collect();
+        let expected = direct_convolution(&input, &ir);
+        let ir_waveform = Waveform::from_interleaved_samples(44100, 1, &ir);
+        let convolved = WaveformSource::from_interleaved_samples(44100, 1, &input)
+            .convolve(&ir_waveform, 1.0)
+            .unwrap();
+        assert_eq!(convolved.num_frames_estimate(), Some(19_000));
+        assert_all_close(&convolved.collect_interleaved_samples(), &expected, 1e-3);
+    }
+
+    #[test]
+    fn test_mono_ir_on_stereo_input() {
+        let input = [1.0, -1.0, 0.5, 0.25];
+        let ir = Waveform::from_interleaved_samples(8000, 1, &[1.0, 0.5]);
+        let out = WaveformSource::from_interleaved_samples(8000, 2, &input)
+            .convolve(&ir, 1.0)
+            .unwrap()
+            .collect_interleaved_samples();
+        assert_all_close(&out, &[1.0, -1.0, 1.0, -0.25, 0.25, 0.125], 1e-5);
+    }
+
+    #[test]
+    fn test_per_channel_ir() {
+        let input = [1.0, 1.0, 0.0, 0.0];
+        let ir = Waveform::from_interleaved_samples(8000, 2, &[0.5, 0.0, 0.0, 0.25]);
+        let out = WaveformSource::from_interleaved_samples(8000, 2, &input)
+            .convolve(&ir, 1.0)
+            .unwrap()
+            .collect_interleaved_samples();
+        assert_all_close(&out, &[0.5, 0.0, 0.0, 0.25, 0.0, 0.0], 1e-5);
+    }
+
+    #[test]
+    fn test_true_stereo_ir() {
+        // Left goes only to the right, and right goes only to the left at half volume.
+        let ir = Waveform::from_interleaved_samples(8000, 4, &[0.0, 1.0, 0.5, 0.0]);
+        let out = WaveformSource::from_interleaved_samples(8000, 2, &[1.0, 0.0, 0.0, 1.0])
+            .convolve(&ir, 1.0)
+            .unwrap()
+            .collect_interleaved_samples();
+        assert_all_close(&out, &[0.0, 1.0, 0.5, 0.0], 1e-5);
+    }
+
+    #[test]
+    fn test_wet_dry() {
+        let ir = Waveform::from_interleaved_samples(8000, 1, &[0.0, 1.0]);
+        let dry = WaveformSource::from_interleaved_samples(8000, 1, &[1.0, 0.0])
+            .convolve(&ir, 0.0)
+            .unwrap()
+            .collect_interleaved_samples();
+        assert_all_close(&dry, &[1.0, 0.0, 0.0], 1e-5);
+        let half = WaveformSource::from_interleaved_samples(8000, 1, &[1.0, 0.0])
+            .convolve(&ir, 0.5)
+            .unwrap()
+            .collect_interleaved_samples();
+        assert_all_close(&half, &[0.5, 0.5, 0.0], 1e-5);
+    }
+
+    #[test]
+    fn test_waveform_convolve() {
+        let waveform = Waveform::from_interleaved_samples(8000, 1, &noise(500, 1.0, 3));
+        let ir = Waveform::from_interleaved_samples(8000, 1, &noise(100, 1.0, 4));
+        let convolved = waveform.convolve(&ir, 1.0).unwrap();
+        assert_eq!(convolved.num_frames(), 599);
+        assert_eq!(convolved.frame_rate_hz(), 8000);
+        assert_all_close(
+            convolved.to_interleaved_samples(),
+            &direct_convolution(
+                waveform.to_interleaved_samples(),
+                ir.to_interleaved_samples(),
+            ),
+            1e-3,
+        );
+    }
+
+    #[test]
+    fn test_empty_input_plays_tail() {
+        let ir = Waveform::from_interleaved_samples(8000, 1, &[1.0, 1.0]);
+        let out = WaveformSource::from_interleaved_samples(8000, 1, &[])
+            .convolve(&ir, 1.0)
+            .unwrap()
+            .collect_interleaved_samples();
+        assert_all_close(&out, &[0.0], 1e-5);
+    }
+
+    #[test]
+    fn test_wrong_inputs() {
+        let source = WaveformSource::from_interleaved_samples(8000, 3, &[0.0; 3]);
+        let other_rate = Waveform::from_interleaved_samples(16000, 1, &[1.0]);
+        assert_eq!(
+            source.clone().convolve(&other_rate, 1.0).unwrap_err(),
+            Error::CannotConvolveWithDifferentFrameRates(8000, 16000)
+        );
+        let two_channels = Waveform::from_interleaved_samples(8000, 2, &[1.0, 1.0]);
+        assert_eq!(
+            source.clone().convolve(&two_channels, 1.0).unwrap_err(),
+            Error::CannotConvolveWithImpulseResponseChannels(3, 2)
+        );
+        let four_channels = Waveform::from_interleaved_samples(8000, 4, &[1.0; 4]);
+        assert_eq!(
+            source.convolve(&four_channels, 1.0).unwrap_err(),
+            Error::CannotConvolveWithImpulseResponseChannels(3, 4)
+        );
+    }
+}
@@ -0,0 +1,177 @@
+mod fixtures;
+mod signals;
+
+mod test_cqt {
+    use crate::fixtures::*;
+    use crate::signals::sine;
+    use babycat::constants::{NUM_CHROMA, WINDOW_TYPE_HAMMING};
+    use babycat::spectral::{chroma, chroma_from_cqt, cqt, CqtArgs, Features};
+    use babycat::{Error, Waveform};
+
+    fn loudest(features: &Features, channel_idx: u16, time_step_idx: usize) -> usize {
+        (0..features.num_features())
+            .max_by(|&a, &b| {
+                let a = features.get(channel_idx, a, time_step_idx).unwrap();
+                let b = features.get(channel_idx, b, time_step_idx).unwrap();
+                a.total_cmp(&b)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_shape() {
+        let waveform = Waveform::from_frames_of_silence(22050, 2, 22050);
+        let transform = waveform.cqt(CqtArgs::default()).unwrap();
+        assert_eq!(transform.frame_rate_hz(), 22050);
+        assert_eq!(transform.hop_length(), 512);
+        assert_eq!(transform.num_channels(), 2);
+        assert_eq!(transform.num_features(), 84);
+        assert_eq!(transform.num_time_steps(), 1 + 22050 / 512);
+        assert!(transform.as_slice().iter().all(|v| *v == 0.0));
+
+        let args = CqtArgs::default().set_hop_length(256);
+        assert_eq!(args.num_time_steps(22050), 1 + 22050 / 256);
+        assert_eq!(waveform.cqt(args).unwrap().num_time_steps(), 87);
+    }
+
+    #[test]
+    fn test_bin_frequencies() {
+        let args = CqtArgs::default();
+        assert!((args.bin_frequency_hz(0) - 32.703).abs() < 1e-3);
+        assert!((args.bin_frequency_hz(12) - 65.406).abs() < 1e-3);
+        assert!((args.bin_frequency_hz(45) - 440.0).abs() < 1e-2);
+        let args = CqtArgs::default().set_bins_per_octave(36);
+        assert!((args.bin_frequency_hz(135) - 440.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_sine_in_every_octave() {
+        // A1 through A7. The lower octaves are computed at lower frame rates.
+        let args = CqtArgs::default();
+        for octave in 0..7 {
+            let bin_idx = 9 + 12 * octave;
+            let freq_hz = args.bin_frequency_hz(bin_idx);
+            let samples = sine(22050, freq_hz, 0.5, 44100);
+            let waveform = Waveform::from_interleaved_samples(22050, 1, &samples);
+            let transform = waveform.cqt(args).unwrap();
+            let t = transform.num_time_steps() / 2;
+            assert_eq!(loudest(&transform, 0, t), bin_idx, "{} hz", freq_hz);
+            // An amplitude of 0.5 gives a magnitude of about 0.25.
+            let magnitude = transform.get(0, bin_idx, t).unwrap();
+            assert!(
+                (magnitude - 0.25).abs() < 0.01,
+                "{} hz has a magnitude of {}",
+                freq_hz,
+                magnitude
+            );
+            // Bins an octave away hear almost nothing.
+            if bin_idx >= 12 {
+                assert!(transform.get(0, bin_idx - 12, t).unwrap() < 0.01);
+            }
+            if bin_idx + 12 < 84 {
+                assert!(transform.get(0, bin_idx + 12, t).unwrap() < 0.01);
+            }
+        }
+    }
+
+    #[test]
+    fn test_channels_are_separate() {
+        let left = sine(22050, 261.63, 0.5, 22050);
+        let right = sine(22050, 392.0, 0.5, 22050);
+        let interleaved: Vec<f32> = left
+            .iter()
+            .zip(&right)
+            .flat_map(|(l, r)| [*l, *r])
+            .collect();
+        let waveform = Waveform::from_interleaved_samples(22050, 2, &interleaved);
+        let args = CqtArgs::default().set_window_type(WINDOW_TYPE_HAMMING);
+        let transform = waveform.cqt(args).unwrap();
+        let t = transform.num_time_steps() / 2;
+        // C4 is 36 semitones above C1, and G4 is 43.
+        assert_eq!(loudest(&transform, 0, t), 36);
+        assert_eq!(loudest(&transform, 1, t), 43);
+    }
+
+    #[test]
+    fn test_log_sweep_rises() {
+        let waveform = Waveform::from_file(LS_PCM_16_FILENAME, Default::default()).unwrap();
+        let transform = waveform.cqt(CqtArgs::default()).unwrap();
+        let num_time_steps = transform.num_time_steps();
+        let early = loudest(&transform, 0, num_time_steps / 4);
+        let late = loudest(&transform, 0, num_time_steps * 3 / 4);
+        assert!(late > early, "{} <= {}", late, early);
+    }
+
+    #[test]
+    fn test_wrong_args() {
+        let waveform = Waveform::from_frames_of_silence(22050, 1, 22050);
+        assert_eq!(
+            waveform.cqt(CqtArgs::default().set_hop_length(0)),
+            Err(Error::WrongHopLength(0))
+        );
+        assert_eq!(
+            waveform.cqt(CqtArgs::default().set_bins_per_octave(0)),
+            Err(Error::WrongBinsPerOctave(0))
+        );
+        assert_eq!(
+            waveform.cqt(CqtArgs::default().set_num_bins(0)),
+            Err(Error::WrongNumFeatures(0, 0))
+        );
+        let args = CqtArgs::default().set_num_bins(120);
+        assert_eq!(
+            waveform.cqt(args),
+            Err(Error::WrongFilterFrequency(
+                args.bin_frequency_hz(119),
+                22050
+            ))
+        );
+        assert_eq!(
+            waveform.cqt(CqtArgs::default().set_window_type(99)),
+            Err(Error::FeatureNotCompiled("unknown-window-type"))
+        );
+    }
+
+    #[test]
+    fn test_chroma_of_a_tone() {
+        let args = CqtArgs::default()
+            .set_bins_per_octave(36)
+            .set_num_bins(36 * 7);
+        // D4, then F#3, which are pitch classes 2 and 6.
+        for (freq_hz, pitch_class) in [(293.66, 2), (185.0, 6)] {
+            let samples = sine(22050, freq_hz, 0.5, 22050);
+            let waveform = Waveform::from_interleaved_samples(22050, 1, &samples);
+            let chromagram = waveform.chroma(args).unwrap();
+            assert_eq!(chromagram.num_features(), NUM_CHROMA);
+            let t = chromagram.num_time_steps() / 2;
+            assert_eq!(loudest(&chromagram, 0, t), pitch_class);
+            assert_eq!(chromagram.get(0, pitch_class, t), Some(1.0));
+            for other in 0..NUM_CHROMA {
+                if other != pitch_class {
+                    assert!(chromagram.get(0, other, t).unwrap() < 0.2);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_chroma_from_cqt() {
+        let samples = sine(22050, 440.0, 0.5, 22050);
+        let waveform = Waveform::from_interleaved_samples(22050, 1, &samples);
+        let args = CqtArgs::default();
+        let transform = cqt(&waveform, args).unwrap();
+        assert_eq!(
+            chroma_from_cqt(&transform, args).unwrap(),
+            chroma(&waveform, args).unwrap()
+        );
+        assert_eq!(
+            chroma_from_cqt(&transform, CqtArgs::default().set_num_bins(72)),
+            Err(Error::WrongNumFeatures(72, 84))
+        );
+
+        // Every time step is normalized so that its loudest pitch class is 1,
+        // except for silence, which stays at 0.
+        let silence = Waveform::from_frames_of_silence(22050, 1, 22050);
+        let chromagram = chroma(&silence, args).unwrap();
+        assert!(chromagram.as_slice().iter().all(|v| *v == 0.0));
+    }
+}
@@ -0,0 +1,305 @@
+mod fixtures;
+mod signals;
+
+mod test_dtmf {
+    use crate::fixtures::*;
+    use crate::signals::{duplicate_channels, noise, sine};
+    use babycat::decoder;
+    use babycat::dtmf::{
+        dtmf_digits, DtmfArgs, DtmfDigit, DTMF_COLUMN_FREQUENCIES_HZ, DTMF_KEYPAD,
+        DTMF_MIN_FRAME_RATE_HZ, DTMF_ROW_FREQUENCIES_HZ,
+    };
+    use babycat::{Error, Signal, Source, Waveform, WaveformArgs, WaveformSource};
+
+    /// Returns the row and column frequencies of a key.
+    fn frequencies(key: char) -> (f32, f32) {
+        for (row_idx, row) in DTMF_KEYPAD.iter().enumerate() {
+            if let Some(column_idx) = row.iter().position(|k| *k == key) {
+                return (
+                    DTMF_ROW_FREQUENCIES_HZ[row_idx],
+                    DTMF_COLUMN_FREQUENCIES_HZ[column_idx],
+                );
+            }
+        }
+        panic!("{} is not a DTMF key", key);
+    }
+
+    /// Sends every key in `keys`, given as `(key, start_ms, end_ms)`, in
+    /// `total_ms` of silence. The row tone is at `row_dbfs` and the column
+    /// tone is `column_db` louder.
+    fn keypad(
+        frame_rate_hz: u32,
+        total_ms: usize,
+        keys: &[(char, usize, usize)],
+        row_dbfs: f32,
+        column_db: f32,
+    ) -> Vec<f32> {
+        let to_frames = |ms: usize| ms * frame_rate_hz as usize / 1000;
+        let row_amplitude = 10.0_f32.powf(row_dbfs / 20.0);
+        let column_amplitude = 10.0_f32.powf((row_dbfs + column_db) / 20.0);
+        let mut samples = vec![0.0; to_frames(total_ms)];
+        for &(key, start_ms, end_ms) in keys {
+            let (row_hz, column_hz) = frequencies(key);
+            let (start, end) = (to_frames(start_ms), to_frames(end_ms));
+            for (frame_idx, sample) in samples.iter_mut().enumerate().take(end).skip(start) {
+                let t = frame_idx as f32 / frame_rate_hz as f32;
+                *sample += row_amplitude * (2.0 * std::f32::consts::PI * row_hz * t).sin()
+                    + column_amplitude * (2.0 * std::f32::consts::PI * column_hz * t).sin();
+            }
+        }
+        samples
+    }
+
+    fn decode(frame_rate_hz: u32, samples: &[f32], args: DtmfArgs) -> Vec<DtmfDigit> {
+        let source = WaveformSource::from_interleaved_samples(frame_rate_hz, 1, samples);
+        dtmf_digits(source, args).unwrap()
+    }
+
+    fn keyed(digits: &[DtmfDigit]) -> String {
+        digits.iter().map(|digit| digit.digit).collect()
+    }
+
+    #[test]
+    fn test_fixture() {
+        let waveform = Waveform::from_file(MONO_DTMF_FILENAME, WaveformArgs::default()).unwrap();
+        let digits = waveform.dtmf_digits(DtmfArgs::default()).unwrap();
+        assert_eq!(keyed(&digits), "2229228");
+        // Every key is held for about 1.25 seconds, with about 0.2 seconds between keys.
+        for digit in &digits {
+            let duration = digit.end_milliseconds - digit.start_milliseconds;
+            assert!((1200..=1300).contains(&duration), "{:?}", digit);
+        }
+        for pair in digits.windows(2) {
+            let pause = pair[1].start_milliseconds - pair[0].end_milliseconds;
+            assert!((150..=250).contains(&pause), "{:?}", pair);
+        }
+        assert_eq!(digits[0].start_frame_idx, 0);
+        assert!(digits[6].end_frame_idx <= MONO_DTMF_NUM_FRAMES);
+    }
+
+    #[test]
+    fn test_fixture_mp3_and_source() {
+        let mp3 = Waveform::from_file(
+            "./audio-for-tests/mono-dtmf-tones/track.mp3",
+            WaveformArgs::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            keyed(&mp3.dtmf_digits(DtmfArgs::default()).unwrap()),
+            "2229228"
+        );
+
+        let waveform = Waveform::from_file(MONO_DTMF_FILENAME, WaveformArgs::default()).unwrap();
+        let source = decoder::from_file(MONO_DTMF_FILENAME).unwrap();
+        assert_eq!(
+            dtmf_digits(source, DtmfArgs::default()).unwrap(),
+            waveform.dtmf_digits(DtmfArgs::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_every_key() {
+        let keys: Vec<(char, usize, usize)> = "123A456B789C*0#D"
+            .chars()
+            .enumerate()
+            .map(|(idx, key)| (key, 100 + idx * 150, 180 + idx * 150))
+            .collect();
+        let samples = keypad(8000, 2600, &keys, -10.0, 0.0);
+        let digits = decode(8000, &samples, DtmfArgs::default());
+        assert_eq!(keyed(&digits), "123A456B789C*0#D");
+    }
+
+    #[test]
+    fn test_timestamps() {
+        let samples = keypad(8000, 1000, &[('7', 500, 700)], -10.0, 0.0);
+        let digits = decode(8000, &samples, DtmfArgs::default());
+        assert_eq!(digits.len(), 1);
+        // Timestamps are accurate to within one block.
+        assert!((487..=513).contains(&digits[0].start_milliseconds));
+        assert!((687..=713).contains(&digits[0].end_milliseconds));
+        assert_eq!(digits[0].start_frame_idx / 8, digits[0].start_milliseconds);
+    }
+
+    #[test]
+    fn test_frame_rates_and_channels() {
+        let keys = [('1', 100, 300), ('#', 400, 600), ('D', 700, 900)];
+        for frame_rate_hz in [8000, 11025, 16000, 22050, 44100, 48000] {
+            let mono = keypad(frame_rate_hz, 1000, &keys, -10.0, 0.0);
+            let digits = decode(frame_rate_hz, &mono, DtmfArgs::default());
+            assert_eq!(keyed(&digits), "1#D", "{} hz", frame_rate_hz);
+
+            // The same keys in both channels, with noise in the right channel.
+            let right = noise(mono.len(), 0.05, 12345);
+            let stereo: Vec<f32> = mono
+                .iter()
+                .zip(&right)
+                .flat_map(|(left, noise)| [*left, *left + noise])
+                .collect();
+            let source = WaveformSource::from_interleaved_samples(frame_rate_hz, 2, &stereo);
+            let from_source = dtmf_digits(source, DtmfArgs::default()).unwrap();
+            assert_eq!(keyed(&from_source), "1#D", "{} hz stereo", frame_rate_hz);
+            let waveform = Waveform::from_interleaved_samples(frame_rate_hz, 2, &stereo);
+            assert_eq!(
+                waveform.dtmf_digits(DtmfArgs::default()).unwrap(),
+                from_source
+            );
+        }
+    }
+
+    #[test]
+    fn test_twist() {
+        let decode_with_twist = |column_db: f32| {
+            let samples = keypad(8000, 400, &[('5', 100, 300)], -10.0, column_db);
+            keyed(&decode(8000, &samples, DtmfArgs::default()))
+        };
+        // The high group can be up to 8 dB quieter or 4 dB louder.
+        assert_eq!(decode_with_twist(-7.0), "5");
+        assert_eq!(decode_with_twist(-9.0), "");
+        assert_eq!(decode_with_twist(3.0), "5");
+        assert_eq!(decode_with_twist(5.0), "");
+
+        let samples = keypad(8000, 400, &[('5', 100, 300)], -10.0, -9.0);
+        let args = DtmfArgs::default().set_max_normal_twist_db(10.0);
+        assert_eq!(keyed(&decode(8000, &samples, args)), "5");
+        let samples = keypad(8000, 400, &[('5', 100, 300)], -10.0, 5.0);
+        let args = DtmfArgs::default().set_max_reverse_twist_db(6.0);
+        assert_eq!(keyed(&decode(8000, &samples, args)), "5");
+    }
+
+    #[test]
+    fn test_min_tone_duration() {
+        // ITU-T Q.24 tones of 40 milliseconds have to be decoded, and tones
+        // of 20 milliseconds have to be ignored, wherever they start.
+        for offset_ms in 0..13 {
+            let start_ms = 100 + offset_ms;
+            let long = keypad(8000, 300, &[('0', start_ms, start_ms + 40)], -10.0, 0.0);
+            assert_eq!(keyed(&decode(8000, &long, DtmfArgs::default())), "0");
+            let short = keypad(8000, 300, &[('0', start_ms, start_ms + 20)], -10.0, 0.0);
+            assert_eq!(keyed(&decode(8000, &short, DtmfArgs::default())), "");
+        }
+        let long = keypad(8000, 300, &[('0', 100, 140)], -10.0, 0.0);
+        let args = DtmfArgs::default().set_min_tone_milliseconds(100);
+        assert_eq!(keyed(&decode(8000, &long, args)), "");
+    }
+
+    #[test]
+    fn test_pauses() {
+        for offset_ms in 0..13 {
+            let start_ms = 100 + offset_ms;
+            // A 10 millisecond interruption is bridged.
+            let interrupted = keypad(
+                8000,
+                500,
+                &[
+                    ('9', start_ms, start_ms + 100),
+                    ('9', start_ms + 110, start_ms + 200),
+                ],
+                -10.0,
+                0.0,
+            );
+            let digits = decode(8000, &interrupted, DtmfArgs::default());
+            assert_eq!(keyed(&digits), "9");
+            // A 40 millisecond pause separates two presses of the same key.
+            let pressed_twice = keypad(
+                8000,
+                500,
+                &[
+                    ('9', start_ms, start_ms + 100),
+                    ('9', start_ms + 140, start_ms + 240),
+                ],
+                -10.0,
+                0.0,
+            );
+            let digits = decode(8000, &pressed_twice, DtmfArgs::default());
+            assert_eq!(keyed(&digits), "99");
+        }
+    }
+
+    #[test]
+    fn test_min_level() {
+        let quiet = keypad(8000, 400, &[('B', 100, 300)], -35.0, 0.0);
+        assert_eq!(keyed(&decode(8000, &quiet, DtmfArgs::default())), "B");
+        let quieter = keypad(8000, 400, &[('B', 100, 300)], -45.0, 0.0);
+        assert_eq!(keyed(&decode(8000, &quieter, DtmfArgs::default())), "");
+        let args = DtmfArgs::default().set_min_level_dbfs(-50.0);
+        assert_eq!(keyed(&decode(8000, &quieter, args)), "B");
+    }
+
+    #[test]
+    fn test_rejects_non_dtmf() {
+        // A single tone.
+        let samples = sine(8000, 770.0, 0.5, 8000);
+        assert!(decode(8000, &samples, DtmfArgs::default()).is_empty());
+        // Loud noise.
+        assert!(decode(8000, &noise(8000, 0.5, 12345), DtmfArgs::default()).is_empty());
+        // A key buried in noise that is louder than its tones.
+        let key = keypad(8000, 1000, &[('3', 100, 900)], -20.0, 0.0);
+        let noisy: Vec<f32> = key
+            .iter()
+            .zip(noise(8000, 0.5, 12345))
+            .map(|(tone, noise)| tone + noise)
+            .collect();
+        assert!(decode(8000, &noisy, DtmfArgs::default()).is_empty());
+        // Two keys from the same column at once.
+        let chord = keypad(8000, 400, &[('1', 100, 300), ('4', 100, 300)], -10.0, 0.0);
+        assert!(decode(8000, &chord, DtmfArgs::default()).is_empty());
+    }
+
+    #[test]
+    fn test_low_frame_rate() {
+        let samples = keypad(8000, 400, &[('1', 100, 300)], -10.0, 0.0);
+        let source = WaveformSource::from_interleaved_samples(2000, 1, &samples);
+        assert_eq!(
+            dtmf_digits(source, DtmfArgs::default()),
+            Err(Error::FrameRateTooLow(2000, DTMF_MIN_FRAME_RATE_HZ))
+        );
+        let waveform =
+            Waveform::from_interleaved_samples(2000, 2, &duplicate_channels(&samples, 2));
+        assert_eq!(
+            waveform.dtmf_digits(DtmfArgs::default()),
+            Err(Error::FrameRateTooLow(2000, DTMF_MIN_FRAME_RATE_HZ))
+        );
+    }
+
+    #[test]
+    fn test_zero_channels() {
+        /// A source without any channels, which a [`Waveform`] cannot have.
+        #[derive(Debug)]
+        struct NoChannels;
+
+        impl Iterator for NoChannels {
+            type Item = f32;
+
+            fn next(&mut self) -> Option<f32> {
+                None
+            }
+        }
+
+        impl Signal for NoChannels {
+            fn frame_rate_hz(&self) -> u32 {
+                8000
+            }
+
+            fn num_channels(&self) -> u16 {
+                0
+            }
+
+            fn num_frames_estimate(&self) -> Option<usize> {
+                Some(0)
+            }
+        }
+
+        impl Source for NoChannels {}
+
+        assert_eq!(
+            dtmf_digits(NoChannels, DtmfArgs::default()),
+            Err(Error::WrongNumChannels(1, 0))
+        );
+    }
+
+    #[test]
+    fn test_silence() {
+        assert!(decode(8000, &[0.0; 8000], DtmfArgs::default()).is_empty());
+        assert!(decode(8000, &[], DtmfArgs::default()).is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+mod fixtures;
+
+mod test_duck {
+    use crate::fixtures::new_small_waveform;
+    use babycat::source::{DuckAmount, DuckArgs, DuckOutput};
+    use babycat::{Error, Signal, Source, WaveformSource};
+
+    fn mono(samples: &[f32]) -> WaveformSource {
+        WaveformSource::from_interleaved_samples(1000, 1, samples)
+    }
+
+    fn instant_args() -> DuckArgs {
+        DuckArgs::default()
+            .set_threshold_dbfs(-20.0)
+            .set_detector_release_milliseconds(0)
+            .set_attack_milliseconds(0)
+            .set_release_milliseconds(0)
+            .set_hold_milliseconds(0)
+    }
+
+    #[test]
+    fn test_silent_sidechain_is_passthrough() {
+        let waveform = new_small_waveform();
+        let expected = waveform.to_interleaved_samples().to_vec();
+        let sidechain = WaveformSource::from_interleaved_samples(
+            waveform.frame_rate_hz(),
+            1,
+            &vec![0.0; waveform.num_frames()],
+        );
+        let ducked = waveform
+            .into_source()
+            .duck(sidechain, DuckArgs::default())
+            .unwrap();
+        assert_eq!(ducked.num_frames_estimate(), Some(10));
+        assert_eq!(ducked.size_hint(), (30, Some(30)));
+        assert_eq!(ducked.collect_interleaved_samples(), expected);
+    }
+
+    #[test]
+    fn test_depth() {
+        let args = instant_args().set_amount(DuckAmount::Depth(20.0));
+        let out = mono(&[1.0; 4])
+            .duck(mono(&[0.0, 1.0, 1.0, 0.0]), args)
+            .unwrap()
+            .collect_interleaved_samples();
+        let expected = [1.0, 0.1, 0.1, 1.0];
+        for (actual, expected) in out.iter().zip(expected.iter()) {
+            assert!((actual - expected).abs() < 1e-5, "{:?}", out);
+        }
+    }
+
+    #[test]
+    fn test_ratio() {
+        // The sidechain is 20 dB over the threshold, so a 2:1 ratio lowers the program by 10 dB.
+        let args = instant_args().set_amount(DuckAmount::Ratio(2.0));
+        let out = mono(&[1.0])
+            .duck(mono(&[1.0]), args)
+            .unwrap()
+            .collect_interleaved_samples();
+        assert!((out[0] - 0.316_227_8).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_ratio_with_steady_sine_sidechain() {
+        // A full-scale 100 hz sine wave is 20 dB over the threshold, so a 2:1
+        // ratio lowers the program by about 10 dB. The level detector keeps
+        // the gain from following every cycle of the sidechain.
+        let sidechain: Vec<f32> = (0..16000)
+            .map(|i| (2.0 * std::f32::consts::PI * 100.0 * i as f32 / 8000.0).sin())
+            .collect();
+        let args = DuckArgs::default()
+            .set_threshold_dbfs(-20.0)
+            .set_amount(DuckAmount::Ratio(2.0))
+            .set_hold_milliseconds(0);
+        let out = WaveformSource::from_interleaved_samples(8000, 1, &[1.0; 16000])
+            .duck(
+                WaveformSource::from_interleaved_samples(8000, 1, &sidechain),
+                args,
+            )
+            .unwrap()
+            .collect_interleaved_samples();
+        let settled_db: Vec<f32> = out[8000..].iter().map(|s| 20.0 * s.log10()).collect();
+        let quietest = settled_db.iter().copied().fold(f32::INFINITY, f32::min);
+        let loudest = settled_db.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        assert!(loudest - quietest < 0.01, "{} {}", quietest, loudest);
+        assert!(
+            (-10.1..=-9.9).contains(&quietest),
+            "{} {}",
+            quietest,
+            loudest
+        );
+    }
+
+    #[test]
+    fn test_hold_attack_and_release() {
+        let mut sidechain = vec![0.0_f32; 200];
+        sidechain[0] = 1.0;
+        let args = DuckArgs::default()
+            .set_threshold_dbfs(-20.0)
+            .set_amount(DuckAmount::Depth(20.0))
+            .set_detector_release_milliseconds(0)
+            .set_attack_milliseconds(5)
+            .set_release_milliseconds(20)
+            .set_hold_milliseconds(50);
+        let out = mono(&[1.0; 200])
+            .duck(mono(&sidechain), args)
+            .unwrap()
+            .collect_interleaved_samples();
+        // The attack is not instant...
+        assert!(out[0] < 1.0 && out[0] > 0.1);
+        // ...but the program settles near the full depth during the hold...
+        assert!((out[49] - 0.1).abs() < 0.01);
+        // ...and recovers once the hold and release are over.
+        assert!(out[55] > out[50]);
+        assert!((out[199] - 1.0).abs() < 0.01);
+        for pair in out[50..].windows(2) {
+            assert!(pair[1] >= pair[0]);
+        }
+    }
+
+    #[test]
+    fn test_mix_output() {
+        let args = instant_args()
+            .set_amount(DuckAmount::Depth(20.0))
+            .set_output(DuckOutput::Mix);
+        let ducked = mono(&[0.5, 0.5])
+            .duck(mono(&[0.0, 0.5, 0.25]), args)
+            .unwrap();
+        assert_eq!(ducked.num_frames_estimate(), Some(3));
+        let out = ducked.collect_interleaved_samples();
+        assert_eq!(out.len(), 3);
+        assert!((out[0] - 0.5).abs() < 1e-6);
+        assert!((out[1] - 0.55).abs() < 1e-6);
+        assert!((out[2] - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ducked_output_ends_with_program() {
+        let out = mono(&[0.5, 0.5])
+            .duck(mono(&[0.0; 5]), instant_args())
+            .unwrap()
+            .collect_interleaved_samples();
+        assert_eq!(out, &[0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_wrong_inputs() {
+        let stereo = WaveformSource::from_interleaved_samples(1000, 2, &[0.0, 0.0]);
+        assert_eq!(
+            mono(&[0.0])
+                .duck(stereo, instant_args().set_output(DuckOutput::Mix))
+                .unwrap_err(),
+            Error::CannotMixSourcesWithDifferentNumChannels(1, 2)
+        );
+        let other_rate = WaveformSource::from_interleaved_samples(2000, 1, &[0.0]);
+        assert_eq!(
+            mono(&[0.0]).duck(other_rate, instant_args()).unwrap_err(),
+            Error::CannotMixSourcesWithDifferentFrameRates(1000, 2000)
+        );
+    }
+}
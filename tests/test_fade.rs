@@ -0,0 +1,133 @@
+mod fixtures;
+
+mod test_fade {
+    use crate::fixtures::new_small_waveform;
+    use babycat::source::FadeCurve;
+    use babycat::{Error, Signal, Source, WaveformSource};
+
+    const ALL_CURVES: &[FadeCurve] = &[
+        FadeCurve::Linear,
+        FadeCurve::EqualPower,
+        FadeCurve::Logarithmic,
+        FadeCurve::SCurve,
+    ];
+
+    fn ones(num_frames: usize) -> WaveformSource {
+        WaveformSource::from_interleaved_samples(44100, 1, &vec![1.0; num_frames])
+    }
+
+    #[test]
+    fn test_curves_are_monotonic() {
+        for curve in ALL_CURVES {
+            assert_eq!(curve.gain(0.0), 0.0);
+            assert_eq!(curve.gain(1.0), 1.0);
+            let mut prev = 0.0;
+            for i in 1..=100 {
+                let gain = curve.gain(i as f32 / 100.0);
+                assert!(gain >= prev, "{:?} is not monotonic", curve);
+                prev = gain;
+            }
+        }
+    }
+
+    #[test]
+    fn test_fade_in_do_nothing() {
+        let waveform = new_small_waveform();
+        let expected = waveform.to_interleaved_samples().to_vec();
+        let out = waveform
+            .into_source()
+            .fade_in(0, FadeCurve::Linear)
+            .collect_interleaved_samples();
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_fade_out_size_hint() {
+        let waveform = new_small_waveform();
+        let mut ws = waveform.into_source().fade_out(4, FadeCurve::SCurve);
+        assert_eq!(ws.size_hint(), (30, Some(30)));
+        ws.next();
+        assert_eq!(ws.size_hint(), (29, Some(29)));
+        assert_eq!(ws.count(), 29);
+    }
+
+    #[test]
+    fn test_fade_out_longer_than_source() {
+        let out = ones(2)
+            .fade_out(4, FadeCurve::Linear)
+            .collect_interleaved_samples();
+        assert_eq!(out, &[0.25, 0.0]);
+    }
+
+    #[test]
+    fn test_fade_out_mirrors_fade_in() {
+        for curve in ALL_CURVES {
+            let mut fade_in = ones(7).fade_in(4, *curve).collect_interleaved_samples();
+            fade_in.reverse();
+            let fade_out = ones(7).fade_out(4, *curve).collect_interleaved_samples();
+            assert_eq!(fade_out, fade_in, "{:?} is not mirrored", curve);
+        }
+    }
+
+    #[test]
+    fn test_crossfade_equal_power_keeps_power() {
+        let first = ones(100);
+        let second = ones(100);
+        let out = first
+            .append_with_crossfade(second, 50, FadeCurve::EqualPower)
+            .unwrap();
+        assert_eq!(out.num_frames_estimate(), Some(150));
+        let samples = out.collect_interleaved_samples();
+        assert_eq!(samples.len(), 150);
+        for (frame_idx, sample) in samples[50..100].iter().enumerate() {
+            let t = frame_idx as f32 / 50.0;
+            let fade_out = FadeCurve::EqualPower.gain(1.0 - t);
+            let fade_in = FadeCurve::EqualPower.gain(t);
+            assert!((fade_out * fade_out + fade_in * fade_in - 1.0).abs() < 1e-5);
+            assert!((sample - (fade_out + fade_in)).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_crossfade_stereo_linear_is_gapless() {
+        let waveform = new_small_waveform();
+        let first = WaveformSource::from_interleaved_samples(1234, 3, &[1.0; 30]);
+        let out = first
+            .append_with_crossfade(waveform.into_source(), 3, FadeCurve::Linear)
+            .unwrap()
+            .collect_interleaved_samples();
+        assert_eq!(out.len(), (10 + 10 - 3) * 3);
+        // Before the crossfade.
+        assert_eq!(&out[..21], &[1.0; 21]);
+        // The first crossfade frame is entirely from the first source.
+        assert_eq!(&out[21..24], &[1.0, 1.0, 1.0]);
+        // After the crossfade, we only hear the second source.
+        assert_eq!(
+            out[30..],
+            [
+                9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0, 17.0, 18.0, 19.0, 20.0, 21.0, 22.0,
+                23.0, 24.0, 25.0, 26.0, 27.0, 28.0, 29.0
+            ]
+        );
+    }
+
+    #[test]
+    fn test_crossfade_shorter_than_fade() {
+        let out = ones(2)
+            .append_with_crossfade(ones(3).scale(0.0), 10, FadeCurve::Linear)
+            .unwrap()
+            .collect_interleaved_samples();
+        assert_eq!(out, &[1.0, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn test_crossfade_wrong_inputs() {
+        let stereo = WaveformSource::from_interleaved_samples(44100, 2, &[1.0, 1.0]);
+        assert_eq!(
+            ones(2)
+                .append_with_crossfade(stereo, 1, FadeCurve::Linear)
+                .unwrap_err(),
+            Error::CannotAppendSourcesWithDifferentNumChannels(1, 2)
+        );
+    }
+}
@@ -0,0 +1,237 @@
+mod fixtures;
+
+mod test_filter {
+    use crate::fixtures::*;
+    use babycat::filter::{Biquad, FilterDesign};
+    use babycat::{Error, Signal, Source, Waveform, WaveformSource};
+
+    /// Returns the instantaneous frequency of the log-sweep fixture at `frame_idx`.
+    fn sweep_frequency_hz(frame_idx: usize) -> f32 {
+        let ratio = LS_END_FREQUENCY_HZ / LS_START_FREQUENCY_HZ;
+        LS_START_FREQUENCY_HZ * ratio.powf(frame_idx as f32 / LS_NUM_FRAMES as f32)
+    }
+
+    /// Returns the frame index where the log-sweep fixture reaches `frequency_hz`.
+    fn sweep_frame_idx(frequency_hz: f32) -> usize {
+        let ratio = LS_END_FREQUENCY_HZ / LS_START_FREQUENCY_HZ;
+        ((frequency_hz / LS_START_FREQUENCY_HZ).ln() / ratio.ln() * LS_NUM_FRAMES as f32) as usize
+    }
+
+    fn rms(waveform: &Waveform, start_frame_idx: usize, num_frames: usize) -> f32 {
+        let mut total: f64 = 0.0;
+        for frame_idx in start_frame_idx..start_frame_idx + num_frames {
+            let sample = waveform.get_sample(frame_idx, 0).unwrap() as f64;
+            total += sample * sample;
+        }
+        (total / num_frames as f64).sqrt() as f32
+    }
+
+    /// Filters the log sweep and checks the measured gain against the design
+    /// at every `frequencies_hz`, within `tolerance_db`.
+    fn assert_sweep_response(design: &FilterDesign, frequencies_hz: &[f32], tolerance_db: f32) {
+        let original = Waveform::from_file(LS_FILENAME, Default::default()).unwrap();
+        assert_eq!(original.num_frames(), LS_NUM_FRAMES);
+        assert_eq!(original.num_channels(), LS_NUM_CHANNELS);
+        assert_eq!(original.frame_rate_hz(), LS_FRAME_RATE_HZ);
+        let filtered = original.apply_filter(design);
+        for &frequency_hz in frequencies_hz {
+            // Measure over 25 milliseconds centered on the frequency.
+            let num_frames = LS_FRAME_RATE_HZ as usize / 40;
+            let start_frame_idx = sweep_frame_idx(frequency_hz) - num_frames / 2;
+            let center_hz = sweep_frequency_hz(start_frame_idx + num_frames / 2);
+            let measured_db = 20.0
+                * (rms(&filtered, start_frame_idx, num_frames)
+                    / rms(&original, start_frame_idx, num_frames))
+                .log10();
+            let expected_db = 20.0
+                * design
+                    .magnitude_response(LS_FRAME_RATE_HZ, center_hz)
+                    .log10();
+            assert!(
+                (measured_db - expected_db).abs() < tolerance_db,
+                "At {} hz we measured {} dB but expected {} dB",
+                center_hz,
+                measured_db,
+                expected_db
+            );
+        }
+    }
+
+    #[test]
+    fn test_sweep_butterworth_lowpass() {
+        let design = FilterDesign::butterworth_lowpass(LS_FRAME_RATE_HZ, 1000.0, 4).unwrap();
+        assert_sweep_response(
+            &design,
+            &[200.0, 500.0, 1000.0, 1500.0, 2000.0, 3000.0],
+            0.5,
+        );
+    }
+
+    #[test]
+    fn test_sweep_butterworth_highpass_odd_order() {
+        let design = FilterDesign::butterworth_highpass(LS_FRAME_RATE_HZ, 500.0, 3).unwrap();
+        assert_sweep_response(&design, &[200.0, 300.0, 500.0, 800.0, 2000.0], 0.5);
+    }
+
+    #[test]
+    fn test_sweep_linkwitz_riley() {
+        let lowpass = FilterDesign::linkwitz_riley_lowpass(LS_FRAME_RATE_HZ, 2000.0, 4).unwrap();
+        let highpass = FilterDesign::linkwitz_riley_highpass(LS_FRAME_RATE_HZ, 2000.0, 4).unwrap();
+        let at_cutoff = 20.0 * lowpass.magnitude_response(LS_FRAME_RATE_HZ, 2000.0).log10();
+        assert!((at_cutoff + 6.02).abs() < 0.01);
+        assert_sweep_response(&lowpass, &[1000.0, 2000.0, 3000.0], 0.5);
+        assert_sweep_response(&highpass, &[1000.0, 2000.0, 3000.0], 0.5);
+    }
+
+    #[test]
+    fn test_sweep_peaking_and_shelves() {
+        let design =
+            FilterDesign::from(Biquad::peaking(LS_FRAME_RATE_HZ, 1000.0, 1.0, 6.0).unwrap())
+                .then(
+                    Biquad::low_shelf(
+                        LS_FRAME_RATE_HZ,
+                        100.0,
+                        std::f32::consts::FRAC_1_SQRT_2,
+                        -6.0,
+                    )
+                    .unwrap(),
+                )
+                .then(
+                    Biquad::high_shelf(
+                        LS_FRAME_RATE_HZ,
+                        8000.0,
+                        std::f32::consts::FRAC_1_SQRT_2,
+                        3.0,
+                    )
+                    .unwrap(),
+                );
+        assert_sweep_response(&design, &[50.0, 100.0, 500.0, 1000.0, 4000.0, 12000.0], 0.5);
+    }
+
+    #[test]
+    fn test_sweep_bandpass_and_notch() {
+        let bandpass: FilterDesign = Biquad::bandpass(LS_FRAME_RATE_HZ, 1000.0, 2.0)
+            .unwrap()
+            .into();
+        assert!((bandpass.magnitude_response(LS_FRAME_RATE_HZ, 1000.0) - 1.0).abs() < 1e-4);
+        assert_sweep_response(&bandpass, &[500.0, 800.0, 1000.0, 1200.0, 2000.0], 0.5);
+        let notch: FilterDesign = Biquad::notch(LS_FRAME_RATE_HZ, 1000.0, 1.0).unwrap().into();
+        assert_sweep_response(&notch, &[300.0, 600.0, 1600.0, 3000.0], 0.5);
+    }
+
+    #[test]
+    fn test_notch_removes_hum() {
+        let frame_rate_hz = 8000;
+        let samples: Vec<f32> = (0..frame_rate_hz)
+            .map(|i| (2.0 * std::f32::consts::PI * 60.0 * i as f32 / frame_rate_hz as f32).sin())
+            .collect();
+        let waveform = Waveform::from_interleaved_samples(frame_rate_hz, 1, &samples)
+            .notch(60.0, 10.0)
+            .unwrap();
+        let tail = &waveform.to_interleaved_samples()[frame_rate_hz as usize / 2..];
+        assert!(tail.iter().all(|s| s.abs() < 0.01));
+    }
+
+    #[test]
+    fn test_allpass_keeps_magnitude() {
+        let allpass = Biquad::allpass(44100, 1000.0, 0.7).unwrap();
+        for frequency_hz in [20.0, 1000.0, 15000.0] {
+            assert!((allpass.magnitude_response(44100, frequency_hz) - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_pre_emphasis() {
+        let waveform = Waveform::from_interleaved_samples(16000, 1, &[1.0, 1.0, 0.0, 0.5]);
+        let emphasized = waveform.pre_emphasis(0.5);
+        assert_eq!(waveform.to_interleaved_samples(), &[1.0, 1.0, 0.0, 0.5]);
+        assert_eq!(emphasized.to_interleaved_samples(), &[1.0, 0.5, -0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_source_matches_waveform_and_channels_are_independent() {
+        let design = FilterDesign::butterworth_lowpass(1234, 100.0, 3).unwrap();
+        let waveform = new_small_waveform();
+        let from_waveform = waveform.apply_filter(&design);
+        let source = waveform.clone().into_source().apply_filter(&design);
+        assert_eq!(source.num_frames_estimate(), Some(10));
+        assert_eq!(source.size_hint(), (30, Some(30)));
+        assert_eq!(
+            source.collect_interleaved_samples(),
+            from_waveform.to_interleaved_samples()
+        );
+
+        // Filtering one channel on its own gives the same result as
+        // filtering it alongside the others.
+        let first_channel: Vec<f32> = waveform
+            .to_interleaved_samples()
+            .iter()
+            .step_by(3)
+            .copied()
+            .collect();
+        let mono = WaveformSource::from_interleaved_samples(1234, 1, &first_channel)
+            .apply_filter(&design)
+            .collect_interleaved_samples();
+        let interleaved_first: Vec<f32> = from_waveform
+            .to_interleaved_samples()
+            .iter()
+            .step_by(3)
+            .copied()
+            .collect();
+        assert_eq!(mono, interleaved_first);
+    }
+
+    #[test]
+    fn test_empty_design_is_passthrough() {
+        let waveform = new_small_waveform();
+        let out = waveform
+            .clone()
+            .into_source()
+            .apply_filter(&FilterDesign::default())
+            .collect_interleaved_samples();
+        assert_eq!(out, waveform.to_interleaved_samples());
+    }
+
+    #[test]
+    fn test_wrong_parameters() {
+        assert_eq!(
+            Biquad::lowpass(44100, 22050.0, 0.7),
+            Err(Error::WrongFilterFrequency(22050.0, 44100))
+        );
+        assert_eq!(
+            Biquad::highpass(44100, 0.0, 0.7),
+            Err(Error::WrongFilterFrequency(0.0, 44100))
+        );
+        assert_eq!(
+            FilterDesign::butterworth_lowpass(44100, 1000.0, 0),
+            Err(Error::WrongFilterOrder(0))
+        );
+        assert_eq!(
+            FilterDesign::linkwitz_riley_highpass(44100, 1000.0, 3),
+            Err(Error::WrongFilterOrder(3))
+        );
+        let waveform = new_small_waveform();
+        assert_eq!(
+            waveform.lowpass(1000.0, 2),
+            Err(Error::WrongFilterFrequency(1000.0, 1234))
+        );
+        for q in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                Biquad::lowpass(44100, 1000.0, q),
+                Err(Error::WrongFilterQ(_))
+            ));
+            assert!(matches!(
+                Biquad::high_shelf(44100, 1000.0, q, 6.0),
+                Err(Error::WrongFilterQ(_))
+            ));
+            assert!(matches!(
+                waveform.notch(60.0, q),
+                Err(Error::WrongFilterQ(_))
+            ));
+        }
+        assert_eq!(
+            Biquad::peaking(44100, 1000.0, 0.0, 6.0),
+            Err(Error::WrongFilterQ(0.0))
+        );
+    }
+}
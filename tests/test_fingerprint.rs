@@ -0,0 +1,162 @@
+mod fixtures;
+
+mod test_fingerprint {
+    use crate::fixtures::*;
+    use babycat::batch::{cluster_duplicates, fingerprints_from_files};
+    use babycat::constants::DEFAULT_DUPLICATE_THRESHOLD;
+    use babycat::decoder;
+    use babycat::fingerprint::Fingerprint;
+    use babycat::{Waveform, WaveformArgs};
+
+    const LS_MP3_FILENAMES: [&str; 3] = [
+        "./audio-for-tests/log-sweep/192kbps-cbr.mp3",
+        "./audio-for-tests/log-sweep/320kbps-cbr.mp3",
+        "./audio-for-tests/log-sweep/variable-low.mp3",
+    ];
+
+    fn fingerprint_file(filename: &str, waveform_args: WaveformArgs) -> Fingerprint {
+        Waveform::from_file(filename, waveform_args)
+            .unwrap()
+            .fingerprint()
+    }
+
+    #[test]
+    fn test_fingerprint_shape() {
+        let fingerprint = fingerprint_file(LS_FILENAME, Default::default());
+        assert!((fingerprint.duration_seconds - 10.0).abs() < 1e-9);
+        // One word every 4096 / 3 frames at 11025 hz, less the frames
+        // that the smoothing and the widest filter need.
+        assert_eq!(fingerprint.words.len(), 67);
+        assert_eq!(fingerprint.similarity(&fingerprint), 1.0);
+    }
+
+    #[test]
+    fn test_from_source_matches_from_waveform() {
+        for filename in [LS_FILENAME, MONO_DTMF_FILENAME] {
+            let source = decoder::from_file(filename).unwrap();
+            assert_eq!(
+                Fingerprint::from_source(source),
+                fingerprint_file(filename, Default::default())
+            );
+        }
+    }
+
+    #[test]
+    fn test_reencoded_copies_are_similar() {
+        let flac = fingerprint_file(LS_FILENAME, Default::default());
+        for filename in LS_MP3_FILENAMES {
+            let mp3 = fingerprint_file(filename, Default::default());
+            let similarity = flac.similarity(&mp3);
+            assert!(
+                similarity > DEFAULT_DUPLICATE_THRESHOLD,
+                "{}: {}",
+                filename,
+                similarity
+            );
+            assert_eq!(similarity, mp3.similarity(&flac));
+        }
+    }
+
+    #[test]
+    fn test_resampled_copies_are_similar() {
+        let original = fingerprint_file(MONO_DTMF_FILENAME, Default::default());
+        for frame_rate_hz in [22050, 48000] {
+            let args = WaveformArgs::default().set_frame_rate_hz(frame_rate_hz);
+            let resampled = fingerprint_file(MONO_DTMF_FILENAME, args);
+            let similarity = original.similarity(&resampled);
+            assert!(similarity > 0.9, "{}: {}", frame_rate_hz, similarity);
+        }
+    }
+
+    #[test]
+    fn test_trimmed_and_quieter_copies_are_similar() {
+        let original = fingerprint_file(LS_FILENAME, Default::default());
+        for start_time_milliseconds in [300, 1000, 2500] {
+            let args = WaveformArgs::default().set_start_time_milliseconds(start_time_milliseconds);
+            let trimmed = fingerprint_file(LS_FILENAME, args);
+            let similarity = original.similarity(&trimmed);
+            assert!(
+                similarity > DEFAULT_DUPLICATE_THRESHOLD,
+                "{}: {}",
+                start_time_milliseconds,
+                similarity
+            );
+        }
+
+        let waveform = Waveform::from_file(LS_FILENAME, Default::default()).unwrap();
+        let quieter: Vec<f32> = waveform
+            .to_interleaved_samples()
+            .iter()
+            .map(|sample| sample * 0.1)
+            .collect();
+        let quieter = Waveform::from_interleaved_samples(44100, 2, &quieter);
+        assert!(original.similarity(&quieter.fingerprint()) > 0.95);
+    }
+
+    #[test]
+    fn test_different_recordings_are_not_similar() {
+        let fingerprints: Vec<Fingerprint> = [
+            LS_FILENAME,
+            MONO_DTMF_FILENAME,
+            LCT_FILENAME,
+            "./audio-for-tests/circus-of-freaks/track.mp3",
+        ]
+        .iter()
+        .map(|filename| fingerprint_file(filename, Default::default()))
+        .collect();
+        for (i, a) in fingerprints.iter().enumerate() {
+            for b in &fingerprints[i + 1..] {
+                assert!(a.similarity(b) < 0.3, "{}", a.similarity(b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_short_and_silent_audio() {
+        let short = Waveform::from_frames_of_silence(44100, 2, 44100).fingerprint();
+        assert!(short.words.is_empty());
+        assert_eq!(short.similarity(&short), 0.0);
+
+        let empty = Waveform::from_frames_of_silence(44100, 1, 0).fingerprint();
+        assert_eq!(empty.duration_seconds, 0.0);
+        assert!(empty.words.is_empty());
+
+        // Silence has one word that repeats.
+        let silence = Waveform::from_frames_of_silence(22050, 1, 22050 * 5).fingerprint();
+        assert!(!silence.words.is_empty());
+        assert!(silence.words.iter().all(|word| *word == silence.words[0]));
+    }
+
+    #[test]
+    fn test_cluster_duplicates() {
+        let filenames = &[
+            LS_MP3_FILENAMES[0],
+            MONO_DTMF_FILENAME,
+            "does-not-exist",
+            LS_FILENAME,
+            LCT_FILENAME,
+            LS_MP3_FILENAMES[2],
+            "./audio-for-tests/mono-dtmf-tones/track.mp3",
+        ];
+        let fingerprints = fingerprints_from_files(filenames, Default::default());
+        assert_eq!(fingerprints.len(), filenames.len());
+        assert!(fingerprints[2].result.is_err());
+        let groups = cluster_duplicates(
+            &fingerprints,
+            DEFAULT_DUPLICATE_THRESHOLD,
+            Default::default(),
+        );
+        assert_eq!(
+            groups,
+            vec![
+                vec![LS_MP3_FILENAMES[0], LS_FILENAME, LS_MP3_FILENAMES[2]],
+                vec![
+                    MONO_DTMF_FILENAME,
+                    "./audio-for-tests/mono-dtmf-tones/track.mp3"
+                ],
+            ]
+        );
+        // Nothing is similar enough at the top of the scale.
+        assert!(cluster_duplicates(&fingerprints, 1.01, Default::default()).is_empty());
+    }
+}
@@ -0,0 +1,90 @@
+mod fixtures;
+
+mod test_gain_envelope {
+    use crate::fixtures::new_small_waveform;
+    use babycat::envelope::{EnvelopeInterpolation, EnvelopePoint, GainEnvelope};
+    use babycat::{Error, Signal, Source, WaveformSource};
+
+    #[test]
+    fn test_empty_envelope_does_nothing() {
+        let waveform = new_small_waveform();
+        let out = waveform.gain_envelope(&GainEnvelope::default());
+        assert_eq!(out, waveform);
+    }
+
+    #[test]
+    fn test_same_gain_for_every_channel() {
+        let waveform = new_small_waveform();
+        let envelope = GainEnvelope::new(
+            vec![
+                EnvelopePoint::at_frame(9, 0.0),
+                EnvelopePoint::at_frame(0, -200.0),
+            ],
+            EnvelopeInterpolation::Linear,
+        );
+        let out = waveform.gain_envelope(&envelope);
+        assert_eq!(out.num_frames(), 10);
+        for channel_idx in 0..3 {
+            assert!(out.get_sample(0, channel_idx).unwrap().abs() < 1e-6);
+            assert_eq!(
+                out.get_sample(9, channel_idx).unwrap(),
+                waveform.get_sample(9, channel_idx).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_exponential_interpolation_is_linear_in_decibels() {
+        let source = WaveformSource::from_interleaved_samples(44100, 1, &[1.0; 11]);
+        let envelope = GainEnvelope::new(
+            vec![
+                EnvelopePoint::at_frame(0, 0.0),
+                EnvelopePoint::at_frame(10, -20.0),
+            ],
+            EnvelopeInterpolation::Exponential,
+        );
+        let out = source
+            .gain_envelope(&envelope)
+            .collect_interleaved_samples();
+        for (frame_idx, sample) in out.iter().enumerate() {
+            let expected_dbfs = -2.0 * frame_idx as f32;
+            assert!((20.0 * sample.log10() - expected_dbfs).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_holds_after_last_point() {
+        let source = WaveformSource::from_interleaved_samples(1000, 2, &[1.0; 10]);
+        let envelope = GainEnvelope::new(
+            vec![EnvelopePoint::at_milliseconds(1, -6.0)],
+            EnvelopeInterpolation::Linear,
+        );
+        let out = source.gain_envelope(&envelope);
+        assert_eq!(out.num_frames_estimate(), Some(5));
+        let samples = out.collect_interleaved_samples();
+        assert!(samples.iter().all(|s| (*s - 0.5011872).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let envelope = GainEnvelope::new(
+            vec![
+                EnvelopePoint::at_frame(0, 0.0),
+                EnvelopePoint::at_milliseconds(500, -12.0),
+            ],
+            EnvelopeInterpolation::Exponential,
+        );
+        let json = envelope.to_json();
+        assert_eq!(
+            json,
+            r#"{"points":[{"frame":0,"dbfs":0.0},{"milliseconds":500,"dbfs":-12.0}],"interpolation":"exponential"}"#
+        );
+        assert_eq!(GainEnvelope::from_json(&json).unwrap(), envelope);
+    }
+
+    #[test]
+    fn test_json_parse_error() {
+        let result = GainEnvelope::from_json(r#"{"points": [{"dbfs": 0.0}]}"#);
+        assert!(matches!(result, Err(Error::CannotParseGainEnvelope(_))));
+    }
+}
@@ -0,0 +1,102 @@
+mod fixtures;
+
+mod test_gate {
+    use babycat::source::{ChannelLink, GateArgs};
+    use babycat::{Signal, Source, WaveformSource};
+
+    fn mono(samples: &[f32]) -> WaveformSource {
+        WaveformSource::from_interleaved_samples(1000, 1, samples)
+    }
+
+    fn gain(reduction_db: f32) -> f32 {
+        10.0_f32.powf(-reduction_db / 20.0)
+    }
+
+    fn instant_args() -> GateArgs {
+        GateArgs::default()
+            .set_threshold_dbfs(-40.0)
+            .set_range_db(60.0)
+            .set_attack_milliseconds(0)
+            .set_hold_milliseconds(0)
+            .set_release_milliseconds(0)
+    }
+
+    /// Returns a -6 dBFS step, then -60 dBFS of noise floor, then the step again.
+    fn steps() -> Vec<f32> {
+        let mut samples = vec![0.5_f32; 50];
+        samples.extend(vec![0.001_f32; 100]);
+        samples.extend(vec![0.5_f32; 50]);
+        samples
+    }
+
+    #[test]
+    fn test_hold_and_release_of_step() {
+        let args = instant_args()
+            .set_hold_milliseconds(20)
+            .set_release_milliseconds(10);
+        let gated = mono(&steps()).gate(args);
+        assert_eq!(gated.num_frames_estimate(), Some(200));
+        let out = gated.collect_interleaved_samples();
+        assert_eq!(out.len(), 200);
+
+        assert!(out[..50].iter().all(|&s| s == 0.5));
+        // The gate stays open during the hold...
+        assert!(out[50..70].iter().all(|&s| s == 0.001));
+        // ...then closes over the release.
+        assert!((out[70] / 0.001 - gain(60.0 * (1.0 - (-0.1_f32).exp()))).abs() < 1e-5);
+        for pair in out[70..150].windows(2) {
+            assert!(pair[1] < pair[0]);
+        }
+        assert!((out[149] / 0.001 - gain(60.0)).abs() < 1e-5);
+        // With an instant attack, the gate opens right away.
+        assert!(out[150..].iter().all(|&s| s == 0.5));
+    }
+
+    #[test]
+    fn test_attack_of_step() {
+        let args = instant_args().set_attack_milliseconds(5);
+        let out = mono(&steps()).gate(args).collect_interleaved_samples();
+        assert!(out[150] < 0.5);
+        for pair in out[150..].windows(2) {
+            assert!(pair[1] > pair[0]);
+        }
+        let after_attack = 0.5 * gain(60.0 * (-1.0_f32).exp());
+        assert!((out[154] - after_attack).abs() < 1e-5);
+        assert!((out[199] - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_expander_ratio_and_range() {
+        let quiet = 10.0_f32.powf(-50.0 / 20.0);
+        // 10 dB under the threshold with a 2:1 ratio is lowered by another 10 dB.
+        let args = instant_args().set_ratio(2.0);
+        let out = mono(&[quiet]).gate(args).collect_interleaved_samples();
+        assert!((out[0] - quiet * gain(10.0)).abs() < 1e-7);
+        // A hard gate is limited by the range.
+        let args = instant_args().set_range_db(20.0);
+        let out = mono(&[quiet]).gate(args).collect_interleaved_samples();
+        assert!((out[0] - quiet * gain(20.0)).abs() < 1e-7);
+        // An infinite range silences the audio.
+        let args = instant_args().set_range_db(f32::INFINITY);
+        let out = mono(&[quiet, 0.0, 0.5])
+            .gate(args)
+            .collect_interleaved_samples();
+        assert_eq!(out, &[0.0, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn test_channel_linking() {
+        let stereo = || WaveformSource::from_interleaved_samples(1000, 2, &[0.5, 0.001]);
+
+        let out = stereo()
+            .gate(instant_args().set_link(ChannelLink::Max))
+            .collect_interleaved_samples();
+        assert_eq!(out, &[0.5, 0.001]);
+
+        let out = stereo()
+            .gate(instant_args().set_link(ChannelLink::Independent))
+            .collect_interleaved_samples();
+        assert_eq!(out[0], 0.5);
+        assert!((out[1] - 0.001 * gain(60.0)).abs() < 1e-9);
+    }
+}
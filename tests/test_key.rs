@@ -0,0 +1,167 @@
+mod fixtures;
+
+mod test_key {
+    use babycat::key::{key, key_cqt_args, key_from_chroma, key_index, Mode};
+    use babycat::spectral::{CqtArgs, MelArgs, StftArgs};
+    use babycat::{Error, Waveform};
+
+    const FRAME_RATE_HZ: u32 = 22050;
+
+    /// Semitones above the tonic of every note in a major scale.
+    const MAJOR_SCALE: [usize; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+    /// Semitones above the tonic of every note in a harmonic minor scale.
+    const HARMONIC_MINOR_SCALE: [usize; 7] = [0, 2, 3, 5, 7, 8, 11];
+
+    fn args() -> CqtArgs {
+        CqtArgs::default().set_bins_per_octave(36).set_num_bins(252)
+    }
+
+    /// Returns the frequency of a pitch class in the octave above middle C.
+    fn note_hz(pitch_class: usize) -> f32 {
+        261.63 * 2.0_f32.powf(pitch_class as f32 / 12.0)
+    }
+
+    /// Returns a mono waveform that plays every pitch class in turn for
+    /// a quarter of a second, with the tonic played twice as long.
+    fn scale(tonic: usize, steps: &[usize]) -> Waveform {
+        scale_at(FRAME_RATE_HZ, tonic, steps)
+    }
+
+    /// Returns the same waveform as [`scale()`] at another frame rate.
+    fn scale_at(frame_rate_hz: u32, tonic: usize, steps: &[usize]) -> Waveform {
+        let mut notes: Vec<usize> = steps.iter().map(|step| (tonic + step) % 12).collect();
+        notes.push(tonic);
+        let note_len = frame_rate_hz as usize / 4;
+        let samples: Vec<f32> = (0..notes.len() * note_len)
+            .map(|i| {
+                let t = i as f32 / frame_rate_hz as f32;
+                0.5 * (2.0 * std::f32::consts::PI * note_hz(notes[i / note_len]) * t).sin()
+            })
+            .collect();
+        Waveform::from_interleaved_samples(frame_rate_hz, 1, &samples)
+    }
+
+    #[test]
+    fn test_every_major_key() {
+        for tonic in 0..12 {
+            let estimate = scale(tonic, &MAJOR_SCALE).key(args()).unwrap().unwrap();
+            assert_eq!(estimate.tonic, tonic, "{}", estimate.name);
+            assert_eq!(estimate.mode, Mode::Major, "{}", estimate.name);
+            assert!(estimate.confidence > 0.7, "{:?}", estimate);
+        }
+    }
+
+    #[test]
+    fn test_every_minor_key() {
+        for tonic in 0..12 {
+            let estimate = scale(tonic, &HARMONIC_MINOR_SCALE)
+                .key(args())
+                .unwrap()
+                .unwrap();
+            assert_eq!(estimate.tonic, tonic, "{}", estimate.name);
+            assert_eq!(estimate.mode, Mode::Minor, "{}", estimate.name);
+            assert!(estimate.confidence > 0.7, "{:?}", estimate);
+        }
+    }
+
+    #[test]
+    fn test_names_and_camelot() {
+        let estimate = key(&scale(0, &MAJOR_SCALE), args()).unwrap().unwrap();
+        assert_eq!(estimate.name, "C major");
+        assert_eq!(estimate.camelot, "8B");
+        let estimate = key(&scale(9, &HARMONIC_MINOR_SCALE), args())
+            .unwrap()
+            .unwrap();
+        assert_eq!(estimate.name, "A minor");
+        assert_eq!(estimate.camelot, "8A");
+        let estimate = key(&scale(6, &MAJOR_SCALE), args()).unwrap().unwrap();
+        assert_eq!(estimate.name, "F# major");
+        assert_eq!(estimate.camelot, "2B");
+        let estimate = key(&scale(2, &HARMONIC_MINOR_SCALE), args())
+            .unwrap()
+            .unwrap();
+        assert_eq!(estimate.name, "D minor");
+        assert_eq!(estimate.camelot, "7A");
+    }
+
+    #[test]
+    fn test_correlations() {
+        let estimate = scale(7, &MAJOR_SCALE).key(args()).unwrap().unwrap();
+        assert_eq!(estimate.correlations.len(), 24);
+        assert!(estimate
+            .correlations
+            .iter()
+            .all(|value| (-1.0..=1.0).contains(value)));
+        let best = key_index(estimate.tonic, estimate.mode);
+        assert_eq!(best, 7);
+        assert_eq!(estimate.confidence, estimate.correlations[best]);
+        assert!(estimate
+            .correlations
+            .iter()
+            .all(|value| *value <= estimate.correlations[best]));
+        assert_eq!(key_index(9, Mode::Minor), 21);
+    }
+
+    #[test]
+    fn test_stereo_audio() {
+        let mono = scale(4, &MAJOR_SCALE);
+        let interleaved: Vec<f32> = mono
+            .to_interleaved_samples()
+            .iter()
+            .flat_map(|s| [*s, 0.5 * s])
+            .collect();
+        let stereo = Waveform::from_interleaved_samples(FRAME_RATE_HZ, 2, &interleaved);
+        let estimate = stereo.key(args()).unwrap().unwrap();
+        assert_eq!(estimate.name, "E major");
+        assert_eq!(estimate, mono.key(args()).unwrap().unwrap());
+    }
+
+    #[test]
+    fn test_silence_has_no_key() {
+        let waveform = Waveform::from_frames_of_silence(FRAME_RATE_HZ, 2, 22050);
+        assert_eq!(waveform.key(args()), Ok(None));
+        let waveform = Waveform::from_frames_of_silence(FRAME_RATE_HZ, 1, 0);
+        assert_eq!(waveform.key(args()), Ok(None));
+    }
+
+    #[test]
+    fn test_wrong_args() {
+        let waveform = scale(0, &MAJOR_SCALE);
+        assert_eq!(
+            waveform.key(args().set_hop_length(0)),
+            Err(Error::WrongHopLength(0))
+        );
+        let mel = waveform
+            .mel_spectrogram(StftArgs::default(), MelArgs::default())
+            .unwrap();
+        assert_eq!(
+            key_from_chroma(&mel),
+            Err(Error::WrongNumFeatures(mel.num_features(), 12))
+        );
+    }
+
+    #[test]
+    fn test_key_cqt_args() {
+        assert_eq!(key_cqt_args(FRAME_RATE_HZ), Some(args()));
+        assert_eq!(key_cqt_args(44100), Some(args()));
+        // At 8 khz, the seventh octave reaches past the Nyquist frequency.
+        let narrowband = key_cqt_args(8000).unwrap();
+        assert_eq!(narrowband.bins_per_octave, 36);
+        assert_eq!(narrowband.num_bins, 216);
+        let waveform = scale_at(8000, 0, &MAJOR_SCALE);
+        assert!(waveform.key(args()).is_err());
+        assert_eq!(waveform.key(narrowband).unwrap().unwrap().name, "C major");
+        for frame_rate_hz in [11025, 16000, 22050, 48000] {
+            let waveform = scale_at(frame_rate_hz, 9, &HARMONIC_MINOR_SCALE);
+            let estimate = waveform.key(key_cqt_args(frame_rate_hz).unwrap());
+            assert_eq!(
+                estimate.unwrap().unwrap().name,
+                "A minor",
+                "{} hz",
+                frame_rate_hz
+            );
+        }
+        assert_eq!(key_cqt_args(100), None);
+    }
+}
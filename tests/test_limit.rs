@@ -0,0 +1,107 @@
+mod fixtures;
+
+mod test_limit {
+    use babycat::source::{ChannelLink, LimitArgs};
+    use babycat::{Signal, Source, WaveformSource};
+
+    fn mono(samples: &[f32]) -> WaveformSource {
+        WaveformSource::from_interleaved_samples(1000, 1, samples)
+    }
+
+    /// Returns loud pseudo-random samples between -2.0 and 2.0.
+    fn loud_noise(num_samples: usize) -> Vec<f32> {
+        let mut state: u32 = 12345;
+        (0..num_samples)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                (state >> 8) as f32 / (1 << 24) as f32 * 4.0 - 2.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_quiet_audio_passes_through() {
+        let samples = vec![0.5, -0.25, 0.125, 0.5, -0.5];
+        let limited = mono(&samples).limit(LimitArgs::default());
+        assert_eq!(limited.num_frames_estimate(), Some(5));
+        assert_eq!(limited.collect_interleaved_samples(), samples);
+    }
+
+    #[test]
+    fn test_never_exceeds_ceiling() {
+        let samples = loud_noise(20000);
+        for &link in &[
+            ChannelLink::Max,
+            ChannelLink::Independent,
+            ChannelLink::Average,
+        ] {
+            let args = LimitArgs::default().set_ceiling_dbfs(-3.0).set_link(link);
+            let out = WaveformSource::from_interleaved_samples(44100, 2, &samples)
+                .limit(args)
+                .collect_interleaved_samples();
+            assert_eq!(out.len(), samples.len());
+            let ceiling = 10.0_f32.powf(-3.0 / 20.0);
+            assert!(out.iter().all(|s| s.abs() <= ceiling));
+        }
+    }
+
+    #[test]
+    fn test_lookahead_and_release_of_step() {
+        let mut samples = vec![0.25_f32; 100];
+        samples.extend(vec![1.0_f32; 100]);
+        samples.extend(vec![0.25_f32; 200]);
+        let args = LimitArgs::default()
+            .set_ceiling_dbfs(-6.0206)
+            .set_lookahead_milliseconds(10)
+            .set_release_milliseconds(100);
+        let out = mono(&samples).limit(args).collect_interleaved_samples();
+        assert_eq!(out.len(), 400);
+
+        // The output is aligned with the input, and the gain only starts
+        // to come down one lookahead window before the step.
+        assert!(out[..90].iter().all(|&s| s == 0.25));
+        assert!(out[90] < 0.25);
+        for pair in out[90..100].windows(2) {
+            assert!(pair[1] < pair[0]);
+        }
+
+        // The step is held right at the ceiling.
+        assert!(out[100..200].iter().all(|&s| s <= 0.5));
+        assert!((out[150] - 0.5).abs() < 1e-4);
+
+        // Then the gain comes back up.
+        for pair in out[200..].windows(2) {
+            assert!(pair[1] >= pair[0]);
+        }
+        assert!(out[399] > 0.2 && out[399] < 0.25);
+    }
+
+    #[test]
+    fn test_channel_linking() {
+        let samples = [1.0, 0.25, 1.0, 0.25];
+        let mut args = LimitArgs::default()
+            .set_ceiling_dbfs(-6.0206)
+            .set_lookahead_milliseconds(0);
+
+        let out = WaveformSource::from_interleaved_samples(1000, 2, &samples)
+            .limit(args.set_link(ChannelLink::Max))
+            .collect_interleaved_samples();
+        assert!((out[3] - 0.125).abs() < 1e-4);
+
+        let out = WaveformSource::from_interleaved_samples(1000, 2, &samples)
+            .limit(args.set_link(ChannelLink::Independent))
+            .collect_interleaved_samples();
+        assert_eq!(out[3], 0.25);
+    }
+
+    #[test]
+    fn test_size_hint() {
+        let mut limited = WaveformSource::from_interleaved_samples(1000, 2, &loud_noise(40))
+            .limit(LimitArgs::default());
+        for remaining in (0..=40).rev() {
+            assert_eq!(limited.size_hint(), (remaining, Some(remaining)));
+            limited.next();
+        }
+        assert_eq!(limited.next(), None);
+    }
+}
@@ -0,0 +1,107 @@
+mod fixtures;
+
+mod test_loop {
+    use crate::fixtures::new_small_waveform;
+    use babycat::{Signal, Source, Waveform, WaveformArgs, WaveformSource};
+
+    fn mono(samples: &[f32]) -> WaveformSource {
+        WaveformSource::from_interleaved_samples(44100, 1, samples)
+    }
+
+    #[test]
+    fn test_repeat() {
+        let waveform = new_small_waveform();
+        let samples = waveform.to_interleaved_samples().to_vec();
+        let repeated = waveform.into_source().repeat(3);
+        assert_eq!(repeated.num_frames_estimate(), Some(30));
+        assert_eq!(repeated.size_hint(), (90, Some(90)));
+        let out = repeated.collect_interleaved_samples();
+        assert_eq!(out, samples.repeat(3));
+    }
+
+    #[test]
+    fn test_repeat_zero_and_one_times() {
+        let zero = mono(&[0.1, 0.2]).repeat(0);
+        assert_eq!(zero.num_frames_estimate(), Some(0));
+        assert_eq!(zero.collect_interleaved_samples(), Vec::<f32>::new());
+        assert_eq!(
+            mono(&[0.1, 0.2]).repeat(1).collect_interleaved_samples(),
+            &[0.1, 0.2]
+        );
+    }
+
+    #[test]
+    fn test_loop_forever() {
+        let looped = mono(&[0.1, 0.2, 0.3]).loop_forever();
+        assert_eq!(looped.num_frames_estimate(), None);
+        assert_eq!(looped.size_hint(), (usize::MAX, None));
+        let out: Vec<f32> = looped.take(1000).collect();
+        assert_eq!(out.len(), 1000);
+        assert_eq!(out[999], 0.1);
+    }
+
+    #[test]
+    fn test_loop_forever_empty() {
+        let looped = mono(&[]).loop_forever();
+        assert_eq!(looped.num_frames_estimate(), Some(0));
+        assert_eq!(looped.collect_interleaved_samples(), Vec::<f32>::new());
+    }
+
+    #[test]
+    fn test_loop_frames() {
+        let waveform = new_small_waveform();
+        let looped = waveform.into_source().loop_frames(2, 4, Some(3));
+        assert_eq!(looped.num_frames_estimate(), Some(14));
+        assert_eq!(looped.size_hint(), (42, Some(42)));
+        let out = looped.collect_interleaved_samples();
+        let mut expected: Vec<f32> = (0..12).map(|i| i as f32).collect();
+        for _ in 0..2 {
+            expected.extend((6..12).map(|i| i as f32));
+        }
+        expected.extend((12..30).map(|i| i as f32));
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_loop_frames_zero_times_skips_region() {
+        let out = mono(&[0.0, 0.1, 0.2, 0.3])
+            .loop_frames(1, 3, Some(0))
+            .collect_interleaved_samples();
+        assert_eq!(out, &[0.0, 0.3]);
+    }
+
+    #[test]
+    fn test_loop_frames_past_end() {
+        let looped = mono(&[0.0, 0.1, 0.2]).loop_frames(1, 10, Some(2));
+        assert_eq!(looped.num_frames_estimate(), Some(5));
+        assert_eq!(
+            looped.collect_interleaved_samples(),
+            &[0.0, 0.1, 0.2, 0.1, 0.2]
+        );
+    }
+
+    #[test]
+    fn test_loop_frames_forever() {
+        let out: Vec<f32> = mono(&[0.0, 0.1, 0.2, 0.3])
+            .loop_frames(1, 3, None)
+            .take(7)
+            .collect();
+        assert_eq!(out, &[0.0, 0.1, 0.2, 0.1, 0.2, 0.1, 0.2]);
+    }
+
+    #[test]
+    fn test_repeat_pad_ending() {
+        // 1234 hz, so 2ms is 2 frames and 20ms is 24 frames.
+        let waveform = new_small_waveform();
+        let args = WaveformArgs {
+            start_time_milliseconds: 2,
+            end_time_milliseconds: 20,
+            repeat_pad_ending: true,
+            ..Default::default()
+        };
+        let padded = Waveform::from_source(args, Box::new(waveform.into_source())).unwrap();
+        assert_eq!(padded.num_frames(), 22);
+        let expected: Vec<f32> = (0..66).map(|i| (6 + i % 24) as f32).collect();
+        assert_eq!(padded.to_interleaved_samples(), &expected[..]);
+    }
+}
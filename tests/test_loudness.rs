@@ -0,0 +1,169 @@
+mod fixtures;
+mod signals;
+
+mod test_loudness {
+    use crate::fixtures::*;
+    use crate::signals::assert_close;
+    use babycat::decoder;
+    use babycat::loudness::{
+        integrated_loudness, true_peak_dbtp, LoudnessMeter, LoudnessReport,
+        LOUDNESS_MIN_FRAME_RATE_HZ, MIN_REPORTED_LEVEL,
+    };
+    use babycat::{Error, Waveform};
+
+    /// Returns a stereo 1 khz sine where each channel peaks at `dbfs`.
+    fn stereo_tone(dbfs: f32, num_seconds: usize) -> Vec<f32> {
+        let amplitude = 10.0_f32.powf(dbfs / 20.0);
+        (0..num_seconds * 48000)
+            .flat_map(|frame_idx| {
+                let t = (frame_idx % 48) as f32 / 48.0;
+                let value = amplitude * (2.0 * std::f32::consts::PI * t).sin();
+                vec![value, value]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ebu_3341_stereo_tone() {
+        let waveform = Waveform::from_interleaved_samples(48000, 2, &stereo_tone(-23.0, 20));
+        let report = LoudnessReport::from_waveform(&waveform).unwrap();
+        assert_close(report.integrated_lufs.unwrap(), -23.0, 0.1);
+        assert!(report
+            .momentary_lufs
+            .iter()
+            .all(|&m| (m + 23.0).abs() < 0.1));
+        assert!(report
+            .short_term_lufs
+            .iter()
+            .all(|&s| (s + 23.0).abs() < 0.1));
+        for &peak in &report.sample_peaks_dbfs {
+            assert_close(peak, -23.0, 0.01);
+        }
+    }
+
+    #[test]
+    fn test_ebu_3342_loudness_range() {
+        let samples: Vec<f32> = stereo_tone(-20.0, 20)
+            .into_iter()
+            .chain(stereo_tone(-30.0, 20))
+            .collect();
+        let waveform = Waveform::from_interleaved_samples(48000, 2, &samples);
+        let report = LoudnessReport::from_waveform(&waveform).unwrap();
+        assert_close(report.loudness_range_lu.unwrap(), 10.0, 1.0);
+    }
+
+    #[test]
+    fn test_series_lengths() {
+        let waveform = Waveform::from_interleaved_samples(48000, 2, &stereo_tone(-20.0, 10));
+        let report = LoudnessReport::from_waveform(&waveform).unwrap();
+        assert_eq!(report.frame_rate_hz, 48000);
+        assert_eq!(report.num_channels, 2);
+        assert_eq!(report.num_frames, 480000);
+        // 100 steps of 100 milliseconds.
+        assert_eq!(report.momentary_lufs.len(), 97);
+        assert_eq!(report.short_term_lufs.len(), 71);
+    }
+
+    #[test]
+    fn test_short_audio_has_no_loudness_range() {
+        let waveform = Waveform::from_interleaved_samples(48000, 2, &stereo_tone(-20.0, 2));
+        let report = LoudnessReport::from_waveform(&waveform).unwrap();
+        assert!(report.integrated_lufs.is_some());
+        assert_eq!(report.loudness_range_lu, None);
+        assert!(report.short_term_lufs.is_empty());
+    }
+
+    #[test]
+    fn test_silence_is_floored() {
+        let waveform = Waveform::from_frames_of_silence(44100, 2, 441000);
+        let report = LoudnessReport::from_waveform(&waveform).unwrap();
+        assert_eq!(report.integrated_lufs, None);
+        assert_eq!(report.loudness_range_lu, None);
+        assert!(report
+            .momentary_lufs
+            .iter()
+            .all(|&m| m == MIN_REPORTED_LEVEL));
+        assert_eq!(report.sample_peaks_dbfs, vec![MIN_REPORTED_LEVEL; 2]);
+        assert_eq!(report.true_peaks_dbtp, vec![MIN_REPORTED_LEVEL; 2]);
+        // Every value is finite, so the report survives a JSON round trip.
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed: LoudnessReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn test_peaks_are_per_channel() {
+        let samples: Vec<f32> = stereo_tone(-6.0, 1)
+            .chunks(2)
+            .flat_map(|frame| vec![frame[0], 0.0])
+            .collect();
+        let waveform = Waveform::from_interleaved_samples(48000, 2, &samples);
+        let report = LoudnessReport::from_waveform(&waveform).unwrap();
+        assert_close(report.sample_peaks_dbfs[0], -6.0, 0.01);
+        assert!(report.true_peaks_dbtp[0] >= report.sample_peaks_dbfs[0]);
+        assert_eq!(report.sample_peaks_dbfs[1], MIN_REPORTED_LEVEL);
+        assert_eq!(report.true_peaks_dbtp[1], MIN_REPORTED_LEVEL);
+    }
+
+    #[test]
+    fn test_log_sweep_streaming_matches_waveform() {
+        let waveform = Waveform::from_file(LS_FILENAME, Default::default()).unwrap();
+        let from_waveform = LoudnessReport::from_waveform(&waveform).unwrap();
+        let from_source =
+            LoudnessReport::from_source(decoder::from_file(LS_FILENAME).unwrap()).unwrap();
+        assert_eq!(from_waveform, from_source);
+        assert_eq!(from_waveform.num_frames, LS_NUM_FRAMES);
+        assert_eq!(from_waveform.num_channels, LS_NUM_CHANNELS);
+        assert_eq!(
+            from_waveform.integrated_lufs,
+            integrated_loudness(&waveform).unwrap()
+        );
+        for (true_peak, sample_peak) in from_waveform
+            .true_peaks_dbtp
+            .iter()
+            .zip(&from_waveform.sample_peaks_dbfs)
+        {
+            assert!(true_peak >= sample_peak);
+        }
+    }
+
+    #[test]
+    fn test_meter_accepts_partial_frames() {
+        let samples = stereo_tone(-20.0, 1);
+        let mut meter = LoudnessMeter::new(48000, 2).unwrap();
+        for chunk in samples.chunks(7) {
+            meter.push_interleaved_samples(chunk);
+        }
+        let waveform = Waveform::from_interleaved_samples(48000, 2, &samples);
+        assert_eq!(
+            meter.finish(),
+            LoudnessReport::from_waveform(&waveform).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_zero_channels() {
+        assert_eq!(
+            LoudnessMeter::new(48000, 0).unwrap_err(),
+            Error::WrongNumChannels(1, 0)
+        );
+    }
+
+    #[test]
+    fn test_frame_rate_too_low() {
+        for frame_rate_hz in [0, 1, LOUDNESS_MIN_FRAME_RATE_HZ - 1] {
+            let expected = Error::FrameRateTooLow(frame_rate_hz, LOUDNESS_MIN_FRAME_RATE_HZ);
+            assert_eq!(LoudnessMeter::new(frame_rate_hz, 2).unwrap_err(), expected);
+            let waveform = Waveform::from_interleaved_samples(frame_rate_hz, 1, &[0.5; 100]);
+            assert_eq!(
+                LoudnessReport::from_waveform(&waveform).unwrap_err(),
+                expected
+            );
+            assert_eq!(integrated_loudness(&waveform).unwrap_err(), expected);
+            assert_eq!(true_peak_dbtp(&waveform).unwrap_err(), expected);
+        }
+        let waveform =
+            Waveform::from_interleaved_samples(LOUDNESS_MIN_FRAME_RATE_HZ, 1, &[0.5; 100]);
+        assert!(LoudnessReport::from_waveform(&waveform).is_ok());
+    }
+}
@@ -0,0 +1,459 @@
+mod fixtures;
+mod signals;
+
+mod test_mel {
+    use crate::fixtures::*;
+    use crate::signals::{assert_close, sine};
+    use babycat::batch::{mel_spectrograms_from_files, mfccs_from_files};
+    use babycat::constants::{MEL_SCALE_HTK, MEL_SCALE_SLANEY};
+    use babycat::spectral::mel::{hz_to_mel, mel_to_hz};
+    use babycat::spectral::{
+        mel_filterbank, mel_spectrogram, mfcc, Features, MelArgs, MfccArgs, StftArgs,
+    };
+    use babycat::{Error, Waveform};
+
+    fn log_sweep() -> Waveform {
+        Waveform::from_file(LS_PCM_16_FILENAME, Default::default()).unwrap()
+    }
+
+    #[test]
+    fn test_hz_to_mel() {
+        // These are the examples from the librosa documentation.
+        assert_close(hz_to_mel(60.0, MEL_SCALE_SLANEY), 0.9, 1e-5);
+        assert_close(hz_to_mel(110.0, MEL_SCALE_SLANEY), 1.65, 1e-5);
+        assert_close(hz_to_mel(220.0, MEL_SCALE_SLANEY), 3.3, 1e-5);
+        assert_close(hz_to_mel(440.0, MEL_SCALE_SLANEY), 6.6, 1e-5);
+        assert_close(mel_to_hz(3.0, MEL_SCALE_SLANEY), 200.0, 1e-3);
+        // Above 1000 hz, every 27 mels is a factor of 6.4.
+        assert_close(hz_to_mel(6400.0, MEL_SCALE_SLANEY), 15.0 + 27.0, 1e-4);
+        // The HTK scale maps 1000 hz to about 1000 mels.
+        assert_close(hz_to_mel(1000.0, MEL_SCALE_HTK), 999.9855, 1e-3);
+        for hz in [0.0, 60.0, 999.0, 1000.0, 4000.0, 11025.0] {
+            for mel_scale in [MEL_SCALE_SLANEY, MEL_SCALE_HTK] {
+                let round_trip = mel_to_hz(hz_to_mel(hz, mel_scale), mel_scale);
+                assert_close(round_trip, hz, hz * 1e-5 + 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_filterbank_matches_librosa() {
+        // librosa.filters.mel(sr=22050, n_fft=2048) has the shape (128, 1025),
+        // and its first row starts with [0, 0.016, ...].
+        let filterbank = mel_filterbank(22050, 2048, MelArgs::default()).unwrap();
+        assert_eq!(filterbank.len(), 128 * 1025);
+        assert_eq!(filterbank[0], 0.0);
+        assert_close(filterbank[1], 0.016_18, 5e-5);
+        assert!(filterbank.iter().all(|w| *w >= 0.0));
+
+        // With Slaney normalization, each triangle has an area of 1 in hz,
+        // which the bins approximate well for the wide, high bands.
+        let bin_width_hz = 22050.0 / 2048.0;
+        for row in filterbank.as_chunks::<1025>().0.iter().skip(64) {
+            let area: f32 = row.iter().sum::<f32>() * bin_width_hz;
+            assert_close(area, 1.0, 0.02);
+        }
+    }
+
+    #[test]
+    fn test_filterbank_htk_unnormalized() {
+        let args = MelArgs::default()
+            .set_num_mels(40)
+            .set_mel_scale(MEL_SCALE_HTK)
+            .set_normalize(false)
+            .set_min_frequency_hz(20.0)
+            .set_max_frequency_hz(8000.0);
+        let filterbank = mel_filterbank(16000, 512, args).unwrap();
+        assert_eq!(filterbank.len(), 40 * 257);
+        let min_mel = hz_to_mel(20.0, MEL_SCALE_HTK);
+        let max_mel = hz_to_mel(8000.0, MEL_SCALE_HTK);
+        for (mel_idx, row) in filterbank.as_chunks::<257>().0.iter().enumerate() {
+            // Each triangle peaks at 1 on its center frequency, so the
+            // loudest bin is the one closest to the center.
+            let peak = row.iter().copied().fold(0.0_f32, f32::max);
+            assert!(
+                peak <= 1.0 && peak > 0.4,
+                "band {} peaks at {}",
+                mel_idx,
+                peak
+            );
+            let center_mel = min_mel + (max_mel - min_mel) * (mel_idx + 1) as f32 / 41.0;
+            let center_hz = mel_to_hz(center_mel, MEL_SCALE_HTK);
+            let peak_bin = row.iter().position(|w| *w == peak).unwrap();
+            assert!((peak_bin as f32 * 16000.0 / 512.0 - center_hz).abs() <= 16000.0 / 512.0);
+        }
+    }
+
+    #[test]
+    fn test_filterbank_wrong_args() {
+        assert_eq!(
+            mel_filterbank(16000, 512, MelArgs::default().set_num_mels(0)),
+            Err(Error::WrongNumFeatures(0, 257))
+        );
+        assert_eq!(
+            mel_filterbank(16000, 512, MelArgs::default().set_max_frequency_hz(9000.0)),
+            Err(Error::WrongFilterFrequency(9000.0, 16000))
+        );
+        assert_eq!(
+            mel_filterbank(16000, 512, MelArgs::default().set_min_frequency_hz(8000.0)),
+            Err(Error::WrongFilterFrequency(8000.0, 16000))
+        );
+        assert_eq!(
+            mel_filterbank(16000, 512, MelArgs::default().set_mel_scale(99)),
+            Err(Error::FeatureNotCompiled("unknown-mel-scale"))
+        );
+        assert_eq!(
+            mel_filterbank(16000, 0, MelArgs::default()),
+            Err(Error::WrongFftSize(0, 0))
+        );
+    }
+
+    #[test]
+    fn test_mel_spectrogram_is_filterbank_times_power() {
+        let waveform = log_sweep();
+        let stft_args = StftArgs::default();
+        let mel_args = MelArgs::default().set_num_mels(64);
+        let stft = waveform.stft(stft_args).unwrap();
+        let mel = mel_spectrogram(&stft, mel_args).unwrap();
+        assert_eq!(mel.frame_rate_hz(), LS_FRAME_RATE_HZ);
+        assert_eq!(mel.hop_length(), 512);
+        assert_eq!(mel.num_channels(), LS_NUM_CHANNELS);
+        assert_eq!(mel.num_features(), 64);
+        assert_eq!(mel.num_time_steps(), stft.num_time_steps());
+        assert_eq!(mel, waveform.mel_spectrogram(stft_args, mel_args).unwrap());
+
+        let filterbank = mel_filterbank(LS_FRAME_RATE_HZ, 2048, mel_args).unwrap();
+        let powers = stft.powers();
+        let num_bins = stft.num_bins();
+        let num_time_steps = stft.num_time_steps();
+        for channel_idx in 0..2_u16 {
+            for time_step_idx in (0..num_time_steps).step_by(97) {
+                for mel_idx in (0..64).step_by(7) {
+                    let expected: f32 = (0..num_bins)
+                        .map(|bin_idx| {
+                            filterbank[mel_idx * num_bins + bin_idx]
+                                * powers[(channel_idx as usize * num_bins + bin_idx)
+                                    * num_time_steps
+                                    + time_step_idx]
+                        })
+                        .sum();
+                    let actual = mel.get(channel_idx, mel_idx, time_step_idx).unwrap();
+                    assert_close(actual, expected, expected.abs() * 1e-4 + 1e-6);
+                }
+            }
+        }
+        assert_eq!(mel.get(2, 0, 0), None);
+        assert_eq!(mel.get(0, 64, 0), None);
+    }
+
+    /// Reads reference features written by `tests/reference/librosa_features.py`.
+    fn librosa_reference(name: &str) -> serde_json::Value {
+        let path = format!("./tests/reference/librosa_{}.json", name);
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    /// Checks the default mel spectrogram and MFCCs of a fixture against
+    /// the librosa reference features for it, at every time step the
+    /// reference keeps.
+    ///
+    /// Mel band powers have to be within 0.005 dB of librosa, and MFCCs
+    /// within 0.005. Mel band powers more than 80 dB below the loudest
+    /// one are only compared in the MFCCs, where they are clipped.
+    fn assert_matches_librosa(name: &str) {
+        let reference = librosa_reference(name);
+        let filename = format!("./{}", reference["filename"].as_str().unwrap());
+        let waveform = Waveform::from_file(&filename, Default::default()).unwrap();
+        let mel = waveform
+            .mel_spectrogram(StftArgs::default(), MelArgs::default())
+            .unwrap();
+        let coefficients = waveform
+            .mfcc(StftArgs::default(), MelArgs::default(), MfccArgs::default())
+            .unwrap();
+        assert_eq!(
+            u64::from(mel.num_channels()),
+            reference["num_channels"].as_u64().unwrap()
+        );
+        assert_eq!(
+            mel.num_time_steps() as u64,
+            reference["num_time_steps"].as_u64().unwrap()
+        );
+
+        let as_f32 = |value: &serde_json::Value| value.as_f64().unwrap() as f32;
+        let loudest_db = mel
+            .to_db(None)
+            .as_slice()
+            .iter()
+            .copied()
+            .fold(f32::NEG_INFINITY, f32::max);
+        let time_steps = reference["time_steps"].as_array().unwrap();
+        for channel_idx in 0..mel.num_channels() {
+            let channel = channel_idx as usize;
+            for (idx, time_step) in time_steps.iter().enumerate() {
+                let time_step_idx = time_step.as_u64().unwrap() as usize;
+                let expected_mel = reference["mel"][channel][idx].as_array().unwrap();
+                for (mel_idx, expected) in expected_mel.iter().enumerate() {
+                    let expected_db = 10.0 * as_f32(expected).max(1e-10).log10();
+                    if expected_db < loudest_db - 80.0 {
+                        continue;
+                    }
+                    let actual = mel.get(channel_idx, mel_idx, time_step_idx).unwrap();
+                    let actual_db = 10.0 * actual.max(1e-10).log10();
+                    assert_close(actual_db, expected_db, 0.005);
+                }
+                let expected_mfcc = reference["mfcc"][channel][idx].as_array().unwrap();
+                for (mfcc_idx, expected) in expected_mfcc.iter().enumerate() {
+                    let actual = coefficients
+                        .get(channel_idx, mfcc_idx, time_step_idx)
+                        .unwrap();
+                    assert_close(actual, as_f32(expected), 0.005);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_log_sweep_matches_librosa() {
+        let reference = librosa_reference("log_sweep_pcm16");
+        assert_eq!(
+            format!("./{}", reference["filename"].as_str().unwrap()),
+            LS_PCM_16_FILENAME
+        );
+        assert_matches_librosa("log_sweep_pcm16");
+    }
+
+    #[test]
+    fn test_dtmf_matches_librosa() {
+        let reference = librosa_reference("mono_dtmf");
+        assert_eq!(
+            format!("./{}", reference["filename"].as_str().unwrap()),
+            MONO_DTMF_FILENAME
+        );
+        assert_matches_librosa("mono_dtmf");
+    }
+
+    #[test]
+    fn test_mel_spectrogram_of_a_sine() {
+        let samples = sine(16000, 1000.0, 0.5, 16000);
+        let waveform = Waveform::from_interleaved_samples(16000, 1, &samples);
+        let stft_args = StftArgs::default().set_fft_size(512).set_hop_length(160);
+        let mel_args = MelArgs::default().set_num_mels(40);
+        let mel = waveform.mel_spectrogram(stft_args, mel_args).unwrap();
+        let time_step_idx = mel.num_time_steps() / 2;
+        let loudest_band = (0..40)
+            .max_by(|&a, &b| {
+                let a = mel.get(0, a, time_step_idx).unwrap();
+                let b = mel.get(0, b, time_step_idx).unwrap();
+                a.total_cmp(&b)
+            })
+            .unwrap();
+        // Find the band whose triangle is highest at 1000 hz.
+        let filterbank = mel_filterbank(16000, 512, mel_args).unwrap();
+        let expected_band = (0..40)
+            .max_by(|&a, &b| filterbank[a * 257 + 32].total_cmp(&filterbank[b * 257 + 32]))
+            .unwrap();
+        assert_eq!(loudest_band, expected_band);
+    }
+
+    #[test]
+    fn test_to_db() {
+        let samples = sine(16000, 1000.0, 0.5, 16000);
+        let waveform = Waveform::from_interleaved_samples(16000, 1, &samples);
+        let mel = waveform
+            .mel_spectrogram(StftArgs::default(), MelArgs::default().set_num_mels(40))
+            .unwrap();
+        let db = mel.to_db(None);
+        for (power, value) in mel.as_slice().iter().zip(db.as_slice()) {
+            assert_close(*value, 10.0 * power.max(1e-10).log10(), 1e-3);
+        }
+        let max_db = db
+            .as_slice()
+            .iter()
+            .copied()
+            .fold(f32::NEG_INFINITY, f32::max);
+        let clipped = mel.to_db(Some(80.0));
+        let min_db = clipped
+            .as_slice()
+            .iter()
+            .copied()
+            .fold(f32::INFINITY, f32::min);
+        assert_close(min_db, max_db - 80.0, 1e-3);
+    }
+
+    #[test]
+    fn test_mfcc_is_orthonormal_dct() {
+        let waveform = log_sweep();
+        let mel_args = MelArgs::default().set_num_mels(40);
+        let mel = waveform
+            .mel_spectrogram(StftArgs::default(), mel_args)
+            .unwrap();
+        let log_mel = mel.to_db(Some(80.0));
+
+        // Keeping every coefficient, an orthonormal DCT keeps the energy
+        // of each time step, and the first coefficient is a scaled sum.
+        let args = MfccArgs::default().set_num_mfcc(40);
+        let coefficients = mfcc(&mel, args).unwrap();
+        assert_eq!(coefficients.num_features(), 40);
+        assert_eq!(coefficients.num_time_steps(), mel.num_time_steps());
+        for channel_idx in 0..2_u16 {
+            for time_step_idx in (0..mel.num_time_steps()).step_by(50) {
+                let column = |features: &Features| -> Vec<f32> {
+                    (0..40)
+                        .map(|idx| features.get(channel_idx, idx, time_step_idx).unwrap())
+                        .collect()
+                };
+                let mel_column = column(&log_mel);
+                let mfcc_column = column(&coefficients);
+                let mel_energy: f32 = mel_column.iter().map(|x| x * x).sum();
+                let mfcc_energy: f32 = mfcc_column.iter().map(|x| x * x).sum();
+                assert_close(mfcc_energy, mel_energy, mel_energy * 1e-4);
+                let sum: f32 = mel_column.iter().sum();
+                assert_close(
+                    mfcc_column[0],
+                    sum / 40.0_f32.sqrt(),
+                    sum.abs() * 1e-5 + 1e-3,
+                );
+            }
+        }
+
+        // The default keeps the first 20 coefficients.
+        let first_20 = waveform
+            .mfcc(StftArgs::default(), mel_args, MfccArgs::default())
+            .unwrap();
+        assert_eq!(first_20.num_features(), 20);
+        for channel_idx in 0..2_u16 {
+            for idx in 0..20 {
+                assert_eq!(
+                    first_20.get(channel_idx, idx, 100),
+                    coefficients.get(channel_idx, idx, 100)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_mfcc_lifter() {
+        let samples = sine(16000, 440.0, 0.5, 16000);
+        let waveform = Waveform::from_interleaved_samples(16000, 1, &samples);
+        let stft_args = StftArgs::default().set_fft_size(512).set_hop_length(160);
+        let mel_args = MelArgs::default().set_num_mels(40);
+        let mut mfcc_args = MfccArgs::default().set_num_mfcc(13);
+        let plain = waveform.mfcc(stft_args, mel_args, mfcc_args).unwrap();
+        let liftered = waveform
+            .mfcc(stft_args, mel_args, mfcc_args.set_lifter(22.0))
+            .unwrap();
+        for k in 0..13 {
+            let scale = 1.0 + 11.0 * (std::f32::consts::PI * (k + 1) as f32 / 22.0).sin();
+            let a = plain.get(0, k, 50).unwrap() * scale;
+            let b = liftered.get(0, k, 50).unwrap();
+            assert_close(b, a, a.abs() * 1e-4 + 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_mfcc_wrong_num_mfcc() {
+        let waveform = Waveform::from_frames_of_silence(16000, 1, 16000);
+        let mel_args = MelArgs::default().set_num_mels(40);
+        for num_mfcc in [0, 41] {
+            let mfcc_args = MfccArgs::default().set_num_mfcc(num_mfcc);
+            assert_eq!(
+                waveform.mfcc(StftArgs::default(), mel_args, mfcc_args),
+                Err(Error::WrongNumFeatures(num_mfcc, 40))
+            );
+        }
+    }
+
+    #[test]
+    fn test_deltas() {
+        let waveform = log_sweep();
+        let coefficients = waveform
+            .mfcc(StftArgs::default(), MelArgs::default(), MfccArgs::default())
+            .unwrap();
+        let num_time_steps = coefficients.num_time_steps();
+        let deltas = coefficients.deltas(9, 1).unwrap();
+        let delta_deltas = coefficients.deltas(9, 2).unwrap();
+        assert_eq!(deltas.num_features(), 20);
+        assert_eq!(deltas.num_time_steps(), num_time_steps);
+
+        // With a width of 9, the deltas are the least-squares slope over
+        // 4 time steps on each side, and the delta-deltas are twice the
+        // leading coefficient of the least-squares parabola.
+        let slope: Vec<f32> = (-4..=4).map(|n| n as f32 / 60.0).collect();
+        let curvature: Vec<f32> = (-4..=4)
+            .map(|n| 2.0 * ((n * n) as f32 - 20.0 / 3.0) / 308.0)
+            .collect();
+        for channel_idx in 0..2_u16 {
+            for feature_idx in [0, 5, 19] {
+                for time_step_idx in [0, 3, 4, 100, num_time_steps - 1] {
+                    let center = time_step_idx.clamp(4, num_time_steps - 5);
+                    let window: Vec<f32> = (center - 4..=center + 4)
+                        .map(|t| coefficients.get(channel_idx, feature_idx, t).unwrap())
+                        .collect();
+                    let expected: f32 = window.iter().zip(&slope).map(|(x, c)| x * c).sum();
+                    let actual = deltas.get(channel_idx, feature_idx, time_step_idx).unwrap();
+                    assert_close(actual, expected, expected.abs() * 1e-4 + 1e-4);
+                    let expected: f32 = window.iter().zip(&curvature).map(|(x, c)| x * c).sum();
+                    let actual = delta_deltas
+                        .get(channel_idx, feature_idx, time_step_idx)
+                        .unwrap();
+                    assert_close(actual, expected, expected.abs() * 1e-4 + 1e-4);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_deltas_wrong_args() {
+        let waveform = Waveform::from_frames_of_silence(16000, 1, 2048);
+        let stft_args = StftArgs::default().set_fft_size(512).set_hop_length(256);
+        let mel = waveform
+            .mel_spectrogram(stft_args, MelArgs::default().set_num_mels(40))
+            .unwrap();
+        assert_eq!(mel.num_time_steps(), 9);
+        assert_eq!(mel.deltas(11, 1), Err(Error::WrongDeltaWidth(11, 9)));
+        assert_eq!(mel.deltas(4, 1), Err(Error::WrongDeltaWidth(4, 9)));
+        assert_eq!(mel.deltas(1, 1), Err(Error::WrongDeltaWidth(1, 9)));
+        assert_eq!(mel.deltas(3, 0), Err(Error::WrongDeltaOrder(0, 3)));
+        assert_eq!(mel.deltas(3, 3), Err(Error::WrongDeltaOrder(3, 3)));
+        assert!(mel.deltas(9, 1).is_ok());
+    }
+
+    #[test]
+    fn test_batch() {
+        let filenames = &[LS_PCM_16_FILENAME, "does-not-exist"];
+        let stft_args = StftArgs::default();
+        let mel_args = MelArgs::default().set_num_mels(40);
+        let mel_batch = mel_spectrograms_from_files(
+            filenames,
+            Default::default(),
+            stft_args,
+            mel_args,
+            Default::default(),
+        );
+        let mfcc_batch = mfccs_from_files(
+            filenames,
+            Default::default(),
+            stft_args,
+            mel_args,
+            MfccArgs::default(),
+            Default::default(),
+        );
+        let waveform = log_sweep();
+        assert_eq!(mel_batch[0].name, LS_PCM_16_FILENAME);
+        assert_eq!(
+            mel_batch[0].result,
+            waveform.mel_spectrogram(stft_args, mel_args)
+        );
+        assert_eq!(
+            mfcc_batch[0].result,
+            waveform.mfcc(stft_args, mel_args, MfccArgs::default())
+        );
+        assert_eq!(
+            mel_batch[1].result,
+            Err(Error::FileNotFound("does-not-exist"))
+        );
+        assert_eq!(
+            mfcc_batch[1].result,
+            Err(Error::FileNotFound("does-not-exist"))
+        );
+    }
+}
@@ -0,0 +1,140 @@
+mod fixtures;
+
+mod test_mixer {
+    use crate::fixtures::new_small_waveform;
+    use babycat::source::{ClipPolicy, Mixer, MixerInputArgs, Pan, PanLaw};
+    use babycat::{Error, Signal, Source, WaveformSource};
+
+    fn mono(samples: &[f32]) -> Box<WaveformSource> {
+        Box::new(WaveformSource::from_interleaved_samples(44100, 1, samples))
+    }
+
+    #[test]
+    fn test_empty_mixer() {
+        let mixer = Mixer::new(44100, 2, ClipPolicy::None).unwrap();
+        assert_eq!(mixer.num_frames_estimate(), Some(0));
+        assert_eq!(mixer.collect_interleaved_samples(), Vec::<f32>::new());
+    }
+
+    #[test]
+    fn test_many_inputs_with_offsets() {
+        let mut mixer = Mixer::new(44100, 1, ClipPolicy::None).unwrap();
+        for offset_frames in 0..12 {
+            let args = MixerInputArgs::default().set_offset_frames(offset_frames);
+            mixer.add_input(mono(&[1.0, 1.0]), args).unwrap();
+        }
+        assert_eq!(mixer.num_inputs(), 12);
+        assert_eq!(mixer.num_frames_estimate(), Some(13));
+        assert_eq!(mixer.size_hint(), (13, Some(13)));
+        let out = mixer.collect_interleaved_samples();
+        let mut expected = vec![2.0_f32; 13];
+        expected[0] = 1.0;
+        expected[12] = 1.0;
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_num_frames_estimate_with_offset() {
+        let waveform = new_small_waveform();
+        let num_frames = waveform.num_frames();
+        let mut mixer = Mixer::new(
+            waveform.frame_rate_hz(),
+            waveform.num_channels(),
+            ClipPolicy::None,
+        )
+        .unwrap();
+        mixer
+            .add_input(Box::new(waveform.clone().into_source()), Default::default())
+            .unwrap();
+        mixer
+            .add_input(
+                Box::new(waveform.into_source()),
+                MixerInputArgs::default().set_offset_frames(4),
+            )
+            .unwrap();
+        assert_eq!(mixer.num_frames_estimate(), Some(num_frames + 4));
+        assert_eq!(mixer.count(), (num_frames + 4) * 3);
+    }
+
+    #[test]
+    fn test_gain_and_hard_clip() {
+        let mut mixer = Mixer::new(44100, 1, ClipPolicy::HardClip).unwrap();
+        mixer
+            .add_input(mono(&[0.8, -0.8]), Default::default())
+            .unwrap();
+        mixer
+            .add_input(mono(&[0.8, -0.8]), Default::default())
+            .unwrap();
+        mixer
+            .add_input(
+                mono(&[0.5, 0.5]),
+                MixerInputArgs::default().set_gain_dbfs(-200.0),
+            )
+            .unwrap();
+        assert_eq!(mixer.collect_interleaved_samples(), &[1.0, -1.0]);
+    }
+
+    #[test]
+    fn test_auto_headroom() {
+        let mut mixer = Mixer::new(44100, 1, ClipPolicy::AutoHeadroom).unwrap();
+        for _ in 0..4 {
+            mixer
+                .add_input(mono(&[1.0, -1.0]), Default::default())
+                .unwrap();
+        }
+        assert_eq!(mixer.collect_interleaved_samples(), &[1.0, -1.0]);
+    }
+
+    #[test]
+    fn test_pan() {
+        let stereo = WaveformSource::from_interleaved_samples(44100, 2, &[1.0, 1.0]);
+        let pan = Pan {
+            position: -1.0,
+            law: PanLaw::ConstantPower,
+        };
+        let mut mixer = Mixer::new(44100, 2, ClipPolicy::None).unwrap();
+        mixer
+            .add_input(Box::new(stereo), MixerInputArgs::default().set_pan(pan))
+            .unwrap();
+        let out = mixer.collect_interleaved_samples();
+        assert!((out[0] - 1.0).abs() < 1e-6);
+        assert!(out[1].abs() < 1e-6);
+
+        let center = Pan {
+            position: 0.0,
+            law: PanLaw::Linear,
+        };
+        assert_eq!(center.gains(), (0.5, 0.5));
+    }
+
+    #[test]
+    fn test_zero_channels() {
+        assert_eq!(
+            Mixer::new(44100, 0, ClipPolicy::None).unwrap_err(),
+            Error::WrongNumChannels(1, 0)
+        );
+    }
+
+    #[test]
+    fn test_wrong_inputs() {
+        let mut mixer = Mixer::new(44100, 1, ClipPolicy::None).unwrap();
+        let stereo = WaveformSource::from_interleaved_samples(44100, 2, &[1.0, 1.0]);
+        assert_eq!(
+            mixer.add_input(Box::new(stereo), Default::default()),
+            Err(Error::CannotMixSourcesWithDifferentNumChannels(1, 2))
+        );
+        let other_rate = WaveformSource::from_interleaved_samples(48000, 1, &[1.0]);
+        assert_eq!(
+            mixer.add_input(Box::new(other_rate), Default::default()),
+            Err(Error::CannotMixSourcesWithDifferentFrameRates(44100, 48000))
+        );
+        let pan = Pan {
+            position: 0.5,
+            law: PanLaw::Balance,
+        };
+        assert_eq!(
+            mixer.add_input(mono(&[1.0]), MixerInputArgs::default().set_pan(pan)),
+            Err(Error::CannotPanWithoutTwoChannels(1))
+        );
+    }
+}
@@ -0,0 +1,193 @@
+mod fixtures;
+mod signals;
+
+mod test_normalize {
+    use crate::fixtures::*;
+    use crate::signals::{assert_close, duplicate_channels, sine};
+    use babycat::constants::{NORMALIZE_MODE_LOUDNESS, NORMALIZE_MODE_PEAK};
+    use babycat::loudness::{integrated_loudness, k_weighting, sample_peak_dbfs, true_peak_dbtp};
+    use babycat::{Error, Signal, Waveform, WaveformArgs};
+
+    #[test]
+    fn test_k_weighting_matches_standard_at_48000_hz() {
+        let stages = k_weighting(48000).stages().to_vec();
+        let shelf = stages[0];
+        assert!((shelf.b0 - 1.53512485958697).abs() < 1e-9);
+        assert!((shelf.b1 - -2.69169618940638).abs() < 1e-9);
+        assert!((shelf.b2 - 1.19839281085285).abs() < 1e-9);
+        assert!((shelf.a1 - -1.69065929318241).abs() < 1e-9);
+        assert!((shelf.a2 - 0.73248077421585).abs() < 1e-9);
+        let highpass = stages[1];
+        assert!((highpass.a1 - -1.99004745483398).abs() < 1e-9);
+        assert!((highpass.a2 - 0.99007225036621).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_integrated_loudness_of_sines() {
+        for &frame_rate_hz in &[44100, 48000] {
+            // Full scale in one channel.
+            let samples = sine(frame_rate_hz, 997.0, 1.0, 10 * frame_rate_hz as usize);
+            let waveform = Waveform::from_interleaved_samples(frame_rate_hz, 1, &samples);
+            assert_close(
+                integrated_loudness(&waveform).unwrap().unwrap(),
+                -3.01,
+                0.05,
+            );
+            // Full scale in both channels.
+            let samples = duplicate_channels(
+                &sine(frame_rate_hz, 997.0, 1.0, 10 * frame_rate_hz as usize),
+                2,
+            );
+            let waveform = Waveform::from_interleaved_samples(frame_rate_hz, 2, &samples);
+            assert_close(integrated_loudness(&waveform).unwrap().unwrap(), 0.0, 0.05);
+            // -20 dBFS in both channels.
+            let samples = duplicate_channels(
+                &sine(frame_rate_hz, 997.0, 0.1, 10 * frame_rate_hz as usize),
+                2,
+            );
+            let waveform = Waveform::from_interleaved_samples(frame_rate_hz, 2, &samples);
+            assert_close(
+                integrated_loudness(&waveform).unwrap().unwrap(),
+                -20.0,
+                0.05,
+            );
+        }
+    }
+
+    #[test]
+    fn test_integrated_loudness_gates_silence_and_quiet_passages() {
+        let loud = duplicate_channels(&sine(48000, 997.0, 0.1, 480000), 2);
+        let quiet = duplicate_channels(&sine(48000, 997.0, 0.01, 480000), 2);
+        let silence = vec![0.0; 960000];
+        let with_silence: Vec<f32> = loud.iter().chain(silence.iter()).copied().collect();
+        let with_quiet: Vec<f32> = loud.iter().chain(quiet.iter()).copied().collect();
+        let with_silence = Waveform::from_interleaved_samples(48000, 2, &with_silence);
+        let with_quiet = Waveform::from_interleaved_samples(48000, 2, &with_quiet);
+        // Only the few blocks that straddle the boundary pull the loudness down.
+        assert_close(
+            integrated_loudness(&with_silence).unwrap().unwrap(),
+            -20.0,
+            0.1,
+        );
+        // The quiet half is 20 LU down, so the relative gate removes it.
+        assert_close(
+            integrated_loudness(&with_quiet).unwrap().unwrap(),
+            -20.0,
+            0.1,
+        );
+    }
+
+    #[test]
+    fn test_integrated_loudness_of_silence_and_short_audio() {
+        let silence = Waveform::from_frames_of_silence(48000, 2, 48000);
+        assert_eq!(integrated_loudness(&silence), Ok(None));
+        let short = Waveform::from_interleaved_samples(48000, 1, &sine(48000, 997.0, 1.0, 1000));
+        assert_eq!(integrated_loudness(&short), Ok(None));
+    }
+
+    #[test]
+    fn test_true_peak_between_samples() {
+        // A quarter-rate sine sampled 45 degrees off its peaks.
+        let samples: Vec<f32> = (0..48000)
+            .map(|i| {
+                let phase = std::f32::consts::FRAC_PI_2 * (i % 4) as f32;
+                (phase + std::f32::consts::FRAC_PI_4).sin()
+            })
+            .collect();
+        let waveform = Waveform::from_interleaved_samples(48000, 1, &samples);
+        assert_close(sample_peak_dbfs(&waveform), -3.01, 0.01);
+        assert_close(true_peak_dbtp(&waveform).unwrap(), 0.0, 0.2);
+    }
+
+    #[test]
+    fn test_normalize_peak_log_sweep() {
+        let waveform = Waveform::from_file(LS_FILENAME, Default::default()).unwrap();
+        let original_peak_dbfs = sample_peak_dbfs(&waveform);
+        let normalized = waveform.normalize_peak(-3.0);
+        assert_close(sample_peak_dbfs(&normalized), -3.0, 1e-4);
+        assert_eq!(sample_peak_dbfs(&waveform), original_peak_dbfs);
+        assert_eq!(normalized.num_frames(), LS_NUM_FRAMES);
+    }
+
+    #[test]
+    fn test_normalize_loudness_log_sweep() {
+        let waveform = Waveform::from_file(LS_FILENAME, Default::default())
+            .unwrap()
+            .normalize_peak(-40.0);
+        let normalized = waveform.normalize_loudness(-23.0).unwrap();
+        assert!(sample_peak_dbfs(&normalized) > sample_peak_dbfs(&waveform));
+        assert_close(
+            integrated_loudness(&normalized).unwrap().unwrap(),
+            -23.0,
+            0.05,
+        );
+        assert!(true_peak_dbtp(&normalized).unwrap() <= 0.0);
+    }
+
+    #[test]
+    fn test_normalize_loudness_respects_ceiling() {
+        let samples = duplicate_channels(&sine(48000, 997.0, 0.1, 480000), 2);
+        let waveform = Waveform::from_interleaved_samples(48000, 2, &samples);
+        // Reaching 0 LUFS needs a 0 dBFS peak, which is above the ceiling.
+        let normalized = waveform.normalize_loudness_with_ceiling(0.0, -6.0).unwrap();
+        let gain_db = sample_peak_dbfs(&normalized) - sample_peak_dbfs(&waveform);
+        assert_close(gain_db, 14.0, 0.1);
+        assert_close(true_peak_dbtp(&normalized).unwrap(), -6.0, 0.01);
+        assert_close(
+            integrated_loudness(&normalized).unwrap().unwrap(),
+            -6.0,
+            0.1,
+        );
+    }
+
+    #[test]
+    fn test_normalize_silence_does_nothing() {
+        let waveform = Waveform::from_frames_of_silence(48000, 2, 48000);
+        assert_eq!(waveform.normalize_peak(0.0), waveform);
+        assert_eq!(waveform.normalize_loudness(-23.0), Ok(waveform.clone()));
+    }
+
+    #[test]
+    fn test_waveform_args_normalize_peak() {
+        let waveform_args = WaveformArgs {
+            normalize_mode: NORMALIZE_MODE_PEAK,
+            normalize_target: -6.0,
+            ..Default::default()
+        };
+        let waveform = Waveform::from_file(LS_FILENAME, waveform_args).unwrap();
+        assert_close(sample_peak_dbfs(&waveform), -6.0, 1e-4);
+    }
+
+    #[test]
+    fn test_waveform_args_normalize_loudness_after_resampling() {
+        let waveform_args = WaveformArgs {
+            frame_rate_hz: 48000,
+            convert_to_mono: true,
+            normalize_mode: NORMALIZE_MODE_LOUDNESS,
+            normalize_target: -30.0,
+            normalize_true_peak_ceiling_dbtp: -1.0,
+            ..Default::default()
+        };
+        let waveform = Waveform::from_file(LS_FILENAME, waveform_args).unwrap();
+        assert_eq!(waveform.frame_rate_hz(), 48000);
+        assert_eq!(waveform.num_channels(), 1);
+        assert_close(
+            integrated_loudness(&waveform).unwrap().unwrap(),
+            -30.0,
+            0.05,
+        );
+    }
+
+    #[test]
+    fn test_waveform_args_unknown_normalize_mode() {
+        let waveform_args = WaveformArgs {
+            normalize_mode: 1000,
+            ..Default::default()
+        };
+        let result = Waveform::from_file(LS_FILENAME, waveform_args);
+        assert_eq!(
+            result.unwrap_err(),
+            Error::FeatureNotCompiled("unknown-normalize-mode")
+        );
+    }
+}
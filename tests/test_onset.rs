@@ -0,0 +1,207 @@
+mod fixtures;
+
+mod test_onset {
+    use crate::fixtures::*;
+    use babycat::constants::{
+        ONSET_FUNCTION_COMPLEX_DOMAIN, ONSET_FUNCTION_HIGH_FREQUENCY_CONTENT,
+        ONSET_FUNCTION_SPECTRAL_FLUX,
+    };
+    use babycat::onset::{onset_envelope, onsets, pick_peaks, split_at_onsets, OnsetArgs};
+    use babycat::{Error, Signal, Waveform};
+
+    const NOTE_STARTS: [usize; 4] = [22050, 44100, 66150, 88200];
+
+    /// Returns a mono melody of four decaying notes, one every half second.
+    fn melody(frame_rate_hz: u32) -> Waveform {
+        let freqs_hz = [440.0, 554.37, 659.26, 880.0];
+        let mut samples = vec![0.0_f32; 44100 * 5 / 2];
+        for (start, freq_hz) in NOTE_STARTS.iter().zip(freqs_hz) {
+            for (i, sample) in samples[*start..].iter_mut().take(22050).enumerate() {
+                let t = i as f32 / frame_rate_hz as f32;
+                *sample = 0.5 * (-t * 6.0).exp() * (2.0 * std::f32::consts::PI * freq_hz * t).sin();
+            }
+        }
+        Waveform::from_interleaved_samples(frame_rate_hz, 1, &samples)
+    }
+
+    #[test]
+    fn test_every_function_finds_the_notes() {
+        let waveform = melody(44100);
+        for onset_function in [
+            ONSET_FUNCTION_SPECTRAL_FLUX,
+            ONSET_FUNCTION_HIGH_FREQUENCY_CONTENT,
+            ONSET_FUNCTION_COMPLEX_DOMAIN,
+        ] {
+            let args = OnsetArgs::default().set_onset_function(onset_function);
+            let frame_idxs = waveform.onsets(args).unwrap();
+            assert_eq!(frame_idxs.len(), 4, "{}: {:?}", onset_function, frame_idxs);
+            for (frame_idx, start) in frame_idxs.iter().zip(NOTE_STARTS) {
+                assert!(
+                    (*frame_idx as i64 - start as i64).abs() <= 1024,
+                    "{}: {:?}",
+                    onset_function,
+                    frame_idxs
+                );
+                assert_eq!(frame_idx % 512, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_onsets_of_stereo_audio() {
+        let mono = melody(44100);
+        let interleaved: Vec<f32> = mono
+            .to_interleaved_samples()
+            .iter()
+            .flat_map(|s| [*s, -0.5 * s])
+            .collect();
+        let stereo = Waveform::from_interleaved_samples(44100, 2, &interleaved);
+        assert_eq!(
+            onsets(&stereo, OnsetArgs::default()).unwrap(),
+            onsets(&mono, OnsetArgs::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_silence_has_no_onsets() {
+        let waveform = Waveform::from_frames_of_silence(44100, 2, 44100);
+        let envelope = onset_envelope(&waveform, OnsetArgs::default()).unwrap();
+        assert_eq!(envelope.len(), 1 + 44100 / 512);
+        assert!(waveform.onsets(OnsetArgs::default()).unwrap().is_empty());
+        assert!(waveform
+            .split_at_onsets(OnsetArgs::default())
+            .unwrap()
+            .is_empty());
+
+        let waveform = Waveform::from_frames_of_silence(44100, 1, 0);
+        assert!(waveform.onsets(OnsetArgs::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_wrong_args() {
+        let waveform = melody(22050);
+        assert_eq!(
+            waveform.onsets(OnsetArgs::default().set_onset_function(99)),
+            Err(Error::FeatureNotCompiled("unknown-onset-function"))
+        );
+        assert_eq!(
+            waveform.onsets(OnsetArgs::default().set_hop_length(0)),
+            Err(Error::WrongHopLength(0))
+        );
+    }
+
+    #[test]
+    fn test_pick_peaks() {
+        let mut envelope = vec![0.0_f32; 100];
+        envelope[10] = 10.0;
+        envelope[14] = 8.0;
+        envelope[50] = 5.0;
+        envelope[80] = 0.9;
+        // 86 time steps per second, so the 30 ms windows are 3 time steps.
+        let args = OnsetArgs::default();
+        assert_eq!(
+            pick_peaks(&envelope, 44100, args).unwrap(),
+            vec![10, 14, 50]
+        );
+        // A longer wait drops the onset right after the first one.
+        let args = OnsetArgs::default().set_wait_milliseconds(100);
+        assert_eq!(pick_peaks(&envelope, 44100, args).unwrap(), vec![10, 50]);
+        // Every peak is loud enough without the floor.
+        let args = OnsetArgs::default().set_min_strength_db(0.0);
+        assert_eq!(
+            pick_peaks(&envelope, 44100, args).unwrap(),
+            vec![10, 14, 50, 80]
+        );
+        assert!(pick_peaks(&[], 44100, args).unwrap().is_empty());
+        assert!(pick_peaks(&[3.0; 10], 44100, args).unwrap().is_empty());
+        // A zero hop length would divide by zero when converting the windows.
+        let args = OnsetArgs::default().set_hop_length(0);
+        assert_eq!(
+            pick_peaks(&envelope, 44100, args),
+            Err(Error::WrongHopLength(0))
+        );
+    }
+
+    #[test]
+    fn test_backtrack() {
+        let waveform = melody(44100);
+        let forward = waveform.onsets(OnsetArgs::default()).unwrap();
+        let backward = waveform
+            .onsets(OnsetArgs::default().set_backtrack(true))
+            .unwrap();
+        assert_eq!(forward.len(), backward.len());
+        for (f, b) in forward.iter().zip(&backward) {
+            assert!(b <= f && f - b <= 2048, "{:?} {:?}", forward, backward);
+        }
+    }
+
+    #[test]
+    fn test_split_at_onsets() {
+        let waveform = melody(44100);
+        let args = OnsetArgs::default();
+        let frame_idxs = waveform.onsets(args).unwrap();
+        let pieces = split_at_onsets(&waveform, args).unwrap();
+        assert_eq!(pieces.len(), frame_idxs.len());
+        let samples = waveform.to_interleaved_samples();
+        for (piece, start) in pieces.iter().zip(&frame_idxs) {
+            assert_eq!(piece.frame_rate_hz(), 44100);
+            assert_eq!(piece.num_channels(), 1);
+            assert_eq!(
+                piece.to_interleaved_samples(),
+                &samples[*start..*start + piece.num_frames()]
+            );
+        }
+        let total: usize = pieces.iter().map(Waveform::num_frames).sum();
+        assert_eq!(total, waveform.num_frames() - frame_idxs[0]);
+    }
+
+    #[test]
+    fn test_dtmf_tones() {
+        let waveform = Waveform::from_file(MONO_DTMF_FILENAME, Default::default()).unwrap();
+        assert_eq!(waveform.num_frames(), MONO_DTMF_NUM_FRAMES);
+        for onset_function in [
+            ONSET_FUNCTION_SPECTRAL_FLUX,
+            ONSET_FUNCTION_HIGH_FREQUENCY_CONTENT,
+        ] {
+            let args = OnsetArgs::default().set_onset_function(onset_function);
+            let frame_idxs = waveform.onsets(args).unwrap();
+            // Every tone after the first one starts about 1.46 seconds after the one before.
+            assert_eq!(frame_idxs.len(), 6, "{:?}", frame_idxs);
+            for pair in frame_idxs.windows(2) {
+                let gap_frames = pair[1] - pair[0];
+                assert!((63_000..=66_000).contains(&gap_frames), "{:?}", frame_idxs);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tone_with_gaps() {
+        // The tone comes back after a gap at 10 and 20 seconds. The
+        // places where it stops are not onsets.
+        let waveform = Waveform::from_file(TMS_FILENAME, Default::default()).unwrap();
+        let frame_idxs = waveform.onsets(OnsetArgs::default()).unwrap();
+        assert_eq!(frame_idxs.len(), 2, "{:?}", frame_idxs);
+        assert!((frame_idxs[0] as i64 - 441_000).abs() <= 1024);
+        assert!((frame_idxs[1] as i64 - 882_000).abs() <= 1024);
+    }
+
+    #[test]
+    fn test_steady_sounds_have_no_onsets() {
+        for filename in [LCT_FILENAME, LS_FILENAME] {
+            let waveform = Waveform::from_file(filename, Default::default()).unwrap();
+            for onset_function in [
+                ONSET_FUNCTION_SPECTRAL_FLUX,
+                ONSET_FUNCTION_HIGH_FREQUENCY_CONTENT,
+            ] {
+                let args = OnsetArgs::default().set_onset_function(onset_function);
+                let frame_idxs = waveform.onsets(args).unwrap();
+                assert!(
+                    frame_idxs.iter().all(|f| *f <= 1024),
+                    "{}: {:?}",
+                    filename,
+                    frame_idxs
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+mod fixtures;
+
+mod test_peaks {
+    use crate::fixtures::*;
+    use babycat::decoder;
+    use babycat::peaks::Peaks;
+    use babycat::{Error, Signal, Waveform, WaveformArgs};
+
+    fn brute_force(
+        waveform: &Waveform,
+        frames_per_bucket: usize,
+    ) -> (Vec<f32>, Vec<f32>, Vec<f32>) {
+        let num_channels = waveform.num_channels() as usize;
+        let samples = waveform.to_interleaved_samples();
+        let (mut min, mut max, mut rms) = (Vec::new(), Vec::new(), Vec::new());
+        for channel_idx in 0..num_channels {
+            let channel: Vec<f32> = samples
+                .iter()
+                .skip(channel_idx)
+                .step_by(num_channels)
+                .copied()
+                .collect();
+            for bucket in channel.chunks(frames_per_bucket) {
+                min.push(bucket.iter().copied().fold(f32::INFINITY, f32::min));
+                max.push(bucket.iter().copied().fold(f32::NEG_INFINITY, f32::max));
+                let sum_of_squares: f64 = bucket.iter().map(|s| f64::from(*s).powi(2)).sum();
+                rms.push((sum_of_squares / bucket.len() as f64).sqrt() as f32);
+            }
+        }
+        (min, max, rms)
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let waveform = Waveform::from_file(LS_FILENAME, WaveformArgs::default()).unwrap();
+        for frames_per_bucket in [1, 100, 256, 4410, 1_000_000] {
+            let peaks = waveform.peaks(frames_per_bucket).unwrap();
+            let (min, max, rms) = brute_force(&waveform, frames_per_bucket);
+            assert_eq!(peaks.frame_rate_hz(), LS_FRAME_RATE_HZ);
+            assert_eq!(peaks.num_channels(), LS_NUM_CHANNELS);
+            assert_eq!(peaks.num_frames(), LS_NUM_FRAMES);
+            assert_eq!(peaks.frames_per_bucket(), frames_per_bucket);
+            assert_eq!(
+                peaks.num_buckets(),
+                LS_NUM_FRAMES.div_ceil(frames_per_bucket)
+            );
+            assert_eq!(peaks.min(), min.as_slice());
+            assert_eq!(peaks.max(), max.as_slice());
+            assert_eq!(peaks.rms(), rms.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_from_source_matches_from_waveform() {
+        for filename in [LS_FILENAME, MONO_DTMF_FILENAME, LCT_FILENAME] {
+            let waveform = Waveform::from_file(filename, WaveformArgs::default()).unwrap();
+            let source = decoder::from_file(filename).unwrap();
+            assert_eq!(
+                Peaks::from_source(source, 512).unwrap(),
+                waveform.peaks(512).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_num_buckets() {
+        let waveform = Waveform::from_file(MONO_DTMF_FILENAME, WaveformArgs::default()).unwrap();
+        for num_buckets in [1, 800, 1000, 1234] {
+            let peaks = waveform.peaks_with_num_buckets(num_buckets).unwrap();
+            assert!(peaks.num_buckets() <= num_buckets);
+            assert!(peaks.num_buckets() * 2 > num_buckets);
+            assert_eq!(
+                peaks.frames_per_bucket(),
+                MONO_DTMF_NUM_FRAMES.div_ceil(num_buckets)
+            );
+        }
+        // There are more buckets than frames.
+        let short = Waveform::from_interleaved_samples(1000, 1, &[0.1, 0.2, 0.3]);
+        let peaks = short.peaks_with_num_buckets(10).unwrap();
+        assert_eq!(peaks.frames_per_bucket(), 1);
+        assert_eq!(peaks.max(), &[0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn test_left_channel_tone() {
+        let waveform = Waveform::from_file(LCT_FILENAME, WaveformArgs::default()).unwrap();
+        let peaks = waveform.peaks_with_num_buckets(100).unwrap();
+        let num_buckets = peaks.num_buckets();
+        let (left, right) = peaks.max().split_at(num_buckets);
+        assert!(left.iter().all(|max| *max > 0.5));
+        assert!(right.iter().all(|max| *max < 0.001));
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let waveform = Waveform::from_file(LS_FILENAME, WaveformArgs::default()).unwrap();
+        let peaks = waveform.peaks(1000).unwrap();
+        let bytes = peaks.to_bytes();
+        assert_eq!(
+            bytes.len(),
+            28 + 6 * peaks.num_buckets() * LS_NUM_CHANNELS as usize
+        );
+        assert_eq!(&bytes[0..4], b"BCPK");
+        let cached = Peaks::from_bytes(&bytes).unwrap();
+        assert_eq!(cached.frame_rate_hz(), peaks.frame_rate_hz());
+        assert_eq!(cached.num_channels(), peaks.num_channels());
+        assert_eq!(cached.num_frames(), peaks.num_frames());
+        assert_eq!(cached.frames_per_bucket(), peaks.frames_per_bucket());
+        for (cached, original) in [
+            (cached.min(), peaks.min()),
+            (cached.max(), peaks.max()),
+            (cached.rms(), peaks.rms()),
+        ] {
+            assert_eq!(cached.len(), original.len());
+            for (cached, original) in cached.iter().zip(original) {
+                assert!((cached - original).abs() <= 0.5 / 32767.0 + 1e-7);
+            }
+        }
+        assert_eq!(Peaks::from_bytes(&cached.to_bytes()).unwrap(), cached);
+    }
+
+    #[test]
+    fn test_bytes_clamp_to_full_scale() {
+        let waveform = Waveform::from_interleaved_samples(1000, 1, &[2.0, -3.0]);
+        let cached = Peaks::from_bytes(&waveform.peaks(1).unwrap().to_bytes()).unwrap();
+        assert_eq!(cached.max(), &[1.0, -1.0]);
+        assert_eq!(cached.rms(), &[1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_malformed_bytes() {
+        let waveform = Waveform::from_interleaved_samples(1000, 2, &[0.5; 20]);
+        let bytes = waveform.peaks(3).unwrap().to_bytes();
+        let is_parse_error =
+            |bytes: &[u8]| matches!(Peaks::from_bytes(bytes), Err(Error::CannotParsePeaks(_)));
+        assert!(is_parse_error(&[]));
+        assert!(is_parse_error(&bytes[..27]));
+        assert!(is_parse_error(&bytes[..bytes.len() - 2]));
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0] = b'X';
+        assert!(is_parse_error(&wrong_magic));
+        let mut wrong_version = bytes.clone();
+        wrong_version[4] = 2;
+        assert!(is_parse_error(&wrong_version));
+        let mut zero_frames_per_bucket = bytes;
+        zero_frames_per_bucket[20..28].copy_from_slice(&[0; 8]);
+        assert!(is_parse_error(&zero_frames_per_bucket));
+    }
+
+    #[test]
+    fn test_hostile_header() {
+        let waveform = Waveform::from_interleaved_samples(1000, 2, &[0.5; 20]);
+        let bytes = waveform.peaks(1).unwrap().to_bytes();
+        let is_parse_error =
+            |bytes: &[u8]| matches!(Peaks::from_bytes(bytes), Err(Error::CannotParsePeaks(_)));
+        // The number of values overflows.
+        let mut too_many_frames = bytes.clone();
+        too_many_frames[6..8].copy_from_slice(&u16::MAX.to_le_bytes());
+        too_many_frames[12..20].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(is_parse_error(&too_many_frames));
+        // The number of values fits, but the number of bytes overflows.
+        let mut too_many_bytes = bytes.clone();
+        too_many_bytes[6..8].copy_from_slice(&1_u16.to_le_bytes());
+        too_many_bytes[12..20].copy_from_slice(&(u64::MAX / 4).to_le_bytes());
+        assert!(is_parse_error(&too_many_bytes));
+        // Without overflow checks, the number of bytes would wrap around
+        // to exactly the length of the input.
+        let mut wrapping = bytes;
+        let num_frames = (1_u64 << 62) + 10;
+        wrapping[12..20].copy_from_slice(&num_frames.to_le_bytes());
+        assert!(is_parse_error(&wrapping));
+    }
+
+    #[test]
+    fn test_wrong_frames_per_bucket() {
+        let waveform = Waveform::from_interleaved_samples(1000, 1, &[0.5; 10]);
+        assert_eq!(waveform.peaks(0), Err(Error::WrongFramesPerBucket(0)));
+    }
+
+    #[test]
+    fn test_wrong_num_buckets() {
+        let waveform = Waveform::from_interleaved_samples(1000, 1, &[0.5; 10]);
+        assert_eq!(
+            waveform.peaks_with_num_buckets(0),
+            Err(Error::WrongNumBuckets(0))
+        );
+    }
+
+    #[test]
+    fn test_empty_waveform() {
+        let waveform = Waveform::from_interleaved_samples(1000, 2, &[]);
+        let peaks = waveform.peaks(10).unwrap();
+        assert_eq!(peaks.num_buckets(), 0);
+        assert!(peaks.min().is_empty());
+        assert_eq!(Peaks::from_bytes(&peaks.to_bytes()).unwrap(), peaks);
+        assert_eq!(
+            waveform.peaks_with_num_buckets(10).unwrap().num_buckets(),
+            0
+        );
+    }
+}
@@ -0,0 +1,220 @@
+mod fixtures;
+mod signals;
+
+mod test_pitch {
+    use crate::signals::sine;
+    use babycat::constants::{PITCH_METHOD_PYIN, PITCH_METHOD_YIN};
+    use babycat::pitch::{pitch, Pitch, PitchArgs};
+    use babycat::{Error, Waveform};
+
+    /// Returns an exponential sine sweep from `start_hz` to `end_hz` that
+    /// lasts `duration_seconds`, with `silence_seconds` of silence before
+    /// and after it. Also returns the frequency of the sweep at any time.
+    fn sweep(
+        frame_rate_hz: u32,
+        start_hz: f64,
+        end_hz: f64,
+        duration_seconds: f64,
+        silence_seconds: f64,
+    ) -> (Waveform, impl Fn(f64) -> Option<f64>) {
+        let rate = (end_hz / start_hz).ln() / duration_seconds;
+        let num_frames =
+            (f64::from(frame_rate_hz) * (duration_seconds + 2.0 * silence_seconds)) as usize;
+        let samples: Vec<f32> = (0..num_frames)
+            .map(|i| {
+                let t = i as f64 / f64::from(frame_rate_hz) - silence_seconds;
+                if t < 0.0 || t >= duration_seconds {
+                    return 0.0;
+                }
+                let phase = 2.0 * std::f64::consts::PI * start_hz * ((rate * t).exp() - 1.0) / rate;
+                (0.5 * phase.sin()) as f32
+            })
+            .collect();
+        let frequency_at = move |time_seconds: f64| {
+            let t = time_seconds - silence_seconds;
+            if (0.0..duration_seconds).contains(&t) {
+                Some(start_hz * (rate * t).exp())
+            } else {
+                None
+            }
+        };
+        (
+            Waveform::from_interleaved_samples(frame_rate_hz, 1, &samples),
+            frequency_at,
+        )
+    }
+
+    fn cents(a: f64, b: f64) -> f64 {
+        1200.0 * (a / b).log2().abs()
+    }
+
+    #[test]
+    fn test_sweeps_are_tracked() {
+        for frame_rate_hz in [22050, 44100] {
+            for (start_hz, end_hz) in [(110.0, 880.0), (1000.0, 100.0)] {
+                let (waveform, frequency_at) = sweep(frame_rate_hz, start_hz, end_hz, 2.0, 0.5);
+                for method in [PITCH_METHOD_PYIN, PITCH_METHOD_YIN] {
+                    let contour =
+                        pitch(&waveform, PitchArgs::default().set_method(method)).unwrap();
+                    for estimate in &contour {
+                        // Frames near the start and end of the sweep hear both
+                        // the sweep and the silence.
+                        let time_seconds = estimate.frame_idx as f64 / f64::from(frame_rate_hz);
+                        let hears_edge = frequency_at(time_seconds - 0.1).is_some()
+                            != frequency_at(time_seconds + 0.1).is_some();
+                        match frequency_at(time_seconds) {
+                            _ if hears_edge => {}
+                            Some(expected_hz) => {
+                                assert!(estimate.is_voiced(), "{:?}", estimate);
+                                assert!(estimate.voiced_prob > 0.9, "{:?}", estimate);
+                                assert!(
+                                    cents(f64::from(estimate.f0_hz), expected_hz) < 20.0,
+                                    "{} hz with method {} expected {} hz: {:?}",
+                                    frame_rate_hz,
+                                    method,
+                                    expected_hz,
+                                    estimate
+                                );
+                            }
+                            None => {
+                                assert!(!estimate.is_voiced(), "{:?}", estimate);
+                                assert_eq!(estimate.voiced_prob, 0.0);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_steady_tones() {
+        for freq_hz in [82.41, 220.0, 440.0, 1000.0, 1760.0] {
+            let samples = sine(44100, freq_hz, 0.5, 44100);
+            let waveform = Waveform::from_interleaved_samples(44100, 1, &samples);
+            for method in [PITCH_METHOD_PYIN, PITCH_METHOD_YIN] {
+                let contour = waveform
+                    .pitch(PitchArgs::default().set_method(method))
+                    .unwrap();
+                // Leave out the frames that hear the padding.
+                for estimate in &contour[4..contour.len() - 4] {
+                    assert!(
+                        cents(f64::from(estimate.f0_hz), f64::from(freq_hz)) < 5.0,
+                        "{} hz: {:?}",
+                        freq_hz,
+                        estimate
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_contour_shape() {
+        let samples = sine(22050, 300.0, 0.5, 22050);
+        let waveform = Waveform::from_interleaved_samples(22050, 1, &samples);
+        for hop_length in [256, 512, 1000] {
+            let args = PitchArgs::default().set_hop_length(hop_length);
+            let contour = waveform.pitch(args).unwrap();
+            assert_eq!(contour.len(), 1 + 22050 / hop_length);
+            for (t, estimate) in contour.iter().enumerate() {
+                assert_eq!(estimate.frame_idx, t * hop_length);
+                assert_eq!(estimate.milliseconds, t * hop_length * 1000 / 22050);
+                assert!((0.0..=1.0).contains(&estimate.voiced_prob));
+            }
+        }
+
+        let silence = Waveform::from_frames_of_silence(22050, 2, 0);
+        let contour = silence.pitch(PitchArgs::default()).unwrap();
+        assert_eq!(contour.len(), 1);
+        assert!(!contour[0].is_voiced());
+        assert_eq!(contour[0].voiced_prob, 0.0);
+    }
+
+    #[test]
+    fn test_frequency_bounds() {
+        // A 440 hz tone also repeats every two periods, so with a
+        // ceiling of 300 hz it is heard an octave down.
+        let samples = sine(22050, 440.0, 0.5, 22050);
+        let waveform = Waveform::from_interleaved_samples(22050, 1, &samples);
+        let args = PitchArgs::default().set_max_frequency_hz(300.0);
+        let contour = waveform.pitch(args).unwrap();
+        let voiced: Vec<&Pitch> = contour.iter().filter(|p| p.is_voiced()).collect();
+        assert!(!voiced.is_empty());
+        for estimate in voiced {
+            assert!(
+                cents(f64::from(estimate.f0_hz), 220.0) < 5.0,
+                "{:?}",
+                estimate
+            );
+        }
+    }
+
+    #[test]
+    fn test_stereo_is_mixed_down() {
+        let left = sine(22050, 330.0, 0.5, 22050);
+        let interleaved: Vec<f32> = left.iter().flat_map(|s| [*s, 0.5 * s]).collect();
+        let stereo = Waveform::from_interleaved_samples(22050, 2, &interleaved);
+        let contour = stereo.pitch(PitchArgs::default()).unwrap();
+        let middle = contour[contour.len() / 2];
+        assert!(cents(f64::from(middle.f0_hz), 330.0) < 5.0, "{:?}", middle);
+    }
+
+    #[test]
+    fn test_noise_is_mostly_unvoiced() {
+        let mut state: u32 = 12345;
+        let samples: Vec<f32> = (0..44100 * 2)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                0.3 * ((state >> 8) as f32 / (1 << 23) as f32 - 1.0)
+            })
+            .collect();
+        let waveform = Waveform::from_interleaved_samples(44100, 1, &samples);
+        for method in [PITCH_METHOD_PYIN, PITCH_METHOD_YIN] {
+            let contour = waveform
+                .pitch(PitchArgs::default().set_method(method))
+                .unwrap();
+            let num_voiced = contour.iter().filter(|p| p.is_voiced()).count();
+            assert!(num_voiced * 10 < contour.len(), "{}", num_voiced);
+            let mean_prob =
+                contour.iter().map(|p| p.voiced_prob).sum::<f32>() / contour.len() as f32;
+            assert!(mean_prob < 0.2, "{}", mean_prob);
+        }
+    }
+
+    #[test]
+    fn test_wrong_args() {
+        let waveform = Waveform::from_frames_of_silence(44100, 1, 44100);
+        assert_eq!(
+            waveform.pitch(PitchArgs::default().set_hop_length(0)),
+            Err(Error::WrongHopLength(0))
+        );
+        assert_eq!(
+            waveform.pitch(PitchArgs::default().set_max_frequency_hz(30000.0)),
+            Err(Error::WrongFilterFrequency(30000.0, 44100))
+        );
+        assert_eq!(
+            waveform.pitch(
+                PitchArgs::default()
+                    .set_min_frequency_hz(500.0)
+                    .set_max_frequency_hz(400.0)
+            ),
+            Err(Error::WrongFilterFrequency(500.0, 44100))
+        );
+        assert_eq!(
+            waveform.pitch(PitchArgs::default().set_min_frequency_hz(30.0)),
+            Err(Error::WrongFrameLength(2048, 2942))
+        );
+        assert!(waveform
+            .pitch(
+                PitchArgs::default()
+                    .set_min_frequency_hz(30.0)
+                    .set_frame_length(4096)
+            )
+            .is_ok());
+        assert_eq!(
+            waveform.pitch(PitchArgs::default().set_method(99)),
+            Err(Error::FeatureNotCompiled("unknown-pitch-method"))
+        );
+    }
+}
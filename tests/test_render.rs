@@ -0,0 +1,288 @@
+mod fixtures;
+mod signals;
+
+mod test_render {
+    use crate::fixtures::*;
+    use crate::signals::{duplicate_channels, sine};
+    use babycat::constants::{COLOR_MAP_GRAYSCALE, COLOR_MAP_MAGMA, MAX_RENDER_SIZE};
+    use babycat::peaks::Peaks;
+    use babycat::render::{render_peaks, render_spectrogram, render_waveform, Image, RenderArgs};
+    use babycat::spectral::StftArgs;
+    use babycat::{Error, Waveform, WaveformArgs, WaveformSource};
+
+    fn grayscale(width: u32, height: u32) -> RenderArgs {
+        RenderArgs::default()
+            .set_width(width)
+            .set_height(height)
+            .set_color_map(COLOR_MAP_GRAYSCALE)
+    }
+
+    /// Returns the rows of column `x` that are not the black background
+    /// of the grayscale color map.
+    fn drawn_rows(image: &Image, x: u32) -> Vec<u32> {
+        (0..image.height())
+            .filter(|y| image.pixel(x, *y) != Some([0, 0, 0]))
+            .collect()
+    }
+
+    #[test]
+    fn test_waveform_image_size() {
+        let waveform = Waveform::from_file(LCT_FILENAME, WaveformArgs::default()).unwrap();
+        for (width, height) in [(1, 1), (800, 200), (1024, 256), (3000, 7)] {
+            let image = render_waveform(&waveform, grayscale(width, height)).unwrap();
+            assert_eq!(image.width(), width);
+            assert_eq!(image.height(), height);
+            assert_eq!(image.as_slice().len(), 3 * width as usize * height as usize);
+        }
+    }
+
+    #[test]
+    fn test_waveform_left_channel_tone() {
+        let waveform = Waveform::from_file(LCT_FILENAME, WaveformArgs::default()).unwrap();
+        let image = render_waveform(&waveform, grayscale(400, 201)).unwrap();
+        // The left channel fills most of the top half, and the silent
+        // right channel is a line through the middle of the bottom half.
+        let (left_rows, right_rows): (Vec<u32>, Vec<u32>) =
+            drawn_rows(&image, 200).iter().partition(|y| **y < 100);
+        assert!(left_rows.len() > 50);
+        assert_eq!(right_rows, [150]);
+    }
+
+    #[test]
+    fn test_waveform_envelope_and_rms() {
+        // A full-scale sine wave touches the top and bottom of its lane,
+        // and its RMS level covers about 71% of the lane.
+        let waveform =
+            Waveform::from_interleaved_samples(44100, 1, &sine(44100, 100.0, 1.0, 44100));
+        let image = render_waveform(&waveform, grayscale(10, 101)).unwrap();
+        let white = [255, 255, 255];
+        for x in 0..10 {
+            assert_ne!(image.pixel(x, 0), Some([0, 0, 0]));
+            assert_ne!(image.pixel(x, 100), Some([0, 0, 0]));
+            let rms_rows: Vec<u32> = (0..101)
+                .filter(|y| image.pixel(x, *y) == Some(white))
+                .collect();
+            assert_eq!(rms_rows.first(), Some(&15));
+            assert_eq!(rms_rows.last(), Some(&85));
+        }
+    }
+
+    #[test]
+    fn test_waveform_is_stretched_when_short() {
+        let waveform = Waveform::from_interleaved_samples(1000, 1, &[1.0, -1.0, 0.0]);
+        let image = render_waveform(&waveform, grayscale(9, 3)).unwrap();
+        for x in 0..3 {
+            assert_eq!(drawn_rows(&image, x), [0]);
+            assert_eq!(drawn_rows(&image, x + 3), [2]);
+            assert_eq!(drawn_rows(&image, x + 6), [1]);
+        }
+    }
+
+    #[test]
+    fn test_spectrogram_tone_row() {
+        // 5512.5 hz is a quarter of the way up to the Nyquist frequency.
+        let samples = duplicate_channels(&sine(44100, 5512.5, 0.5, 44100), 2);
+        let waveform = Waveform::from_interleaved_samples(44100, 2, &samples);
+        let image = render_spectrogram(&waveform, StftArgs::default(), grayscale(50, 200)).unwrap();
+        let brightest_row = |rows: std::ops::Range<u32>| {
+            rows.max_by_key(|y| image.pixel(25, *y).unwrap()[0])
+                .unwrap()
+        };
+        assert_eq!(brightest_row(0..100), 74);
+        assert_eq!(brightest_row(100..200), 174);
+    }
+
+    #[test]
+    fn test_spectrogram_decibel_range() {
+        // A -6 dBFS tone is drawn with the last color of a range that ends
+        // just below it, with the first color of a range that starts just
+        // above it, and with the middle color of a range centered on it.
+        let waveform =
+            Waveform::from_interleaved_samples(44100, 1, &sine(44100, 2756.25, 0.5, 44100));
+        let loudest = |args: RenderArgs| {
+            let image = render_spectrogram(&waveform, StftArgs::default(), args).unwrap();
+            (0..64)
+                .map(|y| image.pixel(50, y).unwrap()[0])
+                .max()
+                .unwrap()
+        };
+        assert_eq!(
+            loudest(grayscale(100, 64).set_min_db(-60.0).set_max_db(-6.5)),
+            255
+        );
+        assert_eq!(
+            loudest(grayscale(100, 64).set_min_db(-5.5).set_max_db(0.0)),
+            0
+        );
+        let middle = loudest(grayscale(100, 64).set_min_db(-12.0).set_max_db(0.0));
+        assert!((120..=135).contains(&middle));
+    }
+
+    #[test]
+    fn test_color_maps() {
+        // Silence is drawn with the first color of the color map.
+        let waveform = Waveform::from_interleaved_samples(44100, 1, &[0.0; 44100]);
+        let viridis = render_spectrogram(
+            &waveform,
+            StftArgs::default(),
+            RenderArgs::default().set_width(64).set_height(64),
+        )
+        .unwrap();
+        let magma = render_spectrogram(
+            &waveform,
+            StftArgs::default(),
+            RenderArgs::default()
+                .set_width(64)
+                .set_height(64)
+                .set_color_map(COLOR_MAP_MAGMA),
+        )
+        .unwrap();
+        assert!(viridis
+            .as_slice()
+            .chunks(3)
+            .all(|pixel| pixel == [68, 1, 84]));
+        assert!(magma.as_slice().chunks(3).all(|pixel| pixel == [0, 0, 4]));
+    }
+
+    #[test]
+    fn test_svg() {
+        let waveform = Waveform::from_interleaved_samples(1000, 1, &[0.5, -0.5]);
+        let svg = render_waveform(&waveform, grayscale(3, 2))
+            .unwrap()
+            .to_svg();
+        // The envelope fills most of the image, so it is the background,
+        // and the remaining black pixels are drawn in runs along the rows.
+        assert_eq!(
+            svg,
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="3" height="2" "#,
+                r#"viewBox="0 0 3 2" shape-rendering="crispEdges">"#,
+                r##"<rect width="3" height="2" fill="#ffffff"/>"##,
+                r##"<rect x="2" y="0" width="1" height="1" fill="#000000"/>"##,
+                r##"<rect x="0" y="1" width="2" height="1" fill="#000000"/>"##,
+                "</svg>\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_svg_spectrogram() {
+        let waveform =
+            Waveform::from_interleaved_samples(44100, 1, &sine(44100, 440.0, 0.5, 44100));
+        let image = render_spectrogram(&waveform, StftArgs::default(), grayscale(50, 20)).unwrap();
+        let svg = image.to_svg();
+        // Every pixel that is not the background color is covered by a rectangle.
+        let background = svg
+            .split(r#"fill=""#)
+            .nth(1)
+            .and_then(|rest| rest.get(..7))
+            .unwrap();
+        let num_pixels: u32 = svg
+            .split("<rect x=")
+            .skip(1)
+            .map(|rect| {
+                let size = |name: &str| -> u32 {
+                    let start = rect.find(&format!(" {}=\"", name)).unwrap() + name.len() + 3;
+                    rect[start..].split('"').next().unwrap().parse().unwrap()
+                };
+                size("width") * size("height")
+            })
+            .sum();
+        let num_background_pixels = (0..image.width())
+            .flat_map(|x| (0..image.height()).map(move |y| (x, y)))
+            .filter(|(x, y)| {
+                let [r, g, b] = image.pixel(*x, *y).unwrap();
+                format!("#{:02x}{:02x}{:02x}", r, g, b) == background
+            })
+            .count() as u32;
+        assert!(num_pixels > 0);
+        assert_eq!(num_pixels + num_background_pixels, 50 * 20);
+    }
+
+    #[test]
+    fn test_render_peaks() {
+        let samples = sine(1000, 3.0, 0.8, 3000);
+        let waveform = Waveform::from_interleaved_samples(1000, 1, &samples);
+        let source = WaveformSource::from_interleaved_samples(1000, 1, &samples);
+        // One bucket for every column draws the same image as the waveform.
+        let peaks = Peaks::from_source(source, 30).unwrap();
+        assert_eq!(
+            render_peaks(&peaks, grayscale(100, 40)),
+            render_waveform(&waveform, grayscale(100, 40))
+        );
+        // Smaller buckets are combined to fit every column.
+        let peaks = Peaks::from_waveform(&waveform, 3).unwrap();
+        assert_eq!(
+            render_peaks(&peaks, grayscale(100, 40)),
+            render_waveform(&waveform, grayscale(100, 40))
+        );
+        let empty =
+            Peaks::from_waveform(&Waveform::from_interleaved_samples(1000, 1, &[]), 3).unwrap();
+        let image = render_peaks(&empty, grayscale(10, 10)).unwrap();
+        assert!(image.as_slice().iter().all(|component| *component == 0));
+    }
+
+    #[cfg(feature = "enable-png")]
+    #[test]
+    fn test_png() {
+        let waveform = Waveform::from_file(LCT_FILENAME, WaveformArgs::default()).unwrap();
+        let png = render_waveform(&waveform, grayscale(100, 50))
+            .unwrap()
+            .to_png()
+            .unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+
+    #[cfg(not(feature = "enable-png"))]
+    #[test]
+    fn test_png_without_feature() {
+        let waveform = Waveform::from_interleaved_samples(1000, 1, &[0.5]);
+        let image = render_waveform(&waveform, grayscale(1, 1)).unwrap();
+        assert_eq!(image.to_png(), Err(Error::FeatureNotCompiled("enable-png")));
+    }
+
+    #[test]
+    fn test_empty_waveform() {
+        let waveform = Waveform::from_interleaved_samples(44100, 2, &[]);
+        for image in [
+            render_waveform(&waveform, grayscale(10, 10)).unwrap(),
+            render_spectrogram(&waveform, StftArgs::default(), grayscale(10, 10)).unwrap(),
+        ] {
+            assert!(image.as_slice().iter().all(|component| *component == 0));
+        }
+    }
+
+    #[test]
+    fn test_wrong_args() {
+        let waveform = Waveform::from_interleaved_samples(1000, 1, &[0.5, -0.5]);
+        assert_eq!(
+            render_waveform(&waveform, grayscale(0, 10)),
+            Err(Error::WrongImageSize(0, 10))
+        );
+        assert_eq!(
+            render_spectrogram(&waveform, StftArgs::default(), grayscale(10, 0)),
+            Err(Error::WrongImageSize(10, 0))
+        );
+        assert_eq!(
+            render_spectrogram(
+                &waveform,
+                StftArgs::default(),
+                grayscale(10, 10).set_min_db(0.0).set_max_db(-10.0)
+            ),
+            Err(Error::WrongDecibelRange(0.0, -10.0))
+        );
+        assert_eq!(
+            render_waveform(&waveform, grayscale(MAX_RENDER_SIZE + 1, 10)),
+            Err(Error::WrongImageSize(MAX_RENDER_SIZE + 1, 10))
+        );
+        assert_eq!(
+            render_spectrogram(&waveform, StftArgs::default(), grayscale(10, u32::MAX)),
+            Err(Error::WrongImageSize(10, u32::MAX))
+        );
+        assert!(render_waveform(&waveform, grayscale(MAX_RENDER_SIZE, 1)).is_ok());
+        assert_eq!(
+            render_waveform(&waveform, grayscale(10, 10).set_color_map(99)),
+            Err(Error::FeatureNotCompiled("unknown-color-map"))
+        );
+    }
+}
@@ -0,0 +1,154 @@
+mod fixtures;
+
+mod test_silence {
+    use crate::fixtures::*;
+    use babycat::silence::{non_silent_intervals, Interval};
+    use babycat::{Signal, Source, Waveform, WaveformArgs, WaveformSource};
+
+    fn interval(start_frame_idx: usize, end_frame_idx: usize) -> Interval {
+        Interval {
+            start_frame_idx,
+            end_frame_idx,
+        }
+    }
+
+    /// Returns 10 ms of silence, 20 ms of tone, 5 ms of silence,
+    /// 20 ms of tone, 30 ms of silence, 10 ms of tone, and 10 ms of silence.
+    fn bursts() -> Waveform {
+        let mut samples = vec![0.0_f32; 10];
+        samples.extend(vec![0.5_f32; 20]);
+        samples.extend(vec![0.0_f32; 5]);
+        samples.extend(vec![-0.5_f32; 20]);
+        samples.extend(vec![0.0_f32; 30]);
+        samples.extend(vec![0.25_f32; 10]);
+        samples.extend(vec![0.0_f32; 10]);
+        Waveform::from_interleaved_samples(1000, 1, &samples)
+    }
+
+    #[test]
+    fn test_min_silence_milliseconds() {
+        let waveform = bursts();
+        assert_eq!(
+            non_silent_intervals(&waveform, -60.0, 0),
+            &[interval(10, 30), interval(35, 55), interval(85, 95)]
+        );
+        assert_eq!(
+            non_silent_intervals(&waveform, -60.0, 10),
+            &[interval(10, 55), interval(85, 95)]
+        );
+        assert_eq!(
+            non_silent_intervals(&waveform, -60.0, 31),
+            &[interval(10, 95)]
+        );
+    }
+
+    #[test]
+    fn test_interval_milliseconds() {
+        let intervals = non_silent_intervals(&bursts(), -60.0, 10);
+        assert_eq!(intervals[1].num_frames(), 10);
+        assert_eq!(intervals[1].start_milliseconds(1000), 85);
+        assert_eq!(intervals[1].end_milliseconds(1000), 95);
+    }
+
+    #[test]
+    fn test_threshold_dbfs() {
+        // -12 dBFS is 0.251, so the quietest burst counts as silence.
+        let intervals = non_silent_intervals(&bursts(), -12.0, 0);
+        assert_eq!(intervals, &[interval(10, 30), interval(35, 55)]);
+    }
+
+    #[test]
+    fn test_all_silent() {
+        let waveform = Waveform::from_frames_of_silence(1000, 2, 100);
+        assert!(non_silent_intervals(&waveform, -60.0, 0).is_empty());
+        let trimmed = waveform.trim_silence(-60.0);
+        assert_eq!(trimmed.num_frames(), 0);
+        assert_eq!(trimmed.num_channels(), 2);
+        let samples = WaveformSource::new(waveform.clone())
+            .trim_silence(-60.0)
+            .collect_interleaved_samples();
+        assert!(samples.is_empty());
+    }
+
+    #[test]
+    fn test_stereo_needs_every_channel_silent() {
+        let samples = [
+            0.0, 0.0, //
+            0.0, 0.5, //
+            0.0, 0.0, //
+            0.5, 0.0, //
+            0.0, 0.0, //
+        ];
+        let waveform = Waveform::from_interleaved_samples(1000, 2, &samples);
+        assert_eq!(
+            non_silent_intervals(&waveform, -60.0, 0),
+            &[interval(1, 2), interval(3, 4)]
+        );
+        let trimmed = waveform.trim_silence(-60.0);
+        assert_eq!(
+            trimmed.to_interleaved_samples(),
+            &[0.0, 0.5, 0.0, 0.0, 0.5, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_source_matches_waveform() {
+        let waveform = bursts();
+        let from_source = WaveformSource::new(waveform.clone())
+            .trim_silence(-60.0)
+            .collect_interleaved_samples();
+        let from_waveform = waveform.trim_silence(-60.0);
+        assert_eq!(from_source.len(), 85);
+        assert_eq!(from_source, from_waveform.to_interleaved_samples());
+    }
+
+    #[test]
+    fn test_waveform_args_trim_silence() {
+        let waveform = bursts();
+        let args = WaveformArgs {
+            trim_silence: true,
+            ..Default::default()
+        };
+        let trimmed =
+            Waveform::from_source(args, Box::new(WaveformSource::new(waveform.clone()))).unwrap();
+        assert_eq!(trimmed.num_frames(), 85);
+        assert_eq!(trimmed.to_interleaved_samples()[0], 0.5);
+        assert_eq!(trimmed.to_interleaved_samples()[84], 0.25);
+    }
+
+    #[test]
+    fn test_waveform_args_trim_silence_then_zero_pad() {
+        let waveform = bursts();
+        let args = WaveformArgs {
+            trim_silence: true,
+            end_time_milliseconds: 100,
+            zero_pad_ending: true,
+            ..Default::default()
+        };
+        let trimmed =
+            Waveform::from_source(args, Box::new(WaveformSource::new(waveform.clone()))).unwrap();
+        assert_eq!(trimmed.num_frames(), 100);
+        assert_eq!(trimmed.to_interleaved_samples()[0], 0.5);
+        assert!(trimmed.to_interleaved_samples()[85..]
+            .iter()
+            .all(|&sample| sample == 0.0));
+    }
+
+    #[test]
+    fn test_dtmf_file() {
+        let waveform = Waveform::from_file(MONO_DTMF_FILENAME, Default::default()).unwrap();
+        assert_eq!(waveform.num_frames(), MONO_DTMF_NUM_FRAMES);
+        let intervals = waveform.non_silent_intervals(-50.0, 20);
+        assert!(!intervals.is_empty());
+        for pair in intervals.windows(2) {
+            assert!(pair[0].end_frame_idx < pair[1].start_frame_idx);
+        }
+        let trimmed = waveform.trim_silence(-50.0);
+        let first = intervals.first().unwrap();
+        let last = intervals.last().unwrap();
+        assert_eq!(
+            trimmed.num_frames(),
+            last.end_frame_idx - first.start_frame_idx
+        );
+    }
+}
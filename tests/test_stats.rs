@@ -0,0 +1,171 @@
+mod fixtures;
+mod signals;
+
+mod test_stats {
+    use crate::fixtures::*;
+    use crate::signals::sine;
+    use babycat::decoder;
+    use babycat::stats::{StatsMeter, StatsReport};
+    use babycat::{Error, Waveform, WaveformArgs};
+
+    const SILENCE_THRESHOLD_DBFS: f32 = -60.0;
+
+    fn stereo(left: &[f32], right: &[f32]) -> Waveform {
+        let samples: Vec<f32> = left
+            .iter()
+            .zip(right)
+            .flat_map(|(left, right)| vec![*left, *right])
+            .collect();
+        Waveform::from_interleaved_samples(44100, 2, &samples)
+    }
+
+    fn measure(waveform: &Waveform) -> StatsReport {
+        StatsReport::from_waveform(waveform, SILENCE_THRESHOLD_DBFS).unwrap()
+    }
+
+    #[test]
+    fn test_left_channel_tone() {
+        let source = decoder::from_file(LCT_FILENAME).unwrap();
+        let report = StatsReport::from_source(source, SILENCE_THRESHOLD_DBFS).unwrap();
+        assert_eq!(report.frame_rate_hz, LCT_FRAME_RATE_HZ);
+        assert_eq!(report.num_channels, LCT_NUM_CHANNELS);
+        assert_eq!(report.num_frames, LCT_NUM_FRAMES);
+        assert_eq!(report.silent_channels, vec![1]);
+        assert!(report.left_only);
+        assert!(!report.right_only);
+        assert!(report.identical_channels.is_empty());
+        assert!(report.inverted_channels.is_empty());
+        assert!(report.channels[0].rms_dbfs > -10.0);
+        assert_eq!(report.channels[1].silence_percent, 100.0);
+    }
+
+    #[test]
+    fn test_from_source_matches_from_waveform() {
+        for filename in [LCT_FILENAME, MONO_DTMF_FILENAME, LS_FILENAME] {
+            let waveform = Waveform::from_file(filename, WaveformArgs::default()).unwrap();
+            let source = decoder::from_file(filename).unwrap();
+            assert_eq!(
+                StatsReport::from_source(source, SILENCE_THRESHOLD_DBFS).unwrap(),
+                measure(&waveform)
+            );
+        }
+    }
+
+    #[test]
+    fn test_levels_of_a_sine() {
+        let samples = sine(44100, 440.0, 0.5, 44100);
+        let report = measure(&Waveform::from_interleaved_samples(44100, 1, &samples));
+        let channel = &report.channels[0];
+        assert!((channel.peak_dbfs + 6.02).abs() < 0.01);
+        assert!((channel.rms_dbfs + 9.03).abs() < 0.01);
+        assert!((channel.crest_factor_db.unwrap() - 3.01).abs() < 0.01);
+        assert!(channel.dc_offset.abs() < 1e-4);
+        assert_eq!(channel.num_clipped_samples, 0);
+        assert!(channel.silence_percent < 1.0);
+        assert!(report.silent_channels.is_empty());
+        assert!(!report.left_only && !report.right_only);
+    }
+
+    #[test]
+    fn test_dc_offset_and_clipping() {
+        let samples: Vec<f32> = sine(44100, 440.0, 1.5, 44100)
+            .iter()
+            .map(|s| (s + 0.25).clamp(-1.0, 1.0))
+            .collect();
+        let report = measure(&Waveform::from_interleaved_samples(44100, 1, &samples));
+        let channel = &report.channels[0];
+        assert_eq!(channel.peak_dbfs, 0.0);
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        assert!(mean > 0.1);
+        assert!((channel.dc_offset - mean).abs() < 1e-4);
+        let expected_clipped = samples.iter().filter(|s| s.abs() >= 1.0).count();
+        assert!(expected_clipped > 0);
+        assert_eq!(channel.num_clipped_samples, expected_clipped);
+    }
+
+    #[test]
+    fn test_silence_percent() {
+        let mut samples = sine(44100, 440.0, 0.5, 44100);
+        for sample in &mut samples[..11025] {
+            *sample = 0.0;
+        }
+        let report = measure(&Waveform::from_interleaved_samples(44100, 1, &samples));
+        assert!((report.channels[0].silence_percent - 25.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_fake_stereo() {
+        let left = sine(44100, 440.0, 0.5, 44100);
+        let report = measure(&stereo(&left, &left));
+        assert_eq!(report.identical_channels, vec![[0, 1]]);
+        assert!(report.inverted_channels.is_empty());
+        assert_eq!(report.channels[0], report.channels[1]);
+    }
+
+    #[test]
+    fn test_inverted_polarity() {
+        let left = sine(44100, 440.0, 0.5, 44100);
+        let right: Vec<f32> = left.iter().map(|s| -s).collect();
+        let report = measure(&stereo(&left, &right));
+        assert!(report.identical_channels.is_empty());
+        assert_eq!(report.inverted_channels, vec![[0, 1]]);
+    }
+
+    #[test]
+    fn test_different_channels_are_not_flagged() {
+        let left = sine(44100, 440.0, 0.5, 44100);
+        let right: Vec<f32> = left.iter().rev().copied().collect();
+        let quieter: Vec<f32> = left.iter().map(|s| 0.5 * s).collect();
+        for right in [right, quieter] {
+            let report = measure(&stereo(&left, &right));
+            assert!(report.identical_channels.is_empty());
+            assert!(report.inverted_channels.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_right_only_and_silent_channels() {
+        let silence = vec![0.0; 44100];
+        let report = measure(&stereo(&silence, &sine(44100, 440.0, 0.5, 44100)));
+        assert!(report.right_only);
+        assert!(!report.left_only);
+        assert_eq!(report.silent_channels, vec![0]);
+        assert_eq!(report.channels[0].peak_dbfs, -144.0);
+        assert_eq!(report.channels[0].crest_factor_db, None);
+
+        let report = measure(&stereo(&silence, &silence));
+        assert!(!report.left_only && !report.right_only);
+        assert_eq!(report.silent_channels, vec![0, 1]);
+        assert!(report.identical_channels.is_empty());
+    }
+
+    #[test]
+    fn test_many_channels() {
+        let samples = sine(44100, 440.0, 0.5, 4410);
+        let mut interleaved = Vec::with_capacity(samples.len() * 4);
+        for sample in &samples {
+            interleaved.extend_from_slice(&[*sample, 0.5 * sample, -sample, *sample]);
+        }
+        let report = measure(&Waveform::from_interleaved_samples(44100, 4, &interleaved));
+        assert_eq!(report.identical_channels, vec![[0, 3]]);
+        assert_eq!(report.inverted_channels, vec![[0, 2], [2, 3]]);
+        assert!(!report.left_only && !report.right_only);
+    }
+
+    #[test]
+    fn test_empty_waveform() {
+        let report = measure(&Waveform::from_interleaved_samples(44100, 2, &[]));
+        assert_eq!(report.num_frames, 0);
+        assert_eq!(report.channels[0].rms_dbfs, -144.0);
+        assert_eq!(report.silent_channels, vec![0, 1]);
+        assert!(serde_json::to_string(&report).is_ok());
+    }
+
+    #[test]
+    fn test_zero_channels() {
+        assert_eq!(
+            StatsMeter::new(44100, 0, SILENCE_THRESHOLD_DBFS).err(),
+            Some(Error::WrongNumChannels(1, 0))
+        );
+    }
+}
@@ -0,0 +1,221 @@
+mod fixtures;
+mod signals;
+
+mod test_stft {
+    use crate::signals::{assert_all_close, sine};
+    use babycat::constants::{
+        PAD_MODE_REFLECT, PAD_MODE_ZEROS, WINDOW_TYPE_BLACKMAN, WINDOW_TYPE_HAMMING,
+        WINDOW_TYPE_HANN, WINDOW_TYPE_RECTANGULAR,
+    };
+    use babycat::spectral::{Complex, Stft, StftArgs};
+    use babycat::{Error, Signal, Waveform};
+
+    #[test]
+    fn test_num_time_steps() {
+        let waveform = Waveform::from_frames_of_silence(44100, 2, 44100);
+        let stft = waveform.stft(StftArgs::default()).unwrap();
+        assert_eq!(stft.num_channels(), 2);
+        assert_eq!(stft.num_frames(), 44100);
+        assert_eq!(stft.num_bins(), 1025);
+        assert_eq!(stft.num_time_steps(), 87);
+        assert_eq!(stft.as_slice().len(), 2 * 1025 * 87);
+
+        let args = StftArgs::default().set_center(false);
+        let stft = waveform.stft(args).unwrap();
+        assert_eq!(stft.num_time_steps(), 1 + (44100 - 2048) / 512);
+
+        // Too short for a single window without centering.
+        let short = Waveform::from_frames_of_silence(44100, 1, 100);
+        assert_eq!(short.stft(args).unwrap().num_time_steps(), 0);
+        assert_eq!(
+            short
+                .stft(args)
+                .unwrap()
+                .to_waveform()
+                .unwrap()
+                .num_frames(),
+            100
+        );
+    }
+
+    #[test]
+    fn test_min_num_frames() {
+        for &center in &[true, false] {
+            let args = StftArgs::default()
+                .set_fft_size(400)
+                .set_hop_length(160)
+                .set_center(center);
+            for num_time_steps in 1..20 {
+                let num_frames = args.min_num_frames(num_time_steps);
+                assert_eq!(args.num_time_steps(num_frames), num_time_steps);
+                if num_frames > 0 {
+                    assert_eq!(args.num_time_steps(num_frames - 1), num_time_steps - 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sine_peak_bin() {
+        let samples = sine(16000, 1000.0, 0.5, 16000);
+        let waveform = Waveform::from_interleaved_samples(16000, 1, &samples);
+        let args = StftArgs::default().set_fft_size(512).set_hop_length(128);
+        let stft = waveform.stft(args).unwrap();
+        // Bins are 31.25 hz wide, so 1000 hz is bin 32.
+        assert_eq!(stft.bin_frequency_hz(32), 1000.0);
+        let powers = stft.powers();
+        let magnitudes = stft.magnitudes();
+        let t = stft.num_time_steps() / 2;
+        let column = |values: &[f32], b: usize| values[b * stft.num_time_steps() + t];
+        let loudest = (0..stft.num_bins())
+            .max_by(|&a, &b| column(&powers, a).total_cmp(&column(&powers, b)))
+            .unwrap();
+        assert_eq!(loudest, 32);
+        let value = stft.get(0, 32, t).unwrap();
+        assert!((column(&magnitudes, 32) - value.norm()).abs() < 1e-3);
+        assert!((column(&powers, 32) - value.norm_sqr()).abs() < 1e-1);
+        // A Hann window sums to fft_size / 2, and a sine of amplitude
+        // 0.5 puts a quarter of that in its positive-frequency bin.
+        assert!((value.norm() - 64.0).abs() < 1.0);
+        assert!(stft.get(1, 0, 0).is_none());
+        assert!(stft.get(0, stft.num_bins(), 0).is_none());
+        assert!(stft.get(0, 0, stft.num_time_steps()).is_none());
+    }
+
+    #[test]
+    fn test_round_trip_every_window() {
+        let samples: Vec<f32> = sine(8000, 440.0, 0.5, 4000)
+            .into_iter()
+            .zip(sine(8000, 1230.0, 0.5, 4000))
+            .flat_map(|(a, b)| vec![a, b])
+            .collect();
+        let waveform = Waveform::from_interleaved_samples(8000, 2, &samples);
+        for &(window_type, hop_length) in &[
+            (WINDOW_TYPE_HANN, 64),
+            (WINDOW_TYPE_HAMMING, 128),
+            (WINDOW_TYPE_BLACKMAN, 64),
+            (WINDOW_TYPE_RECTANGULAR, 128),
+        ] {
+            for &pad_mode in &[PAD_MODE_ZEROS, PAD_MODE_REFLECT] {
+                let args = StftArgs::default()
+                    .set_fft_size(256)
+                    .set_hop_length(hop_length)
+                    .set_window_type(window_type)
+                    .set_pad_mode(pad_mode);
+                let inverse = waveform.stft(args).unwrap().to_waveform().unwrap();
+                assert_eq!(inverse.num_channels(), 2);
+                assert_eq!(inverse.frame_rate_hz(), 8000);
+                assert_all_close(inverse.to_interleaved_samples(), &samples, 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip_without_centering() {
+        let samples = sine(8000, 440.0, 0.5, 1000);
+        let waveform = Waveform::from_interleaved_samples(8000, 1, &samples);
+        let args = StftArgs::default()
+            .set_fft_size(256)
+            .set_hop_length(64)
+            .set_window_length(200)
+            .set_center(false);
+        let inverse = waveform.stft(args).unwrap().to_waveform().unwrap();
+        assert_eq!(inverse.num_frames(), 1000);
+        let inverse = inverse.to_interleaved_samples();
+        // The window is zero-padded by 28 frames on each side, and a Hann
+        // window starts at zero. The last full window starts at frame 704,
+        // and the frames after it are not covered at all.
+        let covered = 29..704 + 28 + 200;
+        assert_all_close(&inverse[covered.clone()], &samples[covered.clone()], 1e-4);
+        assert!(inverse[..covered.start].iter().all(|&s| s == 0.0));
+        assert!(inverse[covered.end..].iter().all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn test_pad_modes() {
+        let waveform = Waveform::from_interleaved_samples(1000, 1, &[0.5; 64]);
+        let mut args = StftArgs::default()
+            .set_fft_size(16)
+            .set_hop_length(4)
+            .set_window_type(WINDOW_TYPE_RECTANGULAR);
+        // Reflecting a constant signal keeps it constant.
+        let reflect = waveform.stft(args.set_pad_mode(PAD_MODE_REFLECT)).unwrap();
+        assert!((reflect.get(0, 0, 0).unwrap().re - 8.0).abs() < 1e-5);
+        // Padding with zeros halves the first window.
+        let zeros = waveform.stft(args.set_pad_mode(PAD_MODE_ZEROS)).unwrap();
+        assert!((zeros.get(0, 0, 0).unwrap().re - 4.0).abs() < 1e-5);
+        // Windows that do not touch the padding are the same.
+        assert_eq!(reflect.get(0, 0, 5), zeros.get(0, 0, 5));
+    }
+
+    #[test]
+    fn test_edit_spectrum() {
+        let samples: Vec<f32> = sine(8000, 200.0, 0.5, 2000)
+            .into_iter()
+            .zip(sine(8000, 3000.0, 0.5, 2000))
+            .map(|(a, b)| a + b)
+            .collect();
+        let waveform = Waveform::from_interleaved_samples(8000, 1, &samples);
+        let args = StftArgs::default().set_fft_size(512).set_hop_length(128);
+        let mut stft = waveform.stft(args).unwrap();
+        // Zero every bin above 1000 hz.
+        let num_time_steps = stft.num_time_steps();
+        let cutoff_bin = 64;
+        for value in &mut stft.as_mut_slice()[cutoff_bin * num_time_steps..] {
+            *value = Complex::new(0.0, 0.0);
+        }
+        let filtered = stft.to_waveform().unwrap();
+        assert_all_close(
+            &filtered.to_interleaved_samples()[256..1744],
+            &sine(8000, 200.0, 0.5, 2000)[256..1744],
+            1e-2,
+        );
+    }
+
+    #[test]
+    fn test_new_checks_shape() {
+        let args = StftArgs::default().set_fft_size(8).set_hop_length(4);
+        let num_time_steps = args.num_time_steps(16);
+        assert_eq!(num_time_steps, 5);
+        let data = vec![Complex::new(0.0, 0.0); 2 * 5 * num_time_steps];
+        let stft = Stft::new(args, 1000, 2, 16, data).unwrap();
+        assert_eq!(stft.to_waveform().unwrap().num_frames(), 16);
+        let data = vec![Complex::new(0.0, 0.0); 7];
+        assert_eq!(
+            Stft::new(args, 1000, 2, 16, data),
+            Err(Error::WrongStftShape(50, 7))
+        );
+    }
+
+    #[test]
+    fn test_wrong_args() {
+        let waveform = Waveform::from_frames_of_silence(1000, 1, 100);
+        let stft = |args: StftArgs| waveform.stft(args).map(|_| ());
+        assert_eq!(
+            stft(StftArgs::default().set_fft_size(0)),
+            Err(Error::WrongFftSize(0, 0))
+        );
+        assert_eq!(
+            stft(StftArgs::default().set_fft_size(64).set_window_length(65)),
+            Err(Error::WrongFftSize(64, 65))
+        );
+        assert_eq!(
+            stft(StftArgs::default().set_hop_length(0)),
+            Err(Error::WrongHopLength(0))
+        );
+        assert_eq!(
+            stft(StftArgs::default().set_window_type(1000)),
+            Err(Error::FeatureNotCompiled("unknown-window-type"))
+        );
+        assert_eq!(
+            stft(StftArgs::default().set_pad_mode(1000)),
+            Err(Error::FeatureNotCompiled("unknown-pad-mode"))
+        );
+    }
+
+    #[test]
+    fn test_args_from_json() {
+        let args: StftArgs = serde_json::from_str(r#"{"fft_size": 1024}"#).unwrap();
+        assert_eq!(args, StftArgs::default().set_fft_size(1024));
+    }
+}
@@ -0,0 +1,209 @@
+mod fixtures;
+mod signals;
+
+mod test_vad {
+    use crate::fixtures::*;
+    use crate::signals::noise;
+    use babycat::vad::{speech_segments, SpeechSegment, VadArgs};
+    use babycat::{Signal, Waveform, WaveformArgs, WaveformSource};
+
+    /// Returns a steady harmonic hum with a 150 hz fundamental.
+    fn hum(frame_rate_hz: u32, num_frames: usize, amplitude: f32) -> Vec<f32> {
+        (0..num_frames)
+            .map(|i| {
+                let t = i as f32 / frame_rate_hz as f32;
+                (1..=4)
+                    .map(|h| {
+                        amplitude / h as f32
+                            * (2.0 * std::f32::consts::PI * 150.0 * h as f32 * t).sin()
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Returns the hum, rising and falling four times a second the way
+    /// syllables do, which looks like voiced speech.
+    fn voice(frame_rate_hz: u32, num_frames: usize, amplitude: f32) -> Vec<f32> {
+        hum(frame_rate_hz, num_frames, amplitude)
+            .iter()
+            .enumerate()
+            .map(|(i, sample)| {
+                let t = i as f32 / frame_rate_hz as f32;
+                sample * (0.6 - 0.4 * (2.0 * std::f32::consts::PI * 4.0 * t).cos())
+            })
+            .collect()
+    }
+
+    /// Places `bursts` of voice, given as `(start_ms, end_ms)`, into `total_ms` of `floor`.
+    fn bursts(
+        frame_rate_hz: u32,
+        total_ms: usize,
+        bursts: &[(usize, usize)],
+        floor: &[f32],
+    ) -> Vec<f32> {
+        let per_ms = frame_rate_hz as usize / 1000;
+        let mut samples = floor[..total_ms * per_ms].to_vec();
+        let tone = voice(frame_rate_hz, total_ms * per_ms, 0.3);
+        for &(start_ms, end_ms) in bursts {
+            for i in start_ms * per_ms..end_ms * per_ms {
+                samples[i] += tone[i];
+            }
+        }
+        samples
+    }
+
+    fn ms(segments: &[SpeechSegment]) -> Vec<(usize, usize)> {
+        segments
+            .iter()
+            .map(|s| (s.start_milliseconds, s.end_milliseconds))
+            .collect()
+    }
+
+    #[test]
+    fn test_silence_has_no_speech() {
+        let source = WaveformSource::from_interleaved_samples(16000, 1, &vec![0.0; 32000]);
+        let segments = speech_segments(source, VadArgs::default()).unwrap();
+        assert!(segments.is_empty());
+    }
+
+    #[test]
+    fn test_loud_noise_is_not_speech() {
+        // Noise that is loud all the way through sets its own noise floor,
+        // and it is neither tonal nor slow to cross zero.
+        let samples = noise(32000, 0.5, 12345);
+        let source = WaveformSource::from_interleaved_samples(16000, 1, &samples);
+        let args = VadArgs::default().set_energy_margin_db(0.0);
+        let segments = speech_segments(source, args).unwrap();
+        assert!(segments.is_empty(), "{:?}", segments);
+    }
+
+    #[test]
+    fn test_steady_hum_and_tones_are_not_speech() {
+        // A steady hum is as tonal as voiced speech, but it never rises and falls.
+        let floor = vec![0.0; 16 * 3000];
+        let mut samples = floor.clone();
+        samples[16 * 500..16 * 2500].copy_from_slice(&hum(16000, 16 * 2000, 0.3));
+        let source = WaveformSource::from_interleaved_samples(16000, 1, &samples);
+        let segments = speech_segments(source, VadArgs::default()).unwrap();
+        assert!(segments.is_empty(), "{:?}", segments);
+
+        // Neither does a pure tone.
+        let tone: Vec<f32> = (0..16 * 3000)
+            .map(|i| 0.5 * (2.0 * std::f32::consts::PI * 200.0 * i as f32 / 16000.0).sin())
+            .collect();
+        let source = WaveformSource::from_interleaved_samples(16000, 1, &tone);
+        let segments = speech_segments(source, VadArgs::default()).unwrap();
+        assert!(segments.is_empty(), "{:?}", segments);
+
+        // Rising and falling is what makes the same hum look like speech.
+        let samples = bursts(16000, 3000, &[(500, 2500)], &floor);
+        let source = WaveformSource::from_interleaved_samples(16000, 1, &samples);
+        let args = VadArgs::default().set_hangover_milliseconds(0);
+        let segments = speech_segments(source, args).unwrap();
+        assert_eq!(ms(&segments), &[(500, 2500)]);
+    }
+
+    #[test]
+    fn test_dtmf_tones_are_not_speech() {
+        let waveform = Waveform::from_file(MONO_DTMF_FILENAME, WaveformArgs::default()).unwrap();
+        let segments = waveform.speech_segments(VadArgs::default()).unwrap();
+        assert!(segments.is_empty(), "{:?}", segments);
+    }
+
+    #[test]
+    fn test_hum_in_noise() {
+        let floor = noise(16 * 3000, 0.01, 12345);
+        let samples = bursts(16000, 3000, &[(500, 1000), (2000, 2500)], &floor);
+        let source = WaveformSource::from_interleaved_samples(16000, 1, &samples);
+        let args = VadArgs::default().set_hangover_milliseconds(0);
+        let segments = speech_segments(source, args).unwrap();
+        assert_eq!(ms(&segments), &[(500, 1000), (2000, 2500)]);
+        assert_eq!(segments[0].start_frame_idx, 8000);
+        assert_eq!(segments[0].end_frame_idx, 16000);
+    }
+
+    #[test]
+    fn test_hangover_bridges_short_gaps() {
+        let floor = vec![0.0; 16 * 2000];
+        let samples = bursts(16000, 2000, &[(200, 600), (700, 1000)], &floor);
+        let args = VadArgs::default().set_hangover_milliseconds(200);
+        let source = WaveformSource::from_interleaved_samples(16000, 1, &samples);
+        let segments = speech_segments(source, args).unwrap();
+        assert_eq!(ms(&segments), &[(200, 1200)]);
+
+        let args = VadArgs::default().set_hangover_milliseconds(60);
+        let source = WaveformSource::from_interleaved_samples(16000, 1, &samples);
+        let segments = speech_segments(source, args).unwrap();
+        assert_eq!(ms(&segments), &[(200, 660), (700, 1060)]);
+    }
+
+    #[test]
+    fn test_min_speech_milliseconds() {
+        let floor = vec![0.0; 16 * 2000];
+        let samples = bursts(16000, 2000, &[(200, 240), (1000, 1400)], &floor);
+        let args = VadArgs::default()
+            .set_hangover_milliseconds(0)
+            .set_min_speech_milliseconds(100);
+        let source = WaveformSource::from_interleaved_samples(16000, 1, &samples);
+        let segments = speech_segments(source, args).unwrap();
+        assert_eq!(ms(&segments), &[(1000, 1400)]);
+    }
+
+    #[test]
+    fn test_any_frame_rate_and_channels() {
+        let args = VadArgs::default().set_hangover_milliseconds(0);
+        let floor = vec![0.0; 48 * 2000];
+        let mono = bursts(48000, 2000, &[(600, 1200)], &floor);
+        let stereo: Vec<f32> = mono.iter().flat_map(|&s| vec![s, 0.0]).collect();
+        let source = WaveformSource::from_interleaved_samples(48000, 2, &stereo);
+        let segments = speech_segments(source, args).unwrap();
+        // Ringing from the resampler can leak into the frames on either side.
+        assert_eq!(segments.len(), 1);
+        assert!((580..=600).contains(&segments[0].start_milliseconds));
+        assert!((1200..=1220).contains(&segments[0].end_milliseconds));
+        assert_eq!(
+            segments[0].start_frame_idx,
+            segments[0].start_milliseconds * 48
+        );
+
+        let waveform = Waveform::from_interleaved_samples(48000, 2, &stereo);
+        assert_eq!(waveform.speech_segments(args).unwrap(), segments);
+    }
+
+    #[test]
+    fn test_extract_speech() {
+        let floor = vec![0.0; 16 * 2000];
+        let samples = bursts(16000, 2000, &[(200, 400), (1000, 1300)], &floor);
+        let waveform = Waveform::from_interleaved_samples(16000, 1, &samples);
+        let args = VadArgs::default().set_hangover_milliseconds(0);
+        let speech = waveform.extract_speech(args).unwrap();
+        assert_eq!(speech.frame_rate_hz(), 16000);
+        assert_eq!(speech.num_frames(), 16 * 500);
+        assert_eq!(
+            &speech.to_interleaved_samples()[..16 * 200],
+            &samples[16 * 200..16 * 400]
+        );
+        assert_eq!(
+            &speech.to_interleaved_samples()[16 * 200..],
+            &samples[16 * 1000..16 * 1300]
+        );
+    }
+
+    #[test]
+    fn test_segments_serialize() {
+        let segment = SpeechSegment {
+            start_frame_idx: 8000,
+            end_frame_idx: 16000,
+            start_milliseconds: 500,
+            end_milliseconds: 1000,
+        };
+        let json = serde_json::to_string(&vec![segment]).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"start_frame_idx":8000,"end_frame_idx":16000,"start_milliseconds":500,"end_milliseconds":1000}]"#
+        );
+        let parsed: Vec<SpeechSegment> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, &[segment]);
+    }
+}
@@ -36,4 +36,22 @@ mod test_waveform_args {
         assert_eq!(args.resample_mode, 1);
         assert_eq!(args.decoding_backend, 1);
     }
+
+    /// Test that WaveformArgs can be compared, including its float fields.
+    #[test]
+    fn test_eq() {
+        fn assert_eq_impl<T: Eq>(_: &T) {}
+        let target = || WaveformArgs::default().set_normalize_target(-23.0);
+        assert_eq_impl(&target());
+        assert_eq!(target(), target());
+        assert_ne!(
+            target(),
+            WaveformArgs::default().set_normalize_target(-24.0)
+        );
+        assert_ne!(
+            target(),
+            target().set_trim_silence_threshold_dbfs(f32::NEG_INFINITY)
+        );
+        assert_ne!(target(), target().set_trim_silence(true));
+    }
 }